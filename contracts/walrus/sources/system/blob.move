@@ -4,7 +4,7 @@
 module walrus::blob;
 
 use std::string::String;
-use sui::{bcs, dynamic_field, hash};
+use sui::{bcs, dynamic_field, event, hash};
 use walrus::{
     encoding,
     events::{emit_blob_registered, emit_blob_certified, emit_blob_deleted},
@@ -57,6 +57,15 @@ public struct Blob has key, store {
     deletable: bool,
 }
 
+/// Signals that the metadata attached to a blob object has changed.
+///
+/// Defined in this module rather than in `events` so that it is not delivered to consumers of the
+/// events of the `events` module, which do not know this event.
+public struct BlobAttributeChanged has copy, drop {
+    blob_id: u256,
+    object_id: ID,
+}
+
 // === Accessors ===
 
 public fun object_id(self: &Blob): ID {
@@ -310,7 +319,8 @@ public(package) fun emit_certified(self: &Blob, is_extension: bool) {
 /// Aborts if the metadata is already present.
 public fun add_metadata(self: &mut Blob, metadata: Metadata) {
     assert!(!dynamic_field::exists_(&self.id, METADATA_DF), EDuplicateMetadata);
-    dynamic_field::add(&mut self.id, METADATA_DF, metadata)
+    dynamic_field::add(&mut self.id, METADATA_DF, metadata);
+    self.emit_attribute_changed();
 }
 
 /// Adds the metadata dynamic field to the Blob, replacing the existing metadata if present.
@@ -331,6 +341,7 @@ public fun add_or_replace_metadata(self: &mut Blob, metadata: Metadata): option:
 /// Aborts if the metadata does not exist.
 public fun take_metadata(self: &mut Blob): Metadata {
     assert!(dynamic_field::exists_(&self.id, METADATA_DF), EMissingMetadata);
+    self.emit_attribute_changed();
     dynamic_field::remove(&mut self.id, METADATA_DF)
 }
 
@@ -357,13 +368,15 @@ fun metadata_or_create(self: &mut Blob): &mut Metadata {
 /// If the key is already present, the value is updated. Creates new metadata on the Blob object if
 /// it does not exist already.
 public fun insert_or_update_metadata_pair(self: &mut Blob, key: String, value: String) {
-    self.metadata_or_create().insert_or_update(key, value)
+    self.metadata_or_create().insert_or_update(key, value);
+    self.emit_attribute_changed();
 }
 
 /// Removes the metadata associated with the given key.
 ///
 /// Aborts if the metadata does not exist.
 public fun remove_metadata_pair(self: &mut Blob, key: &String): (String, String) {
+    self.emit_attribute_changed();
     self.metadata().remove(key)
 }
 
@@ -372,10 +385,16 @@ public fun remove_metadata_pair_if_exists(self: &mut Blob, key: &String): option
     if (!dynamic_field::exists_(&self.id, METADATA_DF)) {
         option::none()
     } else {
+        self.emit_attribute_changed();
         self.metadata().remove_if_exists(key)
     }
 }
 
+/// Emits a `BlobAttributeChanged` event for the given blob.
+fun emit_attribute_changed(self: &Blob) {
+    event::emit(BlobAttributeChanged { blob_id: self.blob_id, object_id: self.id.to_inner() })
+}
+
 #[test_only]
 public fun certify_with_certified_msg_for_testing(
     blob: &mut Blob,
@@ -5,7 +5,7 @@
 module walrus::blob_tests;
 
 use std::unit_test::assert_eq;
-use sui::bcs;
+use sui::{bcs, event};
 use walrus::{
     blob::{Self, Blob},
     encoding,
@@ -511,6 +511,9 @@ fun blob_add_metadata() {
         let (key, value) = blob.remove_metadata_pair(&b"key1".to_string());
         assert_eq!(key, b"key1".to_string());
         assert_eq!(value, b"value3".to_string());
+
+        // Every change of the metadata emits an event.
+        assert_eq!(event::events_by_type<blob::BlobAttributeChanged>().length(), 4);
     })
 }
 
@@ -0,0 +1,369 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The [`WalrusClient`] and its builder.
+
+use std::path::{Path, PathBuf};
+
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::ObjectID;
+use tokio::io::{AsyncRead, AsyncReadExt as _};
+use walrus_core::{
+    encoding::{EncodingConfigTrait as _, Primary},
+    BlobId,
+    EncodingType,
+    EpochCount,
+    DEFAULT_ENCODING,
+};
+use walrus_sdk::api::BlobStatus;
+use walrus_service::client::{
+    cli::{get_contract_client, get_read_client, load_configuration},
+    responses::BlobStoreResult,
+    Client,
+    Config,
+    MetadataReplication,
+    RetryPolicy,
+    StoreWhen,
+};
+use walrus_sui::{
+    client::{BlobPersistence, PostStoreAction, SuiContractClient, SuiReadClient},
+    config::WalletConfig,
+};
+
+use crate::error::{Error, Result};
+
+/// The options for storing a blob.
+#[derive(Debug, Clone, Copy)]
+pub struct StoreOptions {
+    /// The number of epochs ahead for which to store the blob.
+    pub epochs: EpochCount,
+    /// Whether the blob is stored as deletable or permanent.
+    pub persistence: BlobPersistence,
+    /// Whether to store the blob if it is already stored on Walrus.
+    pub store_when: StoreWhen,
+    /// What to do with the Sui blob object after the blob has been stored.
+    pub post_store: PostStoreAction,
+    /// The encoding type to use for the blob.
+    pub encoding_type: EncodingType,
+    /// To which storage nodes the metadata of the blob is pushed.
+    pub metadata_replication: MetadataReplication,
+}
+
+impl Default for StoreOptions {
+    fn default() -> Self {
+        Self {
+            epochs: 1,
+            persistence: BlobPersistence::Permanent,
+            store_when: StoreWhen::NotStored,
+            post_store: PostStoreAction::Keep,
+            encoding_type: DEFAULT_ENCODING,
+            metadata_replication: MetadataReplication::WithSlivers,
+        }
+    }
+}
+
+/// The underlying Walrus client, with or without a wallet.
+#[derive(Debug)]
+enum Inner {
+    ReadOnly(Client<SuiReadClient>),
+    ReadWrite(Client<SuiContractClient>),
+}
+
+/// A high-level client to store and read blobs on Walrus.
+///
+/// Use [`WalrusClient::builder`] to construct a client.
+#[derive(Debug)]
+pub struct WalrusClient {
+    inner: Inner,
+}
+
+impl WalrusClient {
+    /// Returns a builder for a new client.
+    pub fn builder() -> WalrusClientBuilder {
+        WalrusClientBuilder::default()
+    }
+
+    /// Returns `true` if the client was built without a wallet and can therefore only read.
+    pub fn is_read_only(&self) -> bool {
+        matches!(self.inner, Inner::ReadOnly(_))
+    }
+
+    /// Returns the configuration of the client.
+    pub fn config(&self) -> &Config {
+        match &self.inner {
+            Inner::ReadOnly(client) => client.config(),
+            Inner::ReadWrite(client) => client.config(),
+        }
+    }
+
+    /// Stores the blob on Walrus.
+    pub async fn store(&self, blob: &[u8], options: &StoreOptions) -> Result<BlobStoreResult> {
+        let client = self.write_client()?;
+        let results = client
+            .reserve_and_store_blobs_retry_committees(
+                &[blob],
+                options.encoding_type,
+                options.epochs,
+                options.store_when,
+                options.persistence,
+                options.post_store,
+                options.metadata_replication,
+            )
+            .await?;
+        results.into_iter().next().ok_or(Error::EncodingFailed)
+    }
+
+    /// Stores the contents of the file at the given path on Walrus.
+    pub async fn store_path(
+        &self,
+        path: impl AsRef<Path>,
+        options: &StoreOptions,
+    ) -> Result<BlobStoreResult> {
+        let blob = tokio::fs::read(path).await?;
+        self.store(&blob, options).await
+    }
+
+    /// Stores the data read from `reader` until EOF on Walrus.
+    ///
+    /// Blobs are encoded as a whole, so the complete stream is buffered in memory before storing.
+    pub async fn store_stream<R>(
+        &self,
+        mut reader: R,
+        options: &StoreOptions,
+    ) -> Result<BlobStoreResult>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut blob = Vec::new();
+        reader.read_to_end(&mut blob).await?;
+        self.store(&blob, options).await
+    }
+
+    /// Reads the blob with the given ID from Walrus.
+    pub async fn read(&self, blob_id: &BlobId) -> Result<Vec<u8>> {
+        let blob = match &self.inner {
+            Inner::ReadOnly(client) => client.read_blob_retry_committees::<Primary>(blob_id).await,
+            Inner::ReadWrite(client) => client.read_blob_retry_committees::<Primary>(blob_id).await,
+        }?;
+        Ok(blob)
+    }
+
+    /// Computes the blob ID of `blob` for the current committee without storing it.
+    ///
+    /// This can be used to verify that data obtained from another source corresponds to a blob ID.
+    pub fn compute_blob_id(&self, blob: &[u8], encoding_type: EncodingType) -> Result<BlobId> {
+        let encoding_config = match &self.inner {
+            Inner::ReadOnly(client) => client.encoding_config(),
+            Inner::ReadWrite(client) => client.encoding_config(),
+        };
+        let metadata = encoding_config
+            .get_for_type(encoding_type)
+            .compute_metadata(blob)
+            .map_err(|_| Error::BlobTooLarge(blob.len() as u64))?;
+        Ok(*metadata.blob_id())
+    }
+
+    /// Returns the status of the blob with the given ID, as verified with the storage nodes.
+    pub async fn status(&self, blob_id: &BlobId) -> Result<BlobStatus> {
+        let status = match &self.inner {
+            Inner::ReadOnly(client) => {
+                client
+                    .get_blob_status_with_retries(blob_id, client.sui_client())
+                    .await
+            }
+            Inner::ReadWrite(client) => {
+                client
+                    .get_blob_status_with_retries(blob_id, client.sui_client())
+                    .await
+            }
+        }?;
+        Ok(status)
+    }
+
+    /// Estimates the cost, in FROST and excluding gas, of storing a blob of `unencoded_size` bytes
+    /// for `epochs` epochs with the given encoding type.
+    pub async fn estimate_cost(
+        &self,
+        unencoded_size: u64,
+        epochs: EpochCount,
+        encoding_type: EncodingType,
+    ) -> Result<u64> {
+        let (encoding_config, price_computation) = match &self.inner {
+            Inner::ReadOnly(client) => (
+                client.encoding_config(),
+                client.get_price_computation().await?,
+            ),
+            Inner::ReadWrite(client) => (
+                client.encoding_config(),
+                client.get_price_computation().await?,
+            ),
+        };
+        let encoded_length = encoding_config
+            .get_for_type(encoding_type)
+            .encoded_blob_length(unencoded_size)
+            .ok_or(Error::BlobTooLarge(unencoded_size))?;
+        Ok(
+            price_computation.storage_fee_for_encoded_length(encoded_length, epochs)
+                + price_computation.write_fee_for_encoded_length(encoded_length),
+        )
+    }
+
+    /// Extends the lifetime of the owned blob object by `epochs_extended` epochs.
+    pub async fn extend(
+        &self,
+        blob_object_id: ObjectID,
+        epochs_extended: EpochCount,
+    ) -> Result<()> {
+        self.write_client()?
+            .sui_client()
+            .extend_blob(blob_object_id, epochs_extended)
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes all owned deletable blob objects with the given blob ID.
+    ///
+    /// Returns the number of deleted blob objects.
+    pub async fn delete(&self, blob_id: &BlobId) -> Result<usize> {
+        Ok(self.write_client()?.delete_owned_blob(blob_id).await?)
+    }
+
+    fn write_client(&self) -> Result<&Client<SuiContractClient>> {
+        match &self.inner {
+            Inner::ReadOnly(_) => Err(Error::ReadOnly),
+            Inner::ReadWrite(client) => Ok(client),
+        }
+    }
+}
+
+/// A builder for a [`WalrusClient`].
+///
+/// By default, the Walrus configuration is loaded from the default configuration paths, and the
+/// Sui wallet is loaded from the wallet configuration specified therein.
+#[derive(Default)]
+pub struct WalrusClientBuilder {
+    config: Option<Config>,
+    config_path: Option<PathBuf>,
+    context: Option<String>,
+    wallet: Option<WalletContext>,
+    rpc_url: Option<String>,
+    gas_budget: Option<u64>,
+    retry_policy: Option<RetryPolicy>,
+    read_only: bool,
+}
+
+impl std::fmt::Debug for WalrusClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalrusClientBuilder")
+            .field("config", &self.config)
+            .field("config_path", &self.config_path)
+            .field("context", &self.context)
+            .field("wallet", &self.wallet.as_ref().map(|_| "<wallet>"))
+            .field("rpc_url", &self.rpc_url)
+            .field("gas_budget", &self.gas_budget)
+            .field("retry_policy", &self.retry_policy)
+            .field("read_only", &self.read_only)
+            .finish()
+    }
+}
+
+impl WalrusClientBuilder {
+    /// Uses the given configuration instead of loading it from a file.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Loads the configuration from the given path instead of the default paths.
+    pub fn config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// Selects the context to use from a multi-context configuration file.
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Uses the given Sui wallet instead of the one specified in the configuration.
+    pub fn wallet(mut self, wallet: WalletContext) -> Self {
+        self.wallet = Some(wallet);
+        self
+    }
+
+    /// Sets the Sui RPC URL used by a read-only client.
+    ///
+    /// If unset, the RPC URL of the wallet is used, falling back to the default RPC URL if no
+    /// wallet is available.
+    pub fn rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = Some(rpc_url.into());
+        self
+    }
+
+    /// Sets the gas budget for transactions; if unset, the gas budget is estimated.
+    pub fn gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = Some(gas_budget);
+        self
+    }
+
+    /// Sets the timeouts and retry limits of the client, overriding the retry policy in the
+    /// configuration.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Builds a client that can only read blobs and their status, and does not require a wallet.
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Builds the client and starts refreshing the committees in the background.
+    pub async fn build(self) -> Result<WalrusClient> {
+        let mut config = match self.config {
+            Some(config) => config,
+            None => load_configuration(self.config_path.as_ref(), self.context.as_deref())
+                .map_err(Error::Config)?,
+        };
+        if let Some(retry_policy) = self.retry_policy {
+            config.communication_config.retry_policy = retry_policy;
+        }
+        let wallet = match self.wallet {
+            Some(wallet) => Ok(wallet),
+            None => WalletConfig::load_wallet_context(config.wallet_config.as_ref()),
+        };
+
+        let inner = if self.read_only {
+            Inner::ReadOnly(
+                get_read_client(config, self.rpc_url, wallet, true, &None)
+                    .await
+                    .map_err(Error::Config)?,
+            )
+        } else {
+            Inner::ReadWrite(
+                get_contract_client(config, wallet, self.gas_budget, &None)
+                    .await
+                    .map_err(Error::Config)?,
+            )
+        };
+        Ok(WalrusClient { inner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_configuration_is_reported() {
+        let error = WalrusClientBuilder::default()
+            .config_path("/nonexistent/client_config.yaml")
+            .read_only()
+            .build()
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Config(_)));
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The errors returned by the [`WalrusClient`][crate::WalrusClient].
+
+use walrus_service::client::{ClientError, ClientErrorKind};
+use walrus_sui::client::SuiClientError;
+
+/// The result type of the operations of the [`WalrusClient`][crate::WalrusClient].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error returned by the [`WalrusClient`][crate::WalrusClient].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The client configuration could not be loaded or is invalid.
+    #[error("invalid client configuration: {0}")]
+    Config(anyhow::Error),
+    /// The operation requires a Sui wallet, but the client was built in read-only mode.
+    #[error("the operation requires a wallet, but the client is read-only")]
+    ReadOnly,
+    /// The blob does not exist on Walrus.
+    #[error("the blob does not exist")]
+    BlobNotFound,
+    /// The blob exceeds the maximum blob size.
+    #[error("the blob size {0} exceeds the maximum blob size")]
+    BlobTooLarge(u64),
+    /// The blob could not be encoded.
+    #[error("the blob could not be encoded")]
+    EncodingFailed,
+    /// The storage nodes could not be reached, or their responses were not sufficient to complete
+    /// the operation.
+    #[error(transparent)]
+    Client(ClientError),
+    /// An error occurred while interacting with Sui.
+    #[error(transparent)]
+    Sui(#[from] SuiClientError),
+    /// The blob could not be read from its source.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl From<ClientError> for Error {
+    fn from(error: ClientError) -> Self {
+        match error.kind() {
+            ClientErrorKind::BlobIdDoesNotExist => Self::BlobNotFound,
+            _ => Self::Client(error),
+        }
+    }
+}
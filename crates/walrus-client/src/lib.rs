@@ -0,0 +1,51 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A high-level client to store and read blobs on Walrus.
+//!
+//! The [`WalrusClient`] hides the committee handling, the erasure encoding, and the interaction
+//! with the Walrus contracts on Sui behind a small set of operations: storing, reading, checking
+//! the status of, extending, and deleting blobs.
+//!
+//! ```no_run
+//! # async fn example() -> walrus_client::Result<()> {
+//! use walrus_client::{StoreOptions, WalrusClient};
+//!
+//! // Uses the Walrus configuration and the Sui wallet from the default locations.
+//! let client = WalrusClient::builder().build().await?;
+//!
+//! let result = client.store(b"hello walrus", &StoreOptions::default()).await?;
+//! let blob = client.read(result.blob_id()).await?;
+//! assert_eq!(blob, b"hello walrus");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The [`WalrusClient`] requires the `native` feature, which is enabled by default. Without it, the
+//! crate only contains the [`reader::BlobReader`], which reads and verifies blobs over a pluggable
+//! HTTP transport and compiles to `wasm32-unknown-unknown`.
+
+pub mod reader;
+
+#[cfg(feature = "native")]
+mod client;
+#[cfg(feature = "native")]
+pub use client::{StoreOptions, WalrusClient, WalrusClientBuilder};
+
+#[cfg(feature = "native")]
+mod error;
+#[cfg(feature = "native")]
+pub use error::{Error, Result};
+pub use walrus_core::{BlobId, EncodingType, EpochCount};
+#[cfg(feature = "native")]
+pub use walrus_sdk::api::BlobStatus;
+#[cfg(feature = "native")]
+pub use walrus_service::client::{
+    responses::BlobStoreResult,
+    Config,
+    RetryPolicy,
+    RetryPolicyBuilder,
+    StoreWhen,
+};
+#[cfg(feature = "native")]
+pub use walrus_sui::client::{BlobPersistence, PostStoreAction};
@@ -0,0 +1,270 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A blob reader that fetches and verifies blobs directly from the storage nodes.
+//!
+//! The reader does not depend on tokio or on a Sui client and leaves the HTTP transport to the
+//! caller through the [`HttpClient`] trait. It therefore compiles to `wasm32-unknown-unknown`
+//! (with `--no-default-features`), which allows browsers to read blobs without trusting an
+//! aggregator. The caller is responsible for providing the storage nodes of the current committee.
+
+use std::{future::Future, num::NonZeroU16};
+
+use futures::{stream::FuturesUnordered, StreamExt as _};
+use serde::de::DeserializeOwned;
+use walrus_core::{
+    encoding::{DataTooLargeError, DecodingVerificationError, EncodingConfig, Primary, SliverData},
+    metadata::{UnverifiedBlobMetadataWithId, VerifiedBlobMetadataWithId},
+    BlobId,
+    ShardIndex,
+    SliverPairIndex,
+    SliverType,
+};
+
+/// A minimal asynchronous HTTP client used by the [`BlobReader`].
+///
+/// Implementations can be based on any HTTP library, for example, `fetch` in the browser.
+pub trait HttpClient {
+    /// The error returned when a request fails.
+    type Error: std::error::Error;
+
+    /// Sends a GET request to `url` and returns the body of the response.
+    ///
+    /// Responses with a non-success status code must be returned as errors.
+    fn get(&self, url: &str) -> impl Future<Output = Result<Vec<u8>, Self::Error>>;
+}
+
+/// The HTTP endpoint of a storage node and the shards it holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageNodeEndpoint {
+    /// The base URL of the storage node's REST API, e.g., `https://node.example.com:9185`.
+    pub url: String,
+    /// The shards assigned to the storage node in the current epoch.
+    pub shards: Vec<ShardIndex>,
+}
+
+/// An error returned by the [`BlobReader`].
+#[derive(Debug, thiserror::Error)]
+pub enum ReadError {
+    /// None of the storage nodes returned metadata that matches the blob ID.
+    #[error("no storage node returned valid metadata for the blob")]
+    NoValidMetadata,
+    /// Not enough valid slivers could be retrieved to reconstruct the blob.
+    #[error("not enough valid slivers could be retrieved to reconstruct the blob")]
+    NotEnoughSlivers,
+    /// The blob size in the metadata exceeds the maximum blob size.
+    #[error(transparent)]
+    DataTooLarge(#[from] DataTooLargeError),
+    /// The decoded blob does not match the blob ID.
+    #[error(transparent)]
+    Decoding(#[from] DecodingVerificationError),
+}
+
+/// Reads blobs from the storage nodes and verifies them against their blob IDs.
+#[derive(Debug, Clone)]
+pub struct BlobReader<H> {
+    http_client: H,
+    encoding_config: EncodingConfig,
+    nodes: Vec<StorageNodeEndpoint>,
+}
+
+impl<H: HttpClient> BlobReader<H> {
+    /// Creates a new reader for a committee with the given number of shards and storage nodes.
+    pub fn new(http_client: H, n_shards: NonZeroU16, nodes: Vec<StorageNodeEndpoint>) -> Self {
+        Self {
+            http_client,
+            encoding_config: EncodingConfig::new(n_shards),
+            nodes,
+        }
+    }
+
+    /// Returns the number of shards in the committee.
+    pub fn n_shards(&self) -> NonZeroU16 {
+        self.encoding_config.n_shards()
+    }
+
+    /// Reads the blob with the given ID and verifies that it matches the blob ID.
+    pub async fn read_blob(&self, blob_id: &BlobId) -> Result<Vec<u8>, ReadError> {
+        let metadata = self.get_verified_metadata(blob_id).await?;
+        self.read_slivers_and_decode(&metadata).await
+    }
+
+    /// Requests the metadata of the blob from the storage nodes, one at a time, and returns the
+    /// first metadata that matches the blob ID.
+    pub async fn get_verified_metadata(
+        &self,
+        blob_id: &BlobId,
+    ) -> Result<VerifiedBlobMetadataWithId, ReadError> {
+        for node in &self.nodes {
+            let url = format!("{}/v1/blobs/{blob_id}/metadata", base_url(node));
+            let metadata = match self.get_bcs::<UnverifiedBlobMetadataWithId>(&url).await {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+            match metadata.verify(&self.encoding_config) {
+                Ok(metadata) => return Ok(metadata),
+                Err(error) => {
+                    tracing::debug!(node = %node.url, %error, "received invalid metadata");
+                }
+            }
+        }
+        Err(ReadError::NoValidMetadata)
+    }
+
+    /// Requests the primary slivers from all shards and decodes the blob as soon as enough valid
+    /// slivers have been received.
+    async fn read_slivers_and_decode(
+        &self,
+        metadata: &VerifiedBlobMetadataWithId,
+    ) -> Result<Vec<u8>, ReadError> {
+        let blob_id = metadata.blob_id();
+        let mut decoder = self
+            .encoding_config
+            .get_for_type(metadata.metadata().encoding_type())
+            .get_blob_decoder::<Primary>(metadata.metadata().unencoded_length())?;
+
+        let n_shards = self.n_shards();
+        let mut requests: FuturesUnordered<_> = self
+            .nodes
+            .iter()
+            .flat_map(|node| {
+                node.shards
+                    .iter()
+                    .map(move |shard| (node, shard.to_pair_index(n_shards, blob_id)))
+            })
+            .map(|(node, pair_index)| self.get_verified_sliver(node, metadata, pair_index))
+            .collect();
+
+        while let Some(sliver) = requests.next().await {
+            let Some(sliver) = sliver else {
+                continue;
+            };
+            if let Some((blob, _metadata)) = decoder.decode_and_verify(blob_id, [sliver])? {
+                return Ok(blob);
+            }
+        }
+        Err(ReadError::NotEnoughSlivers)
+    }
+
+    async fn get_verified_sliver(
+        &self,
+        node: &StorageNodeEndpoint,
+        metadata: &VerifiedBlobMetadataWithId,
+        pair_index: SliverPairIndex,
+    ) -> Option<SliverData<Primary>> {
+        let url = format!(
+            "{}/v1/blobs/{}/slivers/{}/{}",
+            base_url(node),
+            metadata.blob_id(),
+            pair_index.0,
+            SliverType::Primary,
+        );
+        let sliver = self.get_bcs::<SliverData<Primary>>(&url).await?;
+        sliver
+            .verify(&self.encoding_config, metadata.metadata())
+            .inspect_err(|error| {
+                tracing::debug!(node = %node.url, %error, "received invalid sliver");
+            })
+            .ok()
+            .map(|()| sliver)
+    }
+
+    async fn get_bcs<T: DeserializeOwned>(&self, url: &str) -> Option<T> {
+        let body = self
+            .http_client
+            .get(url)
+            .await
+            .inspect_err(|error| tracing::debug!(url, %error, "request to storage node failed"))
+            .ok()?;
+        bcs::from_bytes(&body)
+            .inspect_err(|error| tracing::debug!(url, %error, "failed to decode response"))
+            .ok()
+    }
+}
+
+fn base_url(node: &StorageNodeEndpoint) -> &str {
+    node.url.trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use walrus_core::{encoding::EncodingConfigTrait as _, DEFAULT_ENCODING};
+
+    use super::*;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("not found")]
+    struct NotFound;
+
+    /// Serves the responses of a single storage node from memory.
+    struct InMemoryHttpClient(HashMap<String, Vec<u8>>);
+
+    impl HttpClient for InMemoryHttpClient {
+        type Error = NotFound;
+
+        async fn get(&self, url: &str) -> Result<Vec<u8>, NotFound> {
+            self.0.get(url).cloned().ok_or(NotFound)
+        }
+    }
+
+    const N_SHARDS: u16 = 10;
+    const NODE_URL: &str = "http://node.test";
+
+    fn reader_for_blob(
+        blob: &[u8],
+        tamper: impl Fn(&mut Vec<u8>),
+    ) -> (BlobReader<InMemoryHttpClient>, BlobId) {
+        let n_shards = NonZeroU16::new(N_SHARDS).unwrap();
+        let config = EncodingConfig::new(n_shards);
+        let (pairs, metadata) = config
+            .get_for_type(DEFAULT_ENCODING)
+            .encode_with_metadata(blob)
+            .unwrap();
+        let blob_id = *metadata.blob_id();
+
+        let mut responses = HashMap::new();
+        responses.insert(
+            format!("{NODE_URL}/v1/blobs/{blob_id}/metadata"),
+            bcs::to_bytes(&metadata.into_unverified()).unwrap(),
+        );
+        for pair in pairs {
+            let mut sliver = bcs::to_bytes(&pair.primary).unwrap();
+            tamper(&mut sliver);
+            responses.insert(
+                format!(
+                    "{NODE_URL}/v1/blobs/{blob_id}/slivers/{}/primary",
+                    pair.index().0
+                ),
+                sliver,
+            );
+        }
+
+        let node = StorageNodeEndpoint {
+            url: format!("{NODE_URL}/"),
+            shards: ShardIndex::range(..N_SHARDS).collect(),
+        };
+        let reader = BlobReader::new(InMemoryHttpClient(responses), n_shards, vec![node]);
+        (reader, blob_id)
+    }
+
+    #[tokio::test]
+    async fn reads_and_verifies_blob() {
+        let blob = walrus_test_utils::random_data(1000);
+        let (reader, blob_id) = reader_for_blob(&blob, |_| ());
+        let read = reader.read_blob(&blob_id).await.unwrap();
+        assert_eq!(read, blob);
+    }
+
+    #[tokio::test]
+    async fn rejects_tampered_slivers() {
+        let blob = walrus_test_utils::random_data(1000);
+        let (reader, blob_id) = reader_for_blob(&blob, |sliver| {
+            let last = sliver.last_mut().unwrap();
+            *last = last.wrapping_add(1);
+        });
+        let result = reader.read_blob(&blob_id).await;
+        assert!(matches!(result, Err(ReadError::NotEnoughSlivers)));
+    }
+}
@@ -663,12 +663,14 @@ impl<'a, D: Decoder, E: EncodingAxis> BlobDecoder<'a, D, E> {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use walrus_test_utils::{param_test, random_data, random_subset};
 
     use super::*;
     use crate::{
         encoding::{EncodingConfig, RaptorQEncodingConfig},
         metadata::{BlobMetadataApi as _, UnverifiedBlobMetadataWithId},
+        test_utils::strategies,
         EncodingType,
     };
 
@@ -890,4 +892,39 @@ mod tests {
         assert_eq!(blob, blob_dec);
         assert_eq!(metadata_enc, metadata_dec);
     }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn blob_is_decoded_from_any_sufficient_subset_of_primary_slivers(
+            (encoded, sliver_indices) in strategies::encoded_blob_with_encoding(
+                EncodingType::RS2,
+                10_000,
+            )
+            .prop_flat_map(|encoded| {
+                let config = encoded.config.get_for_type(encoded.encoding_type);
+                let indices = proptest::sample::subsequence(
+                    (0..config.n_shards_as_usize()).collect::<Vec<_>>(),
+                    usize::from(config.n_primary_source_symbols().get()),
+                );
+                (Just(encoded), indices)
+            })
+        ) {
+            let config = encoded.config.get_for_type(encoded.encoding_type);
+            let slivers = sliver_indices
+                .iter()
+                .map(|index| encoded.sliver_pairs[*index].primary.clone());
+
+            let (blob, metadata) = config
+                .get_blob_decoder::<Primary>(encoded.blob.len().try_into().unwrap())
+                .unwrap()
+                .decode_and_verify(encoded.metadata.blob_id(), slivers)
+                .unwrap()
+                .expect("the number of slivers is sufficient for decoding");
+
+            assert_eq!(blob, encoded.blob);
+            assert_eq!(metadata, encoded.metadata);
+        }
+    }
 }
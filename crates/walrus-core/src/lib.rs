@@ -9,10 +9,7 @@
 extern crate alloc;
 extern crate std;
 
-use alloc::vec::Vec;
-#[allow(unused)]
-#[cfg(feature = "utoipa")]
-use alloc::{format, string::String};
+use alloc::{format, string::String, vec::Vec};
 use core::{
     fmt::{self, Debug, Display},
     num::NonZeroU16,
@@ -38,6 +35,7 @@ use encoding::{
 };
 use fastcrypto::{
     bls12381::min_pk::{BLS12381PublicKey, BLS12381Signature},
+    encoding::{Encoding as _, Hex},
     hash::{Blake2b256, HashFunction},
     secp256r1::Secp256r1PublicKey,
 };
@@ -48,6 +46,7 @@ use inconsistency::{
 };
 use merkle::{MerkleAuth, MerkleProof, Node};
 pub use metadata::BlobMetadata;
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use serde_with::{DeserializeAs, DisplayFromStr, SerializeAs};
 #[cfg(feature = "sui-types")]
@@ -195,6 +194,120 @@ impl BlobId {
 
         Self(hasher.finalize().into())
     }
+
+    /// Returns the blob ID as a string in the given encoding.
+    pub fn to_string_with_encoding(&self, encoding: BlobIdEncoding) -> String {
+        match encoding {
+            BlobIdEncoding::Base64 => format!("{self}"),
+            BlobIdEncoding::Hex => format!("0x{}", Hex::encode(self.0)),
+            BlobIdEncoding::Decimal => BigUint::from_bytes_le(&self.0).to_str_radix(10),
+        }
+    }
+
+    /// Parses a blob ID from a string in the given encoding.
+    ///
+    /// Hex-encoded blob IDs may be prefixed with `0x`.
+    pub fn parse_with_encoding(
+        s: &str,
+        encoding: BlobIdEncoding,
+    ) -> Result<Self, BlobIdParseError> {
+        match encoding {
+            BlobIdEncoding::Base64 => Self::from_str(s),
+            BlobIdEncoding::Hex => {
+                let digits = s.strip_prefix("0x").unwrap_or(s);
+                if digits.len() != 2 * Self::LENGTH {
+                    return Err(BlobIdParseError);
+                }
+                let bytes = Hex::decode(digits).map_err(|_| BlobIdParseError)?;
+                Self::try_from(bytes.as_slice())
+            }
+            BlobIdEncoding::Decimal => {
+                if s.is_empty() || !s.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return Err(BlobIdParseError);
+                }
+                let bytes = BigUint::parse_bytes(s.as_bytes(), 10)
+                    .ok_or(BlobIdParseError)?
+                    .to_bytes_le();
+                if bytes.len() > Self::LENGTH {
+                    return Err(BlobIdParseError);
+                }
+                let mut blob_id = Self::ZERO;
+                blob_id.0[..bytes.len()].copy_from_slice(&bytes);
+                Ok(blob_id)
+            }
+        }
+    }
+
+    /// Parses a blob ID from a string in any [`BlobIdEncoding`], and returns the blob ID together
+    /// with the encoding that was used.
+    ///
+    /// The encodings are attempted in the order of [`BlobIdEncoding::ALL`]. A few strings are valid
+    /// in more than one encoding (e.g., 43-digit decimal numbers are also valid base64 strings);
+    /// these are parsed with the first matching encoding, such that canonical blob IDs are never
+    /// reinterpreted.
+    pub fn parse_any_encoding(s: &str) -> Result<(Self, BlobIdEncoding), BlobIdParseError> {
+        BlobIdEncoding::ALL
+            .into_iter()
+            .find_map(|encoding| {
+                Self::parse_with_encoding(s, encoding)
+                    .ok()
+                    .map(|blob_id| (blob_id, encoding))
+            })
+            .ok_or(BlobIdParseError)
+    }
+
+    /// Converts a blob ID given in any [`BlobIdEncoding`] to its canonical base64 representation.
+    pub fn canonicalize(s: &str) -> Result<String, BlobIdParseError> {
+        Self::parse_any_encoding(s).map(|(blob_id, _)| format!("{blob_id}"))
+    }
+}
+
+/// The string encodings of blob IDs.
+///
+/// Walrus uses URL-safe base64 as the canonical encoding of blob IDs, which is used by the
+/// [`Display`] and [`FromStr`] implementations of [`BlobId`]. The other encodings are accepted by
+/// [`BlobId::parse_any_encoding`], so that blob IDs can be copied from other tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobIdEncoding {
+    /// URL-safe base64 without padding.
+    #[default]
+    Base64,
+    /// The bytes of the blob ID in hexadecimal, prefixed with `0x`.
+    Hex,
+    /// The blob ID as a little-endian `u256` in decimal, as it is shown by Sui explorers.
+    Decimal,
+}
+
+impl BlobIdEncoding {
+    /// All encodings, in the order in which they are attempted when parsing a blob ID.
+    pub const ALL: [Self; 3] = [Self::Base64, Self::Hex, Self::Decimal];
+}
+
+/// A [`serde_with`] adapter that serializes blob IDs in the canonical encoding and deserializes
+/// them from strings in any [`BlobIdEncoding`].
+#[derive(Debug)]
+pub struct BlobIdFromAnyEncoding;
+
+impl SerializeAs<BlobId> for BlobIdFromAnyEncoding {
+    fn serialize_as<S>(source: &BlobId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(source)
+    }
+}
+
+impl<'de> DeserializeAs<'de, BlobId> for BlobIdFromAnyEncoding {
+    fn deserialize_as<D>(deserializer: D) -> Result<BlobId, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        BlobId::parse_any_encoding(&s)
+            .map(|(blob_id, _)| blob_id)
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 impl AsRef<[u8]> for BlobId {
@@ -869,6 +982,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blob_id_is_parsed_from_any_encoding() -> Result<(), BlobIdParseError> {
+        let blob_id = BlobId::from_str("M5YQinGO3RoRLaW_KbCfvXStVlWEqO5dGDe5cSiN07I")?;
+        for encoding in BlobIdEncoding::ALL {
+            let encoded = blob_id.to_string_with_encoding(encoding);
+            assert_eq!(BlobId::parse_any_encoding(&encoded)?, (blob_id, encoding));
+        }
+        assert_eq!(
+            BlobId::parse_any_encoding(
+                "80885466015098902458382552429473803233277035186046880821304527730792838764083"
+            )?,
+            (blob_id, BlobIdEncoding::Decimal)
+        );
+        assert_eq!(
+            BlobId::canonicalize(
+                "0x3396108a718edd1a112da5bf29b09fbd74ad565584a8ee5d1837b971288dd3b2"
+            )?,
+            "M5YQinGO3RoRLaW_KbCfvXStVlWEqO5dGDe5cSiN07I"
+        );
+
+        // A 43-digit decimal value is also valid base64, which takes precedence.
+        let (_, encoding) =
+            BlobId::parse_any_encoding("1000000000000000000000000000000000000000000")?;
+        assert_eq!(encoding, BlobIdEncoding::Base64);
+
+        for invalid in [
+            "",
+            "aaaa",
+            "0x1234",
+            "-1",
+            format!("1{}", "0".repeat(78)).as_str(),
+        ] {
+            assert_eq!(BlobId::parse_any_encoding(invalid), Err(BlobIdParseError));
+        }
+        Ok(())
+    }
+
     #[test]
     fn symbol_id_serde_human_readable() {
         let symbol_id = SymbolId::new(17.into(), 21.into());
@@ -313,6 +313,8 @@ fn n_nodes(n_leaves: usize) -> usize {
 
 #[cfg(test)]
 mod test {
+    use proptest::{collection, prelude::*};
+
     use super::*;
 
     const TEST_INPUT: [&[u8]; 9] = [
@@ -396,4 +398,21 @@ mod test {
             }
         }
     }
+
+    proptest! {
+        #[test]
+        fn merkle_proofs_verify_only_for_their_leaf(
+            leaves in collection::vec(collection::vec(any::<u8>(), 0..64), 1..64),
+        ) {
+            let mt: MerkleTree = MerkleTree::build(&leaves);
+            for (index, leaf_data) in leaves.iter().enumerate() {
+                let proof = mt.get_proof(index).unwrap();
+                assert!(proof.verify_proof(&mt.root(), leaf_data, index));
+
+                let mut modified_leaf = leaf_data.clone();
+                modified_leaf.push(0);
+                assert!(!proof.verify_proof(&mt.root(), &modified_leaf, index));
+            }
+        }
+    }
 }
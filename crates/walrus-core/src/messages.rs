@@ -227,12 +227,14 @@ impl Intent {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use storage_confirmation::{BlobPersistenceType, StorageConfirmationBody};
     use walrus_test_utils::{param_test, Result as TestResult};
 
     use super::*;
     use crate::{
         messages::{IntentAppId, IntentVersion},
+        test_utils::strategies,
         BlobId,
         Epoch,
     };
@@ -345,4 +347,35 @@ mod tests {
 
         Ok(())
     }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn signed_confirmation_verifies_only_under_signer_key(
+            key_pair in strategies::protocol_key_pair(),
+            other_key_pair in strategies::protocol_key_pair(),
+            epoch: Epoch,
+            blob_id: BlobId,
+            blob_type: BlobPersistenceType,
+        ) {
+            let confirmation = Confirmation::new(epoch, blob_id, blob_type);
+            let signed = key_pair.sign_message(&confirmation);
+            let decoded: SignedMessage<Confirmation> =
+                bcs::from_bytes(&bcs::to_bytes(&signed).unwrap()).unwrap();
+
+            let body = StorageConfirmationBody { blob_id, blob_type };
+            assert_eq!(
+                decoded
+                    .verify_signature_and_contents(key_pair.public(), epoch, &body)
+                    .unwrap(),
+                confirmation
+            );
+            if other_key_pair.public() != key_pair.public() {
+                assert!(decoded
+                    .verify_signature_and_get_message(other_key_pair.public())
+                    .is_err());
+            }
+        }
+    }
 }
@@ -83,3 +83,37 @@ pub enum CertificateError {
     #[error("the signed messages differ")]
     MessageMismatch,
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::test_utils::strategies;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn certificate_verifies_only_for_signed_message(
+            certificate in strategies::confirmation_certificate(10),
+        ) {
+            let signer_keys = certificate.signer_keys();
+            let decoded: ConfirmationCertificate =
+                bcs::from_bytes(&bcs::to_bytes(&certificate.certificate).unwrap()).unwrap();
+            assert_eq!(decoded, certificate.certificate);
+
+            assert!(decoded
+                .signature
+                .verify(&signer_keys, &decoded.serialized_message)
+                .is_ok());
+
+            let mut modified_message = decoded.serialized_message.clone();
+            modified_message[0] ^= 1;
+            assert!(decoded
+                .signature
+                .verify(&signer_keys, &modified_message)
+                .is_err());
+        }
+    }
+}
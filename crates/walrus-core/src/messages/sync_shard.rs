@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use alloc::vec::Vec;
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
@@ -37,15 +39,38 @@ pub struct SyncShardRequestV1 {
     epoch: Epoch,
 }
 
+/// Represents version 2 of the sync shard request, which adds a nonce and an expiry to version 1
+/// such that a signed request cannot be replayed.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SyncShardRequestV2 {
+    /// The parameters of the request, see [`SyncShardRequestV1`].
+    request: SyncShardRequestV1,
+
+    /// A random value identifying the request, which the serving node accepts only once.
+    nonce: u64,
+
+    /// The Unix timestamp, in seconds, after which the serving node rejects the request.
+    expires_at: u64,
+}
+
 /// Represents a request to sync a shard from a storage node.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SyncShardRequest {
     /// Version 1 of the sync shard request.
     V1(SyncShardRequestV1),
+    /// Version 2 of the sync shard request, which includes a nonce and an expiry.
+    V2(SyncShardRequestV2),
 }
 
 impl SyncShardRequest {
-    /// Creates a new `SyncShardRequest` with the specified parameters.
+    /// The time for which requests created with [`SyncShardRequest::new_with_replay_protection`]
+    /// are valid.
+    pub const DEFAULT_VALIDITY: Duration = Duration::from_secs(5 * 60);
+
+    /// Creates a new version 1 `SyncShardRequest` with the specified parameters.
+    ///
+    /// The request has neither a nonce nor an expiry, such that it is understood by nodes that do
+    /// not support version 2 of the request yet.
     pub fn new(
         shard_index: ShardIndex,
         sliver_type: SliverType,
@@ -62,38 +87,101 @@ impl SyncShardRequest {
         })
     }
 
-    /// Returns the shard index of the request.
-    pub fn shard_index(&self) -> ShardIndex {
+    /// Creates a new version 2 `SyncShardRequest` with the specified parameters.
+    ///
+    /// The request has a random nonce and expires after [`Self::DEFAULT_VALIDITY`]. Only nodes
+    /// that support version 2 of the request can decode it.
+    pub fn new_with_replay_protection(
+        shard_index: ShardIndex,
+        sliver_type: SliverType,
+        starting_blob_id: BlobId,
+        sliver_count: u64,
+        epoch: Epoch,
+    ) -> SyncShardRequest {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Self::new_with_expiry(
+            shard_index,
+            sliver_type,
+            starting_blob_id,
+            sliver_count,
+            epoch,
+            rand::random(),
+            now + Self::DEFAULT_VALIDITY.as_secs(),
+        )
+    }
+
+    /// Creates a new version 2 `SyncShardRequest` with the specified nonce and expiry, given as a
+    /// Unix timestamp in seconds.
+    pub fn new_with_expiry(
+        shard_index: ShardIndex,
+        sliver_type: SliverType,
+        starting_blob_id: BlobId,
+        sliver_count: u64,
+        epoch: Epoch,
+        nonce: u64,
+        expires_at: u64,
+    ) -> SyncShardRequest {
+        Self::V2(SyncShardRequestV2 {
+            request: SyncShardRequestV1 {
+                shard_index,
+                sliver_type,
+                starting_blob_id,
+                sliver_count,
+                epoch,
+            },
+            nonce,
+            expires_at,
+        })
+    }
+
+    fn parameters(&self) -> &SyncShardRequestV1 {
         match self {
-            Self::V1(request) => request.shard_index,
+            Self::V1(request) => request,
+            Self::V2(request) => &request.request,
         }
     }
 
+    /// Returns the shard index of the request.
+    pub fn shard_index(&self) -> ShardIndex {
+        self.parameters().shard_index
+    }
+
     /// Returns the sliver type of the request.
     pub fn sliver_type(&self) -> SliverType {
-        match self {
-            Self::V1(request) => request.sliver_type,
-        }
+        self.parameters().sliver_type
     }
 
     /// Returns the starting blob ID of the request.
     pub fn starting_blob_id(&self) -> BlobId {
-        match self {
-            Self::V1(request) => request.starting_blob_id,
-        }
+        self.parameters().starting_blob_id
     }
 
     /// Returns the number of slivers to sync starting from the starting blob ID.
     pub fn sliver_count(&self) -> u64 {
-        match self {
-            Self::V1(request) => request.sliver_count,
-        }
+        self.parameters().sliver_count
     }
 
     /// Returns the epoch of the request.
     pub fn epoch(&self) -> Epoch {
+        self.parameters().epoch
+    }
+
+    /// Returns the nonce of the request, or `None` for requests without replay protection.
+    pub fn nonce(&self) -> Option<u64> {
+        match self {
+            Self::V1(_) => None,
+            Self::V2(request) => Some(request.nonce),
+        }
+    }
+
+    /// Returns the Unix timestamp, in seconds, at which the request expires, or `None` for
+    /// requests without replay protection.
+    pub fn expires_at(&self) -> Option<u64> {
         match self {
-            Self::V1(request) => request.epoch,
+            Self::V1(_) => None,
+            Self::V2(request) => Some(request.expires_at),
         }
     }
 }
@@ -369,12 +369,95 @@ impl SliverPairMetadata {
     }
 }
 
+/// The blob ID of a blob together with further values derived from its contents.
+///
+/// These values can be computed without interacting with Walrus and allow referencing a blob
+/// before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobIdInfo {
+    /// The ID of the blob.
+    pub blob_id: BlobId,
+    /// The type of encoding used to erasure encode the blob.
+    pub encoding_type: EncodingType,
+    /// The length of the unencoded blob.
+    pub unencoded_length: u64,
+    /// The total length of the encoded blob, including the metadata stored on all shards.
+    pub encoded_length: u64,
+    /// The roots of the Merkle trees over the primary and secondary slivers, one entry per sliver
+    /// pair.
+    pub sliver_roots: Vec<SliverPairMetadata>,
+}
+
+/// Computes the blob ID, encoded length, and sliver roots of the blob, when encoded with the
+/// specified encoding type for a system with `n_shards` shards.
+///
+/// The result is the same as the one obtained when storing the blob on Walrus, provided that
+/// `n_shards` matches the number of shards of the system.
+pub fn compute_blob_id_info(
+    blob: &[u8],
+    n_shards: NonZeroU16,
+    encoding_type: EncodingType,
+) -> Result<BlobIdInfo, DataTooLargeError> {
+    let metadata = EncodingConfig::new(n_shards)
+        .get_for_type(encoding_type)
+        .compute_metadata(blob)?;
+    let encoded_length = metadata
+        .metadata()
+        .encoded_size()
+        .expect("the encoded size is defined for blobs that can be encoded");
+    Ok(BlobIdInfo {
+        blob_id: metadata.blob_id,
+        encoding_type,
+        unencoded_length: metadata.metadata().unencoded_length(),
+        encoded_length,
+        sliver_roots: metadata.metadata().hashes().clone(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::{encoding::EncodingConfigTrait as _, test_utils::strategies};
 
     const BLOB_ID: BlobId = BlobId([7; 32]);
 
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn encoded_metadata_verifies_after_bcs_roundtrip(
+            encoded in strategies::encoded_blob(10_000),
+        ) {
+            let unverified = encoded.metadata.clone().into_unverified();
+            let decoded: UnverifiedBlobMetadataWithId =
+                bcs::from_bytes(&bcs::to_bytes(&unverified).unwrap()).unwrap();
+
+            assert_eq!(decoded.verify(&encoded.config).unwrap(), encoded.metadata);
+        }
+    }
+
+    #[test]
+    fn computes_blob_id_info() {
+        let n_shards = NonZeroU16::new(10).unwrap();
+        let blob = b"content addressed";
+        let info = compute_blob_id_info(blob, n_shards, EncodingType::RS2).unwrap();
+        let metadata = EncodingConfig::new(n_shards)
+            .get_for_type(EncodingType::RS2)
+            .compute_metadata(blob)
+            .unwrap();
+
+        assert_eq!(&info.blob_id, metadata.blob_id());
+        assert_eq!(info.unencoded_length, 17);
+        assert_eq!(
+            Some(info.encoded_length),
+            encoded_blob_length_for_n_shards(n_shards, 17, EncodingType::RS2)
+        );
+        assert_eq!(&info.sliver_roots, metadata.metadata().hashes());
+        assert_eq!(info.sliver_roots.len(), 10);
+    }
+
     mod verify {
         use super::*;
         use crate::test_utils;
@@ -34,6 +34,8 @@ use crate::{
     DEFAULT_ENCODING,
 };
 
+pub mod strategies;
+
 /// Returns a deterministic fixed protocol key pair for testing.
 ///
 /// Various testing facilities can use this key and unit-test can re-generate it to verify the
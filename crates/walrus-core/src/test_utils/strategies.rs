@@ -0,0 +1,250 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+//! [`proptest`] strategies and [`Arbitrary`] implementations for property-based tests.
+//!
+//! Strategies that involve encoding blobs or generating key pairs are comparatively expensive;
+//! properties using them should limit the number of cases with
+//! [`ProptestConfig::with_cases`][proptest::test_runner::Config::with_cases].
+
+use alloc::vec::Vec;
+use core::num::NonZeroU16;
+
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    collection,
+    prop_oneof,
+    strategy::{BoxedStrategy, Just, Strategy},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    encoding::{EncodingConfig, EncodingConfigTrait as _, SliverPair},
+    keys::ProtocolKeyPair,
+    messages::{BlobPersistenceType, Confirmation, ConfirmationCertificate},
+    metadata::VerifiedBlobMetadataWithId,
+    BlobId,
+    EncodingType,
+    Epoch,
+    PublicKey,
+    ShardIndex,
+    SliverIndex,
+    SliverPairIndex,
+    SuiObjectId,
+};
+
+/// The smallest number of shards generated by [`n_shards`].
+pub const MIN_N_SHARDS: u16 = 4;
+/// The largest number of shards generated by [`n_shards`].
+///
+/// This is smaller than the number of shards on the production networks to keep the encoding in
+/// property tests fast.
+pub const MAX_N_SHARDS: u16 = 300;
+
+impl Arbitrary for BlobId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<[u8; Self::LENGTH]>().prop_map(BlobId).boxed()
+    }
+}
+
+impl Arbitrary for SuiObjectId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<[u8; Self::LENGTH]>().prop_map(SuiObjectId).boxed()
+    }
+}
+
+impl Arbitrary for EncodingType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(EncodingType::RedStuffRaptorQ), Just(EncodingType::RS2)].boxed()
+    }
+}
+
+impl Arbitrary for BlobPersistenceType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(BlobPersistenceType::Permanent),
+            any::<SuiObjectId>().prop_map(|object_id| BlobPersistenceType::Deletable { object_id }),
+        ]
+        .boxed()
+    }
+}
+
+/// Returns a strategy for the number of shards in a system, between [`MIN_N_SHARDS`] and
+/// [`MAX_N_SHARDS`].
+pub fn n_shards() -> impl Strategy<Value = NonZeroU16> {
+    (MIN_N_SHARDS..=MAX_N_SHARDS)
+        .prop_map(|n_shards| NonZeroU16::new(n_shards).expect("the minimum is non-zero"))
+}
+
+/// Returns a strategy for encoding configurations with a number of shards given by [`n_shards`].
+pub fn encoding_config() -> impl Strategy<Value = EncodingConfig> {
+    n_shards().prop_map(EncodingConfig::new)
+}
+
+/// Returns a strategy for blob sizes that can be encoded with any number of shards generated by
+/// [`n_shards`], up to `max_size`.
+pub fn blob_size(max_size: u64) -> impl Strategy<Value = u64> {
+    0..=max_size
+}
+
+/// Returns a strategy for blobs with a size given by [`blob_size`].
+pub fn blob(max_size: u64) -> impl Strategy<Value = Vec<u8>> {
+    blob_size(max_size).prop_flat_map(|size| {
+        collection::vec(
+            any::<u8>(),
+            usize::try_from(size).expect("the size of test blobs fits into a usize"),
+        )
+    })
+}
+
+/// Returns a strategy for valid sliver indices in a system with `n_shards` shards.
+pub fn sliver_index(n_shards: NonZeroU16) -> impl Strategy<Value = SliverIndex> {
+    (0..n_shards.get()).prop_map(SliverIndex)
+}
+
+/// Returns a strategy for valid sliver-pair indices in a system with `n_shards` shards.
+pub fn sliver_pair_index(n_shards: NonZeroU16) -> impl Strategy<Value = SliverPairIndex> {
+    (0..n_shards.get()).prop_map(SliverPairIndex)
+}
+
+/// Returns a strategy for valid shard indices in a system with `n_shards` shards.
+pub fn shard_index(n_shards: NonZeroU16) -> impl Strategy<Value = ShardIndex> {
+    (0..n_shards.get()).prop_map(ShardIndex)
+}
+
+/// Returns a strategy for protocol key pairs, which are generated deterministically from a seed.
+pub fn protocol_key_pair() -> impl Strategy<Value = ProtocolKeyPair> {
+    any::<u64>()
+        .no_shrink()
+        .prop_map(|seed| ProtocolKeyPair::generate_with_rng(&mut StdRng::seed_from_u64(seed)))
+}
+
+/// A blob along with its encoding.
+#[derive(Debug, Clone)]
+pub struct EncodedBlob {
+    /// The encoding configuration used to encode the blob.
+    pub config: EncodingConfig,
+    /// The encoding type used to encode the blob.
+    pub encoding_type: EncodingType,
+    /// The unencoded blob.
+    pub blob: Vec<u8>,
+    /// The sliver pairs of the encoded blob.
+    pub sliver_pairs: Vec<SliverPair>,
+    /// The metadata of the encoded blob.
+    pub metadata: VerifiedBlobMetadataWithId,
+}
+
+/// Returns a strategy for blobs of up to `max_size` bytes, encoded with an arbitrary encoding
+/// type and configuration.
+pub fn encoded_blob(max_size: u64) -> impl Strategy<Value = EncodedBlob> {
+    any::<EncodingType>()
+        .prop_flat_map(move |encoding_type| encoded_blob_with_encoding(encoding_type, max_size))
+}
+
+/// Returns a strategy for blobs of up to `max_size` bytes, encoded with the specified encoding
+/// type and an arbitrary configuration.
+///
+/// Unlike Reed-Solomon, RaptorQ decoding may fail with small probability if exactly the minimum
+/// number of slivers is available; properties relying on decoding from the minimum number of
+/// slivers should therefore use [`EncodingType::RS2`].
+pub fn encoded_blob_with_encoding(
+    encoding_type: EncodingType,
+    max_size: u64,
+) -> impl Strategy<Value = EncodedBlob> {
+    (encoding_config(), blob(max_size)).prop_map(move |(config, blob)| {
+        let (sliver_pairs, metadata) = config
+            .get_for_type(encoding_type)
+            .encode_with_metadata(&blob)
+            .expect("the test blobs are small enough to be encoded");
+        EncodedBlob {
+            config,
+            encoding_type,
+            blob,
+            sliver_pairs,
+            metadata,
+        }
+    })
+}
+
+/// Returns a strategy for the metadata of blobs of up to `max_size` bytes.
+pub fn verified_blob_metadata(max_size: u64) -> impl Strategy<Value = VerifiedBlobMetadataWithId> {
+    encoded_blob(max_size).prop_map(|encoded| encoded.metadata)
+}
+
+/// A certificate along with the public keys of the nodes in the committee that may have signed
+/// it, such that `public_keys[i]` is the key of the signer with index `i`.
+#[derive(Debug, Clone)]
+pub struct CertificateWithCommittee {
+    /// The certificate.
+    pub certificate: ConfirmationCertificate,
+    /// The public keys of all members of the committee.
+    pub public_keys: Vec<PublicKey>,
+}
+
+impl CertificateWithCommittee {
+    /// Returns the public keys of the signers of the certificate.
+    pub fn signer_keys(&self) -> Vec<PublicKey> {
+        self.certificate
+            .signers
+            .iter()
+            .map(|index| self.public_keys[usize::from(*index)].clone())
+            .collect()
+    }
+}
+
+/// Returns a strategy for confirmation certificates signed by a non-empty subset of a committee
+/// with up to `max_committee_size` members.
+pub fn confirmation_certificate(
+    max_committee_size: usize,
+) -> impl Strategy<Value = CertificateWithCommittee> {
+    let committee = collection::vec(protocol_key_pair(), 1..=max_committee_size);
+    let confirmation = (
+        any::<Epoch>(),
+        any::<BlobId>(),
+        any::<BlobPersistenceType>(),
+    )
+        .prop_map(|(epoch, blob_id, blob_type)| Confirmation::new(epoch, blob_id, blob_type));
+
+    (committee, confirmation)
+        .prop_flat_map(|(committee, confirmation)| {
+            let n_members = committee.len();
+            (
+                Just(committee),
+                Just(confirmation),
+                collection::btree_set(0..n_members, 1..=n_members),
+            )
+        })
+        .prop_map(|(committee, confirmation, signers)| {
+            let signed_messages = signers
+                .iter()
+                .map(|index| committee[*index].sign_message(&confirmation));
+            let signer_indices = signers
+                .iter()
+                .map(|index| u16::try_from(*index).expect("the committee size fits into a u16"))
+                .collect();
+            let certificate = ConfirmationCertificate::from_signed_messages_and_indices(
+                signed_messages,
+                signer_indices,
+            )
+            .expect("all signed messages are identical");
+
+            CertificateWithCommittee {
+                certificate,
+                public_keys: committee
+                    .iter()
+                    .map(|key_pair| key_pair.public().clone())
+                    .collect(),
+            }
+        })
+}
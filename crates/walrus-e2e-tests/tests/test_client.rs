@@ -18,10 +18,12 @@ use std::{
 use indicatif::MultiProgress;
 use rand::random;
 #[cfg(msim)]
-use sui_macros::{clear_fail_point, register_fail_point_if};
+use sui_macros::{clear_fail_point, register_fail_point_arg, register_fail_point_if};
 use sui_types::base_types::{SuiAddress, SUI_ADDRESS_LENGTH};
 use tempfile::TempDir;
 use tokio_stream::StreamExt;
+#[cfg(msim)]
+use walrus_core::PublicKey;
 use walrus_core::{
     encoding::{EncodingConfigTrait as _, Primary},
     merkle::Node,
@@ -36,6 +38,10 @@ use walrus_core::{
 };
 use walrus_proc_macros::walrus_simtest;
 use walrus_sdk::api::BlobStatus;
+#[cfg(msim)]
+use walrus_sdk::api::StoredOnNodeStatus;
+#[cfg(msim)]
+use walrus_service::node::ServiceState as _;
 use walrus_service::{
     client::{
         responses::BlobStoreResult,
@@ -50,6 +56,7 @@ use walrus_service::{
             NotEnoughConfirmations,
             NotEnoughSlivers,
         },
+        MetadataReplication,
         StoreWhen,
     },
     test_utils::{
@@ -131,6 +138,7 @@ where
             StoreWhen::Always,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
 
@@ -168,6 +176,88 @@ where
     Ok(())
 }
 
+#[ignore = "ignore E2E tests by default"]
+#[walrus_simtest]
+async fn test_read_blob_stream() -> TestResult {
+    telemetry_subscribers::init_for_testing();
+    let (_sui_cluster_handle, _cluster, client) = test_cluster::default_setup().await?;
+    let client = client.as_ref();
+    let blob = walrus_test_utils::random_data(30000);
+
+    let results = client
+        .reserve_and_store_blobs(
+            &[blob.as_slice()],
+            DEFAULT_ENCODING,
+            1,
+            StoreWhen::Always,
+            BlobPersistence::Permanent,
+            PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
+        )
+        .await?;
+    let blob_id = results[0].blob_id();
+
+    let mut stream = std::pin::pin!(client.read_blob_stream(blob_id).await?);
+    let mut read_data = Vec::new();
+    let mut n_chunks = 0;
+    while let Some(chunk) = stream.next().await {
+        read_data.extend_from_slice(&chunk?);
+        n_chunks += 1;
+    }
+    assert_eq!(read_data, blob);
+    assert!(
+        n_chunks > 1,
+        "the blob should be streamed in multiple chunks"
+    );
+
+    Ok(())
+}
+
+#[ignore = "ignore E2E tests by default"]
+#[walrus_simtest]
+async fn test_read_blob_stream_prefetched() -> TestResult {
+    telemetry_subscribers::init_for_testing();
+    let (_sui_cluster_handle, _cluster, client) = test_cluster::default_setup().await?;
+    let WithTempDir {
+        inner: client,
+        temp_dir: _temp_dir,
+    } = client;
+    let client = std::sync::Arc::new(client);
+    let blob = walrus_test_utils::random_data(30000);
+
+    let results = client
+        .reserve_and_store_blobs(
+            &[blob.as_slice()],
+            DEFAULT_ENCODING,
+            1,
+            StoreWhen::Always,
+            BlobPersistence::Permanent,
+            PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
+        )
+        .await?;
+    let blob_id = results[0].blob_id();
+
+    let (length, stream) = client.read_blob_stream_prefetched(blob_id, 2).await?;
+    assert_eq!(length, u64::try_from(blob.len())?);
+    let mut stream = std::pin::pin!(stream);
+    let mut read_data = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        read_data.extend_from_slice(&chunk?);
+        // Simulate a slow consumer, while the remaining slivers are prefetched.
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(read_data, blob);
+
+    // Reading a blob that does not exist fails before any data is streamed.
+    assert!(client
+        .read_blob_stream_prefetched(&BlobId::ZERO, 2)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 async_param_test! {
     #[ignore = "ignore E2E tests by default"]
     #[walrus_simtest]
@@ -330,6 +420,7 @@ async fn test_inconsistency(failed_nodes: &[usize]) -> TestResult {
             &metadata,
             &pairs,
             &BlobPersistenceType::Permanent,
+            MetadataReplication::WithSlivers,
             &MultiProgress::new(),
         )
         .await?;
@@ -460,6 +551,7 @@ async fn test_store_with_existing_blob_resource(
             StoreWhen::NotStored,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?
         .into_iter()
@@ -537,6 +629,7 @@ async fn store_blob(
             StoreWhen::NotStored,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
 
@@ -578,6 +671,7 @@ async fn test_store_with_existing_blobs() -> TestResult {
             StoreWhen::NotStored,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     for result in store_results {
@@ -713,6 +807,7 @@ async fn test_store_with_existing_storage_resource(
             StoreWhen::NotStored,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?
         .into_iter()
@@ -755,6 +850,7 @@ async fn test_delete_blob(blobs_to_create: u32) -> TestResult {
                 StoreWhen::Always,
                 BlobPersistence::Deletable,
                 PostStoreAction::Keep,
+                MetadataReplication::WithSlivers,
             )
             .await?;
     }
@@ -769,6 +865,7 @@ async fn test_delete_blob(blobs_to_create: u32) -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     let blob_id = result.first().unwrap().blob_id();
@@ -813,6 +910,7 @@ async fn test_storage_nodes_delete_data_for_deleted_blobs() -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Deletable,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     let store_result = results.first().expect("should have one blob store result");
@@ -873,6 +971,7 @@ async fn test_blocklist() -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Deletable,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     let store_result = store_results[0].clone();
@@ -892,7 +991,7 @@ async fn test_blocklist() -> TestResult {
 
     tracing::info!("Adding blob to blocklist");
 
-    for blocklist in blocklists.iter_mut() {
+    for blocklist in blocklists.iter() {
         blocklist.insert(*blob_id)?;
     }
 
@@ -913,7 +1012,7 @@ async fn test_blocklist() -> TestResult {
     );
 
     // Remove the blob from the blocklist
-    for blocklist in blocklists.iter_mut() {
+    for blocklist in blocklists.iter() {
         blocklist.remove(blob_id)?;
     }
 
@@ -951,6 +1050,7 @@ async fn test_blob_operations_with_subsidies() -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
 
@@ -1042,6 +1142,7 @@ async fn test_multiple_stores_same_blob() -> TestResult {
                 store_when,
                 persistence,
                 PostStoreAction::Keep,
+                MetadataReplication::WithSlivers,
             )
             .await?;
         let store_result = results.first().expect("should have one blob store result");
@@ -1184,6 +1285,7 @@ async fn test_burn_blobs() -> TestResult {
                 StoreWhen::Always,
                 BlobPersistence::Permanent,
                 PostStoreAction::Keep,
+                MetadataReplication::WithSlivers,
             )
             .await?;
         blob_object_ids.push({
@@ -1239,6 +1341,7 @@ async fn test_extend_owned_blobs() -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     let BlobStoreResult::NewlyCreated { blob_object, .. } = result[0].clone() else {
@@ -1272,6 +1375,7 @@ async fn test_extend_owned_blobs() -> TestResult {
             StoreWhen::NotStored,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     let BlobStoreResult::NewlyCreated {
@@ -1309,6 +1413,7 @@ async fn test_share_blobs() -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
     let (end_epoch, blob_object_id) = {
@@ -1404,6 +1509,7 @@ async fn test_post_store_action(
             StoreWhen::Always,
             BlobPersistence::Permanent,
             post_store,
+            MetadataReplication::WithSlivers,
         )
         .await?;
 
@@ -1553,6 +1659,7 @@ async fn test_quorum_contract_upgrade() -> TestResult {
             StoreWhen::Always,
             BlobPersistence::Permanent,
             PostStoreAction::Keep,
+            MetadataReplication::WithSlivers,
         )
         .await?;
 
@@ -1701,6 +1808,7 @@ impl<'a> BlobAttributeTestContext<'a> {
                     StoreWhen::Always,
                     BlobPersistence::Permanent,
                     PostStoreAction::Keep,
+                    MetadataReplication::WithSlivers,
                 )
                 .await
                 .expect("reserve_and_store_blobs should succeed.");
@@ -2044,3 +2152,48 @@ async fn test_ptb_retriable_error() -> TestResult {
     clear_fail_point("ptb_executor_stake_pool_retriable_error");
     Ok(())
 }
+
+/// Tests that the metadata of a blob is pushed to the nodes that did not receive its slivers when
+/// storing with [`MetadataReplication::AllNodes`].
+#[ignore = "ignore E2E tests by default"]
+#[cfg(msim)]
+#[walrus_simtest]
+async fn test_store_with_metadata_replication_to_all_nodes() -> TestResult {
+    telemetry_subscribers::init_for_testing();
+    let (_sui_cluster_handle, cluster, client) = test_cluster::default_setup().await?;
+
+    // Prevent the client from storing the metadata and slivers on the node with the fewest shards,
+    // such that the node does not confirm storing the blob.
+    let skipped_node = &cluster.nodes[4];
+    let skipped_node_key = skipped_node.public_key().clone();
+    register_fail_point_arg(
+        "fail_point_client_store_metadata_and_pairs",
+        move || -> Option<PublicKey> { Some(skipped_node_key.clone()) },
+    );
+
+    let blob = walrus_test_utils::random_data(31415);
+    let result = client
+        .inner
+        .reserve_and_store_blobs(
+            &[&blob],
+            DEFAULT_ENCODING,
+            1,
+            StoreWhen::Always,
+            BlobPersistence::Permanent,
+            PostStoreAction::Keep,
+            MetadataReplication::AllNodes,
+        )
+        .await;
+    clear_fail_point("fail_point_client_store_metadata_and_pairs");
+    let blob_id = *result?
+        .first()
+        .expect("should have one blob store result")
+        .blob_id();
+
+    assert_eq!(
+        skipped_node.storage_node().metadata_status(&blob_id)?,
+        StoredOnNodeStatus::Stored
+    );
+
+    Ok(())
+}
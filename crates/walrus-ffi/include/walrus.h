@@ -0,0 +1,79 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// C interface to the Walrus client, implemented by the `walrus-ffi` crate.
+//
+// Link against `libwalrus` (built with `cargo build --release -p walrus-ffi`). All functions are
+// blocking. Fallible functions return a `WalrusStatus`; if it is not `WALRUS_STATUS_OK`, call
+// `walrus_last_error` for a description of the error. Panics in the library do not unwind into
+// the caller; they are reported as `WALRUS_STATUS_PANIC`, or as NULL by `walrus_client_new`.
+
+#ifndef WALRUS_H
+#define WALRUS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum WalrusStatus {
+    WALRUS_STATUS_OK = 0,
+    WALRUS_STATUS_INVALID_ARGUMENT = 1,
+    WALRUS_STATUS_BLOB_NOT_FOUND = 2,
+    WALRUS_STATUS_READ_ONLY = 3,
+    WALRUS_STATUS_MISMATCH = 4,
+    WALRUS_STATUS_ERROR = 5,
+    // The library panicked; the panic message is available from `walrus_last_error`.
+    WALRUS_STATUS_PANIC = 6,
+} WalrusStatus;
+
+// An opaque handle to a Walrus client.
+typedef struct WalrusClientHandle WalrusClientHandle;
+
+// Returns a description of the last error on the calling thread, or NULL. The string is owned by
+// the library and valid until the next call on the same thread.
+const char *walrus_last_error(void);
+
+// Creates a client from the configuration at `config_path` (or the default paths if NULL), using
+// the optional `context`. Returns NULL on failure.
+WalrusClientHandle *walrus_client_new(const char *config_path, const char *context, bool read_only);
+
+// Destroys a client. Accepts NULL.
+void walrus_client_free(WalrusClientHandle *client);
+
+// Stores `len` bytes for `epochs` epochs; on success, `*blob_id_out` must be freed with
+// `walrus_string_free`.
+WalrusStatus walrus_store(const WalrusClientHandle *client,
+                          const uint8_t *data,
+                          size_t len,
+                          uint32_t epochs,
+                          bool deletable,
+                          char **blob_id_out);
+
+// Reads a blob; on success, `*data_out` must be freed with `walrus_bytes_free(*data_out, *len_out)`.
+WalrusStatus walrus_read(const WalrusClientHandle *client,
+                         const char *blob_id,
+                         uint8_t **data_out,
+                         size_t *len_out);
+
+// Returns `WALRUS_STATUS_OK` if the `len` bytes at `data` correspond to `blob_id`, and
+// `WALRUS_STATUS_MISMATCH` otherwise.
+WalrusStatus walrus_verify(const WalrusClientHandle *client,
+                           const char *blob_id,
+                           const uint8_t *data,
+                           size_t len);
+
+// Frees a string returned by the library. Accepts NULL.
+void walrus_string_free(char *string);
+
+// Frees a buffer returned by the library. Accepts NULL.
+void walrus_bytes_free(uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // WALRUS_H
@@ -0,0 +1,446 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A C ABI for the [`WalrusClient`], to embed Walrus in applications written in other languages.
+//!
+//! The client is exposed as an opaque [`WalrusClientHandle`], created with [`walrus_client_new`]
+//! and destroyed with [`walrus_client_free`]. All functions are blocking; each handle owns a tokio
+//! runtime on which the operations are executed.
+//!
+//! Fallible functions return a [`WalrusStatus`]. If the status is not [`WalrusStatus::Ok`], a
+//! description of the error can be obtained with [`walrus_last_error`]. Memory returned by the
+//! library must be released with [`walrus_string_free`] or [`walrus_bytes_free`], respectively.
+//!
+//! Panics never unwind into the caller: a panicking function returns [`WalrusStatus::Panic`], or
+//! null if it returns a pointer, and the panic message is available from [`walrus_last_error`].
+//!
+//! The corresponding C header is in `include/walrus.h`.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr,
+    slice,
+};
+
+use tokio::runtime::Runtime;
+use walrus_client::{BlobId, BlobPersistence, Error, StoreOptions, WalrusClient};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The status returned by fallible functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalrusStatus {
+    /// The operation succeeded.
+    Ok = 0,
+    /// An argument was null or invalid.
+    InvalidArgument = 1,
+    /// The blob does not exist on Walrus.
+    BlobNotFound = 2,
+    /// The operation requires a wallet, but the client is read-only.
+    ReadOnly = 3,
+    /// The data does not match the blob ID.
+    Mismatch = 4,
+    /// Any other error.
+    Error = 5,
+    /// The library panicked; this indicates a bug in the library.
+    Panic = 6,
+}
+
+/// An opaque handle to a Walrus client.
+#[derive(Debug)]
+pub struct WalrusClientHandle {
+    runtime: Runtime,
+    client: WalrusClient,
+}
+
+fn set_last_error(error: impl Display) {
+    let message = CString::new(error.to_string().replace('\0', " "))
+        .expect("all NUL bytes have been replaced");
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+fn fail(status: WalrusStatus, error: impl Display) -> WalrusStatus {
+    set_last_error(error);
+    status
+}
+
+fn status_for_error(error: Error) -> WalrusStatus {
+    let status = match error {
+        Error::BlobNotFound => WalrusStatus::BlobNotFound,
+        Error::ReadOnly => WalrusStatus::ReadOnly,
+        _ => WalrusStatus::Error,
+    };
+    fail(status, error)
+}
+
+/// Converts a nullable C string to an optional Rust string.
+///
+/// # Safety
+///
+/// `string` must be null or a valid pointer to a NUL-terminated string.
+unsafe fn optional_str<'a>(string: *const c_char) -> Result<Option<&'a str>, WalrusStatus> {
+    if string.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(string)
+        .to_str()
+        .map(Some)
+        .map_err(|error| fail(WalrusStatus::InvalidArgument, error))
+}
+
+/// Parses a blob ID from a C string.
+///
+/// # Safety
+///
+/// `blob_id` must be null or a valid pointer to a NUL-terminated string.
+unsafe fn parse_blob_id(blob_id: *const c_char) -> Result<BlobId, WalrusStatus> {
+    optional_str(blob_id)?
+        .ok_or_else(|| {
+            fail(
+                WalrusStatus::InvalidArgument,
+                "the blob ID must not be null",
+            )
+        })?
+        .parse()
+        .map_err(|error| fail(WalrusStatus::InvalidArgument, error))
+}
+
+/// Converts a pointer and a length to a slice.
+///
+/// # Safety
+///
+/// If `len > 0`, `data` must be a valid pointer to `len` readable bytes.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], WalrusStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(fail(
+            WalrusStatus::InvalidArgument,
+            "the data must not be null",
+        ))
+    } else {
+        Ok(slice::from_raw_parts(data, len))
+    }
+}
+
+/// Converts the result of an operation to a status.
+fn into_status(result: Result<(), WalrusStatus>) -> WalrusStatus {
+    result.err().unwrap_or(WalrusStatus::Ok)
+}
+
+/// Runs `f`, returning `on_panic` and recording the panic message as the last error if it panics.
+///
+/// Unwinding across the C ABI is undefined behavior, so every exported function runs its body
+/// through this function.
+fn catch_panic<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        set_last_error(format_args!(
+            "the Walrus library panicked: {}",
+            panic_message(payload.as_ref())
+        ));
+        on_panic
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        *message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str()
+    } else {
+        "unknown panic payload"
+    }
+}
+
+/// Returns a description of the last error that occurred on the calling thread, or null if no
+/// error occurred.
+///
+/// The returned string is owned by the library and valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn walrus_last_error() -> *const c_char {
+    catch_panic(ptr::null(), || {
+        LAST_ERROR.with(|last_error| {
+            last_error
+                .borrow()
+                .as_ref()
+                .map_or(ptr::null(), |message| message.as_ptr())
+        })
+    })
+}
+
+/// Creates a new Walrus client.
+///
+/// The configuration is loaded from `config_path`, or from the default configuration paths if
+/// `config_path` is null. `context` optionally selects a context in a multi-context configuration
+/// file. If `read_only` is true, no wallet is required, but blobs cannot be stored.
+///
+/// Returns null on failure.
+///
+/// # Safety
+///
+/// `config_path` and `context` must be null or valid pointers to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_client_new(
+    config_path: *const c_char,
+    context: *const c_char,
+    read_only: bool,
+) -> *mut WalrusClientHandle {
+    catch_panic(ptr::null_mut(), || {
+        client_new(config_path, context, read_only)
+    })
+}
+
+unsafe fn client_new(
+    config_path: *const c_char,
+    context: *const c_char,
+    read_only: bool,
+) -> *mut WalrusClientHandle {
+    let (Ok(config_path), Ok(context)) = (optional_str(config_path), optional_str(context)) else {
+        return ptr::null_mut();
+    };
+    let runtime = match Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            set_last_error(error);
+            return ptr::null_mut();
+        }
+    };
+
+    let mut builder = WalrusClient::builder();
+    if let Some(config_path) = config_path {
+        builder = builder.config_path(PathBuf::from(config_path));
+    }
+    if let Some(context) = context {
+        builder = builder.context(context);
+    }
+    if read_only {
+        builder = builder.read_only();
+    }
+    match runtime.block_on(builder.build()) {
+        Ok(client) => Box::into_raw(Box::new(WalrusClientHandle { runtime, client })),
+        Err(error) => {
+            set_last_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Destroys a client created with [`walrus_client_new`].
+///
+/// # Safety
+///
+/// `client` must be null or a handle returned by [`walrus_client_new`] that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_client_free(client: *mut WalrusClientHandle) {
+    catch_panic((), || {
+        if !client.is_null() {
+            drop(Box::from_raw(client));
+        }
+    })
+}
+
+/// Stores `len` bytes at `data` on Walrus for `epochs` epochs.
+///
+/// On success, writes the blob ID to `blob_id_out`; it must be freed with
+/// [`walrus_string_free`].
+///
+/// # Safety
+///
+/// `client` must be a valid handle, `data` must point to `len` readable bytes, and `blob_id_out`
+/// must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_store(
+    client: *const WalrusClientHandle,
+    data: *const u8,
+    len: usize,
+    epochs: u32,
+    deletable: bool,
+    blob_id_out: *mut *mut c_char,
+) -> WalrusStatus {
+    catch_panic(WalrusStatus::Panic, || {
+        into_status(store(client, data, len, epochs, deletable, blob_id_out))
+    })
+}
+
+unsafe fn store(
+    client: *const WalrusClientHandle,
+    data: *const u8,
+    len: usize,
+    epochs: u32,
+    deletable: bool,
+    blob_id_out: *mut *mut c_char,
+) -> Result<(), WalrusStatus> {
+    let (Some(handle), false) = (client.as_ref(), blob_id_out.is_null()) else {
+        return Err(fail(
+            WalrusStatus::InvalidArgument,
+            "the client and the output pointer must not be null",
+        ));
+    };
+    let blob = bytes(data, len)?;
+    let options = StoreOptions {
+        epochs,
+        persistence: BlobPersistence::from_deletable(deletable),
+        ..StoreOptions::default()
+    };
+    let result = handle
+        .runtime
+        .block_on(handle.client.store(blob, &options))
+        .map_err(status_for_error)?;
+    let blob_id =
+        CString::new(result.blob_id().to_string()).expect("the blob ID does not contain NUL bytes");
+    *blob_id_out = blob_id.into_raw();
+    Ok(())
+}
+
+/// Reads the blob with the given ID.
+///
+/// On success, writes a pointer to the blob to `data_out` and its length to `len_out`; the blob
+/// must be freed with [`walrus_bytes_free`].
+///
+/// # Safety
+///
+/// `client` must be a valid handle, `blob_id` a NUL-terminated string, and `data_out` and
+/// `len_out` valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_read(
+    client: *const WalrusClientHandle,
+    blob_id: *const c_char,
+    data_out: *mut *mut u8,
+    len_out: *mut usize,
+) -> WalrusStatus {
+    catch_panic(WalrusStatus::Panic, || {
+        into_status(read(client, blob_id, data_out, len_out))
+    })
+}
+
+unsafe fn read(
+    client: *const WalrusClientHandle,
+    blob_id: *const c_char,
+    data_out: *mut *mut u8,
+    len_out: *mut usize,
+) -> Result<(), WalrusStatus> {
+    let (Some(handle), false, false) = (client.as_ref(), data_out.is_null(), len_out.is_null())
+    else {
+        return Err(fail(
+            WalrusStatus::InvalidArgument,
+            "the client and the output pointers must not be null",
+        ));
+    };
+    let blob_id = parse_blob_id(blob_id)?;
+    let blob = handle
+        .runtime
+        .block_on(handle.client.read(&blob_id))
+        .map_err(status_for_error)?;
+    *len_out = blob.len();
+    *data_out = Box::into_raw(blob.into_boxed_slice()).cast();
+    Ok(())
+}
+
+/// Verifies that the `len` bytes at `data` correspond to the blob ID.
+///
+/// Returns [`WalrusStatus::Ok`] if the data matches, and [`WalrusStatus::Mismatch`] otherwise.
+///
+/// # Safety
+///
+/// `client` must be a valid handle, `blob_id` a NUL-terminated string, and `data` must point to
+/// `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_verify(
+    client: *const WalrusClientHandle,
+    blob_id: *const c_char,
+    data: *const u8,
+    len: usize,
+) -> WalrusStatus {
+    catch_panic(WalrusStatus::Panic, || {
+        into_status(verify(client, blob_id, data, len))
+    })
+}
+
+unsafe fn verify(
+    client: *const WalrusClientHandle,
+    blob_id: *const c_char,
+    data: *const u8,
+    len: usize,
+) -> Result<(), WalrusStatus> {
+    let Some(handle) = client.as_ref() else {
+        return Err(fail(
+            WalrusStatus::InvalidArgument,
+            "the client must not be null",
+        ));
+    };
+    let blob_id = parse_blob_id(blob_id)?;
+    let blob = bytes(data, len)?;
+    let computed = handle
+        .client
+        .compute_blob_id(blob, StoreOptions::default().encoding_type)
+        .map_err(status_for_error)?;
+    if computed == blob_id {
+        Ok(())
+    } else {
+        Err(fail(
+            WalrusStatus::Mismatch,
+            format!("the data has blob ID {computed}, expected {blob_id}"),
+        ))
+    }
+}
+
+/// Frees a string returned by the library.
+///
+/// # Safety
+///
+/// `string` must be null or a string returned by the library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_string_free(string: *mut c_char) {
+    catch_panic((), || {
+        if !string.is_null() {
+            drop(CString::from_raw(string));
+        }
+    })
+}
+
+/// Frees a byte buffer returned by the library.
+///
+/// # Safety
+///
+/// `data` must be null or a buffer of length `len` returned by the library that has not been
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn walrus_bytes_free(data: *mut u8, len: usize) {
+    catch_panic((), || {
+        if !data.is_null() {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(data, len)));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_arguments_are_reported() {
+        let status =
+            unsafe { walrus_read(ptr::null(), ptr::null(), ptr::null_mut(), ptr::null_mut()) };
+        assert_eq!(status, WalrusStatus::InvalidArgument);
+        let message = unsafe { CStr::from_ptr(walrus_last_error()) };
+        assert!(message.to_str().unwrap().contains("must not be null"));
+    }
+
+    #[test]
+    fn panics_are_reported_as_status() {
+        let status = catch_panic(WalrusStatus::Panic, || -> WalrusStatus {
+            panic!("unexpected state {}", 42)
+        });
+        assert_eq!(status, WalrusStatus::Panic);
+        let message = unsafe { CStr::from_ptr(walrus_last_error()) };
+        assert!(message.to_str().unwrap().contains("unexpected state 42"));
+    }
+}
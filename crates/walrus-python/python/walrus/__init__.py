@@ -0,0 +1,32 @@
+# Copyright (c) Walrus Foundation
+# SPDX-License-Identifier: Apache-2.0
+
+"""Store and read blobs on Walrus.
+
+Example:
+
+    import asyncio
+    import walrus
+
+    async def main():
+        client = await walrus.WalrusClient.connect()
+        result = await client.store(b"hello walrus", epochs=2)
+        blob_id = result["newlyCreated"]["blobObject"]["blobId"]
+        assert await client.read(blob_id) == b"hello walrus"
+
+    asyncio.run(main())
+"""
+
+from ._walrus import (
+    BlobNotFoundError,
+    ReadOnlyError,
+    WalrusClient,
+    WalrusError,
+)
+
+__all__ = [
+    "BlobNotFoundError",
+    "ReadOnlyError",
+    "WalrusClient",
+    "WalrusError",
+]
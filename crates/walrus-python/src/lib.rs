@@ -0,0 +1,238 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Python bindings for the [`WalrusClient`].
+//!
+//! All operations that interact with Walrus return Python awaitables, which are executed on a
+//! tokio runtime managed by the extension module. Structured results (store results and blob
+//! statuses) are returned as dictionaries with the same layout as the JSON output of the CLI.
+
+use std::{path::PathBuf, sync::Arc};
+
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use pyo3_async_runtimes::tokio::future_into_py;
+use walrus_client::{
+    BlobId,
+    BlobPersistence,
+    Error,
+    PostStoreAction,
+    StoreOptions,
+    StoreWhen,
+    WalrusClient,
+};
+
+create_exception!(
+    walrus,
+    WalrusError,
+    PyException,
+    "An error returned by the Walrus client."
+);
+create_exception!(
+    walrus,
+    BlobNotFoundError,
+    WalrusError,
+    "The blob does not exist on Walrus."
+);
+create_exception!(
+    walrus,
+    ReadOnlyError,
+    WalrusError,
+    "The operation requires a wallet, but the client is read-only."
+);
+
+fn to_py_err(error: Error) -> PyErr {
+    match error {
+        Error::BlobNotFound => BlobNotFoundError::new_err(error.to_string()),
+        Error::ReadOnly => ReadOnlyError::new_err(error.to_string()),
+        _ => WalrusError::new_err(error.to_string()),
+    }
+}
+
+fn parse_blob_id(blob_id: &str) -> PyResult<BlobId> {
+    blob_id
+        .parse()
+        .map_err(|error| PyValueError::new_err(format!("invalid blob ID '{blob_id}': {error}")))
+}
+
+fn store_options(epochs: u32, deletable: bool, force: bool) -> StoreOptions {
+    StoreOptions {
+        epochs,
+        persistence: BlobPersistence::from_deletable(deletable),
+        store_when: StoreWhen::from_flags(force, false),
+        post_store: PostStoreAction::Keep,
+        ..StoreOptions::default()
+    }
+}
+
+/// Converts a serializable result to the corresponding Python object.
+fn to_py_object<T: serde::Serialize>(value: &T) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        pythonize::pythonize(py, value)
+            .map(Bound::unbind)
+            .map_err(|error| WalrusError::new_err(error.to_string()))
+    })
+}
+
+/// A client to store and read blobs on Walrus.
+#[pyclass(name = "WalrusClient", frozen)]
+struct PyWalrusClient {
+    inner: Arc<WalrusClient>,
+}
+
+#[pymethods]
+impl PyWalrusClient {
+    /// Connects to Walrus.
+    ///
+    /// The Walrus configuration is loaded from `config_path`, or from the default configuration
+    /// paths if unset. If `read_only` is true, no wallet is required, and the client can only
+    /// read blobs, their status, and cost estimates.
+    #[staticmethod]
+    #[pyo3(signature = (config_path=None, context=None, read_only=false, rpc_url=None))]
+    fn connect(
+        py: Python<'_>,
+        config_path: Option<PathBuf>,
+        context: Option<String>,
+        read_only: bool,
+        rpc_url: Option<String>,
+    ) -> PyResult<Bound<'_, PyAny>> {
+        let mut builder = WalrusClient::builder();
+        if let Some(config_path) = config_path {
+            builder = builder.config_path(config_path);
+        }
+        if let Some(context) = context {
+            builder = builder.context(context);
+        }
+        if let Some(rpc_url) = rpc_url {
+            builder = builder.rpc_url(rpc_url);
+        }
+        if read_only {
+            builder = builder.read_only();
+        }
+        future_into_py(py, async move {
+            let client = builder.build().await.map_err(to_py_err)?;
+            Ok(Self {
+                inner: Arc::new(client),
+            })
+        })
+    }
+
+    /// Whether the client was connected without a wallet.
+    #[getter]
+    fn read_only(&self) -> bool {
+        self.inner.is_read_only()
+    }
+
+    /// Stores `data` on Walrus for `epochs` epochs and returns the store result.
+    #[pyo3(signature = (data, epochs=1, deletable=false, force=false))]
+    fn store<'py>(
+        &self,
+        py: Python<'py>,
+        data: Vec<u8>,
+        epochs: u32,
+        deletable: bool,
+        force: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        let options = store_options(epochs, deletable, force);
+        future_into_py(py, async move {
+            let result = client.store(&data, &options).await.map_err(to_py_err)?;
+            to_py_object(&result)
+        })
+    }
+
+    /// Stores the file at `path` on Walrus for `epochs` epochs and returns the store result.
+    #[pyo3(signature = (path, epochs=1, deletable=false, force=false))]
+    fn store_file<'py>(
+        &self,
+        py: Python<'py>,
+        path: PathBuf,
+        epochs: u32,
+        deletable: bool,
+        force: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        let options = store_options(epochs, deletable, force);
+        future_into_py(py, async move {
+            let result = client.store_path(path, &options).await.map_err(to_py_err)?;
+            to_py_object(&result)
+        })
+    }
+
+    /// Reads the blob with the given ID and returns its contents as `bytes`.
+    fn read<'py>(&self, py: Python<'py>, blob_id: &str) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        let blob_id = parse_blob_id(blob_id)?;
+        future_into_py(py, async move {
+            let blob = client.read(&blob_id).await.map_err(to_py_err)?;
+            Ok(Python::with_gil(|py| PyBytes::new(py, &blob).unbind()))
+        })
+    }
+
+    /// Returns the status of the blob with the given ID.
+    fn status<'py>(&self, py: Python<'py>, blob_id: &str) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        let blob_id = parse_blob_id(blob_id)?;
+        future_into_py(py, async move {
+            let status = client.status(&blob_id).await.map_err(to_py_err)?;
+            to_py_object(&status)
+        })
+    }
+
+    /// Estimates the cost, in FROST and excluding gas, of storing `size` bytes for `epochs` epochs.
+    #[pyo3(signature = (size, epochs=1))]
+    fn estimate_cost<'py>(
+        &self,
+        py: Python<'py>,
+        size: u64,
+        epochs: u32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = self.inner.clone();
+        let encoding_type = StoreOptions::default().encoding_type;
+        future_into_py(py, async move {
+            client
+                .estimate_cost(size, epochs, encoding_type)
+                .await
+                .map_err(to_py_err)
+        })
+    }
+}
+
+/// The native module of the `walrus` Python package.
+#[pymodule]
+fn _walrus(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyWalrusClient>()?;
+    m.add("WalrusError", m.py().get_type::<WalrusError>())?;
+    m.add("BlobNotFoundError", m.py().get_type::<BlobNotFoundError>())?;
+    m.add("ReadOnlyError", m.py().get_type::<ReadOnlyError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_options_reflect_flags() {
+        let options = store_options(5, true, false);
+        assert_eq!(options.epochs, 5);
+        assert_eq!(options.persistence, BlobPersistence::Deletable);
+        assert!(matches!(options.store_when, StoreWhen::NotStored));
+
+        let options = store_options(1, false, true);
+        assert_eq!(options.persistence, BlobPersistence::Permanent);
+        assert!(matches!(options.store_when, StoreWhen::Always));
+        assert!(matches!(options.post_store, PostStoreAction::Keep));
+    }
+
+    #[test]
+    fn parses_blob_ids() {
+        let blob_id = "4BKcDC0Ih5RJ8R0tFMz3MZVNZV8b2goT6_JiEEwNHQo";
+        assert_eq!(parse_blob_id(blob_id).unwrap().to_string(), blob_id);
+        assert!(parse_blob_id("not a blob ID").is_err());
+    }
+}
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use sui_types::event::EventID;
 use tokio::time::Duration;
 use utoipa::openapi::Ref;
-use walrus_core::{Epoch, PublicKey, ShardIndex};
+use walrus_core::{Epoch, NetworkPublicKey, PublicKey, ShardIndex};
 
 use self::errors::Status;
 
@@ -215,6 +215,30 @@ pub enum StoredOnNodeStatus {
     Stored,
 }
 
+/// The storage status of the metadata and slivers of a blob on a storage node.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Default, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobStorageStatus {
+    /// Whether the metadata of the blob is stored on the node.
+    pub metadata: StoredOnNodeStatus,
+    /// Whether the slivers of the blob are stored, for each shard owned by the node in the current
+    /// epoch.
+    pub shards: Vec<ShardBlobStorageStatus>,
+}
+
+/// The storage status of the slivers of a blob in a shard.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardBlobStorageStatus {
+    /// The index of the shard.
+    #[schema(value_type = u16)]
+    pub shard: ShardIndex,
+    /// Whether the primary sliver of the blob is stored in the shard.
+    pub primary_sliver: StoredOnNodeStatus,
+    /// Whether the secondary sliver of the blob is stored in the shard.
+    pub secondary_sliver: StoredOnNodeStatus,
+}
+
 /// Represents information about the health of the storage node service.
 #[derive(Debug, Deserialize, Serialize, Clone, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -228,6 +252,10 @@ pub struct ServiceHealthInfo {
     /// The public key of the storage node.
     #[schema(value_type = [u8], format = "Base58")]
     pub public_key: PublicKey,
+    /// The network public key authenticating the transport endpoint of the storage node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub network_public_key: Option<NetworkPublicKey>,
     /// The status of the storage node.
     pub node_status: String,
     /// The event progress of the storage node.
@@ -15,6 +15,8 @@
 //!    "code": 400,
 //!    "message": "shard 0 is not assigned to this node in epoch 17",
 //!    "status": "FAILED_PRECONDITION",
+//!    "retryable": false,
+//!    "requestId": "7f3a0c9e2b8d4e61a5c0f1d2e3b4a596",
 //!    "details": [
 //!      {
 //!        "@type": "ErrorInfo",
@@ -31,13 +33,19 @@
 //!
 //! - `message`, a detailed message about the failure that occurred;
 //! - `status`, one of a small set of general error types (see [`StatusCode`]);
-//! - `code`, the HTTP error code associated with `status`; and
+//! - `code`, the HTTP error code associated with `status`;
+//! - `retryable`, whether the same request may succeed if retried later;
+//! - `requestId`, the ID of the failed request, if assigned by the service, which can be used to
+//!   correlate the failure with the logs of the services involved in handling the request; and
 //! - `details`, a list of additional machine-readable error details.
 //!
 //! The `details` field will always contain an object of type `ErrorInfo`. Its fields
 //! (`domain`, `reason`) provide a machine-readable way of identifying the specific error returned
 //! by the API.  Additionally, its `metadata` field can be used to retrieve arguments specific to
 //! the error.
+//!
+//! Clients should branch on the `status`, the `retryable` flag, and the (`domain`, `reason`) pair,
+//! which are stable, rather than on the `message`, which may change between versions.
 
 use core::mem;
 use std::{
@@ -75,6 +83,8 @@ impl Status {
             inner: StatusInner {
                 status_code: code,
                 message,
+                retryable: code.is_retryable(),
+                request_id: None,
                 details: vec![info.into()],
             },
         }
@@ -85,6 +95,28 @@ impl Status {
         self.inner.status_code
     }
 
+    /// Returns true if the same request may succeed if it is retried later.
+    ///
+    /// Defaults to [`StatusCode::is_retryable`] for the status code of the error.
+    pub fn is_retryable(&self) -> bool {
+        self.inner.retryable
+    }
+
+    /// Sets whether the same request may succeed if it is retried later.
+    pub fn set_retryable(&mut self, retryable: bool) {
+        self.inner.retryable = retryable;
+    }
+
+    /// Returns the ID of the failed request, if it was assigned one by the service.
+    pub fn request_id(&self) -> Option<&str> {
+        self.inner.request_id.as_deref()
+    }
+
+    /// Sets the ID of the failed request.
+    pub fn set_request_id(&mut self, request_id: Option<String>) {
+        self.inner.request_id = request_id;
+    }
+
     /// The status message, a developer-facing, human-readable "debug message".
     pub fn message(&self) -> &str {
         &self.inner.message
@@ -173,6 +205,15 @@ struct StatusInner {
     // actionable resolution to it.
     message: String,
 
+    /// Whether the same request may succeed if it is retried later, possibly after a backoff.
+    // Defaults to false for responses of services that predate the field.
+    #[serde(default)]
+    retryable: bool,
+
+    /// The ID of the failed request, which can be used to correlate the failure across services.
+    #[serde(default, rename = "requestId", skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+
     /// Machine readable details of the error.
     ///
     /// Always contains an [`ErrorInfo`], which provides a machine-readable
@@ -373,6 +414,17 @@ status_codes![
     (Unavailable, "UNAVAILABLE", HttpStatusCode::SERVICE_UNAVAILABLE),
 ];
 
+impl StatusCode {
+    /// Returns true if errors with this status code are generally transient, such that the same
+    /// request may succeed if retried later.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable | Self::DeadlineExceeded | Self::ResourceExhausted
+        )
+    }
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, ToSchema)]
 struct StatusCodeFields<'a> {
@@ -436,6 +488,7 @@ mod tests {
                 "code": 400,
                 "status": "FAILED_PRECONDITION",
                 "message": message,
+                "retryable": false,
                 "details": [
                 {
                     "@type": "ErrorInfo",
@@ -466,4 +519,27 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn deserialize_without_retryable_flag() -> TestResult {
+        let (status, mut json) = example_response();
+        json["error"]
+            .as_object_mut()
+            .expect("the error is an object")
+            .remove("retryable");
+
+        assert_eq!(serde_json::from_value::<Status>(json)?, status);
+
+        Ok(())
+    }
+
+    #[test]
+    fn retryable_flag_follows_status_code() {
+        let info = ErrorInfo::new("OVERLOADED".to_owned(), "test".to_owned());
+        let mut status = Status::new(StatusCode::Unavailable, "overloaded".to_owned(), info);
+        assert!(status.is_retryable());
+
+        status.set_retryable(false);
+        assert!(!status.is_retryable());
+    }
 }
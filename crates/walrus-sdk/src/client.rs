@@ -30,6 +30,7 @@ use walrus_core::{
         BlobPersistenceType,
         InvalidBlobIdAttestation,
         SignedStorageConfirmation,
+        SignedSyncShardRequest,
         StorageConfirmation,
         SyncShardMsg,
         SyncShardRequest,
@@ -50,7 +51,7 @@ use walrus_core::{
 };
 
 use crate::{
-    api::{BlobStatus, ServiceHealthInfo, StoredOnNodeStatus},
+    api::{BlobStatus, BlobStorageStatus, ServiceHealthInfo, StoredOnNodeStatus},
     error::{ClientBuildError, ListAndVerifyRecoverySymbolsError, NodeError},
     node_response::NodeResponse,
 };
@@ -74,8 +75,10 @@ const RECOVERY_SYMBOL_URL_TEMPLATE: &str = "/v1/blobs/:blob_id/recoverySymbols/:
 const LIST_RECOVERY_SYMBOLS_URL_TEMPLATE: &str = "/v1/blobs/:blob_id/recoverySymbols";
 const INCONSISTENCY_PROOF_URL_TEMPLATE: &str = "/v1/blobs/:blob_id/inconsistencyProof/:sliver_type";
 const BLOB_STATUS_URL_TEMPLATE: &str = "/v1/blobs/:blob_id/status";
+const BLOB_STORAGE_STATUS_URL_TEMPLATE: &str = "/v1/blobs/:blob_id/storage-status";
 const HEALTH_URL_TEMPLATE: &str = "/v1/health";
 const SYNC_SHARD_TEMPLATE: &str = "/v1/migrate/sync_shard";
+const PUSH_METADATA_TEMPLATE: &str = "/v1/migrate/metadata";
 
 #[derive(Debug, Clone)]
 struct UrlEndpoints(Url);
@@ -144,6 +147,13 @@ impl UrlEndpoints {
         )
     }
 
+    fn blob_storage_status(&self, blob_id: &BlobId) -> (Url, &'static str) {
+        (
+            self.blob_resource(blob_id, "storage-status"),
+            BLOB_STORAGE_STATUS_URL_TEMPLATE,
+        )
+    }
+
     fn sliver_path<A: EncodingAxis>(
         &self,
         blob_id: &BlobId,
@@ -232,6 +242,15 @@ impl UrlEndpoints {
             SYNC_SHARD_TEMPLATE,
         )
     }
+
+    fn push_metadata(&self) -> (Url, &'static str) {
+        (
+            self.0
+                .join("/v1/migrate/metadata")
+                .expect("this is a valid URL"),
+            PUSH_METADATA_TEMPLATE,
+        )
+    }
 }
 
 /// Filter for [`Client::list_recovery_symbols()`] endpoint.
@@ -347,6 +366,9 @@ pub struct Client {
     /// This is needed, because the reqwest builder wants the client for the ergonmics of being
     /// able to send the request directly from the builder.
     client_clone: ReqwestClient,
+
+    /// The maximum size of a response body that is read from the node, if any.
+    max_response_size: Option<u64>,
 }
 
 impl Client {
@@ -409,7 +431,7 @@ impl Client {
         self.get_metadata(blob_id)
             .await?
             .verify(encoding_config)
-            .map_err(NodeError::other)
+            .map_err(NodeError::verification)
     }
 
     /// Requests the status of a blob ID from the node.
@@ -420,6 +442,18 @@ impl Client {
             .await
     }
 
+    /// Requests from the node whether the metadata and the slivers of the blob are stored, for
+    /// each shard owned by the node.
+    #[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+    pub async fn get_blob_storage_status(
+        &self,
+        blob_id: &BlobId,
+    ) -> Result<BlobStorageStatus, NodeError> {
+        let (url, template) = self.endpoints.blob_storage_status(blob_id);
+        self.send_and_parse_service_response(Request::new(Method::GET, url), template)
+            .await
+    }
+
     /// Requests a storage confirmation from the node for the Blob specified by the given ID
     #[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
     pub async fn get_confirmation(
@@ -457,7 +491,7 @@ impl Client {
             .await?;
         let _ = confirmation
             .verify(public_key, epoch, *blob_id, blob_persistence_type)
-            .map_err(NodeError::other)?;
+            .map_err(NodeError::verification)?;
         Ok(confirmation)
     }
 
@@ -555,7 +589,7 @@ impl Client {
 
         sliver
             .verify(encoding_config, metadata.metadata())
-            .map_err(NodeError::other)?;
+            .map_err(NodeError::verification)?;
 
         Ok(sliver)
     }
@@ -671,7 +705,7 @@ impl Client {
                     target_type,
                 ) {
                     tracing::warn!(?error, "recovery symbol verification failed");
-                    final_error = NodeError::other(error);
+                    final_error = NodeError::verification(error);
                     return false;
                 }
 
@@ -722,7 +756,7 @@ impl Client {
                 encoding_config,
                 local_sliver_pair.to_sliver_index::<A>(encoding_config.n_shards()),
             )
-            .map_err(NodeError::other)?;
+            .map_err(NodeError::verification)?;
 
         Ok(symbol)
     }
@@ -836,7 +870,7 @@ impl Client {
             .await?;
         let _ = attestation
             .verify(public_key, epoch, blob_id)
-            .map_err(NodeError::other)?;
+            .map_err(NodeError::verification)?;
         Ok(attestation)
     }
 
@@ -870,7 +904,6 @@ impl Client {
         epoch: Epoch,
         key_pair: &ProtocolKeyPair,
     ) -> Result<SyncShardResponse, NodeError> {
-        let (url, template) = self.endpoints.sync_shard();
         let request = SyncShardRequest::new(
             shard_index,
             A::sliver_type(),
@@ -881,16 +914,48 @@ impl Client {
 
         let sync_shard_msg = SyncShardMsg::new(epoch, request);
         let signed_request = key_pair.sign_message(&sync_shard_msg);
+        self.sync_shard_with_signed_request(&signed_request, key_pair.as_ref().public())
+            .await
+    }
+
+    /// Syncs a shard from the storage node with a request that has already been signed.
+    ///
+    /// This allows the request to be signed outside of the client, for example, by a remote
+    /// signing service. The `public_key` must be the one of the key pair that signed the request.
+    #[tracing::instrument(skip_all, err(level = Level::DEBUG))]
+    pub async fn sync_shard_with_signed_request(
+        &self,
+        signed_request: &SignedSyncShardRequest,
+        public_key: &PublicKey,
+    ) -> Result<SyncShardResponse, NodeError> {
+        let (url, template) = self.endpoints.sync_shard();
         let http_request = self.create_request_with_payload_and_public_key(
             Method::POST,
             url,
-            &signed_request,
-            key_pair.as_ref().public(),
+            signed_request,
+            public_key,
         )?;
         self.send_and_parse_bcs_response(http_request, template)
             .await
     }
 
+    /// Pushes the metadata of multiple blobs to the node.
+    ///
+    /// This is used by storage nodes to seed the metadata of nodes that obtain new shards during
+    /// epoch change. Returns the number of metadata newly stored by the node.
+    #[tracing::instrument(
+        skip_all, fields(walrus.metadata_count = metadata.len()), err(level = Level::DEBUG)
+    )]
+    pub async fn push_metadata(
+        &self,
+        metadata: &[VerifiedBlobMetadataWithId],
+    ) -> Result<u64, NodeError> {
+        let (url, template) = self.endpoints.push_metadata();
+        let request = self.create_request_with_payload(Method::PUT, url, &metadata);
+        self.send_and_parse_service_response(request, template)
+            .await
+    }
+
     /// Send a request with tracing and context propagation.
     ///
     /// The HTTP span ends after the parsing of the headers, since the response may be streamed.
@@ -906,7 +971,11 @@ impl Client {
             .await;
 
         match output {
-            Ok(response) => response.response_error_for_status().await,
+            Ok(response) => {
+                response
+                    .response_error_for_status(self.max_response_size)
+                    .await
+            }
             Err(err) => Err(NodeError::reqwest(err)),
         }
     }
@@ -917,7 +986,7 @@ impl Client {
         url_template: &'static str,
     ) -> Result<T, NodeError> {
         self.send_request(request, url_template)
-            .and_then(|response| response.bcs())
+            .and_then(|response| response.bcs(self.max_response_size))
             .inspect_err(|error| tracing::trace!(?error))
             .await
     }
@@ -928,7 +997,7 @@ impl Client {
         url_template: &'static str,
     ) -> Result<T, NodeError> {
         self.send_request(request, url_template)
-            .and_then(|response| response.service_response())
+            .and_then(|response| response.service_response(self.max_response_size))
             .inspect_err(|error| tracing::trace!(?error))
             .await
     }
@@ -979,6 +1048,7 @@ mod tests {
         test_blob_url_endpoint: [
             blob: (|e| e.blob_resource(&BLOB_ID, ""), ""),
             metadata: (|e| e.metadata(&BLOB_ID).0, "metadata"),
+            storage_status: (|e| e.blob_storage_status(&BLOB_ID).0, "storage-status"),
             permanent_confirmation: (
                 |e| e.confirmation(&BLOB_ID, &BlobPersistenceType::Permanent).0,
                 "confirmation/permanent"
@@ -26,6 +26,7 @@ pub struct ClientBuilder {
     no_built_in_root_certs: bool,
     connect_timeout: Option<Duration>,
     registry: Option<Registry>,
+    max_response_size: Option<u64>,
 }
 
 impl ClientBuilder {
@@ -112,6 +113,15 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the maximum size in bytes of a response body read from the server.
+    ///
+    /// Responses with larger bodies are rejected with an error as soon as the limit is exceeded,
+    /// without reading the remainder of the body. By default, the size is not limited.
+    pub fn max_response_size(mut self, max_size: u64) -> Self {
+        self.max_response_size = Some(max_size);
+        self
+    }
+
     /// Registers metrics the provided registry. Defaults to the globabl default registry.
     pub fn metric_registry(mut self, registry: Registry) -> Self {
         self.registry = Some(registry);
@@ -195,6 +205,7 @@ impl ClientBuilder {
             client_clone: inner.clone(),
             inner: HttpMiddleware::new(inner, HttpClientMetrics::new(registry)),
             endpoints,
+            max_response_size: self.max_response_size,
         })
     }
 }
@@ -34,6 +34,7 @@ use walrus_utils::{
 };
 
 use self::helpers::ErrorType;
+use crate::request_id::{self, REQUEST_ID_HEADER};
 
 pub(crate) type RequestWithUrlTemplate = (Request, UrlTemplate);
 pub(crate) struct UrlTemplate(pub &'static str); // Helps with incorrect lifetime inferences.
@@ -248,9 +249,11 @@ impl HttpLabels {
 ///
 /// - instruments the returned future and response to track the metrics present
 ///   in [`HttpClientMetrics`],
-/// - creates an HTTP span around the request identifying the remote server, status code, etc; and
+/// - creates an HTTP span around the request identifying the remote server, status code, etc;
 /// - propagates the trace ID of the created span to the server, so that their traces can be
-///   stitched together.
+///   stitched together; and
+/// - sends the ID of the request being handled by the current task, if any, such that the request
+///   can be correlated with the request that caused it.
 ///
 #[derive(Debug, Clone)]
 pub(crate) struct HttpMiddleware<S> {
@@ -277,6 +280,18 @@ impl<S> HttpMiddleware<S> {
             );
         });
     }
+
+    fn propagate_request_id(request: &mut Request) {
+        let Some(request_id) = request_id::current() else {
+            return;
+        };
+        if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+            request
+                .headers_mut()
+                .entry(REQUEST_ID_HEADER)
+                .or_insert(value);
+        }
+    }
 }
 
 impl<S> Service<RequestWithUrlTemplate> for HttpMiddleware<S>
@@ -296,6 +311,7 @@ where
         let http_span = monitor.http_span().expect("span was just created").clone();
 
         Self::propagate_trace(&mut request, &http_span);
+        Self::propagate_request_id(&mut request);
 
         HttpMiddlewareFuture::new(self.inner.call(request), monitor).instrument(http_span)
     }
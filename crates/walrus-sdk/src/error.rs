@@ -96,6 +96,27 @@ impl NodeError {
         Some(StatusCode::INTERNAL_SERVER_ERROR) == self.http_status_code()
     }
 
+    /// Returns true if the response was rejected for exceeding the maximum response size.
+    pub fn is_response_too_large(&self) -> bool {
+        matches!(self.kind, Kind::ResponseTooLarge { .. })
+    }
+
+    /// Returns true if the node responded, but the response failed verification.
+    ///
+    /// Such errors indicate that the node is faulty or malicious, rather than that it is
+    /// unreachable or lacks the requested data.
+    pub fn is_verification_error(&self) -> bool {
+        matches!(self.kind, Kind::Verification(_))
+    }
+
+    /// Wrap an error encountered when verifying a node's response as a Node error.
+    pub fn verification<E>(err: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Kind::Verification(err.into()).into()
+    }
+
     /// Wrap a standard error as a Node error.
     pub fn other<E>(err: E) -> Self
     where
@@ -136,6 +157,10 @@ impl NodeError {
 pub(crate) enum Kind {
     #[error("failed to decode the response body as BCS")]
     Bcs(#[from] bcs::Error),
+    #[error("failed to decode the response body as JSON")]
+    Json(#[from] serde_json::Error),
+    #[error("the response body exceeds the maximum size of {limit} bytes")]
+    ResponseTooLarge { limit: u64 },
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
     #[error("{inner}: {status}")]
@@ -147,6 +172,8 @@ pub(crate) enum Kind {
     ErrorInNonErrorMessage(Status),
     #[error("invalid content type in response")]
     InvalidContentType,
+    #[error("the response failed verification: {0}")]
+    Verification(Box<dyn std::error::Error + Send + Sync>),
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
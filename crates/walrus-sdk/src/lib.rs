@@ -11,6 +11,7 @@ use walrus_core::NetworkPublicKey;
 pub mod api;
 pub mod client;
 pub mod error;
+pub mod request_id;
 
 mod node_response;
 mod tls;
@@ -1,6 +1,7 @@
 // Copyright (c) Walrus Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use bytes::{Bytes, BytesMut};
 use reqwest::{
     header::{self, HeaderMap},
     Response,
@@ -16,17 +17,29 @@ use crate::{
 pub(crate) trait NodeResponse: Sized {
     /// Converts the response to an error if the status is a client or service error code,
     /// with any server-provided error message.
-    async fn response_error_for_status(self) -> Result<Self, NodeError>;
+    ///
+    /// Error bodies larger than `max_size` bytes are not parsed.
+    async fn response_error_for_status(self, max_size: Option<u64>) -> Result<Self, NodeError>;
 
     /// Decode the body of the response using BCS encoding.
-    async fn bcs<T: DeserializeOwned>(self) -> Result<T, NodeError>;
+    ///
+    /// Fails without reading the entire body if it is larger than `max_size` bytes.
+    async fn bcs<T: DeserializeOwned>(self, max_size: Option<u64>) -> Result<T, NodeError>;
 
     /// Decode the body of the response as a [`ServiceResponse`].
-    async fn service_response<T: DeserializeOwned>(self) -> Result<T, NodeError>;
+    ///
+    /// Fails without reading the entire body if it is larger than `max_size` bytes.
+    async fn service_response<T: DeserializeOwned>(
+        self,
+        max_size: Option<u64>,
+    ) -> Result<T, NodeError>;
+
+    /// Reads the body of the response, failing as soon as it exceeds `max_size` bytes.
+    async fn bytes_with_limit(self, max_size: Option<u64>) -> Result<Bytes, NodeError>;
 }
 
 impl NodeResponse for Response {
-    async fn response_error_for_status(self) -> Result<Self, NodeError> {
+    async fn response_error_for_status(self, max_size: Option<u64>) -> Result<Self, NodeError> {
         let Err(inner) = self.error_for_status_ref() else {
             return Ok(self);
         };
@@ -34,8 +47,15 @@ impl NodeResponse for Response {
             return Err(Kind::Reqwest(inner).into());
         }
 
-        match self.json().await {
-            Ok(ServiceResponse::<()>::Error(status)) => Err(Kind::Status { inner, status }.into()),
+        let parsed = self
+            .bytes_with_limit(max_size)
+            .await
+            .ok()
+            .and_then(|body| serde_json::from_slice(&body).ok());
+        match parsed {
+            Some(ServiceResponse::<()>::Error(status)) => {
+                Err(Kind::Status { inner, status }.into())
+            }
             _ => {
                 tracing::debug!("unable to parse the service's JSON response");
                 Err(Kind::Reqwest(inner).into())
@@ -43,34 +63,57 @@ impl NodeResponse for Response {
         }
     }
 
-    async fn bcs<T: DeserializeOwned>(self) -> Result<T, NodeError> {
+    async fn bcs<T: DeserializeOwned>(self, max_size: Option<u64>) -> Result<T, NodeError> {
         ensure!(
             is_content_type_octet_stream(self.headers()),
             NodeError::from(Kind::InvalidContentType)
         );
 
-        let body = self.bytes().await.map_err(Kind::Reqwest)?;
+        let body = self.bytes_with_limit(max_size).await?;
 
         Ok(bcs::from_bytes(&body).map_err(Kind::Bcs)?)
     }
 
-    async fn service_response<T: DeserializeOwned>(self) -> Result<T, NodeError> {
-        let non_error_response = self.response_error_for_status().await?;
+    async fn service_response<T: DeserializeOwned>(
+        self,
+        max_size: Option<u64>,
+    ) -> Result<T, NodeError> {
+        let non_error_response = self.response_error_for_status(max_size).await?;
 
         ensure!(
             is_content_type_json(non_error_response.headers()),
             NodeError::from(Kind::InvalidContentType)
         );
 
-        match non_error_response
-            .json::<ServiceResponse<T>>()
-            .await
-            .map_err(Kind::Reqwest)?
-        {
+        let body = non_error_response.bytes_with_limit(max_size).await?;
+        match serde_json::from_slice::<ServiceResponse<T>>(&body).map_err(Kind::Json)? {
             ServiceResponse::Success { data, .. } => Ok(data),
             ServiceResponse::Error(status) => Err(Kind::ErrorInNonErrorMessage(status).into()),
         }
     }
+
+    async fn bytes_with_limit(mut self, max_size: Option<u64>) -> Result<Bytes, NodeError> {
+        let Some(limit) = max_size else {
+            return Ok(self.bytes().await.map_err(Kind::Reqwest)?);
+        };
+
+        // Reject the response early if the server announces a body that is too large, but do not
+        // rely on the header, as it is not authenticated.
+        ensure!(
+            self.content_length().is_none_or(|length| length <= limit),
+            NodeError::from(Kind::ResponseTooLarge { limit })
+        );
+
+        let mut body = BytesMut::new();
+        while let Some(chunk) = self.chunk().await.map_err(Kind::Reqwest)? {
+            ensure!(
+                body.len() as u64 + chunk.len() as u64 <= limit,
+                NodeError::from(Kind::ResponseTooLarge { limit })
+            );
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.freeze())
+    }
 }
 
 fn is_content_type_octet_stream(headers: &HeaderMap) -> bool {
@@ -107,3 +150,43 @@ fn is_content_type_json(headers: &header::HeaderMap) -> bool {
     // Check the media type and subtype, but allow any params.
     media_type.type_() == mime::APPLICATION && media_type.subtype() == mime::JSON
 }
+
+#[cfg(test)]
+mod tests {
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    fn octet_stream_response(body: Vec<u8>) -> Response {
+        http::Response::builder()
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .body(body)
+            .expect("response is valid")
+            .into()
+    }
+
+    #[tokio::test]
+    async fn decodes_bcs_response_within_limit() -> TestResult {
+        let value: Vec<u8> = vec![7; 100];
+        let response = octet_stream_response(bcs::to_bytes(&value)?);
+
+        let decoded: Vec<u8> = response.bcs(Some(1024)).await?;
+
+        assert_eq!(decoded, value);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_bcs_response_exceeding_limit() -> TestResult {
+        let value: Vec<u8> = vec![7; 2048];
+        let response = octet_stream_response(bcs::to_bytes(&value)?);
+
+        let error = response
+            .bcs::<Vec<u8>>(Some(1024))
+            .await
+            .expect_err("response is larger than the limit");
+
+        assert!(error.is_response_too_large());
+        Ok(())
+    }
+}
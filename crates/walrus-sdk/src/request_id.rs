@@ -0,0 +1,111 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Identifiers of the requests made to Walrus services.
+//!
+//! The services assign an ID to each request received on their APIs, or accept the ID provided by
+//! the caller in the [`REQUEST_ID_HEADER`]. The ID is available to the task handling the request
+//! through [`current`], is returned in error responses, and is sent with the requests to storage
+//! nodes made on behalf of the request, such that a failure can be correlated across the services.
+
+use std::{fmt, future::Future, sync::Arc};
+
+/// The header in which the ID of a request is sent and returned.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The maximum length of a request ID provided by a caller.
+pub const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+/// The ID of a request to a Walrus service.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(Arc<str>);
+
+impl RequestId {
+    /// Creates a new random request ID.
+    pub fn new_random() -> Self {
+        Self(format!("{:032x}", rand::random::<u128>()).into())
+    }
+
+    /// Parses a request ID provided by a caller.
+    ///
+    /// Returns `None` if the ID is empty, longer than [`MAX_REQUEST_ID_LENGTH`], or contains
+    /// characters other than printable ASCII characters.
+    pub fn parse(value: &str) -> Option<Self> {
+        let is_valid = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LENGTH
+            && value.bytes().all(|byte| byte.is_ascii_graphic());
+        is_valid.then(|| Self(value.into()))
+    }
+
+    /// Returns the ID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Returns the ID of the request handled by the current task, if any.
+pub fn current() -> Option<RequestId> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Runs the future as part of handling the request with the given ID.
+pub async fn scope<F: Future>(request_id: RequestId, future: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, future).await
+}
+
+/// Returns a future that runs as part of handling the current request, if any.
+///
+/// This must be used for futures that are spawned on separate tasks, as these do not inherit the
+/// request ID of the task spawning them.
+pub fn in_current_request<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let request_id = current();
+    async move {
+        match request_id {
+            Some(request_id) => scope(request_id, future).await,
+            None => future.await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_only_valid_request_ids() {
+        assert_eq!(
+            RequestId::parse("req-1234").map(|id| id.to_string()),
+            Some("req-1234".to_owned())
+        );
+        assert!(RequestId::parse("").is_none());
+        assert!(RequestId::parse("with space").is_none());
+        assert!(RequestId::parse(&"a".repeat(MAX_REQUEST_ID_LENGTH + 1)).is_none());
+        assert!(RequestId::parse(RequestId::new_random().as_str()).is_some());
+    }
+
+    #[tokio::test]
+    async fn request_id_is_available_within_scope_and_spawned_tasks() {
+        let request_id = RequestId::new_random();
+        assert_eq!(current(), None);
+
+        let expected = request_id.clone();
+        scope(request_id, async move {
+            assert_eq!(current(), Some(expected.clone()));
+            let spawned = tokio::spawn(in_current_request(async { current() }));
+            assert_eq!(spawned.await.unwrap(), Some(expected));
+        })
+        .await;
+
+        assert_eq!(current(), None);
+    }
+}
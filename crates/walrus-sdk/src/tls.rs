@@ -237,6 +237,13 @@ impl ServerCertVerifier for TlsCertificateVerifier {
         if self.has_pinned_public_key(end_entity) {
             Ok(verified)
         } else {
+            // A mismatch may indicate that the node's endpoint was hijacked, e.g., through DNS, so
+            // surface it to operators rather than only failing the connection.
+            tracing::warn!(
+                walrus.node.public_key = ?self.public_key,
+                ?server_name,
+                "server presented a certificate that does not match the pinned public key"
+            );
             Err(rustls::Error::General(
                 "the certificate presented by the server does not match the pinned public key"
                     .to_owned(),
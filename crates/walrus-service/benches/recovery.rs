@@ -0,0 +1,143 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks for sliver recovery, metadata verification, and shard sync through the committee
+//! service, using in-memory mock peers.
+
+use core::{num::NonZeroU16, time::Duration};
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+use walrus_core::{ShardIndex, SliverPairIndex, SliverType};
+use walrus_service::node::committee::bench::{RecoveryBench, RecoveryBenchConfig};
+
+const N_SHARDS: [u16; 2] = [100, 1000];
+const BLOB_SIZES: [usize; 2] = [1 << 16, 1 << 20];
+const SYNC_BATCH_SIZE: u64 = 10;
+
+fn bench_configs() -> impl Iterator<Item = RecoveryBenchConfig> {
+    N_SHARDS.into_iter().flat_map(|n_shards| {
+        BLOB_SIZES
+            .into_iter()
+            .map(move |blob_size| RecoveryBenchConfig {
+                n_shards: NonZeroU16::new(n_shards).unwrap(),
+                n_nodes: 10,
+                n_blobs: 10,
+                blob_size,
+                ..Default::default()
+            })
+    })
+}
+
+fn parameter(config: &RecoveryBenchConfig) -> String {
+    format!(
+        "n_shards={},blob_size={}",
+        config.n_shards, config.blob_size
+    )
+}
+
+fn recovery(
+    c: &mut Criterion,
+    runtime: &Runtime,
+    benches: &[(RecoveryBenchConfig, RecoveryBench)],
+) {
+    let mut group = c.benchmark_group("sliver_recovery");
+
+    for (config, bench) in benches {
+        let blob_id = *bench.blob_ids().next().unwrap();
+
+        for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+            let sliver = runtime
+                .block_on(bench.recover_sliver(&blob_id, SliverPairIndex(0), sliver_type))
+                .unwrap();
+            group.throughput(Throughput::Bytes(sliver.len().try_into().unwrap()));
+            group.bench_with_input(
+                BenchmarkId::new(sliver_type.to_string(), parameter(config)),
+                &blob_id,
+                |b, blob_id| {
+                    b.iter(|| {
+                        runtime
+                            .block_on(bench.recover_sliver(
+                                blob_id,
+                                SliverPairIndex(0),
+                                sliver_type,
+                            ))
+                            .unwrap()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn metadata_verification(
+    c: &mut Criterion,
+    runtime: &Runtime,
+    benches: &[(RecoveryBenchConfig, RecoveryBench)],
+) {
+    let mut group = c.benchmark_group("metadata_verification");
+
+    for (config, bench) in benches {
+        let blob_id = *bench.blob_ids().next().unwrap();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(parameter(config)),
+            &blob_id,
+            |b, blob_id| {
+                b.iter(|| runtime.block_on(bench.get_and_verify_metadata(blob_id)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn shard_sync(
+    c: &mut Criterion,
+    runtime: &Runtime,
+    benches: &[(RecoveryBenchConfig, RecoveryBench)],
+) {
+    let mut group = c.benchmark_group("shard_sync");
+
+    for (config, bench) in benches {
+        group.throughput(Throughput::Elements(config.n_blobs.try_into().unwrap()));
+
+        for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+            group.bench_with_input(
+                BenchmarkId::new(sliver_type.to_string(), parameter(config)),
+                &ShardIndex(0),
+                |b, shard| {
+                    b.iter(|| {
+                        runtime
+                            .block_on(bench.sync_shard(*shard, sliver_type, SYNC_BATCH_SIZE))
+                            .unwrap()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn main() {
+    let runtime = Runtime::new().unwrap();
+    let benches: Vec<_> = bench_configs()
+        .map(|config| {
+            let bench = runtime.block_on(RecoveryBench::new(&config)).unwrap();
+            (config, bench)
+        })
+        .collect();
+
+    let mut criterion = Criterion::default()
+        .configure_from_args()
+        .sample_size(10) // set sample size to the minimum to limit execution time
+        .warm_up_time(Duration::from_millis(100));
+
+    recovery(&mut criterion, &runtime, &benches);
+    metadata_verification(&mut criterion, &runtime, &benches);
+    shard_sync(&mut criterion, &runtime, &benches);
+
+    criterion.final_summary();
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks the recovery path of Walrus storage nodes against in-memory mock peers.
+
+use std::{
+    future::Future,
+    num::NonZeroU16,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use walrus_core::{ShardIndex, SliverPairIndex, SliverType};
+use walrus_service::node::committee::bench::{RecoveryBench, RecoveryBenchConfig};
+
+/// Benchmark sliver recovery, metadata verification, and shard sync of Walrus storage nodes
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+#[clap(name = env!("CARGO_BIN_NAME"))]
+#[derive(Debug)]
+struct Args {
+    /// The number of shards in the system.
+    #[clap(long, default_value_t = NonZeroU16::new(1000).expect("1000 is non-zero"))]
+    n_shards: NonZeroU16,
+    /// The number of mock peers among which the shards are distributed.
+    #[clap(long, default_value_t = 100)]
+    n_nodes: u16,
+    /// The number of blobs stored with the mock peers.
+    #[clap(long, default_value_t = 10)]
+    n_blobs: usize,
+    /// The size of each stored blob in bytes.
+    #[clap(long, default_value_t = 1 << 20)]
+    blob_size: usize,
+    /// The number of times each operation is repeated.
+    #[clap(long, default_value_t = 10)]
+    iterations: u32,
+    /// The maximum number of slivers requested at a time during shard sync.
+    #[clap(long, default_value_t = 10)]
+    sync_batch_size: u64,
+    /// The seed used to generate the blobs and keys.
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let config = RecoveryBenchConfig {
+        n_shards: args.n_shards,
+        n_nodes: args.n_nodes,
+        n_blobs: args.n_blobs,
+        blob_size: args.blob_size,
+        seed: args.seed,
+    };
+
+    let start = Instant::now();
+    let bench = RecoveryBench::new(&config).await?;
+    println!(
+        "encoded {} blobs of {} bytes for {} shards on {} nodes in {:.2?}",
+        config.n_blobs,
+        config.blob_size,
+        config.n_shards,
+        config.n_nodes,
+        start.elapsed()
+    );
+    let bench = &bench;
+    let blob_id = bench
+        .blob_ids()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("at least one blob is required"))?;
+
+    for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+        let (elapsed, sliver) = time(args.iterations, || async move {
+            bench
+                .recover_sliver(blob_id, SliverPairIndex(0), sliver_type)
+                .await
+        })
+        .await?;
+        report(
+            &format!("{sliver_type} sliver recovery"),
+            args.iterations,
+            elapsed,
+            sliver.map(|sliver| sliver.len()),
+        );
+    }
+
+    let (elapsed, _) = time(args.iterations, || async move {
+        Ok(bench.get_and_verify_metadata(blob_id).await)
+    })
+    .await?;
+    report("metadata verification", args.iterations, elapsed, None);
+
+    for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+        let (elapsed, n_synced) = time(args.iterations, || async move {
+            bench
+                .sync_shard(ShardIndex(0), sliver_type, args.sync_batch_size)
+                .await
+        })
+        .await?;
+        report(
+            &format!(
+                "{sliver_type} shard sync ({} slivers)",
+                n_synced.unwrap_or_default()
+            ),
+            args.iterations,
+            elapsed,
+            None,
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs the operation `iterations` times and returns the total elapsed time along with the output
+/// of the last run, if any.
+async fn time<T, F, Fut>(iterations: u32, mut operation: F) -> anyhow::Result<(Duration, Option<T>)>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let start = Instant::now();
+    let mut output = None;
+    for _ in 0..iterations {
+        output = Some(operation().await?);
+    }
+    Ok((start.elapsed(), output))
+}
+
+/// Prints the mean duration of an operation and, if `bytes_per_operation` is set, its throughput.
+fn report(name: &str, iterations: u32, elapsed: Duration, bytes_per_operation: Option<usize>) {
+    let mean = elapsed / iterations.max(1);
+    match bytes_per_operation {
+        Some(bytes) if !mean.is_zero() => {
+            let throughput = bytes as f64 / mean.as_secs_f64() / f64::from(1 << 20);
+            println!("{name}: {mean:.2?} per operation, {throughput:.2} MiB/s");
+        }
+        _ => println!("{name}: {mean:.2?} per operation"),
+    }
+}
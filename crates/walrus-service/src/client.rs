@@ -3,12 +3,20 @@
 
 //! Client for the Walrus service.
 
-use std::{collections::HashMap, fmt::Display, path::PathBuf, sync::Arc, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
 
 use anyhow::anyhow;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use cli::{styled_progress_bar, styled_spinner};
 use communication::NodeCommunicationFactory;
-use futures::{Future, FutureExt};
+use futures::{stream, Future, FutureExt, Stream, StreamExt as _, TryFutureExt as _};
 use indicatif::{HumanDuration, MultiProgress};
 use prometheus::Registry;
 use rand::{rngs::ThreadRng, RngCore as _};
@@ -24,12 +32,14 @@ use tokio::{sync::Semaphore, time::Duration};
 use tracing::{Instrument as _, Level};
 use utils::WeightedResult;
 use walrus_core::{
-    bft,
     encoding::{
         BlobDecoderEnum,
+        DecodingVerificationError,
         EncodingAxis,
         EncodingConfig,
         EncodingConfigTrait as _,
+        Primary,
+        Secondary,
         SliverData,
         SliverPair,
     },
@@ -42,8 +52,10 @@ use walrus_core::{
     EpochCount,
     ShardIndex,
     Sliver,
+    SliverPairIndex,
+    DEFAULT_ENCODING,
 };
-use walrus_sdk::{api::BlobStatus, error::NodeError};
+use walrus_sdk::{api::BlobStatus, error::NodeError, request_id};
 use walrus_sui::{
     client::{
         BlobPersistence,
@@ -53,14 +65,25 @@ use walrus_sui::{
         ReadClient,
         SuiContractClient,
     },
-    types::{move_structs::BlobWithAttribute, Blob, BlobEvent, StakedWal},
+    types::{
+        move_structs::{BlobWithAttribute, EpochState},
+        Blob,
+        BlobEvent,
+        StakedWal,
+    },
 };
 use walrus_utils::backoff::BackoffStrategy;
 
 use self::{
-    communication::NodeResult,
+    communication::{NodeResult, NodeWriteCommunication},
     config::CommunicationLimits,
-    responses::BlobStoreResult,
+    responses::{
+        BlobAvailability,
+        BlobCertificationStatus,
+        BlobReadInfo,
+        BlobStoreResult,
+        NodeBlobAvailability,
+    },
     utils::{CompletedReasonWeight, WeightedFutures},
 };
 use crate::common::active_committees::ActiveCommittees;
@@ -73,7 +96,14 @@ pub use crate::common::blocklist::Blocklist;
 mod communication;
 
 pub(crate) mod config;
-pub use config::{default_configuration_paths, ClientCommunicationConfig, Config};
+pub use config::{
+    default_configuration_paths,
+    ClientCommunicationConfig,
+    ClientPhase,
+    Config,
+    RetryPolicy,
+    RetryPolicyBuilder,
+};
 
 mod daemon;
 pub use daemon::{auth::Claim, ClientDaemon, PublisherQuery, WalrusWriteClient};
@@ -81,6 +111,8 @@ pub use daemon::{auth::Claim, ClientDaemon, PublisherQuery, WalrusWriteClient};
 mod error;
 pub use error::{ClientError, ClientErrorKind};
 
+pub mod receipt;
+
 mod refresh;
 pub use refresh::{
     CommitteesRefreshConfig,
@@ -88,8 +120,12 @@ pub use refresh::{
     CommitteesRefresherHandle,
     RequestKind,
 };
+pub(crate) mod renewal;
+
 mod resource;
 
+pub(crate) mod site;
+
 mod utils;
 pub use utils::string_prefix;
 
@@ -156,6 +192,29 @@ impl StoreWhen {
     }
 }
 
+/// Represents to which storage nodes the client pushes the metadata of a blob when storing it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetadataReplication {
+    /// Push the metadata only along with the slivers, until the slivers are stored on a quorum of
+    /// nodes and the extra time for additional nodes has elapsed.
+    #[default]
+    WithSlivers,
+    /// Additionally push the metadata to all nodes of the committee that did not confirm storing
+    /// the blob in time, which makes later reads and recoveries cheaper.
+    AllNodes,
+}
+
+impl MetadataReplication {
+    /// Returns [`Self`] based on the value of the `push-metadata-to-all-nodes` flag.
+    pub fn from_flag(push_metadata_to_all_nodes: bool) -> Self {
+        if push_metadata_to_all_nodes {
+            Self::AllNodes
+        } else {
+            Self::WithSlivers
+        }
+    }
+}
+
 /// A client to communicate with Walrus shards and storage nodes.
 #[derive(Debug, Clone)]
 pub struct Client<T> {
@@ -316,6 +375,170 @@ impl<T: ReadClient> Client<T> {
         self.read_blob_internal(blob_id, Some(blob_status)).await
     }
 
+    /// Returns the size, encoding type, certification epoch, and status of the blob.
+    ///
+    /// Only the blob status and metadata are retrieved; the blob itself is not read. Returns an
+    /// error of kind [`ClientErrorKind::BlobIdDoesNotExist`] if the blob is not certified.
+    #[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+    pub async fn blob_read_info(&self, blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+        self.check_blob_id(blob_id)?;
+        let status = self
+            .get_blob_status_with_retries(blob_id, &self.sui_client)
+            .await?;
+        let certified_epoch = status
+            .initial_certified_epoch()
+            .ok_or_else(|| ClientError::from(ClientErrorKind::BlobIdDoesNotExist))?;
+        let read_epoch = self.certified_epoch_for_read(blob_id, Some(status)).await?;
+        let metadata = self.retrieve_metadata(read_epoch, blob_id).await?;
+
+        Ok(BlobReadInfo {
+            blob_id: *blob_id,
+            unencoded_length: metadata.metadata().unencoded_length(),
+            encoding_type: metadata.metadata().encoding_type(),
+            certified_epoch,
+            status,
+        })
+    }
+
+    /// Returns the size, encoding type, certification epoch, and status of the blob.
+    ///
+    /// The operation is retried if it fails due to epoch change, see [`Self::blob_read_info`].
+    pub async fn blob_read_info_retry_committees(
+        &self,
+        blob_id: &BlobId,
+    ) -> ClientResult<BlobReadInfo> {
+        self.retry_if_notified_epoch_change(|| self.blob_read_info(blob_id))
+            .await
+    }
+
+    /// Returns the certification status of the blob.
+    ///
+    /// The operation is retried if it fails due to epoch change, see [`Self::get_blob_status`].
+    pub async fn get_blob_status_retry_committees(
+        &self,
+        blob_id: &BlobId,
+    ) -> ClientResult<BlobCertificationStatus> {
+        self.retry_if_notified_epoch_change(|| self.get_blob_status(blob_id))
+            .await
+    }
+
+    /// Returns whether the blob is certified, by which epoch, its expiry, and whether it was
+    /// invalidated.
+    ///
+    /// Neither the blob nor its metadata are retrieved; only the verified blob status and, for
+    /// certified or permanent blobs, the on-chain system parameters and epoch state used to
+    /// determine the start of the certification epoch and to estimate the expiry are queried. Blob
+    /// IDs unknown to the storage nodes are reported as [`BlobStatus::Nonexistent`].
+    #[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+    pub async fn get_blob_status(&self, blob_id: &BlobId) -> ClientResult<BlobCertificationStatus> {
+        self.check_blob_id(blob_id)?;
+        let status = match self
+            .get_blob_status_with_retries(blob_id, &self.sui_client)
+            .await
+        {
+            Ok(status) => status,
+            Err(error) if matches!(error.kind(), ClientErrorKind::BlobIdDoesNotExist) => {
+                BlobStatus::Nonexistent
+            }
+            Err(error) => return Err(error),
+        };
+        let certified_epoch_start = match status.initial_certified_epoch() {
+            Some(certified_epoch) => Some(
+                self.sui_client
+                    .fixed_system_parameters()
+                    .await?
+                    .scheduled_epoch_start(certified_epoch),
+            ),
+            None => None,
+        };
+        let estimated_expiry_timestamp = match status {
+            BlobStatus::Permanent { end_epoch, .. } => {
+                Some(self.estimated_epoch_start(end_epoch).await?)
+            }
+            _ => None,
+        };
+
+        Ok(BlobCertificationStatus::new(
+            *blob_id,
+            status,
+            certified_epoch_start,
+            estimated_expiry_timestamp,
+        ))
+    }
+
+    /// Checks which storage nodes of the current committee store the metadata and slivers of the
+    /// blob, and whether enough of them are available to read the blob.
+    ///
+    /// All members of the current committee are queried for the status of the blob's metadata and
+    /// of its slivers for each of their shards. This is intended for debugging blobs that cannot be
+    /// read, and is considerably more expensive than reading the blob status.
+    #[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+    pub async fn check_blob_availability(
+        &self,
+        blob_id: &BlobId,
+    ) -> ClientResult<BlobAvailability> {
+        self.check_blob_id(blob_id)?;
+        let committees = self.get_committees().await?;
+        let epoch = committees.epoch();
+
+        let encoding_type = match self.retrieve_metadata(epoch, blob_id).await {
+            Ok(metadata) => Some(metadata.metadata().encoding_type()),
+            Err(error) => {
+                tracing::info!(%error, "unable to retrieve the metadata of the blob");
+                None
+            }
+        };
+        let encoding_config = self
+            .encoding_config
+            .get_for_type(encoding_type.unwrap_or(DEFAULT_ENCODING));
+
+        let comms = self
+            .communication_factory
+            .node_read_communications(&committees, epoch)?;
+        let mut requests = WeightedFutures::new(
+            comms
+                .iter()
+                .map(|n| n.get_stored_slivers(blob_id).instrument(n.span.clone())),
+        );
+        requests
+            .execute_all(self.communication_limits.max_concurrent_status_reads)
+            .await;
+
+        let members = committees.current_committee().members();
+        let nodes = requests
+            .into_results()
+            .into_iter()
+            .map(|NodeResult(_, _, node, result)| {
+                NodeBlobAvailability::new(&members[node], result.map_err(|error| error.to_string()))
+            })
+            .collect();
+
+        Ok(BlobAvailability::new(
+            *blob_id,
+            epoch,
+            encoding_type.is_some(),
+            encoding_config.n_source_symbols::<Primary>().get().into(),
+            encoding_config.n_source_symbols::<Secondary>().get().into(),
+            nodes,
+        ))
+    }
+
+    /// Estimates the time at which the given epoch starts, based on the current epoch state.
+    async fn estimated_epoch_start(&self, epoch: Epoch) -> ClientResult<DateTime<Utc>> {
+        let epoch_duration = self
+            .sui_client
+            .fixed_system_parameters()
+            .await?
+            .epoch_duration;
+        let current_epoch = self.sui_client.current_epoch().await?;
+        let estimated_start_of_current_epoch = match self.sui_client.epoch_state().await? {
+            EpochState::EpochChangeDone(epoch_start)
+            | EpochState::NextParamsSelected(epoch_start) => epoch_start,
+            EpochState::EpochChangeSync(_) => Utc::now(),
+        };
+        Ok(estimated_start_of_current_epoch + epoch_duration * epoch.saturating_sub(current_epoch))
+    }
+
     /// Internal method to handle the common logic for reading blobs.
     async fn read_blob_internal<U>(
         &self,
@@ -328,6 +551,21 @@ impl<T: ReadClient> Client<T> {
     {
         tracing::debug!("starting to read blob");
         self.check_blob_id(blob_id)?;
+        let certified_epoch = self.certified_epoch_for_read(blob_id, blob_status).await?;
+
+        self.read_metadata_and_slivers::<U>(certified_epoch, blob_id)
+            .await
+    }
+
+    /// Returns the epoch in which the blob must be read.
+    ///
+    /// During epoch change, this is the epoch in which the blob was initially certified; otherwise,
+    /// it is the current epoch.
+    async fn certified_epoch_for_read(
+        &self,
+        blob_id: &BlobId,
+        blob_status: Option<BlobStatus>,
+    ) -> ClientResult<Epoch> {
         let committees = self.get_committees().await?;
 
         let certified_epoch = if committees.is_change_in_progress() {
@@ -355,9 +593,225 @@ impl<T: ReadClient> Client<T> {
                 certified_epoch,
             }));
         }
+        Ok(certified_epoch)
+    }
 
-        self.read_metadata_and_slivers::<U>(certified_epoch, blob_id)
-            .await
+    /// Reads the blob and returns a stream that yields its contents incrementally.
+    ///
+    /// The first `n` primary slivers of a blob contain the blob's data in order. After retrieving
+    /// and verifying the blob metadata, these slivers are requested with bounded concurrency, and
+    /// the data of each sliver is yielded as soon as it is verified against the metadata and all
+    /// preceding data has been yielded. This bounds the memory required to read large blobs.
+    ///
+    /// If one of these slivers cannot be retrieved, the client falls back to reconstructing the
+    /// full blob from all available slivers and yields the remaining data at once.
+    #[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+    pub async fn read_blob_stream(
+        &self,
+        blob_id: &BlobId,
+    ) -> ClientResult<impl Stream<Item = ClientResult<Bytes>> + '_> {
+        tracing::debug!("starting to stream blob");
+        let (certified_epoch, metadata) = self.metadata_for_stream(blob_id).await?;
+        self.stream_blob_with_metadata(certified_epoch, Arc::new(metadata))
+    }
+
+    /// Returns the epoch in which the blob was certified and its verified metadata.
+    async fn metadata_for_stream(
+        &self,
+        blob_id: &BlobId,
+    ) -> ClientResult<(Epoch, VerifiedBlobMetadataWithId)> {
+        self.check_blob_id(blob_id)?;
+        let certified_epoch = self.certified_epoch_for_read(blob_id, None).await?;
+        let metadata = self.retrieve_metadata(certified_epoch, blob_id).await?;
+        Ok((certified_epoch, metadata))
+    }
+
+    /// Returns a stream of the contents of the blob with the given metadata, see
+    /// [`Self::read_blob_stream`].
+    fn stream_blob_with_metadata(
+        &self,
+        certified_epoch: Epoch,
+        metadata: Arc<VerifiedBlobMetadataWithId>,
+    ) -> ClientResult<impl Stream<Item = ClientResult<Bytes>> + '_> {
+        let blob_size: usize = metadata
+            .metadata()
+            .unencoded_length()
+            .try_into()
+            .map_err(ClientError::other)?;
+        let n_systematic_slivers = self
+            .encoding_config
+            .get_for_type(metadata.metadata().encoding_type())
+            .n_source_symbols::<Primary>()
+            .get();
+
+        let slivers = {
+            let metadata = metadata.clone();
+            stream::iter(0..n_systematic_slivers)
+                .map(move |index| {
+                    let metadata = metadata.clone();
+                    async move {
+                        self.retrieve_primary_sliver(
+                            certified_epoch,
+                            &metadata,
+                            SliverPairIndex(index),
+                        )
+                        .await
+                    }
+                })
+                .buffered(self.communication_limits.max_concurrent_sliver_reads)
+        };
+
+        let state = (Box::pin(slivers), 0usize);
+        Ok(stream::try_unfold(state, move |(mut slivers, offset)| {
+            let metadata = metadata.clone();
+            async move {
+                if offset >= blob_size {
+                    return Ok(None);
+                }
+                let data = match slivers.next().await {
+                    Some(Ok(sliver)) => {
+                        let mut data = sliver.symbols.into_vec();
+                        data.truncate(blob_size - offset);
+                        data
+                    }
+                    Some(Err(error)) => {
+                        tracing::warn!(
+                            %error,
+                            offset,
+                            "failed to retrieve systematic sliver; decoding the full blob"
+                        );
+                        let mut blob = self
+                            .request_slivers_and_decode::<Primary>(certified_epoch, &metadata)
+                            .await?;
+                        blob.drain(..offset);
+                        blob
+                    }
+                    None => return Ok(None),
+                };
+                let offset = offset + data.len();
+                Ok(Some((Bytes::from(data), (slivers, offset))))
+            }
+        }))
+    }
+
+    /// Reads the blob and returns a stream of its contents, which are prefetched in the
+    /// background.
+    ///
+    /// Unlike with [`Self::read_blob_stream`], the slivers are retrieved by a separate task, which
+    /// keeps retrieving up to `prefetch_window` verified slivers ahead of the consumer, even while
+    /// the consumer is not polling the stream. Sequential consumers that forward the data at their
+    /// own pace, such as the aggregator serving a video, therefore do not stall at sliver
+    /// boundaries. The task stops when the stream is dropped.
+    ///
+    /// In contrast to [`Self::read_blob_stream`], the blob is also checked for consistency: the
+    /// task re-encodes the received data and compares the resulting blob ID with the requested
+    /// one, and the last chunk of the blob is only yielded once this check has passed. If it fails,
+    /// an error is yielded instead, such that consumers never receive the full data of an
+    /// inconsistent blob. This requires the task to keep the data until the blob is complete.
+    ///
+    /// Returns the length of the blob and the stream of its contents. Errors that occur before any
+    /// data is retrieved, e.g., if the blob does not exist, are returned directly rather than
+    /// through the stream.
+    pub async fn read_blob_stream_prefetched(
+        self: &Arc<Self>,
+        blob_id: &BlobId,
+        prefetch_window: usize,
+    ) -> ClientResult<(
+        u64,
+        impl Stream<Item = ClientResult<Bytes>> + Send + 'static,
+    )>
+    where
+        T: 'static,
+    {
+        let (certified_epoch, metadata) = self.metadata_for_stream(blob_id).await?;
+        let blob_length = metadata.metadata().unencoded_length();
+
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(prefetch_window.max(1));
+        let client = self.clone();
+        let blob_id = *blob_id;
+        tokio::spawn(
+            request_id::in_current_request(async move {
+                let encoding_type = metadata.metadata().encoding_type();
+                let slivers =
+                    match client.stream_blob_with_metadata(certified_epoch, Arc::new(metadata)) {
+                        Ok(slivers) => slivers,
+                        Err(error) => {
+                            let _ = sender.send(Err(error)).await;
+                            return;
+                        }
+                    };
+                let slivers = utils::hold_back_until_verified(Box::pin(slivers), |blob| {
+                    client.verify_blob_consistency(&blob_id, encoding_type, blob)
+                });
+                let mut slivers = std::pin::pin!(slivers);
+                while let Some(data) = slivers.next().await {
+                    let is_error = data.is_err();
+                    if sender.send(data).await.is_err() || is_error {
+                        // The consumer dropped the stream, or no further data can be read.
+                        return;
+                    }
+                }
+            })
+            .in_current_span(),
+        );
+
+        let first = match receiver.recv().await {
+            Some(Err(error)) => return Err(error),
+            first => first,
+        };
+        let rest = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|data| (data, receiver))
+        });
+        Ok((blob_length, stream::iter(first).chain(rest)))
+    }
+
+    /// Checks that the blob is consistently encoded, by re-encoding it and comparing the resulting
+    /// blob ID with the expected one.
+    fn verify_blob_consistency(
+        &self,
+        blob_id: &BlobId,
+        encoding_type: EncodingType,
+        blob: &[u8],
+    ) -> ClientResult<()> {
+        let metadata = self
+            .encoding_config
+            .get_for_type(encoding_type)
+            .compute_metadata(blob)
+            .map_err(ClientError::other)?;
+        if metadata.blob_id() != blob_id {
+            tracing::warn!(%blob_id, "the streamed blob is inconsistent");
+            return Err(ClientError::other(DecodingVerificationError));
+        }
+        Ok(())
+    }
+
+    /// Retrieves and verifies the primary sliver with the given index from the node holding the
+    /// corresponding shard.
+    async fn retrieve_primary_sliver(
+        &self,
+        certified_epoch: Epoch,
+        metadata: &VerifiedBlobMetadataWithId,
+        sliver_pair_index: SliverPairIndex,
+    ) -> ClientResult<SliverData<Primary>> {
+        let committees = self.get_committees().await?;
+        let shard_index =
+            sliver_pair_index.to_shard_index(committees.n_shards(), metadata.blob_id());
+        let comms = self
+            .communication_factory
+            .node_read_communications(&committees, certified_epoch)?;
+        let node = comms
+            .iter()
+            .find(|n| n.node.shard_ids.contains(&shard_index))
+            .ok_or_else(|| {
+                ClientError::from(ClientErrorKind::Other(
+                    anyhow!("no storage node holds shard {shard_index}").into(),
+                ))
+            })?;
+        let NodeResult(_, _, _, result) = node
+            .retrieve_verified_sliver::<Primary>(metadata, shard_index)
+            .instrument(node.span.clone())
+            .await;
+        result.map_err(ClientError::other)
     }
 
     async fn read_metadata_and_slivers<U>(
@@ -400,7 +854,7 @@ impl<T: ReadClient> Client<T> {
         let mut backoff = self
             .config
             .communication_config
-            .committee_change_backoff
+            .committee_change_backoff_config()
             .get_strategy(ThreadRng::default().next_u64());
 
         // Retry the given function N-1 times; if it does not succeed after N-1 times, then the
@@ -509,6 +963,7 @@ impl Client<SuiContractClient> {
 
     /// Stores a list of blobs to Walrus, retrying if it fails because of epoch change.
     #[tracing::instrument(skip_all, fields(blob_id))]
+    #[allow(clippy::too_many_arguments)]
     pub async fn reserve_and_store_blobs_retry_committees(
         &self,
         blobs: &[&[u8]],
@@ -517,6 +972,7 @@ impl Client<SuiContractClient> {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<Vec<BlobStoreResult>> {
         let pairs_and_metadata = self.encode_blobs_to_pairs_and_metadata(blobs, encoding_type)?;
 
@@ -527,6 +983,7 @@ impl Client<SuiContractClient> {
                 store_when,
                 persistence,
                 post_store,
+                metadata_replication,
             )
         })
         .await
@@ -536,6 +993,7 @@ impl Client<SuiContractClient> {
     /// Similar to `[Client::reserve_and_store_blobs_retry_committees]`, except the result
     /// includes the corresponding path for blob.
     #[tracing::instrument(skip_all, fields(blob_id))]
+    #[allow(clippy::too_many_arguments)]
     pub async fn reserve_and_store_blobs_retry_committees_with_path(
         &self,
         blobs_with_paths: &[(PathBuf, Vec<u8>)],
@@ -544,6 +1002,7 @@ impl Client<SuiContractClient> {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<Vec<BlobStoreResultWithPath>> {
         let EncodedResult {
             pairs_and_metadata,
@@ -560,6 +1019,7 @@ impl Client<SuiContractClient> {
                     store_when,
                     persistence,
                     post_store,
+                    metadata_replication,
                 )
             })
             .await?;
@@ -578,6 +1038,7 @@ impl Client<SuiContractClient> {
     /// storage nodes. Finally, the function aggregates the storage confirmations and posts the
     /// [`ConfirmationCertificate`] on chain.
     #[tracing::instrument(skip_all, fields(blob_id))]
+    #[allow(clippy::too_many_arguments)]
     pub async fn reserve_and_store_blobs(
         &self,
         blobs: &[&[u8]],
@@ -586,6 +1047,7 @@ impl Client<SuiContractClient> {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<Vec<BlobStoreResult>> {
         let pairs_and_metadata = self.encode_blobs_to_pairs_and_metadata(blobs, encoding_type)?;
 
@@ -595,6 +1057,7 @@ impl Client<SuiContractClient> {
             store_when,
             persistence,
             post_store,
+            metadata_replication,
         )
         .await
     }
@@ -737,6 +1200,7 @@ impl Client<SuiContractClient> {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<Vec<BlobStoreResult>> {
         tracing::info!(
             "storing {} sliver pairs with metadata",
@@ -828,9 +1292,11 @@ impl Client<SuiContractClient> {
         // changed in the meantime.
         // This operation can be safely interrupted as it does not require a wallet.
         let blobs_with_certificates = self
-            .await_while_checking_notification(
-                self.get_all_blob_certificates(&certify_blobs, &blob_id_to_metadata_with_status),
-            )
+            .await_while_checking_notification(self.get_all_blob_certificates(
+                &certify_blobs,
+                &blob_id_to_metadata_with_status,
+                metadata_replication,
+            ))
             .await?;
 
         let blobs_with_cert_and_extend: Vec<CertifyAndExtendBlobParams> = blobs_with_certificates
@@ -853,14 +1319,17 @@ impl Client<SuiContractClient> {
 
         // Certify all blobs on Sui.
         let sui_cert_timer = Instant::now();
-        let shared_blob_object_map = self
-            .sui_client
-            .certify_and_extend_blobs(&blobs_with_cert_and_extend, post_store)
-            .await
-            .map_err(|e| {
-                tracing::warn!(error = %e, "failed to certify blobs on Sui");
-                ClientError::from(ClientErrorKind::CertificationFailed(e))
-            })?;
+        let certify_and_extend_result = self
+            .with_phase_timeout(
+                ClientPhase::Certification,
+                self.sui_client
+                    .certify_and_extend_blobs(&blobs_with_cert_and_extend, post_store)
+                    .map_err(|e| {
+                        tracing::warn!(error = %e, "failed to certify blobs on Sui");
+                        ClientError::from(ClientErrorKind::CertificationFailed(e))
+                    }),
+            )
+            .await?;
         tracing::info!(
             duration = ?sui_cert_timer.elapsed(),
             "certified {} blobs on Sui",
@@ -882,7 +1351,14 @@ impl Client<SuiContractClient> {
                     },
                     resource_operation,
                     cost,
-                    shared_blob_object: shared_blob_object_map.get(&blob.blob_id).copied(),
+                    shared_blob_object: certify_and_extend_result
+                        .shared_blob_objects
+                        .get(&blob.blob_id)
+                        .copied(),
+                    certification_event: certify_and_extend_result
+                        .certification_events
+                        .get(&blob.blob_id)
+                        .copied(),
                 }
             })
             .collect();
@@ -892,7 +1368,11 @@ impl Client<SuiContractClient> {
             .map(|(mut blob, op)| {
                 blob.storage.end_epoch = write_committee_epoch + epochs_ahead;
                 BlobStoreResult::NewlyCreated {
-                    shared_blob_object: shared_blob_object_map.get(&blob.blob_id).copied(),
+                    shared_blob_object: certify_and_extend_result
+                        .shared_blob_objects
+                        .get(&blob.blob_id)
+                        .copied(),
+                    certification_event: None,
                     blob_object: blob,
                     cost: price_computation.operation_cost(&op),
                     resource_operation: op,
@@ -908,7 +1388,14 @@ impl Client<SuiContractClient> {
                     BlobStoreResult::NewlyCreated {
                         cost: price_computation.operation_cost(&op),
                         resource_operation: op,
-                        shared_blob_object: shared_blob_object_map.get(&blob.blob_id).copied(),
+                        shared_blob_object: certify_and_extend_result
+                            .shared_blob_objects
+                            .get(&blob.blob_id)
+                            .copied(),
+                        certification_event: certify_and_extend_result
+                            .certification_events
+                            .get(&blob.blob_id)
+                            .copied(),
                         blob_object: blob,
                     }
                 });
@@ -983,6 +1470,7 @@ impl Client<SuiContractClient> {
                 BlobStatus,
             ),
         >,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<Vec<(&'a Blob, ConfirmationCertificate)>> {
         if new_blobs_and_ops.is_empty() {
             return Ok(vec![]);
@@ -1007,6 +1495,7 @@ impl Client<SuiContractClient> {
                             pairs,
                             metadata,
                             &blob_status,
+                            metadata_replication,
                             multi_pb_arc.as_ref(),
                         )
                         .await
@@ -1045,6 +1534,7 @@ impl Client<SuiContractClient> {
         Ok(blobs_with_certificates)
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn get_blob_certificate(
         &self,
         blob_object: &Blob,
@@ -1052,6 +1542,7 @@ impl Client<SuiContractClient> {
         pairs: &[SliverPair],
         metadata: &VerifiedBlobMetadataWithId,
         blob_status: &BlobStatus,
+        metadata_replication: MetadataReplication,
         multi_pb: &MultiProgress,
     ) -> ClientResult<ConfirmationCertificate> {
         let committees = self.get_committees().await?;
@@ -1086,6 +1577,7 @@ impl Client<SuiContractClient> {
                         metadata,
                         pairs,
                         &blob_object.blob_persistence_type(),
+                        metadata_replication,
                         multi_pb,
                     )
                     .await?;
@@ -1160,6 +1652,26 @@ impl Client<SuiContractClient> {
         Ok(())
     }
 
+    /// Requests the withdrawal of the staked WAL with id `staked_wal_id` and returns the updated
+    /// [`StakedWal`], which contains the epoch in which the stake can be withdrawn.
+    pub async fn request_withdraw_stake(&self, staked_wal_id: ObjectID) -> ClientResult<StakedWal> {
+        self.sui_client
+            .request_withdraw_stake(staked_wal_id)
+            .await?;
+        let staked_wal = self
+            .sui_client
+            .sui_client()
+            .get_sui_object(staked_wal_id)
+            .await?;
+        Ok(staked_wal)
+    }
+
+    /// Withdraws the staked WAL with id `staked_wal_id` and returns the withdrawn amount in FROST.
+    pub async fn withdraw_stake(&self, staked_wal_id: ObjectID) -> ClientResult<u64> {
+        let amount = self.sui_client.withdraw_stake(staked_wal_id).await?;
+        Ok(amount)
+    }
+
     /// Exchanges the provided amount of SUI (in MIST) for WAL using the specified exchange.
     pub async fn exchange_sui_for_wal(
         &self,
@@ -1193,9 +1705,17 @@ impl<T> Client<T> {
         self
     }
 
+    /// Returns the [`Blocklist`] checked by the client, if any.
+    pub fn blocklist(&self) -> Option<&Blocklist> {
+        self.blocklist.as_ref()
+    }
+
     /// Stores the already-encoded metadata and sliver pairs for a blob into Walrus, by sending
     /// sliver pairs to at least 2f+1 shards.
     ///
+    /// With [`MetadataReplication::AllNodes`], the metadata is additionally pushed to the nodes
+    /// that did not confirm storing the blob before the certificate is returned.
+    ///
     /// Assumes the blob ID has already been registered, with an appropriate blob size.
     #[tracing::instrument(skip_all)]
     pub async fn send_blob_data_and_get_certificate(
@@ -1203,6 +1723,7 @@ impl<T> Client<T> {
         metadata: &VerifiedBlobMetadataWithId,
         pairs: &[SliverPair],
         blob_persistence_type: &BlobPersistenceType,
+        metadata_replication: MetadataReplication,
         multi_pb: &MultiProgress,
     ) -> ClientResult<ConfirmationCertificate> {
         tracing::info!(blob_id = %metadata.blob_id(), "starting to send data to storage nodes");
@@ -1228,7 +1749,7 @@ impl<T> Client<T> {
             .node_write_communications(&committees, Arc::new(Semaphore::new(sliver_write_limit)))?;
 
         let progress_bar = {
-            let pb = styled_progress_bar(bft::min_n_correct(committees.n_shards()).get().into());
+            let pb = styled_progress_bar(committees.quorum_threshold().into());
             pb.set_message(format!("sending slivers ({})", metadata.blob_id()));
             multi_pb.add(pb)
         };
@@ -1254,17 +1775,18 @@ impl<T> Client<T> {
 
         // We do not limit the number of concurrent futures awaited here, because the number of
         // connections is limited through a semaphore depending on the [`max_data_in_flight`][]
-        if let CompletedReasonWeight::FuturesConsumed(weight) = requests
-            .execute_weight(
-                &|weight| {
-                    committees
-                        .write_committee()
-                        .is_at_least_min_n_correct(weight)
-                },
-                committees.n_shards().get().into(),
+        let completed_reason = self
+            .with_phase_timeout(
+                ClientPhase::SliverUpload,
+                requests
+                    .execute_weight(
+                        &|weight| committees.write_committee().is_quorum(weight),
+                        committees.n_shards().get().into(),
+                    )
+                    .map(Ok),
             )
-            .await
-        {
+            .await?;
+        if let CompletedReasonWeight::FuturesConsumed(weight) = completed_reason {
             tracing::debug!(
                 elapsed_time = ?start.elapsed(),
                 executed_weight = weight,
@@ -1324,10 +1846,62 @@ impl<T> Client<T> {
 
         let results = requests.into_results();
 
+        if metadata_replication == MetadataReplication::AllNodes {
+            let timeout = self
+                .config
+                .communication_config
+                .sliver_write_extra_time
+                .extra_time(start.elapsed());
+            self.push_metadata_to_unconfirmed_nodes(metadata, &comms, &results, timeout)
+                .await;
+        }
+
         self.confirmations_to_certificate(results, &committees)
             .await
     }
 
+    /// Pushes the metadata of the blob to the nodes that did not return a storage confirmation.
+    ///
+    /// This is best effort: the pushes stop after `timeout`, and failures are only logged.
+    async fn push_metadata_to_unconfirmed_nodes<R, E>(
+        &self,
+        metadata: &VerifiedBlobMetadataWithId,
+        comms: &[NodeWriteCommunication<'_>],
+        results: &[NodeResult<R, E>],
+        timeout: Duration,
+    ) {
+        let confirmed_nodes: HashSet<_> = results
+            .iter()
+            .filter(|result| result.3.is_ok())
+            .map(|result| result.2)
+            .collect();
+        let unconfirmed_comms: Vec<_> = comms
+            .iter()
+            .filter(|n| !confirmed_nodes.contains(&n.node_index))
+            .collect();
+        if unconfirmed_comms.is_empty() {
+            return;
+        }
+
+        let mut requests =
+            WeightedFutures::new(unconfirmed_comms.iter().map(|n| n.store_metadata(metadata)));
+        let completed_reason = requests
+            .execute_time(timeout, unconfirmed_comms.len())
+            .await;
+        let n_stored = requests
+            .into_results()
+            .iter()
+            .filter(|result| result.3.is_ok())
+            .count();
+        tracing::debug!(
+            blob_id = %metadata.blob_id(),
+            n_stored,
+            n_unconfirmed = unconfirmed_comms.len(),
+            %completed_reason,
+            "pushed metadata to the nodes that did not confirm storing the blob"
+        );
+    }
+
     /// Fetches confirmations for a blob from a quorum of nodes and returns the certificate.
     async fn get_certificate_standalone(
         &self,
@@ -1344,12 +1918,16 @@ impl<T> Client<T> {
             n.get_confirmation_with_retries(blob_id, committees.epoch(), blob_persistence_type)
         }));
 
-        requests
-            .execute_weight(
-                &|weight| committees.is_quorum(weight),
-                self.communication_limits.max_concurrent_sliver_reads,
-            )
-            .await;
+        self.with_phase_timeout(
+            ClientPhase::Confirmation,
+            requests
+                .execute_weight(
+                    &|weight| committees.is_quorum(weight),
+                    self.communication_limits.max_concurrent_sliver_reads,
+                )
+                .map(Ok),
+        )
+        .await?;
         let results = requests.into_results();
 
         self.confirmations_to_certificate(results, &committees)
@@ -1386,9 +1964,7 @@ impl<T> Client<T> {
         }
 
         ensure!(
-            committees
-                .write_committee()
-                .is_at_least_min_n_correct(aggregate_weight),
+            committees.write_committee().is_quorum(aggregate_weight),
             self.not_enough_confirmations_error(aggregate_weight, committees)
                 .await
         );
@@ -1404,7 +1980,7 @@ impl<T> Client<T> {
         weight: usize,
         committees: &ActiveCommittees,
     ) -> ClientError {
-        ClientErrorKind::NotEnoughConfirmations(weight, committees.min_n_correct()).into()
+        ClientErrorKind::NotEnoughConfirmations(weight, committees.quorum_threshold().into()).into()
     }
 
     /// Requests the slivers and decodes them into a blob.
@@ -1627,6 +2203,36 @@ impl<T> Client<T> {
         &self,
         certified_epoch: Epoch,
         blob_id: &BlobId,
+    ) -> ClientResult<VerifiedBlobMetadataWithId> {
+        self.with_phase_timeout(
+            ClientPhase::Metadata,
+            self.retrieve_metadata_inner(certified_epoch, blob_id),
+        )
+        .await
+    }
+
+    /// Awaits the future, failing with [`ClientErrorKind::Timeout`] if it does not complete within
+    /// the timeout of the [`RetryPolicy`][config::RetryPolicy] for the given phase.
+    async fn with_phase_timeout<R>(
+        &self,
+        phase: ClientPhase,
+        future: impl Future<Output = ClientResult<R>>,
+    ) -> ClientResult<R> {
+        let Some(timeout) = self.config.communication_config.retry_policy.timeout(phase) else {
+            return future.await;
+        };
+        tokio::time::timeout(timeout, future)
+            .await
+            .unwrap_or_else(|_| {
+                tracing::warn!(%phase, ?timeout, "client operation timed out");
+                Err(ClientErrorKind::Timeout { phase, timeout }.into())
+            })
+    }
+
+    async fn retrieve_metadata_inner(
+        &self,
+        certified_epoch: Epoch,
+        blob_id: &BlobId,
     ) -> ClientResult<VerifiedBlobMetadataWithId> {
         let committees = self.get_committees().await?;
         let comms = self
@@ -1695,7 +2301,8 @@ impl<T> Client<T> {
         // The backoff is both the interval between retries and the maximum duration of the retry.
         let backoff = self
             .config
-            .backoff_config()
+            .communication_config
+            .node_backoff_config()
             .get_strategy(ThreadRng::default().next_u64());
 
         let mut peekable = backoff.peekable();
@@ -1848,6 +2455,20 @@ impl<T> Client<T> {
         &self.encoding_config
     }
 
+    /// Computes the ID of the blob from its metadata, without keeping the encoded slivers.
+    pub fn compute_blob_id(
+        &self,
+        blob: &[u8],
+        encoding_type: EncodingType,
+    ) -> ClientResult<BlobId> {
+        let metadata = self
+            .encoding_config
+            .get_for_type(encoding_type)
+            .compute_metadata(blob)
+            .map_err(ClientError::other)?;
+        Ok(*metadata.blob_id())
+    }
+
     /// Returns the inner sui client.
     pub fn sui_client(&self) -> &T {
         &self.sui_client
@@ -4,21 +4,21 @@
 //! Utilities for running the walrus cli tools.
 
 use std::{
+    collections::{hash_map::Entry, HashMap},
     fmt::{self, Display},
-    fs,
+    fs::{self, File},
+    io::Read,
     path::{Path, PathBuf},
-    str::FromStr,
     time::Duration,
 };
 
 use anyhow::{anyhow, Context, Result};
 use colored::{Color, ColoredString, Colorize};
+use fastcrypto::hash::{Blake2b256, HashFunction as _};
 use indicatif::{ProgressBar, ProgressStyle};
-use num_bigint::BigUint;
-use serde::{Deserialize, Serialize};
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::event::EventID;
-use walrus_core::BlobId;
+use walrus_core::{BlobId, BlobIdParseError};
 use walrus_sui::client::{retry_client::RetriableSuiClient, SuiContractClient, SuiReadClient};
 use walrus_utils::config::path_or_defaults_if_exist;
 
@@ -39,6 +39,7 @@ pub use args::{
     NodeSortBy,
     PublisherArgs,
     SortBy,
+    UnsignedPublisherArgs,
 };
 pub use cli_output::CliOutput;
 pub use runner::ClientCommandRunner;
@@ -415,96 +416,54 @@ pub fn read_blob_from_file(path: impl AsRef<Path>) -> anyhow::Result<Vec<u8>> {
     ))
 }
 
-/// Format the event ID as the transaction digest and the sequence number.
-pub fn format_event_id(event_id: &EventID) -> String {
-    format!("(tx: {}, seq: {})", event_id.tx_digest, event_id.event_seq)
-}
-
-/// Error type distinguishing between a decimal value that corresponds to a valid blob ID and any
-/// other parse error.
-#[derive(Debug, thiserror::Error)]
-pub enum BlobIdParseError {
-    /// Returned when attempting to parse a decimal value for the blob ID.
-    #[error(
-        "you seem to be using a numeric value in decimal format corresponding to a Walrus blob ID \
-        (maybe copied from a Sui explorer) whereas Walrus uses URL-safe base64 encoding;\n\
-        the Walrus blob ID corresponding to the provided value is {0}"
-    )]
-    BlobIdInDecimalFormat(BlobId),
-    /// Returned when attempting to parse any other invalid string as a blob ID.
-    #[error("the provided blob ID is invalid")]
-    InvalidBlobId,
-}
-
-/// Attempts to parse the blob ID and provides a detailed error when the blob ID was provided in
-/// decimal format.
-pub fn parse_blob_id(input: &str) -> Result<BlobId, BlobIdParseError> {
-    if let Ok(blob_id) = BlobId::from_str(input) {
-        return Ok(blob_id);
-    }
-    Err(match BlobIdDecimal::from_str(input) {
-        Err(_) => BlobIdParseError::InvalidBlobId,
-        Ok(blob_id) => BlobIdParseError::BlobIdInDecimalFormat(blob_id.into()),
-    })
-}
-
-/// Helper struct to parse and format blob IDs as decimal numbers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
-#[repr(transparent)]
-pub struct BlobIdDecimal(BlobId);
-
-/// Error returned when unable to parse a decimal value corresponding to a Walrus blob ID.
-#[derive(Debug, thiserror::Error)]
-pub enum BlobIdDecimalParseError {
-    /// Returned when attempting to parse an actual Walrus blob ID.
-    #[error(
-        "the provided value is already a valid Walrus blob ID;\n\
-        the value represented as a decimal number is {0}"
-    )]
-    BlobIdInBase64Format(BlobIdDecimal),
-    /// Returned when attempting to parse any other invalid string as a decimal blob ID.
-    #[error("the provided value cannot be converted to a Walrus blob ID")]
-    InvalidBlobId,
-}
-
-impl FromStr for BlobIdDecimal {
-    type Err = BlobIdDecimalParseError;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        if let Some(number) = BigUint::parse_bytes(s.as_bytes(), 10) {
-            let bytes = number.to_bytes_le();
-
-            if bytes.len() <= BlobId::LENGTH {
-                let mut blob_id = [0; BlobId::LENGTH];
-                blob_id[..bytes.len()].copy_from_slice(&bytes);
-                return Ok(Self(BlobId(blob_id)));
+/// Groups the files by their contents, hashing each file in chunks instead of reading it into
+/// memory.
+///
+/// Returns the first of the files with each distinct content, in the order of `files`, and the
+/// remaining files paired with the earlier file with the same contents.
+pub(crate) fn deduplicate_files_by_contents(
+    files: &[PathBuf],
+) -> Result<(Vec<PathBuf>, Vec<(PathBuf, PathBuf)>)> {
+    let mut unique_files = Vec::new();
+    let mut duplicates = Vec::new();
+    let mut path_by_contents = HashMap::new();
+    for file in files {
+        match path_by_contents.entry(file_digest(file)?) {
+            Entry::Occupied(original) => duplicates.push((file.clone(), original.get().clone())),
+            Entry::Vacant(entry) => {
+                entry.insert(file.clone());
+                unique_files.push(file.clone());
             }
         }
-
-        Err(if let Ok(blob_id) = BlobId::from_str(s) {
-            BlobIdDecimalParseError::BlobIdInBase64Format(blob_id.into())
-        } else {
-            BlobIdDecimalParseError::InvalidBlobId
-        })
     }
+    Ok((unique_files, duplicates))
 }
 
-impl From<BlobIdDecimal> for BlobId {
-    fn from(value: BlobIdDecimal) -> Self {
-        value.0
+fn file_digest(path: &Path) -> Result<[u8; 32]> {
+    let context = || format!("unable to read blob from '{}'", path.display());
+    let mut file = File::open(path).with_context(context)?;
+    let mut hasher = Blake2b256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).with_context(context)?;
+        if read == 0 {
+            return Ok(hasher.finalize().digest);
+        }
+        hasher.update(&buffer[..read]);
     }
 }
 
-impl From<BlobId> for BlobIdDecimal {
-    fn from(value: BlobId) -> Self {
-        Self(value)
-    }
+/// Format the event ID as the transaction digest and the sequence number.
+pub fn format_event_id(event_id: &EventID) -> String {
+    format!("(tx: {}, seq: {})", event_id.tx_digest, event_id.event_seq)
 }
 
-impl Display for BlobIdDecimal {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", BigUint::from_bytes_le(self.0.as_ref()))
-    }
+/// Parses a blob ID given in any [`BlobIdEncoding`][walrus_core::BlobIdEncoding].
+///
+/// Besides the canonical URL-safe base64 encoding, this accepts blob IDs in hex and in the decimal
+/// format used by Sui explorers.
+pub fn parse_blob_id(input: &str) -> Result<BlobId, BlobIdParseError> {
+    BlobId::parse_any_encoding(input).map(|(blob_id, _)| blob_id)
 }
 
 /// Returns a progress bar with the given length and stlyle already applied
@@ -535,10 +494,35 @@ pub(crate) fn styled_spinner() -> ProgressBar {
 }
 #[cfg(test)]
 mod tests {
-    use walrus_test_utils::param_test;
+    use walrus_test_utils::{param_test, Result as TestResult};
 
     use super::*;
 
+    #[test]
+    fn deduplicates_files_with_identical_contents() -> TestResult {
+        let directory = tempfile::tempdir()?;
+        let files: Vec<_> = [("a", "foo"), ("b", "bar"), ("c", "foo"), ("d", "foo")]
+            .into_iter()
+            .map(|(name, contents)| {
+                let path = directory.path().join(name);
+                fs::write(&path, contents).map(|()| path)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let (unique_files, duplicates) = deduplicate_files_by_contents(&files)?;
+
+        assert_eq!(unique_files, [files[0].clone(), files[1].clone()]);
+        assert_eq!(
+            duplicates,
+            [
+                (files[2].clone(), files[0].clone()),
+                (files[3].clone(), files[0].clone())
+            ]
+        );
+        assert!(deduplicate_files_by_contents(&[directory.path().join("missing")]).is_err());
+        Ok(())
+    }
+
     param_test! {
         test_display_without_precision: [
             b_0: (0, "0 B"),
@@ -643,22 +627,24 @@ mod tests {
     }
 
     param_test! {
-        test_parse_blob_id_provides_correct_decimal_error: [
+        test_parse_blob_id_from_other_encodings: [
             zero: ("0", &[0; 32]),
             ten: ("256", &[0, 1]),
             valid_decimal: (
                 "80885466015098902458382552429473803233277035186046880821304527730792838764083",
-                &[]
+                &[51, 150, 16, 138]
+            ),
+            valid_hex: (
+                "0x3396108a718edd1a112da5bf29b09fbd74ad565584a8ee5d1837b971288dd3b2",
+                &[51, 150, 16, 138]
+            ),
+            valid_hex_without_prefix: (
+                "3396108a718edd1a112da5bf29b09fbd74ad565584a8ee5d1837b971288dd3b2",
+                &[51, 150, 16, 138]
             ),
     ]}
-    fn test_parse_blob_id_provides_correct_decimal_error(
-        input_string: &str,
-        expected_result: &[u8],
-    ) {
-        let Err(BlobIdParseError::BlobIdInDecimalFormat(blob_id)) = parse_blob_id(input_string)
-        else {
-            panic!()
-        };
+    fn test_parse_blob_id_from_other_encodings(input_string: &str, expected_result: &[u8]) {
+        let blob_id = parse_blob_id(input_string).unwrap();
         assert_eq!(blob_id.0[..expected_result.len()], expected_result[..]);
     }
 
@@ -672,9 +658,6 @@ mod tests {
         ]
     }
     fn test_parse_blob_id_failure(input_string: &str) {
-        assert!(matches!(
-            parse_blob_id(input_string),
-            Err(BlobIdParseError::InvalidBlobId)
-        ));
+        assert_eq!(parse_blob_id(input_string), Err(BlobIdParseError));
     }
 }
@@ -5,7 +5,7 @@
 
 use std::{
     net::SocketAddr,
-    num::{NonZeroU16, NonZeroU32},
+    num::{NonZeroU16, NonZeroU32, NonZeroUsize},
     path::PathBuf,
     time::{Duration, SystemTime},
 };
@@ -20,6 +20,7 @@ use walrus_core::{
     encoding::{EncodingConfig, EncodingConfigTrait},
     ensure,
     BlobId,
+    BlobIdFromAnyEncoding,
     EncodingType,
     Epoch,
     EpochCount,
@@ -30,8 +31,14 @@ use walrus_sui::{
     utils::SuiNetwork,
 };
 
-use super::{parse_blob_id, read_blob_from_file, BlobIdDecimal, HumanReadableBytes};
-use crate::client::{config::AuthConfig, daemon::CacheConfig};
+use super::{parse_blob_id, read_blob_from_file, HumanReadableBytes};
+use crate::{
+    client::{
+        config::AuthConfig,
+        daemon::{CacheConfig, CorsConfig, QuotaConfig, TlsConfig},
+    },
+    common::balance_monitor::{BalanceMonitorConfig, BalanceThresholds},
+};
 
 /// The command-line arguments for the Walrus client.
 #[derive(Parser, Debug, Clone, Deserialize)]
@@ -230,19 +237,157 @@ pub enum CliCommands {
         #[clap(long, action)]
         #[serde(default)]
         deletable: bool,
+        /// Mark the blob as permanent.
+        ///
+        /// Permanent blobs cannot be removed from Walrus before their expiration time. This is the
+        /// default if `--deletable` is not specified.
+        #[clap(long, action, conflicts_with = "deletable")]
+        #[serde(default)]
+        permanent: bool,
         /// Whether to put the blob into a shared blob object.
         #[clap(long, action)]
         #[serde(default)]
         share: bool,
+        /// Push the metadata of the blob to all storage nodes of the committee.
+        ///
+        /// By default, the metadata is only sent to the nodes that store slivers of the blob. With
+        /// this flag, it is also sent to the remaining nodes, such that they do not need to
+        /// recover it from their peers.
+        #[clap(long, action)]
+        #[serde(default)]
+        push_metadata_to_all_nodes: bool,
+        /// The encoding type to use for encoding the files.
+        #[clap(long, hide = true)]
+        #[serde(default)]
+        encoding_type: Option<EncodingType>,
+    },
+    /// Store a directory tree on Walrus, such that its files can be served by aggregators.
+    ///
+    /// Each file is stored as a separate blob. In addition, a manifest blob is stored that maps
+    /// the path of each file relative to the directory to its blob ID and content type. Files can
+    /// then be read from aggregators at `/v1/sites/<manifest blob ID>/<path>`.
+    StoreDir {
+        /// The directory to be stored on Walrus.
+        #[serde(deserialize_with = "walrus_utils::config::resolve_home_dir")]
+        directory: PathBuf,
+        /// The epoch argument to specify either the number of epochs to store the blobs, or the
+        /// end epoch, or the earliest expiry time in rfc3339 format.
+        #[clap(flatten)]
+        #[serde(flatten)]
+        epoch_arg: EpochArg,
+        /// Do not check for the blob status before storing the blobs.
+        ///
+        /// This will create new blobs even if they are already certified for a sufficient
+        /// duration.
+        #[clap(long, action)]
+        #[serde(default)]
+        force: bool,
+        /// Ignore the storage resources owned by the wallet.
+        ///
+        /// The client will not check if it can reuse existing resources, and just check the blob
+        /// status on chain.
+        #[clap(long, action)]
+        #[serde(default)]
+        ignore_resources: bool,
+        /// Mark the blobs as deletable.
+        ///
+        /// Deletable blobs can be removed from Walrus before their expiration time.
+        #[clap(long, action)]
+        #[serde(default)]
+        deletable: bool,
+        /// Mark the blobs as permanent.
+        ///
+        /// Permanent blobs cannot be removed from Walrus before their expiration time. This is the
+        /// default if `--deletable` is not specified.
+        #[clap(long, action, conflicts_with = "deletable")]
+        #[serde(default)]
+        permanent: bool,
+        /// Whether to put the blobs into shared blob objects.
+        #[clap(long, action)]
+        #[serde(default)]
+        share: bool,
+        /// Push the metadata of the blob to all storage nodes of the committee.
+        ///
+        /// By default, the metadata is only sent to the nodes that store slivers of the blob. With
+        /// this flag, it is also sent to the remaining nodes, such that they do not need to
+        /// recover it from their peers.
+        #[clap(long, action)]
+        #[serde(default)]
+        push_metadata_to_all_nodes: bool,
+        /// The encoding type to use for encoding the files.
+        #[clap(long, hide = true)]
+        #[serde(default)]
+        encoding_type: Option<EncodingType>,
+    },
+    /// Store many files matching a glob pattern on Walrus.
+    ///
+    /// The files are stored in batches, several of which are stored concurrently. The files in a
+    /// batch share the transactions reserving storage for, registering, and certifying their blobs.
+    /// Files with identical contents, and therefore identical blob IDs, are only stored once.
+    /// Failed batches are retried, and a summary with the blob ID, cost, and status of each file is
+    /// printed at the end.
+    StoreMany {
+        /// The glob pattern matching the files to store, e.g., `"images/**/*.png"`.
+        ///
+        /// Quote the pattern to prevent the shell from expanding it.
+        pattern: String,
+        /// The epoch argument to specify either the number of epochs to store the blobs, or the
+        /// end epoch, or the earliest expiry time in rfc3339 format.
+        #[clap(flatten)]
+        #[serde(flatten)]
+        epoch_arg: EpochArg,
+        /// Do not check for the blob status before storing the blobs.
+        ///
+        /// This will create new blobs even if they are already certified for a sufficient
+        /// duration.
+        #[clap(long, action)]
+        #[serde(default)]
+        force: bool,
+        /// Ignore the storage resources owned by the wallet.
+        ///
+        /// The client will not check if it can reuse existing resources, and just check the blob
+        /// status on chain.
+        #[clap(long, action)]
+        #[serde(default)]
+        ignore_resources: bool,
+        /// Mark the blobs as deletable.
+        ///
+        /// Deletable blobs can be removed from Walrus before their expiration time.
+        #[clap(long, action)]
+        #[serde(default)]
+        deletable: bool,
+        /// Mark the blobs as permanent.
+        ///
+        /// Permanent blobs cannot be removed from Walrus before their expiration time. This is the
+        /// default if `--deletable` is not specified.
+        #[clap(long, action, conflicts_with = "deletable")]
+        #[serde(default)]
+        permanent: bool,
+        /// Whether to put the blobs into shared blob objects.
+        #[clap(long, action)]
+        #[serde(default)]
+        share: bool,
+        /// Push the metadata of the blob to all storage nodes of the committee.
+        ///
+        /// By default, the metadata is only sent to the nodes that store slivers of the blob. With
+        /// this flag, it is also sent to the remaining nodes, such that they do not need to
+        /// recover it from their peers.
+        #[clap(long, action)]
+        #[serde(default)]
+        push_metadata_to_all_nodes: bool,
         /// The encoding type to use for encoding the files.
         #[clap(long, hide = true)]
         #[serde(default)]
         encoding_type: Option<EncodingType>,
+        /// The arguments controlling the batches in which the files are stored.
+        #[clap(flatten)]
+        #[serde(flatten)]
+        schedule: StoreManySchedule,
     },
     /// Read a blob from Walrus, given the blob ID.
     Read {
         /// The blob ID to be read.
-        #[serde_as(as = "DisplayFromStr")]
+        #[serde_as(as = "BlobIdFromAnyEncoding")]
         #[clap(allow_hyphen_values = true, value_parser = parse_blob_id)]
         blob_id: BlobId,
         /// The file path where to write the blob.
@@ -307,10 +452,19 @@ pub enum CliCommands {
         #[command(subcommand)]
         command: Option<InfoCommands>,
     },
-    /// Print health information for one or multiple storage nodes.
+    /// Print health information for one or multiple storage nodes, or the availability of a blob.
     ///
     /// Only one of `--node_ids`, `--node_urls`, `--committee`, and `--active_set` can be specified.
+    ///
+    /// If a blob ID is specified instead, all members of the current committee are queried for
+    /// whether they store the blob's metadata and its slivers for their shards, and it is reported
+    /// whether enough slivers are available to read the blob.
     Health {
+        /// The blob ID of the blob to check the availability of.
+        #[clap(allow_hyphen_values = true, value_parser = parse_blob_id)]
+        #[serde_as(as = "Option<BlobIdFromAnyEncoding>")]
+        #[serde(default)]
+        blob_id: Option<BlobId>,
         /// The URL of the Sui RPC node to use.
         #[clap(flatten)]
         #[serde(flatten)]
@@ -328,11 +482,16 @@ pub enum CliCommands {
         #[serde(flatten)]
         sort: SortBy<HealthSortBy>,
     },
-    /// Encode the specified file to obtain its blob ID.
+    /// Encode the specified file to obtain its blob ID and encoded length.
+    #[clap(alias("compute-blob-id"))]
     BlobId {
         /// The file containing the blob for which to compute the blob ID.
         #[serde(deserialize_with = "walrus_utils::config::resolve_home_dir")]
         file: PathBuf,
+        /// Also output the roots of the Merkle trees over the primary and secondary slivers.
+        #[clap(long, action)]
+        #[serde(default)]
+        sliver_roots: bool,
         /// The number of shards for which to compute the blob ID.
         ///
         /// If not specified, the number of shards is read from chain.
@@ -348,11 +507,16 @@ pub enum CliCommands {
         #[serde(default)]
         encoding_type: Option<EncodingType>,
     },
-    /// Convert a decimal value to the Walrus blob ID (using URL-safe base64 encoding).
+    /// Convert a blob ID to all supported encodings.
+    ///
+    /// The blob ID can be given as URL-safe base64 (the encoding used by Walrus), as hex, or as a
+    /// decimal number (as shown by some Sui explorers).
     ConvertBlobId {
-        /// The decimal value to be converted to the Walrus blob ID.
-        #[serde_as(as = "DisplayFromStr")]
-        blob_id_decimal: BlobIdDecimal,
+        /// The blob ID to be converted.
+        #[serde_as(as = "BlobIdFromAnyEncoding")]
+        #[serde(alias = "blobIdDecimal")]
+        #[clap(allow_hyphen_values = true, value_parser = parse_blob_id)]
+        blob_id: BlobId,
     },
     /// List all registered blobs for the current wallet.
     ListBlobs {
@@ -361,6 +525,28 @@ pub enum CliCommands {
         /// The output list of blobs will include expired blobs.
         include_expired: bool,
     },
+    /// Print the Walrus contract events, starting with the events emitted in a Sui checkpoint.
+    ///
+    /// This allows backfilling or auditing events for which no event cursor is known, for example
+    /// because the original cursor has been pruned. The Sui RPC node must still have the events of
+    /// the checkpoint available.
+    ReplayEvents {
+        /// The sequence number of the Sui checkpoint from which to replay the events.
+        #[clap(long)]
+        from_checkpoint: u64,
+        /// The maximum number of events to print.
+        #[clap(long, default_value_t = default::replay_events_limit())]
+        #[serde(default = "default::replay_events_limit")]
+        limit: usize,
+        /// Stop once no new event has been received for this long.
+        #[clap(long, value_parser = humantime::parse_duration, default_value = "10s")]
+        #[serde(default = "default::replay_events_idle_timeout")]
+        idle_timeout: Duration,
+        /// The URL of the Sui RPC node to use.
+        #[clap(flatten)]
+        #[serde(flatten)]
+        rpc_arg: RpcArg,
+    },
     /// Delete a blob from Walrus.
     ///
     /// This command is only available for blobs that are deletable.
@@ -400,6 +586,26 @@ pub enum CliCommands {
         #[serde(default = "default::staking_amounts_frost")]
         amounts: Vec<u64>,
     },
+    /// Unstake WAL that was staked with a storage node.
+    ///
+    /// If the stake can be withdrawn immediately, e.g., because it is not yet active or its
+    /// withdrawal was previously requested and the withdrawal epoch is reached, the WAL is returned
+    /// to the wallet. Otherwise, the withdrawal is requested and the command must be run again
+    /// once the withdrawal epoch is reached.
+    Unstake {
+        /// The object ID of the staked WAL object.
+        #[clap(long)]
+        staked_wal_id: ObjectID,
+    },
+    /// List the staked WAL objects owned by the wallet.
+    ListStake {
+        /// The owner of the staked WAL objects.
+        ///
+        /// If not specified, the address of the wallet is used.
+        #[clap(long)]
+        #[serde(default)]
+        owner: Option<SuiAddress>,
+    },
     /// Generates a new Sui wallet.
     GenerateSuiWallet {
         /// The path where the wallet configuration will be stored.
@@ -489,6 +695,35 @@ pub enum CliCommands {
         #[clap(long)]
         epochs_extended: EpochCount,
     },
+    /// Report the wallet's blobs that expire soon, and renew them according to a policy.
+    ///
+    /// Only certified blobs are reported, as other blobs cannot be extended. If a policy file is
+    /// provided, the reported blobs are extended according to the policy, preferably with owned
+    /// storage resources that start in the epoch in which the blob expires, and otherwise by paying
+    /// WAL.
+    Renew {
+        /// Report the blobs that expire within this number of epochs.
+        #[clap(long, default_value_t = default::renew_within_epochs())]
+        #[serde(default = "default::renew_within_epochs")]
+        within_epochs: EpochCount,
+        /// The path to the YAML file with the renewal policy.
+        ///
+        /// The policy can specify the number of epochs by which blobs are extended
+        /// (`epochs_extended`), whether deletable blobs are renewed (`renew_deletable`), whether
+        /// owned storage resources are used (`use_owned_storage`), whether WAL is paid if no
+        /// suitable storage resource is owned (`pay_with_wal`), and the maximum amount of FROST
+        /// spent per run (`max_total_cost`). If not provided, the blobs are only reported.
+        #[clap(long)]
+        #[serde(
+            default,
+            deserialize_with = "walrus_utils::config::resolve_home_dir_option"
+        )]
+        policy: Option<PathBuf>,
+        /// Proceed to renew the blobs without confirmation.
+        #[clap(long, action)]
+        #[serde(default)]
+        yes: bool,
+    },
     /// Share a blob.
     Share {
         /// The object ID of the (owned) blob to share.
@@ -611,6 +846,26 @@ pub enum NodeAdminCommands {
         /// The object or address to set as authorized entity.
         object_or_address: ObjectOrAddress,
     },
+    /// Update the name, network address, or commission rate of the node on chain.
+    ///
+    /// Must be run with the wallet that owns the storage node capability of the node. After the
+    /// update, the on-chain state of the node is read back to verify that the update was applied.
+    UpdateNodeParams {
+        /// The new name of the node.
+        #[clap(long)]
+        #[serde(default)]
+        name: Option<String>,
+        /// The new public network address of the node, in the format `host:port`.
+        #[clap(long)]
+        #[serde(default)]
+        network_address: Option<String>,
+        /// The new commission rate of the node, in basis points (1% = 100 basis points).
+        ///
+        /// Changes to the commission rate take effect with a delay of two epochs.
+        #[clap(long)]
+        #[serde(default)]
+        commission_rate: Option<u16>,
+    },
 }
 
 #[derive(Debug, Clone, Args, Deserialize, PartialEq, Eq)]
@@ -685,6 +940,21 @@ pub enum DaemonCommands {
         /// The aggregator args.
         aggregator_args: AggregatorArgs,
     },
+    /// Run a publisher service that does not hold a wallet.
+    ///
+    /// The service encodes uploaded blobs and distributes them to the storage nodes, but returns
+    /// the transactions to register and certify the blobs unsigned, such that callers sign and
+    /// execute them with their own wallets and pay for storage themselves.
+    UnsignedPublisher {
+        #[clap(flatten)]
+        #[serde(flatten)]
+        /// The URL of the Sui RPC node to use.
+        rpc_arg: RpcArg,
+        #[clap(flatten)]
+        #[serde(flatten)]
+        /// The unsigned publisher args.
+        args: UnsignedPublisherArgs,
+    },
 }
 
 impl DaemonCommands {
@@ -694,6 +964,7 @@ impl DaemonCommands {
             DaemonCommands::Publisher { args } => args.daemon_args.metrics_address,
             DaemonCommands::Aggregator { daemon_args, .. } => daemon_args.metrics_address,
             DaemonCommands::Daemon { args, .. } => args.daemon_args.metrics_address,
+            DaemonCommands::UnsignedPublisher { args, .. } => args.daemon_args.metrics_address,
         }
     }
 }
@@ -711,6 +982,61 @@ pub struct AggregatorArgs {
     #[clap(long, num_args = 1.., default_values_t = default::allowed_headers())]
     #[serde(default = "default::allowed_headers")]
     pub(crate) allowed_headers: Vec<String>,
+    /// Allowed content types for the daemon.
+    ///
+    /// A `content-type` attribute of a blob is only returned as header if its media type is in
+    /// this list; otherwise, the blob is served as `application/octet-stream`. Entries of the form
+    /// `type/*` allow all subtypes. `text/html` is allowed by default, such that stored sites can
+    /// be browsed; other types that browsers may execute, such as `image/svg+xml`, should only be
+    /// added if the aggregator's origin does not serve other content.
+    #[clap(long, num_args = 1.., default_values_t = default::allowed_content_types())]
+    #[serde(default = "default::allowed_content_types")]
+    pub(crate) allowed_content_types: Vec<String>,
+    /// Directory in which to persistently cache reconstructed blobs.
+    ///
+    /// Repeated reads of cached blobs are served from local disk. If not set, blobs are not
+    /// cached.
+    #[clap(long)]
+    #[serde(
+        default,
+        deserialize_with = "walrus_utils::config::resolve_home_dir_option"
+    )]
+    pub(crate) blob_cache_dir: Option<PathBuf>,
+    /// The maximum total size, in bytes, of the blobs held in the blob cache.
+    ///
+    /// When this size is exceeded, the least recently read blobs are evicted.
+    #[clap(long, default_value_t = default::blob_cache_max_size())]
+    #[serde(default = "default::blob_cache_max_size")]
+    pub(crate) blob_cache_max_size: u64,
+    /// Directory in which to persist an index of the attributes of blob objects.
+    ///
+    /// If set, the aggregator follows the events of the Walrus contracts to index the attributes
+    /// of certified blob objects and exposes the `/v1/blobs?attr=key:value` endpoint to query
+    /// blobs by attribute.
+    #[clap(long)]
+    #[serde(
+        default,
+        deserialize_with = "walrus_utils::config::resolve_home_dir_option"
+    )]
+    pub(crate) attribute_index_dir: Option<PathBuf>,
+    /// The maximum number of blobs that can be requested at once from the `/v1/blobs:batchGet`
+    /// endpoint.
+    #[clap(long, default_value_t = default::max_batch_get_blobs())]
+    #[serde(default = "default::max_batch_get_blobs")]
+    pub(crate) max_batch_get_blobs: usize,
+    /// The maximum number of blobs of a request to the `/v1/blobs:batchGet` endpoint that are read
+    /// concurrently.
+    #[clap(long, default_value_t = default::max_concurrent_batch_get_reads())]
+    #[serde(default = "default::max_concurrent_batch_get_reads")]
+    pub(crate) max_concurrent_batch_get_reads: usize,
+    /// The maximum total size, in bytes, of the blobs requested at once from the
+    /// `/v1/blobs:batchGet` endpoint.
+    ///
+    /// The sizes of the blobs are looked up before any of them is read, and larger batches are
+    /// rejected.
+    #[clap(long, default_value_t = default::max_batch_get_size())]
+    #[serde(default = "default::max_batch_get_size")]
+    pub(crate) max_batch_get_size: u64,
 }
 
 /// The arguments for the publisher service.
@@ -766,6 +1092,29 @@ pub struct PublisherArgs {
     #[clap(long, default_value_t = default::sub_wallets_min_balance())]
     #[serde(default = "default::sub_wallets_min_balance")]
     pub sub_wallets_min_balance: u64,
+    /// The interval of time between checks of the main wallet's SUI and WAL balances.
+    ///
+    /// The balances are exported as metrics and logged as warnings or errors when they fall below
+    /// the thresholds below.
+    #[clap(long, value_parser = humantime::parse_duration, default_value = "10m")]
+    #[serde(default = "default::balance_check_interval")]
+    pub balance_check_interval: Duration,
+    /// The SUI balance of the main wallet (in MIST) below which a warning is logged.
+    #[clap(long, default_value_t = default::sui_balance_warning_threshold())]
+    #[serde(default = "default::sui_balance_warning_threshold")]
+    pub sui_balance_warning_threshold: u64,
+    /// The SUI balance of the main wallet (in MIST) below which an error is logged.
+    #[clap(long)]
+    #[serde(default)]
+    pub sui_balance_error_threshold: Option<u64>,
+    /// The WAL balance of the main wallet (in FROST) below which a warning is logged.
+    #[clap(long)]
+    #[serde(default)]
+    pub wal_balance_warning_threshold: Option<u64>,
+    /// The WAL balance of the main wallet (in FROST) below which an error is logged.
+    #[clap(long)]
+    #[serde(default)]
+    pub wal_balance_error_threshold: Option<u64>,
     /// Deprecated flag for backwards compatibility.
     ///
     /// By default, the publisher already keeps created Blob objects in its main wallet. This flag
@@ -822,10 +1171,53 @@ pub struct PublisherArgs {
     #[clap(long, action)]
     #[serde(default)]
     pub jwt_verify_upload: bool,
+    /// The path to a YAML file with per-subject quotas on uploads.
+    ///
+    /// If set, JWT tokens must specify a subject (`sub`), and the publisher limits the maximum blob
+    /// size, the maximum number of epochs, and the number of bytes stored per UTC day for each
+    /// subject. The file contains a `default_quota` and optional per-subject overrides in
+    /// `subjects`, each with the optional fields `max_bytes_per_day`, `max_blob_size`, and
+    /// `max_epochs`.
+    #[clap(long)]
+    #[serde(default)]
+    pub jwt_quota_config: Option<PathBuf>,
     #[clap(flatten)]
     #[serde(flatten)]
     /// The configuration for the JWT duplicate suppression cache.
     pub replay_suppression_config: CacheConfig,
+    /// Directory in which to persist store jobs.
+    ///
+    /// If set, the publisher additionally exposes the `/v1/jobs` endpoints, which accept blobs to
+    /// be stored in the background and report the status of each job. Jobs that have not finished
+    /// when the publisher stops are resumed on the next start.
+    #[clap(long)]
+    #[serde(
+        default,
+        deserialize_with = "walrus_utils::config::resolve_home_dir_option"
+    )]
+    pub job_queue_dir: Option<PathBuf>,
+    /// The maximum number of store jobs the publisher processes concurrently.
+    #[clap(long, default_value_t = default::max_concurrent_jobs())]
+    #[serde(default = "default::max_concurrent_jobs")]
+    pub max_concurrent_jobs: usize,
+    /// The time for which the record of a completed or failed store job is kept.
+    ///
+    /// Records of finished jobs are removed once they have not changed for this long, after which
+    /// their status can no longer be polled.
+    #[clap(long, value_parser = humantime::parse_duration, default_value = "7days")]
+    #[serde(default = "default::job_record_ttl")]
+    pub job_record_ttl: Duration,
+    /// The path to a P-256 private key in PKCS#8 PEM format with which to sign store receipts.
+    ///
+    /// The publisher returns a receipt with the blob ID, blob object ID, certification event, and
+    /// epochs of each stored blob. If set, the receipts are signed with this key, such that
+    /// downstream systems can verify them without querying Sui.
+    #[clap(long)]
+    #[serde(
+        default,
+        deserialize_with = "walrus_utils::config::resolve_home_dir_option"
+    )]
+    pub receipt_signing_key: Option<PathBuf>,
 }
 
 impl PublisherArgs {
@@ -833,6 +1225,20 @@ impl PublisherArgs {
         self.max_body_size_kib << 10
     }
 
+    pub(crate) fn balance_monitor_config(&self) -> BalanceMonitorConfig {
+        BalanceMonitorConfig {
+            interval: self.balance_check_interval,
+            sui: BalanceThresholds {
+                warning: Some(self.sui_balance_warning_threshold),
+                error: self.sui_balance_error_threshold,
+            },
+            wal: BalanceThresholds {
+                warning: self.wal_balance_warning_threshold,
+                error: self.wal_balance_error_threshold,
+            },
+        }
+    }
+
     fn format_max_body_size(&self) -> String {
         format!(
             "{}",
@@ -853,12 +1259,22 @@ impl PublisherArgs {
     }
 
     pub(crate) fn generate_auth_config(&self) -> Result<Option<AuthConfig>> {
-        if self.jwt_decode_secret.is_some() || self.jwt_expiring_sec > 0 || self.jwt_verify_upload {
+        if self.jwt_decode_secret.is_some()
+            || self.jwt_expiring_sec > 0
+            || self.jwt_verify_upload
+            || self.jwt_quota_config.is_some()
+        {
             let mut auth_config = AuthConfig {
                 expiring_sec: self.jwt_expiring_sec,
                 verify_upload: self.jwt_verify_upload,
                 algorithm: self.jwt_algorithm,
                 replay_suppression_config: self.replay_suppression_config.clone(),
+                quota_config: self
+                    .jwt_quota_config
+                    .as_ref()
+                    .map(QuotaConfig::load)
+                    .transpose()
+                    .context("failed to load the JWT quota configuration")?,
                 ..Default::default()
             };
 
@@ -875,6 +1291,52 @@ impl PublisherArgs {
     }
 }
 
+/// The arguments for the unsigned publisher service.
+#[derive(Debug, Clone, Args, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedPublisherArgs {
+    /// The configuration for the daemon.
+    #[clap(flatten)]
+    #[serde(flatten)]
+    pub daemon_args: DaemonArgs,
+    /// The maximum body size of PUT requests in KiB.
+    #[clap(long = "max-body-size", default_value_t = default::max_body_size_kib())]
+    #[serde(default = "default::max_body_size_kib")]
+    pub max_body_size_kib: usize,
+    /// The maximum number of requests the publisher can process concurrently.
+    ///
+    /// Any request exceeding this maximum results in a response with a 429 HTTP status code.
+    #[clap(long, default_value_t = default::max_concurrent_requests())]
+    #[serde(default = "default::max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// The maximum number of encoded blobs kept in memory while waiting for their registration.
+    #[clap(long, default_value_t = default::max_pending_uploads())]
+    #[serde(default = "default::max_pending_uploads")]
+    pub max_pending_uploads: usize,
+    /// The maximum total size in MiB of the encoded blobs kept in memory while waiting for their
+    /// registration.
+    #[clap(
+        long = "max-pending-uploads-size",
+        default_value_t = default::max_pending_uploads_size_mib()
+    )]
+    #[serde(default = "default::max_pending_uploads_size_mib")]
+    pub max_pending_uploads_size_mib: u64,
+    /// The time for which an encoded blob is kept in memory while waiting for its registration.
+    #[clap(long, value_parser = humantime::parse_duration, default_value = "10m")]
+    #[serde(default = "default::pending_upload_ttl")]
+    pub pending_upload_ttl: Duration,
+}
+
+impl UnsignedPublisherArgs {
+    pub(crate) fn max_body_size(&self) -> usize {
+        self.max_body_size_kib << 10
+    }
+
+    pub(crate) fn max_pending_uploads_size(&self) -> u64 {
+        self.max_pending_uploads_size_mib << 20
+    }
+}
+
 /// The URL of the Sui RPC node to use.
 #[derive(Default, Debug, Clone, Args, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -907,6 +1369,14 @@ pub struct DaemonArgs {
         deserialize_with = "walrus_utils::config::resolve_home_dir_option"
     )]
     pub(crate) blocklist: Option<PathBuf>,
+    /// The CORS policy of the service.
+    #[clap(flatten)]
+    #[serde(flatten)]
+    pub(crate) cors_config: CorsConfig,
+    /// The TLS configuration of the service.
+    #[clap(flatten)]
+    #[serde(flatten)]
+    pub(crate) tls_config: TlsConfig,
 }
 
 #[serde_as]
@@ -920,7 +1390,7 @@ pub struct FileOrBlobId {
     pub(crate) file: Option<PathBuf>,
     /// The blob ID to be checked.
     #[clap(long, allow_hyphen_values = true, value_parser = parse_blob_id)]
-    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde_as(as = "Option<BlobIdFromAnyEncoding>")]
     #[serde(default)]
     pub(crate) blob_id: Option<BlobId>,
 }
@@ -1008,7 +1478,7 @@ pub struct BlobIdentifiers {
         alias = "blob-id",
         action = clap::ArgAction::Append
     )]
-    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde_as(as = "Vec<BlobIdFromAnyEncoding>")]
     #[serde(default)]
     pub(crate) blob_ids: Vec<BlobId>,
     /// The object ID of the blob object to be deleted.
@@ -1123,7 +1593,7 @@ impl BurnSelection {
 #[serde_as]
 #[derive(Debug, Clone, Args, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
-#[group(required = true, multiple = false)]
+#[group(multiple = false)]
 pub struct NodeSelection {
     /// The IDs of the storage nodes to be selected.
     #[clap(long, alias="node-id", num_args=1..)]
@@ -1144,6 +1614,11 @@ pub struct NodeSelection {
 }
 
 impl NodeSelection {
+    /// Returns true if none of the node selection options is set.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.node_ids.is_empty() && self.node_urls.is_empty() && !self.committee && !self.active_set
+    }
+
     /// Checks that exactly one of the node selection options is set and returns an error otherwise.
     pub(crate) fn exactly_one_is_set(&self) -> Result<()> {
         match (
@@ -1245,6 +1720,24 @@ impl EpochCountOrMax {
     }
 }
 
+/// The arguments controlling the batches in which `store-many` stores files.
+#[derive(Debug, Clone, Args, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreManySchedule {
+    /// The maximum number of files stored in a single batch.
+    #[clap(long, default_value_t = default::store_many_batch_size())]
+    #[serde(default = "default::store_many_batch_size")]
+    pub batch_size: NonZeroUsize,
+    /// The maximum number of batches stored concurrently.
+    #[clap(long, default_value_t = default::store_many_parallelism())]
+    #[serde(default = "default::store_many_parallelism")]
+    pub parallelism: NonZeroUsize,
+    /// The number of times a batch is retried after it failed to be stored.
+    #[clap(long, default_value_t = default::store_many_max_retries())]
+    #[serde(default = "default::store_many_max_retries")]
+    pub max_retries: u32,
+}
+
 /// The number of epochs to store the blob for.
 #[serde_as]
 #[derive(Debug, Clone, Args, Deserialize, PartialEq, Eq)]
@@ -1285,7 +1778,7 @@ impl EpochArg {
 }
 
 pub(crate) mod default {
-    use std::{net::SocketAddr, time::Duration};
+    use std::{net::SocketAddr, num::NonZeroUsize, time::Duration};
 
     use walrus_sui::utils::SuiNetwork;
 
@@ -1306,6 +1799,38 @@ pub(crate) mod default {
         max_concurrent_requests()
     }
 
+    pub(crate) fn max_concurrent_jobs() -> usize {
+        max_concurrent_requests()
+    }
+
+    pub(crate) fn job_record_ttl() -> Duration {
+        Duration::from_secs(7 * 24 * 60 * 60)
+    }
+
+    pub(crate) fn store_many_batch_size() -> NonZeroUsize {
+        NonZeroUsize::new(16).expect("16 is not zero")
+    }
+
+    pub(crate) fn store_many_parallelism() -> NonZeroUsize {
+        NonZeroUsize::new(4).expect("4 is not zero")
+    }
+
+    pub(crate) fn store_many_max_retries() -> u32 {
+        2
+    }
+
+    pub(crate) fn max_pending_uploads() -> usize {
+        100
+    }
+
+    pub(crate) fn max_pending_uploads_size_mib() -> u64 {
+        8 * 1024
+    }
+
+    pub(crate) fn pending_upload_ttl() -> Duration {
+        Duration::from_secs(600)
+    }
+
     pub(crate) fn n_publisher_clients() -> usize {
         // Use the same number of clients as the number of concurrent requests. This way, the
         // publisher will have the lowest possible latency for every request.
@@ -1328,10 +1853,26 @@ pub(crate) mod default {
         Duration::from_secs(1)
     }
 
+    pub(crate) fn balance_check_interval() -> Duration {
+        Duration::from_secs(600)
+    }
+
+    pub(crate) fn sui_balance_warning_threshold() -> u64 {
+        5_000_000_000 // 5 SUI
+    }
+
     pub(crate) fn status_timeout() -> Duration {
         Duration::from_secs(10)
     }
 
+    pub(crate) fn replay_events_limit() -> usize {
+        1_000
+    }
+
+    pub(crate) fn replay_events_idle_timeout() -> Duration {
+        Duration::from_secs(10)
+    }
+
     pub(crate) fn bind_address() -> SocketAddr {
         "127.0.0.1:31415"
             .parse()
@@ -1360,6 +1901,26 @@ pub(crate) mod default {
         Duration::from_secs(60)
     }
 
+    pub(crate) fn blob_cache_max_size() -> u64 {
+        10 * 1024 * 1024 * 1024
+    }
+
+    pub(crate) fn renew_within_epochs() -> EpochCount {
+        2
+    }
+
+    pub(crate) fn max_batch_get_blobs() -> usize {
+        100
+    }
+
+    pub(crate) fn max_concurrent_batch_get_reads() -> usize {
+        8
+    }
+
+    pub(crate) fn max_batch_get_size() -> u64 {
+        100 * 1024 * 1024
+    }
+
     pub(crate) fn allowed_headers() -> Vec<String> {
         vec![
             "content-type".to_string(),
@@ -1369,7 +1930,27 @@ pub(crate) mod default {
             "content-language".to_string(),
             "content-location".to_string(),
             "link".to_string(),
+            "cache-control".to_string(),
+        ]
+    }
+
+    pub(crate) fn allowed_content_types() -> Vec<String> {
+        [
+            "text/plain",
+            "text/html",
+            "application/json",
+            "application/pdf",
+            "image/png",
+            "image/jpeg",
+            "image/gif",
+            "image/webp",
+            "image/avif",
+            "audio/*",
+            "video/*",
+            "font/*",
         ]
+        .map(String::from)
+        .to_vec()
     }
 }
 
@@ -1387,6 +1968,7 @@ mod tests {
     const READ_STR: &str = r#"{"read": {"blobId": "4BKcDC0Ih5RJ8R0tFMz3MZVNZV8b2goT6_JiEEwNHQo"}}"#;
     const DAEMON_STR: &str =
         r#"{"daemon": {"bindAddress": "127.0.0.1:12345", "subWalletsDir": "/some/path"}}"#;
+    const REPLAY_EVENTS_STR: &str = r#"{"replayEvents": {"fromCheckpoint": 42}}"#;
 
     // Creates the fixture for the JSON command string.
     fn make_cmd_str(command: &str) -> String {
@@ -1412,7 +1994,9 @@ mod tests {
             force: false,
             ignore_resources: false,
             deletable: false,
+            permanent: false,
             share: false,
+            push_metadata_to_all_nodes: false,
             encoding_type: Default::default(),
         })
     }
@@ -1426,6 +2010,16 @@ mod tests {
         })
     }
 
+    // Fixture for the replay-events command.
+    fn replay_events_command() -> Commands {
+        Commands::Cli(CliCommands::ReplayEvents {
+            from_checkpoint: 42,
+            limit: default::replay_events_limit(),
+            idle_timeout: default::replay_events_idle_timeout(),
+            rpc_arg: RpcArg { rpc_url: None },
+        })
+    }
+
     // Fixture for the daemon command.
     fn daemon_command() -> Commands {
         Commands::Daemon(DaemonCommands::Daemon {
@@ -1435,6 +2029,8 @@ mod tests {
                     bind_address: SocketAddr::from_str("127.0.0.1:12345").unwrap(),
                     metrics_address: default::metrics_address(),
                     blocklist: None,
+                    cors_config: Default::default(),
+                    tls_config: Default::default(),
                 },
                 max_body_size_kib: default::max_body_size_kib(),
                 max_request_buffer_size: default::max_request_buffer_size(),
@@ -1444,16 +2040,33 @@ mod tests {
                 gas_refill_amount: default::gas_refill_amount(),
                 wal_refill_amount: default::wal_refill_amount(),
                 sub_wallets_min_balance: default::sub_wallets_min_balance(),
+                balance_check_interval: default::balance_check_interval(),
+                sui_balance_warning_threshold: default::sui_balance_warning_threshold(),
+                sui_balance_error_threshold: None,
+                wal_balance_warning_threshold: None,
+                wal_balance_error_threshold: None,
                 keep: false,
                 burn_after_store: false,
                 jwt_decode_secret: None,
                 jwt_algorithm: None,
                 jwt_expiring_sec: 0,
                 jwt_verify_upload: false,
+                jwt_quota_config: None,
                 replay_suppression_config: Default::default(),
+                job_queue_dir: None,
+                max_concurrent_jobs: default::max_concurrent_jobs(),
+                job_record_ttl: default::job_record_ttl(),
+                receipt_signing_key: None,
             },
             aggregator_args: AggregatorArgs {
                 allowed_headers: default::allowed_headers(),
+                allowed_content_types: default::allowed_content_types(),
+                blob_cache_dir: None,
+                blob_cache_max_size: default::blob_cache_max_size(),
+                attribute_index_dir: None,
+                max_batch_get_blobs: default::max_batch_get_blobs(),
+                max_concurrent_batch_get_reads: default::max_concurrent_batch_get_reads(),
+                max_batch_get_size: default::max_batch_get_size(),
             },
         })
     }
@@ -1466,6 +2079,7 @@ mod tests {
                 store_command(EpochCountOrMax::Epochs(NonZeroU32::new(1).expect("1 > 0")))
             ),
             read: (&make_cmd_str(READ_STR), read_command()),
+            replay_events: (&make_cmd_str(REPLAY_EVENTS_STR), replay_events_command()),
             daemon: (&make_cmd_str(DAEMON_STR), daemon_command())
         ]
     }
@@ -1485,6 +2099,48 @@ mod tests {
         assert_eq!(app.command, command);
         Ok(())
     }
+
+    #[test]
+    fn replay_events_defaults_match_json_defaults() -> TestResult {
+        let app = App::try_parse_from(["walrus", "replay-events", "--from-checkpoint", "42"])?;
+        assert_eq!(app.command, replay_events_command());
+        Ok(())
+    }
+
+    #[test]
+    fn store_rejects_deletable_and_permanent() {
+        for args in [
+            [
+                "walrus",
+                "store",
+                "--epochs",
+                "1",
+                "--deletable",
+                "--permanent",
+                "README.md",
+            ],
+            [
+                "walrus",
+                "store-dir",
+                "--epochs",
+                "1",
+                "--deletable",
+                "--permanent",
+                "docs",
+            ],
+        ] {
+            assert!(App::try_parse_from(args).is_err());
+        }
+        assert!(App::try_parse_from([
+            "walrus",
+            "store",
+            "--epochs",
+            "1",
+            "--permanent",
+            "README.md"
+        ])
+        .is_ok());
+    }
 }
 
 /// Specifies whether the user has granted the confirmation for the action, or if it is required.
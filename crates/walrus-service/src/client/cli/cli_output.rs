@@ -5,13 +5,14 @@ use std::{io::stdout, num::NonZeroU16, path::PathBuf};
 
 use anyhow::Result;
 use colored::Colorize;
+use fastcrypto::encoding::{Base64, Encoding as _};
 use indoc::printdoc;
 use itertools::Itertools as _;
 use prettytable::{format, row, Table};
 use serde::Serialize;
 use walrus_core::{BlobId, ShardIndex};
 use walrus_sdk::api::{BlobStatus, DeletableCounts, EventProgress};
-use walrus_sui::types::Blob;
+use walrus_sui::types::{Blob, ContractEvent, StakedWal};
 
 use super::warning;
 use crate::client::{
@@ -25,8 +26,10 @@ use crate::client::{
         HumanReadableMist,
         WalrusColors,
     },
+    renewal::RenewalMethod,
     resource::RegisterBlobOp,
     responses::{
+        BlobAvailability,
         BlobIdConversionOutput,
         BlobIdOutput,
         BlobStatusOutput,
@@ -49,10 +52,14 @@ use crate::client::{
         InfoStorageOutput,
         NodeHealthOutput,
         ReadOutput,
+        RenewOutput,
         ServiceHealthInfoOutput,
         ShareBlobOutput,
         StakeOutput,
         StorageNodeInfo,
+        StoreDirOutput,
+        StoreManyOutput,
+        StoreManyStatus,
         WalletOutput,
     },
     BlobStoreResult,
@@ -168,6 +175,7 @@ impl CliOutput for BlobStoreResultWithPath {
                 resource_operation,
                 cost,
                 shared_blob_object,
+                ..
             } => {
                 let operation_str = match resource_operation {
                     RegisterBlobOp::RegisterFromScratch { .. } => {
@@ -227,6 +235,66 @@ impl CliOutput for BlobStoreResultWithPath {
     }
 }
 
+impl CliOutput for StoreDirOutput {
+    fn print_cli_output(&self) {
+        self.files.print_cli_output();
+        println!(
+            "\n{} Directory stored successfully.\n\
+            Manifest blob ID: {}\n\
+            Expiry epoch (exclusive): {}\n\
+            Files can be read from an aggregator at /v1/sites/{}/<path>",
+            success(),
+            self.manifest.blob_id(),
+            self.manifest
+                .end_epoch()
+                .map_or_else(|| "unknown".to_owned(), |epoch| epoch.to_string()),
+            self.manifest.blob_id(),
+        );
+    }
+}
+
+impl CliOutput for StoreManyOutput {
+    fn print_cli_output(&self) {
+        let mut table = Table::new();
+        table.set_format(default_table_format());
+        table.set_titles(row![b->"Path", b->"Blob ID", b->"Cost", b->"Status"]);
+        let mut total_cost = 0;
+        for file in &self.files {
+            total_cost += file.cost;
+            let status = match &file.status {
+                StoreManyStatus::NewlyCreated => "stored".to_owned(),
+                StoreManyStatus::AlreadyCertified => "already certified".to_owned(),
+                StoreManyStatus::Duplicate { original } => {
+                    format!("duplicate of {}", original.display())
+                }
+                StoreManyStatus::MarkedInvalid => "marked invalid".to_owned(),
+                StoreManyStatus::Failed { error } => format!("failed: {error}"),
+            };
+            table.add_row(row![
+                file.path.display(),
+                file.blob_id
+                    .map_or_else(|| "-".to_owned(), |blob_id| blob_id.to_string()),
+                r->HumanReadableFrost::from(file.cost),
+                status,
+            ]);
+        }
+        table.printstd();
+
+        let failed = self.failed_count();
+        let summary = format!(
+            "{} of {} files stored, total cost: {}",
+            self.files.len() - failed,
+            self.files.len(),
+            HumanReadableFrost::from(total_cost)
+        );
+        if failed == 0 {
+            println!("{} {summary}", success());
+        } else {
+            println!("{} {summary}", error());
+        }
+    }
+}
+
 impl CliOutput for ReadOutput {
     fn print_cli_output(&self) {
         if let Some(path) = &self.out {
@@ -245,14 +313,29 @@ impl CliOutput for BlobIdOutput {
         println!(
             "{} Blob from file '{}' encoded successfully.\n\
                 Unencoded size: {}\n\
+                Encoded size (including replicated metadata): {}\n\
                 Blob ID: {}\n\
                 Encoding type: {}",
             success(),
             self.file.display(),
             self.unencoded_length,
+            HumanReadableBytes(self.encoded_length),
             self.blob_id,
             self.encoding_type,
-        )
+        );
+        if let Some(sliver_roots) = &self.sliver_roots {
+            let mut table = Table::new();
+            table.set_format(default_table_format());
+            table.set_titles(row![b->"Sliver pair", b->"Primary root", b->"Secondary root"]);
+            for (index, roots) in sliver_roots.iter().enumerate() {
+                table.add_row(row![
+                    r->index,
+                    Base64::encode(roots.primary),
+                    Base64::encode(roots.secondary),
+                ]);
+            }
+            table.printstd();
+        }
     }
 }
 
@@ -359,7 +442,16 @@ impl CliOutput for BlobStatusOutput {
 
 impl CliOutput for BlobIdConversionOutput {
     fn print_cli_output(&self) {
-        println!("Walrus blob ID: {}", self.0);
+        println!(
+            "{}\n\
+            Walrus blob ID: {}\n\
+            Hex: {}\n\
+            Decimal: {}",
+            "Blob ID encodings".bold().walrus_purple(),
+            self.blob_id,
+            self.hex,
+            self.decimal,
+        );
     }
 }
 
@@ -435,7 +527,17 @@ impl CliOutput for InfoEpochOutput {
 
 impl CliOutput for InfoStorageOutput {
     fn print_cli_output(&self) {
-        let Self { n_shards, n_nodes } = self;
+        let Self {
+            n_shards,
+            n_nodes,
+            total_capacity_size,
+            used_capacity_size,
+        } = self;
+        let used_percentage = if *total_capacity_size == 0 {
+            0.0
+        } else {
+            *used_capacity_size as f64 / *total_capacity_size as f64 * 100.0
+        };
 
         printdoc!(
             "
@@ -443,8 +545,12 @@ impl CliOutput for InfoStorageOutput {
             {heading}
             Number of storage nodes: {n_nodes}
             Number of shards: {n_shards}
+            Total capacity: {total_capacity}
+            Used capacity: {used_capacity} ({used_percentage:.2}%)
             ",
             heading = "Storage nodes".bold().walrus_teal(),
+            total_capacity = HumanReadableBytes(*total_capacity_size),
+            used_capacity = HumanReadableBytes(*used_capacity_size),
         );
     }
 }
@@ -599,7 +705,7 @@ impl CliOutput for InfoBftOutput {
 
             {heading}
             Tolerated faults (f): {max_faulty_shards}
-            Quorum threshold (2f+1): {quorum_threshold}
+            Quorum threshold: {quorum_threshold}
             Minimum number of correct shards (n-f): {min_correct_shards}
             Minimum number of nodes to get above f: {min_nodes_above} ({shards_above} shards)
             ",
@@ -693,6 +799,31 @@ impl CliOutput for Vec<Blob> {
     }
 }
 
+impl CliOutput for Vec<ContractEvent> {
+    fn print_cli_output(&self) {
+        let mut table = Table::new();
+        table.set_format(default_table_format());
+        table.set_titles(row![
+            b->"Event ID",
+            bc->"Epoch",
+            b->"Event",
+            b->"Blob ID",
+        ]);
+
+        for event in self {
+            table.add_row(row![
+                format_event_id(&event.event_id()),
+                c->event.event_epoch(),
+                event.name(),
+                event
+                    .blob_id()
+                    .map_or_else(|| "-".to_owned(), |blob_id| blob_id.to_string()),
+            ]);
+        }
+        table.printstd();
+    }
+}
+
 impl CliOutput for DeleteOutput {
     fn print_cli_output(&self) {
         let identity = self.blob_identity.to_string();
@@ -786,6 +917,65 @@ impl CliOutput for StakeOutput {
                 self.staked_wal.iter().map(ToString::to_string).join("\n")
             );
         }
+        for staked_wal in &self.staked_wal {
+            println!(
+                "The stake of {} with node {} becomes active in epoch {}.",
+                HumanReadableFrost::from(staked_wal.principal),
+                staked_wal.node_id,
+                staked_wal.activation_epoch,
+            );
+        }
+    }
+}
+
+impl CliOutput for UnstakeOutput {
+    fn print_cli_output(&self) {
+        if let Some(amount) = self.withdrawn_amount {
+            println!(
+                "{} Withdrew {} from staked WAL {}.",
+                success(),
+                HumanReadableFrost::from(amount),
+                self.staked_wal_id,
+            );
+        }
+        if let Some(epoch) = self.withdrawal_epoch {
+            println!(
+                "{} Requested the withdrawal of staked WAL {}.\n\
+                Run `walrus unstake` again in epoch {} or later to withdraw the WAL.",
+                success(),
+                self.staked_wal_id,
+                epoch,
+            );
+        }
+    }
+}
+
+impl CliOutput for Vec<StakedWal> {
+    fn print_cli_output(&self) {
+        if self.is_empty() {
+            println!("{} No staked WAL found.", warning());
+            return;
+        }
+        let mut table = Table::new();
+        table.set_format(default_table_format());
+        table.set_titles(row![
+            b->"Object ID",
+            b->"Node ID",
+            bc->"Principal",
+            bc->"Activation epoch",
+            b->"State",
+        ]);
+
+        for staked_wal in self {
+            table.add_row(row![
+                staked_wal.id,
+                staked_wal.node_id,
+                c->HumanReadableFrost::from(staked_wal.principal),
+                c->staked_wal.activation_epoch,
+                staked_wal.state,
+            ]);
+        }
+        table.printstd();
     }
 }
 
@@ -844,6 +1034,79 @@ impl CliOutput for ExtendBlobOutput {
     }
 }
 
+impl CliOutput for RenewOutput {
+    fn print_cli_output(&self) {
+        if self.expiring.is_empty() {
+            println!(
+                "{} No certified blobs expire within {} epochs of the current epoch {}.",
+                success(),
+                self.within_epochs,
+                self.current_epoch
+            );
+            return;
+        }
+
+        println!(
+            "{} {} blob(s) expire within {} epochs of the current epoch {}:",
+            warning(),
+            self.expiring.len(),
+            self.within_epochs,
+            self.current_epoch
+        );
+        let mut table = Table::new();
+        table.set_format(default_table_format());
+        table.set_titles(row![
+            b->"Blob ID",
+            bc->"Deletable?",
+            bc->"Exp. epoch",
+            bc->"Epochs left",
+            b->"Object ID",
+        ]);
+        for expiring in &self.expiring {
+            table.add_row(row![
+                expiring.blob.blob_id,
+                c->expiring.blob.deletable,
+                c->expiring.blob.storage.end_epoch,
+                c->expiring.epochs_left,
+                expiring.blob.id,
+            ]);
+        }
+        table.printstd();
+
+        let Some(renewal) = &self.renewal else {
+            return;
+        };
+        for renewed in &renewal.renewals {
+            let method = match renewed.method {
+                RenewalMethod::OwnedStorage { storage_object_id } => {
+                    format!("with owned storage {storage_object_id}")
+                }
+                RenewalMethod::Payment { cost, .. } => {
+                    format!("for {}", HumanReadableFrost::from(cost))
+                }
+            };
+            println!(
+                "{} Blob {} was renewed until epoch {} {method}.",
+                success(),
+                renewed.blob_id,
+                renewed.new_end_epoch
+            );
+        }
+        for skipped in &renewal.skipped {
+            println!(
+                "{} Blob {} was not renewed: {}.",
+                warning(),
+                skipped.blob_id,
+                skipped.reason
+            );
+        }
+        println!(
+            "Total cost: {}",
+            HumanReadableFrost::from(renewal.total_cost())
+        );
+    }
+}
+
 impl CliOutput for NodeHealthOutput {
     fn print_cli_output(&self) {
         printdoc! {"
@@ -912,6 +1175,18 @@ impl CliOutput for NodeHealthOutput {
                     in_recovery = health_info.shard_summary.owned_shard_status.in_recovery,
                 };
 
+                if health_info
+                    .network_public_key
+                    .as_ref()
+                    .is_some_and(|key| key != &self.network_public_key)
+                {
+                    println!(
+                        "\n{} The network public key used by the node does not match the \
+                        on-chain network public key.",
+                        warning()
+                    );
+                }
+
                 // Print shard details if available
                 if let Some(detail) = &health_info.shard_detail {
                     if !detail.owned.is_empty() {
@@ -974,6 +1249,98 @@ impl CliOutput for ServiceHealthInfoOutput {
     }
 }
 
+impl CliOutput for BlobAvailability {
+    fn print_cli_output(&self) {
+        println!(
+            "\n{} {}\n",
+            "Availability of blob".bold(),
+            self.blob_id.to_string().bold()
+        );
+
+        let mut table = Table::new();
+        table.set_format(default_table_format());
+        table.set_titles(row![
+            b->"Idx",
+            b->"Name",
+            b->"Node ID",
+            br->"# Shards",
+            bc->"Metadata",
+            bc->"Primary\nslivers",
+            bc->"Secondary\nslivers",
+        ]);
+        for (idx, node) in self.nodes.iter().enumerate() {
+            match &node.stored_slivers {
+                Ok(stored) => {
+                    let metadata_str = if stored.metadata_stored {
+                        "stored"
+                    } else {
+                        "missing"
+                    };
+                    table.add_row(row![
+                        r->idx,
+                        node.node_name,
+                        node.node_id,
+                        r->node.n_shards,
+                        c->metadata_str,
+                        c->format!("{} / {}", stored.primary_slivers, node.n_shards),
+                        c->format!("{} / {}", stored.secondary_slivers, node.n_shards),
+                    ]);
+                }
+                Err(error) => {
+                    let truncated_error = if error.len() > 40 {
+                        format!("{}...", &error[..37])
+                    } else {
+                        error.clone()
+                    };
+                    table.add_row(row![
+                        r->idx,
+                        node.node_name,
+                        node.node_id,
+                        r->node.n_shards,
+                        H3Fr->truncated_error,
+                    ]);
+                }
+            }
+        }
+        table.printstd();
+
+        let n_errors = self
+            .nodes
+            .iter()
+            .filter(|node| node.stored_slivers.is_err())
+            .count();
+        printdoc!(
+            "
+
+            Epoch: {epoch}
+            Nodes queried: {n_nodes} ({n_errors} failed to respond)
+            Verified metadata retrievable: {metadata}
+            Primary slivers stored: {primary} (required for reading: {primary_required})
+            Secondary slivers stored: {secondary} (required for reading: {secondary_required})
+            ",
+            epoch = self.epoch,
+            n_nodes = self.nodes.len(),
+            metadata = if self.metadata_retrievable {
+                "yes"
+            } else {
+                "no"
+            },
+            primary = self.primary_slivers,
+            primary_required = self.primary_slivers_required,
+            secondary = self.secondary_slivers,
+            secondary_required = self.secondary_slivers_required,
+        );
+        if self.read_quorum {
+            println!("{} The blob can be read.", success());
+        } else {
+            println!(
+                "{} The blob cannot be read: not enough metadata or slivers are available.",
+                error()
+            );
+        }
+    }
+}
+
 /// Default style for tables printed to stdout.
 fn default_table_format() -> format::TableFormat {
     format::FormatBuilder::new()
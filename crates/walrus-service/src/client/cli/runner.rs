@@ -4,6 +4,7 @@
 //! Helper struct to run the Walrus client binary commands.
 
 use std::{
+    collections::HashMap,
     io::Write,
     iter,
     num::NonZeroU16,
@@ -14,22 +15,18 @@ use std::{
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use futures::StreamExt as _;
 use indicatif::MultiProgress;
 use itertools::Itertools as _;
 use prometheus::Registry;
 use rand::seq::SliceRandom;
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
 use sui_sdk::wallet_context::WalletContext;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use walrus_core::{
-    encoding::{
-        encoded_blob_length_for_n_shards,
-        EncodingConfig,
-        EncodingConfigTrait as _,
-        Primary,
-    },
+    encoding::{encoded_blob_length_for_n_shards, EncodingConfig, Primary},
     ensure,
-    metadata::BlobMetadataApi as _,
+    metadata::{compute_blob_id_info, BlobMetadataApi as _},
     BlobId,
     EncodingType,
     EpochCount,
@@ -40,13 +37,20 @@ use walrus_sdk::api::BlobStatus;
 use walrus_sui::{
     client::{
         BlobPersistence,
+        CommitteesAndState,
         ExpirySelectionPolicy,
         PostStoreAction,
         ReadClient,
         SuiContractClient,
+        MIN_STAKING_THRESHOLD,
     },
     config::WalletConfig,
-    types::move_structs::{Authorized, BlobAttribute, EpochState},
+    types::{
+        move_structs::{Authorized, BlobAttribute, EpochState, StakedWalState},
+        NetworkAddress,
+        NodeUpdateParams,
+        StakedWal,
+    },
     utils::SuiNetwork,
 };
 
@@ -67,11 +71,14 @@ use super::args::{
     PublisherArgs,
     RpcArg,
     SortBy,
+    StoreManySchedule,
+    UnsignedPublisherArgs,
     UserConfirmation,
 };
 use crate::{
     client::{
         cli::{
+            deduplicate_files_by_contents,
             get_contract_client,
             get_read_client,
             get_sui_read_client_from_rpc_node_or_wallet,
@@ -79,14 +86,15 @@ use crate::{
             read_blob_from_file,
             success,
             warning,
-            BlobIdDecimal,
             CliOutput,
             HumanReadableFrost,
             HumanReadableMist,
         },
         communication::NodeCommunicationFactory,
+        daemon::{AttributeIndex, BlobCache, CachingClient},
         error::ClientErrorKind,
         multiplexer::ClientMultiplexer,
+        renewal::{self, RenewalParameters, RenewalPlan, RenewalPolicy},
         responses::{
             BlobIdConversionOutput,
             BlobIdOutput,
@@ -105,20 +113,35 @@ use crate::{
             InfoSizeOutput,
             InfoStorageOutput,
             ReadOutput,
+            RenewOutput,
             ServiceHealthInfoOutput,
             ShareBlobOutput,
             StakeOutput,
+            StoreDirOutput,
+            StoreManyFileOutput,
+            StoreManyOutput,
+            UnstakeOutput,
             WalletOutput,
         },
+        site::{self, ManifestEntry, SiteManifest},
         styled_spinner,
+        BlobStoreResult,
         Client,
         ClientDaemon,
         Config,
+        MetadataReplication,
         StoreWhen,
     },
     utils::{self, generate_sui_wallet, MetricsAndLoggingRuntime},
 };
 
+/// The delay before retrying to store a batch of files with `store-many`, multiplied by the number
+/// of the attempt.
+const STORE_MANY_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// The interval at which the Sui RPC node is polled for new events with `replay-events`.
+const REPLAY_EVENTS_POLLING_INTERVAL: Duration = Duration::from_millis(500);
+
 /// A helper struct to run commands for the Walrus client.
 #[allow(missing_debug_implementations)]
 pub struct ClientCommandRunner {
@@ -181,7 +204,9 @@ impl ClientCommandRunner {
                 force,
                 ignore_resources,
                 deletable,
+                permanent,
                 share,
+                push_metadata_to_all_nodes,
                 encoding_type,
             } => {
                 self.store(
@@ -189,13 +214,62 @@ impl ClientCommandRunner {
                     epoch_arg,
                     dry_run,
                     StoreWhen::from_flags(force, ignore_resources),
-                    BlobPersistence::from_deletable(deletable),
+                    blob_persistence(deletable, permanent)?,
                     PostStoreAction::from_share(share),
+                    MetadataReplication::from_flag(push_metadata_to_all_nodes),
                     encoding_type,
                 )
                 .await
             }
 
+            CliCommands::StoreDir {
+                directory,
+                epoch_arg,
+                force,
+                ignore_resources,
+                deletable,
+                permanent,
+                share,
+                push_metadata_to_all_nodes,
+                encoding_type,
+            } => {
+                self.store_dir(
+                    directory,
+                    epoch_arg,
+                    StoreWhen::from_flags(force, ignore_resources),
+                    blob_persistence(deletable, permanent)?,
+                    PostStoreAction::from_share(share),
+                    MetadataReplication::from_flag(push_metadata_to_all_nodes),
+                    encoding_type,
+                )
+                .await
+            }
+
+            CliCommands::StoreMany {
+                pattern,
+                epoch_arg,
+                force,
+                ignore_resources,
+                deletable,
+                permanent,
+                share,
+                push_metadata_to_all_nodes,
+                encoding_type,
+                schedule,
+            } => {
+                self.store_many(
+                    pattern,
+                    epoch_arg,
+                    StoreWhen::from_flags(force, ignore_resources),
+                    blob_persistence(deletable, permanent)?,
+                    PostStoreAction::from_share(share),
+                    MetadataReplication::from_flag(push_metadata_to_all_nodes),
+                    encoding_type,
+                    schedule,
+                )
+                .await
+            }
+
             CliCommands::BlobStatus {
                 file_or_blob_id,
                 timeout,
@@ -212,6 +286,14 @@ impl ClientCommandRunner {
             } => self.info(rpc_url, command).await,
 
             CliCommands::Health {
+                blob_id: Some(blob_id),
+                node_selection,
+                rpc_arg: RpcArg { rpc_url },
+                ..
+            } => self.blob_health(rpc_url, blob_id, node_selection).await,
+
+            CliCommands::Health {
+                blob_id: None,
                 node_selection,
                 detail,
                 sort,
@@ -220,15 +302,29 @@ impl ClientCommandRunner {
 
             CliCommands::BlobId {
                 file,
+                sliver_roots,
                 n_shards,
                 encoding_type,
                 rpc_arg: RpcArg { rpc_url },
-            } => self.blob_id(file, n_shards, rpc_url, encoding_type).await,
+            } => {
+                self.blob_id(file, sliver_roots, n_shards, rpc_url, encoding_type)
+                    .await
+            }
 
-            CliCommands::ConvertBlobId { blob_id_decimal } => self.convert_blob_id(blob_id_decimal),
+            CliCommands::ConvertBlobId { blob_id } => self.convert_blob_id(blob_id),
 
             CliCommands::ListBlobs { include_expired } => self.list_blobs(include_expired).await,
 
+            CliCommands::ReplayEvents {
+                from_checkpoint,
+                limit,
+                idle_timeout,
+                rpc_arg: RpcArg { rpc_url },
+            } => {
+                self.replay_events(rpc_url, from_checkpoint, limit, idle_timeout)
+                    .await
+            }
+
             CliCommands::Delete {
                 target,
                 yes,
@@ -243,6 +339,10 @@ impl ClientCommandRunner {
                 self.stake_with_node_pools(node_ids, amounts).await
             }
 
+            CliCommands::Unstake { staked_wal_id } => self.unstake(staked_wal_id).await,
+
+            CliCommands::ListStake { owner } => self.list_stake(owner).await,
+
             CliCommands::GenerateSuiWallet {
                 path,
                 sui_network,
@@ -326,6 +426,12 @@ impl ClientCommandRunner {
                 ExtendBlobOutput { epochs_extended }.print_output(self.json)
             }
 
+            CliCommands::Renew {
+                within_epochs,
+                policy,
+                yes,
+            } => self.renew(within_epochs, policy, yes.into()).await,
+
             CliCommands::Share {
                 blob_obj_id,
                 amount,
@@ -457,6 +563,14 @@ impl ClientCommandRunner {
                 .await
             }
 
+            DaemonCommands::UnsignedPublisher {
+                rpc_arg: RpcArg { rpc_url },
+                args,
+            } => {
+                self.unsigned_publisher(&metrics_runtime.registry, rpc_url, args)
+                    .await
+            }
+
             DaemonCommands::Daemon {
                 args,
                 aggregator_args,
@@ -526,6 +640,7 @@ impl ClientCommandRunner {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
         encoding_type: Option<EncodingType>,
     ) -> Result<()> {
         epoch_arg.exactly_one_is_some()?;
@@ -566,6 +681,7 @@ impl ClientCommandRunner {
                 store_when,
                 persistence,
                 post_store,
+                metadata_replication,
             )
             .await?;
         let blobs_len = blobs.len();
@@ -590,6 +706,261 @@ impl ClientCommandRunner {
         results.print_output(self.json)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn store_dir(
+        self,
+        directory: PathBuf,
+        epoch_arg: EpochArg,
+        store_when: StoreWhen,
+        persistence: BlobPersistence,
+        post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
+        encoding_type: Option<EncodingType>,
+    ) -> Result<()> {
+        epoch_arg.exactly_one_is_some()?;
+        if encoding_type.is_some_and(|encoding| !encoding.is_supported()) {
+            anyhow::bail!(ClientErrorKind::UnsupportedEncodingType(
+                encoding_type.expect("just checked that option is Some")
+            ));
+        }
+        if persistence.is_deletable() && post_store == PostStoreAction::Share {
+            anyhow::bail!("deletable blobs cannot be shared");
+        }
+        let encoding_type = encoding_type.unwrap_or(DEFAULT_ENCODING);
+
+        let files = site::collect_files(&directory)?;
+        let blobs = files
+            .iter()
+            .map(|(_, file)| read_blob_from_file(file).map(|blob| (file.clone(), blob)))
+            .collect::<Result<Vec<(PathBuf, Vec<u8>)>>>()?;
+
+        let client = get_contract_client(self.config?, self.wallet, self.gas_budget, &None).await?;
+        let system_object = client.sui_client().read_client.get_system_object().await?;
+        let epochs_ahead =
+            get_epochs_ahead(epoch_arg, system_object.max_epochs_ahead(), &client).await?;
+
+        tracing::info!(
+            directory = %directory.display(),
+            "storing {} files as blobs on Walrus",
+            files.len()
+        );
+        let results = client
+            .reserve_and_store_blobs_retry_committees_with_path(
+                &blobs,
+                encoding_type,
+                epochs_ahead,
+                store_when,
+                persistence,
+                post_store,
+                metadata_replication,
+            )
+            .await?;
+        if let Some(result) = results.iter().find(|result| {
+            matches!(
+                result.blob_store_result,
+                BlobStoreResult::MarkedInvalid { .. }
+            )
+        }) {
+            anyhow::bail!(
+                "the blob for file {} was marked as invalid",
+                result.path.display()
+            );
+        }
+
+        // Files with identical contents are only stored once, so blob IDs are looked up by
+        // contents.
+        let stored_paths: HashMap<_, _> = results
+            .iter()
+            .map(|result| (result.path.as_path(), *result.blob_store_result.blob_id()))
+            .collect();
+        let blob_ids_by_contents: HashMap<_, _> = blobs
+            .iter()
+            .filter_map(|(file, blob)| {
+                stored_paths
+                    .get(file.as_path())
+                    .map(|blob_id| (blob.as_slice(), *blob_id))
+            })
+            .collect();
+        let mut manifest = SiteManifest::default();
+        for ((path, file), (_, blob)) in files.iter().zip(&blobs) {
+            let blob_id = blob_ids_by_contents
+                .get(blob.as_slice())
+                .with_context(|| format!("the file {} was not stored", file.display()))?;
+            manifest.files.insert(
+                path.clone(),
+                ManifestEntry {
+                    blob_id: *blob_id,
+                    content_type: site::content_type_for_path(path).to_owned(),
+                },
+            );
+        }
+
+        let manifest = client
+            .reserve_and_store_blobs_retry_committees(
+                &[&manifest.to_bytes()],
+                encoding_type,
+                epochs_ahead,
+                store_when,
+                persistence,
+                post_store,
+                metadata_replication,
+            )
+            .await?
+            .into_iter()
+            .next()
+            .expect("there is only one blob, as store was called with one blob");
+
+        StoreDirOutput {
+            manifest,
+            files: results,
+        }
+        .print_output(self.json)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn store_many(
+        self,
+        pattern: String,
+        epoch_arg: EpochArg,
+        store_when: StoreWhen,
+        persistence: BlobPersistence,
+        post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
+        encoding_type: Option<EncodingType>,
+        schedule: StoreManySchedule,
+    ) -> Result<()> {
+        epoch_arg.exactly_one_is_some()?;
+        if encoding_type.is_some_and(|encoding| !encoding.is_supported()) {
+            anyhow::bail!(ClientErrorKind::UnsupportedEncodingType(
+                encoding_type.expect("just checked that option is Some")
+            ));
+        }
+        if persistence.is_deletable() && post_store == PostStoreAction::Share {
+            anyhow::bail!("deletable blobs cannot be shared");
+        }
+        let encoding_type = encoding_type.unwrap_or(DEFAULT_ENCODING);
+
+        let mut files = Vec::new();
+        for entry in glob::glob(&pattern).context("invalid glob pattern")? {
+            let path = entry?;
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+        ensure!(!files.is_empty(), "no files match the pattern {pattern}");
+
+        // Files with identical contents have the same blob ID, so each distinct content is only
+        // stored once, and the result is reported for all files with that content.
+        let (unique_files, duplicates) = deduplicate_files_by_contents(&files)?;
+
+        let client = get_contract_client(self.config?, self.wallet, self.gas_budget, &None).await?;
+        let system_object = client.sui_client().read_client.get_system_object().await?;
+        let epochs_ahead =
+            get_epochs_ahead(epoch_arg, system_object.max_epochs_ahead(), &client).await?;
+
+        tracing::info!(
+            files = files.len(),
+            unique_blobs = unique_files.len(),
+            batch_size = schedule.batch_size.get(),
+            parallelism = schedule.parallelism.get(),
+            "storing files as blobs on Walrus"
+        );
+        let client = &client;
+        let max_retries = schedule.max_retries;
+        let batch_results: Vec<_> =
+            futures::stream::iter(unique_files.chunks(schedule.batch_size.get()))
+                .map(|batch| async move {
+                    // The files are only read once their batch is scheduled, such that at most
+                    // `parallelism` batches are held in memory.
+                    let blobs = match batch
+                        .iter()
+                        .map(|path| Ok((path.clone(), read_blob_from_file(path)?)))
+                        .collect::<Result<Vec<_>>>()
+                    {
+                        Ok(blobs) => blobs,
+                        Err(error) => return (batch, Err(error)),
+                    };
+                    let mut attempt = 0;
+                    loop {
+                        let result = client
+                            .reserve_and_store_blobs_retry_committees_with_path(
+                                &blobs,
+                                encoding_type,
+                                epochs_ahead,
+                                store_when,
+                                persistence,
+                                post_store,
+                                metadata_replication,
+                            )
+                            .await;
+                        match result {
+                            Err(error) if attempt < max_retries => {
+                                attempt += 1;
+                                tracing::warn!(
+                                    ?error,
+                                    attempt,
+                                    "failed to store a batch, retrying"
+                                );
+                                tokio::time::sleep(STORE_MANY_RETRY_DELAY * attempt).await;
+                            }
+                            result => break (batch, result.map_err(anyhow::Error::from)),
+                        }
+                    }
+                })
+                .buffer_unordered(schedule.parallelism.get())
+                .collect()
+                .await;
+
+        let mut outputs: HashMap<PathBuf, StoreManyFileOutput> = HashMap::new();
+        for (batch, result) in batch_results {
+            match result {
+                Ok(results) => {
+                    outputs.extend(
+                        results
+                            .into_iter()
+                            .map(|result| (result.path.clone(), StoreManyFileOutput::from(result))),
+                    );
+                }
+                Err(error) => {
+                    tracing::error!(?error, "failed to store a batch of files");
+                    outputs.extend(batch.iter().map(|path| {
+                        (
+                            path.clone(),
+                            StoreManyFileOutput::failed(path.clone(), &error),
+                        )
+                    }));
+                }
+            }
+        }
+        for (path, original) in duplicates {
+            let output = outputs
+                .get(&original)
+                .map(|original_output| original_output.duplicate(path.clone(), original));
+            if let Some(output) = output {
+                outputs.insert(path, output);
+            }
+        }
+
+        let output = StoreManyOutput {
+            files: files
+                .into_iter()
+                .map(|path| {
+                    outputs.remove(&path).unwrap_or_else(|| {
+                        StoreManyFileOutput::failed(path, "the blob was not stored")
+                    })
+                })
+                .collect(),
+        };
+        let failed = output.failed_count();
+        output.print_output(self.json)?;
+        ensure!(
+            failed == 0,
+            "{failed} of {} files could not be stored",
+            output.files.len()
+        );
+        Ok(())
+    }
+
     async fn store_dry_run(
         client: Client<SuiContractClient>,
         files: Vec<PathBuf>,
@@ -780,9 +1151,41 @@ impl ClientCommandRunner {
         .print_output(self.json)
     }
 
+    pub(crate) async fn blob_health(
+        self,
+        rpc_url: Option<String>,
+        blob_id: BlobId,
+        node_selection: NodeSelection,
+    ) -> Result<()> {
+        ensure!(
+            node_selection.is_empty(),
+            "the availability of a blob is always checked on the current committee; \
+            node selection options cannot be specified together with a blob ID"
+        );
+        let config = self.config?;
+        let sui_read_client = get_sui_read_client_from_rpc_node_or_wallet(
+            &config,
+            rpc_url,
+            self.wallet,
+            !self.wallet_set_explicitly,
+        )
+        .await?;
+        let refresher_handle = config
+            .refresh_config
+            .build_refresher_and_run(sui_read_client)
+            .await?;
+        let client = Client::new(config, refresher_handle).await?;
+
+        client
+            .check_blob_availability(&blob_id)
+            .await?
+            .print_output(self.json)
+    }
+
     pub(crate) async fn blob_id(
         self,
         file: PathBuf,
+        sliver_roots: bool,
         n_shards: Option<NonZeroU16>,
         rpc_url: Option<String>,
         encoding_type: Option<EncodingType>,
@@ -812,12 +1215,10 @@ impl ClientCommandRunner {
         tracing::debug!(%n_shards, "encoding the blob");
         let spinner = styled_spinner();
         spinner.set_message("computing the blob ID");
-        let metadata = EncodingConfig::new(n_shards)
-            .get_for_type(encoding_type)
-            .compute_metadata(&read_blob_from_file(&file)?)?;
-        spinner.finish_with_message(format!("blob ID computed: {}", metadata.blob_id()));
+        let info = compute_blob_id_info(&read_blob_from_file(&file)?, n_shards, encoding_type)?;
+        spinner.finish_with_message(format!("blob ID computed: {}", info.blob_id));
 
-        BlobIdOutput::new(&file, &metadata).print_output(self.json)
+        BlobIdOutput::new(&file, info, sliver_roots).print_output(self.json)
     }
 
     pub(crate) async fn list_blobs(self, include_expired: bool) -> Result<()> {
@@ -834,6 +1235,33 @@ impl ClientCommandRunner {
         blobs.print_output(self.json)
     }
 
+    pub(crate) async fn replay_events(
+        self,
+        rpc_url: Option<String>,
+        from_checkpoint: u64,
+        limit: usize,
+        idle_timeout: Duration,
+    ) -> Result<()> {
+        let config = self.config?;
+        let sui_read_client = get_sui_read_client_from_rpc_node_or_wallet(
+            &config,
+            rpc_url,
+            self.wallet,
+            !self.wallet_set_explicitly,
+        )
+        .await?;
+        let events = sui_read_client
+            .event_stream_from_checkpoint(REPLAY_EVENTS_POLLING_INTERVAL, from_checkpoint)
+            .await?;
+        let mut events = std::pin::pin!(events.take(limit));
+
+        let mut replayed = vec![];
+        while let Ok(Some(event)) = tokio::time::timeout(idle_timeout, events.next()).await {
+            replayed.push(event);
+        }
+        replayed.print_output(self.json)
+    }
+
     pub(crate) async fn publisher(self, registry: &Registry, args: PublisherArgs) -> Result<()> {
         args.print_debug_message("attempting to run the Walrus publisher");
         let client = ClientMultiplexer::new(
@@ -846,17 +1274,9 @@ impl ClientCommandRunner {
         .await?;
         let auth_config = args.generate_auth_config()?;
 
-        ClientDaemon::new_publisher(
-            client,
-            auth_config,
-            args.daemon_args.bind_address,
-            args.max_body_size(),
-            registry,
-            args.max_request_buffer_size,
-            args.max_concurrent_requests,
-        )
-        .run()
-        .await?;
+        ClientDaemon::new_publisher(client, auth_config, registry, &args)?
+            .run()
+            .await?;
         Ok(())
     }
 
@@ -876,14 +1296,35 @@ impl ClientCommandRunner {
             &daemon_args.blocklist,
         )
         .await?;
-        ClientDaemon::new_aggregator(
-            client,
-            daemon_args.bind_address,
-            registry,
-            aggregator_args.allowed_headers,
+        let attribute_index = spawn_attribute_index(client.sui_client().clone(), &aggregator_args)?;
+        let client = with_blob_cache(client, &aggregator_args)?;
+        let mut daemon =
+            ClientDaemon::new_aggregator(client, registry, &daemon_args, &aggregator_args)?;
+        if let Some(attribute_index) = attribute_index {
+            daemon = daemon.with_attribute_index(attribute_index);
+        }
+        daemon.run().await?;
+        Ok(())
+    }
+
+    pub(crate) async fn unsigned_publisher(
+        self,
+        registry: &Registry,
+        rpc_url: Option<String>,
+        args: UnsignedPublisherArgs,
+    ) -> Result<()> {
+        tracing::debug!(?rpc_url, "attempting to run the Walrus unsigned publisher");
+        let client = get_read_client(
+            self.config?,
+            rpc_url,
+            self.wallet,
+            !self.wallet_set_explicitly,
+            &args.daemon_args.blocklist,
         )
-        .run()
         .await?;
+        ClientDaemon::new_unsigned_publisher(client, registry, &args)?
+            .run()
+            .await?;
         Ok(())
     }
 
@@ -903,14 +1344,20 @@ impl ClientCommandRunner {
             &args.daemon_args.blocklist,
         )
         .await?;
-        ClientDaemon::new_daemon(client, auth_config, registry, &args, &aggregator_args)
-            .run()
-            .await?;
+        let attribute_index =
+            spawn_attribute_index(client.sui_client().read_client().clone(), &aggregator_args)?;
+        let client = with_blob_cache(client, &aggregator_args)?;
+        let mut daemon =
+            ClientDaemon::new_daemon(client, auth_config, registry, &args, &aggregator_args)?;
+        if let Some(attribute_index) = attribute_index {
+            daemon = daemon.with_attribute_index(attribute_index);
+        }
+        daemon.run().await?;
         Ok(())
     }
 
-    pub(crate) fn convert_blob_id(self, blob_id_decimal: BlobIdDecimal) -> Result<()> {
-        BlobIdConversionOutput::from(blob_id_decimal).print_output(self.json)
+    pub(crate) fn convert_blob_id(self, blob_id: BlobId) -> Result<()> {
+        BlobIdConversionOutput::from(blob_id).print_output(self.json)
     }
 
     pub(crate) async fn delete(
@@ -1015,11 +1462,86 @@ impl ClientCommandRunner {
                 .zip(amounts.into_iter())
                 .collect::<Vec<_>>()
         };
+        if let Some((_, amount)) = node_ids_with_amounts
+            .iter()
+            .find(|(_, amount)| *amount < MIN_STAKING_THRESHOLD)
+        {
+            anyhow::bail!(
+                "the stake amount {} is below the minimum stake of {}",
+                HumanReadableFrost::from(*amount),
+                HumanReadableFrost::from(MIN_STAKING_THRESHOLD),
+            );
+        }
         let client = get_contract_client(self.config?, self.wallet, self.gas_budget, &None).await?;
+        for (node_id, _) in &node_ids_with_amounts {
+            let pool = client
+                .sui_client()
+                .read_client()
+                .get_staking_pool(*node_id)
+                .await
+                .with_context(|| format!("unable to get the staking pool of node {node_id}"))?;
+            ensure!(
+                pool.is_active(),
+                "the staking pool of node {node_id} does not accept stake"
+            );
+        }
         let staked_wal = client.stake_with_node_pools(&node_ids_with_amounts).await?;
         StakeOutput { staked_wal }.print_output(self.json)
     }
 
+    pub(crate) async fn unstake(self, staked_wal_id: ObjectID) -> Result<()> {
+        let client = get_contract_client(self.config?, self.wallet, self.gas_budget, &None).await?;
+        let staked_wal: StakedWal = client
+            .sui_client()
+            .sui_client()
+            .get_sui_object(staked_wal_id)
+            .await
+            .with_context(|| format!("unable to get the staked WAL object {staked_wal_id}"))?;
+        let committees = client.sui_client().get_committees_and_state().await?;
+        let current_epoch = committees.current.epoch;
+
+        let withdraw = match staked_wal.state {
+            StakedWalState::Withdrawing(withdrawal_epoch, _) => {
+                ensure!(
+                    withdrawal_epoch <= current_epoch,
+                    "the withdrawal of the staked WAL was already requested and can be \
+                    completed in epoch {withdrawal_epoch} (current epoch: {current_epoch})"
+                );
+                true
+            }
+            StakedWalState::Staked => can_withdraw_stake_directly(&staked_wal, &committees),
+        };
+
+        let output = if withdraw {
+            UnstakeOutput {
+                staked_wal_id,
+                withdrawn_amount: Some(client.withdraw_stake(staked_wal_id).await?),
+                withdrawal_epoch: None,
+            }
+        } else {
+            let StakedWalState::Withdrawing(withdrawal_epoch, _) =
+                client.request_withdraw_stake(staked_wal_id).await?.state
+            else {
+                anyhow::bail!("the staked WAL is not withdrawing after requesting the withdrawal");
+            };
+            UnstakeOutput {
+                staked_wal_id,
+                withdrawn_amount: None,
+                withdrawal_epoch: Some(withdrawal_epoch),
+            }
+        };
+        output.print_output(self.json)
+    }
+
+    pub(crate) async fn list_stake(self, owner: Option<SuiAddress>) -> Result<()> {
+        let contract_client = self
+            .config?
+            .new_contract_client(self.wallet?, self.gas_budget)
+            .await?;
+        let staked_wal = contract_client.owned_staked_wal(owner).await?;
+        staked_wal.print_output(self.json)
+    }
+
     pub(crate) async fn generate_sui_wallet(
         self,
         path: &Path,
@@ -1106,6 +1628,80 @@ impl ClientCommandRunner {
         Ok(())
     }
 
+    pub(crate) async fn renew(
+        self,
+        within_epochs: EpochCount,
+        policy_path: Option<PathBuf>,
+        confirmation: UserConfirmation,
+    ) -> Result<()> {
+        let policy = policy_path
+            .as_deref()
+            .map(RenewalPolicy::load)
+            .transpose()
+            .context("failed to load the renewal policy")?;
+        let sui_client = self
+            .config?
+            .new_contract_client(self.wallet?, self.gas_budget)
+            .await?;
+        let read_client = sui_client.read_client();
+        let current_epoch = read_client.current_epoch().await?;
+        let expiring = renewal::expiring_blobs(
+            sui_client
+                .owned_blobs(None, ExpirySelectionPolicy::Valid)
+                .await?,
+            current_epoch,
+            within_epochs,
+        );
+
+        let mut output = RenewOutput {
+            current_epoch,
+            within_epochs,
+            expiring,
+            renewal: None,
+        };
+        let Some(policy) = policy.filter(|_| !output.expiring.is_empty()) else {
+            return output.print_output(self.json);
+        };
+
+        let owned_storage = if policy.use_owned_storage {
+            sui_client
+                .owned_storage(ExpirySelectionPolicy::Valid)
+                .await?
+        } else {
+            vec![]
+        };
+        let parameters = RenewalParameters {
+            current_epoch,
+            max_epochs_ahead: read_client
+                .fixed_system_parameters()
+                .await?
+                .max_epochs_ahead,
+            storage_price_per_unit_size: read_client.storage_price_per_unit_size().await?,
+        };
+        let plan = RenewalPlan::new(&output.expiring, &policy, owned_storage, parameters);
+
+        if !plan.renewals.is_empty() && confirmation.is_required() {
+            println!(
+                "{} You are about to renew {} blob(s) for a total cost of {}.\n\
+                If unsure, please enter `No` and check the `--help` manual.",
+                warning(),
+                plan.renewals.len(),
+                HumanReadableFrost::from(plan.total_cost()),
+            );
+            if !ask_for_confirmation()? {
+                println!("{} Aborting. No blobs were renewed.", success());
+                return Ok(());
+            }
+        }
+
+        let spinner = styled_spinner();
+        spinner.set_message("renewing blobs...");
+        output.renewal = Some(plan.execute(&sui_client).await);
+        spinner.finish_with_message("done");
+
+        output.print_output(self.json)
+    }
+
     pub(crate) async fn run_admin_command(
         self,
         node_id: ObjectID,
@@ -1158,11 +1754,95 @@ impl ClientCommandRunner {
                     HumanReadableFrost::from(sui_client.collect_commission(node_id).await?);
                 println!("{} Collected {} as commission", success(), amount);
             }
+            NodeAdminCommands::UpdateNodeParams {
+                name,
+                network_address,
+                commission_rate,
+            } => {
+                update_node_params(
+                    &sui_client,
+                    node_id,
+                    NodeUpdateParams {
+                        name,
+                        network_address: network_address.map(NetworkAddress),
+                        commission_rate,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+                println!(
+                    "{} The parameters of node {} have been updated",
+                    success(),
+                    node_id
+                );
+            }
         }
         Ok(())
     }
 }
 
+/// Updates the parameters of the node with ID `node_id` and verifies that the on-chain state of
+/// the node reflects the update.
+async fn update_node_params(
+    sui_client: &SuiContractClient,
+    node_id: ObjectID,
+    params: NodeUpdateParams,
+) -> Result<()> {
+    ensure!(
+        params.needs_update(),
+        "no parameters to update were specified"
+    );
+    if let Some(network_address) = &params.network_address {
+        ensure!(
+            matches!(network_address.try_get_port(), Ok(Some(_))),
+            "the network address must be in the format `host:port`"
+        );
+    }
+    let node_capability = sui_client
+        .read_client()
+        .get_address_capability_object(sui_client.address())
+        .await?
+        .filter(|capability| capability.node_id == node_id)
+        .with_context(|| {
+            format!(
+                "the wallet with address {} does not own the storage node capability of node {}",
+                sui_client.address(),
+                node_id
+            )
+        })?;
+
+    sui_client
+        .update_node_params(params.clone(), node_capability.id)
+        .await?;
+
+    let pool = sui_client.read_client().get_staking_pool(node_id).await?;
+    if let Some(name) = &params.name {
+        ensure!(
+            &pool.node_info.name == name,
+            "the name of the node is '{}' after the update",
+            pool.node_info.name
+        );
+    }
+    if let Some(network_address) = &params.network_address {
+        ensure!(
+            &pool.node_info.network_address == network_address,
+            "the network address of the node is '{}' after the update",
+            pool.node_info.network_address
+        );
+    }
+    if let Some(commission_rate) = params.commission_rate {
+        ensure!(
+            pool.commission_rate == commission_rate
+                || pool
+                    .pending_commission_rate
+                    .iter()
+                    .any(|(_, pending)| *pending == u64::from(commission_rate)),
+            "the commission rate {commission_rate} is not pending after the update"
+        );
+    }
+    Ok(())
+}
+
 async fn delete_blob(
     client: &Client<SuiContractClient>,
     target: BlobIdentity,
@@ -1264,6 +1944,45 @@ async fn delete_blob(
     result
 }
 
+/// Wraps the client such that reconstructed blobs are cached on disk, if a blob cache directory is
+/// configured in the aggregator arguments.
+fn with_blob_cache<T>(
+    client: Client<T>,
+    aggregator_args: &AggregatorArgs,
+) -> Result<CachingClient<Client<T>>> {
+    let cache = aggregator_args
+        .blob_cache_dir
+        .as_deref()
+        .map(|directory| BlobCache::open(directory, aggregator_args.blob_cache_max_size))
+        .transpose()?
+        .map(Arc::new);
+    let blocklist = client.blocklist().cloned();
+    Ok(CachingClient::new(client, cache, blocklist))
+}
+
+/// Opens the attribute index if a directory is configured and keeps it up to date in the
+/// background.
+fn spawn_attribute_index(
+    read_client: impl ReadClient + Send + Sync + 'static,
+    aggregator_args: &AggregatorArgs,
+) -> Result<Option<Arc<AttributeIndex>>> {
+    let Some(directory) = aggregator_args.attribute_index_dir.as_deref() else {
+        return Ok(None);
+    };
+    let attribute_index = Arc::new(AttributeIndex::open(directory)?);
+    tokio::spawn(attribute_index.clone().run(read_client));
+    Ok(Some(attribute_index))
+}
+
+/// Returns the persistence of the blobs to store for the `--deletable` and `--permanent` flags.
+///
+/// The flags are mutually exclusive, which clap only enforces on the command line and not for
+/// commands in JSON mode.
+fn blob_persistence(deletable: bool, permanent: bool) -> Result<BlobPersistence> {
+    BlobPersistence::from_flags(deletable, permanent)
+        .context("the `deletable` and `permanent` options are mutually exclusive")
+}
+
 async fn get_epochs_ahead(
     epoch_arg: EpochArg,
     max_epochs_ahead: EpochCount,
@@ -1330,3 +2049,20 @@ pub fn ask_for_confirmation() -> Result<bool> {
     std::io::stdin().read_line(&mut input)?;
     Ok(input.trim().to_lowercase().starts_with('y'))
 }
+
+/// Returns true if the staked WAL can be withdrawn without first requesting the withdrawal.
+///
+/// This is the case if the stake is not yet active and does not become active in the next epoch,
+/// or if the node is neither part of the current nor of the next committee.
+fn can_withdraw_stake_directly(staked_wal: &StakedWal, committees: &CommitteesAndState) -> bool {
+    let current_epoch = committees.current.epoch;
+    let in_next_committee = committees
+        .next
+        .as_ref()
+        .is_some_and(|next| next.find(&staked_wal.node_id).is_some());
+    let in_current_committee = committees.current.find(&staked_wal.node_id).is_some();
+
+    staked_wal.activation_epoch == current_epoch + 2
+        || (staked_wal.activation_epoch == current_epoch + 1 && !in_next_committee)
+        || (!in_current_committee && !in_next_committee)
+}
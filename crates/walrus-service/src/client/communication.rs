@@ -3,6 +3,7 @@
 
 //! Logic to handle the communication between the client and the storage nodes.
 
+mod adaptive;
 mod factory;
 mod node;
 
@@ -0,0 +1,257 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adaptive limit on the number of concurrent requests towards a storage node.
+//!
+//! The limit is adjusted with additive increase and multiplicative decrease (AIMD). Each request
+//! that succeeds within the tolerated latency increases the limit by `1 / limit`, i.e., by one per
+//! window of requests, while a request that fails or is too slow multiplies the limit by the
+//! decrease factor. Only requests that were started after the last decrease can cause a further
+//! decrease, such that a burst of concurrent failures reduces the limit only once.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::client::config::AdaptiveConcurrencyConfig;
+
+/// A limit on the number of concurrent requests towards a storage node that adapts to the
+/// observed latency and error rate of the requests.
+#[derive(Debug)]
+pub(crate) struct AdaptiveLimit {
+    semaphore: Arc<Semaphore>,
+    config: AdaptiveConcurrencyConfig,
+    state: Mutex<LimitState>,
+}
+
+#[derive(Debug)]
+struct LimitState {
+    /// The current limit, kept fractional to allow increases by less than one.
+    limit: f64,
+    /// The number of permits in circulation, whether held or available.
+    ///
+    /// This can temporarily exceed the limit after a decrease, until enough held permits are
+    /// returned.
+    permits: usize,
+    /// The lowest latency observed for a successful request.
+    min_latency: Option<Duration>,
+    /// The number of times the limit was decreased.
+    decreases: u64,
+}
+
+impl AdaptiveLimit {
+    /// Creates a new limit starting at `initial_limit`.
+    ///
+    /// If adaptive concurrency is disabled in the `config`, the limit is fixed.
+    pub(crate) fn new(initial_limit: usize, config: AdaptiveConcurrencyConfig) -> Self {
+        let initial_limit = if config.enabled {
+            initial_limit
+                .min(config.max_node_connections)
+                .max(config.min_node_connections)
+                .max(1)
+        } else {
+            initial_limit
+        };
+        Self {
+            semaphore: Arc::new(Semaphore::new(initial_limit)),
+            config,
+            state: Mutex::new(LimitState {
+                limit: initial_limit as f64,
+                permits: initial_limit,
+                min_latency: None,
+                decreases: 0,
+            }),
+        }
+    }
+
+    /// Waits until a request can be sent to the node within the current limit.
+    pub(crate) async fn acquire(&self) -> AdaptivePermit<'_> {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore never closed");
+        AdaptivePermit {
+            limit: self,
+            permit: Some(permit),
+            decreases_at_acquisition: self.lock_state().decreases,
+        }
+    }
+
+    /// Returns the current limit.
+    pub(crate) fn current(&self) -> usize {
+        self.lock_state().limit as usize
+    }
+
+    /// Returns the number of requests that can currently be started without waiting.
+    pub(crate) fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LimitState> {
+        self.state.lock().expect("other threads should not panic")
+    }
+
+    fn on_success(&self, state: &mut LimitState, decreases_at_acquisition: u64, latency: Duration) {
+        let min_latency = *state.min_latency.get_or_insert(latency);
+        if latency < min_latency {
+            state.min_latency = Some(latency);
+        } else if latency.as_secs_f64() > min_latency.as_secs_f64() * self.config.latency_tolerance
+        {
+            tracing::debug!(
+                ?latency,
+                ?min_latency,
+                "request exceeded the latency tolerance"
+            );
+            self.on_congestion(state, decreases_at_acquisition);
+            return;
+        }
+        state.limit =
+            (state.limit + 1.0 / state.limit).min(self.config.max_node_connections as f64);
+        self.resize(state);
+    }
+
+    fn on_congestion(&self, state: &mut LimitState, decreases_at_acquisition: u64) {
+        if decreases_at_acquisition < state.decreases {
+            // The request was already in flight when the limit was last decreased.
+            return;
+        }
+        state.limit = (state.limit * self.config.decrease_factor)
+            .max(self.config.min_node_connections as f64)
+            .max(1.0);
+        state.decreases += 1;
+        tracing::debug!(
+            limit = state.limit,
+            "decreased the concurrency limit towards the node"
+        );
+        self.resize(state);
+    }
+
+    /// Adds permits up to the current limit, or removes available permits above it.
+    ///
+    /// Permits that are currently held are removed when they are returned.
+    fn resize(&self, state: &mut LimitState) {
+        let target = state.limit as usize;
+        if target > state.permits {
+            self.semaphore.add_permits(target - state.permits);
+            state.permits = target;
+        } else if target < state.permits {
+            state.permits -= self.semaphore.forget_permits(state.permits - target);
+        }
+    }
+
+    fn release(&self, permit: OwnedSemaphorePermit) {
+        let mut state = self.lock_state();
+        if state.permits > state.limit as usize {
+            permit.forget();
+            state.permits -= 1;
+        }
+    }
+}
+
+/// A permit to send a request to the node, obtained from an [`AdaptiveLimit`].
+///
+/// The permit is returned to the limit when dropped.
+#[derive(Debug)]
+pub(crate) struct AdaptivePermit<'a> {
+    limit: &'a AdaptiveLimit,
+    permit: Option<OwnedSemaphorePermit>,
+    decreases_at_acquisition: u64,
+}
+
+impl AdaptivePermit<'_> {
+    /// Records that the request succeeded with the given latency, and releases the permit.
+    pub(crate) fn record_success(self, latency: Duration) {
+        if self.limit.config.enabled {
+            let mut state = self.limit.lock_state();
+            self.limit
+                .on_success(&mut state, self.decreases_at_acquisition, latency);
+        }
+    }
+
+    /// Records that the request failed, and releases the permit.
+    pub(crate) fn record_failure(self) {
+        if self.limit.config.enabled {
+            let mut state = self.limit.lock_state();
+            self.limit
+                .on_congestion(&mut state, self.decreases_at_acquisition);
+        }
+    }
+}
+
+impl Drop for AdaptivePermit<'_> {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            self.limit.release(permit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AdaptiveConcurrencyConfig {
+        AdaptiveConcurrencyConfig {
+            enabled: true,
+            min_node_connections: 2,
+            max_node_connections: 12,
+            latency_tolerance: 3.0,
+            decrease_factor: 0.5,
+        }
+    }
+
+    #[tokio::test]
+    async fn limit_increases_additively_and_decreases_multiplicatively() {
+        let limit = AdaptiveLimit::new(10, config());
+        for _ in 0..11 {
+            limit
+                .acquire()
+                .await
+                .record_success(Duration::from_millis(100));
+        }
+        assert_eq!(limit.current(), 11);
+        assert_eq!(limit.available_permits(), 11);
+
+        // A slow request decreases the limit, even if other permits are still held.
+        let held = limit.acquire().await;
+        limit
+            .acquire()
+            .await
+            .record_success(Duration::from_millis(400));
+        assert_eq!(limit.current(), 5);
+        assert_eq!(limit.available_permits(), 4);
+
+        // Failures of requests that started before the last decrease are ignored.
+        held.record_failure();
+        assert_eq!(limit.current(), 5);
+        assert_eq!(limit.available_permits(), 5);
+
+        limit.acquire().await.record_failure();
+        limit.acquire().await.record_failure();
+        assert_eq!(limit.current(), 2);
+        assert_eq!(limit.available_permits(), 2);
+    }
+
+    #[tokio::test]
+    async fn fixed_limit_does_not_adapt() {
+        let limit = AdaptiveLimit::new(
+            3,
+            AdaptiveConcurrencyConfig {
+                enabled: false,
+                ..config()
+            },
+        );
+        limit.acquire().await.record_failure();
+        limit
+            .acquire()
+            .await
+            .record_success(Duration::from_millis(100));
+        assert_eq!(limit.current(), 3);
+        assert_eq!(limit.available_permits(), 3);
+    }
+}
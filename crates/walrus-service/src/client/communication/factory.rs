@@ -15,16 +15,27 @@ use reqwest::Client as ReqwestClient;
 use rustls::pki_types::CertificateDer;
 use rustls_native_certs::CertificateResult;
 use tokio::sync::Semaphore;
-use walrus_core::{encoding::EncodingConfig, Epoch, NetworkPublicKey};
+use walrus_core::{encoding::EncodingConfig, Epoch, NetworkPublicKey, PublicKey};
 use walrus_sdk::{
     client::{Client as StorageNodeClient, ClientBuilder as StorageNodeClientBuilder},
     error::ClientBuildError,
 };
 use walrus_sui::types::{Committee, NetworkAddress, StorageNode};
 
-use super::{NodeCommunication, NodeReadCommunication, NodeWriteCommunication};
+use super::{
+    adaptive::AdaptiveLimit,
+    NodeCommunication,
+    NodeReadCommunication,
+    NodeWriteCommunication,
+};
 use crate::{
-    client::{ClientCommunicationConfig, ClientError, ClientErrorKind, ClientResult},
+    client::{
+        config::RequestRateConfig,
+        ClientCommunicationConfig,
+        ClientError,
+        ClientErrorKind,
+        ClientResult,
+    },
     common::active_committees::ActiveCommittees,
 };
 
@@ -33,6 +44,8 @@ pub(crate) struct NodeCommunicationFactory {
     config: ClientCommunicationConfig,
     encoding_config: Arc<EncodingConfig>,
     client_cache: Arc<Mutex<HashMap<(NetworkAddress, NetworkPublicKey), StorageNodeClient>>>,
+    /// The adaptive request limits towards the storage nodes, shared by all communications.
+    node_limits: Arc<Mutex<HashMap<PublicKey, Arc<AdaptiveLimit>>>>,
     native_certs: Vec<CertificateDer<'static>>,
     metrics_registry: Option<Registry>,
 }
@@ -66,6 +79,7 @@ impl NodeCommunicationFactory {
             config,
             encoding_config,
             client_cache: Default::default(),
+            node_limits: Default::default(),
             native_certs,
             metrics_registry,
         })
@@ -84,6 +98,7 @@ impl NodeCommunicationFactory {
                 .lock()
                 .expect("other threads should not panic"),
         );
+        self.remove_old_node_limits(committees);
 
         let write_committee = committees.write_committee();
 
@@ -112,6 +127,7 @@ impl NodeCommunicationFactory {
                 .lock()
                 .expect("other threads should not panic"),
         );
+        self.remove_old_node_limits(committees);
 
         let read_committee = committees.read_committee(certified_epoch).ok_or_else(|| {
             ClientErrorKind::BehindCurrentEpoch {
@@ -154,6 +170,8 @@ impl NodeCommunicationFactory {
     ) -> Result<Option<NodeCommunication<'a>>, ClientBuildError> {
         let node = &committee.members()[index];
         let client = self.create_client(node)?;
+        let config = self.config.node_request_rate_config();
+        let node_limit = self.node_limit(node, &config);
 
         Ok(NodeCommunication::new(
             index,
@@ -161,10 +179,26 @@ impl NodeCommunicationFactory {
             client,
             node,
             &self.encoding_config,
-            self.config.request_rate_config.clone(),
+            config,
+            node_limit,
         ))
     }
 
+    /// Returns the adaptive request limit towards the node, creating it if necessary.
+    fn node_limit(&self, node: &StorageNode, config: &RequestRateConfig) -> Arc<AdaptiveLimit> {
+        self.node_limits
+            .lock()
+            .expect("other threads should not panic")
+            .entry(node.public_key.clone())
+            .or_insert_with(|| {
+                Arc::new(AdaptiveLimit::new(
+                    config.max_node_connections,
+                    config.adaptive_concurrency.clone(),
+                ))
+            })
+            .clone()
+    }
+
     /// Builds a [`NodeReadCommunication`] object for the identified storage node within the
     /// committee.
     ///
@@ -243,6 +277,16 @@ impl NodeCommunicationFactory {
         cache.retain(|(addr, key), _| active_members.contains(&(addr, key)));
     }
 
+    /// Removes the request limits of all nodes that are not in the previous, current, or next
+    /// committee.
+    fn remove_old_node_limits(&self, committees: &ActiveCommittees) {
+        let active_members = committees.unique_node_public_keys();
+        self.node_limits
+            .lock()
+            .expect("other threads should not panic")
+            .retain(|public_key, _| active_members.contains(public_key));
+    }
+
     /// Returns a vector of [`NodeReadCommunication`] objects the total weight of which fulfills the
     /// threshold function.
     ///
@@ -330,3 +374,37 @@ fn node_communications<'a, W>(
 
     Ok(comms)
 }
+
+#[cfg(test)]
+mod tests {
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+    use crate::test_utils::test_committee_with_epoch;
+
+    #[test]
+    fn node_limits_are_shared_across_communications() -> TestResult {
+        let committees = ActiveCommittees::new(test_committee_with_epoch(&[1, 2, 3], 0), None);
+        let factory = NodeCommunicationFactory::new(
+            ClientCommunicationConfig {
+                disable_native_certs: true,
+                ..Default::default()
+            },
+            Arc::new(EncodingConfig::new(committees.n_shards())),
+            None,
+        )?;
+
+        let read_comms = factory.node_read_communications(&committees, 0)?;
+        let write_comms =
+            factory.node_write_communications(&committees, Arc::new(Semaphore::new(1)))?;
+        assert_eq!(read_comms.len(), 3);
+        for read_comm in &read_comms {
+            let write_comm = write_comms
+                .iter()
+                .find(|comm| comm.node_index == read_comm.node_index)
+                .expect("all nodes have shards");
+            assert!(Arc::ptr_eq(&read_comm.node_limit, &write_comm.node_limit));
+        }
+        Ok(())
+    }
+}
@@ -4,12 +4,18 @@
 use std::{num::NonZeroU16, sync::Arc};
 
 use anyhow::Result;
-use futures::{future::Either, stream::FuturesUnordered, Future, StreamExt};
+use futures::{
+    future::Either,
+    stream::{self, FuturesUnordered},
+    Future,
+    StreamExt,
+    TryStreamExt,
+};
 use rand::rngs::StdRng;
-use tokio::sync::Semaphore;
+use tokio::{sync::Semaphore, time::Instant};
 use tracing::{Level, Span};
 use walrus_core::{
-    encoding::{EncodingAxis, EncodingConfig, SliverData, SliverPair},
+    encoding::{EncodingAxis, EncodingConfig, Primary, Secondary, SliverData, SliverPair},
     messages::{BlobPersistenceType, SignedStorageConfirmation},
     metadata::VerifiedBlobMetadataWithId,
     BlobId,
@@ -27,10 +33,12 @@ use walrus_sdk::{
 use walrus_sui::types::StorageNode;
 use walrus_utils::backoff::{self, ExponentialBackoff};
 
+use super::adaptive::AdaptiveLimit;
 use crate::{
     client::{
         config::RequestRateConfig,
         error::{SliverStoreError, StoreError},
+        responses::StoredSlivers,
         utils::{string_prefix, WeightedResult},
     },
     common::utils::FutureHelpers,
@@ -84,7 +92,7 @@ pub(crate) struct NodeCommunication<'a, W = ()> {
     pub span: Span,
     pub client: StorageNodeClient,
     pub config: RequestRateConfig,
-    pub node_write_limit: W,
+    pub node_limit: Arc<AdaptiveLimit>,
     pub sliver_write_limit: W,
 }
 
@@ -94,6 +102,9 @@ pub type NodeWriteCommunication<'a> = NodeCommunication<'a, Arc<Semaphore>>;
 impl<'a> NodeReadCommunication<'a> {
     /// Creates a new [`NodeCommunication`].
     ///
+    /// The `node_limit` is shared by all communications with the node, such that it adapts across
+    /// operations.
+    ///
     /// Returns `None` if the `node` has no shards.
     pub fn new(
         node_index: NodeIndex,
@@ -102,6 +113,7 @@ impl<'a> NodeReadCommunication<'a> {
         node: &'a StorageNode,
         encoding_config: &'a EncodingConfig,
         config: RequestRateConfig,
+        node_limit: Arc<AdaptiveLimit>,
     ) -> Option<Self> {
         if node.shard_ids.is_empty() {
             tracing::debug!("do not create NodeCommunication for node without shards");
@@ -126,8 +138,8 @@ impl<'a> NodeReadCommunication<'a> {
                 pk_prefix = string_prefix(&node.public_key)
             ),
             client,
+            node_limit,
             config,
-            node_write_limit: (),
             sliver_write_limit: (),
         })
     }
@@ -136,7 +148,6 @@ impl<'a> NodeReadCommunication<'a> {
         self,
         sliver_write_limit: Arc<Semaphore>,
     ) -> NodeWriteCommunication<'a> {
-        let node_write_limit = Arc::new(Semaphore::new(self.config.max_node_connections));
         let Self {
             node_index,
             committee_epoch,
@@ -145,6 +156,7 @@ impl<'a> NodeReadCommunication<'a> {
             span,
             client,
             config,
+            node_limit,
             ..
         } = self;
         NodeWriteCommunication {
@@ -155,7 +167,7 @@ impl<'a> NodeReadCommunication<'a> {
             span,
             client,
             config,
-            node_write_limit,
+            node_limit,
             sliver_write_limit,
         }
     }
@@ -220,10 +232,16 @@ impl<W> NodeCommunication<'_, W> {
             "retrieving verified sliver"
         );
         let sliver_pair_index = shard_index.to_pair_index(self.n_shards(), metadata.blob_id());
+        let permit = self.node_limit.acquire().await;
+        let start = Instant::now();
         let sliver = self
             .client
             .get_and_verify_sliver(sliver_pair_index, metadata, self.encoding_config)
             .await;
+        match &sliver {
+            Ok(_) => permit.record_success(start.elapsed()),
+            Err(_) => permit.record_failure(),
+        }
 
         // Each sliver is in this case requested individually, so the weight is 1.
         self.to_node_result(1, sliver)
@@ -236,6 +254,52 @@ impl<W> NodeCommunication<'_, W> {
         self.to_node_result_with_n_shards(self.client.get_blob_status(blob_id).await)
     }
 
+    /// Requests whether the node stores the metadata of the blob and its slivers for each of the
+    /// node's shards.
+    #[tracing::instrument(level = Level::TRACE, parent = &self.span, skip_all)]
+    pub async fn get_stored_slivers(
+        &self,
+        blob_id: &BlobId,
+    ) -> NodeResult<StoredSlivers, NodeError> {
+        tracing::debug!(%blob_id, "retrieving the stored slivers");
+        let result = async {
+            let metadata_stored =
+                self.client.get_metadata_status(blob_id).await? == StoredOnNodeStatus::Stored;
+            let sliver_statuses: Vec<_> = stream::iter(self.node.shard_ids.iter())
+                .map(|shard_index| async move {
+                    let pair_index = shard_index.to_pair_index(self.n_shards(), blob_id);
+                    let primary = self
+                        .client
+                        .get_sliver_status::<Primary>(blob_id, pair_index)
+                        .await?;
+                    let secondary = self
+                        .client
+                        .get_sliver_status::<Secondary>(blob_id, pair_index)
+                        .await?;
+                    Ok::<_, NodeError>((
+                        primary == StoredOnNodeStatus::Stored,
+                        secondary == StoredOnNodeStatus::Stored,
+                    ))
+                })
+                .buffer_unordered(self.config.max_node_connections)
+                .try_collect()
+                .await?;
+            Ok(StoredSlivers {
+                metadata_stored,
+                primary_slivers: sliver_statuses
+                    .iter()
+                    .filter(|(primary, _)| *primary)
+                    .count(),
+                secondary_slivers: sliver_statuses
+                    .iter()
+                    .filter(|(_, secondary)| *secondary)
+                    .count(),
+            })
+        }
+        .await;
+        self.to_node_result_with_n_shards(result)
+    }
+
     /// Retries getting the confirmation for the blob ID.
     async fn get_confirmation_with_retries_inner(
         &self,
@@ -303,6 +367,9 @@ impl NodeWriteCommunication<'_> {
     ) -> NodeResult<SignedStorageConfirmation, StoreError> {
         tracing::debug!(blob_id = %metadata.blob_id(), "storing metadata and sliver pairs");
         let result = async {
+            #[cfg(msim)]
+            self.inject_store_failure()?;
+
             let metadata_status = self
                 .store_metadata_with_retries(metadata)
                 .await
@@ -340,6 +407,38 @@ impl NodeWriteCommunication<'_> {
         self.to_node_result_with_n_shards(result)
     }
 
+    /// Fails storing the metadata and slivers if the node is selected through the corresponding
+    /// fail point.
+    #[cfg(msim)]
+    fn inject_store_failure(&self) -> Result<(), StoreError> {
+        let mut fail = false;
+        sui_macros::fail_point_arg!(
+            "fail_point_client_store_metadata_and_pairs",
+            |public_key: PublicKey| {
+                fail = &public_key == self.public_key();
+            }
+        );
+        if fail {
+            return Err(StoreError::Metadata(NodeError::other(
+                std::io::Error::other("fail point triggered storing metadata and sliver pairs"),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Stores only the metadata on a node, without any slivers.
+    ///
+    /// Returns a [`NodeResult`], where the weight is the number of shards of the node.
+    #[tracing::instrument(level = Level::TRACE, parent = &self.span, skip_all)]
+    pub async fn store_metadata(
+        &self,
+        metadata: &VerifiedBlobMetadataWithId,
+    ) -> NodeResult<StoredOnNodeStatus, NodeError> {
+        tracing::debug!(blob_id = %metadata.blob_id(), "storing metadata");
+        let result = self.store_metadata_with_retries(metadata).await;
+        self.to_node_result_with_n_shards(result)
+    }
+
     /// Stores the metadata on the storage node.
     ///
     /// Before storing the metadata, it checks whether the metadata is already stored.
@@ -349,7 +448,9 @@ impl NodeWriteCommunication<'_> {
         metadata: &VerifiedBlobMetadataWithId,
     ) -> Result<StoredOnNodeStatus, NodeError> {
         let metadata_status = self
-            .retry_with_limits_and_backoff(|| self.client.get_metadata_status(metadata.blob_id()))
+            .retry_with_limits_and_backoff(false, || {
+                self.client.get_metadata_status(metadata.blob_id())
+            })
             .await?;
 
         match metadata_status {
@@ -357,7 +458,7 @@ impl NodeWriteCommunication<'_> {
                 tracing::debug!("the metadata is already stored on the node");
             }
             StoredOnNodeStatus::Nonexistent => {
-                self.retry_with_limits_and_backoff(|| self.client.store_metadata(metadata))
+                self.retry_with_limits_and_backoff(false, || self.client.store_metadata(metadata))
                     .await?;
             }
         }
@@ -407,7 +508,8 @@ impl NodeWriteCommunication<'_> {
         while let Some(result) = requests.next().await {
             if let Err(error) = result {
                 tracing::warn!(
-                    node_permits=?self.node_write_limit.available_permits(),
+                    node_limit=self.node_limit.current(),
+                    node_permits=?self.node_limit.available_permits(),
                     sliver_permits=?self.sliver_write_limit.available_permits(),
                     ?error,
                     ?self.config.backoff_config.max_retries,
@@ -416,7 +518,8 @@ impl NodeWriteCommunication<'_> {
                 return Err(error);
             }
             tracing::trace!(
-                node_permits=?self.node_write_limit.available_permits(),
+                node_limit=self.node_limit.current(),
+                node_permits=?self.node_limit.available_permits(),
                 sliver_permits=?self.sliver_write_limit.available_permits(),
                 progress = format!("{}/{}", n_slivers - requests.len(), n_slivers),
                 "sliver stored"
@@ -478,13 +581,15 @@ impl NodeWriteCommunication<'_> {
         sliver: &SliverData<A>,
         pair_index: SliverPairIndex,
     ) -> Result<(), SliverStoreError> {
-        self.retry_with_limits_and_backoff(|| self.client.store_sliver(blob_id, pair_index, sliver))
-            .await
-            .map_err(|error| SliverStoreError {
-                pair_index,
-                sliver_type: A::sliver_type(),
-                error,
-            })
+        self.retry_with_limits_and_backoff(true, || {
+            self.client.store_sliver(blob_id, pair_index, sliver)
+        })
+        .await
+        .map_err(|error| SliverStoreError {
+            pair_index,
+            sliver_type: A::sliver_type(),
+            error,
+        })
     }
 
     /// Requests the status for sliver after retrying.
@@ -493,7 +598,7 @@ impl NodeWriteCommunication<'_> {
         blob_id: &BlobId,
         pair_index: SliverPairIndex,
     ) -> Result<StoredOnNodeStatus, SliverStoreError> {
-        self.retry_with_limits_and_backoff(|| {
+        self.retry_with_limits_and_backoff(false, || {
             self.client.get_sliver_status::<A>(blob_id, pair_index)
         })
         .await
@@ -504,14 +609,35 @@ impl NodeWriteCommunication<'_> {
         })
     }
 
-    async fn retry_with_limits_and_backoff<F, Fut, T, E>(&self, f: F) -> Result<T, E>
+    /// Retries the request according to the backoff strategy, acquiring a permit from the node
+    /// limit and then from the sliver write limit for each attempt.
+    ///
+    /// Failed attempts decrease the node limit. If `track_latency` is true, the latency of
+    /// successful attempts is used to adapt the node limit; this should only be the case for
+    /// requests that transfer slivers, such that the latencies are comparable.
+    async fn retry_with_limits_and_backoff<F, Fut, T, E>(
+        &self,
+        track_latency: bool,
+        mut f: F,
+    ) -> Result<T, E>
     where
         F: FnMut() -> Fut,
         Fut: Future<Output = Result<T, E>>,
     {
-        backoff::retry(self.backoff_strategy(), f)
-            .batch_limit(self.node_write_limit.clone())
-            .batch_limit(self.sliver_write_limit.clone())
-            .await
+        backoff::retry(self.backoff_strategy(), || {
+            let request = f();
+            async move {
+                let permit = self.node_limit.acquire().await;
+                let start = Instant::now();
+                let result = request.batch_limit(self.sliver_write_limit.clone()).await;
+                match &result {
+                    Ok(_) if track_latency => permit.record_success(start.elapsed()),
+                    Ok(_) => drop(permit),
+                    Err(_) => permit.record_failure(),
+                }
+                result
+            }
+        })
+        .await
     }
 }
@@ -35,7 +35,7 @@ use walrus_sui::{
 };
 use walrus_utils::backoff::ExponentialBackoffConfig;
 
-use super::daemon::CacheConfig;
+use super::daemon::{CacheConfig, QuotaConfig};
 use crate::{
     client::{error::JwtDecodeError, refresh::CommitteesRefreshConfig},
     common::utils,
@@ -76,9 +76,23 @@ pub struct Config {
 impl Config {
     /// Loads the Walrus client configuration from the given path along with a context. If the file
     /// is a multi-config file, the context argument can be used to override the default context.
+    ///
+    /// Fields of the selected configuration can be overridden with `WALRUS_CLIENT__*` environment
+    /// variables, see [`utils::with_env_overrides`].
     pub fn load_from_multi_config(
         path: impl AsRef<Path>,
         context: Option<&str>,
+    ) -> anyhow::Result<(Self, Option<String>)> {
+        let (config, context) = Self::load_from_multi_config_file(path, context)?;
+        Ok((
+            utils::with_env_overrides(config, utils::CLIENT_CONFIG_ENV_PREFIX)?,
+            context,
+        ))
+    }
+
+    fn load_from_multi_config_file(
+        path: impl AsRef<Path>,
+        context: Option<&str>,
     ) -> anyhow::Result<(Self, Option<String>)> {
         let path = path.as_ref();
         match utils::load_from_yaml(path)? {
@@ -185,6 +199,8 @@ pub struct AuthConfig {
     pub(crate) verify_upload: bool,
     /// The configuration for the replay suppression cache.
     pub(crate) replay_suppression_config: CacheConfig,
+    /// The per-subject quotas on uploads; if set, tokens must specify a subject.
+    pub(crate) quota_config: Option<QuotaConfig>,
 }
 
 impl fmt::Debug for AuthConfig {
@@ -193,6 +209,7 @@ impl fmt::Debug for AuthConfig {
             .field("algorithm", &self.algorithm)
             .field("expiring_sec", &self.expiring_sec)
             .field("verify_upload", &self.verify_upload)
+            .field("quota_config", &self.quota_config)
             .finish()
     }
 }
@@ -275,6 +292,8 @@ pub struct ClientCommunicationConfig {
     pub max_total_blob_size: usize,
     /// The configuration for the backoff after committee change is detected.
     pub committee_change_backoff: ExponentialBackoffConfig,
+    /// The per-phase timeouts and retry overrides of the client.
+    pub retry_policy: RetryPolicy,
 }
 
 impl Default for ClientCommunicationConfig {
@@ -297,11 +316,44 @@ impl Default for ClientCommunicationConfig {
                 Duration::from_secs(5),
                 Some(5),
             ),
+            retry_policy: Default::default(),
         }
     }
 }
 
 impl ClientCommunicationConfig {
+    /// Returns the request rate configuration for the storage nodes, with the backoff of the
+    /// [`RetryPolicy`] applied, if set.
+    pub fn node_request_rate_config(&self) -> RequestRateConfig {
+        let mut config = self.request_rate_config.clone();
+        if let Some(backoff) = &self.retry_policy.node_backoff {
+            config.backoff_config = backoff.clone();
+        }
+        config
+    }
+
+    /// Returns the backoff configuration for requests to the storage nodes.
+    ///
+    /// This is the backoff of the [`RetryPolicy`] if set, and the one of the
+    /// [`RequestRateConfig`] otherwise.
+    pub fn node_backoff_config(&self) -> &ExponentialBackoffConfig {
+        self.retry_policy
+            .node_backoff
+            .as_ref()
+            .unwrap_or(&self.request_rate_config.backoff_config)
+    }
+
+    /// Returns the backoff configuration for retries after a committee change.
+    ///
+    /// This is the backoff of the [`RetryPolicy`] if set, and
+    /// [`committee_change_backoff`][Self::committee_change_backoff] otherwise.
+    pub fn committee_change_backoff_config(&self) -> &ExponentialBackoffConfig {
+        self.retry_policy
+            .committee_change_backoff
+            .as_ref()
+            .unwrap_or(&self.committee_change_backoff)
+    }
+
     /// Provides a config with lower number of retries to speed up integration testing.
     #[cfg(any(test, feature = "test-utils"))]
     pub fn default_for_test() -> Self {
@@ -321,6 +373,7 @@ impl ClientCommunicationConfig {
                     min_backoff: Duration::from_secs(2),
                     max_backoff: Duration::from_secs(10),
                 },
+                adaptive_concurrency: Default::default(),
             },
             ..Default::default()
         }
@@ -450,9 +503,14 @@ impl CommunicationLimits {
 #[serde(default)]
 pub struct RequestRateConfig {
     /// The maximum number of connections the client can open towards each node.
+    ///
+    /// If adaptive concurrency is enabled, this is the initial limit, which is then adjusted
+    /// within the bounds of the [`AdaptiveConcurrencyConfig`].
     pub max_node_connections: usize,
     /// The configuration for the backoff strategy.
     pub backoff_config: ExponentialBackoffConfig,
+    /// The configuration for adapting the number of connections towards each node.
+    pub adaptive_concurrency: AdaptiveConcurrencyConfig,
 }
 
 impl Default for RequestRateConfig {
@@ -460,6 +518,43 @@ impl Default for RequestRateConfig {
         Self {
             max_node_connections: 10,
             backoff_config: Default::default(),
+            adaptive_concurrency: Default::default(),
+        }
+    }
+}
+
+/// Configuration for adapting the number of concurrent sliver requests towards each node.
+///
+/// The limit is adjusted with additive increase and multiplicative decrease (AIMD): it grows by
+/// one for every window of requests that complete successfully and fast, and is multiplied by the
+/// `decrease_factor` when a request fails or takes longer than `latency_tolerance` times the
+/// lowest latency observed for the node.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct AdaptiveConcurrencyConfig {
+    /// Whether to adapt the limit.
+    ///
+    /// If disabled, the limit is fixed to [`RequestRateConfig::max_node_connections`].
+    pub enabled: bool,
+    /// The minimum number of concurrent requests towards each node.
+    pub min_node_connections: usize,
+    /// The maximum number of concurrent requests towards each node.
+    pub max_node_connections: usize,
+    /// The factor by which the latency of a request can exceed the lowest latency observed for
+    /// the node before the limit is decreased.
+    pub latency_tolerance: f64,
+    /// The factor by which the limit is multiplied when it is decreased.
+    pub decrease_factor: f64,
+}
+
+impl Default for AdaptiveConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_node_connections: 1,
+            max_node_connections: 50,
+            latency_tolerance: 3.0,
+            decrease_factor: 0.5,
         }
     }
 }
@@ -620,6 +715,138 @@ impl Default for SliverWriteExtraTime {
     }
 }
 
+/// A phase of storing or reading a blob that can be bounded by a [`RetryPolicy`] timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientPhase {
+    /// Retrieving the metadata of a blob from the storage nodes.
+    Metadata,
+    /// Uploading the metadata and slivers to a quorum of storage nodes.
+    SliverUpload,
+    /// Collecting storage confirmations for blobs that are already stored on the nodes.
+    Confirmation,
+    /// Certifying the blobs on Sui.
+    Certification,
+}
+
+impl fmt::Display for ClientPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase = match self {
+            ClientPhase::Metadata => "metadata retrieval",
+            ClientPhase::SliverUpload => "sliver upload",
+            ClientPhase::Confirmation => "confirmation collection",
+            ClientPhase::Certification => "certification",
+        };
+        f.write_str(phase)
+    }
+}
+
+/// The timeouts and retry limits of the client, to adapt it to the latency tolerance of the
+/// application.
+///
+/// All values are optional. Phases without a timeout run until they succeed or exhaust their
+/// retries, and unset backoffs fall back to the corresponding values of the
+/// [`ClientCommunicationConfig`]. Use [`RetryPolicy::builder`] to construct a policy in code.
+#[serde_as]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// The maximum time to retrieve the metadata of a blob when reading.
+    #[serde(rename = "metadata_timeout_millis")]
+    #[serde_as(as = "Option<DurationMilliSeconds>")]
+    pub metadata_timeout: Option<Duration>,
+    /// The maximum time to upload the metadata and slivers to a quorum of storage nodes.
+    ///
+    /// This does not include the [extra time][ClientCommunicationConfig::sliver_write_extra_time]
+    /// for storing slivers on additional nodes.
+    #[serde(rename = "sliver_upload_timeout_millis")]
+    #[serde_as(as = "Option<DurationMilliSeconds>")]
+    pub sliver_upload_timeout: Option<Duration>,
+    /// The maximum time to collect storage confirmations for blobs that are already stored.
+    #[serde(rename = "confirmation_timeout_millis")]
+    #[serde_as(as = "Option<DurationMilliSeconds>")]
+    pub confirmation_timeout: Option<Duration>,
+    /// The maximum time to wait for the certification of the blobs on Sui.
+    ///
+    /// Note that the certification transaction may still be executed after the timeout expired.
+    #[serde(rename = "certification_timeout_millis")]
+    #[serde_as(as = "Option<DurationMilliSeconds>")]
+    pub certification_timeout: Option<Duration>,
+    /// The backoff for retrying requests to individual storage nodes.
+    ///
+    /// Overrides the backoff in the [`RequestRateConfig`].
+    pub node_backoff: Option<ExponentialBackoffConfig>,
+    /// The backoff for retrying operations that failed because of a committee change.
+    ///
+    /// Overrides [`ClientCommunicationConfig::committee_change_backoff`].
+    pub committee_change_backoff: Option<ExponentialBackoffConfig>,
+}
+
+impl RetryPolicy {
+    /// Returns a builder for a policy without any timeouts or overrides.
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::default()
+    }
+
+    /// Returns the timeout for the given phase, if any.
+    pub fn timeout(&self, phase: ClientPhase) -> Option<Duration> {
+        match phase {
+            ClientPhase::Metadata => self.metadata_timeout,
+            ClientPhase::SliverUpload => self.sliver_upload_timeout,
+            ClientPhase::Confirmation => self.confirmation_timeout,
+            ClientPhase::Certification => self.certification_timeout,
+        }
+    }
+}
+
+/// A builder for a [`RetryPolicy`].
+#[derive(Debug, Default, Clone)]
+pub struct RetryPolicyBuilder {
+    policy: RetryPolicy,
+}
+
+impl RetryPolicyBuilder {
+    /// Sets the timeout for retrieving the metadata of a blob.
+    pub fn metadata_timeout(mut self, timeout: Duration) -> Self {
+        self.policy.metadata_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for uploading the metadata and slivers to a quorum of storage nodes.
+    pub fn sliver_upload_timeout(mut self, timeout: Duration) -> Self {
+        self.policy.sliver_upload_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for collecting storage confirmations.
+    pub fn confirmation_timeout(mut self, timeout: Duration) -> Self {
+        self.policy.confirmation_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for certifying blobs on Sui.
+    pub fn certification_timeout(mut self, timeout: Duration) -> Self {
+        self.policy.certification_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the backoff curve and retry cap for requests to individual storage nodes.
+    pub fn node_backoff(mut self, backoff: ExponentialBackoffConfig) -> Self {
+        self.policy.node_backoff = Some(backoff);
+        self
+    }
+
+    /// Sets the backoff curve and retry cap for retries after a committee change.
+    pub fn committee_change_backoff(mut self, backoff: ExponentialBackoffConfig) -> Self {
+        self.policy.committee_change_backoff = Some(backoff);
+        self
+    }
+
+    /// Returns the configured policy.
+    pub fn build(self) -> RetryPolicy {
+        self.policy
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
@@ -767,6 +994,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn retry_policy_overrides_communication_config() -> TestResult {
+        let yaml = indoc! {"
+            metadata_timeout_millis: 2000
+            node_backoff:
+                min_backoff_millis: 100
+                max_backoff_millis: 1000
+                max_retries: 2
+        "};
+        let policy: RetryPolicy = serde_yaml::from_str(yaml)?;
+        let node_backoff = ExponentialBackoffConfig::new(
+            Duration::from_millis(100),
+            Duration::from_secs(1),
+            Some(2),
+        );
+        assert_eq!(
+            policy,
+            RetryPolicy::builder()
+                .metadata_timeout(Duration::from_secs(2))
+                .node_backoff(node_backoff.clone())
+                .build()
+        );
+        assert_eq!(policy.timeout(ClientPhase::SliverUpload), None);
+
+        let config = ClientCommunicationConfig {
+            retry_policy: policy,
+            ..Default::default()
+        };
+        assert_eq!(config.node_backoff_config(), &node_backoff);
+        assert_eq!(
+            config.node_request_rate_config().backoff_config,
+            node_backoff
+        );
+        assert_eq!(
+            config.committee_change_backoff_config(),
+            &config.committee_change_backoff
+        );
+
+        Ok(())
+    }
+
     param_test! {
         test_secret_to_bytes -> TestResult: [
             correct: ("0xff", Ok(vec![255])),
@@ -3,7 +3,7 @@
 
 //! A client daemon who serves a set of simple HTTP endpoints to store, encode, or read blobs.
 
-use std::{collections::HashSet, fmt::Debug, net::SocketAddr, sync::Arc};
+use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use axum::{
     body::HttpBody,
@@ -11,19 +11,41 @@ use axum::{
     extract::{DefaultBodyLimit, Query, Request, State},
     middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::{get, put},
+    routing::{get, post, put, MethodRouter},
     BoxError,
     Router,
 };
 use axum_extra::{
     headers::{authorization::Bearer, Authorization},
+    typed_header::TypedHeaderRejection,
     TypedHeader,
 };
-use openapi::{AggregatorApiDoc, DaemonApiDoc, PublisherApiDoc};
+use axum_server::Handle;
+use bytes::Bytes;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt as _,
+};
+use openapi::{AggregatorApiDoc, DaemonApiDoc, PublisherApiDoc, UnsignedPublisherApiDoc};
 use prometheus::Registry;
-use reqwest::StatusCode;
 pub use routes::PublisherQuery;
-use routes::{BLOB_GET_ENDPOINT, BLOB_OBJECT_GET_ENDPOINT, BLOB_PUT_ENDPOINT, STATUS_ENDPOINT};
+use routes::{
+    BLOB_BATCH_GET_ENDPOINT,
+    BLOB_GET_ENDPOINT,
+    BLOB_INFO_ENDPOINT,
+    BLOB_OBJECT_GET_ENDPOINT,
+    BLOB_PUT_ENDPOINT,
+    BLOB_QUERY_ENDPOINT,
+    BLOB_STATUS_ENDPOINT,
+    JOB_GET_ENDPOINT,
+    JOB_PUT_ENDPOINT,
+    SITE_FILE_ENDPOINT,
+    SITE_REDIRECT_ENDPOINT,
+    SITE_ROOT_ENDPOINT,
+    STATUS_ENDPOINT,
+    UNSIGNED_BLOB_CERTIFY_ENDPOINT,
+    UNSIGNED_BLOB_PUT_ENDPOINT,
+};
 use sui_types::base_types::ObjectID;
 use tower::{
     buffer::BufferLayer,
@@ -31,30 +53,84 @@ use tower::{
     load_shed::{error::Overloaded, LoadShedLayer},
     ServiceBuilder,
 };
-use tower_http::trace::TraceLayer;
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use utoipa::OpenApi;
 use utoipa_redoc::{Redoc, Servable};
 use walrus_core::{encoding::Primary, BlobId, EncodingType, EpochCount, DEFAULT_ENCODING};
+use walrus_proc_macros::RestApiError;
+use walrus_sdk::api::errors::DAEMON_ERROR_DOMAIN;
 use walrus_sui::{
-    client::{BlobPersistence, PostStoreAction, ReadClient, SuiContractClient},
+    client::{BlobPersistence, PostStoreAction, ReadClient, SuiContractClient, SuiReadClient},
     types::move_structs::BlobWithAttribute,
 };
 
-use super::{responses::BlobStoreResult, Client, ClientResult, StoreWhen};
+use super::{
+    responses::{BlobCertificationStatus, BlobReadInfo, BlobStoreResult},
+    Client,
+    ClientResult,
+    MetadataReplication,
+    StoreWhen,
+};
 use crate::{
     client::{
-        cli::{AggregatorArgs, PublisherArgs},
+        cli::{AggregatorArgs, DaemonArgs, PublisherArgs, UnsignedPublisherArgs},
         config::AuthConfig,
-        daemon::auth::verify_jwt_claim,
+        daemon::auth::{verify_jwt_claim, PublisherAuthError},
+        receipt::ReceiptSigner,
+        site,
+    },
+    common::{
+        api::RestApiError,
+        telemetry::{metrics_middleware, request_id_middleware, HttpServerMetrics, MakeHttpSpan},
     },
-    common::telemetry::{metrics_middleware, HttpServerMetrics, MakeHttpSpan},
 };
 
+mod attribute_index;
+pub(crate) use attribute_index::AttributeIndex;
 pub mod auth;
+mod batch;
+use batch::BatchGetLimits;
+mod blob_cache;
+pub(crate) use blob_cache::{BlobCache, CachingClient};
 pub(crate) mod cache;
 pub(crate) use cache::{CacheConfig, CacheHandle};
+mod conditional;
+mod cors;
+pub use cors::CorsConfig;
+mod headers;
+use headers::AttributeHeaderPolicy;
+mod jobs;
+use jobs::JobQueue;
+mod quota;
+pub(crate) use quota::QuotaTracker;
+pub use quota::{QuotaConfig, SubjectQuota};
 mod openapi;
 mod routes;
+mod tls;
+pub use tls::TlsConfig;
+mod unsigned;
+use unsigned::UnsignedPublisher;
+
+/// A stream of the contents of a blob.
+///
+/// The stream ends with an error if the blob cannot be read completely, or if it turns out to be
+/// inconsistent; the full `length` bytes are only yielded for a blob that was read successfully.
+pub struct BlobStream {
+    /// The length of the blob in bytes.
+    pub length: u64,
+    /// The contents of the blob.
+    pub data: BoxStream<'static, ClientResult<Bytes>>,
+}
+
+impl BlobStream {
+    /// Returns a stream yielding the blob, which has already been read in full, as a single chunk.
+    pub fn from_blob(blob: Vec<u8>) -> Self {
+        Self {
+            length: blob.len() as u64,
+            data: stream::once(async move { Ok(Bytes::from(blob)) }).boxed(),
+        }
+    }
+}
 
 pub trait WalrusReadClient {
     fn read_blob(
@@ -62,15 +138,41 @@ pub trait WalrusReadClient {
         blob_id: &BlobId,
     ) -> impl std::future::Future<Output = ClientResult<Vec<u8>>> + Send;
 
+    /// Reads the blob and returns a stream of its contents.
+    ///
+    /// By default, the full blob is read and returned as a single chunk.
+    fn read_blob_stream(
+        self: Arc<Self>,
+        blob_id: BlobId,
+    ) -> impl std::future::Future<Output = ClientResult<BlobStream>> + Send
+    where
+        Self: Send + Sync + 'static,
+    {
+        async move { Ok(BlobStream::from_blob(self.read_blob(&blob_id).await?)) }
+    }
+
     fn get_blob_by_object_id(
         &self,
         blob_object_id: &ObjectID,
     ) -> impl std::future::Future<Output = ClientResult<BlobWithAttribute>> + Send;
+
+    /// Returns information about the certified blob without reading it.
+    fn blob_read_info(
+        &self,
+        blob_id: &BlobId,
+    ) -> impl std::future::Future<Output = ClientResult<BlobReadInfo>> + Send;
+
+    /// Returns the certification status of the blob without reading it or its metadata.
+    fn get_blob_status(
+        &self,
+        blob_id: &BlobId,
+    ) -> impl std::future::Future<Output = ClientResult<BlobCertificationStatus>> + Send;
 }
 
 /// Trait representing a client that can write blobs to Walrus.
 pub trait WalrusWriteClient: WalrusReadClient {
     /// Writes a blob to Walrus.
+    #[allow(clippy::too_many_arguments)]
     fn write_blob(
         &self,
         blob: &[u8],
@@ -79,23 +181,59 @@ pub trait WalrusWriteClient: WalrusReadClient {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> impl std::future::Future<Output = ClientResult<BlobStoreResult>> + Send;
 
     /// Returns the default [`PostStoreAction`] for this client.
     fn default_post_store_action(&self) -> PostStoreAction;
+
+    /// Computes the ID of the blob for the encoding type, without storing it.
+    fn compute_blob_id(
+        &self,
+        blob: &[u8],
+        encoding_type: Option<EncodingType>,
+    ) -> ClientResult<BlobId>;
 }
 
-impl<T: ReadClient> WalrusReadClient for Client<T> {
+impl<T: ReadClient + 'static> WalrusReadClient for Client<T> {
     async fn read_blob(&self, blob_id: &BlobId) -> ClientResult<Vec<u8>> {
         self.read_blob_retry_committees::<Primary>(blob_id).await
     }
 
+    /// Streams the blob, prefetching as many slivers ahead of the consumer as the client reads
+    /// concurrently.
+    ///
+    /// Starting the stream is retried if it fails due to epoch change.
+    async fn read_blob_stream(self: Arc<Self>, blob_id: BlobId) -> ClientResult<BlobStream>
+    where
+        Self: Send + Sync + 'static,
+    {
+        let prefetch_window = self.communication_limits.max_concurrent_sliver_reads;
+        let (length, data) = self
+            .retry_if_notified_epoch_change(|| {
+                self.read_blob_stream_prefetched(&blob_id, prefetch_window)
+            })
+            .await?;
+        Ok(BlobStream {
+            length,
+            data: data.boxed(),
+        })
+    }
+
     async fn get_blob_by_object_id(
         &self,
         blob_object_id: &ObjectID,
     ) -> ClientResult<BlobWithAttribute> {
         self.get_blob_by_object_id(blob_object_id).await
     }
+
+    async fn blob_read_info(&self, blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+        self.blob_read_info_retry_committees(blob_id).await
+    }
+
+    async fn get_blob_status(&self, blob_id: &BlobId) -> ClientResult<BlobCertificationStatus> {
+        self.get_blob_status_retry_committees(blob_id).await
+    }
 }
 
 impl WalrusWriteClient for Client<SuiContractClient> {
@@ -107,6 +245,7 @@ impl WalrusWriteClient for Client<SuiContractClient> {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<BlobStoreResult> {
         let encoding_type = encoding_type.unwrap_or(DEFAULT_ENCODING);
 
@@ -118,6 +257,7 @@ impl WalrusWriteClient for Client<SuiContractClient> {
                 store_when,
                 persistence,
                 post_store,
+                metadata_replication,
             )
             .await?;
 
@@ -130,6 +270,14 @@ impl WalrusWriteClient for Client<SuiContractClient> {
     fn default_post_store_action(&self) -> PostStoreAction {
         PostStoreAction::Keep
     }
+
+    fn compute_blob_id(
+        &self,
+        blob: &[u8],
+        encoding_type: Option<EncodingType>,
+    ) -> ClientResult<BlobId> {
+        Client::compute_blob_id(self, blob, encoding_type.unwrap_or(DEFAULT_ENCODING))
+    }
 }
 
 /// The client daemon.
@@ -142,19 +290,25 @@ pub struct ClientDaemon<T> {
     network_address: SocketAddr,
     metrics: HttpServerMetrics,
     router: Router<Arc<T>>,
-    allowed_headers: Arc<HashSet<String>>,
+    attribute_headers: Arc<AttributeHeaderPolicy>,
+    cors: CorsLayer,
+    tls_config: TlsConfig,
 }
 
 impl<T: WalrusReadClient + Send + Sync + 'static> ClientDaemon<T> {
     /// Constructs a new [`ClientDaemon`] with aggregator functionality.
     pub fn new_aggregator(
         client: T,
-        network_address: SocketAddr,
         registry: &Registry,
-        allowed_headers: Vec<String>,
-    ) -> Self {
-        Self::new::<AggregatorApiDoc>(client, network_address, registry)
-            .with_aggregator(allowed_headers)
+        daemon_args: &DaemonArgs,
+        aggregator_args: &AggregatorArgs,
+    ) -> anyhow::Result<Self> {
+        Ok(
+            Self::new::<AggregatorApiDoc>(client, daemon_args.bind_address, registry)
+                .with_cors(&daemon_args.cors_config)?
+                .with_tls(&daemon_args.tls_config)
+                .with_aggregator(aggregator_args),
+        )
     }
 
     /// Creates a new [`ClientDaemon`], which serves requests at the provided `network_address` and
@@ -170,49 +324,138 @@ impl<T: WalrusReadClient + Send + Sync + 'static> ClientDaemon<T> {
             router: Router::new()
                 .merge(Redoc::with_url(routes::API_DOCS, A::openapi()))
                 .route(STATUS_ENDPOINT, get(routes::status)),
-            allowed_headers: Arc::new(HashSet::new()),
+            attribute_headers: Default::default(),
+            cors: CorsLayer::new(),
+            tls_config: Default::default(),
         }
     }
 
+    /// Specifies the CORS policy of all endpoints.
+    fn with_cors(mut self, cors_config: &CorsConfig) -> anyhow::Result<Self> {
+        tracing::info!(?cors_config, "configuring the CORS policy");
+        self.cors = cors_config.layer()?;
+        Ok(self)
+    }
+
+    /// Specifies how the daemon terminates TLS.
+    fn with_tls(mut self, tls_config: &TlsConfig) -> Self {
+        self.tls_config = tls_config.clone();
+        self
+    }
+
     /// Specifies that the daemon should expose the aggregator interface (read blobs).
-    fn with_aggregator(mut self, allowed_headers: Vec<String>) -> Self {
-        self.with_allowed_headers(allowed_headers);
-        tracing::info!("Aggregator allowed headers: {:?}", self.allowed_headers);
+    fn with_aggregator(mut self, aggregator_args: &AggregatorArgs) -> Self {
+        let manifest_cache = site::new_manifest_cache();
+        self.attribute_headers = Arc::new(AttributeHeaderPolicy::new(
+            aggregator_args.allowed_headers.clone(),
+            aggregator_args.allowed_content_types.clone(),
+        ));
+        tracing::info!(
+            "Aggregator attribute header policy: {:?}",
+            self.attribute_headers
+        );
         self.router = self
             .router
-            .route(BLOB_GET_ENDPOINT, get(routes::get_blob))
+            .route(
+                BLOB_GET_ENDPOINT,
+                get(routes::get_blob).head(routes::head_blob),
+            )
+            .route(BLOB_INFO_ENDPOINT, get(routes::get_blob_info))
+            .route(BLOB_STATUS_ENDPOINT, get(routes::get_blob_status))
+            .route(
+                BLOB_BATCH_GET_ENDPOINT,
+                post(routes::batch_get_blobs).with_state((
+                    self.client.clone(),
+                    BatchGetLimits {
+                        max_blobs: aggregator_args.max_batch_get_blobs,
+                        max_concurrent_reads: aggregator_args.max_concurrent_batch_get_reads,
+                        max_total_size: aggregator_args.max_batch_get_size,
+                    },
+                )),
+            )
             .route(
                 BLOB_OBJECT_GET_ENDPOINT,
                 get(routes::get_blob_by_object_id)
-                    .with_state((self.client.clone(), self.allowed_headers.clone())),
-            );
+                    .with_state((self.client.clone(), self.attribute_headers.clone())),
+            )
+            .route(
+                SITE_ROOT_ENDPOINT,
+                get(routes::get_site_file).with_state((
+                    self.client.clone(),
+                    self.attribute_headers.clone(),
+                    manifest_cache.clone(),
+                )),
+            )
+            .route(
+                SITE_FILE_ENDPOINT,
+                get(routes::get_site_file).with_state((
+                    self.client.clone(),
+                    self.attribute_headers.clone(),
+                    manifest_cache.clone(),
+                )),
+            )
+            .route(SITE_REDIRECT_ENDPOINT, get(routes::redirect_to_site_root));
+        self
+    }
+
+    /// Specifies that the daemon should expose the endpoint to query blobs by their attributes,
+    /// served from the provided index.
+    pub(crate) fn with_attribute_index(mut self, attribute_index: Arc<AttributeIndex>) -> Self {
+        self.router = self.router.route(
+            BLOB_QUERY_ENDPOINT,
+            get(routes::get_blobs_by_attribute).with_state(attribute_index),
+        );
         self
     }
 
     /// Runs the daemon.
-    pub async fn run(self) -> Result<(), std::io::Error> {
-        let listener = tokio::net::TcpListener::bind(self.network_address).await?;
-        tracing::info!(address = %self.network_address, "the client daemon is starting");
+    ///
+    /// If TLS is configured, the daemon serves HTTPS; this first obtains a certificate if ACME is
+    /// used and no valid certificate is cached.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let tls_config = self.tls_config.load().await?;
 
         let request_layers = ServiceBuilder::new()
             .layer(middleware::from_fn_with_state(
                 self.metrics.clone(),
                 metrics_middleware,
             ))
+            .layer(middleware::from_fn(request_id_middleware))
             .layer(
                 TraceLayer::new_for_http()
                     .make_span_with(MakeHttpSpan::new())
                     .on_response(MakeHttpSpan::new()),
-            );
+            )
+            .layer(self.cors);
+        let app = self.router.with_state(self.client).layer(request_layers);
 
-        axum::serve(
-            listener,
-            self.router.with_state(self.client).layer(request_layers),
-        )
-        .with_graceful_shutdown(async {
-            let _ = tokio::signal::ctrl_c().await;
-        })
-        .await
+        if let Some(tls_config) = tls_config {
+            tracing::info!(
+                address = %self.network_address,
+                "the client daemon is starting with TLS"
+            );
+            let handle = Handle::new();
+            tokio::spawn({
+                let handle = handle.clone();
+                async move {
+                    let _ = tokio::signal::ctrl_c().await;
+                    handle.graceful_shutdown(None);
+                }
+            });
+            axum_server::bind_rustls(self.network_address, tls_config)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await?;
+        } else {
+            let listener = tokio::net::TcpListener::bind(self.network_address).await?;
+            tracing::info!(address = %self.network_address, "the client daemon is starting");
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = tokio::signal::ctrl_c().await;
+                })
+                .await?;
+        }
+        Ok(())
     }
 }
 
@@ -221,18 +464,13 @@ impl<T: WalrusWriteClient + Send + Sync + 'static> ClientDaemon<T> {
     pub fn new_publisher(
         client: T,
         auth_config: Option<AuthConfig>,
-        network_address: SocketAddr,
-        max_body_limit: usize,
         registry: &Registry,
-        max_request_buffer_size: usize,
-        max_concurrent_requests: usize,
-    ) -> Self {
-        Self::new::<PublisherApiDoc>(client, network_address, registry).with_publisher(
-            auth_config,
-            max_body_limit,
-            max_request_buffer_size,
-            max_concurrent_requests,
-        )
+        publisher_args: &PublisherArgs,
+    ) -> anyhow::Result<Self> {
+        Self::new::<PublisherApiDoc>(client, publisher_args.daemon_args.bind_address, registry)
+            .with_cors(&publisher_args.daemon_args.cors_config)?
+            .with_tls(&publisher_args.daemon_args.tls_config)
+            .with_publisher(auth_config, publisher_args)
     }
 
     /// Constructs a new [`ClientDaemon`] with combined aggregator and publisher functionality.
@@ -242,25 +480,26 @@ impl<T: WalrusWriteClient + Send + Sync + 'static> ClientDaemon<T> {
         registry: &Registry,
         publisher_args: &PublisherArgs,
         aggregator_args: &AggregatorArgs,
-    ) -> Self {
+    ) -> anyhow::Result<Self> {
         Self::new::<DaemonApiDoc>(client, publisher_args.daemon_args.bind_address, registry)
-            .with_aggregator(aggregator_args.allowed_headers.clone())
-            .with_publisher(
-                auth_config,
-                publisher_args.max_body_size_kib,
-                publisher_args.max_request_buffer_size,
-                publisher_args.max_concurrent_requests,
-            )
+            .with_cors(&publisher_args.daemon_args.cors_config)?
+            .with_tls(&publisher_args.daemon_args.tls_config)
+            .with_aggregator(aggregator_args)
+            .with_publisher(auth_config, publisher_args)
     }
 
     /// Specifies that the daemon should expose the publisher interface (store blobs).
+    ///
+    /// If a job queue directory is configured, the endpoints to submit store jobs and to poll
+    /// their status are exposed as well.
     fn with_publisher(
         mut self,
         auth_config: Option<AuthConfig>,
-        max_body_limit: usize,
-        max_request_buffer_size: usize,
-        max_concurrent_requests: usize,
-    ) -> Self {
+        publisher_args: &PublisherArgs,
+    ) -> anyhow::Result<Self> {
+        let max_body_limit = publisher_args.max_body_size();
+        let max_request_buffer_size = publisher_args.max_request_buffer_size;
+        let max_concurrent_requests = publisher_args.max_concurrent_requests;
         tracing::debug!(
             %max_body_limit,
             %max_request_buffer_size,
@@ -275,44 +514,130 @@ impl<T: WalrusWriteClient + Send + Sync + 'static> ClientDaemon<T> {
             .layer(BufferLayer::new(max_request_buffer_size))
             .layer(ConcurrencyLimitLayer::new(max_concurrent_requests));
 
-        if let Some(auth_config) = auth_config {
+        let auth_state: Option<AuthState> = auth_config.map(|auth_config| {
             // Create and run the cache to track the used JWT tokens.
             let replay_suppression_cache = auth_config.replay_suppression_config.build_and_run();
-            self.router = self.router.route(
-                BLOB_PUT_ENDPOINT,
-                put(routes::put_blob)
-                    .route_layer(
-                        ServiceBuilder::new()
-                            .layer(axum::middleware::from_fn_with_state(
-                                (Arc::new(auth_config), Arc::new(replay_suppression_cache)),
-                                auth_layer,
-                            ))
-                            .layer(base_layers),
-                    )
-                    .options(routes::store_blob_options),
-            );
-        } else {
-            self.router = self.router.route(
-                BLOB_PUT_ENDPOINT,
-                put(routes::put_blob)
-                    .route_layer(base_layers)
-                    .options(routes::store_blob_options),
-            );
+            let quota_tracker = auth_config
+                .quota_config
+                .clone()
+                .map(|config| Arc::new(QuotaTracker::new(config)));
+            (
+                Arc::new(auth_config),
+                Arc::new(replay_suppression_cache),
+                quota_tracker,
+            )
+        });
+        let with_upload_layers = |method_router: MethodRouter<Arc<T>>| {
+            let method_router = method_router.route_layer(base_layers.clone());
+            if let Some(auth_state) = &auth_state {
+                method_router.route_layer(middleware::from_fn_with_state(
+                    auth_state.clone(),
+                    auth_layer,
+                ))
+            } else {
+                method_router
+            }
+        };
+
+        let receipt_signer = publisher_args
+            .receipt_signing_key
+            .as_deref()
+            .map(ReceiptSigner::load)
+            .transpose()?
+            .map(Arc::new);
+        self.router = self.router.route(
+            BLOB_PUT_ENDPOINT,
+            with_upload_layers(
+                put(routes::put_blob).with_state((self.client.clone(), receipt_signer)),
+            ),
+        );
+
+        if let Some(job_queue_dir) = &publisher_args.job_queue_dir {
+            let job_queue = JobQueue::open(
+                self.client.clone(),
+                job_queue_dir,
+                publisher_args.max_concurrent_jobs,
+                publisher_args.job_record_ttl,
+            )?;
+            self.router = self
+                .router
+                .route(
+                    JOB_PUT_ENDPOINT,
+                    with_upload_layers(put(routes::put_job).with_state(job_queue.clone())),
+                )
+                .route(JOB_GET_ENDPOINT, get(routes::get_job).with_state(job_queue));
         }
-        self
+        Ok(self)
     }
 }
 
-impl<T> ClientDaemon<T> {
-    fn with_allowed_headers(&mut self, allowed_headers: Vec<String>) {
-        self.allowed_headers = Arc::new(allowed_headers.into_iter().collect());
+impl ClientDaemon<Client<SuiReadClient>> {
+    /// Constructs a new [`ClientDaemon`] with unsigned publisher functionality.
+    pub fn new_unsigned_publisher(
+        client: Client<SuiReadClient>,
+        registry: &Registry,
+        args: &UnsignedPublisherArgs,
+    ) -> anyhow::Result<Self> {
+        Ok(
+            Self::new::<UnsignedPublisherApiDoc>(client, args.daemon_args.bind_address, registry)
+                .with_cors(&args.daemon_args.cors_config)?
+                .with_tls(&args.daemon_args.tls_config)
+                .with_unsigned_publisher(args),
+        )
+    }
+
+    /// Specifies that the daemon should expose the unsigned publisher interface, which encodes
+    /// and distributes blobs but leaves signing the transactions to the caller.
+    fn with_unsigned_publisher(mut self, args: &UnsignedPublisherArgs) -> Self {
+        tracing::debug!(
+            max_body_size = args.max_body_size(),
+            max_concurrent_requests = args.max_concurrent_requests,
+            max_pending_uploads = args.max_pending_uploads,
+            max_pending_uploads_size = args.max_pending_uploads_size(),
+            pending_upload_ttl = ?args.pending_upload_ttl,
+            "configuring the unsigned publisher endpoints",
+        );
+        let unsigned_publisher = Arc::new(UnsignedPublisher::new(
+            self.client.clone(),
+            args.max_pending_uploads,
+            args.max_pending_uploads_size(),
+            args.pending_upload_ttl,
+        ));
+        let layers = ServiceBuilder::new()
+            .layer(DefaultBodyLimit::max(args.max_body_size()))
+            .layer(HandleErrorLayer::new(handle_publisher_error))
+            .layer(LoadShedLayer::new())
+            .layer(ConcurrencyLimitLayer::new(args.max_concurrent_requests));
+
+        self.router = self
+            .router
+            .route(
+                UNSIGNED_BLOB_PUT_ENDPOINT,
+                put(routes::put_unsigned_blob)
+                    .with_state(unsigned_publisher.clone())
+                    .route_layer(layers.clone()),
+            )
+            .route(
+                UNSIGNED_BLOB_CERTIFY_ENDPOINT,
+                post(routes::post_unsigned_certification)
+                    .with_state(unsigned_publisher)
+                    .route_layer(layers),
+            );
+        self
     }
 }
 
+/// The state of the [`auth_layer`].
+pub(crate) type AuthState = (
+    Arc<AuthConfig>,
+    Arc<CacheHandle<String>>,
+    Option<Arc<QuotaTracker>>,
+);
+
 pub(crate) async fn auth_layer(
-    State((auth_config, token_cache)): State<(Arc<AuthConfig>, Arc<CacheHandle<String>>)>,
+    State((auth_config, token_cache, quota_tracker)): State<AuthState>,
     query: Query<PublisherQuery>,
-    TypedHeader(bearer_header): TypedHeader<Authorization<Bearer>>,
+    bearer_header: Result<TypedHeader<Authorization<Bearer>>, TypedHeaderRejection>,
     request: Request,
     next: Next,
 ) -> Response {
@@ -322,33 +647,64 @@ pub(crate) async fn auth_layer(
     // Walrus.
     tracing::debug!(query = ?query.0, "authenticating a request to store a blob");
 
-    if let Err(resp) = verify_jwt_claim(
+    let Ok(TypedHeader(bearer_header)) = bearer_header else {
+        return PublisherAuthError::MissingToken.to_response();
+    };
+    let epochs = query.epochs;
+    let body_size_hint = request.body().size_hint();
+    let claim = match verify_jwt_claim(
         query,
         bearer_header,
         &auth_config,
         token_cache.as_ref(),
-        request.body().size_hint(),
+        body_size_hint.clone(),
     )
     .await
     {
-        resp
-    } else {
-        next.run(request).await
+        Ok(claim) => claim,
+        Err(response) => return response,
+    };
+
+    let Some(quota_tracker) = quota_tracker else {
+        return next.run(request).await;
+    };
+    let Some(subject) = claim.sub else {
+        return PublisherAuthError::MissingSubject.to_response();
+    };
+    // The exact size is known if the request specifies its content length, which is then
+    // enforced when reading the body.
+    let reservation = match quota_tracker.reserve(&subject, epochs, body_size_hint.exact()) {
+        Ok(reservation) => reservation,
+        Err(error) => return error.to_response(),
+    };
+
+    let response = next.run(request).await;
+    if !response.status().is_success() {
+        if let Some(reservation) = reservation {
+            quota_tracker.release(reservation);
+        }
     }
+    response
+}
+
+/// Errors returned by the publisher's middleware before a request reaches its handler.
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = DAEMON_ERROR_DOMAIN)]
+enum PublisherServiceError {
+    /// The publisher is receiving more requests than it can process.
+    #[error("the publisher is receiving too many requests; please try again later")]
+    #[rest_api_error(reason = "PUBLISHER_OVERLOADED", status = ApiStatusCode::ResourceExhausted)]
+    Overloaded,
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] anyhow::Error),
 }
 
 async fn handle_publisher_error(error: BoxError) -> Response {
     if error.is::<Overloaded>() {
-        (
-            StatusCode::TOO_MANY_REQUESTS,
-            "the publisher is receiving too many requests; please try again later",
-        )
-            .into_response()
+        PublisherServiceError::Overloaded.into_response()
     } else {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "something went wrong while storing the blob",
-        )
-            .into_response()
+        PublisherServiceError::Internal(anyhow::anyhow!(error)).into_response()
     }
 }
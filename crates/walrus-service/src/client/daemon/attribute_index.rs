@@ -0,0 +1,541 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Index of the attributes of blob objects for aggregator deployments.
+//!
+//! The index is built from the events emitted by the Walrus contracts. Whenever a blob object is
+//! certified, its lifetime is extended, or its attributes change, its current attributes are read
+//! from Sui and recorded; blob objects are removed from the index when they are deleted or their
+//! storage expires.
+//!
+//! The events signaling attribute changes require an upgraded Walrus package. On networks where
+//! the deployed package does not define them yet, attributes are only refreshed when blob objects
+//! are certified or their lifetime is extended, and the index periodically checks whether the
+//! package has been upgraded.
+//!
+//! The index is stored in a RocksDB database in the index directory. Every event is applied to the
+//! database in a single write batch together with the cursor of the event, such that event
+//! processing resumes from the last processed event after a restart.
+
+use std::{
+    convert::Infallible,
+    ops::Bound::{Included, Unbounded},
+    path::Path,
+    pin::pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context;
+use futures::StreamExt as _;
+use rocksdb::Options;
+use serde::{Deserialize, Serialize};
+use sui_types::{base_types::ObjectID, event::EventID};
+use typed_store::{
+    rocks::{self, DBBatch, DBMap, MetricConf, ReadWriteOptions},
+    Map,
+    TypedStoreError,
+};
+use walrus_core::{BlobId, Epoch};
+use walrus_sui::{
+    client::{ReadClient, SuiClientError, SuiClientResult},
+    types::{
+        move_structs::{BlobAttribute, BlobWithAttribute},
+        BlobAttributeChanged,
+        BlobEvent,
+        ContractEvent,
+        EpochChangeEvent,
+    },
+};
+
+/// The column family mapping object IDs to the indexed blob objects.
+const BLOBS_CF: &str = "blobs";
+/// The column family mapping attributes and object IDs to nothing, to look up blob objects by
+/// attribute.
+const BY_ATTRIBUTE_CF: &str = "by_attribute";
+/// The column family mapping end epochs and object IDs to nothing, to find expired blob objects.
+const BY_END_EPOCH_CF: &str = "by_end_epoch";
+/// The column family holding the cursors of the event streams.
+const CURSORS_CF: &str = "cursors";
+/// The interval at which the full node is polled for new events.
+const EVENT_POLLING_INTERVAL: Duration = Duration::from_secs(1);
+/// The time to wait before resuming event processing after an error.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+/// The interval at which it is checked whether the Walrus package has been upgraded to a version
+/// that emits events for attribute changes.
+const PACKAGE_UPGRADE_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// A blob object recorded in the attribute index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexedBlob {
+    /// The ID of the blob object.
+    pub object_id: ObjectID,
+    /// The ID of the blob.
+    pub blob_id: BlobId,
+    /// The epoch at which the storage of the blob object expires (exclusive).
+    pub end_epoch: Epoch,
+    /// The attributes of the blob object.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// The event streams followed by the index, each with its own cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum EventSource {
+    /// The events of the `events` module of the Walrus contracts.
+    Contract,
+    /// The events signaling changes to the attributes of blob objects.
+    BlobAttributes,
+}
+
+/// An index from the attributes of blob objects to the blob objects and their blob IDs.
+#[derive(Debug)]
+pub(crate) struct AttributeIndex {
+    blobs: DBMap<ObjectID, IndexedBlob>,
+    by_attribute: DBMap<(String, String, ObjectID), ()>,
+    by_end_epoch: DBMap<(Epoch, ObjectID), ()>,
+    cursors: DBMap<EventSource, EventID>,
+    /// Serializes the updates of the index, which read the previous entry of a blob object before
+    /// replacing it.
+    write_lock: Mutex<()>,
+}
+
+impl AttributeIndex {
+    /// Opens the index in the provided directory, creating the database if it does not exist.
+    pub(crate) fn open(directory: &Path) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(directory).with_context(|| {
+            format!(
+                "unable to create attribute index directory {}",
+                directory.display()
+            )
+        })?;
+
+        let mut db_opts = Options::default();
+        db_opts.create_missing_column_families(true);
+        db_opts.create_if_missing(true);
+        let database = rocks::open_cf_opts(
+            directory,
+            Some(db_opts),
+            MetricConf::default(),
+            &[
+                (BLOBS_CF, Options::default()),
+                (BY_ATTRIBUTE_CF, Options::default()),
+                (BY_END_EPOCH_CF, Options::default()),
+                (CURSORS_CF, Options::default()),
+            ],
+        )
+        .context("unable to open the attribute index database")?;
+        let options = ReadWriteOptions::default();
+        let this = Self {
+            blobs: DBMap::reopen(&database, Some(BLOBS_CF), &options, false)?,
+            by_attribute: DBMap::reopen(&database, Some(BY_ATTRIBUTE_CF), &options, false)?,
+            by_end_epoch: DBMap::reopen(&database, Some(BY_END_EPOCH_CF), &options, false)?,
+            cursors: DBMap::reopen(&database, Some(CURSORS_CF), &options, false)?,
+            write_lock: Mutex::new(()),
+        };
+        tracing::info!(
+            directory = %directory.display(),
+            contract_cursor = ?this.cursors.get(&EventSource::Contract)?,
+            attribute_cursor = ?this.cursors.get(&EventSource::BlobAttributes)?,
+            "opened attribute index"
+        );
+
+        Ok(this)
+    }
+
+    /// Returns up to `limit` blob objects that have the attribute `key` set to `value`, ordered
+    /// by their object ID.
+    pub(crate) fn query(
+        &self,
+        key: &str,
+        value: &str,
+        limit: usize,
+    ) -> Result<Vec<IndexedBlob>, TypedStoreError> {
+        let mut blobs = Vec::new();
+        for entry in self
+            .by_attribute
+            .safe_range_iter((
+                Included((key.to_owned(), value.to_owned(), ObjectID::ZERO)),
+                Unbounded,
+            ))
+            .take(limit)
+        {
+            let ((entry_key, entry_value, object_id), ()) = entry?;
+            if entry_key != key || entry_value != value {
+                break;
+            }
+            if let Some(blob) = self.blobs.get(&object_id)? {
+                blobs.push(blob);
+            }
+        }
+        Ok(blobs)
+    }
+
+    /// Keeps the index up to date with the events emitted by the Walrus contracts.
+    ///
+    /// Never returns; errors while processing events are logged and processing is resumed from the
+    /// last processed event.
+    pub(crate) async fn run(self: Arc<Self>, read_client: impl ReadClient) {
+        let contract_events = async {
+            loop {
+                if let Err(error) = self.process_contract_events(&read_client).await {
+                    tracing::warn!(?error, "error while applying contract events to the index");
+                }
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        };
+        let attribute_events = async {
+            let mut warned_about_package = false;
+            loop {
+                let Err(error) = self.process_attribute_events(&read_client).await;
+                if !is_struct_not_deployed(&error) {
+                    tracing::warn!(
+                        ?error,
+                        "error while applying attribute changes to the index"
+                    );
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+
+                if !warned_about_package {
+                    tracing::warn!(
+                        "the deployed Walrus package does not emit events for attribute changes; \
+                        attributes are only refreshed when blob objects are certified or extended \
+                        until the package is upgraded"
+                    );
+                    warned_about_package = true;
+                }
+                tokio::time::sleep(PACKAGE_UPGRADE_CHECK_INTERVAL).await;
+                if let Err(error) = read_client.refresh_package_id().await {
+                    tracing::warn!(?error, "unable to refresh the Walrus package ID");
+                }
+            }
+        };
+        tokio::join!(contract_events, attribute_events);
+    }
+
+    async fn process_contract_events(&self, read_client: &impl ReadClient) -> anyhow::Result<()> {
+        let cursor = self.cursors.get(&EventSource::Contract)?;
+        let mut events = pin!(
+            read_client
+                .event_stream(EVENT_POLLING_INTERVAL, cursor)
+                .await?
+        );
+        while let Some(event) = events.next().await {
+            self.handle_contract_event(read_client, event).await?;
+        }
+        anyhow::bail!("the event stream ended unexpectedly");
+    }
+
+    async fn process_attribute_events(
+        &self,
+        read_client: &impl ReadClient,
+    ) -> anyhow::Result<Infallible> {
+        let cursor = self.cursors.get(&EventSource::BlobAttributes)?;
+        let mut events = pin!(
+            read_client
+                .blob_attribute_event_stream(EVENT_POLLING_INTERVAL, cursor)
+                .await?
+        );
+        while let Some(event) = events.next().await {
+            self.handle_attribute_event(read_client, event).await?;
+        }
+        anyhow::bail!("the attribute event stream ended unexpectedly");
+    }
+
+    async fn handle_contract_event(
+        &self,
+        read_client: &impl ReadClient,
+        event: ContractEvent,
+    ) -> anyhow::Result<()> {
+        let cursor = (EventSource::Contract, event.event_id());
+        match event {
+            ContractEvent::BlobEvent(BlobEvent::Certified(certified)) => {
+                let attributes =
+                    attribute_pairs(read_client.get_blob_attribute(&certified.object_id).await?);
+                let blob = (!attributes.is_empty()).then(|| IndexedBlob {
+                    object_id: certified.object_id,
+                    blob_id: certified.blob_id,
+                    end_epoch: certified.end_epoch,
+                    attributes,
+                });
+                self.update(certified.object_id, blob, cursor)?;
+            }
+            ContractEvent::BlobEvent(BlobEvent::Deleted(deleted)) => {
+                self.update(deleted.object_id, None, cursor)?
+            }
+            ContractEvent::EpochChangeEvent(EpochChangeEvent::EpochChangeStart(start)) => {
+                self.remove_expired(start.epoch, cursor)?
+            }
+            _ => self.cursors.insert(&cursor.0, &cursor.1)?,
+        }
+        Ok(())
+    }
+
+    /// Re-reads the blob object whose attributes changed and updates its entry in the index.
+    async fn handle_attribute_event(
+        &self,
+        read_client: &impl ReadClient,
+        event: BlobAttributeChanged,
+    ) -> anyhow::Result<()> {
+        let lookup = read_client.get_blob_by_object_id(&event.object_id).await;
+        self.apply_attribute_change(event, lookup)
+    }
+
+    /// Updates the entry of the blob object whose attributes changed with the result of reading
+    /// the blob object.
+    ///
+    /// Only certified blob objects are indexed. Blob objects that no longer exist, for example
+    /// because they were deleted or wrapped since the event was emitted, are removed from the
+    /// index. Any other error is returned without changing the index or the cursor, such that the
+    /// event is processed again.
+    fn apply_attribute_change(
+        &self,
+        event: BlobAttributeChanged,
+        lookup: SuiClientResult<BlobWithAttribute>,
+    ) -> anyhow::Result<()> {
+        let cursor = (EventSource::BlobAttributes, event.event_id);
+        let blob = match lookup {
+            Ok(blob_with_attribute) if blob_with_attribute.blob.certified_epoch.is_some() => {
+                let attributes = attribute_pairs(blob_with_attribute.attribute);
+                (!attributes.is_empty()).then(|| IndexedBlob {
+                    object_id: event.object_id,
+                    blob_id: blob_with_attribute.blob.blob_id,
+                    end_epoch: blob_with_attribute.blob.storage.end_epoch,
+                    attributes,
+                })
+            }
+            Ok(_) => None,
+            Err(error) if error.is_object_not_found() => {
+                tracing::debug!(
+                    object_id = %event.object_id,
+                    "blob object with changed attributes no longer exists"
+                );
+                None
+            }
+            Err(error) => {
+                return Err(error).with_context(|| {
+                    format!(
+                        "unable to read blob object {} with changed attributes",
+                        event.object_id
+                    )
+                });
+            }
+        };
+        self.update(event.object_id, blob, cursor)?;
+        Ok(())
+    }
+
+    /// Replaces the entry of the blob object with `blob`, or removes it if `blob` is `None`, and
+    /// advances the cursor of the event source, all in a single write batch.
+    fn update(
+        &self,
+        object_id: ObjectID,
+        blob: Option<IndexedBlob>,
+        (source, event_id): (EventSource, EventID),
+    ) -> Result<(), TypedStoreError> {
+        let _guard = self
+            .write_lock
+            .lock()
+            .expect("mutex should not be poisoned");
+        let mut batch = self.blobs.batch();
+        if let Some(previous) = self.blobs.get(&object_id)? {
+            self.delete_entry_batch(&mut batch, previous)?;
+        }
+        if let Some(blob) = blob {
+            batch.insert_batch(
+                &self.by_attribute,
+                blob.attributes
+                    .iter()
+                    .map(|(key, value)| ((key.clone(), value.clone(), object_id), ())),
+            )?;
+            batch.insert_batch(&self.by_end_epoch, [((blob.end_epoch, object_id), ())])?;
+            batch.insert_batch(&self.blobs, [(object_id, blob)])?;
+        }
+        batch.insert_batch(&self.cursors, [(source, event_id)])?;
+        batch.write()
+    }
+
+    /// Removes the blob objects whose storage has expired by the start of `epoch`, and advances the
+    /// cursor of the event source, all in a single write batch.
+    fn remove_expired(
+        &self,
+        epoch: Epoch,
+        (source, event_id): (EventSource, EventID),
+    ) -> Result<(), TypedStoreError> {
+        let _guard = self
+            .write_lock
+            .lock()
+            .expect("mutex should not be poisoned");
+        let mut batch = self.blobs.batch();
+        for entry in self.by_end_epoch.safe_iter() {
+            let ((end_epoch, object_id), ()) = entry?;
+            if end_epoch > epoch {
+                break;
+            }
+            match self.blobs.get(&object_id)? {
+                Some(blob) => self.delete_entry_batch(&mut batch, blob)?,
+                None => batch.delete_batch(&self.by_end_epoch, [(end_epoch, object_id)])?,
+            }
+        }
+        batch.insert_batch(&self.cursors, [(source, event_id)])?;
+        batch.write()
+    }
+
+    /// Adds the deletion of all entries of the indexed blob object to the batch.
+    fn delete_entry_batch(
+        &self,
+        batch: &mut DBBatch,
+        blob: IndexedBlob,
+    ) -> Result<(), TypedStoreError> {
+        let object_id = blob.object_id;
+        batch.delete_batch(
+            &self.by_attribute,
+            blob.attributes
+                .into_iter()
+                .map(|(key, value)| (key, value, object_id)),
+        )?;
+        batch.delete_batch(&self.by_end_epoch, [(blob.end_epoch, object_id)])?;
+        batch.delete_batch(&self.blobs, [object_id])?;
+        Ok(())
+    }
+}
+
+/// Returns true if the error signals that the deployed Walrus package does not define a struct.
+fn is_struct_not_deployed(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<SuiClientError>(),
+        Some(SuiClientError::StructNotDeployed(_))
+    )
+}
+
+/// Returns the key-value pairs of the attribute of a blob object, if any.
+fn attribute_pairs(attribute: Option<BlobAttribute>) -> Vec<(String, String)> {
+    attribute
+        .map(|attribute| {
+            attribute
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use walrus_core::test_utils::random_blob_id;
+    use walrus_sui::test_utils::fixed_event_id_for_testing;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    fn blob(end_epoch: Epoch, attributes: &[(&str, &str)]) -> IndexedBlob {
+        IndexedBlob {
+            object_id: ObjectID::random(),
+            blob_id: random_blob_id(),
+            end_epoch,
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    fn insert(index: &AttributeIndex, blob: &IndexedBlob, event_seq: u64) -> TestResult {
+        let cursor = (EventSource::Contract, fixed_event_id_for_testing(event_seq));
+        index.update(blob.object_id, Some(blob.clone()), cursor)?;
+        Ok(())
+    }
+
+    #[test]
+    fn queries_by_attribute_and_persists_updates() -> TestResult {
+        let directory = TempDir::new()?;
+        let index = AttributeIndex::open(directory.path())?;
+        let image = blob(5, &[("content-type", "image/png")]);
+        let replaced = blob(5, &[("content-type", "image/png")]);
+        let expiring = blob(2, &[("content-type", "image/png"), ("app", "demo")]);
+        let removed = blob(5, &[("app", "demo")]);
+        insert(&index, &image, 0)?;
+        insert(&index, &replaced, 1)?;
+        insert(&index, &expiring, 2)?;
+        insert(&index, &removed, 3)?;
+        // Changing the attributes of a blob object replaces its entries.
+        let attribute_cursor = fixed_event_id_for_testing(0);
+        index.update(
+            replaced.object_id,
+            Some(IndexedBlob {
+                attributes: vec![("content-type".to_owned(), "text/plain".to_owned())],
+                ..replaced.clone()
+            }),
+            (EventSource::BlobAttributes, attribute_cursor),
+        )?;
+        index.update(
+            removed.object_id,
+            None,
+            (EventSource::Contract, fixed_event_id_for_testing(4)),
+        )?;
+        index.remove_expired(2, (EventSource::Contract, fixed_event_id_for_testing(5)))?;
+
+        assert_eq!(
+            index.query("content-type", "image/png", 10)?,
+            vec![image.clone()]
+        );
+        assert_eq!(index.query("content-type", "image/png", 0)?, vec![]);
+        assert_eq!(index.query("app", "demo", 10)?, vec![]);
+        assert_eq!(index.by_end_epoch.safe_iter().count(), 2);
+
+        drop(index);
+        let restored = AttributeIndex::open(directory.path())?;
+        assert_eq!(
+            restored.query("content-type", "image/png", 10)?,
+            vec![image]
+        );
+        assert_eq!(restored.query("content-type", "text/plain", 10)?.len(), 1);
+        assert_eq!(
+            restored.cursors.get(&EventSource::Contract)?,
+            Some(fixed_event_id_for_testing(5))
+        );
+        assert_eq!(
+            restored.cursors.get(&EventSource::BlobAttributes)?,
+            Some(attribute_cursor)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_entries_and_cursor_if_reading_blob_object_fails() -> TestResult {
+        let directory = TempDir::new()?;
+        let index = AttributeIndex::open(directory.path())?;
+        let image = blob(5, &[("content-type", "image/png")]);
+        insert(&index, &image, 0)?;
+        let event = |event_seq| BlobAttributeChanged {
+            blob_id: image.blob_id,
+            object_id: image.object_id,
+            event_id: fixed_event_id_for_testing(event_seq),
+        };
+
+        let transient_error = SuiClientError::Internal(anyhow::anyhow!("request timed out"));
+        assert!(index
+            .apply_attribute_change(event(1), Err(transient_error))
+            .is_err());
+        assert_eq!(
+            index.query("content-type", "image/png", 10)?,
+            vec![image.clone()]
+        );
+        assert_eq!(index.cursors.get(&EventSource::BlobAttributes)?, None);
+
+        index.apply_attribute_change(
+            event(2),
+            Err(SuiClientError::ObjectNotFound(image.object_id)),
+        )?;
+        assert_eq!(index.query("content-type", "image/png", 10)?, vec![]);
+        assert_eq!(
+            index.cursors.get(&EventSource::BlobAttributes)?,
+            Some(fixed_event_id_for_testing(2))
+        );
+
+        Ok(())
+    }
+}
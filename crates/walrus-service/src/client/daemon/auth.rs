@@ -37,6 +37,14 @@ pub struct Claim {
     /// [rfc7519s4.1.7]: https://datatracker.ietf.org/doc/html/rfc7519#section-4.1.7
     pub jti: String,
 
+    /// The subject of the token, which identifies the client to which quotas are applied.
+    ///
+    /// See [RFC 7519][rfc7519s4.1.2].
+    ///
+    /// [rfc7519s4.1.2]: https://datatracker.ietf.org/doc/html/rfc7519#section-4.1.2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+
     /// The owner address of the sui blob object.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub send_object_to: Option<SuiAddress>,
@@ -265,7 +273,7 @@ pub async fn verify_jwt_claim(
     auth_config: &AuthConfig,
     token_cache: &CacheHandle<String>,
     body_size_hint: http_body::SizeHint,
-) -> Result<(), Response<Body>> {
+) -> Result<Claim, Response<Body>> {
     let mut validation = if auth_config.decoding_key.is_some() {
         auth_config
             .algorithm
@@ -317,7 +325,7 @@ pub async fn verify_jwt_claim(
                     });
                 Err(error.to_response())
             } else {
-                Ok(())
+                Ok(claim)
             }
         }
         Err(code) => Err(code.to_response()),
@@ -378,6 +386,55 @@ pub enum PublisherAuthError {
     #[rest_api_error(reason = "INVALID_TIMESTAMP", status = ApiStatusCode::FailedPrecondition)]
     InvalidTimestamp,
 
+    /// The request does not contain a valid bearer token.
+    #[error("the request does not contain a valid bearer token")]
+    #[rest_api_error(reason = "MISSING_TOKEN", status = ApiStatusCode::Unauthenticated)]
+    MissingToken,
+
+    /// The JWT token does not specify a subject, which is required to apply quotas.
+    #[error("the JWT token does not specify a subject (`sub` claim)")]
+    #[rest_api_error(reason = "MISSING_SUBJECT", status = ApiStatusCode::Unauthenticated)]
+    MissingSubject,
+
+    /// The request does not specify its content length, which is required to apply size quotas.
+    #[error("the request must specify its content length")]
+    #[rest_api_error(
+        reason = "CONTENT_LENGTH_REQUIRED", status = ApiStatusCode::FailedPrecondition
+    )]
+    ContentLengthRequired,
+
+    /// The blob exceeds the maximum blob size of the subject.
+    #[error("the blob size of {size} B exceeds the maximum blob size of {max_blob_size} B")]
+    #[rest_api_error(
+        reason = "BLOB_SIZE_QUOTA_EXCEEDED", status = ApiStatusCode::ResourceExhausted
+    )]
+    BlobSizeQuotaExceeded {
+        /// The size of the blob.
+        size: u64,
+        /// The maximum blob size of the subject.
+        max_blob_size: u64,
+    },
+
+    /// The requested number of epochs exceeds the maximum of the subject.
+    #[error("storing for {epochs} epochs exceeds the maximum of {max_epochs} epochs")]
+    #[rest_api_error(reason = "EPOCHS_QUOTA_EXCEEDED", status = ApiStatusCode::ResourceExhausted)]
+    EpochsQuotaExceeded {
+        /// The requested number of epochs.
+        epochs: EpochCount,
+        /// The maximum number of epochs of the subject.
+        max_epochs: EpochCount,
+    },
+
+    /// The blob exceeds the remaining daily quota of the subject.
+    #[error("the blob size of {size} B exceeds the remaining daily quota of {remaining} B")]
+    #[rest_api_error(reason = "DAILY_QUOTA_EXCEEDED", status = ApiStatusCode::ResourceExhausted)]
+    DailyQuotaExceeded {
+        /// The size of the blob.
+        size: u64,
+        /// The number of bytes the subject can still store today.
+        remaining: u64,
+    },
+
     /// Other errors that are not covered by the other variants.
     #[error("an internal error occurred")]
     #[rest_api_error(delegate)]
@@ -402,7 +459,11 @@ mod tests {
     use super::*;
     use crate::client::{
         config::AuthConfig,
-        daemon::{auth_layer, cache::CacheConfig},
+        daemon::{
+            auth_layer,
+            cache::CacheConfig,
+            quota::{QuotaConfig, QuotaTracker, SubjectQuota},
+        },
     };
 
     // Fixtures and helpers for tests.
@@ -447,16 +508,23 @@ mod tests {
         let encode_key = EncodingKey::from_secret(secret.as_bytes());
         let token = encode(&Header::default(), &claim, &encode_key).unwrap();
 
+        let router = router_for_config(auth_config, None);
+        (router, token, encode_key)
+    }
+
+    fn router_for_config(auth_config: AuthConfig, quota_tracker: Option<QuotaTracker>) -> Router {
         let token_cache = CacheConfig::default().build_and_run();
 
         let publisher_layers = ServiceBuilder::new().layer(axum::middleware::from_fn_with_state(
-            (Arc::new(auth_config), Arc::new(token_cache)),
+            (
+                Arc::new(auth_config),
+                Arc::new(token_cache),
+                quota_tracker.map(Arc::new),
+            ),
             auth_layer,
         ));
 
-        let router =
-            Router::new().route("/v1/blobs", get(|| async {}).route_layer(publisher_layers));
-        (router, token, encode_key)
+        Router::new().route("/v1/blobs", get(|| async {}).route_layer(publisher_layers))
     }
 
     /// A helper to build requests
@@ -522,7 +590,7 @@ mod tests {
             (
                 // No token supplied.
                 RequestHeadersAndData::new("/v1/blobs", None, None),
-                StatusCode::BAD_REQUEST,
+                StatusCode::UNAUTHORIZED,
             ),
             (
                 RequestHeadersAndData::new(
@@ -531,7 +599,7 @@ mod tests {
                     Some(("authorization".to_owned(), token.clone())),
                     None,
                 ),
-                StatusCode::BAD_REQUEST,
+                StatusCode::UNAUTHORIZED,
             ),
             (
                 RequestHeadersAndData::new("/v1/blobs", correct_auth_header(token), None),
@@ -937,20 +1005,13 @@ mod tests {
         )
         .unwrap();
 
-        let token_cache = CacheConfig::default().build_and_run();
-        let publisher_layers = ServiceBuilder::new().layer(axum::middleware::from_fn_with_state(
-            (Arc::new(auth_config.clone()), Arc::new(token_cache)),
-            auth_layer,
-        ));
-
-        let router =
-            Router::new().route("/v1/blobs", get(|| async {}).route_layer(publisher_layers));
+        let router = router_for_config(auth_config, None);
 
         let requests = vec![
             (
                 // No token supplied.
                 RequestHeadersAndData::new("/v1/blobs", None, None),
-                StatusCode::BAD_REQUEST,
+                StatusCode::UNAUTHORIZED,
             ),
             (
                 RequestHeadersAndData::new(
@@ -959,7 +1020,7 @@ mod tests {
                     Some(("authorization".to_owned(), token.clone())),
                     None,
                 ),
-                StatusCode::BAD_REQUEST,
+                StatusCode::UNAUTHORIZED,
             ),
             (
                 RequestHeadersAndData::new("/v1/blobs", correct_auth_header(token), None),
@@ -969,4 +1030,88 @@ mod tests {
 
         execute_requests(&router, requests).await;
     }
+
+    #[tokio::test]
+    async fn enforces_subject_quotas() {
+        let secret = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
+        let auth_config = auth_config_for_tests(Some(&secret), None, 0, false);
+        let quota_tracker = QuotaTracker::new(QuotaConfig {
+            default_quota: SubjectQuota {
+                max_bytes_per_day: Some(15),
+                max_blob_size: Some(10),
+                max_epochs: Some(2),
+            },
+            ..Default::default()
+        });
+        let router = router_for_config(auth_config, Some(quota_tracker));
+
+        let encode_key = EncodingKey::from_secret(secret.as_bytes());
+        let token = |jti: &str, sub: Option<&str>| {
+            let claim = Claim {
+                jti: jti.to_string(),
+                sub: sub.map(str::to_string),
+                exp: FAR_EXP,
+                ..Default::default()
+            };
+            encode(&Header::default(), &claim, &encode_key).unwrap()
+        };
+
+        let requests = vec![
+            (
+                // The token has no subject.
+                RequestHeadersAndData::new(
+                    "/v1/blobs",
+                    correct_auth_header(token("no-subject", None)),
+                    Some(vec![42; 10].into()),
+                ),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                // Too many epochs.
+                RequestHeadersAndData::new(
+                    "/v1/blobs?epochs=3",
+                    correct_auth_header(token("epochs", Some("alice"))),
+                    Some(vec![42; 10].into()),
+                ),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                // The blob is too large.
+                RequestHeadersAndData::new(
+                    "/v1/blobs",
+                    correct_auth_header(token("size", Some("alice"))),
+                    Some(vec![42; 11].into()),
+                ),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                RequestHeadersAndData::new(
+                    "/v1/blobs",
+                    correct_auth_header(token("first", Some("alice"))),
+                    Some(vec![42; 10].into()),
+                ),
+                StatusCode::OK,
+            ),
+            (
+                // The daily quota is exhausted.
+                RequestHeadersAndData::new(
+                    "/v1/blobs",
+                    correct_auth_header(token("second", Some("alice"))),
+                    Some(vec![42; 10].into()),
+                ),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                // Other subjects have their own quota.
+                RequestHeadersAndData::new(
+                    "/v1/blobs",
+                    correct_auth_header(token("third", Some("bob"))),
+                    Some(vec![42; 10].into()),
+                ),
+                StatusCode::OK,
+            ),
+        ];
+
+        execute_requests(&router, requests).await;
+    }
 }
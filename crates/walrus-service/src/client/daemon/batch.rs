@@ -0,0 +1,131 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encoding of the responses to batched blob reads.
+//!
+//! The aggregator returns the blobs requested in a batch as the parts of a `multipart/mixed`
+//! response (RFC 2046), in the order in which they were requested. Each part carries the ID of the
+//! blob in the `X-Walrus-Blob-Id` header. Blobs that cannot be read do not fail the whole batch;
+//! their part instead contains the JSON error status, and its `X-Walrus-Status` header contains the
+//! HTTP status code that reading the blob individually would have returned.
+
+use axum::{body::Bytes, http::HeaderName};
+use walrus_core::BlobId;
+
+/// The header containing the ID of the blob in each part of a batch response.
+pub(super) const BLOB_ID_HEADER: HeaderName = HeaderName::from_static("x-walrus-blob-id");
+/// The header containing the HTTP status code of reading the blob in each part of a batch response.
+pub(super) const STATUS_HEADER: HeaderName = HeaderName::from_static("x-walrus-status");
+
+/// The limits on batched blob reads.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BatchGetLimits {
+    /// The maximum number of blobs that can be requested in a single batch.
+    pub(super) max_blobs: usize,
+    /// The maximum number of blobs of a batch that are read concurrently.
+    pub(super) max_concurrent_reads: usize,
+    /// The maximum total size, in bytes, of the blobs requested in a single batch.
+    pub(super) max_total_size: u64,
+}
+
+/// Writes the parts of a `multipart/mixed` body delimited by a random boundary.
+#[derive(Debug, Clone)]
+pub(super) struct MultipartEncoder {
+    boundary: String,
+}
+
+impl MultipartEncoder {
+    /// Creates a new encoder with a random boundary.
+    pub(super) fn new() -> Self {
+        Self {
+            boundary: format!("walrus-{:032x}", rand::random::<u128>()),
+        }
+    }
+
+    /// Returns the value of the `Content-Type` header of the multipart body.
+    pub(super) fn content_type(&self) -> String {
+        format!("multipart/mixed; boundary={}", self.boundary)
+    }
+
+    /// Encodes a part containing the blob with the given ID.
+    pub(super) fn blob_part(&self, blob_id: &BlobId, blob: &[u8]) -> Bytes {
+        self.encode_part(
+            &[
+                ("content-type", "application/octet-stream"),
+                (BLOB_ID_HEADER.as_str(), &blob_id.to_string()),
+            ],
+            blob,
+        )
+    }
+
+    /// Encodes a part containing the JSON error status of reading the blob with the given ID.
+    pub(super) fn error_part(&self, blob_id: &BlobId, http_status: u16, status: &[u8]) -> Bytes {
+        self.encode_part(
+            &[
+                ("content-type", "application/json"),
+                (BLOB_ID_HEADER.as_str(), &blob_id.to_string()),
+                (STATUS_HEADER.as_str(), &http_status.to_string()),
+            ],
+            status,
+        )
+    }
+
+    /// Encodes the delimiter that terminates the multipart body.
+    pub(super) fn closing_delimiter(&self) -> Bytes {
+        format!("--{}--\r\n", self.boundary).into()
+    }
+
+    fn encode_part(&self, headers: &[(&str, &str)], body: &[u8]) -> Bytes {
+        let mut part = format!("--{}\r\n", self.boundary);
+        for (name, value) in headers {
+            part.push_str(&format!("{name}: {value}\r\n"));
+        }
+        part.push_str("\r\n");
+
+        let mut part = part.into_bytes();
+        part.extend_from_slice(body);
+        part.extend_from_slice(b"\r\n");
+        part.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_parts_between_boundaries() {
+        let encoder = MultipartEncoder::new();
+        let blob_id = BlobId([1; 32]);
+        let boundary = encoder
+            .content_type()
+            .strip_prefix("multipart/mixed; boundary=")
+            .expect("the content type specifies the boundary")
+            .to_owned();
+
+        let body: Vec<u8> = [
+            encoder.blob_part(&blob_id, b"data"),
+            encoder.error_part(&blob_id, 404, b"{}"),
+            encoder.closing_delimiter(),
+        ]
+        .concat();
+
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            format!(
+                "--{boundary}\r\n\
+                content-type: application/octet-stream\r\n\
+                x-walrus-blob-id: {blob_id}\r\n\
+                \r\n\
+                data\r\n\
+                --{boundary}\r\n\
+                content-type: application/json\r\n\
+                x-walrus-blob-id: {blob_id}\r\n\
+                x-walrus-status: 404\r\n\
+                \r\n\
+                {{}}\r\n\
+                --{boundary}--\r\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,684 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistent cache of reconstructed blobs for aggregator deployments.
+//!
+//! Blobs are stored as individual files in the cache directory, each prefixed with the SHA-256
+//! digest of its contents. The digest is re-verified whenever a blob is read from the cache, and
+//! corrupted entries are removed and treated as a cache miss.
+//!
+//! The total size of the cached blobs is bounded; when inserting a blob would exceed the bound,
+//! the least recently used blobs are evicted until the new blob fits. The recency of blobs found
+//! on disk at startup is approximated by their modification time.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use anyhow::Context;
+use bytes::Bytes;
+use futures::{
+    stream::{self, BoxStream},
+    Stream,
+    StreamExt as _,
+};
+use sha2::{Digest, Sha256};
+use sui_types::base_types::ObjectID;
+use walrus_core::{BlobId, EncodingType, EpochCount};
+use walrus_sui::{
+    client::{BlobPersistence, PostStoreAction},
+    types::move_structs::BlobWithAttribute,
+};
+
+use super::{BlobStream, WalrusReadClient, WalrusWriteClient};
+use crate::client::{
+    responses::{BlobCertificationStatus, BlobReadInfo, BlobStoreResult},
+    Blocklist,
+    ClientResult,
+    MetadataReplication,
+    StoreWhen,
+};
+
+/// The extension of the files holding cached blobs.
+const BLOB_FILE_EXTENSION: &str = "blob";
+/// The extension of the files to which blobs are written before being moved into place.
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+/// The length of the digest stored at the start of each cached blob file.
+const DIGEST_LENGTH: usize = 32;
+
+/// An on-disk cache of reconstructed blobs with least-recently-used eviction by size.
+#[derive(Debug)]
+pub(crate) struct BlobCache {
+    directory: PathBuf,
+    max_size: u64,
+    index: Mutex<CacheIndex>,
+}
+
+impl BlobCache {
+    /// Opens the cache in the provided directory, creating the directory if it does not exist.
+    ///
+    /// Blobs already present in the directory are added to the cache, and the least recently
+    /// modified blobs are evicted if they exceed `max_size` bytes.
+    pub(crate) fn open(directory: &Path, max_size: u64) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(directory).with_context(|| {
+            format!(
+                "unable to create blob cache directory {}",
+                directory.display()
+            )
+        })?;
+
+        let mut existing = vec![];
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if has_extension(&path, TEMPORARY_FILE_EXTENSION) {
+                // Left over from an interrupted write.
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            let Some(blob_id) = blob_id_from_path(&path) else {
+                continue;
+            };
+            let metadata = std::fs::metadata(&path)?;
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            existing.push((modified, blob_id, metadata.len()));
+        }
+        existing.sort();
+
+        let cache = Self {
+            directory: directory.to_owned(),
+            max_size,
+            index: Mutex::new(CacheIndex::default()),
+        };
+        let evicted = {
+            let mut index = cache.index.lock().expect("mutex should not be poisoned");
+            for (_, blob_id, size) in existing {
+                index.insert(blob_id, size);
+            }
+            index.evict_until(max_size, 0)
+        };
+        cache.remove_files(&evicted);
+
+        tracing::info!(
+            directory = %directory.display(),
+            max_size,
+            n_blobs = cache.len(),
+            "opened blob cache"
+        );
+        Ok(cache)
+    }
+
+    /// Returns the number of blobs in the cache.
+    pub(crate) fn len(&self) -> usize {
+        self.index
+            .lock()
+            .expect("mutex should not be poisoned")
+            .entries
+            .len()
+    }
+
+    /// Returns the total number of bytes occupied by the cached blob files.
+    pub(crate) fn total_size(&self) -> u64 {
+        self.index
+            .lock()
+            .expect("mutex should not be poisoned")
+            .total_size
+    }
+
+    /// Returns the blob from the cache, if present and intact.
+    pub(crate) async fn get(&self, blob_id: &BlobId) -> Option<Vec<u8>> {
+        if !self
+            .index
+            .lock()
+            .expect("mutex should not be poisoned")
+            .touch(blob_id)
+        {
+            return None;
+        }
+
+        let path = self.blob_path(blob_id);
+        let contents = match tokio::fs::read(&path).await {
+            Ok(contents) => contents,
+            Err(error) => {
+                tracing::warn!(%blob_id, ?error, "unable to read blob from the cache");
+                self.remove(blob_id);
+                return None;
+            }
+        };
+
+        match verify_contents(contents) {
+            Some(blob) => Some(blob),
+            None => {
+                tracing::warn!(%blob_id, "cached blob failed verification, removing it");
+                self.remove(blob_id);
+                None
+            }
+        }
+    }
+
+    /// Returns true if a blob of the given size fits into the cache.
+    pub(crate) fn can_cache(&self, blob_size: u64) -> bool {
+        DIGEST_LENGTH as u64 + blob_size <= self.max_size
+    }
+
+    /// Inserts the blob into the cache, evicting the least recently used blobs as necessary.
+    ///
+    /// Blobs larger than the maximum size of the cache are not cached.
+    pub(crate) async fn insert(&self, blob_id: &BlobId, blob: &[u8]) -> anyhow::Result<()> {
+        let file_size = (DIGEST_LENGTH + blob.len()) as u64;
+        if !self.can_cache(blob.len() as u64) {
+            tracing::debug!(%blob_id, file_size, "blob is too large to be cached");
+            return Ok(());
+        }
+
+        // Use a unique temporary file, such that concurrent insertions of the same blob do not
+        // interfere with each other.
+        let temporary_path = self.directory.join(format!(
+            "{blob_id}.{}.{TEMPORARY_FILE_EXTENSION}",
+            rand::random::<u64>()
+        ));
+        let mut contents = Vec::with_capacity(DIGEST_LENGTH + blob.len());
+        contents.extend_from_slice(&Sha256::digest(blob));
+        contents.extend_from_slice(blob);
+        tokio::fs::write(&temporary_path, contents).await?;
+
+        if let Err(error) = tokio::fs::rename(&temporary_path, self.blob_path(blob_id)).await {
+            let _ = tokio::fs::remove_file(&temporary_path).await;
+            return Err(error.into());
+        }
+
+        let evicted = {
+            let mut index = self.index.lock().expect("mutex should not be poisoned");
+            // Remove any previous entry first, such that the blob itself is never evicted.
+            index.remove(blob_id);
+            let evicted = index.evict_until(self.max_size, file_size);
+            index.insert(*blob_id, file_size);
+            evicted
+        };
+        self.remove_files(&evicted);
+
+        tracing::debug!(%blob_id, file_size, n_evicted = evicted.len(), "cached blob");
+        Ok(())
+    }
+
+    /// Removes the blob from the cache.
+    pub(crate) fn remove(&self, blob_id: &BlobId) {
+        self.index
+            .lock()
+            .expect("mutex should not be poisoned")
+            .remove(blob_id);
+        self.remove_files(&[*blob_id]);
+    }
+
+    fn remove_files(&self, blob_ids: &[BlobId]) {
+        for blob_id in blob_ids {
+            if let Err(error) = std::fs::remove_file(self.blob_path(blob_id)) {
+                if error.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!(%blob_id, ?error, "unable to remove blob from the cache");
+                }
+            }
+        }
+    }
+
+    fn blob_path(&self, blob_id: &BlobId) -> PathBuf {
+        self.directory
+            .join(blob_id.to_string())
+            .with_extension(BLOB_FILE_EXTENSION)
+    }
+}
+
+/// Forwards the chunks of the blob and inserts the blob into the cache once all `length` bytes
+/// have been received without error.
+fn insert_when_complete(
+    cache: Arc<BlobCache>,
+    blob_id: BlobId,
+    length: u64,
+    data: BoxStream<'static, ClientResult<Bytes>>,
+) -> impl Stream<Item = ClientResult<Bytes>> + Send + 'static {
+    let received = Vec::with_capacity(length.try_into().unwrap_or_default());
+    stream::unfold((data, Some(received)), move |(mut data, mut received)| {
+        let cache = cache.clone();
+        async move {
+            let buffer = received.as_mut()?;
+            match data.next().await {
+                Some(Ok(chunk)) => {
+                    buffer.extend_from_slice(&chunk);
+                    Some((Ok(chunk), (data, received)))
+                }
+                Some(Err(error)) => Some((Err(error), (data, None))),
+                None => {
+                    let blob = received.take().expect("checked above");
+                    if blob.len() as u64 == length {
+                        if let Err(error) = cache.insert(&blob_id, &blob).await {
+                            tracing::warn!(
+                                %blob_id,
+                                ?error,
+                                "unable to insert blob into the cache"
+                            );
+                        }
+                    }
+                    None
+                }
+            }
+        }
+    })
+}
+
+/// Returns the blob contained in the file contents if it matches the digest stored alongside it.
+fn verify_contents(mut contents: Vec<u8>) -> Option<Vec<u8>> {
+    if contents.len() < DIGEST_LENGTH {
+        return None;
+    }
+    let blob = contents.split_off(DIGEST_LENGTH);
+    (Sha256::digest(&blob).as_slice() == contents.as_slice()).then_some(blob)
+}
+
+fn blob_id_from_path(path: &Path) -> Option<BlobId> {
+    if !has_extension(path, BLOB_FILE_EXTENSION) {
+        return None;
+    }
+    BlobId::from_str(path.file_stem()?.to_str()?).ok()
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().is_some_and(|value| value == extension)
+}
+
+/// In-memory index of the cached blobs, ordered by the time of their last access.
+#[derive(Debug, Default)]
+struct CacheIndex {
+    /// The size and last access of each cached blob.
+    entries: HashMap<BlobId, (u64, u64)>,
+    /// The cached blobs, keyed by their last access.
+    by_last_access: BTreeMap<u64, BlobId>,
+    /// The sum of the sizes of all cached blobs.
+    total_size: u64,
+    /// A logical clock used to order accesses.
+    clock: u64,
+}
+
+impl CacheIndex {
+    fn insert(&mut self, blob_id: BlobId, size: u64) {
+        self.remove(&blob_id);
+        self.clock += 1;
+        self.entries.insert(blob_id, (size, self.clock));
+        self.by_last_access.insert(self.clock, blob_id);
+        self.total_size += size;
+    }
+
+    /// Marks the blob as accessed, returning false if the blob is not in the index.
+    fn touch(&mut self, blob_id: &BlobId) -> bool {
+        let Some((_, last_access)) = self.entries.get_mut(blob_id) else {
+            return false;
+        };
+        self.by_last_access.remove(last_access);
+        self.clock += 1;
+        *last_access = self.clock;
+        self.by_last_access.insert(self.clock, *blob_id);
+        true
+    }
+
+    fn remove(&mut self, blob_id: &BlobId) {
+        if let Some((size, last_access)) = self.entries.remove(blob_id) {
+            self.by_last_access.remove(&last_access);
+            self.total_size -= size;
+        }
+    }
+
+    /// Removes the least recently used blobs from the index until `additional_size` bytes can be
+    /// added without exceeding `max_size`, and returns the removed blobs.
+    fn evict_until(&mut self, max_size: u64, additional_size: u64) -> Vec<BlobId> {
+        let mut evicted = vec![];
+        while self.total_size + additional_size > max_size {
+            let Some((_, blob_id)) = self.by_last_access.pop_first() else {
+                break;
+            };
+            let (size, _) = self
+                .entries
+                .remove(&blob_id)
+                .expect("entries and access order are consistent");
+            self.total_size -= size;
+            evicted.push(blob_id);
+        }
+        evicted
+    }
+}
+
+/// A client that serves reads from a [`BlobCache`], if one is configured, and populates it with
+/// the blobs it reads.
+///
+/// Blocked blob IDs are never served from the cache, and writes are passed through unchanged.
+#[derive(Debug)]
+pub(crate) struct CachingClient<T> {
+    inner: Arc<T>,
+    cache: Option<Arc<BlobCache>>,
+    blocklist: Option<Blocklist>,
+}
+
+impl<T> CachingClient<T> {
+    /// Wraps the client, caching the blobs it reads in the provided cache.
+    ///
+    /// If `cache` is `None`, all requests are passed through to the inner client.
+    pub(crate) fn new(
+        inner: T,
+        cache: Option<Arc<BlobCache>>,
+        blocklist: Option<Blocklist>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            cache,
+            blocklist,
+        }
+    }
+}
+
+impl<T> CachingClient<T> {
+    /// Returns the blob from the cache, unless it is blocked.
+    async fn cached_blob(&self, cache: &BlobCache, blob_id: &BlobId) -> Option<Vec<u8>> {
+        let is_blocked = self
+            .blocklist
+            .as_ref()
+            .is_some_and(|blocklist| blocklist.is_blocked(blob_id));
+
+        if is_blocked {
+            // Drop the blob in case it was cached before being blocked, and let the inner client
+            // produce the appropriate error.
+            cache.remove(blob_id);
+            return None;
+        }
+        let blob = cache.get(blob_id).await?;
+        tracing::debug!(%blob_id, "serving blob from the cache");
+        Some(blob)
+    }
+}
+
+impl<T: WalrusReadClient + Send + Sync + 'static> WalrusReadClient for CachingClient<T> {
+    async fn read_blob(&self, blob_id: &BlobId) -> ClientResult<Vec<u8>> {
+        let Some(cache) = &self.cache else {
+            return self.inner.read_blob(blob_id).await;
+        };
+        if let Some(blob) = self.cached_blob(cache, blob_id).await {
+            return Ok(blob);
+        }
+
+        let blob = self.inner.read_blob(blob_id).await?;
+        if let Err(error) = cache.insert(blob_id, &blob).await {
+            tracing::warn!(%blob_id, ?error, "unable to insert blob into the cache");
+        }
+        Ok(blob)
+    }
+
+    /// Serves the blob from the cache if present, and otherwise streams it from the inner client.
+    ///
+    /// A streamed blob is inserted into the cache once it has been streamed completely.
+    async fn read_blob_stream(self: Arc<Self>, blob_id: BlobId) -> ClientResult<BlobStream>
+    where
+        Self: Send + Sync + 'static,
+    {
+        let Some(cache) = self.cache.clone() else {
+            return self.inner.clone().read_blob_stream(blob_id).await;
+        };
+        if let Some(blob) = self.cached_blob(&cache, &blob_id).await {
+            return Ok(BlobStream::from_blob(blob));
+        }
+
+        let BlobStream { length, data } = self.inner.clone().read_blob_stream(blob_id).await?;
+        if !cache.can_cache(length) {
+            return Ok(BlobStream { length, data });
+        }
+        Ok(BlobStream {
+            length,
+            data: insert_when_complete(cache, blob_id, length, data).boxed(),
+        })
+    }
+
+    async fn get_blob_by_object_id(
+        &self,
+        blob_object_id: &ObjectID,
+    ) -> ClientResult<BlobWithAttribute> {
+        self.inner.get_blob_by_object_id(blob_object_id).await
+    }
+
+    async fn blob_read_info(&self, blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+        self.inner.blob_read_info(blob_id).await
+    }
+
+    async fn get_blob_status(&self, blob_id: &BlobId) -> ClientResult<BlobCertificationStatus> {
+        self.inner.get_blob_status(blob_id).await
+    }
+}
+
+impl<T: WalrusWriteClient + Send + Sync + 'static> WalrusWriteClient for CachingClient<T> {
+    async fn write_blob(
+        &self,
+        blob: &[u8],
+        encoding_type: Option<EncodingType>,
+        epochs_ahead: EpochCount,
+        store_when: StoreWhen,
+        persistence: BlobPersistence,
+        post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
+    ) -> ClientResult<BlobStoreResult> {
+        self.inner
+            .write_blob(
+                blob,
+                encoding_type,
+                epochs_ahead,
+                store_when,
+                persistence,
+                post_store,
+                metadata_replication,
+            )
+            .await
+    }
+
+    fn default_post_store_action(&self) -> PostStoreAction {
+        self.inner.default_post_store_action()
+    }
+
+    fn compute_blob_id(
+        &self,
+        blob: &[u8],
+        encoding_type: Option<EncodingType>,
+    ) -> ClientResult<BlobId> {
+        self.inner.compute_blob_id(blob, encoding_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tempfile::TempDir;
+    use walrus_core::test_utils::random_blob_id;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+    use crate::client::{ClientError, ClientErrorKind};
+
+    const BLOB_SIZE: usize = 100;
+    const FILE_SIZE: u64 = (DIGEST_LENGTH + BLOB_SIZE) as u64;
+
+    #[tokio::test]
+    async fn evicts_least_recently_used_blobs() -> TestResult {
+        let directory = TempDir::new()?;
+        let cache = BlobCache::open(directory.path(), 2 * FILE_SIZE)?;
+        let blob_ids: Vec<_> = (0..3).map(|_| random_blob_id()).collect();
+
+        cache.insert(&blob_ids[0], &[0; BLOB_SIZE]).await?;
+        cache.insert(&blob_ids[1], &[1; BLOB_SIZE]).await?;
+        assert_eq!(cache.get(&blob_ids[0]).await, Some(vec![0; BLOB_SIZE]));
+
+        // The second blob is now the least recently used.
+        cache.insert(&blob_ids[2], &[2; BLOB_SIZE]).await?;
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.total_size(), 2 * FILE_SIZE);
+        assert_eq!(cache.get(&blob_ids[1]).await, None);
+        assert!(!cache.blob_path(&blob_ids[1]).exists());
+        assert_eq!(cache.get(&blob_ids[2]).await, Some(vec![2; BLOB_SIZE]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn removes_corrupted_blobs() -> TestResult {
+        let directory = TempDir::new()?;
+        let cache = BlobCache::open(directory.path(), 10 * FILE_SIZE)?;
+        let blob_id = random_blob_id();
+
+        cache.insert(&blob_id, &[7; BLOB_SIZE]).await?;
+        let path = cache.blob_path(&blob_id);
+        let mut contents = std::fs::read(&path)?;
+        *contents.last_mut().expect("file is not empty") ^= 1;
+        std::fs::write(&path, contents)?;
+
+        assert_eq!(cache.get(&blob_id).await, None);
+        assert_eq!(cache.len(), 0);
+        assert!(!path.exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reopens_existing_cache() -> TestResult {
+        let directory = TempDir::new()?;
+        let blob_id = random_blob_id();
+        {
+            let cache = BlobCache::open(directory.path(), 10 * FILE_SIZE)?;
+            cache.insert(&blob_id, &[3; BLOB_SIZE]).await?;
+        }
+        std::fs::write(directory.path().join("leftover.tmp"), b"partial")?;
+
+        let cache = BlobCache::open(directory.path(), 10 * FILE_SIZE)?;
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&blob_id).await, Some(vec![3; BLOB_SIZE]));
+        assert!(!directory.path().join("leftover.tmp").exists());
+
+        // Reopening with a smaller bound evicts the excess blobs.
+        drop(cache);
+        let cache = BlobCache::open(directory.path(), FILE_SIZE - 1)?;
+        assert_eq!(cache.len(), 0);
+
+        Ok(())
+    }
+
+    /// A client streaming a fixed blob in chunks of 4 bytes, optionally failing at one chunk.
+    #[derive(Debug)]
+    struct StreamingClient {
+        blob: Vec<u8>,
+        failing_chunk: Option<usize>,
+        n_streams: AtomicUsize,
+    }
+
+    impl StreamingClient {
+        fn new(blob: Vec<u8>, failing_chunk: Option<usize>) -> Self {
+            Self {
+                blob,
+                failing_chunk,
+                n_streams: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl WalrusReadClient for StreamingClient {
+        async fn read_blob(&self, _blob_id: &BlobId) -> ClientResult<Vec<u8>> {
+            unimplemented!("the blob is only streamed")
+        }
+
+        async fn read_blob_stream(self: Arc<Self>, _blob_id: BlobId) -> ClientResult<BlobStream>
+        where
+            Self: Send + Sync + 'static,
+        {
+            self.n_streams.fetch_add(1, Ordering::SeqCst);
+            let chunks: Vec<_> = self
+                .blob
+                .chunks(4)
+                .enumerate()
+                .map(|(index, chunk)| {
+                    if Some(index) == self.failing_chunk {
+                        Err(ClientError::from(ClientErrorKind::NoValidStatusReceived))
+                    } else {
+                        Ok(Bytes::copy_from_slice(chunk))
+                    }
+                })
+                .collect();
+            Ok(BlobStream {
+                length: self.blob.len() as u64,
+                data: stream::iter(chunks).boxed(),
+            })
+        }
+
+        async fn get_blob_by_object_id(
+            &self,
+            _blob_object_id: &ObjectID,
+        ) -> ClientResult<BlobWithAttribute> {
+            unimplemented!()
+        }
+
+        async fn blob_read_info(&self, _blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+            unimplemented!()
+        }
+
+        async fn get_blob_status(
+            &self,
+            _blob_id: &BlobId,
+        ) -> ClientResult<BlobCertificationStatus> {
+            unimplemented!()
+        }
+    }
+
+    async fn stream_blob<T: WalrusReadClient + Send + Sync + 'static>(
+        client: &Arc<CachingClient<T>>,
+        blob_id: BlobId,
+    ) -> ClientResult<Vec<u8>> {
+        let BlobStream { length, mut data } = client.clone().read_blob_stream(blob_id).await?;
+        let mut blob = vec![];
+        while let Some(chunk) = data.next().await {
+            blob.extend_from_slice(&chunk?);
+        }
+        assert_eq!(blob.len() as u64, length);
+        Ok(blob)
+    }
+
+    #[tokio::test]
+    async fn caches_completely_streamed_blobs() -> TestResult {
+        let directory = TempDir::new()?;
+        let cache = Arc::new(BlobCache::open(directory.path(), 10 * FILE_SIZE)?);
+        let blob = walrus_test_utils::random_data(BLOB_SIZE);
+        let client = Arc::new(CachingClient::new(
+            StreamingClient::new(blob.clone(), None),
+            Some(cache.clone()),
+            None,
+        ));
+        let blob_id = random_blob_id();
+
+        assert_eq!(stream_blob(&client, blob_id).await?, blob);
+        assert_eq!(cache.get(&blob_id).await, Some(blob.clone()));
+
+        // The second read is served from the cache.
+        assert_eq!(stream_blob(&client, blob_id).await?, blob);
+        assert_eq!(client.inner.n_streams.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_truncated_streams() -> TestResult {
+        let directory = TempDir::new()?;
+        let cache = Arc::new(BlobCache::open(directory.path(), 10 * FILE_SIZE)?);
+        let client = Arc::new(CachingClient::new(
+            StreamingClient::new(walrus_test_utils::random_data(BLOB_SIZE), Some(3)),
+            Some(cache.clone()),
+            None,
+        ));
+        let blob_id = random_blob_id();
+
+        assert!(stream_blob(&client, blob_id).await.is_err());
+        assert_eq!(cache.len(), 0);
+
+        Ok(())
+    }
+}
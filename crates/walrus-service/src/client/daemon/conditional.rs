@@ -0,0 +1,146 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache validators for blobs served by the aggregator.
+//!
+//! As the contents of a blob are determined by its blob ID, the blob ID serves as strong `ETag`.
+//! The `Last-Modified` time of a blob is the scheduled start of the epoch in which it was initially
+//! certified, which is derived from the on-chain system parameters and therefore stable.
+//! Conditional requests with matching `If-None-Match` or `If-Modified-Since` headers are answered
+//! with `304 Not Modified` without reading the blob, as long as the blob is still certified, which
+//! allows CDNs and browsers to revalidate cached blobs cheaply.
+
+use std::time::SystemTime;
+
+use axum::http::{
+    header::{IF_MODIFIED_SINCE, IF_NONE_MATCH},
+    HeaderMap,
+};
+use axum_extra::headers::{ETag, HeaderMapExt as _, IfModifiedSince, IfNoneMatch, LastModified};
+use chrono::{DateTime, Utc};
+use walrus_core::BlobId;
+
+/// Returns the strong `ETag` of the blob.
+fn blob_etag(blob_id: &BlobId) -> ETag {
+    format!("\"{blob_id}\"")
+        .parse()
+        .expect("the blob ID string only contains valid ETag characters")
+}
+
+/// Inserts the `ETag` and, if known, the `Last-Modified` time of the blob into the headers.
+pub(super) fn insert_validators(
+    headers: &mut HeaderMap,
+    blob_id: &BlobId,
+    last_modified: Option<DateTime<Utc>>,
+) {
+    headers.typed_insert(blob_etag(blob_id));
+    if let Some(last_modified) = last_modified {
+        headers.typed_insert(LastModified::from(SystemTime::from(last_modified)));
+    }
+}
+
+/// Returns true if the request has an `If-None-Match` or `If-Modified-Since` header.
+pub(super) fn has_preconditions(request_headers: &HeaderMap) -> bool {
+    request_headers.contains_key(IF_NONE_MATCH) || request_headers.contains_key(IF_MODIFIED_SINCE)
+}
+
+/// Returns true if the request's preconditions show that the client's copy of the blob is valid.
+///
+/// As specified in RFC 9110, `If-Modified-Since` is only evaluated if the request does not contain
+/// an `If-None-Match` header.
+pub(super) fn is_not_modified(
+    request_headers: &HeaderMap,
+    blob_id: &BlobId,
+    last_modified: Option<DateTime<Utc>>,
+) -> bool {
+    if let Some(if_none_match) = request_headers.typed_get::<IfNoneMatch>() {
+        return !if_none_match.precondition_passes(&blob_etag(blob_id));
+    }
+    match (
+        request_headers.typed_get::<IfModifiedSince>(),
+        last_modified,
+    ) {
+        (Some(if_modified_since), Some(last_modified)) => {
+            !if_modified_since.is_modified(SystemTime::from(last_modified))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::{
+        header::{ETAG, LAST_MODIFIED},
+        HeaderName,
+        HeaderValue,
+    };
+    use walrus_core::test_utils::random_blob_id;
+
+    use super::*;
+
+    fn request(headers: &[(HeaderName, String)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    fn http_date(time: DateTime<Utc>) -> String {
+        let mut headers = HeaderMap::new();
+        headers.typed_insert(LastModified::from(SystemTime::from(time)));
+        headers[LAST_MODIFIED].to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn evaluates_preconditions_against_validators() {
+        let blob_id = random_blob_id();
+        let last_modified = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut response = HeaderMap::new();
+        insert_validators(&mut response, &blob_id, Some(last_modified));
+        let etag = response[ETAG].to_str().unwrap().to_owned();
+        assert_eq!(etag, format!("\"{blob_id}\""));
+        assert_eq!(response[LAST_MODIFIED], http_date(last_modified));
+
+        let not_modified = |headers: &[(HeaderName, String)]| {
+            is_not_modified(&request(headers), &blob_id, Some(last_modified))
+        };
+        assert!(not_modified(&[(IF_NONE_MATCH, etag)]));
+        assert!(not_modified(&[(IF_NONE_MATCH, "*".to_owned())]));
+        assert!(!not_modified(&[(
+            IF_NONE_MATCH,
+            format!("\"{}\"", random_blob_id())
+        )]));
+        assert!(not_modified(&[(
+            IF_MODIFIED_SINCE,
+            http_date(last_modified)
+        )]));
+        assert!(!not_modified(&[(
+            IF_MODIFIED_SINCE,
+            http_date(last_modified - Duration::from_secs(60))
+        )]));
+        // `If-Modified-Since` is ignored if `If-None-Match` is present.
+        assert!(!not_modified(&[
+            (IF_NONE_MATCH, "\"other\"".to_owned()),
+            (IF_MODIFIED_SINCE, http_date(last_modified)),
+        ]));
+        assert!(!not_modified(&[]));
+    }
+
+    #[test]
+    fn detects_preconditions() {
+        assert!(!has_preconditions(&request(&[(
+            ETAG,
+            "\"etag\"".to_owned()
+        )])));
+        assert!(has_preconditions(&request(&[(
+            IF_NONE_MATCH,
+            "\"etag\"".to_owned()
+        )])));
+        assert!(has_preconditions(&request(&[(
+            IF_MODIFIED_SINCE,
+            http_date(Utc::now())
+        )])));
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Cross-Origin Resource Sharing (CORS) policy of the client daemon.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use axum::http::{HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DurationSeconds};
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer, ExposeHeaders};
+
+/// The wildcard that allows any value.
+const WILDCARD: &str = "*";
+
+/// The configuration of the CORS headers returned by the daemon.
+///
+/// By default, requests from any origin are allowed, such that browser-based applications can use
+/// the daemon directly.
+#[serde_as]
+#[derive(Debug, Clone, clap::Args, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsConfig {
+    /// The origins allowed to make cross-origin requests, or `*` to allow any origin.
+    #[clap(long, num_args = 1.., default_values_t = default::allowed_origins())]
+    pub(crate) cors_allowed_origins: Vec<String>,
+    /// The HTTP methods allowed in cross-origin requests.
+    #[clap(long, num_args = 1.., default_values_t = default::allowed_methods())]
+    pub(crate) cors_allowed_methods: Vec<String>,
+    /// The request headers allowed in cross-origin requests, or `*` to allow any header.
+    #[clap(long, num_args = 1.., default_values_t = default::wildcard())]
+    pub(crate) cors_allowed_headers: Vec<String>,
+    /// The response headers exposed to cross-origin requests, or `*` to expose all headers.
+    #[clap(long, num_args = 1.., default_values_t = default::wildcard())]
+    pub(crate) cors_exposed_headers: Vec<String>,
+    /// The time for which browsers may cache the result of a preflight request.
+    #[serde(rename = "cors_max_age_secs")]
+    #[serde_as(as = "DurationSeconds")]
+    #[clap(long, value_parser = humantime::parse_duration, default_value = "1day")]
+    pub(crate) cors_max_age: Duration,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            cors_allowed_origins: default::allowed_origins(),
+            cors_allowed_methods: default::allowed_methods(),
+            cors_allowed_headers: default::wildcard(),
+            cors_exposed_headers: default::wildcard(),
+            cors_max_age: default::max_age(),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Builds the layer that adds the CORS headers to responses and answers preflight requests.
+    pub(crate) fn layer(&self) -> anyhow::Result<CorsLayer> {
+        let allow_origin = if is_wildcard(&self.cors_allowed_origins) {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(
+                self.cors_allowed_origins
+                    .iter()
+                    .map(|origin| HeaderValue::from_str(origin))
+                    .collect::<Result<Vec<_>, _>>()
+                    .context("invalid CORS origin")?,
+            )
+        };
+        // The wildcard does not cover the `Authorization` header, which is required to call an
+        // authenticated publisher; the requested headers are therefore mirrored instead.
+        let allow_headers = if is_wildcard(&self.cors_allowed_headers) {
+            AllowHeaders::mirror_request()
+        } else {
+            AllowHeaders::list(parse_header_names(&self.cors_allowed_headers)?)
+        };
+        let expose_headers = if is_wildcard(&self.cors_exposed_headers) {
+            ExposeHeaders::any()
+        } else {
+            ExposeHeaders::list(parse_header_names(&self.cors_exposed_headers)?)
+        };
+        let allow_methods = self
+            .cors_allowed_methods
+            .iter()
+            .map(|method| Method::from_bytes(method.to_ascii_uppercase().as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .context("invalid CORS method")?;
+
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            .allow_headers(allow_headers)
+            .expose_headers(expose_headers)
+            .max_age(self.cors_max_age))
+    }
+}
+
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|value| value == WILDCARD)
+}
+
+fn parse_header_names(names: &[String]) -> anyhow::Result<Vec<HeaderName>> {
+    names
+        .iter()
+        .map(|name| HeaderName::from_bytes(name.as_bytes()))
+        .collect::<Result<_, _>>()
+        .context("invalid CORS header name")
+}
+
+mod default {
+    use std::time::Duration;
+
+    use super::WILDCARD;
+
+    pub(crate) fn allowed_origins() -> Vec<String> {
+        wildcard()
+    }
+
+    pub(crate) fn allowed_methods() -> Vec<String> {
+        ["GET", "HEAD", "PUT", "POST", "OPTIONS"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    pub(crate) fn wildcard() -> Vec<String> {
+        vec![WILDCARD.to_owned()]
+    }
+
+    pub(crate) fn max_age() -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use tower::ServiceExt as _;
+
+    use super::*;
+    use crate::client::daemon::routes::{BLOB_BATCH_GET_ENDPOINT, UNSIGNED_BLOB_CERTIFY_ENDPOINT};
+
+    /// Sends a preflight request for a `POST` request to `uri` to a router serving `endpoint` with
+    /// the default CORS policy, and returns the allowed methods.
+    async fn preflight_post(endpoint: &str, uri: &str) -> String {
+        let router = Router::new().route(endpoint, post(|| async {})).layer(
+            CorsConfig::default()
+                .layer()
+                .expect("the default config is valid"),
+        );
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri(uri)
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(Body::empty())
+            .expect("the request is valid");
+
+        let response = router.oneshot(request).await.expect("infallible");
+        assert_eq!(response.status(), StatusCode::OK);
+        response.headers()[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .expect("the header is valid")
+            .to_owned()
+    }
+
+    #[tokio::test]
+    async fn allows_preflight_for_unsigned_certification() {
+        let allowed_methods = preflight_post(
+            UNSIGNED_BLOB_CERTIFY_ENDPOINT,
+            "/v1/unsigned/blobs/0x1/certify",
+        )
+        .await;
+        assert!(allowed_methods
+            .split(',')
+            .any(|method| method.trim() == "POST"));
+    }
+
+    #[tokio::test]
+    async fn allows_preflight_for_batched_reads() {
+        let allowed_methods =
+            preflight_post(BLOB_BATCH_GET_ENDPOINT, BLOB_BATCH_GET_ENDPOINT).await;
+        assert!(allowed_methods
+            .split(',')
+            .any(|method| method.trim() == "POST"));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(CorsConfig::default().layer().is_ok());
+        assert!(CorsConfig {
+            cors_allowed_origins: vec!["https://example.com\n".to_owned()],
+            ..Default::default()
+        }
+        .layer()
+        .is_err());
+        assert!(CorsConfig {
+            cors_allowed_headers: vec!["invalid header".to_owned()],
+            ..Default::default()
+        }
+        .layer()
+        .is_err());
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Response headers that the aggregator derives from the attributes of blob objects.
+//!
+//! Attributes are set by the owner of a blob object and are therefore untrusted. Only attributes
+//! whose keys are in the allowlist of headers are returned as headers. In addition, the
+//! `Content-Type` is only applied if it is in the allowlist of content types, such that content
+//! that browsers may execute (e.g., HTML or SVG) is not served from the aggregator's origin unless
+//! explicitly allowed; otherwise, the blob is served as `application/octet-stream`. The
+//! `Content-Disposition` is only applied if its type is `inline` or `attachment`.
+
+use std::{collections::HashSet, str::FromStr};
+
+use axum::http::{
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    HeaderMap,
+    HeaderName,
+    HeaderValue,
+};
+use walrus_sui::types::move_structs::BlobAttribute;
+
+/// Determines which attributes of a blob are returned as response headers.
+#[derive(Debug, Default)]
+pub(crate) struct AttributeHeaderPolicy {
+    /// The lowercase attribute keys that are returned as headers.
+    allowed_headers: HashSet<String>,
+    /// The lowercase content types that are served as specified in the attributes.
+    ///
+    /// An entry of the form `type/*` allows all subtypes of `type`.
+    allowed_content_types: HashSet<String>,
+}
+
+impl AttributeHeaderPolicy {
+    /// Creates a new policy from the allowlists of headers and content types.
+    pub fn new(
+        allowed_headers: impl IntoIterator<Item = String>,
+        allowed_content_types: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            allowed_headers: allowed_headers
+                .into_iter()
+                .map(|header| header.to_ascii_lowercase())
+                .collect(),
+            allowed_content_types: allowed_content_types
+                .into_iter()
+                .map(|content_type| content_type.to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Inserts the allowed and valid attributes of the blob into the response headers.
+    pub fn apply(&self, headers: &mut HeaderMap, attribute: &BlobAttribute) {
+        for (key, value) in attribute.iter() {
+            if !self.allowed_headers.contains(&key.to_ascii_lowercase()) {
+                continue;
+            }
+            let (Ok(header_name), Ok(header_value)) =
+                (HeaderName::from_str(key), HeaderValue::from_str(value))
+            else {
+                continue;
+            };
+            let is_valid = if header_name == CONTENT_TYPE {
+                self.is_allowed_content_type(value)
+            } else if header_name == CONTENT_DISPOSITION {
+                is_valid_content_disposition(value)
+            } else {
+                true
+            };
+
+            if is_valid {
+                headers.insert(header_name, header_value);
+            } else {
+                tracing::debug!(%header_name, %value, "ignoring disallowed attribute header");
+            }
+        }
+    }
+
+    /// Sets the `Content-Type` of the response if the content type is allowed and valid.
+    pub fn apply_content_type(&self, headers: &mut HeaderMap, content_type: &str) {
+        match HeaderValue::from_str(content_type) {
+            Ok(header_value) if self.is_allowed_content_type(content_type) => {
+                headers.insert(CONTENT_TYPE, header_value);
+            }
+            _ => tracing::debug!(%content_type, "ignoring disallowed content type"),
+        }
+    }
+
+    fn is_allowed_content_type(&self, content_type: &str) -> bool {
+        let essence = media_type_essence(content_type);
+        let Some((main_type, _)) = essence.split_once('/') else {
+            return false;
+        };
+        self.allowed_content_types.contains(&essence)
+            || self
+                .allowed_content_types
+                .contains(&format!("{main_type}/*"))
+    }
+}
+
+/// Returns the lowercase media type without parameters, e.g., `text/plain` for
+/// `text/plain; charset=utf-8`.
+fn media_type_essence(value: &str) -> String {
+    value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_valid_content_disposition(value: &str) -> bool {
+    matches!(media_type_essence(value).as_str(), "inline" | "attachment")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(attribute: &[(&str, &str)]) -> HeaderMap {
+        let policy = AttributeHeaderPolicy::new(
+            ["content-type", "Content-Disposition", "cache-control"].map(String::from),
+            ["image/png", "video/*", "text/plain"].map(String::from),
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        );
+        policy.apply(
+            &mut headers,
+            &BlobAttribute::from(attribute.iter().copied()),
+        );
+        headers
+    }
+
+    #[test]
+    fn applies_allowed_headers() {
+        let headers = apply(&[
+            ("content-type", "text/plain; charset=utf-8"),
+            ("content-disposition", "attachment; filename=\"notes.txt\""),
+            ("cache-control", "no-store"),
+            ("authorization", "secret"),
+        ]);
+        assert_eq!(headers[CONTENT_TYPE], "text/plain; charset=utf-8");
+        assert_eq!(
+            headers[CONTENT_DISPOSITION],
+            "attachment; filename=\"notes.txt\""
+        );
+        assert_eq!(headers["cache-control"], "no-store");
+        assert!(!headers.contains_key("authorization"));
+
+        assert_eq!(
+            apply(&[("Content-Type", "Video/MP4")])[CONTENT_TYPE],
+            "Video/MP4"
+        );
+    }
+
+    #[test]
+    fn ignores_unsafe_values() {
+        let headers = apply(&[
+            ("content-type", "text/html"),
+            ("content-disposition", "form-data; name=\"blob\""),
+        ]);
+        assert_eq!(headers[CONTENT_TYPE], "application/octet-stream");
+        assert!(!headers.contains_key(CONTENT_DISPOSITION));
+
+        for content_type in ["image/svg+xml", "image", ""] {
+            assert_eq!(
+                apply(&[("content-type", content_type)])[CONTENT_TYPE],
+                "application/octet-stream"
+            );
+        }
+    }
+}
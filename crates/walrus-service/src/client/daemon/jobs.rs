@@ -0,0 +1,716 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A persistent queue of store jobs for the publisher.
+//!
+//! Blobs submitted as jobs are written to the job directory before the job is acknowledged, and
+//! are then stored on Walrus in the background. Each job consists of a record file
+//! `<job_id>.json`, holding the parameters and the status of the job, and a data file
+//! `<job_id>.blob`, which is removed once the job has completed or failed. The records of finished
+//! jobs are kept for a configurable time after they finished, such that clients can poll their
+//! status.
+//!
+//! Besides the status, each record holds the phase the job reached, which is updated before each
+//! step that cannot be repeated for free. When the publisher starts, all jobs that have not
+//! finished are resumed from their phase: a job that had already submitted its blob for storage
+//! checks the status of the blob and the resources in the wallet before storing again, such that a
+//! blob that was already registered with the same wallet before the restart is not registered (and
+//! paid for) again.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Weak},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr, TimestampSeconds};
+use sui_types::base_types::SuiAddress;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+use walrus_core::{BlobId, EncodingType, EpochCount};
+use walrus_sui::client::{BlobPersistence, PostStoreAction};
+
+use super::WalrusWriteClient;
+use crate::client::{responses::BlobStoreResult, MetadataReplication, StoreWhen};
+
+/// The extension of the files holding job records.
+const RECORD_FILE_EXTENSION: &str = "json";
+/// The extension of the files holding the data of pending jobs.
+const BLOB_FILE_EXTENSION: &str = "blob";
+/// The extension of the files to which records are written before being moved into place.
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+/// The interval at which expired records of finished jobs are removed.
+const RECORD_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The parameters with which a job stores its blob.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JobParams {
+    /// The encoding type to use for the blob.
+    pub encoding_type: Option<EncodingType>,
+    /// The number of epochs, ahead of the current one, for which to store the blob.
+    pub epochs: EpochCount,
+    /// Whether the blob is deletable.
+    pub deletable: bool,
+    /// The address to which the created blob object is sent, if any.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub send_object_to: Option<SuiAddress>,
+    /// Whether the metadata of the blob is pushed to all storage nodes of the committee.
+    #[serde(default)]
+    pub push_metadata_to_all_nodes: bool,
+}
+
+/// The status of a store job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "state",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub(crate) enum JobStatus {
+    /// The blob has been received and is waiting to be stored.
+    Pending,
+    /// The blob is being stored on Walrus.
+    Storing {
+        /// The number of times storing the blob was started, including restarts of the publisher.
+        attempt: u32,
+    },
+    /// The blob has been stored on Walrus.
+    Completed {
+        /// The result of the store operation.
+        result: BlobStoreResult,
+    },
+    /// Storing the blob failed.
+    Failed {
+        /// A description of the error.
+        error: String,
+    },
+}
+
+impl JobStatus {
+    /// Returns `true` if the job will not make any further progress.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed { .. } | Self::Failed { .. })
+    }
+}
+
+/// The progress of a store job, which determines how it is resumed after a restart.
+#[serde_as]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "step",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub(crate) enum JobPhase {
+    /// The blob has been received and persisted, but it has not been submitted for storage.
+    #[default]
+    Received,
+    /// The blob ID has been computed and the blob has been submitted for storage.
+    ///
+    /// The blob may already be registered on Sui, and some of its slivers may be stored.
+    Submitted {
+        /// The ID of the blob.
+        #[serde_as(as = "DisplayFromStr")]
+        blob_id: BlobId,
+    },
+}
+
+/// The persisted state of a store job.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JobRecord {
+    /// The ID of the job.
+    #[serde_as(as = "DisplayFromStr")]
+    pub id: Uuid,
+    /// The parameters of the store operation.
+    pub params: JobParams,
+    /// The size of the blob in bytes.
+    pub size: u64,
+    /// The current status of the job.
+    pub status: JobStatus,
+    /// The phase the job has reached.
+    #[serde(default)]
+    pub phase: JobPhase,
+    /// The time at which the job was accepted.
+    #[serde_as(as = "TimestampSeconds<i64>")]
+    pub created_at: SystemTime,
+    /// The time at which the status of the job last changed.
+    #[serde_as(as = "TimestampSeconds<i64>")]
+    pub updated_at: SystemTime,
+}
+
+impl JobRecord {
+    fn set_status(&mut self, status: JobStatus) {
+        self.status = status;
+        self.updated_at = SystemTime::now();
+    }
+
+    fn set_phase(&mut self, phase: JobPhase) {
+        self.phase = phase;
+        self.updated_at = SystemTime::now();
+    }
+
+    /// Returns `true` if the job has finished and its record was last updated more than `ttl` ago.
+    fn is_expired(&self, ttl: Duration, now: SystemTime) -> bool {
+        self.status.is_finished()
+            && now
+                .duration_since(self.updated_at)
+                .is_ok_and(|elapsed| elapsed > ttl)
+    }
+}
+
+/// The directory in which job records and blobs are persisted.
+#[derive(Debug, Clone)]
+struct JobStore {
+    directory: PathBuf,
+}
+
+impl JobStore {
+    fn record_path(&self, id: &Uuid) -> PathBuf {
+        self.directory.join(format!("{id}.{RECORD_FILE_EXTENSION}"))
+    }
+
+    fn blob_path(&self, id: &Uuid) -> PathBuf {
+        self.directory.join(format!("{id}.{BLOB_FILE_EXTENSION}"))
+    }
+
+    /// Atomically writes the record, replacing any previous version.
+    async fn write_record(&self, record: &JobRecord) -> anyhow::Result<()> {
+        let temporary_path = self
+            .directory
+            .join(format!("{}.{TEMPORARY_FILE_EXTENSION}", record.id));
+        tokio::fs::write(&temporary_path, serde_json::to_vec(record)?).await?;
+        tokio::fs::rename(&temporary_path, self.record_path(&record.id)).await?;
+        Ok(())
+    }
+
+    async fn read_record(&self, id: &Uuid) -> anyhow::Result<Option<JobRecord>> {
+        match tokio::fs::read(self.record_path(id)).await {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns all records in the directory, removing files left over from interrupted writes.
+    fn read_all_records(&self) -> anyhow::Result<Vec<JobRecord>> {
+        let mut records = vec![];
+        for entry in std::fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if has_extension(&path, TEMPORARY_FILE_EXTENSION) {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            if !has_extension(&path, RECORD_FILE_EXTENSION) {
+                continue;
+            }
+            match std::fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| Ok(serde_json::from_slice(&contents)?))
+            {
+                Ok(record) => records.push(record),
+                Err(error) => {
+                    tracing::warn!(path = %path.display(), ?error, "skipping unreadable job record")
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Removes the records of all jobs that finished more than `ttl` ago.
+    ///
+    /// Returns the records that remain.
+    fn remove_expired_records(&self, ttl: Duration) -> anyhow::Result<Vec<JobRecord>> {
+        let now = SystemTime::now();
+        let (expired, remaining): (Vec<_>, Vec<_>) = self
+            .read_all_records()?
+            .into_iter()
+            .partition(|record| record.is_expired(ttl, now));
+        for record in &expired {
+            let _ = std::fs::remove_file(self.blob_path(&record.id));
+            std::fs::remove_file(self.record_path(&record.id))?;
+        }
+        if !expired.is_empty() {
+            tracing::debug!(n_removed = expired.len(), "removed expired job records");
+        }
+        Ok(remaining)
+    }
+}
+
+/// A queue of store jobs that survives restarts of the publisher.
+#[derive(Debug)]
+pub(crate) struct JobQueue<T> {
+    client: Arc<T>,
+    store: JobStore,
+    permits: Semaphore,
+}
+
+impl<T: WalrusWriteClient + Send + Sync + 'static> JobQueue<T> {
+    /// Opens the queue in the provided directory, creating the directory if it does not exist.
+    ///
+    /// All unfinished jobs found in the directory are resumed, and at most `max_concurrent_jobs`
+    /// jobs are processed at the same time. The records of finished jobs are removed once they have
+    /// not changed for `record_ttl`.
+    pub fn open(
+        client: Arc<T>,
+        directory: &Path,
+        max_concurrent_jobs: usize,
+        record_ttl: Duration,
+    ) -> anyhow::Result<Arc<Self>> {
+        std::fs::create_dir_all(directory).with_context(|| {
+            format!(
+                "unable to create job queue directory {}",
+                directory.display()
+            )
+        })?;
+
+        let queue = Arc::new(Self {
+            client,
+            store: JobStore {
+                directory: directory.to_owned(),
+            },
+            permits: Semaphore::new(max_concurrent_jobs),
+        });
+
+        let mut unfinished: Vec<_> = queue
+            .store
+            .remove_expired_records(record_ttl)?
+            .into_iter()
+            .filter(|record| !record.status.is_finished())
+            .collect();
+        unfinished.sort_by_key(|record| record.created_at);
+        tracing::info!(
+            directory = %directory.display(),
+            n_resumed = unfinished.len(),
+            "opened job queue"
+        );
+        for record in unfinished {
+            tokio::spawn(queue.clone().process(record));
+        }
+        tokio::spawn(Self::remove_expired_records_periodically(
+            Arc::downgrade(&queue),
+            record_ttl,
+        ));
+
+        Ok(queue)
+    }
+
+    /// Persists the blob as a new job and schedules it to be stored.
+    pub async fn submit(self: &Arc<Self>, params: JobParams, blob: &[u8]) -> anyhow::Result<Uuid> {
+        let now = SystemTime::now();
+        let record = JobRecord {
+            id: Uuid::now_v7(),
+            params,
+            size: blob.len() as u64,
+            status: JobStatus::Pending,
+            phase: JobPhase::Received,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // The blob must be written before the record, such that every record of an unfinished
+        // job has its data.
+        tokio::fs::write(self.store.blob_path(&record.id), blob).await?;
+        self.store.write_record(&record).await?;
+
+        let id = record.id;
+        tracing::debug!(job_id = %id, size = record.size, "accepted store job");
+        tokio::spawn(self.clone().process(record));
+        Ok(id)
+    }
+
+    /// Returns the record of the job, if it exists.
+    pub async fn get(&self, id: &Uuid) -> anyhow::Result<Option<JobRecord>> {
+        self.store.read_record(id).await
+    }
+
+    async fn process(self: Arc<Self>, mut record: JobRecord) {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+
+        let attempt = match record.status {
+            JobStatus::Storing { attempt } => attempt + 1,
+            _ => 1,
+        };
+        let status = match self.store_blob(&mut record, attempt).await {
+            Ok(BlobStoreResult::MarkedInvalid { .. }) => JobStatus::Failed {
+                error: "the blob was marked invalid, which is likely a system error, please \
+                    report it"
+                    .to_owned(),
+            },
+            Ok(result) => JobStatus::Completed { result },
+            Err(error) => {
+                tracing::error!(job_id = %record.id, ?error, "error storing blob of job");
+                JobStatus::Failed {
+                    error: format!("{error:#}"),
+                }
+            }
+        };
+
+        record.set_status(status);
+        if let Err(error) = self.store.write_record(&record).await {
+            // The job is resumed when the publisher restarts.
+            tracing::error!(job_id = %record.id, ?error, "unable to persist the job status");
+            return;
+        }
+        let _ = tokio::fs::remove_file(self.store.blob_path(&record.id)).await;
+        tracing::debug!(job_id = %record.id, status = ?record.status, "finished store job");
+    }
+
+    async fn store_blob(
+        &self,
+        record: &mut JobRecord,
+        attempt: u32,
+    ) -> anyhow::Result<BlobStoreResult> {
+        record.set_status(JobStatus::Storing { attempt });
+        self.store.write_record(record).await?;
+
+        let mut blob = tokio::fs::read(self.store.blob_path(&record.id))
+            .await
+            .context("unable to read the blob of the job")?;
+        let store_when = match record.phase {
+            JobPhase::Received => {
+                let client = self.client.clone();
+                let encoding_type = record.params.encoding_type;
+                // Computing the blob ID requires encoding the blob, which must not block the
+                // runtime.
+                let (returned_blob, blob_id) = tokio::task::spawn_blocking(move || {
+                    let blob_id = client.compute_blob_id(&blob, encoding_type);
+                    (blob, blob_id)
+                })
+                .await?;
+                blob = returned_blob;
+                record.set_phase(JobPhase::Submitted { blob_id: blob_id? });
+                self.store.write_record(record).await?;
+                // Nothing has been registered for this job yet.
+                StoreWhen::NotStoredIgnoreResources
+            }
+            JobPhase::Submitted { blob_id } => self.store_when_resuming(&blob_id).await,
+        };
+
+        let params = &record.params;
+        let post_store_action = if let Some(address) = params.send_object_to {
+            PostStoreAction::TransferTo(address)
+        } else {
+            self.client.default_post_store_action()
+        };
+        Ok(self
+            .client
+            .write_blob(
+                &blob,
+                params.encoding_type,
+                params.epochs,
+                store_when,
+                BlobPersistence::from_deletable(params.deletable),
+                post_store_action,
+                MetadataReplication::from_flag(params.push_metadata_to_all_nodes),
+            )
+            .await?)
+    }
+
+    /// Returns when to store a blob whose earlier store operation was interrupted.
+    ///
+    /// If the blob is registered, the registration may stem from the interrupted operation;
+    /// checking the resources in the wallet then allows reusing it.
+    async fn store_when_resuming(&self, blob_id: &BlobId) -> StoreWhen {
+        match self.client.get_blob_status(blob_id).await {
+            Ok(status) if !status.status.is_registered() => StoreWhen::NotStoredIgnoreResources,
+            Ok(_) => StoreWhen::NotStored,
+            Err(error) => {
+                tracing::warn!(%blob_id, ?error, "unable to get the status of a resumed blob");
+                StoreWhen::NotStored
+            }
+        }
+    }
+
+    async fn remove_expired_records_periodically(queue: Weak<Self>, record_ttl: Duration) {
+        let mut interval = tokio::time::interval(RECORD_CLEANUP_INTERVAL);
+        // The records were already cleaned up when opening the queue.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let Some(queue) = queue.upgrade() else {
+                return;
+            };
+            let store = queue.store.clone();
+            drop(queue);
+            match tokio::task::spawn_blocking(move || store.remove_expired_records(record_ttl))
+                .await
+            {
+                Ok(Ok(_)) => (),
+                Ok(Err(error)) => tracing::warn!(?error, "unable to remove expired job records"),
+                Err(error) => tracing::warn!(?error, "removing expired job records panicked"),
+            }
+        }
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|path_extension| path_extension == extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use sui_types::base_types::ObjectID;
+    use tempfile::TempDir;
+    use walrus_sdk::api::{BlobStatus, DeletableCounts};
+    use walrus_sui::types::move_structs::BlobWithAttribute;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+    use crate::client::{
+        daemon::{BlobStream, WalrusReadClient},
+        responses::{BlobCertificationStatus, BlobReadInfo, EventOrObjectId},
+        ClientResult,
+    };
+
+    fn record(status: JobStatus) -> JobRecord {
+        JobRecord {
+            id: Uuid::now_v7(),
+            params: JobParams {
+                encoding_type: None,
+                epochs: 2,
+                deletable: true,
+                send_object_to: Some(SuiAddress::ZERO),
+                push_metadata_to_all_nodes: false,
+            },
+            size: 10,
+            status,
+            phase: JobPhase::Received,
+            created_at: SystemTime::UNIX_EPOCH,
+            updated_at: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[tokio::test]
+    async fn records_are_persisted() -> TestResult {
+        let directory = TempDir::new()?;
+        let store = JobStore {
+            directory: directory.path().to_owned(),
+        };
+        let pending = record(JobStatus::Pending);
+        let failed = record(JobStatus::Failed {
+            error: "error".to_owned(),
+        });
+        store.write_record(&pending).await?;
+        store.write_record(&failed).await?;
+        std::fs::write(directory.path().join("interrupted.tmp"), b"{")?;
+
+        let read = store
+            .read_record(&pending.id)
+            .await?
+            .expect("record exists");
+        assert_eq!(read.params, pending.params);
+        assert!(matches!(read.status, JobStatus::Pending));
+        assert!(store.read_record(&Uuid::now_v7()).await?.is_none());
+
+        let mut ids: Vec<_> = store
+            .read_all_records()?
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        ids.sort();
+        let mut expected = vec![pending.id, failed.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(!directory.path().join("interrupted.tmp").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn removes_only_expired_records_of_finished_jobs() -> TestResult {
+        let directory = TempDir::new()?;
+        let store = JobStore {
+            directory: directory.path().to_owned(),
+        };
+        let expired = record(JobStatus::Failed {
+            error: "error".to_owned(),
+        });
+        let mut recent = expired.clone();
+        recent.id = Uuid::now_v7();
+        recent.updated_at = SystemTime::now();
+        let unfinished = record(JobStatus::Pending);
+        for record in [&expired, &recent, &unfinished] {
+            store.write_record(record).await?;
+        }
+
+        let mut remaining: Vec<_> = store
+            .remove_expired_records(Duration::from_secs(60))?
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        remaining.sort();
+        let mut expected = vec![recent.id, unfinished.id];
+        expected.sort();
+        assert_eq!(remaining, expected);
+        assert!(store.read_record(&expired.id).await?.is_none());
+
+        Ok(())
+    }
+
+    /// A client that records the store operations and reports a single blob as registered.
+    #[derive(Debug)]
+    struct RecordingClient {
+        registered_blob_id: BlobId,
+        stored: Mutex<Vec<(Vec<u8>, StoreWhen)>>,
+    }
+
+    impl WalrusReadClient for RecordingClient {
+        async fn read_blob(&self, _blob_id: &BlobId) -> ClientResult<Vec<u8>> {
+            unimplemented!()
+        }
+
+        async fn read_blob_stream(self: Arc<Self>, _blob_id: BlobId) -> ClientResult<BlobStream>
+        where
+            Self: Send + Sync + 'static,
+        {
+            unimplemented!()
+        }
+
+        async fn get_blob_by_object_id(
+            &self,
+            _blob_object_id: &ObjectID,
+        ) -> ClientResult<BlobWithAttribute> {
+            unimplemented!()
+        }
+
+        async fn blob_read_info(&self, _blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+            unimplemented!()
+        }
+
+        async fn get_blob_status(&self, blob_id: &BlobId) -> ClientResult<BlobCertificationStatus> {
+            let status = if *blob_id == self.registered_blob_id {
+                BlobStatus::Deletable {
+                    initial_certified_epoch: None,
+                    deletable_counts: DeletableCounts {
+                        count_deletable_total: 1,
+                        count_deletable_certified: 0,
+                    },
+                }
+            } else {
+                BlobStatus::Nonexistent
+            };
+            Ok(BlobCertificationStatus::new(*blob_id, status, None, None))
+        }
+    }
+
+    impl WalrusWriteClient for RecordingClient {
+        async fn write_blob(
+            &self,
+            blob: &[u8],
+            encoding_type: Option<EncodingType>,
+            epochs_ahead: EpochCount,
+            store_when: StoreWhen,
+            _persistence: BlobPersistence,
+            _post_store: PostStoreAction,
+            _metadata_replication: MetadataReplication,
+        ) -> ClientResult<BlobStoreResult> {
+            self.stored
+                .lock()
+                .expect("mutex is not poisoned")
+                .push((blob.to_vec(), store_when));
+            Ok(BlobStoreResult::AlreadyCertified {
+                blob_id: self.compute_blob_id(blob, encoding_type)?,
+                event_or_object: EventOrObjectId::Object(ObjectID::ZERO),
+                end_epoch: epochs_ahead,
+            })
+        }
+
+        fn default_post_store_action(&self) -> PostStoreAction {
+            PostStoreAction::Keep
+        }
+
+        fn compute_blob_id(
+            &self,
+            blob: &[u8],
+            _encoding_type: Option<EncodingType>,
+        ) -> ClientResult<BlobId> {
+            Ok(BlobId(
+                [u8::try_from(blob.len()).expect("the blob is small"); BlobId::LENGTH],
+            ))
+        }
+    }
+
+    async fn wait_until_finished(
+        queue: &JobQueue<RecordingClient>,
+        id: &Uuid,
+    ) -> anyhow::Result<JobRecord> {
+        for _ in 0..100 {
+            let record = queue.get(id).await?.expect("record exists");
+            if record.status.is_finished() {
+                return Ok(record);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        anyhow::bail!("job {id} did not finish")
+    }
+
+    #[tokio::test]
+    async fn resumes_unfinished_jobs_from_their_phase() -> TestResult {
+        let directory = TempDir::new()?;
+        let store = JobStore {
+            directory: directory.path().to_owned(),
+        };
+        let client = Arc::new(RecordingClient {
+            registered_blob_id: BlobId([11; BlobId::LENGTH]),
+            stored: Mutex::new(vec![]),
+        });
+
+        // One job was interrupted before and one after it submitted its blob for storage.
+        let received = record(JobStatus::Pending);
+        let mut submitted = record(JobStatus::Storing { attempt: 1 });
+        submitted.phase = JobPhase::Submitted {
+            blob_id: client.registered_blob_id,
+        };
+        let expired = record(JobStatus::Failed {
+            error: "error".to_owned(),
+        });
+        for (record, blob) in [
+            (&received, b"fresh".as_slice()),
+            (&submitted, b"interrupted".as_slice()),
+        ] {
+            std::fs::write(store.blob_path(&record.id), blob)?;
+            store.write_record(record).await?;
+        }
+        store.write_record(&expired).await?;
+
+        let queue = JobQueue::open(client.clone(), directory.path(), 2, Duration::from_secs(60))?;
+        let received = wait_until_finished(&queue, &received.id).await?;
+        let submitted = wait_until_finished(&queue, &submitted.id).await?;
+
+        for record in [&received, &submitted] {
+            assert!(matches!(record.status, JobStatus::Completed { .. }));
+            assert!(!store.blob_path(&record.id).exists());
+        }
+        assert_eq!(
+            received.phase,
+            JobPhase::Submitted {
+                blob_id: BlobId([5; BlobId::LENGTH])
+            }
+        );
+        assert!(queue.get(&expired.id).await?.is_none());
+
+        // Only the job that had already submitted its blob checks the resources in the wallet.
+        let mut stored = client.stored.lock().expect("mutex is not poisoned").clone();
+        stored.sort_by_key(|(blob, _)| blob.len());
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].0, b"fresh");
+        assert!(stored[0].1.is_ignore_resources());
+        assert_eq!(stored[1].0, b"interrupted");
+        assert!(!stored[1].1.is_ignore_resources());
+
+        Ok(())
+    }
+}
@@ -11,17 +11,29 @@ use walrus_sui::{
     SuiAddressSchema,
 };
 
-use super::routes;
+use super::{
+    routes,
+    unsigned::{PreparedCertification, PreparedUpload, TransactionToSign},
+};
 use crate::{
-    client::{resource::RegisterBlobOp, responses::EventOrObjectId, BlobStoreResult},
+    client::{
+        receipt::{SignedStoreReceipt, StoreReceipt},
+        resource::RegisterBlobOp,
+        responses::{BlobStoreResponse, EventOrObjectId},
+        BlobStoreResult,
+    },
     common::api::Binary,
 };
 
 #[derive(OpenApi)]
 #[openapi(
     info(title = "Walrus Aggregator"),
-    paths(routes::get_blob, routes::get_blob_by_object_id),
-    components(schemas(BlobId, Status,))
+    paths(
+        routes::get_blob,
+        routes::get_blob_by_object_id,
+        routes::batch_get_blobs
+    ),
+    components(schemas(BlobId, Status, routes::BatchGetRequest))
 )]
 pub(super) struct AggregatorApiDoc;
 
@@ -32,6 +44,7 @@ pub(super) struct AggregatorApiDoc;
     components(schemas(
         Blob,
         BlobId,
+        BlobStoreResponse,
         BlobStoreResult,
         EncodingType,
         EpochSchema,
@@ -39,7 +52,9 @@ pub(super) struct AggregatorApiDoc;
         EventOrObjectId,
         ObjectIdSchema,
         RegisterBlobOp,
+        SignedStoreReceipt,
         Status,
+        StoreReceipt,
         StorageResource,
         SuiAddressSchema,
         Binary,
@@ -50,10 +65,17 @@ pub(super) struct PublisherApiDoc;
 #[derive(OpenApi)]
 #[openapi(
     info(title = "Walrus Daemon"),
-    paths(routes::get_blob, routes::put_blob, routes::get_blob_by_object_id),
+    paths(
+        routes::get_blob,
+        routes::put_blob,
+        routes::get_blob_by_object_id,
+        routes::batch_get_blobs
+    ),
     components(schemas(
+        routes::BatchGetRequest,
         Blob,
         BlobId,
+        BlobStoreResponse,
         BlobStoreResult,
         EncodingType,
         EpochSchema,
@@ -61,7 +83,9 @@ pub(super) struct PublisherApiDoc;
         EventOrObjectId,
         ObjectIdSchema,
         RegisterBlobOp,
+        SignedStoreReceipt,
         Status,
+        StoreReceipt,
         StorageResource,
         SuiAddressSchema,
         Binary,
@@ -69,6 +93,24 @@ pub(super) struct PublisherApiDoc;
 )]
 pub(super) struct DaemonApiDoc;
 
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Walrus Unsigned Publisher"),
+    paths(routes::put_unsigned_blob, routes::post_unsigned_certification),
+    components(schemas(
+        BlobId,
+        EncodingType,
+        ObjectIdSchema,
+        PreparedCertification,
+        PreparedUpload,
+        Status,
+        SuiAddressSchema,
+        TransactionToSign,
+        Binary,
+    ))
+)]
+pub(super) struct UnsignedPublisherApiDoc;
+
 #[cfg(test)]
 mod tests {
     use utoipa::OpenApi as _;
@@ -0,0 +1,243 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-subject quotas for authenticated uploads to the publisher.
+//!
+//! Each JWT subject (the `sub` claim) is limited in the size of the blobs it can store, the number
+//! of epochs it can store them for, and the total number of bytes it can store per UTC day. The
+//! daily usage is tracked in memory, and therefore resets when the publisher restarts.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use walrus_core::EpochCount;
+
+use super::auth::PublisherAuthError;
+use crate::common::utils;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The limits on the uploads of a single JWT subject.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubjectQuota {
+    /// The maximum number of bytes the subject can store per UTC day.
+    pub max_bytes_per_day: Option<u64>,
+    /// The maximum size of a single blob, in bytes.
+    pub max_blob_size: Option<u64>,
+    /// The maximum number of epochs for which the subject can store a blob.
+    pub max_epochs: Option<EpochCount>,
+}
+
+impl SubjectQuota {
+    fn limits_size(&self) -> bool {
+        self.max_bytes_per_day.is_some() || self.max_blob_size.is_some()
+    }
+}
+
+/// The quotas enforced on authenticated uploads, keyed by the JWT subject.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaConfig {
+    /// The quota of all subjects that are not listed in `subjects`.
+    pub default_quota: SubjectQuota,
+    /// The quotas of individual subjects, which replace the default quota.
+    pub subjects: HashMap<String, SubjectQuota>,
+}
+
+impl QuotaConfig {
+    /// Loads the quota configuration from a YAML file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        utils::load_from_yaml(path)
+    }
+
+    /// Returns the quota of the given subject.
+    pub fn quota_for(&self, subject: &str) -> &SubjectQuota {
+        self.subjects.get(subject).unwrap_or(&self.default_quota)
+    }
+}
+
+/// Bytes of the daily quota of a subject that are reserved for an upload in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QuotaReservation {
+    subject: String,
+    day: i64,
+    bytes: u64,
+}
+
+/// The bytes stored by each subject on the current day.
+#[derive(Debug, Default)]
+struct DailyUsage {
+    day: i64,
+    bytes_per_subject: HashMap<String, u64>,
+}
+
+impl DailyUsage {
+    /// Returns the usage of the subject on the given day, resetting all usages on a new day.
+    fn bytes_mut(&mut self, day: i64, subject: &str) -> &mut u64 {
+        if day != self.day {
+            self.day = day;
+            self.bytes_per_subject.clear();
+        }
+        self.bytes_per_subject
+            .entry(subject.to_owned())
+            .or_default()
+    }
+}
+
+/// Enforces the [`QuotaConfig`] and tracks the daily usage of each subject.
+#[derive(Debug)]
+pub(crate) struct QuotaTracker {
+    config: QuotaConfig,
+    usage: Mutex<DailyUsage>,
+}
+
+impl QuotaTracker {
+    pub fn new(config: QuotaConfig) -> Self {
+        Self {
+            config,
+            usage: Default::default(),
+        }
+    }
+
+    /// Checks the upload against the quota of the subject and reserves its size in the daily
+    /// quota.
+    ///
+    /// If the quota limits the size of uploads, the size of the upload must be known in advance.
+    /// The reservation must be [released][Self::release] if the upload fails.
+    pub fn reserve(
+        &self,
+        subject: &str,
+        epochs: EpochCount,
+        size: Option<u64>,
+    ) -> Result<Option<QuotaReservation>, PublisherAuthError> {
+        self.reserve_on_day(current_day(), subject, epochs, size)
+    }
+
+    fn reserve_on_day(
+        &self,
+        day: i64,
+        subject: &str,
+        epochs: EpochCount,
+        size: Option<u64>,
+    ) -> Result<Option<QuotaReservation>, PublisherAuthError> {
+        let quota = self.config.quota_for(subject);
+
+        if let Some(max_epochs) = quota.max_epochs {
+            if epochs > max_epochs {
+                return Err(PublisherAuthError::EpochsQuotaExceeded { epochs, max_epochs });
+            }
+        }
+        if !quota.limits_size() {
+            return Ok(None);
+        }
+        let size = size.ok_or(PublisherAuthError::ContentLengthRequired)?;
+        if let Some(max_blob_size) = quota.max_blob_size {
+            if size > max_blob_size {
+                return Err(PublisherAuthError::BlobSizeQuotaExceeded {
+                    size,
+                    max_blob_size,
+                });
+            }
+        }
+        let Some(max_bytes_per_day) = quota.max_bytes_per_day else {
+            return Ok(None);
+        };
+
+        let mut usage = self.usage.lock().unwrap_or_else(PoisonError::into_inner);
+        let used = usage.bytes_mut(day, subject);
+        let remaining = max_bytes_per_day.saturating_sub(*used);
+        if size > remaining {
+            tracing::debug!(subject, size, remaining, "daily quota exceeded");
+            return Err(PublisherAuthError::DailyQuotaExceeded { size, remaining });
+        }
+        *used += size;
+
+        Ok(Some(QuotaReservation {
+            subject: subject.to_owned(),
+            day,
+            bytes: size,
+        }))
+    }
+
+    /// Returns the reserved bytes to the daily quota of the subject.
+    pub fn release(&self, reservation: QuotaReservation) {
+        let mut usage = self.usage.lock().unwrap_or_else(PoisonError::into_inner);
+        // Reservations of previous days are void, as the usage has been reset.
+        if usage.day == reservation.day {
+            let used = usage.bytes_mut(reservation.day, &reservation.subject);
+            *used = used.saturating_sub(reservation.bytes);
+        }
+    }
+}
+
+fn current_day() -> i64 {
+    Utc::now().timestamp().div_euclid(SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 20_000;
+
+    fn tracker() -> QuotaTracker {
+        QuotaTracker::new(QuotaConfig {
+            default_quota: SubjectQuota {
+                max_bytes_per_day: Some(100),
+                max_blob_size: Some(60),
+                max_epochs: Some(5),
+            },
+            subjects: HashMap::from([("unlimited".to_owned(), SubjectQuota::default())]),
+        })
+    }
+
+    #[test]
+    fn enforces_blob_size_and_epochs() {
+        let tracker = tracker();
+        assert!(matches!(
+            tracker.reserve_on_day(DAY, "alice", 6, Some(10)),
+            Err(PublisherAuthError::EpochsQuotaExceeded { .. })
+        ));
+        assert!(matches!(
+            tracker.reserve_on_day(DAY, "alice", 1, Some(61)),
+            Err(PublisherAuthError::BlobSizeQuotaExceeded { .. })
+        ));
+        assert!(matches!(
+            tracker.reserve_on_day(DAY, "alice", 1, None),
+            Err(PublisherAuthError::ContentLengthRequired)
+        ));
+        assert_eq!(
+            tracker.reserve_on_day(DAY, "unlimited", 100, None).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn enforces_daily_quota_per_subject() {
+        let tracker = tracker();
+        let first = tracker
+            .reserve_on_day(DAY, "alice", 1, Some(60))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            tracker.reserve_on_day(DAY, "alice", 1, Some(50)),
+            Err(PublisherAuthError::DailyQuotaExceeded { remaining: 40, .. })
+        ));
+        tracker.reserve_on_day(DAY, "bob", 1, Some(50)).unwrap();
+
+        // Released bytes can be used again.
+        tracker.release(first);
+        tracker.reserve_on_day(DAY, "alice", 1, Some(50)).unwrap();
+
+        // The usage is reset on the next day.
+        tracker
+            .reserve_on_day(DAY + 1, "alice", 1, Some(60))
+            .unwrap();
+    }
+}
@@ -1,55 +1,71 @@
 // Copyright (c) Walrus Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, str::FromStr, sync::Arc};
+use std::{convert::Infallible, sync::Arc};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as _};
 use axum::{
-    body::Bytes,
+    body::{Body, Bytes},
     extract::{Path, Query, State},
     http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
-    response::{IntoResponse, Response},
+    response::{IntoResponse, Redirect, Response},
     Json,
 };
 use axum_extra::{
     headers::{authorization::Bearer, Authorization},
     TypedHeader,
 };
+use chrono::{DateTime, Utc};
+use futures::{future, stream, StreamExt as _, TryStreamExt as _};
 use jsonwebtoken::{DecodingKey, Validation};
-use reqwest::header::{
-    ACCESS_CONTROL_ALLOW_HEADERS,
-    ACCESS_CONTROL_ALLOW_METHODS,
-    ACCESS_CONTROL_ALLOW_ORIGIN,
-    ACCESS_CONTROL_MAX_AGE,
-    CACHE_CONTROL,
-    CONTENT_TYPE,
-    ETAG,
-    X_CONTENT_TYPE_OPTIONS,
-};
+use reqwest::header::{CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS};
 use serde::Deserialize;
 use sui_types::base_types::{ObjectID, SuiAddress};
-use tracing::Level;
-use utoipa::IntoParams;
+use tracing::{Instrument as _, Level};
+use utoipa::{IntoParams, ToSchema};
 use walrus_core::{BlobId, EncodingType, EpochCount};
 use walrus_proc_macros::RestApiError;
-use walrus_sdk::api::errors::DAEMON_ERROR_DOMAIN as ERROR_DOMAIN;
+use walrus_sdk::{
+    api::{errors::DAEMON_ERROR_DOMAIN as ERROR_DOMAIN, BlobStatus},
+    request_id,
+};
 use walrus_sui::{
     client::BlobPersistence,
-    types::move_structs::{BlobAttribute, BlobWithAttribute},
+    types::move_structs::BlobWithAttribute,
     ObjectIdSchema,
     SuiAddressSchema,
 };
 
-use super::{WalrusReadClient, WalrusWriteClient};
+use super::{
+    attribute_index::{AttributeIndex, IndexedBlob},
+    batch::{BatchGetLimits, MultipartEncoder},
+    conditional,
+    headers::AttributeHeaderPolicy,
+    jobs::{JobParams, JobQueue},
+    unsigned::{
+        PreparedCertification,
+        PreparedUpload,
+        UnsignedPublisher,
+        UnsignedPublisherError,
+        UploadParams,
+    },
+    BlobStream,
+    WalrusReadClient,
+    WalrusWriteClient,
+};
 use crate::{
     client::{
         daemon::{
             auth::{Claim, PublisherAuthError},
             PostStoreAction,
         },
+        receipt::{self, ReceiptSigner},
+        responses::BlobStoreResponse,
+        site::{SiteManifest, SiteManifestCache},
         BlobStoreResult,
         ClientError,
         ClientErrorKind,
+        MetadataReplication,
         StoreWhen,
     },
     common::api::{Binary, BlobIdString, RestApiError},
@@ -61,14 +77,45 @@ pub const STATUS_ENDPOINT: &str = "/status";
 pub const API_DOCS: &str = "/v1/api";
 /// The path to get the blob with the given blob ID.
 pub const BLOB_GET_ENDPOINT: &str = "/v1/blobs/{blob_id}";
+/// The path to get information about the blob with the given blob ID without reading it.
+pub const BLOB_INFO_ENDPOINT: &str = "/v1/blobs/{blob_id}/info";
+/// The path to get the certification status of the blob with the given blob ID.
+pub const BLOB_STATUS_ENDPOINT: &str = "/v1/blobs/{blob_id}/status";
+/// The path to get multiple blobs in a single request.
+pub const BLOB_BATCH_GET_ENDPOINT: &str = "/v1/blobs:batchGet";
 /// The path to get the blob and its attribute with the given object ID.
 pub const BLOB_OBJECT_GET_ENDPOINT: &str = "/v1/blobs/by-object-id/{blob_object_id}";
+/// The path to get the index file of the directory stored with the given manifest blob ID.
+pub const SITE_ROOT_ENDPOINT: &str = "/v1/sites/{manifest_blob_id}/";
+/// The path to get a file from the directory stored with the given manifest blob ID.
+pub const SITE_FILE_ENDPOINT: &str = "/v1/sites/{manifest_blob_id}/{*path}";
+/// The path to the directory stored with the given manifest blob ID without a trailing slash.
+pub const SITE_REDIRECT_ENDPOINT: &str = "/v1/sites/{manifest_blob_id}";
 /// The path to store a blob.
 pub const BLOB_PUT_ENDPOINT: &str = "/v1/blobs";
+/// The path to query blobs by their attributes.
+pub const BLOB_QUERY_ENDPOINT: &str = "/v1/blobs";
+/// The path to submit a job storing a blob.
+pub const JOB_PUT_ENDPOINT: &str = "/v1/jobs";
+/// The path to get the status of the job with the given job ID.
+pub const JOB_GET_ENDPOINT: &str = "/v1/jobs/{job_id}";
+/// The path to upload a blob to the unsigned publisher and get the transaction registering it.
+pub const UNSIGNED_BLOB_PUT_ENDPOINT: &str = "/v1/unsigned/blobs";
+/// The path to get the transaction certifying the registered blob object with the given ID.
+pub const UNSIGNED_BLOB_CERTIFY_ENDPOINT: &str = "/v1/unsigned/blobs/{blob_object_id}/certify";
+
+/// The header containing the encoding type of a blob in responses to `HEAD` requests.
+const ENCODING_TYPE_HEADER: HeaderName = HeaderName::from_static("x-walrus-encoding-type");
+/// The header containing the epoch in which a blob was certified in responses to `HEAD` requests.
+const CERTIFIED_EPOCH_HEADER: HeaderName = HeaderName::from_static("x-walrus-certified-epoch");
+/// The header containing the end epoch of a permanent blob in responses to `HEAD` requests.
+const END_EPOCH_HEADER: HeaderName = HeaderName::from_static("x-walrus-end-epoch");
 
 /// Retrieve a Walrus blob.
 ///
 /// Reconstructs the blob identified by the provided blob ID from Walrus and return it binary data.
+/// Conditional requests with an `If-None-Match` or `If-Modified-Since` header matching the blob's
+/// `ETag` or `Last-Modified` time are answered without reconstructing the blob.
 #[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
 #[utoipa::path(
     get,
@@ -76,40 +123,39 @@ pub const BLOB_PUT_ENDPOINT: &str = "/v1/blobs";
     params(("blob_id" = BlobId,)),
     responses(
         (status = 200, description = "The blob was reconstructed successfully", body = [u8]),
+        (status = 304, description = "The blob was not modified since it was cached"),
         GetBlobError,
     ),
 )]
-pub(super) async fn get_blob<T: WalrusReadClient>(
+pub(super) async fn get_blob<T: WalrusReadClient + Send + Sync + 'static>(
     request_headers: HeaderMap,
     State(client): State<Arc<T>>,
     Path(BlobIdString(blob_id)): Path<BlobIdString>,
 ) -> Response {
+    let last_modified =
+        match check_blob_preconditions(client.as_ref(), &blob_id, &request_headers).await {
+            Ok(last_modified) => last_modified,
+            Err(response) => return response,
+        };
     tracing::debug!("starting to read blob");
-    match client.read_blob(&blob_id).await {
-        Ok(blob) => {
-            tracing::debug!("successfully retrieved blob");
-            let mut response = (StatusCode::OK, blob).into_response();
+    match client.read_blob_stream(blob_id).await {
+        Ok(BlobStream { length, data }) => {
+            tracing::debug!("successfully started streaming blob");
+            // The remaining data is retrieved while the beginning of the blob is sent. If it cannot
+            // be retrieved, or if the blob turns out to be inconsistent, the response is aborted
+            // before all data has been sent. Clients detect this through the `Content-Length`.
+            let data = data.inspect_err(|error| {
+                tracing::error!(?error, "error streaming blob");
+            });
+            let mut response = (
+                StatusCode::OK,
+                [(CONTENT_TYPE, "application/octet-stream")],
+                Body::from_stream(data),
+            )
+                .into_response();
             let headers = response.headers_mut();
-            // Allow requests from any origin, s.t. content can be loaded in browsers.
-            headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
-            // Prevent the browser from trying to guess the MIME type to avoid dangerous inferences.
-            headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
-            // Insert headers that help caches distribute Walrus blobs.
-            //
-            // Cache for 1 day, and allow refreshig on the client side. Refreshes use the ETag to
-            // check if the content has changed. This allows invalidated blobs to be removed from
-            // caches. `stale-while-revalidate` allows stale content to be served for 1 hour while
-            // the browser tries to validate it (async revalidation).
-            headers.insert(
-                CACHE_CONTROL,
-                HeaderValue::from_static("public, max-age=86400, stale-while-revalidate=3600"),
-            );
-            // The `ETag` is the blob ID itself.
-            headers.insert(
-                ETAG,
-                HeaderValue::from_str(&blob_id.to_string())
-                    .expect("the blob ID string only contains visible ASCII characters"),
-            );
+            insert_blob_headers(headers, &blob_id, last_modified);
+            headers.insert(CONTENT_LENGTH, HeaderValue::from(length));
             // Mirror the content type.
             if let Some(content_type) = request_headers.get(CONTENT_TYPE) {
                 tracing::debug!(?content_type, "mirroring the request's content type");
@@ -133,20 +179,290 @@ pub(super) async fn get_blob<T: WalrusReadClient>(
     }
 }
 
-fn populate_response_headers(
+/// Evaluates the preconditions of the request, if there are any.
+///
+/// Only for conditional requests, the certification status of the blob is looked up; requests
+/// without preconditions are served without the additional lookup and without a `Last-Modified`
+/// time.
+///
+/// Returns the time at which the blob was last modified, if known, if the blob has to be served.
+/// Otherwise, returns the response to the request: `304 Not Modified` if the client's copy of the
+/// blob is still valid, or an error if the blob is not certified.
+async fn check_blob_preconditions<T: WalrusReadClient>(
+    client: &T,
+    blob_id: &BlobId,
+    request_headers: &HeaderMap,
+) -> Result<Option<DateTime<Utc>>, Response> {
+    if !conditional::has_preconditions(request_headers) {
+        return Ok(None);
+    }
+    let status = client
+        .get_blob_status(blob_id)
+        .await
+        .map_err(|error| blob_info_error_response(error.into()))?;
+    if !status.certified {
+        return Err(blob_info_error_response(GetBlobError::BlobNotFound));
+    }
+
+    let last_modified = status.certified_epoch_start;
+    if conditional::is_not_modified(request_headers, blob_id, last_modified) {
+        tracing::debug!("the cached blob is still valid");
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        insert_blob_headers(response.headers_mut(), blob_id, last_modified);
+        return Err(response);
+    }
+    Ok(last_modified)
+}
+
+/// Inserts the headers common to all responses containing a blob.
+fn insert_blob_headers(
     headers: &mut HeaderMap,
-    attribute: &BlobAttribute,
-    allowed_headers: &HashSet<String>,
+    blob_id: &BlobId,
+    last_modified: Option<DateTime<Utc>>,
 ) {
-    for (key, value) in attribute.iter() {
-        if allowed_headers.contains(key) {
-            if let (Ok(header_name), Ok(header_value)) =
-                (HeaderName::from_str(key), HeaderValue::from_str(value))
-            {
-                headers.insert(header_name, header_value);
+    // Prevent the browser from trying to guess the MIME type to avoid dangerous inferences.
+    headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    // Insert headers that help caches distribute Walrus blobs.
+    //
+    // Cache for 1 day, and allow refreshig on the client side. Refreshes use the ETag or the
+    // Last-Modified time to check if the content has changed. This allows invalidated blobs to be
+    // removed from caches.
+    // `stale-while-revalidate` allows stale content to be served for 1 hour while the browser
+    // tries to validate it (async revalidation).
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=86400, stale-while-revalidate=3600"),
+    );
+    conditional::insert_validators(headers, blob_id, last_modified);
+}
+
+/// Check a Walrus blob without reading it.
+///
+/// Returns the headers of the response to reading the blob, including its `Content-Length`,
+/// without reconstructing the blob. The encoding type, the epoch in which the blob was certified,
+/// and, for permanent blobs, the end epoch are returned in `X-Walrus-*` headers.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+pub(super) async fn head_blob<T: WalrusReadClient + Send + Sync + 'static>(
+    request_headers: HeaderMap,
+    State(client): State<Arc<T>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Response {
+    let last_modified =
+        match check_blob_preconditions(client.as_ref(), &blob_id, &request_headers).await {
+            Ok(last_modified) => last_modified,
+            Err(response) => return response,
+        };
+    match client.blob_read_info(&blob_id).await {
+        Ok(info) => {
+            let mut response =
+                (StatusCode::OK, [(CONTENT_TYPE, "application/octet-stream")]).into_response();
+            let headers = response.headers_mut();
+            insert_blob_headers(headers, &blob_id, last_modified);
+            headers.insert(CONTENT_LENGTH, HeaderValue::from(info.unencoded_length));
+            headers.insert(
+                ENCODING_TYPE_HEADER,
+                HeaderValue::from_str(&format!("{:?}", info.encoding_type))
+                    .expect("the encoding type only contains visible ASCII characters"),
+            );
+            headers.insert(
+                CERTIFIED_EPOCH_HEADER,
+                HeaderValue::from(info.certified_epoch),
+            );
+            if let BlobStatus::Permanent { end_epoch, .. } = info.status {
+                headers.insert(END_EPOCH_HEADER, HeaderValue::from(end_epoch));
             }
+            response
+        }
+        Err(error) => blob_info_error_response(error.into()),
+    }
+}
+
+/// Get information about a Walrus blob.
+///
+/// Returns the size, encoding type, epoch of certification, and status of the blob without
+/// reconstructing it, such that clients can check what reading the blob would cost.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+pub(super) async fn get_blob_info<T: WalrusReadClient + Send + Sync + 'static>(
+    State(client): State<Arc<T>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Response {
+    match client.blob_read_info(&blob_id).await {
+        Ok(info) => (StatusCode::OK, Json(info)).into_response(),
+        Err(error) => blob_info_error_response(error.into()),
+    }
+}
+
+/// Get the certification status of a Walrus blob.
+///
+/// Returns whether the blob is certified, the epoch of certification, its expiry, and whether it
+/// was invalidated, without retrieving the blob or its metadata. Unlike the blob info, the status
+/// is also returned for blobs that are not certified or do not exist.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_id))]
+pub(super) async fn get_blob_status<T: WalrusReadClient + Send + Sync + 'static>(
+    State(client): State<Arc<T>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Response {
+    match client.get_blob_status(&blob_id).await {
+        Ok(status) => (StatusCode::OK, Json(status)).into_response(),
+        Err(error) => blob_info_error_response(error.into()),
+    }
+}
+
+/// The body of a request to read multiple blobs.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BatchGetRequest {
+    /// The IDs of the blobs to read.
+    #[schema(value_type = Vec<BlobId>)]
+    blob_ids: Vec<BlobIdString>,
+}
+
+/// Retrieve multiple Walrus blobs.
+///
+/// Reconstructs the blobs with the provided blob IDs and returns them as the parts of a
+/// `multipart/mixed` response, in the order in which they were requested. Each part contains the
+/// blob ID in its `X-Walrus-Blob-Id` header. If a blob cannot be read, its part contains the JSON
+/// error status instead, and the part's `X-Walrus-Status` header contains the HTTP status code with
+/// which reading the blob individually would have failed; the other blobs are still returned.
+///
+/// The sizes of the blobs are looked up before any blob is read, and the request is rejected if the
+/// total size of the blobs exceeds the aggregator's limit. The blobs are read with a bounded number
+/// of concurrent reads, which share the aggregator's connections to the storage nodes.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(n_blobs = request.blob_ids.len()))]
+#[utoipa::path(
+    post,
+    path = BLOB_BATCH_GET_ENDPOINT,
+    request_body = BatchGetRequest,
+    responses(
+        (
+            status = 200,
+            description = "The blobs were read; each part contains a blob or the error status of \
+                        reading it",
+            content_type = "multipart/mixed",
+            body = [u8]
+        ),
+        BatchGetError,
+    ),
+)]
+pub(super) async fn batch_get_blobs<T: WalrusReadClient + Send + Sync + 'static>(
+    State((client, limits)): State<(Arc<T>, BatchGetLimits)>,
+    Json(request): Json<BatchGetRequest>,
+) -> Response {
+    if request.blob_ids.is_empty() {
+        return BatchGetError::NoBlobIds.to_response();
+    }
+    if request.blob_ids.len() > limits.max_blobs {
+        return BatchGetError::TooManyBlobIds(limits.max_blobs).to_response();
+    }
+
+    // The sizes are looked up before reading any blob, such that the blobs of an oversized batch
+    // are never held in memory.
+    let read_infos: Vec<_> = stream::iter(request.blob_ids.iter().map(|BlobIdString(blob_id)| {
+        let client = client.clone();
+        let blob_id = *blob_id;
+        async move { client.blob_read_info(&blob_id).await }
+    }))
+    .buffered(limits.max_concurrent_reads.max(1))
+    .collect()
+    .await;
+    let total_size: u64 = read_infos
+        .iter()
+        .filter_map(|info| info.as_ref().ok())
+        .map(|info| info.unencoded_length)
+        .sum();
+    if total_size > limits.max_total_size {
+        return BatchGetError::TooLarge(limits.max_total_size).to_response();
+    }
+
+    let encoder = MultipartEncoder::new();
+    // The reads are created here, such that they run in the scope of the request, even though they
+    // are only polled while the response body is sent.
+    let reads: Vec<_> = request
+        .blob_ids
+        .into_iter()
+        .zip(read_infos)
+        .map(|(BlobIdString(blob_id), read_info)| {
+            let client = client.clone();
+            let encoder = encoder.clone();
+            let read = async move {
+                match read_info {
+                    Ok(_) => read_blob_part(client, blob_id, encoder).await,
+                    Err(error) => error_part(&encoder, &blob_id, error),
+                }
+            };
+            request_id::in_current_request(read).in_current_span()
+        })
+        .collect();
+    let parts = stream::iter(reads)
+        .buffered(limits.max_concurrent_reads.max(1))
+        .chain(stream::once(future::ready(encoder.closing_delimiter())))
+        .map(Ok::<_, Infallible>);
+
+    (
+        StatusCode::OK,
+        [(CONTENT_TYPE, encoder.content_type())],
+        Body::from_stream(parts),
+    )
+        .into_response()
+}
+
+/// Reads the blob and encodes it, or the error status of reading it, as a part of a batch response.
+async fn read_blob_part<T: WalrusReadClient>(
+    client: Arc<T>,
+    blob_id: BlobId,
+    encoder: MultipartEncoder,
+) -> Bytes {
+    match client.read_blob(&blob_id).await {
+        Ok(blob) => encoder.blob_part(&blob_id, &blob),
+        Err(error) => error_part(&encoder, &blob_id, error),
+    }
+}
+
+/// Encodes the error status of reading the blob as a part of a batch response.
+fn error_part(encoder: &MultipartEncoder, blob_id: &BlobId, error: ClientError) -> Bytes {
+    let error = GetBlobError::from(error);
+    match &error {
+        GetBlobError::BlobNotFound => {
+            tracing::debug!(%blob_id, "the requested blob ID does not exist")
+        }
+        GetBlobError::Internal(error) => {
+            tracing::error!(?error, %blob_id, "error retrieving blob in batch")
+        }
+        _ => (),
+    }
+    let status =
+        serde_json::to_vec(&error.to_status()).expect("the error status can be serialized");
+    encoder.error_part(blob_id, error.status_code().http_code().as_u16(), &status)
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub(crate) enum BatchGetError {
+    /// The request does not contain any blob IDs.
+    #[error("the request must contain at least one blob ID")]
+    #[rest_api_error(reason = "NO_BLOB_IDS", status = ApiStatusCode::InvalidArgument)]
+    NoBlobIds,
+
+    /// The request contains more blob IDs than allowed.
+    #[error("the request contains more than the maximum of {0} blob IDs")]
+    #[rest_api_error(reason = "TOO_MANY_BLOB_IDS", status = ApiStatusCode::InvalidArgument)]
+    TooManyBlobIds(usize),
+
+    /// The total size of the requested blobs exceeds the limit.
+    #[error("the total size of the requested blobs exceeds the maximum of {0} bytes")]
+    #[rest_api_error(reason = "BATCH_TOO_LARGE", status = ApiStatusCode::InvalidArgument)]
+    TooLarge(u64),
+}
+
+fn blob_info_error_response(error: GetBlobError) -> Response {
+    match &error {
+        GetBlobError::BlobNotFound => {
+            tracing::debug!("the requested blob ID does not exist or is not certified")
         }
+        GetBlobError::Internal(error) => tracing::error!(?error, "error retrieving blob info"),
+        _ => (),
     }
+    error.to_response()
 }
 
 /// Retrieve a Walrus blob with its associated attribute.
@@ -167,11 +483,12 @@ fn populate_response_headers(
                         in the allowed_headers configuration will be included in the response.",
             body = [u8]
         ),
+        (status = 304, description = "The blob was not modified since it was cached"),
         GetBlobError,
     ),
 )]
-pub(super) async fn get_blob_by_object_id<T: WalrusReadClient>(
-    State((client, allowed_headers)): State<(Arc<T>, Arc<HashSet<String>>)>,
+pub(super) async fn get_blob_by_object_id<T: WalrusReadClient + Send + Sync + 'static>(
+    State((client, attribute_headers)): State<(Arc<T>, Arc<AttributeHeaderPolicy>)>,
     request_headers: HeaderMap,
     Path(blob_object_id): Path<ObjectID>,
 ) -> Response {
@@ -189,7 +506,7 @@ pub(super) async fn get_blob_by_object_id<T: WalrusReadClient>(
             // If the response was successful, add our additional metadata headers
             if response.status() == StatusCode::OK {
                 if let Some(attribute) = attribute {
-                    populate_response_headers(response.headers_mut(), &attribute, &allowed_headers);
+                    attribute_headers.apply(response.headers_mut(), &attribute);
                 }
             }
 
@@ -216,6 +533,107 @@ pub(super) async fn get_blob_by_object_id<T: WalrusReadClient>(
     }
 }
 
+/// The path parameters of a file in a stored directory.
+#[derive(Debug, Deserialize)]
+pub(super) struct SitePath {
+    manifest_blob_id: BlobIdString,
+    #[serde(default)]
+    path: String,
+}
+
+/// Retrieve a file from a directory stored on Walrus.
+///
+/// Reads the manifest blob of the directory, resolves the path to the blob ID of the file, and
+/// returns the file like [`get_blob`]. Paths of directories resolve to their `index.html` file. The
+/// content type recorded in the manifest is returned if it is allowed by the aggregator.
+///
+/// Parsed manifests are kept in memory, such that requests for further files of the same directory
+/// do not read the manifest blob again.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%manifest_blob_id, %path))]
+pub(super) async fn get_site_file<T: WalrusReadClient + Send + Sync + 'static>(
+    State((client, attribute_headers, manifest_cache)): State<(
+        Arc<T>,
+        Arc<AttributeHeaderPolicy>,
+        SiteManifestCache,
+    )>,
+    request_headers: HeaderMap,
+    Path(SitePath {
+        manifest_blob_id: BlobIdString(manifest_blob_id),
+        path,
+    }): Path<SitePath>,
+) -> Response {
+    let manifest = match manifest_cache
+        .try_get_with(manifest_blob_id, read_manifest(&client, manifest_blob_id))
+        .await
+    {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            if let GetSiteError::Manifest(GetBlobError::Internal(error)) = error.as_ref() {
+                tracing::error!(?error, "error retrieving site manifest");
+            }
+            return error.to_response();
+        }
+    };
+    let Some(entry) = manifest.resolve(&path) else {
+        tracing::debug!("the requested path does not exist in the site manifest");
+        return GetSiteError::PathNotFound.to_response();
+    };
+
+    let mut response = get_blob(
+        request_headers,
+        State(client),
+        Path(BlobIdString(entry.blob_id)),
+    )
+    .await;
+    if response.status() == StatusCode::OK {
+        attribute_headers.apply_content_type(response.headers_mut(), &entry.content_type);
+    }
+    response
+}
+
+/// Reads and parses the manifest blob of a stored directory.
+async fn read_manifest<T: WalrusReadClient>(
+    client: &T,
+    manifest_blob_id: BlobId,
+) -> Result<Arc<SiteManifest>, GetSiteError> {
+    let manifest = client
+        .read_blob(&manifest_blob_id)
+        .await
+        .map_err(GetBlobError::from)?;
+    SiteManifest::from_bytes(&manifest)
+        .map(Arc::new)
+        .map_err(|_| GetSiteError::InvalidManifest)
+}
+
+/// Redirect to the index of a directory stored on Walrus.
+///
+/// Appends a trailing slash to the path, such that relative links in the index file resolve within
+/// the directory.
+pub(super) async fn redirect_to_site_root(
+    Path(BlobIdString(manifest_blob_id)): Path<BlobIdString>,
+) -> Redirect {
+    Redirect::permanent(&format!("/v1/sites/{manifest_blob_id}/"))
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub(crate) enum GetSiteError {
+    /// The blob is not a valid site manifest.
+    #[error("the requested blob is not a valid site manifest")]
+    #[rest_api_error(reason = "INVALID_MANIFEST", status = ApiStatusCode::FailedPrecondition)]
+    InvalidManifest,
+
+    /// The path does not exist in the site manifest.
+    #[error("the requested path does not exist in the site")]
+    #[rest_api_error(reason = "PATH_NOT_FOUND", status = ApiStatusCode::NotFound)]
+    PathNotFound,
+
+    /// The manifest blob could not be read.
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Manifest(#[from] GetBlobError),
+}
+
 #[derive(Debug, thiserror::Error, RestApiError)]
 #[rest_api_error(domain = ERROR_DOMAIN)]
 pub(crate) enum GetBlobError {
@@ -249,7 +667,8 @@ impl From<ClientError> for GetBlobError {
 /// Store a blob on Walrus.
 ///
 /// Store a (potentially deletable) blob on Walrus for 1 or more epochs. The associated on-Sui
-/// object can be sent to a specified Sui address.
+/// object can be sent to a specified Sui address. The response includes a receipt of the store
+/// operation, which is signed if the publisher is configured with a receipt signing key.
 #[tracing::instrument(level = Level::ERROR, skip_all, fields(%epochs))]
 #[utoipa::path(
     put,
@@ -260,19 +679,21 @@ impl From<ClientError> for GetBlobError {
         description = "Binary data of the unencoded blob to be stored."),
     params(PublisherQuery),
     responses(
-        (status = 200, description = "The blob was stored successfully", body = BlobStoreResult),
+        (status = 200, description = "The blob was stored successfully", body = BlobStoreResponse),
         (status = 400, description = "The request is malformed"),
         (status = 413, description = "The blob is too large"),
         StoreBlobError,
     ),
 )]
 pub(super) async fn put_blob<T: WalrusWriteClient>(
-    State(client): State<Arc<T>>,
+    State((client, receipt_signer)): State<(Arc<T>, Option<Arc<ReceiptSigner>>)>,
     Query(PublisherQuery {
         encoding_type,
         epochs,
         deletable,
+        permanent,
         send_object_to,
+        push_metadata_to_all_nodes,
     }): Query<PublisherQuery>,
     bearer_header: Option<TypedHeader<Authorization<Bearer>>>,
     blob: Bytes,
@@ -283,6 +704,9 @@ pub(super) async fn put_blob<T: WalrusWriteClient>(
             return error.into_response();
         }
     }
+    let Some(persistence) = BlobPersistence::from_flags(deletable, permanent) else {
+        return StoreBlobError::ConflictingPersistence.into_response();
+    };
 
     let post_store_action = if let Some(address) = send_object_to {
         PostStoreAction::TransferTo(address)
@@ -291,14 +715,15 @@ pub(super) async fn put_blob<T: WalrusWriteClient>(
     };
     tracing::debug!(?post_store_action, "starting to store received blob");
 
-    let mut response = match client
+    match client
         .write_blob(
             &blob[..],
             encoding_type,
             epochs,
             StoreWhen::NotStoredIgnoreResources,
-            BlobPersistence::from_deletable(deletable),
+            persistence,
             post_store_action,
+            MetadataReplication::from_flag(push_metadata_to_all_nodes),
         )
         .await
     {
@@ -309,19 +734,19 @@ pub(super) async fn put_blob<T: WalrusWriteClient>(
                 ))
                 .into_response()
             } else {
-                (StatusCode::OK, Json(result)).into_response()
+                let receipt = receipt::store_receipt(&result, receipt_signer.as_deref());
+                let response = BlobStoreResponse {
+                    blob_store_result: result,
+                    receipt,
+                };
+                (StatusCode::OK, Json(response)).into_response()
             }
         }
         Err(error) => {
             tracing::error!(?error, "error storing blob");
             StoreBlobError::from(error).into_response()
         }
-    };
-
-    response
-        .headers_mut()
-        .insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
-    response
+    }
 }
 
 /// Checks if the JWT claim has a maximum size and if the blob exceeds it.
@@ -377,6 +802,11 @@ pub(crate) enum StoreBlobError {
     #[rest_api_error(reason = "FORBIDDEN_BLOB", status = ApiStatusCode::UnavailableForLegalReasons)]
     Blocked,
 
+    /// The blob cannot be both deletable and permanent.
+    #[error("the `deletable` and `permanent` query parameters are mutually exclusive")]
+    #[rest_api_error(reason = "CONFLICTING_PERSISTENCE", status = ApiStatusCode::InvalidArgument)]
+    ConflictingPersistence,
+
     #[error(transparent)]
     #[rest_api_error(delegate)]
     Internal(#[from] anyhow::Error),
@@ -392,14 +822,239 @@ impl From<ClientError> for StoreBlobError {
     }
 }
 
-#[tracing::instrument(level = Level::ERROR, skip_all)]
-pub(super) async fn store_blob_options() -> impl IntoResponse {
-    [
-        (ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
-        (ACCESS_CONTROL_ALLOW_METHODS, "PUT, OPTIONS"),
-        (ACCESS_CONTROL_MAX_AGE, "86400"),
-        (ACCESS_CONTROL_ALLOW_HEADERS, "*"),
-    ]
+/// The default maximum number of blobs returned by an attribute query.
+const DEFAULT_QUERY_LIMIT: usize = 100;
+/// The largest accepted maximum number of blobs returned by an attribute query.
+const MAX_QUERY_LIMIT: usize = 1000;
+
+/// The query parameters for querying blobs by their attributes.
+#[derive(Debug, Deserialize)]
+pub(super) struct AttributeQuery {
+    /// The attribute to match, formatted as `key:value`.
+    attr: String,
+    /// The maximum number of blobs to return.
+    limit: Option<usize>,
+}
+
+/// Find blobs by attribute.
+///
+/// Returns the blob objects indexed by the aggregator that have the attribute `key` set to
+/// `value`, where the attribute is given as `attr=key:value`. The results are ordered by object ID
+/// and include the blob ID, end epoch, and all attributes of each blob object.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(attr = %query.attr))]
+pub(super) async fn get_blobs_by_attribute(
+    State(index): State<Arc<AttributeIndex>>,
+    Query(query): Query<AttributeQuery>,
+) -> Result<Json<Vec<IndexedBlob>>, QueryBlobsError> {
+    let Some((key, value)) = query.attr.split_once(':') else {
+        return Err(QueryBlobsError::InvalidAttribute);
+    };
+    if key.is_empty() {
+        return Err(QueryBlobsError::InvalidAttribute);
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_QUERY_LIMIT)
+        .min(MAX_QUERY_LIMIT);
+    let blobs = index
+        .query(key, value, limit)
+        .context("unable to query the attribute index")?;
+    Ok(Json(blobs))
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub(crate) enum QueryBlobsError {
+    /// The attribute in the query is not formatted as `key:value`.
+    #[error("the attribute must be provided as `attr=key:value` with a non-empty key")]
+    #[rest_api_error(reason = "INVALID_ATTRIBUTE", status = ApiStatusCode::InvalidArgument)]
+    InvalidAttribute,
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] anyhow::Error),
+}
+
+/// Submit a job to store a blob on Walrus.
+///
+/// The blob is persisted by the publisher and stored on Walrus in the background, with the same
+/// parameters as for storing a blob directly. The response contains the ID of the job, which can be
+/// used to poll its status. Jobs are resumed if the publisher restarts.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%epochs))]
+pub(super) async fn put_job<T: WalrusWriteClient + Send + Sync + 'static>(
+    State(queue): State<Arc<JobQueue<T>>>,
+    Query(PublisherQuery {
+        encoding_type,
+        epochs,
+        deletable,
+        permanent,
+        send_object_to,
+        push_metadata_to_all_nodes,
+    }): Query<PublisherQuery>,
+    bearer_header: Option<TypedHeader<Authorization<Bearer>>>,
+    blob: Bytes,
+) -> Response {
+    if let Some(TypedHeader(header)) = bearer_header {
+        if let Err(error) = check_blob_size(header, blob.len()) {
+            return error.into_response();
+        }
+    }
+    let Some(persistence) = BlobPersistence::from_flags(deletable, permanent) else {
+        return JobError::ConflictingPersistence.into_response();
+    };
+
+    let params = JobParams {
+        encoding_type,
+        epochs,
+        deletable: persistence.is_deletable(),
+        send_object_to,
+        push_metadata_to_all_nodes,
+    };
+    match queue.submit(params, &blob).await {
+        Ok(job_id) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({ "jobId": job_id.to_string() })),
+        )
+            .into_response(),
+        Err(error) => {
+            tracing::error!(?error, "error persisting store job");
+            JobError::Internal(error).into_response()
+        }
+    }
+}
+
+/// Get the status of a store job.
+///
+/// Returns the parameters and the current status of the job. Once the job has completed, the
+/// status contains the result of the store operation.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%job_id))]
+pub(super) async fn get_job<T: WalrusWriteClient + Send + Sync + 'static>(
+    State(queue): State<Arc<JobQueue<T>>>,
+    Path(job_id): Path<String>,
+) -> Response {
+    let Ok(id) = job_id.parse() else {
+        return JobError::JobNotFound.into_response();
+    };
+    match queue.get(&id).await {
+        Ok(Some(record)) => (StatusCode::OK, Json(record)).into_response(),
+        Ok(None) => JobError::JobNotFound.into_response(),
+        Err(error) => {
+            tracing::error!(?error, "error reading store job");
+            JobError::Internal(error).into_response()
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub(crate) enum JobError {
+    /// The requested job does not exist.
+    #[error("the requested job ID does not exist, ensure that it was entered correctly")]
+    #[rest_api_error(reason = "JOB_NOT_FOUND", status = ApiStatusCode::NotFound)]
+    JobNotFound,
+
+    /// The blob cannot be both deletable and permanent.
+    #[error("the `deletable` and `permanent` query parameters are mutually exclusive")]
+    #[rest_api_error(reason = "CONFLICTING_PERSISTENCE", status = ApiStatusCode::InvalidArgument)]
+    ConflictingPersistence,
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] anyhow::Error),
+}
+
+/// The query parameters for an upload to the unsigned publisher.
+#[derive(Debug, Deserialize, IntoParams)]
+pub(super) struct UnsignedPublisherQuery {
+    /// The Sui address that signs the transactions and pays for the storage.
+    #[param(value_type = SuiAddressSchema)]
+    sender: SuiAddress,
+    /// The encoding type to use for the blob.
+    #[serde(default)]
+    encoding_type: Option<EncodingType>,
+    /// The number of epochs, ahead of the current one, for which to store the blob.
+    ///
+    /// The default is 1 epoch.
+    #[serde(default = "default_epochs")]
+    epochs: EpochCount,
+    /// If true, the blob is registered as deletable instead of permanent.
+    #[serde(default)]
+    deletable: bool,
+    /// If true, the blob is registered as permanent. This is the default if `deletable` is not
+    /// set, and cannot be combined with `deletable`.
+    #[serde(default)]
+    permanent: bool,
+}
+
+/// Upload a blob and get the transaction registering it.
+///
+/// Encodes the blob and returns the unsigned transaction that purchases storage with the sender's
+/// WAL and registers the blob. After executing the transaction, the sender requests the
+/// transaction certifying the created blob object.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%sender, %epochs))]
+#[utoipa::path(
+    put,
+    path = UNSIGNED_BLOB_PUT_ENDPOINT,
+    request_body(
+        content = Binary,
+        content_type = "application/octet-stream",
+        description = "Binary data of the unencoded blob to be stored."),
+    params(UnsignedPublisherQuery),
+    responses(
+        (status = 200, description = "The blob was encoded", body = PreparedUpload),
+        (status = 400, description = "The request is malformed"),
+        (status = 413, description = "The blob is too large"),
+        UnsignedPublisherError,
+    ),
+)]
+pub(super) async fn put_unsigned_blob(
+    State(publisher): State<Arc<UnsignedPublisher>>,
+    Query(UnsignedPublisherQuery {
+        sender,
+        encoding_type,
+        epochs,
+        deletable,
+        permanent,
+    }): Query<UnsignedPublisherQuery>,
+    blob: Bytes,
+) -> Result<Json<PreparedUpload>, UnsignedPublisherError> {
+    let params = UploadParams {
+        sender,
+        encoding_type,
+        epochs,
+        persistence: BlobPersistence::from_flags(deletable, permanent)
+            .ok_or(UnsignedPublisherError::ConflictingPersistence)?,
+    };
+    publisher
+        .prepare_upload(&blob, params)
+        .await
+        .map(Json)
+        .inspect_err(|error| tracing::warn!(?error, "error preparing unsigned upload"))
+}
+
+/// Get the transaction certifying a registered blob.
+///
+/// Sends the blob uploaded to the unsigned publisher to the storage nodes and returns the
+/// unsigned transaction that certifies the blob object with the given ID.
+#[tracing::instrument(level = Level::ERROR, skip_all, fields(%blob_object_id))]
+#[utoipa::path(
+    post,
+    path = UNSIGNED_BLOB_CERTIFY_ENDPOINT,
+    params(("blob_object_id" = ObjectIdSchema,)),
+    responses(
+        (status = 200, description = "The blob was stored", body = PreparedCertification),
+        UnsignedPublisherError,
+    ),
+)]
+pub(super) async fn post_unsigned_certification(
+    State(publisher): State<Arc<UnsignedPublisher>>,
+    Path(blob_object_id): Path<ObjectID>,
+) -> Result<Json<PreparedCertification>, UnsignedPublisherError> {
+    publisher
+        .prepare_certification(blob_object_id)
+        .await
+        .map(Json)
+        .inspect_err(|error| tracing::warn!(?error, "error preparing unsigned certification"))
 }
 
 #[tracing::instrument(level = Level::ERROR, skip_all)]
@@ -428,13 +1083,153 @@ pub struct PublisherQuery {
     /// If true, the publisher creates a deletable blob instead of a permanent one.
     #[serde(default)]
     pub deletable: bool,
+    /// If true, the publisher creates a permanent blob. This is the default if `deletable` is not
+    /// set, and cannot be combined with `deletable`.
+    #[serde(default)]
+    pub permanent: bool,
     #[serde(default)]
     /// If specified, the publisher will send the Blob object resulting from the store operation to
     /// this Sui address.
     #[param(value_type = Option<SuiAddressSchema>)]
     pub send_object_to: Option<SuiAddress>,
+    /// If true, the publisher pushes the metadata of the blob to all storage nodes of the
+    /// committee, instead of only to the nodes that store its slivers.
+    ///
+    /// This makes the blob's metadata available on the nodes that did not acknowledge their
+    /// slivers in time, at the cost of additional requests during the store operation.
+    #[serde(default)]
+    pub push_metadata_to_all_nodes: bool,
 }
 
 pub(super) fn default_epochs() -> EpochCount {
     1
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use walrus_core::test_utils::random_blob_id;
+
+    use super::*;
+    use crate::client::{
+        daemon::WalrusReadClient,
+        responses::{BlobCertificationStatus, BlobReadInfo},
+        ClientResult,
+    };
+
+    /// A client serving a fixed set of blobs and counting the blobs it reads.
+    #[derive(Debug, Default)]
+    struct FixedBlobsClient {
+        blobs: HashMap<BlobId, Vec<u8>>,
+        n_reads: AtomicUsize,
+    }
+
+    impl FixedBlobsClient {
+        fn blob(&self, blob_id: &BlobId) -> ClientResult<&Vec<u8>> {
+            self.blobs
+                .get(blob_id)
+                .ok_or_else(|| ClientErrorKind::BlobIdDoesNotExist.into())
+        }
+    }
+
+    impl WalrusReadClient for FixedBlobsClient {
+        async fn read_blob(&self, blob_id: &BlobId) -> ClientResult<Vec<u8>> {
+            self.n_reads.fetch_add(1, Ordering::SeqCst);
+            self.blob(blob_id).cloned()
+        }
+
+        async fn get_blob_by_object_id(
+            &self,
+            _blob_object_id: &ObjectID,
+        ) -> ClientResult<BlobWithAttribute> {
+            unimplemented!()
+        }
+
+        async fn blob_read_info(&self, blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+            Ok(BlobReadInfo {
+                blob_id: *blob_id,
+                unencoded_length: self.blob(blob_id)?.len() as u64,
+                encoding_type: EncodingType::RS2,
+                certified_epoch: 1,
+                status: BlobStatus::Nonexistent,
+            })
+        }
+
+        async fn get_blob_status(
+            &self,
+            _blob_id: &BlobId,
+        ) -> ClientResult<BlobCertificationStatus> {
+            unimplemented!()
+        }
+    }
+
+    async fn batch_get(
+        client: &Arc<FixedBlobsClient>,
+        blob_ids: &[BlobId],
+        max_total_size: u64,
+    ) -> (StatusCode, String) {
+        let limits = BatchGetLimits {
+            max_blobs: 10,
+            max_concurrent_reads: 2,
+            max_total_size,
+        };
+        let request = BatchGetRequest {
+            blob_ids: blob_ids.iter().copied().map(BlobIdString).collect(),
+        };
+        let response = batch_get_blobs(State((client.clone(), limits)), Json(request)).await;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("the body can be read");
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    async fn batch_get_rejects_batches_exceeding_the_size_limit_without_reading() {
+        let blob_ids = [random_blob_id(), random_blob_id()];
+        let client = Arc::new(FixedBlobsClient {
+            blobs: blob_ids
+                .iter()
+                .map(|blob_id| (*blob_id, vec![7; 100]))
+                .collect(),
+            ..Default::default()
+        });
+
+        let (status, body) = batch_get(&client, &blob_ids, 199).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("BATCH_TOO_LARGE"));
+        assert_eq!(client.n_reads.load(Ordering::SeqCst), 0);
+
+        let (status, _) = batch_get(&client, &blob_ids, 200).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(client.n_reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn batch_get_returns_blobs_and_errors_in_order() {
+        let blob_id = random_blob_id();
+        let missing_blob_id = random_blob_id();
+        let client = Arc::new(FixedBlobsClient {
+            blobs: HashMap::from([(blob_id, b"walrus".to_vec())]),
+            ..Default::default()
+        });
+
+        let (status, body) = batch_get(&client, &[missing_blob_id, blob_id], 100).await;
+        assert_eq!(status, StatusCode::OK);
+        let missing_part = body
+            .find(&format!("x-walrus-blob-id: {missing_blob_id}"))
+            .expect("the batch contains the part of the missing blob");
+        let blob_part = body
+            .find(&format!("x-walrus-blob-id: {blob_id}"))
+            .expect("the batch contains the part of the blob");
+        assert!(missing_part < blob_part);
+        assert!(body.contains("x-walrus-status: 404"));
+        assert!(body.contains("\r\n\r\nwalrus\r\n"));
+        // Blobs whose size cannot be looked up are not read.
+        assert_eq!(client.n_reads.load(Ordering::SeqCst), 1);
+    }
+}
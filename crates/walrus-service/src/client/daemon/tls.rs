@@ -0,0 +1,518 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! TLS termination for the client daemon.
+//!
+//! The daemon can serve HTTPS with a certificate loaded from PEM files, or with a certificate that
+//! is automatically obtained and renewed from an ACME certificate authority (e.g., Let's Encrypt)
+//! using the HTTP-01 challenge. For the latter, the daemon additionally serves the challenges over
+//! plain HTTP, which the certificate authority expects on port 80.
+//!
+//! The ACME account, the certificate, and its private key are persisted in a directory, such that
+//! a restarted daemon does not request a new certificate. Certificates are renewed once they
+//! reach a configured age, and the renewed certificate is used for new connections without
+//! restarting the daemon.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, bail, Context};
+use axum::{
+    extract::{Path as UrlPath, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use axum_server::tls_rustls::RustlsConfig;
+use instant_acme::{
+    Account,
+    AccountCredentials,
+    AuthorizationStatus,
+    ChallengeType,
+    Identifier,
+    NewAccount,
+    NewOrder,
+    OrderStatus,
+};
+use rcgen::{CertificateParams, DistinguishedName, KeyPair};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampSeconds};
+use tokio::io::AsyncWriteExt as _;
+
+/// The path under which the HTTP-01 challenges are served.
+const ACME_CHALLENGE_ENDPOINT: &str = "/.well-known/acme-challenge/{token}";
+/// The file in which the ACME account credentials are stored.
+const ACCOUNT_FILE: &str = "account.json";
+/// The file in which the certificate and its private key are stored.
+const CERTIFICATE_FILE: &str = "certificate.json";
+/// The interval at which the status of a pending order is polled.
+const ORDER_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// The maximum number of times the status of a pending order is polled.
+const MAX_ORDER_POLLS: u32 = 60;
+/// The delay after which a failed renewal is retried.
+const RENEWAL_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// The TLS configuration of the daemon.
+///
+/// TLS is disabled unless either a certificate and key or at least one ACME domain are provided.
+#[serde_as]
+#[derive(Debug, Clone, clap::Args, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsConfig {
+    /// Path to the PEM-encoded TLS certificate chain with which to serve HTTPS.
+    #[clap(long, requires = "tls_key", conflicts_with = "acme_domains")]
+    pub(crate) tls_certificate: Option<PathBuf>,
+    /// Path to the PEM-encoded private key of the TLS certificate.
+    #[clap(long, requires = "tls_certificate")]
+    pub(crate) tls_key: Option<PathBuf>,
+    /// The domains for which to obtain a TLS certificate through ACME.
+    ///
+    /// The domains must resolve to this host, and the ACME HTTP address must be reachable on port
+    /// 80 from the internet.
+    #[clap(long, num_args = 1.., requires_all = ["acme_cache_dir", "acme_accept_terms_of_service"])]
+    pub(crate) acme_domains: Vec<String>,
+    /// The contact email address registered with the ACME account.
+    #[clap(long)]
+    pub(crate) acme_contact: Option<String>,
+    /// The directory URL of the ACME certificate authority.
+    #[clap(long, default_value_t = default::acme_directory_url())]
+    pub(crate) acme_directory_url: String,
+    /// Directory in which to store the ACME account and the obtained certificate.
+    #[clap(long)]
+    pub(crate) acme_cache_dir: Option<PathBuf>,
+    /// The address on which to serve the ACME HTTP-01 challenges.
+    #[clap(long, default_value_t = default::acme_http_address())]
+    pub(crate) acme_http_address: SocketAddr,
+    /// The age after which the certificate is renewed.
+    #[serde(rename = "acme_renew_after_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds")]
+    #[clap(long, value_parser = humantime::parse_duration, default_value = "60days")]
+    pub(crate) acme_renew_after: Duration,
+    /// Agree to the terms of service of the ACME certificate authority.
+    #[clap(long, action)]
+    pub(crate) acme_accept_terms_of_service: bool,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            tls_certificate: None,
+            tls_key: None,
+            acme_domains: vec![],
+            acme_contact: None,
+            acme_directory_url: default::acme_directory_url(),
+            acme_cache_dir: None,
+            acme_http_address: default::acme_http_address(),
+            acme_renew_after: default::acme_renew_after(),
+            acme_accept_terms_of_service: false,
+        }
+    }
+}
+
+impl TlsConfig {
+    /// Returns the rustls configuration with which to serve HTTPS, or `None` if TLS is disabled.
+    ///
+    /// If ACME is configured, this obtains a certificate if no valid one is cached, and starts
+    /// the tasks that serve the challenges and renew the certificate.
+    pub(crate) async fn load(&self) -> anyhow::Result<Option<RustlsConfig>> {
+        if self.tls_certificate.is_some() || !self.acme_domains.is_empty() {
+            install_crypto_provider();
+        }
+        if let (Some(certificate), Some(key)) = (&self.tls_certificate, &self.tls_key) {
+            tracing::info!(certificate = %certificate.display(), "loading the TLS certificate");
+            return RustlsConfig::from_pem_file(certificate, key)
+                .await
+                .context("failed to load the TLS certificate and key")
+                .map(Some);
+        }
+        if self.acme_domains.is_empty() {
+            return Ok(None);
+        }
+        AcmeManager::start(self).await.map(Some)
+    }
+}
+
+/// A certificate and its private key, as persisted in the ACME cache directory.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredCertificate {
+    domains: Vec<String>,
+    #[serde_as(as = "TimestampSeconds<i64>")]
+    issued_at: SystemTime,
+    certificate_pem: String,
+    private_key_pem: String,
+}
+
+/// The key authorizations of the pending HTTP-01 challenges, by token.
+type PendingChallenges = Arc<Mutex<HashMap<String, String>>>;
+
+/// Obtains and renews certificates from an ACME certificate authority.
+#[derive(Debug)]
+struct AcmeManager {
+    domains: Vec<String>,
+    contact: Option<String>,
+    directory_url: String,
+    cache_dir: PathBuf,
+    renew_after: Duration,
+    challenges: PendingChallenges,
+}
+
+impl AcmeManager {
+    /// Starts serving the challenges, loads or obtains the certificate, and schedules its
+    /// renewal.
+    async fn start(config: &TlsConfig) -> anyhow::Result<RustlsConfig> {
+        if !config.acme_accept_terms_of_service {
+            bail!("the terms of service of the ACME certificate authority must be accepted");
+        }
+        let cache_dir = config
+            .acme_cache_dir
+            .clone()
+            .context("a cache directory is required for ACME")?;
+        tokio::fs::create_dir_all(&cache_dir)
+            .await
+            .with_context(|| format!("unable to create ACME directory {}", cache_dir.display()))?;
+
+        let manager = Arc::new(Self {
+            domains: config.acme_domains.clone(),
+            contact: config.acme_contact.clone(),
+            directory_url: config.acme_directory_url.clone(),
+            cache_dir,
+            renew_after: config.acme_renew_after,
+            challenges: Default::default(),
+        });
+        manager.serve_challenges(config.acme_http_address).await?;
+
+        let certificate = match manager.load_certificate().await {
+            Some(certificate) => certificate,
+            None => manager.obtain_certificate().await?,
+        };
+        let rustls_config = RustlsConfig::from_pem(
+            certificate.certificate_pem.clone().into_bytes(),
+            certificate.private_key_pem.clone().into_bytes(),
+        )
+        .await
+        .context("the ACME certificate is invalid")?;
+
+        tokio::spawn(
+            manager
+                .clone()
+                .renew_periodically(rustls_config.clone(), certificate.issued_at),
+        );
+        Ok(rustls_config)
+    }
+
+    async fn serve_challenges(&self, address: SocketAddr) -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::bind(address)
+            .await
+            .with_context(|| format!("unable to bind the ACME challenge server to {address}"))?;
+        let router = Router::new()
+            .route(ACME_CHALLENGE_ENDPOINT, get(get_challenge))
+            .with_state(self.challenges.clone());
+        tracing::info!(%address, "serving ACME challenges");
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, router).await {
+                tracing::error!(?error, "the ACME challenge server failed");
+            }
+        });
+        Ok(())
+    }
+
+    /// Returns the cached certificate if it is for the configured domains and not yet due for
+    /// renewal.
+    async fn load_certificate(&self) -> Option<StoredCertificate> {
+        let contents = tokio::fs::read(self.cache_dir.join(CERTIFICATE_FILE))
+            .await
+            .ok()?;
+        let certificate: StoredCertificate = match serde_json::from_slice(&contents) {
+            Ok(certificate) => certificate,
+            Err(error) => {
+                tracing::warn!(?error, "ignoring unreadable cached certificate");
+                return None;
+            }
+        };
+        if certificate.domains != self.domains
+            || self.renewal_delay(certificate.issued_at).is_zero()
+        {
+            return None;
+        }
+        tracing::info!(domains = ?self.domains, "using the cached ACME certificate");
+        Some(certificate)
+    }
+
+    /// Returns the time until the certificate issued at the given time must be renewed.
+    fn renewal_delay(&self, issued_at: SystemTime) -> Duration {
+        let age = SystemTime::now()
+            .duration_since(issued_at)
+            .unwrap_or_default();
+        self.renew_after.saturating_sub(age)
+    }
+
+    async fn renew_periodically(
+        self: Arc<Self>,
+        rustls_config: RustlsConfig,
+        issued_at: SystemTime,
+    ) {
+        let mut delay = self.renewal_delay(issued_at);
+        loop {
+            tokio::time::sleep(delay).await;
+            let result = async {
+                let certificate = self.obtain_certificate().await?;
+                rustls_config
+                    .reload_from_pem(
+                        certificate.certificate_pem.into_bytes(),
+                        certificate.private_key_pem.into_bytes(),
+                    )
+                    .await?;
+                anyhow::Ok(certificate.issued_at)
+            }
+            .await;
+            delay = match result {
+                Ok(issued_at) => {
+                    tracing::info!(domains = ?self.domains, "renewed the ACME certificate");
+                    self.renewal_delay(issued_at)
+                }
+                Err(error) => {
+                    tracing::error!(?error, "failed to renew the ACME certificate");
+                    RENEWAL_RETRY_DELAY
+                }
+            };
+        }
+    }
+
+    async fn account(&self) -> anyhow::Result<Account> {
+        let path = self.cache_dir.join(ACCOUNT_FILE);
+        if let Ok(contents) = tokio::fs::read(&path).await {
+            let credentials: AccountCredentials = serde_json::from_slice(&contents)?;
+            return Ok(Account::from_credentials(credentials).await?);
+        }
+
+        let contact = self
+            .contact
+            .as_ref()
+            .map(|contact| format!("mailto:{contact}"));
+        let contact: Vec<_> = contact.iter().map(String::as_str).collect();
+        let (account, credentials) = Account::create(
+            &NewAccount {
+                contact: &contact,
+                terms_of_service_agreed: true,
+                only_return_existing: false,
+            },
+            &self.directory_url,
+            None,
+        )
+        .await
+        .context("unable to create the ACME account")?;
+        write_atomically(&path, &serde_json::to_vec(&credentials)?).await?;
+        tracing::info!(directory_url = self.directory_url, "created ACME account");
+        Ok(account)
+    }
+
+    /// Obtains a new certificate for the configured domains and persists it.
+    async fn obtain_certificate(&self) -> anyhow::Result<StoredCertificate> {
+        tracing::info!(domains = ?self.domains, "requesting a certificate through ACME");
+        let account = self.account().await?;
+        let identifiers: Vec<_> = self
+            .domains
+            .iter()
+            .map(|domain| Identifier::Dns(domain.clone()))
+            .collect();
+        let mut order = account
+            .new_order(&NewOrder {
+                identifiers: &identifiers,
+            })
+            .await?;
+
+        let mut tokens = vec![];
+        for authorization in order.authorizations().await? {
+            match authorization.status {
+                AuthorizationStatus::Pending => (),
+                AuthorizationStatus::Valid => continue,
+                status => bail!("unexpected ACME authorization status: {status:?}"),
+            }
+            let challenge = authorization
+                .challenges
+                .iter()
+                .find(|challenge| challenge.r#type == ChallengeType::Http01)
+                .ok_or_else(|| anyhow!("the ACME server did not offer an HTTP-01 challenge"))?;
+            let key_authorization = order.key_authorization(challenge);
+            self.challenges
+                .lock()
+                .expect("mutex should not be poisoned")
+                .insert(
+                    challenge.token.clone(),
+                    key_authorization.as_str().to_owned(),
+                );
+            tokens.push(challenge.token.clone());
+            order.set_challenge_ready(&challenge.url).await?;
+        }
+
+        let result = self.finalize_order(&mut order).await;
+        let mut challenges = self
+            .challenges
+            .lock()
+            .expect("mutex should not be poisoned");
+        for token in tokens {
+            challenges.remove(&token);
+        }
+        result
+    }
+
+    async fn finalize_order(
+        &self,
+        order: &mut instant_acme::Order,
+    ) -> anyhow::Result<StoredCertificate> {
+        poll_order(order, OrderStatus::Ready).await?;
+
+        let mut params = CertificateParams::new(self.domains.clone())?;
+        params.distinguished_name = DistinguishedName::new();
+        let private_key = KeyPair::generate()?;
+        let csr = params.serialize_request(&private_key)?;
+        order.finalize(csr.der()).await?;
+
+        let mut certificate_pem = None;
+        for _ in 0..MAX_ORDER_POLLS {
+            if let Some(certificate) = order.certificate().await? {
+                certificate_pem = Some(certificate);
+                break;
+            }
+            tokio::time::sleep(ORDER_POLL_INTERVAL).await;
+        }
+        let certificate = StoredCertificate {
+            domains: self.domains.clone(),
+            issued_at: SystemTime::now(),
+            certificate_pem: certificate_pem
+                .ok_or_else(|| anyhow!("the ACME server did not issue the certificate in time"))?,
+            private_key_pem: private_key.serialize_pem(),
+        };
+        write_atomically(
+            &self.cache_dir.join(CERTIFICATE_FILE),
+            &serde_json::to_vec(&certificate)?,
+        )
+        .await?;
+        Ok(certificate)
+    }
+}
+
+/// Polls the order until it reaches the expected status.
+async fn poll_order(order: &mut instant_acme::Order, expected: OrderStatus) -> anyhow::Result<()> {
+    for _ in 0..MAX_ORDER_POLLS {
+        let state = order.refresh().await?;
+        if state.status == expected {
+            return Ok(());
+        }
+        if state.status == OrderStatus::Invalid {
+            bail!("the ACME order is invalid: {:?}", state.error);
+        }
+        tokio::time::sleep(ORDER_POLL_INTERVAL).await;
+    }
+    bail!("the ACME order did not become {expected:?} in time")
+}
+
+async fn get_challenge(
+    State(challenges): State<PendingChallenges>,
+    UrlPath(token): UrlPath<String>,
+) -> Response {
+    let key_authorization = challenges
+        .lock()
+        .expect("mutex should not be poisoned")
+        .get(&token)
+        .cloned();
+    match key_authorization {
+        Some(key_authorization) => key_authorization.into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Writes the file by moving a temporary file into place, such that it is never partially written.
+/// Installs the `ring` crypto provider as the process-wide default for rustls.
+///
+/// rustls is built without a default provider, so one must be installed before any TLS
+/// configuration is built. Installing fails if a provider is already installed, which is fine.
+fn install_crypto_provider() {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+}
+
+/// Writes the contents to the path by renaming a temporary file, which is only readable and
+/// writable by the owner, as it may contain private keys.
+async fn write_atomically(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    let temporary_path = path.with_extension("tmp");
+    // Remove a leftover temporary file, such that the file is created with the permissions below.
+    match tokio::fs::remove_file(&temporary_path).await {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error.into()),
+        _ => (),
+    }
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(&temporary_path).await?;
+    file.write_all(contents).await?;
+    file.sync_all().await?;
+    tokio::fs::rename(&temporary_path, path).await?;
+    Ok(())
+}
+
+mod default {
+    use std::{net::SocketAddr, time::Duration};
+
+    use instant_acme::LetsEncrypt;
+
+    pub(crate) fn acme_directory_url() -> String {
+        LetsEncrypt::Production.url().to_owned()
+    }
+
+    pub(crate) fn acme_http_address() -> SocketAddr {
+        "0.0.0.0:80"
+            .parse()
+            .expect("this is a correct socket address")
+    }
+
+    pub(crate) fn acme_renew_after() -> Duration {
+        Duration::from_secs(60 * 24 * 60 * 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tls_is_disabled_by_default() -> anyhow::Result<()> {
+        assert!(TlsConfig::default().load().await?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn acme_requires_accepting_the_terms_of_service() {
+        let config = TlsConfig {
+            acme_domains: vec!["walrus.example.com".to_owned()],
+            ..Default::default()
+        };
+        assert!(config.load().await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn writes_files_readable_only_by_the_owner() -> anyhow::Result<()> {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join(CERTIFICATE_FILE);
+        std::fs::write(path.with_extension("tmp"), b"stale")?;
+
+        write_atomically(&path, b"secret").await?;
+
+        assert_eq!(std::fs::read(&path)?, b"secret");
+        let mode = std::fs::metadata(&path)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        Ok(())
+    }
+}
@@ -0,0 +1,535 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wallet-less publishing, in which the caller signs and executes the Sui transactions.
+//!
+//! The unsigned publisher encodes blobs and distributes their slivers to the storage nodes, but
+//! does not hold a wallet. Storing a blob takes two requests:
+//!
+//! 1. The caller uploads the blob together with its Sui address. The publisher encodes the blob,
+//!    keeps the encoded blob in memory, and returns the unsigned transaction that purchases
+//!    storage with the caller's WAL and registers the blob.
+//! 2. After executing the registration, the caller submits the ID of the created blob object. The
+//!    publisher sends the slivers to the storage nodes, collects the certificate, and returns the
+//!    unsigned transaction that certifies the blob.
+//!
+//! Transactions are returned as BCS-encoded `TransactionKind`s, to which wallets add the gas
+//! payment before signing. Encoded blobs are kept per sender, and the certification is only
+//! prepared for the sender owning the blob object. They are dropped once the certification
+//! transaction is built or their upload expires, and are not persisted across restarts.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Context as _};
+use chrono::{DateTime, Utc};
+use indicatif::MultiProgress;
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as, DisplayFromStr};
+use sui_sdk::rpc_types::SuiObjectDataOptions;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    transaction::TransactionKind,
+};
+use utoipa::ToSchema;
+use walrus_core::{
+    encoding::{EncodingConfigTrait as _, SliverPair},
+    metadata::VerifiedBlobMetadataWithId,
+    BlobId,
+    EncodingType,
+    EpochCount,
+    DEFAULT_ENCODING,
+};
+use walrus_proc_macros::RestApiError;
+use walrus_sdk::api::errors::DAEMON_ERROR_DOMAIN as ERROR_DOMAIN;
+use walrus_sui::{
+    client::{
+        transaction_builder::{TransactionBuilder, UnsignedTransaction},
+        BlobObjectMetadata,
+        BlobPersistence,
+        SuiReadClient,
+    },
+    types::move_structs::Blob,
+    ObjectIdSchema,
+    SuiAddressSchema,
+};
+
+use crate::{
+    client::{Client, ClientError, ClientErrorKind, MetadataReplication},
+    common::api::RestApiError,
+};
+
+/// A transaction to be signed and executed by the caller.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionToSign {
+    /// The address that has to sign the transaction.
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = SuiAddressSchema)]
+    pub sender: SuiAddress,
+    /// The BCS-encoded `TransactionKind` of the transaction, in Base64.
+    #[serde_as(as = "Base64")]
+    #[schema(value_type = String, format = Byte)]
+    pub transaction_kind: Vec<u8>,
+    /// The SUI, in MIST, spent by the transaction in addition to the gas fees.
+    pub sui_cost: u64,
+}
+
+impl From<UnsignedTransaction> for TransactionToSign {
+    fn from(transaction: UnsignedTransaction) -> Self {
+        let kind = TransactionKind::ProgrammableTransaction(transaction.programmable_transaction);
+        Self {
+            sender: transaction.sender,
+            transaction_kind: bcs::to_bytes(&kind).expect("transactions can always be encoded"),
+            sui_cost: transaction.sui_cost,
+        }
+    }
+}
+
+/// The response to an upload to the unsigned publisher.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PreparedUpload {
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = BlobId)]
+    pub blob_id: BlobId,
+    /// The transaction that purchases storage for and registers the blob.
+    pub register_transaction: TransactionToSign,
+    /// The time until which the publisher keeps the encoded blob.
+    ///
+    /// The blob must be registered and its certification transaction requested before this time.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The response to a request for the certification transaction of a registered blob.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PreparedCertification {
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = BlobId)]
+    pub blob_id: BlobId,
+    /// The ID of the registered blob object.
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = ObjectIdSchema)]
+    pub blob_object_id: ObjectID,
+    /// The transaction that certifies the blob.
+    pub certify_transaction: TransactionToSign,
+}
+
+/// The parameters of an upload to the unsigned publisher.
+#[derive(Debug, Clone)]
+pub(crate) struct UploadParams {
+    /// The address that signs the transactions and pays for storage.
+    pub sender: SuiAddress,
+    /// The encoding type to use for the blob.
+    pub encoding_type: Option<EncodingType>,
+    /// The number of epochs, ahead of the current one, for which to store the blob.
+    pub epochs: EpochCount,
+    /// Whether the blob is deletable.
+    pub persistence: BlobPersistence,
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub(crate) enum UnsignedPublisherError {
+    /// The blob was not uploaded to this publisher by the owner of the blob object, or its upload
+    /// has expired.
+    #[error("no pending upload exists for the blob and its owner; upload the blob again")]
+    #[rest_api_error(reason = "UPLOAD_NOT_FOUND", status = ApiStatusCode::NotFound)]
+    UploadNotFound,
+
+    /// The blob object is already certified.
+    #[error("the blob object is already certified")]
+    #[rest_api_error(reason = "BLOB_ALREADY_CERTIFIED", status = ApiStatusCode::FailedPrecondition)]
+    AlreadyCertified,
+
+    /// The publisher holds the maximum number or total size of pending uploads.
+    #[error("the publisher has too many pending uploads, please try again later")]
+    #[rest_api_error(reason = "TOO_MANY_UPLOADS", status = ApiStatusCode::ResourceExhausted)]
+    TooManyPendingUploads,
+
+    /// The blob could not be encoded.
+    #[error("the blob could not be encoded: {0}")]
+    #[rest_api_error(reason = "INVALID_BLOB", status = ApiStatusCode::InvalidArgument)]
+    InvalidBlob(String),
+
+    /// The blob cannot be both deletable and permanent.
+    #[error("the `deletable` and `permanent` query parameters are mutually exclusive")]
+    #[rest_api_error(reason = "CONFLICTING_PERSISTENCE", status = ApiStatusCode::InvalidArgument)]
+    ConflictingPersistence,
+
+    /// The blob cannot be stored as has been blocked.
+    #[error("the blob is blocked")]
+    #[rest_api_error(reason = "FORBIDDEN_BLOB", status = ApiStatusCode::UnavailableForLegalReasons)]
+    Blocked,
+
+    /// The publisher failed to store the blob to sufficient Walrus storage nodes before a timeout.
+    #[error("the service timed-out while waiting for confirmations, please try again")]
+    #[rest_api_error(
+        reason = "INSUFFICIENT_CONFIRMATIONS", status = ApiStatusCode::DeadlineExceeded
+    )]
+    NotEnoughConfirmations,
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl From<ClientError> for UnsignedPublisherError {
+    fn from(error: ClientError) -> Self {
+        match error.kind() {
+            ClientErrorKind::NotEnoughConfirmations(_, _) => Self::NotEnoughConfirmations,
+            ClientErrorKind::BlobIdBlocked(_) => Self::Blocked,
+            _ => Self::Internal(anyhow!(error)),
+        }
+    }
+}
+
+/// A blob that was encoded but whose certification transaction has not been built yet.
+#[derive(Debug)]
+struct PendingUpload {
+    pairs: Arc<Vec<SliverPair>>,
+    metadata: Arc<VerifiedBlobMetadataWithId>,
+    /// The number of bytes of the slivers held in memory.
+    size: u64,
+    expires_at: SystemTime,
+}
+
+impl PendingUpload {
+    fn new(
+        pairs: Vec<SliverPair>,
+        metadata: VerifiedBlobMetadataWithId,
+        expires_at: SystemTime,
+    ) -> Self {
+        let size = pairs
+            .iter()
+            .map(|pair| (pair.primary.len() + pair.secondary.len()) as u64)
+            .sum();
+        Self {
+            pairs: Arc::new(pairs),
+            metadata: Arc::new(metadata),
+            size,
+            expires_at,
+        }
+    }
+}
+
+/// The pending uploads of all senders, bounded in number and total size.
+#[derive(Debug)]
+struct PendingUploads {
+    uploads: HashMap<(SuiAddress, BlobId), PendingUpload>,
+    total_size: u64,
+    max_uploads: usize,
+    max_total_size: u64,
+}
+
+impl PendingUploads {
+    fn new(max_uploads: usize, max_total_size: u64) -> Self {
+        Self {
+            uploads: HashMap::new(),
+            total_size: 0,
+            max_uploads,
+            max_total_size,
+        }
+    }
+
+    /// Adds the upload of the blob by the sender, replacing a previous upload of the same blob by
+    /// the same sender.
+    ///
+    /// Fails if the upload would exceed the maximum number or total size of pending uploads.
+    fn insert(
+        &mut self,
+        sender: SuiAddress,
+        blob_id: BlobId,
+        upload: PendingUpload,
+        now: SystemTime,
+    ) -> Result<(), UnsignedPublisherError> {
+        self.remove_expired(now);
+        let key = (sender, blob_id);
+        let replaced_size = self.uploads.get(&key).map(|upload| upload.size);
+        let n_uploads = self.uploads.len() + usize::from(replaced_size.is_none());
+        let replaced_size = replaced_size.unwrap_or_default();
+        if n_uploads > self.max_uploads
+            || self.total_size - replaced_size + upload.size > self.max_total_size
+        {
+            return Err(UnsignedPublisherError::TooManyPendingUploads);
+        }
+        self.total_size = self.total_size - replaced_size + upload.size;
+        self.uploads.insert(key, upload);
+        Ok(())
+    }
+
+    /// Returns the unexpired upload of the blob by the sender.
+    fn get(
+        &mut self,
+        sender: SuiAddress,
+        blob_id: BlobId,
+        now: SystemTime,
+    ) -> Option<&PendingUpload> {
+        self.remove_expired(now);
+        self.uploads.get(&(sender, blob_id))
+    }
+
+    fn remove(&mut self, sender: SuiAddress, blob_id: BlobId) {
+        if let Some(upload) = self.uploads.remove(&(sender, blob_id)) {
+            self.total_size -= upload.size;
+        }
+    }
+
+    fn remove_expired(&mut self, now: SystemTime) {
+        let mut removed_size = 0;
+        self.uploads.retain(|_, upload| {
+            let keep = upload.expires_at > now;
+            if !keep {
+                removed_size += upload.size;
+            }
+            keep
+        });
+        self.total_size -= removed_size;
+    }
+}
+
+/// Encodes and distributes blobs for callers that sign and execute the Sui transactions
+/// themselves.
+#[derive(Debug)]
+pub(crate) struct UnsignedPublisher {
+    client: Arc<Client<SuiReadClient>>,
+    read_client: Arc<SuiReadClient>,
+    uploads: Mutex<PendingUploads>,
+    upload_ttl: Duration,
+}
+
+impl UnsignedPublisher {
+    /// Creates a new unsigned publisher, which keeps at most `max_pending_uploads` encoded blobs,
+    /// with slivers of at most `max_pending_bytes` in total, for up to `upload_ttl` each.
+    pub(crate) fn new(
+        client: Arc<Client<SuiReadClient>>,
+        max_pending_uploads: usize,
+        max_pending_bytes: u64,
+        upload_ttl: Duration,
+    ) -> Self {
+        let read_client = Arc::new(client.sui_client().clone());
+        Self {
+            client,
+            read_client,
+            uploads: Mutex::new(PendingUploads::new(max_pending_uploads, max_pending_bytes)),
+            upload_ttl,
+        }
+    }
+
+    /// Encodes the blob and returns the transaction registering it.
+    pub(crate) async fn prepare_upload(
+        &self,
+        blob: &[u8],
+        params: UploadParams,
+    ) -> Result<PreparedUpload, UnsignedPublisherError> {
+        let encoding_type = params.encoding_type.unwrap_or(DEFAULT_ENCODING);
+        let (pairs, metadata) = self
+            .client
+            .encoding_config()
+            .get_for_type(encoding_type)
+            .encode_with_metadata(blob)
+            .map_err(|error| UnsignedPublisherError::InvalidBlob(error.to_string()))?;
+        let blob_id = *metadata.blob_id();
+        if self
+            .client
+            .blocklist()
+            .is_some_and(|blocklist| blocklist.is_blocked(&blob_id))
+        {
+            return Err(UnsignedPublisherError::Blocked);
+        }
+
+        let register_transaction = TransactionBuilder::new(self.read_client.clone(), params.sender)
+            .reserve_and_register_blobs(
+                params.epochs,
+                vec![BlobObjectMetadata::try_from(&metadata).map_err(|error| anyhow!(error))?],
+                params.persistence,
+            )
+            .await
+            .map_err(|error| anyhow!(error))?;
+
+        let now = SystemTime::now();
+        let expires_at = now + self.upload_ttl;
+        self.uploads().insert(
+            params.sender,
+            blob_id,
+            PendingUpload::new(pairs, metadata, expires_at),
+            now,
+        )?;
+        tracing::info!(%blob_id, sender = %params.sender, "prepared unsigned blob upload");
+
+        Ok(PreparedUpload {
+            blob_id,
+            register_transaction: register_transaction.into(),
+            expires_at: expires_at.into(),
+        })
+    }
+
+    /// Sends the slivers of the registered blob object to the storage nodes and returns the
+    /// transaction certifying it.
+    ///
+    /// The blob must have been uploaded by the address owning the blob object, which is also the
+    /// sender of the certification transaction.
+    pub(crate) async fn prepare_certification(
+        &self,
+        blob_object_id: ObjectID,
+    ) -> Result<PreparedCertification, UnsignedPublisherError> {
+        let blob: Blob = self
+            .read_client
+            .sui_client()
+            .get_sui_object(blob_object_id)
+            .await
+            .map_err(|error| anyhow!(error))?;
+        if blob.certified_epoch.is_some() {
+            return Err(UnsignedPublisherError::AlreadyCertified);
+        }
+        let owner = self.blob_object_owner(blob_object_id).await?;
+        let (pairs, metadata) = {
+            let mut uploads = self.uploads();
+            let upload = uploads
+                .get(owner, blob.blob_id, SystemTime::now())
+                .ok_or(UnsignedPublisherError::UploadNotFound)?;
+            (upload.pairs.clone(), upload.metadata.clone())
+        };
+
+        // The caller has just executed the registration; give the storage nodes time to see it.
+        tokio::time::sleep(self.client.config().communication_config.registration_delay).await;
+        let certificate = self
+            .client
+            .send_blob_data_and_get_certificate(
+                &metadata,
+                &pairs,
+                &blob.blob_persistence_type(),
+                MetadataReplication::WithSlivers,
+                &MultiProgress::new(),
+            )
+            .await?;
+        let certify_transaction = TransactionBuilder::new(self.read_client.clone(), owner)
+            .certify_blobs(&[(blob_object_id, certificate)])
+            .await
+            .map_err(|error| anyhow!(error))?;
+
+        self.uploads().remove(owner, blob.blob_id);
+        tracing::info!(
+            blob_id = %blob.blob_id,
+            %blob_object_id,
+            "prepared certification of unsigned blob upload"
+        );
+
+        Ok(PreparedCertification {
+            blob_id: blob.blob_id,
+            blob_object_id,
+            certify_transaction: certify_transaction.into(),
+        })
+    }
+
+    /// Returns the address owning the blob object.
+    async fn blob_object_owner(
+        &self,
+        blob_object_id: ObjectID,
+    ) -> Result<SuiAddress, UnsignedPublisherError> {
+        let object = self
+            .read_client
+            .sui_client()
+            .get_object_with_options(blob_object_id, SuiObjectDataOptions::new().with_owner())
+            .await
+            .map_err(|error| anyhow!(error))?;
+        let owner = object
+            .owner()
+            .context("no object owner returned from RPC")?
+            .get_owner_address()
+            .map_err(|error| anyhow!(error))?;
+        Ok(owner)
+    }
+
+    fn uploads(&self) -> MutexGuard<'_, PendingUploads> {
+        self.uploads.lock().expect("mutex should not be poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::test_utils::{random_blob_id, verified_blob_metadata};
+
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn upload(expires_at: SystemTime) -> PendingUpload {
+        PendingUpload::new(vec![], verified_blob_metadata(), expires_at)
+    }
+
+    fn upload_of_size(size: u64, expires_at: SystemTime) -> PendingUpload {
+        PendingUpload {
+            size,
+            ..upload(expires_at)
+        }
+    }
+
+    #[test]
+    fn keeps_uploads_per_sender() {
+        let now = SystemTime::now();
+        let (alice, bob) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+        let blob_id = random_blob_id();
+        let mut uploads = PendingUploads::new(10, 1000);
+
+        uploads
+            .insert(alice, blob_id, upload(now + MINUTE), now)
+            .unwrap();
+        assert!(uploads.get(alice, blob_id, now).is_some());
+        // Another sender cannot use, or remove, the upload.
+        assert!(uploads.get(bob, blob_id, now).is_none());
+        uploads.remove(bob, blob_id);
+        assert!(uploads.get(alice, blob_id, now).is_some());
+        // Expired uploads are dropped.
+        assert!(uploads.get(alice, blob_id, now + 2 * MINUTE).is_none());
+    }
+
+    #[test]
+    fn bounds_the_number_and_size_of_uploads() {
+        let now = SystemTime::now();
+        let sender = SuiAddress::random_for_testing_only();
+        let (first, second, third) = (random_blob_id(), random_blob_id(), random_blob_id());
+        let mut uploads = PendingUploads::new(2, 100);
+
+        uploads
+            .insert(sender, first, upload_of_size(60, now + MINUTE), now)
+            .unwrap();
+        assert!(matches!(
+            uploads.insert(sender, second, upload_of_size(50, now + MINUTE), now),
+            Err(UnsignedPublisherError::TooManyPendingUploads)
+        ));
+        uploads
+            .insert(sender, second, upload_of_size(40, now + MINUTE), now)
+            .unwrap();
+        assert!(matches!(
+            uploads.insert(sender, third, upload_of_size(0, now + MINUTE), now),
+            Err(UnsignedPublisherError::TooManyPendingUploads)
+        ));
+        // Replacing an upload only counts its new size.
+        uploads
+            .insert(sender, first, upload_of_size(50, now + MINUTE), now)
+            .unwrap();
+        assert_eq!(uploads.total_size, 90);
+
+        // Removed and expired uploads free their capacity.
+        uploads.remove(sender, first);
+        assert_eq!(uploads.total_size, 40);
+        uploads
+            .insert(sender, third, upload_of_size(60, now + 2 * MINUTE), now)
+            .unwrap();
+        uploads.remove_expired(now + MINUTE);
+        assert_eq!(uploads.total_size, 60);
+    }
+}
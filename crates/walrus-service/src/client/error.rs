@@ -3,10 +3,14 @@
 
 //! The errors for the storage client and the communication with storage nodes.
 
+use std::time::Duration;
+
 use walrus_core::{BlobId, EncodingType, Epoch, SliverPairIndex, SliverType};
 use walrus_sdk::error::{ClientBuildError, NodeError};
 use walrus_sui::client::{SuiClientError, MIN_STAKING_THRESHOLD};
 
+use super::config::ClientPhase;
+
 /// Storing the metadata and the set of sliver pairs onto the storage node, and retrieving the
 /// storage confirmation, failed.
 #[derive(Debug, thiserror::Error)]
@@ -173,6 +177,14 @@ pub enum ClientErrorKind {
     /// Unable to load trusted certificates from the OS.
     #[error("unable to load trusted certificates from the OS: {0:?}")]
     FailedToLoadCerts(Vec<rustls_native_certs::Error>),
+    /// A phase of the operation did not complete within the timeout of the retry policy.
+    #[error("the {phase} did not complete within {timeout:?}")]
+    Timeout {
+        /// The phase that timed out.
+        phase: ClientPhase,
+        /// The timeout configured for the phase.
+        timeout: Duration,
+    },
     /// A failure internal to the node.
     #[error("client internal error: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync + 'static>),
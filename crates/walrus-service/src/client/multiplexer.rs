@@ -18,7 +18,8 @@ use sui_sdk::{
     wallet_context::WalletContext,
 };
 use sui_types::base_types::ObjectID;
-use walrus_core::{BlobId, EncodingType, EpochCount};
+use tokio::task::JoinSet;
+use walrus_core::{BlobId, EncodingType, EpochCount, DEFAULT_ENCODING};
 use walrus_sui::{
     client::{
         retry_client::RetriableSuiClient,
@@ -34,20 +35,26 @@ use walrus_sui::{
 
 use super::{
     cli::PublisherArgs,
-    daemon::{WalrusReadClient, WalrusWriteClient},
+    daemon::{BlobStream, WalrusReadClient, WalrusWriteClient},
     metrics::ClientMetrics,
     refill::{RefillHandles, Refiller},
-    responses::BlobStoreResult,
+    responses::{BlobCertificationStatus, BlobReadInfo, BlobStoreResult},
     Client,
     ClientResult,
+    MetadataReplication,
     StoreWhen,
 };
-use crate::client::{refill::should_refill, CommitteesRefresherHandle, Config};
+use crate::{
+    client::{refill::should_refill, CommitteesRefresherHandle, Config},
+    common::balance_monitor::{monitor_balances, BalanceMonitorMetrics},
+};
 
 pub struct ClientMultiplexer {
     client_pool: WriteClientPool,
-    read_client: Client<SuiReadClient>,
+    read_client: Arc<Client<SuiReadClient>>,
     _refill_handles: RefillHandles,
+    /// The balance monitor for the main wallet, aborted when the multiplexer is dropped.
+    _balance_monitor: JoinSet<()>,
     default_post_store_action: PostStoreAction,
 }
 
@@ -99,6 +106,14 @@ impl ClientMultiplexer {
         )
         .await?;
 
+        let mut balance_monitor = JoinSet::new();
+        balance_monitor.spawn(monitor_balances(
+            args.balance_monitor_config(),
+            BalanceMonitorMetrics::new(prometheus_registry),
+            Arc::new(sui_read_client),
+            main_address,
+        ));
+
         let metrics = Arc::new(ClientMetrics::new(prometheus_registry));
         let refill_handles = refiller.refill_gas_and_wal(
             client_pool.addresses(),
@@ -119,14 +134,16 @@ impl ClientMultiplexer {
 
         Ok(Self {
             client_pool,
-            read_client,
+            read_client: Arc::new(read_client),
             _refill_handles: refill_handles,
+            _balance_monitor: balance_monitor,
             default_post_store_action,
         })
     }
 
     /// Submits a write request to the client pool.
     #[tracing::instrument(err, skip_all)]
+    #[allow(clippy::too_many_arguments)]
     pub async fn submit_write(
         &self,
         blob: &[u8],
@@ -135,6 +152,7 @@ impl ClientMultiplexer {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<BlobStoreResult> {
         let client = self.client_pool.next_client().await;
         tracing::debug!("submitting write request to client in pool");
@@ -147,6 +165,7 @@ impl ClientMultiplexer {
                 store_when,
                 persistence,
                 post_store,
+                metadata_replication,
             )
             .await?;
 
@@ -156,7 +175,14 @@ impl ClientMultiplexer {
 
 impl WalrusReadClient for ClientMultiplexer {
     async fn read_blob(&self, blob_id: &BlobId) -> ClientResult<Vec<u8>> {
-        WalrusReadClient::read_blob(&self.read_client, blob_id).await
+        WalrusReadClient::read_blob(self.read_client.as_ref(), blob_id).await
+    }
+
+    async fn read_blob_stream(self: Arc<Self>, blob_id: BlobId) -> ClientResult<BlobStream>
+    where
+        Self: Send + Sync + 'static,
+    {
+        self.read_client.clone().read_blob_stream(blob_id).await
     }
 
     async fn get_blob_by_object_id(
@@ -165,6 +191,14 @@ impl WalrusReadClient for ClientMultiplexer {
     ) -> ClientResult<BlobWithAttribute> {
         self.read_client.get_blob_by_object_id(blob_object_id).await
     }
+
+    async fn blob_read_info(&self, blob_id: &BlobId) -> ClientResult<BlobReadInfo> {
+        self.read_client.blob_read_info(blob_id).await
+    }
+
+    async fn get_blob_status(&self, blob_id: &BlobId) -> ClientResult<BlobCertificationStatus> {
+        self.read_client.get_blob_status(blob_id).await
+    }
 }
 
 impl WalrusWriteClient for ClientMultiplexer {
@@ -176,6 +210,7 @@ impl WalrusWriteClient for ClientMultiplexer {
         store_when: StoreWhen,
         persistence: BlobPersistence,
         post_store: PostStoreAction,
+        metadata_replication: MetadataReplication,
     ) -> ClientResult<BlobStoreResult> {
         self.submit_write(
             blob,
@@ -184,6 +219,7 @@ impl WalrusWriteClient for ClientMultiplexer {
             store_when,
             persistence,
             post_store,
+            metadata_replication,
         )
         .await
     }
@@ -191,6 +227,15 @@ impl WalrusWriteClient for ClientMultiplexer {
     fn default_post_store_action(&self) -> PostStoreAction {
         self.default_post_store_action
     }
+
+    fn compute_blob_id(
+        &self,
+        blob: &[u8],
+        encoding_type: Option<EncodingType>,
+    ) -> ClientResult<BlobId> {
+        self.read_client
+            .compute_blob_id(blob, encoding_type.unwrap_or(DEFAULT_ENCODING))
+    }
 }
 
 /// The configuration for a [`WriteClientPool`].
@@ -0,0 +1,230 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Receipts for blobs stored through the publisher.
+//!
+//! A receipt summarizes the on-chain outcome of a store operation. If the publisher is configured
+//! with a receipt signing key, it signs each receipt with that key, which allows downstream
+//! systems that trust the publisher's public key to prove that a store happened without querying
+//! Sui.
+
+use std::path::Path;
+
+use anyhow::Context as _;
+use fastcrypto::{
+    secp256r1::Secp256r1Signature,
+    traits::{Signer as _, VerifyingKey as _},
+};
+use p256::pkcs8::DecodePrivateKey as _;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sui_types::{base_types::ObjectID, event::EventID};
+use utoipa::ToSchema;
+use walrus_core::{BlobId, Epoch, NetworkKeyPair, NetworkPublicKey};
+use walrus_sui::{EventIdSchema, ObjectIdSchema};
+
+use super::responses::{BlobStoreResult, EventOrObjectId};
+
+/// The on-chain outcome of a successful store operation.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreReceipt {
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = BlobId)]
+    pub blob_id: BlobId,
+    /// The ID of the Sui blob object, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<ObjectIdSchema>)]
+    pub blob_object_id: Option<ObjectID>,
+    /// The event where the blob was certified, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<EventIdSchema>)]
+    pub certification_event: Option<EventID>,
+    /// The epoch in which the blob was certified, if it was certified in this store operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<u64>)]
+    pub certified_epoch: Option<Epoch>,
+    /// The epoch until which the blob is stored (exclusive).
+    #[schema(value_type = u64)]
+    pub end_epoch: Epoch,
+}
+
+impl StoreReceipt {
+    /// Creates the receipt for the result of a store operation.
+    ///
+    /// Returns `None` if the blob was marked as invalid.
+    pub fn from_store_result(result: &BlobStoreResult) -> Option<Self> {
+        match result {
+            BlobStoreResult::AlreadyCertified {
+                blob_id,
+                event_or_object,
+                end_epoch,
+            } => {
+                let (blob_object_id, certification_event) = match event_or_object {
+                    EventOrObjectId::Event(event_id) => (None, Some(*event_id)),
+                    EventOrObjectId::Object(object_id) => (Some(*object_id), None),
+                };
+                Some(Self {
+                    blob_id: *blob_id,
+                    blob_object_id,
+                    certification_event,
+                    certified_epoch: None,
+                    end_epoch: *end_epoch,
+                })
+            }
+            BlobStoreResult::NewlyCreated {
+                blob_object,
+                certification_event,
+                ..
+            } => Some(Self {
+                blob_id: blob_object.blob_id,
+                blob_object_id: Some(blob_object.id),
+                certification_event: *certification_event,
+                certified_epoch: blob_object.certified_epoch,
+                end_epoch: blob_object.storage.end_epoch,
+            }),
+            BlobStoreResult::MarkedInvalid { .. } => None,
+        }
+    }
+
+    /// Returns the message signed by the publisher.
+    ///
+    /// The message is the BCS encoding of the tuple `(blob_id, blob_object_id,
+    /// certification_event, certified_epoch, end_epoch)`, in which the blob ID is encoded as
+    /// 32 bytes and absent values as `None`.
+    pub fn signed_message(&self) -> Vec<u8> {
+        bcs::to_bytes(&(
+            self.blob_id,
+            self.blob_object_id,
+            self.certification_event,
+            self.certified_epoch,
+            self.end_epoch,
+        ))
+        .expect("the receipt can always be BCS-encoded")
+    }
+}
+
+/// A [`StoreReceipt`], together with the publisher's signature if the publisher signs receipts.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedStoreReceipt {
+    /// The receipt.
+    #[serde(flatten)]
+    pub receipt: StoreReceipt,
+    /// The publisher's signature over the [signed message][StoreReceipt::signed_message].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = Byte)]
+    pub signature: Option<Secp256r1Signature>,
+    /// The public key with which the receipt was signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = Byte)]
+    pub public_key: Option<NetworkPublicKey>,
+}
+
+impl SignedStoreReceipt {
+    /// Returns true if the receipt carries a valid signature by its public key.
+    ///
+    /// Callers must additionally check that the public key belongs to a publisher they trust.
+    pub fn verify(&self) -> bool {
+        match (&self.signature, &self.public_key) {
+            (Some(signature), Some(public_key)) => public_key
+                .verify(&self.receipt.signed_message(), signature)
+                .is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Signs the receipts of blobs stored through the publisher.
+#[derive(Debug, Clone)]
+pub(crate) struct ReceiptSigner {
+    key_pair: NetworkKeyPair,
+}
+
+impl ReceiptSigner {
+    /// Loads the signing key from a PKCS#8 PEM file.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let pem = std::fs::read_to_string(path).with_context(|| {
+            format!(
+                "unable to read the receipt signing key from {}",
+                path.display()
+            )
+        })?;
+        let key_pair = NetworkKeyPair::from_pkcs8_pem(&pem)
+            .context("the receipt signing key must be a P-256 key in PKCS#8 PEM format")?;
+        Ok(Self { key_pair })
+    }
+
+    /// Signs the receipt.
+    pub(crate) fn sign(&self, receipt: StoreReceipt) -> SignedStoreReceipt {
+        let signature = self.key_pair.0.sign(&receipt.signed_message());
+        SignedStoreReceipt {
+            receipt,
+            signature: Some(signature),
+            public_key: Some(self.key_pair.public().clone()),
+        }
+    }
+}
+
+/// Returns the receipt for the result of a store operation, signed if a signer is configured.
+pub(crate) fn store_receipt(
+    result: &BlobStoreResult,
+    signer: Option<&ReceiptSigner>,
+) -> Option<SignedStoreReceipt> {
+    let receipt = StoreReceipt::from_store_result(result)?;
+    Some(match signer {
+        Some(signer) => signer.sign(receipt),
+        None => SignedStoreReceipt {
+            receipt,
+            signature: None,
+            public_key: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::test_utils::random_blob_id;
+
+    use super::*;
+
+    #[test]
+    fn signed_receipts_verify_only_unmodified() {
+        let signer = ReceiptSigner {
+            key_pair: NetworkKeyPair::generate(),
+        };
+        let receipt = StoreReceipt {
+            blob_id: random_blob_id(),
+            blob_object_id: Some(ObjectID::random()),
+            certification_event: None,
+            certified_epoch: Some(3),
+            end_epoch: 8,
+        };
+
+        let signed = signer.sign(receipt.clone());
+        assert!(signed.verify());
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let decoded: SignedStoreReceipt = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.receipt, receipt);
+        assert!(decoded.verify());
+
+        let mut tampered = signed.clone();
+        tampered.receipt.end_epoch = 9;
+        assert!(!tampered.verify());
+
+        let unsigned = store_receipt(
+            &BlobStoreResult::AlreadyCertified {
+                blob_id: receipt.blob_id,
+                event_or_object: EventOrObjectId::Object(ObjectID::random()),
+                end_epoch: 8,
+            },
+            None,
+        )
+        .unwrap();
+        assert!(unsigned.signature.is_none());
+        assert!(!unsigned.verify());
+    }
+}
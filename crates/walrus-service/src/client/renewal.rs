@@ -0,0 +1,487 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Renewal of the wallet's blobs before they expire.
+//!
+//! Certified blobs whose storage ends within a given number of epochs are reported as expiring.
+//! According to a [`RenewalPolicy`], they can then be extended, preferably by fusing their storage
+//! with a storage resource owned by the wallet that starts when the blob expires, and otherwise by
+//! paying WAL for the extension.
+
+use std::{fmt, path::Path};
+
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sui_types::base_types::ObjectID;
+use walrus_core::{BlobId, Epoch, EpochCount};
+use walrus_sui::{
+    client::SuiContractClient,
+    types::{Blob, StorageResource},
+    utils::price_for_encoded_length,
+};
+
+use crate::utils::load_from_yaml;
+
+/// The policy according to which expiring blobs are renewed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct RenewalPolicy {
+    /// The number of epochs by which blobs are extended when paying WAL.
+    pub epochs_extended: EpochCount,
+    /// Whether deletable blobs are renewed.
+    pub renew_deletable: bool,
+    /// Whether storage resources owned by the wallet are used to renew blobs.
+    pub use_owned_storage: bool,
+    /// Whether blobs are renewed by paying WAL if no suitable storage resource is owned.
+    pub pay_with_wal: bool,
+    /// The maximum amount of FROST spent on renewals in a single run; unlimited if not set.
+    pub max_total_cost: Option<u64>,
+}
+
+impl Default for RenewalPolicy {
+    fn default() -> Self {
+        Self {
+            epochs_extended: 5,
+            renew_deletable: true,
+            use_owned_storage: true,
+            pay_with_wal: true,
+            max_total_cost: None,
+        }
+    }
+}
+
+impl RenewalPolicy {
+    /// Loads the policy from the YAML file at the given path.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let policy: Self = load_from_yaml(path)?;
+        ensure!(
+            policy.epochs_extended > 0,
+            "the number of epochs by which blobs are extended must be positive"
+        );
+        Ok(policy)
+    }
+}
+
+/// A certified blob of the wallet that expires within the requested number of epochs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExpiringBlob {
+    /// The blob object.
+    pub blob: Blob,
+    /// The number of epochs until the blob expires.
+    pub epochs_left: EpochCount,
+}
+
+/// Returns the certified blobs that expire within `within_epochs` epochs, ordered by expiry.
+///
+/// Blobs that are not certified are omitted, as they cannot be extended.
+pub(crate) fn expiring_blobs(
+    blobs: impl IntoIterator<Item = Blob>,
+    current_epoch: Epoch,
+    within_epochs: EpochCount,
+) -> Vec<ExpiringBlob> {
+    let mut expiring: Vec<_> = blobs
+        .into_iter()
+        .filter(|blob| blob.certified_epoch.is_some() && blob.storage.end_epoch > current_epoch)
+        .map(|blob| ExpiringBlob {
+            epochs_left: blob.storage.end_epoch - current_epoch,
+            blob,
+        })
+        .filter(|expiring| expiring.epochs_left <= within_epochs)
+        .collect();
+    expiring.sort_by_key(|expiring| (expiring.blob.storage.end_epoch, expiring.blob.id));
+    expiring
+}
+
+/// How a blob is renewed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum RenewalMethod {
+    /// The owned storage resource is fused with the storage of the blob.
+    #[serde(rename_all = "camelCase")]
+    OwnedStorage {
+        /// The object ID of the storage resource.
+        storage_object_id: ObjectID,
+    },
+    /// The storage of the blob is extended by paying WAL.
+    #[serde(rename_all = "camelCase")]
+    Payment {
+        /// The number of epochs by which the blob is extended.
+        epochs_extended: EpochCount,
+        /// The cost of the extension in FROST.
+        cost: u64,
+    },
+}
+
+/// A renewal of a blob.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Renewal {
+    /// The object ID of the blob.
+    pub blob_object_id: ObjectID,
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// The epoch in which the blob expires without the renewal.
+    pub end_epoch: Epoch,
+    /// The epoch in which the blob expires after the renewal.
+    pub new_end_epoch: Epoch,
+    /// How the blob is renewed.
+    pub method: RenewalMethod,
+}
+
+/// The reason why an expiring blob is not renewed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SkipReason {
+    /// The blob is deletable, and the policy excludes deletable blobs.
+    Deletable,
+    /// No suitable storage resource is owned, and the policy does not allow paying WAL.
+    NoOwnedStorage,
+    /// The blob already expires as far in the future as the system allows.
+    MaxEpochsAhead,
+    /// Renewing the blob would exceed the maximum total cost of the policy.
+    BudgetExceeded,
+    /// The renewal transaction failed.
+    Failed(String),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deletable => write!(f, "the policy excludes deletable blobs"),
+            Self::NoOwnedStorage => write!(
+                f,
+                "no suitable storage resource is owned and the policy does not allow paying WAL"
+            ),
+            Self::MaxEpochsAhead => write!(f, "the blob cannot be extended any further"),
+            Self::BudgetExceeded => write!(f, "the maximum total cost of the policy is reached"),
+            Self::Failed(error) => write!(f, "the renewal failed: {error}"),
+        }
+    }
+}
+
+/// An expiring blob that is not renewed.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SkippedRenewal {
+    /// The object ID of the blob.
+    pub blob_object_id: ObjectID,
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// The reason why the blob is not renewed.
+    pub reason: SkipReason,
+}
+
+/// The system parameters that determine how far and at what cost blobs can be extended.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RenewalParameters {
+    /// The current epoch.
+    pub current_epoch: Epoch,
+    /// The maximum number of epochs ahead of the current epoch for which storage can be bought.
+    pub max_epochs_ahead: EpochCount,
+    /// The price of storing one unit of encoded size for one epoch, in FROST.
+    pub storage_price_per_unit_size: u64,
+}
+
+/// The renewals of expiring blobs according to a [`RenewalPolicy`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RenewalPlan {
+    /// The renewals to perform.
+    pub renewals: Vec<Renewal>,
+    /// The expiring blobs that are not renewed.
+    pub skipped: Vec<SkippedRenewal>,
+}
+
+impl RenewalPlan {
+    /// Plans the renewal of the expiring blobs, in order, with the owned storage resources.
+    ///
+    /// A storage resource can only be used for a blob if it has the same size as the storage of the
+    /// blob and starts in the epoch in which the blob expires. Each storage resource is used at
+    /// most once; if several are suitable, the one lasting longest is used.
+    pub(crate) fn new(
+        expiring: &[ExpiringBlob],
+        policy: &RenewalPolicy,
+        mut owned_storage: Vec<StorageResource>,
+        parameters: RenewalParameters,
+    ) -> Self {
+        let mut plan = Self::default();
+        let mut total_cost = 0;
+
+        for ExpiringBlob { blob, .. } in expiring {
+            let end_epoch = blob.storage.end_epoch;
+            let skip = |reason| SkippedRenewal {
+                blob_object_id: blob.id,
+                blob_id: blob.blob_id,
+                reason,
+            };
+            let renew = |new_end_epoch, method| Renewal {
+                blob_object_id: blob.id,
+                blob_id: blob.blob_id,
+                end_epoch,
+                new_end_epoch,
+                method,
+            };
+
+            if blob.deletable && !policy.renew_deletable {
+                plan.skipped.push(skip(SkipReason::Deletable));
+                continue;
+            }
+
+            let storage_position = owned_storage
+                .iter()
+                .enumerate()
+                .filter(|(_, storage)| {
+                    policy.use_owned_storage
+                        && storage.storage_size == blob.storage.storage_size
+                        && storage.start_epoch == end_epoch
+                        && storage.end_epoch > end_epoch
+                })
+                .max_by_key(|(_, storage)| storage.end_epoch)
+                .map(|(position, _)| position);
+            if let Some(position) = storage_position {
+                let storage = owned_storage.swap_remove(position);
+                plan.renewals.push(renew(
+                    storage.end_epoch,
+                    RenewalMethod::OwnedStorage {
+                        storage_object_id: storage.id,
+                    },
+                ));
+                continue;
+            }
+
+            if !policy.pay_with_wal {
+                plan.skipped.push(skip(SkipReason::NoOwnedStorage));
+                continue;
+            }
+            let max_end_epoch = parameters.current_epoch + parameters.max_epochs_ahead;
+            let epochs_extended = policy
+                .epochs_extended
+                .min(max_end_epoch.saturating_sub(end_epoch));
+            if epochs_extended == 0 {
+                plan.skipped.push(skip(SkipReason::MaxEpochsAhead));
+                continue;
+            }
+            let cost = price_for_encoded_length(
+                blob.storage.storage_size,
+                parameters.storage_price_per_unit_size,
+                epochs_extended,
+            );
+            if policy
+                .max_total_cost
+                .is_some_and(|max_total_cost| total_cost + cost > max_total_cost)
+            {
+                plan.skipped.push(skip(SkipReason::BudgetExceeded));
+                continue;
+            }
+            total_cost += cost;
+            plan.renewals.push(renew(
+                end_epoch + epochs_extended,
+                RenewalMethod::Payment {
+                    epochs_extended,
+                    cost,
+                },
+            ));
+        }
+        plan
+    }
+
+    /// Returns the total cost of the renewals paid with WAL, in FROST.
+    pub(crate) fn total_cost(&self) -> u64 {
+        self.renewals
+            .iter()
+            .map(|renewal| match renewal.method {
+                RenewalMethod::Payment { cost, .. } => cost,
+                RenewalMethod::OwnedStorage { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Performs the planned renewals one after the other.
+    ///
+    /// Returns the plan with the renewals that were performed; the failed renewals are moved to
+    /// the skipped blobs.
+    pub(crate) async fn execute(self, sui_client: &SuiContractClient) -> Self {
+        let mut executed = Self {
+            renewals: Vec::with_capacity(self.renewals.len()),
+            skipped: self.skipped,
+        };
+        for renewal in self.renewals {
+            let result = match renewal.method {
+                RenewalMethod::OwnedStorage { storage_object_id } => {
+                    sui_client
+                        .extend_blob_with_resource(renewal.blob_object_id, storage_object_id)
+                        .await
+                }
+                RenewalMethod::Payment {
+                    epochs_extended, ..
+                } => {
+                    sui_client
+                        .extend_blob(renewal.blob_object_id, epochs_extended)
+                        .await
+                }
+            };
+            match result {
+                Ok(()) => executed.renewals.push(renewal),
+                Err(error) => {
+                    tracing::warn!(
+                        ?error,
+                        blob_object_id = %renewal.blob_object_id,
+                        "failed to renew the blob"
+                    );
+                    executed.skipped.push(SkippedRenewal {
+                        blob_object_id: renewal.blob_object_id,
+                        blob_id: renewal.blob_id,
+                        reason: SkipReason::Failed(error.to_string()),
+                    });
+                }
+            }
+        }
+        executed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::EncodingType;
+
+    use super::*;
+
+    const CURRENT_EPOCH: Epoch = 10;
+    const STORAGE_SIZE: u64 = 10 * 1024 * 1024;
+
+    fn blob(end_epoch: Epoch, certified: bool, deletable: bool) -> Blob {
+        Blob {
+            id: ObjectID::random(),
+            registered_epoch: 1,
+            blob_id: BlobId([end_epoch as u8; 32]),
+            size: 1024,
+            encoding_type: EncodingType::RS2,
+            certified_epoch: certified.then_some(1),
+            storage: storage(1, end_epoch),
+            deletable,
+        }
+    }
+
+    fn storage(start_epoch: Epoch, end_epoch: Epoch) -> StorageResource {
+        StorageResource {
+            id: ObjectID::random(),
+            start_epoch,
+            end_epoch,
+            storage_size: STORAGE_SIZE,
+        }
+    }
+
+    fn parameters() -> RenewalParameters {
+        RenewalParameters {
+            current_epoch: CURRENT_EPOCH,
+            max_epochs_ahead: 20,
+            storage_price_per_unit_size: 1000,
+        }
+    }
+
+    #[test]
+    fn reports_certified_blobs_expiring_soon() {
+        let blobs = vec![
+            blob(13, true, false),
+            blob(11, true, true),
+            blob(11, false, false),
+            blob(14, true, false),
+            blob(CURRENT_EPOCH, true, false),
+        ];
+
+        let expiring = expiring_blobs(blobs, CURRENT_EPOCH, 3);
+
+        let end_epochs: Vec<_> = expiring
+            .iter()
+            .map(|expiring| (expiring.blob.storage.end_epoch, expiring.epochs_left))
+            .collect();
+        assert_eq!(end_epochs, vec![(11, 1), (13, 3)]);
+    }
+
+    #[test]
+    fn plans_renewals_with_owned_storage_before_payment() {
+        let expiring = expiring_blobs(
+            vec![
+                blob(11, true, false),
+                blob(12, true, true),
+                blob(12, true, false),
+            ],
+            CURRENT_EPOCH,
+            5,
+        );
+        let owned_storage = storage(12, 20);
+        let policy = RenewalPolicy {
+            renew_deletable: false,
+            ..Default::default()
+        };
+
+        let plan = RenewalPlan::new(
+            &expiring,
+            &policy,
+            vec![storage(10, 30), owned_storage.clone()],
+            parameters(),
+        );
+
+        let methods: Vec<_> = plan
+            .renewals
+            .iter()
+            .map(|renewal| (renewal.new_end_epoch, renewal.method.clone()))
+            .collect();
+        let cost = price_for_encoded_length(STORAGE_SIZE, 1000, 5);
+        assert_eq!(
+            methods,
+            vec![
+                (
+                    16,
+                    RenewalMethod::Payment {
+                        epochs_extended: 5,
+                        cost
+                    }
+                ),
+                (
+                    20,
+                    RenewalMethod::OwnedStorage {
+                        storage_object_id: owned_storage.id
+                    }
+                ),
+            ]
+        );
+        assert_eq!(plan.total_cost(), cost);
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.skipped[0].reason, SkipReason::Deletable);
+    }
+
+    #[test]
+    fn respects_budget_and_max_epochs_ahead() {
+        let expiring = expiring_blobs(
+            vec![
+                blob(11, true, false),
+                blob(12, true, false),
+                blob(30, true, false),
+            ],
+            CURRENT_EPOCH,
+            20,
+        );
+        let cost = price_for_encoded_length(STORAGE_SIZE, 1000, 5);
+        let policy = RenewalPolicy {
+            max_total_cost: Some(cost + cost / 2),
+            ..Default::default()
+        };
+
+        let plan = RenewalPlan::new(&expiring, &policy, vec![], parameters());
+
+        assert_eq!(plan.renewals.len(), 1);
+        assert_eq!(plan.renewals[0].new_end_epoch, 16);
+        let reasons: Vec<_> = plan.skipped.iter().map(|skipped| &skipped.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![&SkipReason::BudgetExceeded, &SkipReason::MaxEpochsAhead]
+        );
+    }
+}
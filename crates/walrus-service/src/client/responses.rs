@@ -22,7 +22,6 @@ use sui_types::{
 };
 use utoipa::ToSchema;
 use walrus_core::{
-    bft,
     encoding::{
         encoded_blob_length_for_n_shards,
         encoded_slivers_length_for_n_shards,
@@ -31,8 +30,10 @@ use walrus_core::{
         metadata_length_for_n_shards,
         source_symbols_for_n_shards,
     },
-    metadata::{BlobMetadataApi as _, VerifiedBlobMetadataWithId},
+    merkle::DIGEST_LEN,
+    metadata::BlobIdInfo,
     BlobId,
+    BlobIdEncoding,
     EncodingType,
     Epoch,
     EpochCount,
@@ -57,8 +58,10 @@ use walrus_sui::{
 };
 
 use super::{
-    cli::{BlobIdDecimal, BlobIdentity, HumanReadableBytes},
+    cli::{BlobIdentity, HumanReadableBytes},
     communication::NodeCommunicationFactory,
+    receipt::SignedStoreReceipt,
+    renewal::{ExpiringBlob, RenewalPlan},
     resource::RegisterBlobOp,
 };
 use crate::client::cli::{format_event_id, HealthSortBy, HumanReadableFrost, NodeSortBy, SortBy};
@@ -98,6 +101,18 @@ pub struct BlobStoreResultWithPath {
     pub path: PathBuf,
 }
 
+/// The response of the publisher to a request to store a blob.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobStoreResponse {
+    /// The result of the store operation.
+    #[serde(flatten)]
+    pub blob_store_result: BlobStoreResult,
+    /// The receipt of the store operation, signed if the publisher signs receipts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<SignedStoreReceipt>,
+}
+
 /// Result when attempting to store a blob.
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -133,6 +148,10 @@ pub enum BlobStoreResult {
         #[serde(skip_serializing_if = "Option::is_none")]
         #[schema(value_type = Option<ObjectIdSchema>)]
         shared_blob_object: Option<ObjectID>,
+        /// The event where the blob was certified, if it was certified in this store operation.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[schema(value_type = Option<EventIdSchema>)]
+        certification_event: Option<EventID>,
     },
     /// The blob is known to Walrus but was marked as invalid.
     ///
@@ -204,30 +223,68 @@ pub(crate) struct BlobIdOutput {
     pub(crate) blob_id: BlobId,
     pub(crate) file: PathBuf,
     pub(crate) unencoded_length: u64,
+    pub(crate) encoded_length: u64,
     pub(crate) encoding_type: EncodingType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) sliver_roots: Option<Vec<SliverRootsOutput>>,
 }
 
 impl BlobIdOutput {
     /// Creates a new [`BlobIdOutput`] object.
-    pub fn new(file: &Path, metadata: &VerifiedBlobMetadataWithId) -> Self {
+    ///
+    /// The sliver roots are only included if `include_sliver_roots` is true.
+    pub fn new(file: &Path, info: BlobIdInfo, include_sliver_roots: bool) -> Self {
         Self {
-            blob_id: *metadata.blob_id(),
+            blob_id: info.blob_id,
             file: file.to_owned(),
-            unencoded_length: metadata.metadata().unencoded_length(),
-            encoding_type: metadata.metadata().encoding_type(),
+            unencoded_length: info.unencoded_length,
+            encoded_length: info.encoded_length,
+            encoding_type: info.encoding_type,
+            sliver_roots: include_sliver_roots.then(|| {
+                info.sliver_roots
+                    .iter()
+                    .map(|pair| SliverRootsOutput {
+                        primary: pair.primary_hash.bytes(),
+                        secondary: pair.secondary_hash.bytes(),
+                    })
+                    .collect()
+            }),
         }
     }
 }
 
-/// The output of the `convert-blob-id` command.
+/// The roots of the Merkle trees over the slivers of a sliver pair.
 #[serde_as]
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct BlobIdConversionOutput(#[serde_as(as = "DisplayFromStr")] pub BlobId);
+pub(crate) struct SliverRootsOutput {
+    /// The root over the primary sliver.
+    #[serde_as(as = "Base64")]
+    pub(crate) primary: [u8; DIGEST_LEN],
+    /// The root over the secondary sliver.
+    #[serde_as(as = "Base64")]
+    pub(crate) secondary: [u8; DIGEST_LEN],
+}
 
-impl From<BlobIdDecimal> for BlobIdConversionOutput {
-    fn from(value: BlobIdDecimal) -> Self {
-        Self(value.into())
+/// The output of the `convert-blob-id` command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BlobIdConversionOutput {
+    /// The blob ID in the URL-safe base64 encoding used by Walrus.
+    pub blob_id: String,
+    /// The blob ID in hex.
+    pub hex: String,
+    /// The blob ID as a decimal number.
+    pub decimal: String,
+}
+
+impl From<BlobId> for BlobIdConversionOutput {
+    fn from(blob_id: BlobId) -> Self {
+        Self {
+            blob_id: blob_id.to_string_with_encoding(BlobIdEncoding::Base64),
+            hex: blob_id.to_string_with_encoding(BlobIdEncoding::Hex),
+            decimal: blob_id.to_string_with_encoding(BlobIdEncoding::Decimal),
+        }
     }
 }
 
@@ -251,6 +308,314 @@ pub(crate) struct DryRunOutput {
     pub encoding_type: EncodingType,
 }
 
+/// The output of the `store-dir` command.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreDirOutput {
+    /// The result of storing the manifest blob of the directory.
+    pub manifest: BlobStoreResult,
+    /// The results of storing the files in the directory.
+    pub files: Vec<BlobStoreResultWithPath>,
+}
+
+/// The output of the `store-many` command.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreManyOutput {
+    /// The outcome for each file matching the pattern, in the order of the paths.
+    pub files: Vec<StoreManyFileOutput>,
+}
+
+impl StoreManyOutput {
+    /// Returns the number of files that could not be stored or whose blobs were marked as
+    /// invalid.
+    pub fn failed_count(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| {
+                matches!(
+                    file.status,
+                    StoreManyStatus::Failed { .. } | StoreManyStatus::MarkedInvalid
+                )
+            })
+            .count()
+    }
+}
+
+/// The outcome of storing a single file with the `store-many` command.
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreManyFileOutput {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The blob ID of the file, if it was stored.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub blob_id: Option<BlobId>,
+    /// The storage cost paid for the file, excluding gas.
+    pub cost: u64,
+    /// The status of the file.
+    pub status: StoreManyStatus,
+}
+
+/// The status of a file stored with the `store-many` command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub(crate) enum StoreManyStatus {
+    /// The blob was newly certified, or the storage of an existing blob was extended.
+    NewlyCreated,
+    /// The blob was already certified for a sufficient number of epochs.
+    AlreadyCertified,
+    /// The file has the same contents as another file, with which it was stored.
+    Duplicate {
+        /// The path of the file with the same contents that was stored.
+        original: PathBuf,
+    },
+    /// The blob was marked as invalid.
+    MarkedInvalid,
+    /// The file could not be stored.
+    Failed {
+        /// The error that occurred while storing the file.
+        error: String,
+    },
+}
+
+impl StoreManyFileOutput {
+    /// Returns the output for a file that could not be stored.
+    pub fn failed(path: PathBuf, error: impl Display) -> Self {
+        Self {
+            path,
+            blob_id: None,
+            cost: 0,
+            status: StoreManyStatus::Failed {
+                error: error.to_string(),
+            },
+        }
+    }
+
+    /// Returns the output for a file with the same contents as the file of `self`, which is
+    /// located at `original`.
+    ///
+    /// If the original file could not be stored, neither could the duplicate.
+    pub fn duplicate(&self, path: PathBuf, original: PathBuf) -> Self {
+        match &self.status {
+            StoreManyStatus::Failed { .. } => Self {
+                path,
+                ..self.clone()
+            },
+            _ => Self {
+                path,
+                blob_id: self.blob_id,
+                cost: 0,
+                status: StoreManyStatus::Duplicate { original },
+            },
+        }
+    }
+}
+
+impl From<BlobStoreResultWithPath> for StoreManyFileOutput {
+    fn from(result: BlobStoreResultWithPath) -> Self {
+        let blob_id = Some(*result.blob_store_result.blob_id());
+        let (cost, status) = match result.blob_store_result {
+            BlobStoreResult::NewlyCreated { cost, .. } => (cost, StoreManyStatus::NewlyCreated),
+            BlobStoreResult::AlreadyCertified { .. } => (0, StoreManyStatus::AlreadyCertified),
+            BlobStoreResult::MarkedInvalid { .. } => (0, StoreManyStatus::MarkedInvalid),
+        };
+        Self {
+            path: result.path,
+            blob_id,
+            cost,
+            status,
+        }
+    }
+}
+
+/// Information about a certified blob that can be determined without reading it.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobReadInfo {
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// The size of the blob in bytes, as returned when reading it.
+    pub unencoded_length: u64,
+    /// The encoding type used for the blob.
+    pub encoding_type: EncodingType,
+    /// The epoch in which the blob was initially certified.
+    pub certified_epoch: Epoch,
+    /// The blob's status, including its end epoch.
+    pub status: BlobStatus,
+}
+
+/// The certification status of a blob, which can be determined without reading the blob or its
+/// metadata.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobCertificationStatus {
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// Whether the blob is certified and can therefore be read.
+    pub certified: bool,
+    /// The epoch in which the blob was initially certified, if it is certified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certified_epoch: Option<Epoch>,
+    /// The scheduled start of the epoch in which the blob was initially certified, if it is
+    /// certified, as derived from the on-chain system parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certified_epoch_start: Option<DateTime<Utc>>,
+    /// The epoch at which the blob expires (exclusive), present only for permanent blobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_epoch: Option<Epoch>,
+    /// The estimated expiry timestamp of the blob, present only for permanent blobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_expiry_timestamp: Option<DateTime<Utc>>,
+    /// The ID of the Sui event marking the blob as invalid, if it was invalidated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalidation_event: Option<EventID>,
+    /// The blob's status as reported by the storage nodes.
+    pub status: BlobStatus,
+}
+
+impl BlobCertificationStatus {
+    /// Creates the certification status of the blob from its verified status.
+    pub fn new(
+        blob_id: BlobId,
+        status: BlobStatus,
+        certified_epoch_start: Option<DateTime<Utc>>,
+        estimated_expiry_timestamp: Option<DateTime<Utc>>,
+    ) -> Self {
+        let certified_epoch = status.initial_certified_epoch();
+        let end_epoch = match status {
+            BlobStatus::Permanent { end_epoch, .. } => Some(end_epoch),
+            _ => None,
+        };
+        let invalidation_event = match status {
+            BlobStatus::Invalid { event } => Some(event),
+            _ => None,
+        };
+        Self {
+            blob_id,
+            certified: certified_epoch.is_some(),
+            certified_epoch,
+            certified_epoch_start,
+            end_epoch,
+            estimated_expiry_timestamp,
+            invalidation_event,
+            status,
+        }
+    }
+}
+
+/// Whether a storage node stores the metadata of a blob and the slivers for its shards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredSlivers {
+    /// Whether the node stores the blob's metadata.
+    pub metadata_stored: bool,
+    /// The number of the node's shards for which the node stores the primary sliver.
+    pub primary_slivers: usize,
+    /// The number of the node's shards for which the node stores the secondary sliver.
+    pub secondary_slivers: usize,
+}
+
+/// The availability of a blob on a storage node of the current committee.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeBlobAvailability {
+    /// The ID of the storage node.
+    pub node_id: ObjectID,
+    /// The name of the storage node.
+    pub node_name: String,
+    /// The network address of the storage node.
+    pub node_url: String,
+    /// The number of shards owned by the node.
+    pub n_shards: usize,
+    /// The metadata and slivers stored by the node, or the error returned when querying them.
+    pub stored_slivers: Result<StoredSlivers, String>,
+}
+
+impl NodeBlobAvailability {
+    /// Creates the availability of the blob on the node from the result of the query.
+    pub fn new(node: &StorageNode, stored_slivers: Result<StoredSlivers, String>) -> Self {
+        Self {
+            node_id: node.node_id,
+            node_name: node.name.clone(),
+            node_url: node.network_address.0.clone(),
+            n_shards: node.shard_ids.len(),
+            stored_slivers,
+        }
+    }
+}
+
+/// The availability of a blob's metadata and slivers across the current committee.
+///
+/// This is the output of the `walrus health <BLOB_ID>` command.
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobAvailability {
+    /// The blob ID.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// The epoch of the committee that was queried.
+    pub epoch: Epoch,
+    /// Whether verified metadata for the blob could be retrieved from the storage nodes.
+    pub metadata_retrievable: bool,
+    /// The number of shards for which the primary sliver is stored.
+    pub primary_slivers: usize,
+    /// The number of primary slivers required to read the blob.
+    pub primary_slivers_required: usize,
+    /// The number of shards for which the secondary sliver is stored.
+    pub secondary_slivers: usize,
+    /// The number of secondary slivers required to read the blob.
+    pub secondary_slivers_required: usize,
+    /// Whether enough metadata and slivers are available to read the blob.
+    pub read_quorum: bool,
+    /// The availability of the blob on each queried storage node.
+    pub nodes: Vec<NodeBlobAvailability>,
+}
+
+impl BlobAvailability {
+    /// Aggregates the availability of the blob on the individual storage nodes.
+    pub fn new(
+        blob_id: BlobId,
+        epoch: Epoch,
+        metadata_retrievable: bool,
+        primary_slivers_required: usize,
+        secondary_slivers_required: usize,
+        nodes: Vec<NodeBlobAvailability>,
+    ) -> Self {
+        let stored_slivers = || {
+            nodes
+                .iter()
+                .filter_map(|node| node.stored_slivers.as_ref().ok())
+        };
+        let primary_slivers = stored_slivers()
+            .map(|slivers| slivers.primary_slivers)
+            .sum();
+        let secondary_slivers = stored_slivers()
+            .map(|slivers| slivers.secondary_slivers)
+            .sum();
+        let read_quorum = metadata_retrievable
+            && (primary_slivers >= primary_slivers_required
+                || secondary_slivers >= secondary_slivers_required);
+        Self {
+            blob_id,
+            epoch,
+            metadata_retrievable,
+            primary_slivers,
+            primary_slivers_required,
+            secondary_slivers,
+            secondary_slivers_required,
+            read_quorum,
+            nodes,
+        }
+    }
+}
+
 /// The output of the `blob-status` command.
 #[serde_as]
 #[derive(Debug, Clone, Serialize)]
@@ -363,15 +728,21 @@ impl InfoEpochOutput {
 pub(crate) struct InfoStorageOutput {
     pub(crate) n_shards: NonZeroU16,
     pub(crate) n_nodes: usize,
+    pub(crate) total_capacity_size: u64,
+    pub(crate) used_capacity_size: u64,
 }
 
 impl InfoStorageOutput {
     pub async fn get_storage_info(sui_read_client: &impl ReadClient) -> anyhow::Result<Self> {
         let committee = sui_read_client.current_committee().await?;
+        let (total_capacity_size, used_capacity_size) =
+            sui_read_client.total_and_used_capacity_size().await?;
 
         Ok(Self {
             n_shards: committee.n_shards(),
             n_nodes: committee.n_members(),
+            total_capacity_size,
+            used_capacity_size,
         })
     }
 }
@@ -582,15 +953,11 @@ pub(crate) struct InfoBftOutput {
 impl InfoBftOutput {
     pub async fn get_bft_info(sui_read_client: &impl ReadClient) -> anyhow::Result<Self> {
         let committee = sui_read_client.current_committee().await?;
-        let n_shards = committee.n_shards();
-        let f = bft::max_n_faulty(n_shards);
-        let min_correct_shards = n_shards.get() - f;
-        let quorum_threshold = 2 * f + 1;
         let (min_nodes_above, shards_above) = committee.min_nodes_above_f();
         Ok(Self {
-            max_faulty_shards: f,
-            min_correct_shards,
-            quorum_threshold,
+            max_faulty_shards: committee.f(),
+            min_correct_shards: committee.n_shards().get() - committee.f(),
+            quorum_threshold: committee.quorum_threshold(),
             min_nodes_above,
             shards_above,
         })
@@ -713,6 +1080,18 @@ pub struct StakeOutput {
     pub staked_wal: Vec<StakedWal>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The output of the `walrus unstake` command.
+pub struct UnstakeOutput {
+    /// The object ID of the staked WAL.
+    pub staked_wal_id: ObjectID,
+    /// The amount of FROST withdrawn, if the stake was withdrawn.
+    pub withdrawn_amount: Option<u64>,
+    /// The epoch in which the stake can be withdrawn, if the withdrawal was requested.
+    pub withdrawal_epoch: Option<Epoch>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 /// The output of the `walrus generate-sui-wallet` command.
@@ -763,6 +1142,20 @@ pub struct ExtendBlobOutput {
     pub epochs_extended: EpochCount,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The output of the `walrus renew` command.
+pub(crate) struct RenewOutput {
+    /// The current epoch.
+    pub current_epoch: Epoch,
+    /// The number of epochs within which the reported blobs expire.
+    pub within_epochs: EpochCount,
+    /// The blobs that expire within the requested number of epochs.
+    pub expiring: Vec<ExpiringBlob>,
+    /// The renewals performed according to the policy, if a policy was provided.
+    pub renewal: Option<RenewalPlan>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 /// The health information of a storage node.
@@ -867,3 +1260,51 @@ impl NodeHealthOutput {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::test_utils::random_blob_id;
+
+    use super::*;
+
+    fn stored_file(path: &str, status: StoreManyStatus) -> StoreManyFileOutput {
+        StoreManyFileOutput {
+            path: path.into(),
+            blob_id: Some(random_blob_id()),
+            cost: 42,
+            status,
+        }
+    }
+
+    #[test]
+    fn duplicate_of_stored_file_refers_to_original_without_cost() {
+        let original = stored_file("a", StoreManyStatus::NewlyCreated);
+
+        let duplicate = original.duplicate("b".into(), "a".into());
+
+        assert_eq!(duplicate.path, PathBuf::from("b"));
+        assert_eq!(duplicate.blob_id, original.blob_id);
+        assert_eq!(duplicate.cost, 0);
+        assert!(matches!(
+            duplicate.status,
+            StoreManyStatus::Duplicate { original } if original == Path::new("a")
+        ));
+    }
+
+    #[test]
+    fn duplicate_of_failed_file_fails() {
+        let original = StoreManyFileOutput::failed("a".into(), "out of gas");
+
+        let duplicate = original.duplicate("b".into(), "a".into());
+        let output = StoreManyOutput {
+            files: vec![original, duplicate],
+        };
+
+        assert_eq!(output.files[1].path, PathBuf::from("b"));
+        assert!(matches!(
+            &output.files[1].status,
+            StoreManyStatus::Failed { error } if error == "out of gas"
+        ));
+        assert_eq!(output.failed_count(), 2);
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Manifests of directory trees stored on Walrus.
+//!
+//! A directory is stored as one blob per file plus a manifest blob, which maps the path of each
+//! file relative to the directory to its blob ID and content type. Aggregators resolve paths
+//! within a stored directory through its manifest, which allows serving static sites.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+use moka::future::Cache;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use walrus_core::BlobId;
+
+/// The name of the file that is served for paths referring to a directory.
+const INDEX_FILE_NAME: &str = "index.html";
+/// The maximum number of parsed manifests that an aggregator keeps in memory.
+const MANIFEST_CACHE_CAPACITY: u64 = 1_000;
+/// The duration for which a parsed manifest is kept in memory.
+///
+/// Manifest blobs never change, but they may be deleted or blocked after being cached.
+const MANIFEST_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// An in-memory cache of parsed manifests, keyed by the ID of the manifest blob.
+pub(crate) type SiteManifestCache = Cache<BlobId, Arc<SiteManifest>>;
+
+/// Creates an empty [`SiteManifestCache`].
+pub(crate) fn new_manifest_cache() -> SiteManifestCache {
+    Cache::builder()
+        .name("site_manifest_cache")
+        .max_capacity(MANIFEST_CACHE_CAPACITY)
+        .time_to_live(MANIFEST_CACHE_TTL)
+        .build()
+}
+
+/// The manifest of a directory tree stored on Walrus.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SiteManifest {
+    /// The files in the directory, keyed by their `/`-separated path relative to the directory.
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// A file in a [`SiteManifest`].
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ManifestEntry {
+    /// The ID of the blob holding the contents of the file.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// The content type of the file.
+    pub content_type: String,
+}
+
+impl SiteManifest {
+    /// Parses a manifest from the contents of a manifest blob.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(bytes)
+    }
+
+    /// Returns the contents of the manifest blob.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("the manifest can always be serialized")
+    }
+
+    /// Returns the entry of the file at the given path.
+    ///
+    /// Paths that are empty or end with a `/`, as well as paths of directories, resolve to the
+    /// `index.html` file in the directory.
+    pub(crate) fn resolve(&self, path: &str) -> Option<&ManifestEntry> {
+        let path = path.trim_start_matches('/');
+        if path.is_empty() || path.ends_with('/') {
+            return self.files.get(&format!("{path}{INDEX_FILE_NAME}"));
+        }
+        self.files
+            .get(path)
+            .or_else(|| self.files.get(&format!("{path}/{INDEX_FILE_NAME}")))
+    }
+}
+
+/// Returns the files in the directory tree, with their `/`-separated paths relative to the
+/// directory, ordered by path.
+///
+/// Symbolic links are skipped, such that no files outside the directory are stored.
+pub(crate) fn collect_files(directory: &Path) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![directory.to_owned()];
+    while let Some(current) = pending.pop() {
+        let entries = std::fs::read_dir(&current)
+            .with_context(|| format!("unable to read directory {}", current.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                tracing::warn!(path = %path.display(), "skipping symbolic link");
+                continue;
+            }
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let relative_path = path
+                .strip_prefix(directory)
+                .expect("all paths are within the directory")
+                .components()
+                .map(|component| {
+                    component
+                        .as_os_str()
+                        .to_str()
+                        .map(str::to_owned)
+                        .with_context(|| format!("the path {} is not valid UTF-8", path.display()))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .join("/");
+            files.push((relative_path, path));
+        }
+    }
+    if files.is_empty() {
+        bail!(
+            "the directory {} does not contain any files",
+            directory.display()
+        );
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the content type of a file based on the extension in its path.
+///
+/// Files with unknown extensions are assigned `application/octet-stream`.
+pub(crate) fn content_type_for_path(path: &str) -> &'static str {
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" | "map" => "application/json",
+        "txt" => "text/plain; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "xml" => "application/xml",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use walrus_core::test_utils::random_blob_id;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    fn entry() -> ManifestEntry {
+        ManifestEntry {
+            blob_id: random_blob_id(),
+            content_type: "text/html; charset=utf-8".to_owned(),
+        }
+    }
+
+    #[test]
+    fn resolves_files_and_directory_indices() {
+        let manifest = SiteManifest {
+            files: [
+                ("index.html".to_owned(), entry()),
+                ("docs/index.html".to_owned(), entry()),
+                ("docs/guide.html".to_owned(), entry()),
+            ]
+            .into(),
+        };
+        let file = |path: &str| manifest.files.get(path);
+
+        assert_eq!(manifest.resolve(""), file("index.html"));
+        assert_eq!(manifest.resolve("/"), file("index.html"));
+        assert_eq!(manifest.resolve("docs"), file("docs/index.html"));
+        assert_eq!(manifest.resolve("docs/"), file("docs/index.html"));
+        assert_eq!(
+            manifest.resolve("/docs/guide.html"),
+            file("docs/guide.html")
+        );
+        assert_eq!(manifest.resolve("docs/missing.html"), None);
+
+        let bytes = manifest.to_bytes();
+        assert_eq!(SiteManifest::from_bytes(&bytes).unwrap(), manifest);
+    }
+
+    #[test]
+    fn collects_files_recursively() -> TestResult {
+        let directory = TempDir::new()?;
+        std::fs::create_dir_all(directory.path().join("assets/images"))?;
+        std::fs::write(directory.path().join("index.html"), "<html></html>")?;
+        std::fs::write(directory.path().join("assets/images/logo.png"), [0u8; 4])?;
+
+        let files = collect_files(directory.path())?;
+        let paths: Vec<_> = files.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["assets/images/logo.png", "index.html"]);
+        assert_eq!(content_type_for_path(paths[0]), "image/png");
+        assert_eq!(content_type_for_path("LICENSE"), "application/octet-stream");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skips_symbolic_links() -> TestResult {
+        let outside = TempDir::new()?;
+        std::fs::create_dir(outside.path().join("secrets"))?;
+        std::fs::write(outside.path().join("secrets/key"), "secret")?;
+
+        let directory = TempDir::new()?;
+        std::fs::write(directory.path().join("index.html"), "<html></html>")?;
+        std::os::unix::fs::symlink(
+            outside.path().join("secrets/key"),
+            directory.path().join("key"),
+        )?;
+        std::os::unix::fs::symlink(
+            outside.path().join("secrets"),
+            directory.path().join("secrets"),
+        )?;
+
+        let files = collect_files(directory.path())?;
+        let paths: Vec<_> = files.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["index.html"]);
+
+        Ok(())
+    }
+}
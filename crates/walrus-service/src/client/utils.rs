@@ -9,7 +9,13 @@ use std::{
 };
 
 use anyhow::Result;
-use futures::{stream::FuturesUnordered, Future, StreamExt};
+use bytes::Bytes;
+use futures::{
+    stream::{self, FuturesUnordered},
+    Future,
+    Stream,
+    StreamExt,
+};
 use tokio::time;
 use tracing::Level;
 
@@ -321,6 +327,49 @@ impl From<CompletedReasonWeight> for CompletedReason {
     }
 }
 
+/// Returns a stream of the chunks of a blob that only yields the last chunk once the complete blob
+/// has been received and accepted by `verify`.
+///
+/// The stream yields the error returned by `verify` instead of the last chunk if the blob is
+/// rejected, and ends after the first error of the inner stream. A consumer therefore never
+/// receives all data of a blob that is incomplete or fails verification. The received data is
+/// kept until the inner stream ends, such that it can be verified.
+pub(crate) fn hold_back_until_verified<S, E, F>(
+    chunks: S,
+    verify: F,
+) -> impl Stream<Item = Result<Bytes, E>>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    F: FnOnce(&[u8]) -> Result<(), E>,
+{
+    let state = (chunks, Vec::new(), None::<Bytes>, Some(verify));
+    stream::unfold(
+        state,
+        |(mut chunks, mut received, mut pending, mut verify)| async move {
+            let verify_fn = verify.take()?;
+            loop {
+                match chunks.next().await {
+                    Some(Ok(chunk)) => {
+                        received.extend_from_slice(&chunk);
+                        if let Some(previous) = pending.replace(chunk) {
+                            return Some((
+                                Ok(previous),
+                                (chunks, received, pending, Some(verify_fn)),
+                            ));
+                        }
+                    }
+                    Some(Err(error)) => return Some((Err(error), (chunks, received, None, None))),
+                    None => {
+                        let last =
+                            verify_fn(&received).map(|()| pending.take().unwrap_or_default());
+                        return Some((last, (chunks, Vec::new(), None, None)));
+                    }
+                }
+            }
+        },
+    )
+}
+
 /// Returns the first 8 characters of the string representing the object.
 pub fn string_prefix<T: ToString>(s: &T) -> String {
     let mut string = s.to_string();
@@ -393,6 +442,58 @@ mod tests {
         );
     }
 
+    fn chunks(items: Vec<Result<&'static [u8]>>) -> impl Stream<Item = Result<Bytes>> + Unpin {
+        stream::iter(items.into_iter().map(|item| item.map(Bytes::from_static)))
+    }
+
+    fn expect_blob(expected: &'static [u8]) -> impl FnOnce(&[u8]) -> Result<()> {
+        move |blob| {
+            anyhow::ensure!(blob == expected, "the blob is inconsistent");
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn verified_stream_yields_all_chunks_of_valid_blob() {
+        let stream = hold_back_until_verified(
+            chunks(vec![Ok(&b"ab"[..]), Ok(&b"cd"[..]), Ok(&b"e"[..])]),
+            expect_blob(b"abcde"),
+        );
+        let received: Vec<_> = stream.map(|chunk| chunk.unwrap()).collect().await;
+        assert_eq!(received, [&b"ab"[..], &b"cd"[..], &b"e"[..]]);
+    }
+
+    #[tokio::test]
+    async fn verified_stream_holds_back_last_chunk_of_inconsistent_blob() {
+        let stream = hold_back_until_verified(
+            chunks(vec![Ok(&b"ab"[..]), Ok(&b"cd"[..]), Ok(&b"x"[..])]),
+            expect_blob(b"abcde"),
+        );
+        let received: Vec<_> = stream.collect().await;
+        assert_eq!(received.len(), 3);
+        assert_eq!(received[0].as_ref().unwrap(), &b"ab"[..]);
+        assert_eq!(received[1].as_ref().unwrap(), &b"cd"[..]);
+        assert!(received[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn verified_stream_ends_at_first_error() {
+        let stream = hold_back_until_verified(
+            chunks(vec![
+                Ok(&b"ab"[..]),
+                Ok(&b"cd"[..]),
+                Err(anyhow::anyhow!("sliver unavailable")),
+                Ok(&b"e"[..]),
+            ]),
+            expect_blob(b"abcde"),
+        );
+        let received: Vec<_> = stream.collect().await;
+        // The data received before the error is incomplete: the last chunk is never released.
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].as_ref().unwrap(), &b"ab"[..]);
+        assert!(received[1].is_err());
+    }
+
     #[tokio::test(start_paused = true)]
     async fn test_execute_time_n_concurrent() {
         create_weighted_futures!(weighted_futures, &[1, 1, 1, 1, 1]);
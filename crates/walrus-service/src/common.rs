@@ -5,6 +5,7 @@
 
 pub(crate) mod active_committees;
 pub(crate) mod api;
+pub(crate) mod balance_monitor;
 pub(crate) mod blocklist;
 pub mod config;
 pub(crate) mod telemetry;
@@ -3,7 +3,7 @@
 
 use std::{cmp::Ordering, collections::HashSet, mem, num::NonZeroU16, sync::Arc};
 
-use walrus_core::{ensure, Epoch, NetworkPublicKey};
+use walrus_core::{ensure, Epoch, NetworkPublicKey, PublicKey};
 use walrus_sui::{
     client::CommitteesAndState,
     types::{Committee, NetworkAddress},
@@ -102,6 +102,26 @@ impl ActiveCommittees {
         this
     }
 
+    /// Construct a new set of `ActiveCommittees`, allowing to set all fields.
+    ///
+    /// Returns an error instead of panicking if the committees are inconsistent, see
+    /// [`Self::new_with_next`].
+    pub fn try_new_with_next(
+        current_committee: Arc<Committee>,
+        previous_committee: Option<Arc<Committee>>,
+        next_committee: Option<Arc<Committee>>,
+        is_transitioning: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let this = Self {
+            current_committee,
+            previous_committee,
+            next_committee,
+            is_transitioning,
+        };
+        this.try_check_invariants()?;
+        Ok(this)
+    }
+
     /// Construct a new set of `ActiveCommittees` from the [`CommitteesAndState`] returned by the
     /// [`ReadClient`].
     ///
@@ -228,41 +248,26 @@ impl ActiveCommittees {
             .expect("ActiveCommittee's invariants must be upheld");
     }
 
-    /// Checks if the number is larger or equal to the minimum number of correct shards.
+    /// Returns the weight, in shards, required for a quorum.
     ///
     /// Given the invariants enforced by this struct, the result of this function is the same for
     /// all committees.
     ///
-    /// See [`min_n_correct`][Self::min_n_correct] for further details.
+    /// See [`Committee::quorum_threshold`] for further details.
     #[inline]
-    #[allow(dead_code)]
-    pub fn is_at_least_min_n_correct(&self, num: usize) -> bool {
-        self.current_committee.is_at_least_min_n_correct(num)
+    pub fn quorum_threshold(&self) -> u16 {
+        self.current_committee.quorum_threshold()
     }
 
-    /// Returns the minimum number of correct shards.
+    /// Checks if the weight, in shards, is large enough to reach a quorum.
     ///
     /// Given the invariants enforced by this struct, the result of this function is the same for
     /// all committees.
     ///
-    /// This is (`n_shards - f`), where `f` is the maximum number of faulty shards, given
-    /// `n_shards`. See [walrus_core::bft] for further details.
+    /// See [`Committee::is_quorum`] for further details.
     #[inline]
-    #[allow(dead_code)]
-    pub fn min_n_correct(&self) -> usize {
-        self.current_committee.min_n_correct()
-    }
-
-    /// Checks if the number is large enough to reach a quorum (`2f + 1`).
-    ///
-    /// Given the invariants enforced by this struct, the result of this function is the same for
-    /// all committees.
-    ///
-    /// `f` is the maximum number of faulty shards, given `n_shards`. See [walrus_core::bft] for
-    /// further details.
-    #[inline]
-    pub fn is_quorum(&self, num: usize) -> bool {
-        self.current_committee.is_quorum(num)
+    pub fn is_quorum(&self, weight: usize) -> bool {
+        self.current_committee.is_quorum(weight)
     }
 
     /// Checks if the number is larger or equal to the validity threshold
@@ -294,6 +299,22 @@ impl ActiveCommittees {
         }
         members
     }
+
+    /// Returns the set of unique public keys of the members in the committees.
+    ///
+    /// The function returns the union over the public keys of the members in previous, current,
+    /// and next, committees.
+    pub fn unique_node_public_keys(&self) -> HashSet<&PublicKey> {
+        [
+            Some(&self.current_committee),
+            self.previous_committee(),
+            self.next_committee(),
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|committee| committee.members().iter().map(|member| &member.public_key))
+        .collect()
+    }
 }
 
 impl TryFrom<CommitteesAndState> for ActiveCommittees {
@@ -17,7 +17,7 @@ use axum::{
 };
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, DisplayFromStr};
+use serde_with::serde_as;
 use utoipa::{
     openapi::{
         schema,
@@ -29,13 +29,18 @@ use utoipa::{
     },
     ToSchema,
 };
-use walrus_core::BlobId;
-use walrus_sdk::api::errors::{ErrorInfo, Status, StatusCode as ApiStatusCode};
+use walrus_core::{BlobId, BlobIdFromAnyEncoding};
+use walrus_sdk::{
+    api::errors::{ErrorInfo, Status, StatusCode as ApiStatusCode},
+    request_id,
+};
 
 /// A blob ID encoded as a URL-safe Base64 string, without the trailing equal (=) signs.
+///
+/// When deserializing, blob IDs in hex or in decimal format are accepted as well.
 #[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
-pub(crate) struct BlobIdString(#[serde_as(as = "DisplayFromStr")] pub(crate) BlobId);
+pub(crate) struct BlobIdString(#[serde_as(as = "BlobIdFromAnyEncoding")] pub(crate) BlobId);
 
 /// Successful API response body as JSON.
 ///
@@ -115,14 +120,27 @@ pub(crate) trait RestApiError: Sized + std::fmt::Display {
         self.to_string()
     }
 
-    /// Converts the error into a [`Response`].
-    fn to_response(&self) -> Response {
+    /// Whether the same request may succeed if it is retried later.
+    ///
+    /// Defaults to whether the status code of the error is retryable.
+    fn retryable(&self) -> bool {
+        self.status_code().is_retryable()
+    }
+
+    /// Converts the error into the [`Status`] returned in the body of error responses.
+    fn to_status(&self) -> Status {
         let info = ErrorInfo::new(self.reason(), self.domain());
         let mut status = Status::new(self.status_code(), self.message(), info);
+        status.set_retryable(self.retryable());
+        status.set_request_id(request_id::current().map(|request_id| request_id.to_string()));
 
         self.add_details(&mut status);
+        status
+    }
 
-        (self.status_code().http_code(), Json(status)).into_response()
+    /// Converts the error into a [`Response`].
+    fn to_response(&self) -> Response {
+        (self.status_code().http_code(), Json(self.to_status())).into_response()
     }
 }
 
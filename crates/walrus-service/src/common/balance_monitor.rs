@@ -0,0 +1,142 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Background monitoring of the SUI and WAL balances of the wallet used to submit transactions.
+
+use std::{sync::Arc, time::Duration};
+
+use prometheus::core::{AtomicU64, GenericGaugeVec};
+use sui_types::base_types::SuiAddress;
+use tokio::time::MissedTickBehavior;
+use tracing::Instrument as _;
+use walrus_sui::client::{CoinType, SuiReadClient};
+
+type UIntGaugeVec = GenericGaugeVec<AtomicU64>;
+
+walrus_utils::metrics::define_metric_set! {
+    #[namespace = "walrus"]
+    pub(crate) struct BalanceMonitorMetrics {
+        #[help = "The observed balance (in MIST) of a SUI address"]
+        sui_balance_mist: UIntGaugeVec["sui_address"],
+        #[help = "The observed WAL balance (in FROST) of a SUI address"]
+        wal_balance_frost: UIntGaugeVec["sui_address"],
+    }
+}
+
+/// Balances below which the monitor logs warnings and errors, respectively.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct BalanceThresholds {
+    /// A balance at or below this value triggers a warning.
+    pub warning: Option<u64>,
+    /// A balance at or below this value triggers an error.
+    pub error: Option<u64>,
+}
+
+/// The severity of a balance with respect to its [`BalanceThresholds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BalanceStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl BalanceThresholds {
+    fn status(&self, balance: u64) -> BalanceStatus {
+        if self.error.is_some_and(|threshold| balance <= threshold) {
+            BalanceStatus::Error
+        } else if self.warning.is_some_and(|threshold| balance <= threshold) {
+            BalanceStatus::Warning
+        } else {
+            BalanceStatus::Ok
+        }
+    }
+}
+
+/// Configuration of the balance monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BalanceMonitorConfig {
+    /// The interval at which the balances are queried.
+    pub interval: Duration,
+    /// The thresholds for the SUI balance, in MIST.
+    pub sui: BalanceThresholds,
+    /// The thresholds for the WAL balance, in FROST.
+    pub wal: BalanceThresholds,
+}
+
+/// Periodically queries the SUI and WAL balances of `address`, exports them as metrics, and logs
+/// them, raising warnings and errors when they fall below the configured thresholds.
+///
+/// Runs until the future is dropped or the task is aborted.
+pub(crate) async fn monitor_balances(
+    config: BalanceMonitorConfig,
+    metrics: BalanceMonitorMetrics,
+    read_client: Arc<SuiReadClient>,
+    address: SuiAddress,
+) {
+    tracing::info!(?config, %address, "starting monitor for SUI and WAL balances");
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let address_label = address.to_string();
+
+    loop {
+        let _now = interval.tick().await;
+        let span = tracing::info_span!("check_balances", sui.address = %address);
+
+        async {
+            for (coin_type, thresholds, gauge) in [
+                (CoinType::Sui, &config.sui, &metrics.sui_balance_mist),
+                (CoinType::Wal, &config.wal, &metrics.wal_balance_frost),
+            ] {
+                tracing::trace!(?coin_type, "querying wallet balance");
+                let balance = match read_client.balance(address, coin_type).await {
+                    Ok(balance) => balance,
+                    Err(error) => {
+                        tracing::warn!(?error, ?coin_type, "failed to get balance, skipping");
+                        continue;
+                    }
+                };
+                tracing::info!(?coin_type, %balance, "retrieved balance");
+                walrus_utils::with_label!(gauge, address_label).set(balance);
+
+                match thresholds.status(balance) {
+                    BalanceStatus::Ok => (),
+                    BalanceStatus::Warning => tracing::warn!(
+                        ?coin_type,
+                        %balance,
+                        ?thresholds,
+                        "balance is below the warning threshold, please top up your account"
+                    ),
+                    BalanceStatus::Error => tracing::error!(
+                        ?coin_type,
+                        %balance,
+                        ?thresholds,
+                        "balance is below the error threshold, transactions may start failing; \
+                        please top up your account"
+                    ),
+                }
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_balances_by_threshold() {
+        let thresholds = BalanceThresholds {
+            warning: Some(100),
+            error: Some(10),
+        };
+        assert_eq!(thresholds.status(101), BalanceStatus::Ok);
+        assert_eq!(thresholds.status(100), BalanceStatus::Warning);
+        assert_eq!(thresholds.status(11), BalanceStatus::Warning);
+        assert_eq!(thresholds.status(10), BalanceStatus::Error);
+        assert_eq!(thresholds.status(0), BalanceStatus::Error);
+
+        assert_eq!(BalanceThresholds::default().status(0), BalanceStatus::Ok);
+    }
+}
@@ -80,47 +80,70 @@ impl Blocklist {
         guard.contains(blob_id)
     }
 
-    /// Adds a blob ID to the blocklist.
+    /// Returns true if the blocklist is backed by a file, to which changes are persisted.
+    pub fn is_persistent(&self) -> bool {
+        !self.deny_list_path.as_os_str().is_empty()
+    }
+
+    /// Returns the blocked blob IDs, in ascending order.
+    pub fn list(&self) -> Vec<BlobId> {
+        let guard = self.blocked_blobs.read().expect("mutex poisoned");
+        let mut blob_ids = guard.iter().copied().collect::<Vec<_>>();
+        blob_ids.sort();
+        blob_ids
+    }
+
+    /// Adds a blob ID to the blocklist and persists the blocklist to its file.
     ///
-    /// Returns whether the ID was newly inserted.
-    #[inline]
-    pub fn insert(&mut self, blob_id: BlobId) -> Result<bool> {
-        let mut guard: std::sync::RwLockWriteGuard<'_, HashSet<BlobId>> =
-            self.blocked_blobs.write().expect("mutex poisoned");
-        guard.insert(blob_id);
-        // Update yaml file to add this blob id
-        let blobs = BlocklistInner(guard.iter().cloned().collect::<Vec<_>>());
-        let mut file = std::fs::OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&self.deny_list_path)?;
-        serde_yaml::to_writer(&mut file, &blobs)?;
+    /// Returns whether the ID was newly inserted. If persisting the change fails, the blocklist is
+    /// left unchanged.
+    pub fn insert(&self, blob_id: BlobId) -> Result<bool> {
+        let mut guard = self.blocked_blobs.write().expect("mutex poisoned");
+        if !guard.insert(blob_id) {
+            return Ok(false);
+        }
+        if let Err(error) = self.persist(&guard) {
+            guard.remove(&blob_id);
+            return Err(error);
+        }
         Ok(true)
     }
 
-    /// Removes a blob ID from the blocklist.
+    /// Removes a blob ID from the blocklist and persists the blocklist to its file.
     ///
     /// Returns whether the ID was previously blocked.
-    #[inline]
-    pub fn remove(&mut self, blob_id: &BlobId) -> Result<bool> {
-        let mut guard: std::sync::RwLockWriteGuard<'_, HashSet<BlobId>> =
-            self.blocked_blobs.write().expect("mutex poisoned");
-        guard.remove(blob_id);
-        let blobs = BlocklistInner(guard.iter().cloned().collect::<Vec<_>>());
-
-        if !self.deny_list_path.exists() {
+    pub fn remove(&self, blob_id: &BlobId) -> Result<bool> {
+        let mut guard = self.blocked_blobs.write().expect("mutex poisoned");
+        if !guard.remove(blob_id) {
             return Ok(false);
-        };
-
-        let mut file = std::fs::OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(&self.deny_list_path)?;
-        serde_yaml::to_writer(&mut file, &blobs)?;
+        }
+        if let Err(error) = self.persist(&guard) {
+            guard.insert(*blob_id);
+            return Err(error);
+        }
         Ok(true)
     }
 
+    /// Writes the blocked blob IDs to the blocklist file.
+    ///
+    /// The file is replaced atomically, such that the refresh task never reads a partial file.
+    fn persist(&self, blocked_blobs: &HashSet<BlobId>) -> Result<()> {
+        let mut blob_ids = blocked_blobs.iter().copied().collect::<Vec<_>>();
+        blob_ids.sort();
+
+        let tmp_path = self.deny_list_path.with_extension("yaml.tmp");
+        let file = std::fs::File::create(&tmp_path).context(format!(
+            "Unable to write blocklist file at {}",
+            tmp_path.display()
+        ))?;
+        serde_yaml::to_writer(file, &BlocklistInner(blob_ids))?;
+        std::fs::rename(&tmp_path, &self.deny_list_path).context(format!(
+            "Unable to replace blocklist file at {}",
+            self.deny_list_path.display()
+        ))?;
+        Ok(())
+    }
+
     /// Loads the blocklist from the file at the given path.
     fn load(&self) -> Result<()> {
         if !self.deny_list_path.exists() {
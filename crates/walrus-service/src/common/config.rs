@@ -3,14 +3,21 @@
 
 //! Common configuration module.
 
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
 
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DurationMilliSeconds};
 use walrus_sui::{
     client::{
         contract_config::ContractConfig,
+        graphql_client::GraphQlClient,
+        retry_client::RetriableSuiClient,
         rpc_config::RpcFallbackConfig,
+        QuorumReadClient,
         SuiClientError,
         SuiContractClient,
         SuiReadClient,
@@ -48,13 +55,22 @@ pub struct SuiConfig {
     /// The config for rpc fallback.
     #[serde(default, skip_serializing_if = "defaults::is_none")]
     pub rpc_fallback_config: Option<RpcFallbackConfig>,
+    /// URL of a Sui GraphQL RPC endpoint. If set, object and event queries are sent to this
+    /// endpoint instead of the JSON-RPC endpoint.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub graphql_url: Option<Url>,
+    /// Configuration for cross-checking committee and system state reads against additional
+    /// full nodes.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub quorum_read_config: Option<QuorumReadConfig>,
 }
 
 impl SuiConfig {
     /// Creates a new [`SuiReadClient`] based on the configuration.
     pub async fn new_read_client(&self) -> Result<SuiReadClient, SuiClientError> {
-        SuiReadClient::new_for_rpc(
+        new_read_client(
             &self.rpc,
+            self.graphql_url.as_ref(),
             &self.contract_config,
             self.backoff_config.clone(),
         )
@@ -73,6 +89,68 @@ impl SuiConfig {
     }
 }
 
+/// Configuration for cross-checking critical reads against multiple full nodes.
+///
+/// Reads of the committees, the epoch state, and the system parameters are issued to the primary
+/// full node and to all full nodes in `rpc_urls`, and fail if the responses diverge.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct QuorumReadConfig {
+    /// HTTP URLs of the additional, independent full-node RPC endpoints.
+    ///
+    /// The URLs must be distinct from each other and from [`SuiConfig::rpc`]. Reads from these
+    /// full nodes always use JSON-RPC, even if [`SuiConfig::graphql_url`] is set, such that they do
+    /// not depend on the same endpoint.
+    pub rpc_urls: Vec<Url>,
+    /// The number of full nodes, including the primary one, that must return the same value.
+    ///
+    /// Defaults to all configured full nodes.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub quorum: Option<usize>,
+}
+
+impl QuorumReadConfig {
+    /// Creates a [`QuorumReadClient`] that uses `primary` as the primary client and cross-checks
+    /// its reads against the additional full nodes.
+    pub async fn new_quorum_read_client(
+        &self,
+        primary: SuiReadClient,
+        sui_config: &SuiConfig,
+    ) -> Result<QuorumReadClient, SuiClientError> {
+        self.check_distinct_rpc_urls(&sui_config.rpc)?;
+        let mut clients = vec![primary];
+        for rpc_url in &self.rpc_urls {
+            clients.push(
+                new_read_client(
+                    rpc_url.as_str(),
+                    None,
+                    &sui_config.contract_config,
+                    sui_config.backoff_config.clone(),
+                )
+                .await?,
+            );
+        }
+        let quorum = self.quorum.unwrap_or(clients.len());
+        QuorumReadClient::new(clients, quorum)
+    }
+
+    /// Checks that the RPC URLs are distinct from each other and from the primary RPC URL.
+    fn check_distinct_rpc_urls(&self, primary_rpc: &str) -> Result<(), SuiClientError> {
+        let mut seen = HashSet::new();
+        if let Ok(primary_rpc_url) = Url::parse(primary_rpc) {
+            seen.insert(primary_rpc_url);
+        }
+        for rpc_url in &self.rpc_urls {
+            if !seen.insert(rpc_url.clone()) {
+                return Err(anyhow::anyhow!(
+                    "the full node {rpc_url} is configured multiple times for quorum reads"
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
 impl From<&SuiConfig> for SuiReaderConfig {
     fn from(config: &SuiConfig) -> Self {
         Self {
@@ -81,6 +159,7 @@ impl From<&SuiConfig> for SuiReaderConfig {
             event_polling_interval: config.event_polling_interval,
             backoff_config: config.backoff_config.clone(),
             rpc_fallback_config: config.rpc_fallback_config.clone(),
+            graphql_url: config.graphql_url.clone(),
         }
     }
 }
@@ -109,13 +188,18 @@ pub struct SuiReaderConfig {
     /// The URL of the checkpoint download fallback endpoint.
     #[serde(default, skip_serializing_if = "defaults::is_none")]
     pub rpc_fallback_config: Option<RpcFallbackConfig>,
+    /// URL of a Sui GraphQL RPC endpoint. If set, object and event queries are sent to this
+    /// endpoint instead of the JSON-RPC endpoint.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub graphql_url: Option<Url>,
 }
 
 impl SuiReaderConfig {
     /// Creates a new [`SuiReadClient`] based on the configuration.
     pub async fn new_read_client(&self) -> Result<SuiReadClient, SuiClientError> {
-        SuiReadClient::new_for_rpc(
+        new_read_client(
             &self.rpc,
+            self.graphql_url.as_ref(),
             &self.contract_config,
             self.backoff_config.clone(),
         )
@@ -123,6 +207,86 @@ impl SuiReaderConfig {
     }
 }
 
+async fn new_read_client(
+    rpc: &str,
+    graphql_url: Option<&Url>,
+    contract_config: &ContractConfig,
+    backoff_config: ExponentialBackoffConfig,
+) -> Result<SuiReadClient, SuiClientError> {
+    let mut sui_client = RetriableSuiClient::new_for_rpc(rpc, backoff_config).await?;
+    if let Some(url) = graphql_url {
+        sui_client = sui_client.with_graphql_client(GraphQlClient::new(url.clone())?);
+    }
+    SuiReadClient::new(sui_client, contract_config).await
+}
+
+/// Configuration of the head-based sampling of traces.
+///
+/// The decision whether to export a trace is made when its root span is created, based on the
+/// rate configured for the name of that span. Spans of traces that are not sampled are still
+/// logged, but are not exported.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct TraceSamplingConfig {
+    /// The fraction of traces that are sampled if no rate is configured for their root span.
+    pub default_rate: f64,
+    /// The fraction of traces that are sampled, by the name of their root span.
+    ///
+    /// For the spans of HTTP requests, the name is of the form `<METHOD> <route>`, for example
+    /// `GET /v1/blobs/{blob_id}/metadata`. For the spans of processed events, a rate can be set
+    /// for all events with the name `blob_store receive` or for a single kind of event with the
+    /// name `blob_store receive <kind>`, for example `blob_store receive epoch-change-start`.
+    pub span_rates: BTreeMap<String, f64>,
+}
+
+impl Default for TraceSamplingConfig {
+    fn default() -> Self {
+        Self {
+            default_rate: 1.0,
+            span_rates: BTreeMap::new(),
+        }
+    }
+}
+
+impl TraceSamplingConfig {
+    /// Checks that all rates are between 0 and 1.
+    pub fn validate(&self) -> Result<(), InvalidSamplingRate> {
+        std::iter::once(("default", self.default_rate))
+            .chain(
+                self.span_rates
+                    .iter()
+                    .map(|(name, rate)| (name.as_str(), *rate)),
+            )
+            .find(|(_, rate)| !(0.0..=1.0).contains(rate))
+            .map_or(Ok(()), |(name, rate)| {
+                Err(InvalidSamplingRate {
+                    name: name.to_owned(),
+                    rate,
+                })
+            })
+    }
+
+    /// Returns the sampling rate for a trace whose root span has the first of the `names` for
+    /// which a rate is configured, or the default rate if there is none.
+    pub fn rate(&self, names: &[&str]) -> f64 {
+        names
+            .iter()
+            .find_map(|name| self.span_rates.get(*name))
+            .copied()
+            .unwrap_or(self.default_rate)
+    }
+}
+
+/// Error returned if a sampling rate is not between 0 and 1.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("the sampling rate {rate} of '{name}' is not between 0 and 1")]
+pub struct InvalidSamplingRate {
+    /// The name of the span for which the rate is configured, or `default`.
+    pub name: String,
+    /// The invalid rate.
+    pub rate: f64,
+}
+
 /// Shared configuration defaults.
 pub mod defaults {
     use super::*;
@@ -149,3 +313,33 @@ pub mod defaults {
         !cfg!(test) && t.is_none()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quorum_read_config_requires_distinct_rpc_urls() {
+        let config = |rpc_urls: &[&str]| QuorumReadConfig {
+            rpc_urls: rpc_urls.iter().map(|url| url.parse().unwrap()).collect(),
+            quorum: None,
+        };
+        let primary = "https://fullnode-a.example.com:443";
+
+        assert!(config(&[
+            "https://fullnode-b.example.com",
+            "https://fullnode-c.example.com"
+        ])
+        .check_distinct_rpc_urls(primary)
+        .is_ok());
+        assert!(config(&[
+            "https://fullnode-b.example.com",
+            "https://fullnode-b.example.com/"
+        ])
+        .check_distinct_rpc_urls(primary)
+        .is_err());
+        assert!(config(&["https://fullnode-a.example.com"])
+            .check_distinct_rpc_urls(primary)
+            .is_err());
+    }
+}
@@ -12,6 +12,8 @@ use std::{
     sync::{
         atomic::{AtomicU64 as StdAtomicU64, Ordering},
         Arc,
+        OnceLock,
+        RwLock,
     },
     time::Duration,
 };
@@ -28,7 +30,10 @@ use axum::{
     },
     middleware,
 };
-use opentelemetry::propagation::Extractor;
+use opentelemetry::{
+    propagation::Extractor,
+    trace::{SpanContext, SpanId, TraceContextExt as _, TraceFlags, TraceId, TraceState},
+};
 use prometheus::{
     core::{AtomicU64, Collector, GenericGauge},
     Histogram,
@@ -42,9 +47,13 @@ use tower_http::trace::{MakeSpan, OnResponse};
 use tracing::{field, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use walrus_core::Epoch;
+use walrus_sdk::request_id::{self, RequestId, REQUEST_ID_HEADER};
 use walrus_utils::http::{http_body::Frame, BodyVisitor, VisitBody};
 
-use super::active_committees::ActiveCommittees;
+use super::{
+    active_committees::ActiveCommittees,
+    config::{InvalidSamplingRate, TraceSamplingConfig},
+};
 
 /// Route string used in metrics for invalid routes.
 pub(crate) const UNMATCHED_ROUTE: &str = "invalid-route";
@@ -122,11 +131,12 @@ impl MakeHttpSpan {
 
     fn make_span<B>(&mut self, request: &Request<B>) -> Span {
         let route = self.get_route(request);
+        let otel_name = format!("{} {}", request.method(), route);
         let span = tracing::info_span!(
             parent: &Span::current(),
             "rest_api",
             // Overrides the exported span name to "{http.request.method} {http.route}"
-            "otel.name" = format!("{} {}", request.method(), route),
+            "otel.name" = otel_name.as_str(),
             "otel.kind" = "SERVER",
             "http.request.method" = %request.method(),
             "http.route" = route,
@@ -142,19 +152,23 @@ impl MakeHttpSpan {
             "network.peer.port" = field::Empty,
             "user_agent.original" = field::Empty,
             "network.protocol.version" = field::Empty,
+            "walrus.request_id" = field::Empty,
             // Populated later with details of the response:
             "error.type" = field::Empty,
             "http.response.status_code" = field::Empty,
             "otel.status_code" = field::Empty,
         );
 
-        self.propagate_context(request, &span);
+        if !self.propagate_context(request, &span) {
+            TraceSampler::global().sample_root_span(&span, &[&otel_name]);
+        }
         let peer_ip = self.record_remote_address(request, &span);
         self.record_client_address(request, &span, peer_ip);
         self.record_server_address(request, &span);
         self.record_url_query(request, &span);
         self.record_user_agent(request, &span);
         self.record_network_protocol_version(request, &span);
+        self.record_request_id(request, &span);
 
         span
     }
@@ -230,6 +244,13 @@ impl MakeHttpSpan {
         }
     }
 
+    /// Record the ID of the request, as set by the [`request_id_middleware`].
+    fn record_request_id<B>(&self, request: &Request<B>, span: &Span) {
+        if let Some(request_id) = get_header_as_str(request, REQUEST_ID_HEADER) {
+            span.record("walrus.request_id", request_id);
+        }
+    }
+
     fn record_network_protocol_version<B>(&self, request: &Request<B>, span: &Span) {
         let version = match request.version() {
             Version::HTTP_09 => "0.9",
@@ -242,11 +263,17 @@ impl MakeHttpSpan {
         span.record("network.protocol.version", version);
     }
 
-    fn propagate_context<B>(&self, request: &Request<B>, span: &Span) {
+    /// Sets the trace context propagated in the request headers as the parent of the span.
+    ///
+    /// Returns true if the request continues a trace, in which case the trace was already sampled
+    /// by the caller.
+    fn propagate_context<B>(&self, request: &Request<B>, span: &Span) -> bool {
         let parent_context = opentelemetry::global::get_text_map_propagator(|propagator| {
             propagator.extract(&HeaderExtractor(request.headers()))
         });
+        let continues_trace = parent_context.span().span_context().is_valid();
         span.set_parent(parent_context);
+        continues_trace
     }
 }
 
@@ -273,6 +300,69 @@ impl<B> MakeSpan<B> for MakeHttpSpan {
     }
 }
 
+/// Samples new traces at the rates configured for the names of their root spans.
+///
+/// The sampler is shared by the whole process, like the tracing subscriber, such that its
+/// configuration can be updated at runtime, for example through the administrative API of the
+/// storage node.
+#[derive(Debug, Default)]
+pub(crate) struct TraceSampler {
+    config: RwLock<TraceSamplingConfig>,
+}
+
+impl TraceSampler {
+    /// Returns the sampler of the process.
+    pub(crate) fn global() -> &'static TraceSampler {
+        static SAMPLER: OnceLock<TraceSampler> = OnceLock::new();
+        SAMPLER.get_or_init(TraceSampler::default)
+    }
+
+    /// Returns the current sampling configuration.
+    pub(crate) fn config(&self) -> TraceSamplingConfig {
+        self.config
+            .read()
+            .expect("other threads should not panic")
+            .clone()
+    }
+
+    /// Replaces the sampling configuration, if all its rates are valid.
+    pub(crate) fn set_config(
+        &self,
+        config: TraceSamplingConfig,
+    ) -> Result<(), InvalidSamplingRate> {
+        config.validate()?;
+        *self.config.write().expect("other threads should not panic") = config;
+        Ok(())
+    }
+
+    /// Decides whether the trace started by `span` is sampled, using the rate of the first of the
+    /// `names` for which a rate is configured, and returns the decision.
+    ///
+    /// If the trace is not sampled, the span is made the child of a remote span that was not
+    /// sampled, such that neither it nor its descendants are exported.
+    pub(crate) fn sample_root_span(&self, span: &Span, names: &[&str]) -> bool {
+        let rate = self
+            .config
+            .read()
+            .expect("other threads should not panic")
+            .rate(names);
+        let sampled = rate >= 1.0 || (rate > 0.0 && rand::random::<f64>() < rate);
+        if !sampled {
+            let unsampled_parent = SpanContext::new(
+                TraceId::from_bytes(rand::random()),
+                SpanId::from_bytes(rand::random()),
+                TraceFlags::default(),
+                true,
+                TraceState::default(),
+            );
+            span.set_parent(
+                opentelemetry::Context::new().with_remote_span_context(unsampled_parent),
+            );
+        }
+        sampled
+    }
+}
+
 /// Marks the wrapped error as an internal error to add corresponding log entries.
 #[derive(Debug, Clone)]
 pub(crate) struct InternalError(pub(crate) Arc<dyn std::error::Error + Sync + Send + 'static>);
@@ -321,6 +411,31 @@ fn http_version(request: &axum::extract::Request) -> &'static str {
     }
 }
 
+/// Middleware that assigns an ID to each request and returns it in the response headers.
+///
+/// The ID provided by the caller in the [`REQUEST_ID_HEADER`] is used if it is valid, and a random
+/// ID is generated otherwise. The request is handled within the scope of the ID, such that it is
+/// included in error responses and sent with the requests to storage nodes made on its behalf.
+pub(crate) async fn request_id_middleware(
+    mut request: axum::extract::Request,
+    next: middleware::Next,
+) -> axum::response::Response {
+    let request_id = get_header_as_str(&request, REQUEST_ID_HEADER)
+        .and_then(RequestId::parse)
+        .unwrap_or_else(RequestId::new_random);
+    let header_value = http::HeaderValue::from_str(request_id.as_str())
+        .expect("request IDs consist of printable ASCII characters");
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value.clone());
+
+    let mut response = request_id::scope(request_id, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value);
+    response
+}
+
 /// Middleware that records the elapsed time, HTTP method, and status of requests.
 pub(crate) async fn metrics_middleware(
     State(metrics): State<HttpServerMetrics>,
@@ -8,8 +8,9 @@ use std::{
     env,
     fmt::Debug,
     future::Future,
+    io,
     mem,
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener},
     path::Path,
     pin::Pin,
     str::FromStr,
@@ -25,6 +26,7 @@ use fastcrypto::{
     traits::{EncodeDecodeBase64, RecoverableSigner},
 };
 use futures::future::FusedFuture;
+use mysten_metrics::RegistryService;
 use pin_project::pin_project;
 use prometheus::{Encoder, HistogramVec, Registry};
 use serde::{
@@ -34,6 +36,7 @@ use serde::{
     Serialize,
 };
 use serde_json;
+use socket2::{Domain, Protocol, Socket, Type};
 use sui_types::base_types::{ObjectID, SuiAddress};
 use telemetry_subscribers::{TelemetryGuards, TracingHandle};
 use tokio::{
@@ -53,14 +56,18 @@ use tracing_subscriber::{
 };
 use typed_store::DBMetrics;
 use uuid::Uuid;
-use walrus_core::{BlobId, PublicKey, ShardIndex};
+use walrus_core::{BlobId, ShardIndex};
 use walrus_sui::{
     client::{retry_client::RetriableSuiClient, SuiReadClient},
     utils::SuiNetwork,
 };
 
-use super::active_committees::ActiveCommittees;
-use crate::node::{config::MetricsPushConfig, events::event_processor::EventProcessorMetrics};
+use super::{config::TraceSamplingConfig, telemetry::TraceSampler};
+use crate::node::{
+    committee::ShardAssignmentDiff,
+    config::MetricsPushConfig,
+    events::event_processor::EventProcessorMetrics,
+};
 
 /// The maximum length of the storage node name. Keep in sync with `MAX_NODE_NAME_LENGTH` in
 /// `contracts/walrus/sources/staking/staking_pool.move`.
@@ -111,6 +118,110 @@ pub fn load_from_yaml<P: AsRef<Path>, T: DeserializeOwned>(path: P) -> anyhow::R
     Ok(serde_yaml::from_reader(reader)?)
 }
 
+/// The prefix of environment variables overriding fields of the storage node configuration.
+pub const NODE_CONFIG_ENV_PREFIX: &str = "WALRUS_NODE";
+/// The prefix of environment variables overriding fields of the client configuration.
+pub const CLIENT_CONFIG_ENV_PREFIX: &str = "WALRUS_CLIENT";
+/// The separator between the prefix and the keys of nested fields in override variables.
+const ENV_OVERRIDE_SEPARATOR: &str = "__";
+
+/// Load the config from a YAML file located at the provided path and apply the overrides from
+/// environment variables with the given prefix.
+///
+/// See [`with_env_overrides`] for the mapping of environment variables to config fields.
+pub fn load_from_yaml_with_env_overrides<P, T>(path: P, prefix: &str) -> anyhow::Result<T>
+where
+    P: AsRef<Path>,
+    T: Serialize + DeserializeOwned,
+{
+    with_env_overrides(load_from_yaml(path)?, prefix)
+}
+
+/// Overrides fields of the config with the values of environment variables with the given prefix.
+///
+/// A variable `<prefix>__<KEY_1>__<KEY_2>` overrides the field `key_2` nested in the field `key_1`
+/// of the config, where keys are matched case-insensitively against the serialized field names.
+/// For example, `WALRUS_NODE__SUI__RPC` overrides `sui.rpc` in the storage node configuration.
+///
+/// The values are parsed as YAML, so numbers, booleans, lists, and maps can be provided. Values
+/// of fields that are strings in the config and values starting with `0x` are always used as
+/// strings.
+pub fn with_env_overrides<T>(config: T, prefix: &str) -> anyhow::Result<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut overrides: Vec<_> = env::vars()
+        .filter_map(|(name, value)| {
+            name.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix(ENV_OVERRIDE_SEPARATOR))
+                .map(|path| (path.to_owned(), value))
+        })
+        .collect();
+    if overrides.is_empty() {
+        return Ok(config);
+    }
+    // Apply overrides in a deterministic order, so nested overrides win over their parents.
+    overrides.sort();
+
+    let mut value = serde_yaml::to_value(&config)?;
+    for (path, raw_value) in &overrides {
+        let variable = format!("{prefix}{ENV_OVERRIDE_SEPARATOR}{path}");
+        tracing::info!(%variable, "overriding config field from environment variable");
+        apply_env_override(&mut value, path, raw_value)
+            .with_context(|| format!("invalid config override for '{path}'"))?;
+    }
+    serde_yaml::from_value(value).context("failed to apply config overrides from environment")
+}
+
+/// Sets the field at the `__`-separated `path` in `config` to the parsed `raw_value`, creating
+/// missing intermediate mappings.
+fn apply_env_override(config: &mut serde_yaml::Value, path: &str, raw_value: &str) -> Result<()> {
+    let keys: Vec<_> = path.split(ENV_OVERRIDE_SEPARATOR).collect();
+    anyhow::ensure!(
+        keys.iter().all(|key| !key.is_empty()),
+        "the variable name must not contain empty keys"
+    );
+
+    let mut current = config;
+    for (i, key) in keys.iter().enumerate() {
+        if !current.is_mapping() {
+            *current = serde_yaml::Value::Mapping(Default::default());
+        }
+        let serde_yaml::Value::Mapping(map) = current else {
+            unreachable!("the value was just replaced with a mapping")
+        };
+        let map_key = map
+            .keys()
+            .find(|existing| {
+                existing
+                    .as_str()
+                    .is_some_and(|existing| existing.eq_ignore_ascii_case(key))
+            })
+            .cloned()
+            .unwrap_or_else(|| serde_yaml::Value::String(key.to_ascii_lowercase()));
+
+        if i == keys.len() - 1 {
+            let new_value = parse_env_override_value(raw_value, map.get(&map_key));
+            map.insert(map_key, new_value);
+            return Ok(());
+        }
+        current = map
+            .entry(map_key)
+            .or_insert(serde_yaml::Value::Mapping(Default::default()));
+    }
+    unreachable!("the path contains at least one key")
+}
+
+fn parse_env_override_value(
+    raw_value: &str,
+    existing: Option<&serde_yaml::Value>,
+) -> serde_yaml::Value {
+    if matches!(existing, Some(serde_yaml::Value::String(_))) || raw_value.starts_with("0x") {
+        return serde_yaml::Value::String(raw_value.to_owned());
+    }
+    serde_yaml::from_str(raw_value).unwrap_or_else(|_| serde_yaml::Value::String(raw_value.into()))
+}
+
 /// Helper functions applied to futures.
 pub(crate) trait FutureHelpers: Future {
     /// Limits the number of simultaneously executing futures.
@@ -250,8 +361,9 @@ where
 pub struct MetricsAndLoggingRuntime {
     /// The Prometheus registry.
     pub registry: Registry,
+    registry_service: RegistryService,
     _telemetry_guards: TelemetryGuards,
-    _tracing_handle: TracingHandle,
+    tracing_handle: Arc<TracingHandle>,
     /// The runtime for metrics and logging.
     // INV: Runtime must be dropped last.
     pub runtime: Option<Runtime>,
@@ -290,10 +402,100 @@ impl MetricsAndLoggingRuntime {
         Ok(Self {
             runtime,
             registry: walrus_registry,
+            registry_service,
             _telemetry_guards: telemetry_guards,
-            _tracing_handle: tracing_handle,
+            tracing_handle: Arc::new(tracing_handle),
         })
     }
+
+    /// Returns the handle to the tracing subscriber, which allows updating the log filter.
+    pub fn tracing_handle(&self) -> Arc<TracingHandle> {
+        self.tracing_handle.clone()
+    }
+
+    /// Serves the metrics on the additional socket addresses.
+    ///
+    /// Listeners on IPv6 addresses only accept IPv6 connections, since the metrics are already
+    /// served on all IPv4 addresses.
+    pub fn serve_metrics_on(&self, addresses: &[SocketAddr]) -> anyhow::Result<()> {
+        for &address in addresses {
+            let listener = bind_tcp_listener(address, true)
+                .with_context(|| format!("failed to bind the metrics listener on {address}"))?;
+            let registry_service = self.registry_service.clone();
+            let app = axum::Router::new().route(
+                "/metrics",
+                axum::routing::get(move || {
+                    let metric_families = registry_service.gather_all();
+                    async move {
+                        prometheus::TextEncoder::new()
+                            .encode_to_string(&metric_families)
+                            .map_err(|error| {
+                                (
+                                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                                    error.to_string(),
+                                )
+                            })
+                    }
+                }),
+            );
+            let server = async move {
+                let listener = tokio::net::TcpListener::from_std(listener)?;
+                axum::serve(listener, app).await
+            };
+            let server = async move {
+                if let Err(error) = server.await {
+                    tracing::error!(?error, %address, "metrics listener failed");
+                }
+            };
+            if let Some(runtime) = &self.runtime {
+                runtime.spawn(server);
+            } else {
+                tokio::spawn(server);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Binds a non-blocking TCP listener on the address.
+///
+/// If `only_v6` is set, a listener on an IPv6 address only accepts IPv6 connections, such that it
+/// can share its port with a listener on an IPv4 address.
+pub(crate) fn bind_tcp_listener(address: SocketAddr, only_v6: bool) -> io::Result<TcpListener> {
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::STREAM,
+        Some(Protocol::TCP),
+    )?;
+    if address.is_ipv6() {
+        socket.set_only_v6(only_v6)?;
+    }
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+    socket.listen(1024)?;
+    Ok(socket.into())
+}
+
+/// Replaces the log filter of the tracing subscriber with the provided directives.
+///
+/// If no directives are provided, the filter is reset to the one specified by the `RUST_LOG`
+/// environment variable.
+pub fn update_log_filter(tracing_handle: &TracingHandle, directives: Option<&str>) -> Result<()> {
+    let directives = directives.map_or_else(
+        || env::var(EnvFilter::DEFAULT_ENV).unwrap_or_else(|_| "info".to_owned()),
+        str::to_owned,
+    );
+    tracing_handle
+        .update_log(&directives)
+        .map_err(|error| anyhow!("failed to update the log filter to '{directives}': {error}"))
+}
+
+/// Replaces the rates at which new traces are sampled for export.
+pub fn update_trace_sampling(config: &TraceSamplingConfig) -> Result<()> {
+    TraceSampler::global()
+        .set_config(config.clone())
+        .context("failed to update the trace sampling rates")
 }
 
 /// A config struct to initialize the push metrics. Some binaries that depend on
@@ -475,46 +677,26 @@ pub(crate) struct ShardDiffCalculator {
 }
 
 impl ShardDiffCalculator {
-    // TODO(WAL-657): Use `node_id` instead of `public_key`.
-    //
-    /// Create a new `ShardDiffCalculator` for a storage node.
-    pub fn new(committees: &ActiveCommittees, id: &PublicKey, shards_exist: &[ShardIndex]) -> Self {
-        let shards_assigned_prev_epoch = committees
-            .previous_committee()
-            .map(|committee| committee.shards_for_node_public_key(id).to_vec())
-            .unwrap_or_default();
-        let shards_assigned_current_epoch = committees
-            .current_committee()
-            .shards_for_node_public_key(id)
-            .to_vec();
-
-        let gained_shards_from_prev_epoch = {
-            let current: HashSet<_> = shards_assigned_current_epoch.iter().copied().collect();
-            let prev: HashSet<_> = shards_assigned_prev_epoch.iter().copied().collect();
-            current.difference(&prev).copied().collect()
-        };
-
+    /// Create a new `ShardDiffCalculator` for a storage node from the changes to its shard
+    /// assignment and the shards that exist locally.
+    pub fn new(assignment: &ShardAssignmentDiff, shards_exist: &[ShardIndex]) -> Self {
         let shards_to_remove = {
             let all: HashSet<_> = shards_exist.iter().copied().collect();
-            let assigned: HashSet<_> = shards_assigned_prev_epoch
+            let assigned: HashSet<_> = assignment
+                .gained
                 .iter()
-                .chain(shards_assigned_current_epoch.iter())
+                .chain(&assignment.lost)
+                .chain(&assignment.retained)
                 .copied()
                 .collect();
             all.difference(&assigned).copied().collect()
         };
 
-        let shards_to_lock = {
-            let prev: HashSet<_> = shards_assigned_prev_epoch.iter().copied().collect();
-            let current: HashSet<_> = shards_assigned_current_epoch.iter().copied().collect();
-            prev.difference(&current).copied().collect()
-        };
-
         Self {
-            all_owned_shards: shards_assigned_current_epoch,
-            gained_shards_from_prev_epoch,
+            all_owned_shards: assignment.owned_shards(),
+            gained_shards_from_prev_epoch: assignment.gained.clone(),
             shards_to_remove,
-            shards_to_lock,
+            shards_to_lock: assignment.lost.clone(),
         }
     }
 
@@ -869,15 +1051,94 @@ where
     mem::replace(svc, unready_clone)
 }
 
+/// Changes the number of permits of `semaphore` from `current` to `target`.
+///
+/// Additional permits are available immediately. When shrinking the semaphore, available permits
+/// are removed immediately and permits that are currently held are removed once they are released.
+pub(crate) fn resize_semaphore(semaphore: &Arc<Semaphore>, current: usize, target: usize) {
+    if target >= current {
+        semaphore.add_permits(target - current);
+        return;
+    }
+
+    let excess = current - target;
+    let outstanding = excess - semaphore.forget_permits(excess);
+    if outstanding > 0 {
+        let semaphore = semaphore.clone();
+        let outstanding = u32::try_from(outstanding).expect("semaphore sizes fit into a u32");
+        tokio::spawn(async move {
+            if let Ok(permits) = semaphore.acquire_many_owned(outstanding).await {
+                permits.forget();
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use std::num::NonZeroU16;
 
     use walrus_sui::{test_utils, types::Committee};
-    use walrus_test_utils::{assert_unordered_eq, param_test};
+    use walrus_test_utils::{assert_unordered_eq, param_test, Result as TestResult};
 
     use super::*;
+    use crate::common::active_committees::ActiveCommittees;
+
+    #[test]
+    fn env_overrides_replace_nested_fields() -> TestResult {
+        let mut config: serde_yaml::Value = serde_yaml::from_str(indoc::indoc! {"
+            name: node
+            public_port: 9185
+            sui:
+              rpc: http://localhost:9000
+              gas_budget: 100
+        "})?;
+
+        apply_env_override(&mut config, "PUBLIC_PORT", "9200")?;
+        apply_env_override(&mut config, "SUI__RPC", "https://example.com")?;
+        apply_env_override(&mut config, "SUI__GAS_BUDGET", "42")?;
+        apply_env_override(&mut config, "NAME", "1234")?;
+        apply_env_override(&mut config, "STORAGE_NODE_CAP", "0x1")?;
+        apply_env_override(&mut config, "METRICS_PUSH__LABELS__ENV", "test")?;
+
+        let expected: serde_yaml::Value = serde_yaml::from_str(indoc::indoc! {"
+            name: '1234'
+            public_port: 9200
+            sui:
+              rpc: https://example.com
+              gas_budget: 42
+            storage_node_cap: '0x1'
+            metrics_push:
+              labels:
+                env: test
+        "})?;
+        assert_eq!(config, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn env_overrides_reject_empty_keys() -> TestResult {
+        let mut config = serde_yaml::Value::Mapping(Default::default());
+        assert!(apply_env_override(&mut config, "SUI____RPC", "value").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resize_semaphore_adds_and_removes_permits() {
+        let semaphore = Arc::new(Semaphore::new(2));
+
+        resize_semaphore(&semaphore, 2, 5);
+        assert_eq!(semaphore.available_permits(), 5);
+
+        let held = semaphore.clone().acquire_many_owned(4).await.unwrap();
+        resize_semaphore(&semaphore, 5, 1);
+        assert_eq!(semaphore.available_permits(), 0);
+
+        drop(held);
+        tokio::task::yield_now().await;
+        assert_eq!(semaphore.available_permits(), 1);
+    }
 
     mod byte_count {
         use super::*;
@@ -1081,8 +1342,7 @@ mod tests {
         let active_committees = ActiveCommittees::new(current_committees, Some(prev_committees));
 
         let shard_diff_calculator = ShardDiffCalculator::new(
-            &active_committees,
-            &node_0.public_key,
+            &ShardAssignmentDiff::new(&active_committees, &node_0.public_key),
             &input
                 .existing_shards_all
                 .iter()
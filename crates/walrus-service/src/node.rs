@@ -11,10 +11,13 @@ use std::{
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
+        Mutex as StdMutex,
     },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{anyhow, bail, Context};
+use blob_invalidation::BlobInvalidator;
 use blob_retirement_notifier::BlobRetirementNotifier;
 use committee::{BeginCommitteeChangeError, EndCommitteeChangeError};
 use epoch_change_driver::EpochChangeDriver;
@@ -38,15 +41,32 @@ use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 use recovery_symbol_service::{RecoverySymbolRequest, RecoverySymbolService};
 use serde::Serialize;
 use start_epoch_change_finisher::StartEpochChangeFinisher;
-use storage::{blob_info::PerObjectBlobInfoApi, StorageShardLock};
-pub use storage::{DatabaseConfig, NodeStatus, Storage};
+use storage::{blob_info::PerObjectBlobInfoApi, is_corruption_error, StorageShardLock};
+pub use storage::{
+    BlobStorageReport,
+    ColumnFamilySize,
+    DatabaseConfig,
+    DeadLetterEvent,
+    EventCursorReport,
+    NodeStatus,
+    PinnedBlob,
+    ShardBlobs,
+    ShardSliverReport,
+    Storage,
+    StorageEncryptionConfig,
+    DEFAULT_SHARD_BLOBS_LIMIT,
+};
 #[cfg(msim)]
 use sui_macros::fail_point_if;
 use sui_macros::{fail_point_arg, fail_point_async};
 use sui_types::{base_types::ObjectID, event::EventID};
 use system_events::{CompletableHandle, EventHandle};
 use thread_pool::ThreadPoolBuilder;
-use tokio::{select, sync::watch, time::Instant};
+use tokio::{
+    select,
+    sync::{watch, Mutex},
+    time::Instant,
+};
 use tokio_util::sync::CancellationToken;
 use tower::{Service, ServiceExt};
 use tracing::{field, Instrument as _, Span};
@@ -61,7 +81,6 @@ use walrus_core::{
         Secondary,
     },
     ensure,
-    keys::ProtocolKeyPair,
     messages::{
         BlobPersistenceType,
         Confirmation,
@@ -82,6 +101,7 @@ use walrus_core::{
     BlobId,
     Epoch,
     InconsistencyProof,
+    NetworkPublicKey,
     PublicKey,
     ShardIndex,
     Sliver,
@@ -92,7 +112,9 @@ use walrus_core::{
 use walrus_sdk::{
     api::{
         BlobStatus,
+        BlobStorageStatus,
         ServiceHealthInfo,
+        ShardBlobStorageStatus,
         ShardHealthInfo,
         ShardStatus as ApiShardStatus,
         ShardStatusDetail,
@@ -118,13 +140,24 @@ use walrus_sui::{
 };
 
 use self::{
+    background_workers::BackgroundWorkerControls,
     blob_sync::BlobSyncHandler,
-    committee::{CommitteeService, NodeCommitteeService},
-    config::StorageNodeConfig,
+    committee::{
+        BackgroundTransferLimits,
+        CachedCommitteeLookupService,
+        CommitteeService,
+        NodeCommitteeService,
+        ShardAssignmentDiff,
+        COMMITTEE_SNAPSHOT_FILE_NAME,
+    },
+    config::{DeadLetterQueueConfig, EventLagMonitorConfig, EventLagStatus, StorageNodeConfig},
     contract_service::{SuiSystemContractService, SystemContractService},
     errors::{
+        BlobPinningError,
         BlobStatusError,
         ComputeStorageConfirmationError,
+        DeadLetterQueueError,
+        DenyListError,
         InconsistencyProofError,
         IndexOutOfRange,
         InvalidEpochError,
@@ -132,6 +165,7 @@ use self::{
         RetrieveSliverError,
         RetrieveSymbolError,
         ShardNotAssigned,
+        StorageInspectionError,
         StoreMetadataError,
         StoreSliverError,
         SyncNodeConfigError,
@@ -147,7 +181,13 @@ use self::{
     },
     metrics::{NodeMetricSet, TelemetryLabel as _, STATUS_PENDING, STATUS_PERSISTED},
     shard_sync::ShardSyncHandler,
-    storage::{blob_info::BlobInfoApi as _, ShardStatus, ShardStorage},
+    storage::{
+        blob_info::BlobInfoApi as _,
+        OrphanedSliverCollectionConfig,
+        ShardStatus,
+        ShardStorage,
+    },
+    sync_shard_replay_guard::SyncShardReplayGuard,
     system_events::{EventManager, SuiSystemEventProvider},
 };
 use crate::{
@@ -155,6 +195,7 @@ use crate::{
     common::{
         active_committees::ActiveCommittees,
         config::SuiConfig,
+        telemetry::TraceSampler,
         utils::should_reposition_cursor,
     },
     utils::ShardDiffCalculator,
@@ -162,6 +203,7 @@ use crate::{
 
 pub mod committee;
 pub mod config;
+pub mod config_check;
 pub mod contract_service;
 pub mod dbtool;
 pub mod events;
@@ -170,6 +212,8 @@ pub mod system_events;
 
 pub(crate) mod metrics;
 
+mod background_workers;
+mod blob_invalidation;
 mod blob_retirement_notifier;
 mod blob_sync;
 mod consistency_check;
@@ -178,13 +222,24 @@ mod node_recovery;
 mod recovery_symbol_service;
 mod shard_sync;
 mod start_epoch_change_finisher;
+mod status_report;
+mod sync_shard_replay_guard;
 mod thread_pool;
 
 pub(crate) mod errors;
 mod storage;
 
 mod config_synchronizer;
+mod protocol_signer;
+pub use background_workers::{BackgroundWorker, BackgroundWorkerStatus};
 pub use config_synchronizer::{ConfigLoader, ConfigSynchronizer, StorageNodeConfigLoader};
+pub use protocol_signer::{
+    LocalProtocolSigner,
+    ProtocolSigner,
+    ProtocolSignerError,
+    RemoteProtocolSigner,
+};
+pub use status_report::{EpochSyncDoneStatus, EventProcessingLag, NodeStatusReport, StorageUsage};
 
 const NUM_CHECKPOINTS_PER_BLOB_ON_TESTNET: u32 = 18_000;
 
@@ -194,6 +249,9 @@ const NUM_EVENTS_PER_DIGEST_RECORDING: u64 = 20_000;
 const NUM_DIGEST_BUCKETS: u64 = 10;
 const CHECKPOINT_EVENT_POSITION_SCALE: u64 = 100;
 
+/// The interval at which the estimated contents and the status of each shard are reported.
+const SHARD_METRICS_REPORTING_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Trait for all functionality offered by a storage node.
 pub trait ServiceState {
     /// Retrieves the metadata associated with a blob.
@@ -271,6 +329,13 @@ pub trait ServiceState {
     /// Retrieves the blob status for the given `blob_id`.
     fn blob_status(&self, blob_id: &BlobId) -> Result<BlobStatus, BlobStatusError>;
 
+    /// Returns whether the metadata and the slivers of the blob are stored, for each shard owned
+    /// by the node in the current epoch.
+    fn blob_storage_status(
+        &self,
+        blob_id: &BlobId,
+    ) -> impl Future<Output = Result<BlobStorageStatus, BlobStatusError>> + Send;
+
     /// Returns the number of shards the node is currently operating with.
     fn n_shards(&self) -> NonZeroU16;
 
@@ -290,6 +355,90 @@ pub trait ServiceState {
         public_key: PublicKey,
         signed_request: SignedSyncShardRequest,
     ) -> impl Future<Output = Result<SyncShardResponse, SyncShardServiceError>> + Send;
+
+    /// Pins the blob, exempting its data on this node from garbage collection.
+    ///
+    /// Returns true if the blob was newly pinned, false if it was already pinned.
+    fn pin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError>;
+
+    /// Unpins the blob.
+    ///
+    /// Returns true if the blob was pinned, false otherwise.
+    fn unpin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError>;
+
+    /// Lists the blobs pinned on this node, along with the amount of data stored for each.
+    fn list_pinned_blobs(
+        &self,
+    ) -> impl Future<Output = Result<Vec<PinnedBlob>, BlobPinningError>> + Send;
+
+    /// Adds the blob to the deny list of this node, such that the node refuses to store or serve
+    /// its data.
+    ///
+    /// Returns true if the blob was newly denied, false if it was already on the deny list.
+    fn deny_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError>;
+
+    /// Removes the blob from the deny list of this node.
+    ///
+    /// Returns true if the blob was on the deny list, false otherwise.
+    fn allow_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError>;
+
+    /// Lists the blobs on the deny list of this node.
+    fn list_denied_blobs(&self) -> Vec<BlobId>;
+
+    /// Lists the blob events that repeatedly failed to be processed and were moved to the
+    /// dead-letter queue.
+    fn list_dead_letter_events(&self) -> Result<Vec<DeadLetterEvent>, DeadLetterQueueError>;
+
+    /// Processes the event with the given index from the dead-letter queue again.
+    ///
+    /// The event is removed from the dead-letter queue if it is processed successfully.
+    fn retry_dead_letter_event(
+        &self,
+        event_index: u64,
+    ) -> impl Future<Output = Result<(), DeadLetterQueueError>> + Send;
+
+    /// Lists up to `limit` IDs of blobs with slivers stored in the shard, starting after the blob
+    /// ID `after`, if provided.
+    fn list_shard_blobs(
+        &self,
+        shard_index: ShardIndex,
+        after: Option<BlobId>,
+        limit: usize,
+    ) -> impl Future<Output = Result<ShardBlobs, StorageInspectionError>> + Send;
+
+    /// Returns the metadata, blob info, and slivers stored for the blob on this node.
+    fn inspect_blob_storage(
+        &self,
+        blob_id: &BlobId,
+    ) -> impl Future<Output = Result<BlobStorageReport, StorageInspectionError>> + Send;
+
+    /// Returns the event cursor stored by the node.
+    fn event_cursor_report(&self) -> Result<EventCursorReport, StorageInspectionError>;
+
+    /// Returns RocksDB's estimates of the sizes of the node's column families.
+    fn column_family_sizes(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ColumnFamilySize>, StorageInspectionError>> + Send;
+
+    /// Enables or disables maintenance mode.
+    ///
+    /// Returns true if the mode was changed, false if it was already set as requested.
+    fn set_maintenance_mode(&self, enabled: bool) -> bool;
+
+    /// Pauses or resumes the background worker.
+    ///
+    /// Returns true if the state of the worker was changed, false if it was already paused or
+    /// running as requested.
+    fn set_background_worker_paused(&self, worker: BackgroundWorker, paused: bool) -> bool;
+
+    /// Returns whether each of the background workers is paused.
+    fn background_worker_statuses(&self) -> Vec<BackgroundWorkerStatus>;
+
+    /// Returns a summary of the state of the node for operators.
+    fn status_report(&self) -> impl Future<Output = NodeStatusReport> + Send;
+
+    /// Returns the shards gained, lost, and retained by this node in the current epoch.
+    fn shard_assignment_diff(&self) -> ShardAssignmentDiff;
 }
 
 /// Builder to construct a [`StorageNode`].
@@ -363,17 +512,13 @@ impl StorageNodeBuilder {
     /// ([`with_system_event_manager()`][Self::with_system_event_manager],
     /// [`with_committee_service()`][Self::with_committee_service],
     /// [`with_system_contract_service()`][Self::with_system_contract_service]); or if the
-    /// `config.protocol_key_pair` has not yet been loaded into memory.
+    /// `config.protocol_key_pair` is configured but has not yet been loaded into memory.
     pub async fn build(
         self,
         config: &StorageNodeConfig,
         metrics_registry: Registry,
     ) -> Result<StorageNode, anyhow::Error> {
-        let protocol_key_pair = config
-            .protocol_key_pair
-            .get()
-            .expect("protocol key pair must already be loaded")
-            .clone();
+        let protocol_public_key = config.protocol_public_key()?.clone();
 
         let sui_config_and_client =
             if self.event_manager.is_none() || self.committee_service.is_none() {
@@ -422,42 +567,73 @@ impl StorageNodeBuilder {
             }
         };
 
+        let background_transfer_limits = Arc::new(BackgroundTransferLimits::new(
+            config.shard_sync_config.max_sync_bytes_per_second,
+            config.blob_recovery.max_recovery_bytes_per_second,
+        ));
         let committee_service: Arc<dyn CommitteeService> =
             if let Some(service) = self.committee_service {
                 service
             } else {
-                let (read_client, _) = sui_config_and_client
+                let (read_client, sui_config) = sui_config_and_client
                     .expect("this is always created if self.committee_service_factory.is_none()");
-                let service = NodeCommitteeService::builder()
-                    .local_identity(protocol_key_pair.public().clone())
+                let builder = NodeCommitteeService::builder()
+                    .local_identity(protocol_public_key.clone())
                     .config(config.blob_recovery.committee_service_config.clone())
                     .metrics_registry(&metrics_registry)
-                    .build(read_client)
-                    .await?;
+                    .background_transfer_limits(background_transfer_limits.clone());
+                let snapshot_path = config.storage_path.join(COMMITTEE_SNAPSHOT_FILE_NAME);
+                let max_staleness = config
+                    .blob_recovery
+                    .committee_service_config
+                    .committee_snapshot_max_staleness;
+                let service = if let Some(quorum_read_config) = &sui_config.quorum_read_config {
+                    let quorum_read_client = quorum_read_config
+                        .new_quorum_read_client(read_client, sui_config)
+                        .await?;
+                    tracing::info!(
+                        quorum = quorum_read_client.quorum(),
+                        "cross-checking committee reads against multiple full nodes"
+                    );
+                    builder
+                        .build(CachedCommitteeLookupService::new(
+                            quorum_read_client,
+                            snapshot_path,
+                            max_staleness,
+                        ))
+                        .await?
+                } else {
+                    builder
+                        .build(CachedCommitteeLookupService::new(
+                            read_client,
+                            snapshot_path,
+                            max_staleness,
+                        ))
+                        .await?
+                };
                 Arc::new(service)
             };
 
-        let contract_service: Arc<dyn SystemContractService> = if let Some(service) =
-            self.contract_service
-        {
-            service
-        } else {
-            Arc::new(
-                SuiSystemContractService::builder()
-                    .metrics_registry(metrics_registry.clone())
-                    .balance_check_frequency(config.balance_check.interval)
-                    .balance_check_warning_threshold(config.balance_check.warning_threshold_mist)
-                    .build_from_config(
-                        config.sui.as_ref().expect("Sui config must be provided"),
-                        committee_service.clone(),
-                    )
-                    .await?,
-            )
-        };
+        let contract_service: Arc<dyn SystemContractService> =
+            if let Some(service) = self.contract_service {
+                service
+            } else {
+                Arc::new(
+                    SuiSystemContractService::builder()
+                        .metrics_registry(metrics_registry.clone())
+                        .balance_check_config(config.balance_check.clone())
+                        .build_from_config(
+                            config.sui.as_ref().expect("Sui config must be provided"),
+                            committee_service.clone(),
+                        )
+                        .await?,
+                )
+            };
 
         let node_params = NodeParameters {
             pre_created_storage: self.storage,
             num_checkpoints_per_blob: self.num_checkpoints_per_blob,
+            background_transfer_limits,
         };
 
         StorageNode::new(
@@ -490,12 +666,18 @@ pub struct StorageNode {
     node_recovery_handler: NodeRecoveryHandler,
     event_blob_writer_factory: Option<EventBlobWriterFactory>,
     config_synchronizer: Option<Arc<ConfigSynchronizer>>,
+    event_lag_monitor_config: EventLagMonitorConfig,
+    dead_letter_queue_config: DeadLetterQueueConfig,
+    /// Held while processing an event, to prevent retries of events from the dead-letter queue
+    /// from interfering with the processing of the event stream.
+    event_processing_lock: Mutex<()>,
 }
 
 /// The internal state of a Walrus storage node.
 #[derive(Debug)]
 pub struct StorageNodeInner {
-    protocol_key_pair: ProtocolKeyPair,
+    protocol_signer: Arc<dyn ProtocolSigner>,
+    network_public_key: NetworkPublicKey,
     storage: Storage,
     encoding_config: Arc<EncodingConfig>,
     event_manager: Box<dyn EventManager>,
@@ -505,10 +687,27 @@ pub struct StorageNodeInner {
     metrics: NodeMetricSet,
     current_epoch: watch::Sender<Epoch>,
     is_shutting_down: AtomicBool,
+    /// Set while the node is in maintenance mode, see [`StorageNodeConfig::maintenance_mode`].
+    is_in_maintenance: watch::Sender<bool>,
+    /// The policy for deleting the data of abandoned uploads, which can be replaced at runtime.
+    orphaned_sliver_collection: watch::Sender<OrphanedSliverCollectionConfig>,
+    /// Allows pausing and resuming background workers at runtime.
+    background_workers: BackgroundWorkerControls,
+    /// The bandwidth limits of the shard-sync and recovery traffic to other nodes.
+    background_transfer_limits: Arc<BackgroundTransferLimits>,
+    /// Rejects expired and replayed sync shard requests.
+    sync_shard_replay_guard: SyncShardReplayGuard,
     blocklist: Arc<Blocklist>,
     node_capability: ObjectID,
     blob_retirement_notifier: Arc<BlobRetirementNotifier>,
+    /// Drives the invalidation of blobs found to be inconsistently encoded during recovery.
+    blob_invalidator: BlobInvalidator,
     symbol_service: RecoverySymbolService,
+    epoch_sync_done_status: StdMutex<EpochSyncDoneStatus>,
+    /// The sequence number of the latest checkpoint whose events have all been processed, if any.
+    last_processed_checkpoint: StdMutex<Option<u64>>,
+    /// The most recently determined lag of event processing behind the chain.
+    event_processing_lag: StdMutex<Option<EventProcessingLag>>,
 }
 
 /// Parameters for configuring and initializing a node.
@@ -522,6 +721,8 @@ pub struct NodeParameters {
     // Number of checkpoints per blob to use when creating event blobs.
     // If not provided, the default value will be used.
     num_checkpoints_per_blob: Option<u32>,
+    // The bandwidth limits shared with the committee service. Unlimited by default.
+    background_transfer_limits: Arc<BackgroundTransferLimits>,
 }
 
 impl StorageNode {
@@ -575,13 +776,25 @@ impl StorageNode {
         };
         tracing::info!("successfully opened the node database");
 
+        // Fails if neither a protocol key pair nor the public key of a remote signer is configured.
+        let protocol_public_key = config.protocol_public_key()?.clone();
+        let protocol_signer: Arc<dyn ProtocolSigner> =
+            if let Some(signer_config) = &config.remote_protocol_signer {
+                tracing::info!(url = %signer_config.url, "signing protocol messages remotely");
+                Arc::new(RemoteProtocolSigner::new(
+                    signer_config,
+                    protocol_public_key,
+                )?)
+            } else {
+                // Without a remote signer, the public key is the one of the protocol key pair.
+                Arc::new(LocalProtocolSigner::new(config.protocol_key_pair().clone()))
+            };
+
         let blocklist: Arc<Blocklist> = Arc::new(Blocklist::new(&config.blocklist_path)?);
+        let orphaned_sliver_collection = storage.orphaned_sliver_collection_config().clone();
         let inner = Arc::new(StorageNodeInner {
-            protocol_key_pair: config
-                .protocol_key_pair
-                .get()
-                .expect("protocol key pair must already be loaded")
-                .clone(),
+            protocol_signer,
+            network_public_key: config.network_key_pair().public().clone(),
             storage,
             event_manager,
             contract_service: contract_service.clone(),
@@ -590,11 +803,22 @@ impl StorageNode {
             metrics: NodeMetricSet::new(registry),
             start_time,
             is_shutting_down: false.into(),
+            is_in_maintenance: watch::Sender::new(config.maintenance_mode),
+            orphaned_sliver_collection: watch::Sender::new(orphaned_sliver_collection),
+            background_workers: BackgroundWorkerControls::default(),
+            background_transfer_limits: node_params.background_transfer_limits,
+            sync_shard_replay_guard: SyncShardReplayGuard::new(&config.shard_sync_config),
             blocklist: blocklist.clone(),
             node_capability: node_capability.id,
             blob_retirement_notifier: Arc::new(BlobRetirementNotifier::new()),
+            blob_invalidator: BlobInvalidator::default(),
+            epoch_sync_done_status: StdMutex::new(EpochSyncDoneStatus::default()),
+            last_processed_checkpoint: StdMutex::new(None),
+            event_processing_lag: StdMutex::new(None),
             symbol_service: RecoverySymbolService::new(
                 config.blob_recovery.max_proof_cache_elements,
+                config.blob_recovery.max_recovery_symbol_cache_elements,
+                config.blob_recovery.recovery_symbol_cache_ttl,
                 encoding_config.clone(),
                 ThreadPoolBuilder::default()
                     .max_concurrent(config.thread_pool.max_concurrent_tasks)
@@ -609,6 +833,13 @@ impl StorageNode {
 
         inner.init_gauges()?;
 
+        // The committees of earlier epochs are no longer returned by the chain, but are needed to
+        // sync shards from their earlier owners.
+        inner
+            .committee_service
+            .restore_committee_history(inner.storage.committee_history()?);
+        inner.persist_committee_history();
+
         let blob_sync_handler = Arc::new(BlobSyncHandler::new(
             inner.clone(),
             config.blob_recovery.max_concurrent_blob_syncs,
@@ -629,8 +860,11 @@ impl StorageNode {
 
         let start_epoch_change_finisher = StartEpochChangeFinisher::new(inner.clone());
 
-        let node_recovery_handler =
-            NodeRecoveryHandler::new(inner.clone(), blob_sync_handler.clone());
+        let node_recovery_handler = NodeRecoveryHandler::new(
+            inner.clone(),
+            blob_sync_handler.clone(),
+            config.blob_recovery.recovery_order,
+        );
         node_recovery_handler.restart_recovery().await?;
 
         // TODO(WAL-667): remove special case
@@ -666,6 +900,9 @@ impl StorageNode {
             node_recovery_handler,
             event_blob_writer_factory,
             config_synchronizer,
+            event_lag_monitor_config: config.event_lag_monitor.clone(),
+            dead_letter_queue_config: config.dead_letter_queue.clone(),
+            event_processing_lock: Mutex::new(()),
         })
     }
 
@@ -685,6 +922,10 @@ impl StorageNode {
             tracing::warn!(?error, "unable to schedule epoch calls on startup")
         };
 
+        self.verify_shard_ownership()
+            .await
+            .context("failed to verify the ownership of local shards")?;
+
         select! {
             () = self.epoch_change_driver.run() => {
                 unreachable!("epoch change driver never completes");
@@ -708,6 +949,18 @@ impl StorageNode {
                     },
                 }
             },
+            () = self.run_sliver_tiering() => {
+                unreachable!("sliver tiering never completes");
+            },
+            () = self.run_orphaned_sliver_collection() => {
+                unreachable!("orphaned sliver collection never completes");
+            },
+            () = self.run_shard_metrics_reporting() => {
+                unreachable!("shard metrics reporting never completes");
+            },
+            () = self.run_event_lag_monitoring() => {
+                unreachable!("event lag monitoring never completes");
+            },
             config_synchronizer_result = async {
                 if let Some(c) = self.config_synchronizer.as_ref() {
                     c.run().await
@@ -727,6 +980,230 @@ impl StorageNode {
         Ok(())
     }
 
+    /// Compares the shards present in local storage against the shards assigned to the node in the
+    /// current committee.
+    ///
+    /// Shards that are no longer owned by the node are locked, such that they stop accepting writes
+    /// while their data is retained until the shards are removed at the end of an epoch change.
+    /// Shards that are owned but missing locally are created and synced from their previous
+    /// owners. Shards gained in the current epoch are left to the processing of the corresponding
+    /// epoch change event, which may not have happened yet.
+    ///
+    /// The check is skipped while the node is recovering or an epoch change is in progress, as in
+    /// these cases the shards are brought in line with the committee by event processing.
+    async fn verify_shard_ownership(&self) -> anyhow::Result<()> {
+        let node_status = self.inner.storage.node_status()?;
+        let committees = self.inner.committee_service.active_committees();
+        if node_status != NodeStatus::Active || committees.is_change_in_progress() {
+            tracing::info!(
+                ?node_status,
+                "skipping the verification of shard ownership on startup"
+            );
+            return Ok(());
+        }
+
+        let shard_map_lock = self.inner.storage.lock_shards().await;
+        let existing_shards = shard_map_lock.existing_shards().to_vec();
+        let shard_diff_calculator = ShardDiffCalculator::new(
+            &self.inner.shard_assignment_diff_for(&committees),
+            &existing_shards,
+        );
+        let owned_shards = shard_diff_calculator.all_owned_shards();
+
+        for shard in existing_shards
+            .iter()
+            .filter(|shard| !owned_shards.contains(shard))
+        {
+            let Some(shard_storage) = shard_map_lock.shard_storage(*shard) else {
+                continue;
+            };
+            if shard_storage.status()? == ShardStatus::LockedToMove {
+                continue;
+            }
+            tracing::warn!(
+                walrus.shard_index = %shard,
+                "shard stored locally is not assigned to this node; locking it"
+            );
+            shard_storage
+                .lock_shard_for_epoch_change()
+                .context("failed to lock shard")?;
+        }
+
+        let gained_shards = shard_diff_calculator.gained_shards_from_prev_epoch();
+        let missing_shards: Vec<_> = owned_shards
+            .iter()
+            .filter(|shard| !existing_shards.contains(shard) && !gained_shards.contains(shard))
+            .copied()
+            .collect();
+        if missing_shards.is_empty() {
+            return Ok(());
+        }
+        tracing::warn!(
+            ?missing_shards,
+            "shards assigned to this node are missing locally; starting shard sync"
+        );
+        self.inner
+            .create_storage_for_shards_in_background(missing_shards.clone(), shard_map_lock)
+            .await?;
+        self.shard_sync_handler
+            .start_sync_shards(missing_shards, false)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Periodically moves slivers that have not been accessed recently to the cold storage tier.
+    ///
+    /// Never completes, and does nothing if sliver tiering is disabled, while the node is in
+    /// maintenance mode, or while the worker is paused.
+    async fn run_sliver_tiering(&self) {
+        let config = self.inner.storage.sliver_tiering_config().clone();
+        if !config.enabled {
+            return std::future::pending().await;
+        }
+
+        let mut interval = tokio::time::interval(config.demotion_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if self.inner.is_in_maintenance()
+                || self
+                    .inner
+                    .background_workers
+                    .is_paused(BackgroundWorker::SliverTiering)
+            {
+                continue;
+            }
+            match self.inner.storage.demote_cold_slivers().await {
+                Ok(demoted) => tracing::debug!(demoted, "moved cold slivers to the cold tier"),
+                Err(error) => tracing::warn!(?error, "failed to move slivers to the cold tier"),
+            }
+        }
+    }
+
+    /// Periodically deletes the metadata and slivers of blobs that were registered but never
+    /// certified within the grace period.
+    ///
+    /// Never completes, and does nothing while the collection is disabled, while the node is in
+    /// maintenance mode, or while the worker is paused. The collection restarts with the new
+    /// policy whenever the policy is replaced by a reloaded configuration.
+    async fn run_orphaned_sliver_collection(&self) {
+        let mut config_receiver = self.inner.orphaned_sliver_collection.subscribe();
+        loop {
+            let config = config_receiver.borrow_and_update().clone();
+            if !config.enabled {
+                config_receiver
+                    .changed()
+                    .await
+                    .expect("the sender is owned by the node");
+                continue;
+            }
+            self.collect_orphaned_slivers_periodically(&config, &mut config_receiver)
+                .await;
+        }
+    }
+
+    /// Deletes the data of abandoned uploads at the interval of `config` until the policy is
+    /// replaced.
+    async fn collect_orphaned_slivers_periodically(
+        &self,
+        config: &OrphanedSliverCollectionConfig,
+        config_receiver: &mut watch::Receiver<OrphanedSliverCollectionConfig>,
+    ) {
+        let mut interval = tokio::time::interval(config.collection_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => (),
+                changed = config_receiver.changed() => {
+                    changed.expect("the sender is owned by the node");
+                    return;
+                }
+            }
+            if self.inner.is_in_maintenance()
+                || self
+                    .inner
+                    .background_workers
+                    .is_paused(BackgroundWorker::OrphanedSliverCollection)
+            {
+                continue;
+            }
+            let current_epoch = self.inner.current_epoch();
+            match self
+                .inner
+                .storage
+                .collect_orphaned_slivers(config, current_epoch, &self.event_processing_lock)
+                .await
+            {
+                Ok(collected) => {
+                    self.inner
+                        .metrics
+                        .orphaned_blobs_deleted_total
+                        .inc_by(collected.deleted_blobs as u64);
+                    self.inner
+                        .metrics
+                        .orphaned_blob_bytes_reclaimed_total
+                        .inc_by(collected.reclaimed_bytes);
+                    if collected.deleted_blobs > 0 {
+                        tracing::info!(
+                            deleted_blobs = collected.deleted_blobs,
+                            reclaimed_bytes = collected.reclaimed_bytes,
+                            "deleted the data of abandoned uploads"
+                        );
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(?error, "failed to delete the data of abandoned uploads")
+                }
+            }
+        }
+    }
+
+    /// Periodically reports the estimated contents and the status of each shard.
+    ///
+    /// Reporting is paused while the node is in maintenance mode.
+    async fn run_shard_metrics_reporting(&self) {
+        let mut interval = tokio::time::interval(SHARD_METRICS_REPORTING_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if self.inner.is_in_maintenance() {
+                continue;
+            }
+            self.inner.report_shard_metrics().await;
+        }
+    }
+
+    /// Periodically reports the lag of event processing behind the chain and logs a warning or an
+    /// error if the lag exceeds the configured thresholds.
+    async fn run_event_lag_monitoring(&self) {
+        let config = &self.event_lag_monitor_config;
+        let mut interval = tokio::time::interval(config.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let lag = match self.inner.report_event_processing_lag().await {
+                Ok(Some(lag)) => lag,
+                Ok(None) => continue,
+                Err(error) => {
+                    tracing::warn!(?error, "failed to determine the event-processing lag");
+                    continue;
+                }
+            };
+            match config.status(lag) {
+                EventLagStatus::Ok => (),
+                EventLagStatus::Warning => tracing::warn!(
+                    lag_secs = lag.as_secs(),
+                    "event processing is lagging behind the chain"
+                ),
+                EventLagStatus::Error => tracing::error!(
+                    lag_secs = lag.as_secs(),
+                    "event processing is severely lagging behind the chain"
+                ),
+            }
+        }
+    }
+
     /// Returns the shards which the node currently manages in its storage.
     ///
     /// This neither considers the current shard assignment from the Walrus contracts nor the status
@@ -748,6 +1225,43 @@ impl StorageNode {
         *epoch_ref
     }
 
+    /// Enables or disables maintenance mode, overriding [`StorageNodeConfig::maintenance_mode`].
+    ///
+    /// Returns true if the mode was changed, false if it was already set as requested.
+    pub fn set_maintenance_mode(&self, enabled: bool) -> bool {
+        self.inner.set_maintenance_mode(enabled)
+    }
+
+    /// Applies the settings of a reloaded configuration that can be changed while the node is
+    /// running.
+    ///
+    /// All other settings are ignored; see [`StorageNodeConfig::reload_changes`] for determining
+    /// which changes require a restart.
+    pub fn apply_reloaded_config(&self, config: &StorageNodeConfig) {
+        self.blob_sync_handler.set_concurrency_limits(
+            config.blob_recovery.max_concurrent_blob_syncs,
+            config.blob_recovery.max_concurrent_sliver_syncs,
+        );
+        self.shard_sync_handler
+            .update_config(config.shard_sync_config.clone());
+        self.inner
+            .background_transfer_limits
+            .recovery
+            .set_rate(config.blob_recovery.max_recovery_bytes_per_second);
+        self.inner
+            .committee_service
+            .update_config(&config.blob_recovery.committee_service_config);
+        self.inner
+            .orphaned_sliver_collection
+            .send_if_modified(|current| {
+                let modified = *current != config.db_config.orphaned_sliver_collection;
+                if modified {
+                    current.clone_from(&config.db_config.orphaned_sliver_collection);
+                }
+                modified
+            });
+    }
+
     /// Continues the event stream from the last committed event.
     async fn continue_event_stream(
         &self,
@@ -885,6 +1399,10 @@ impl StorageNode {
         // Important: Events must be handled consecutively and in order to prevent (intermittent)
         // invariant violations and interference between different events.
         while let Some((element_index, stream_element)) = indexed_element_stream.next().await {
+            self.inner
+                .background_workers
+                .wait_until_resumed(BackgroundWorker::EventProcessing)
+                .await;
             let node_status = self.inner.storage.node_status()?;
             let span = tracing::info_span!(
                 parent: &Span::current(),
@@ -906,6 +1424,13 @@ impl StorageNode {
                 "walrus.node_status" = %node_status,
                 "error.type" = field::Empty,
             );
+            TraceSampler::global().sample_root_span(
+                &span,
+                &[
+                    &format!("blob_store receive {}", stream_element.element.label()),
+                    "blob_store receive",
+                ],
+            );
 
             fail_point_arg!("event_processing_epoch_check", |epoch: Epoch| {
                 tracing::info!("updating epoch check to {:?}", epoch);
@@ -954,6 +1479,7 @@ impl StorageNode {
                     stream_element.element.event_id(),
                     self.inner.clone(),
                 );
+                let _guard = self.event_processing_lock.lock().await;
                 self.process_event(event_handle, stream_element.clone())
                     .inspect_err(|err| {
                         let span = tracing::Span::current();
@@ -962,6 +1488,16 @@ impl StorageNode {
                     })
                     .instrument(span)
                     .await?;
+                // The event stream contains a boundary at the end of every checkpoint, including
+                // checkpoints without Walrus events, such that the processed checkpoint advances
+                // with the chain even if no events are emitted.
+                if stream_element.is_end_of_checkpoint_marker() {
+                    self.inner.set_last_processed_checkpoint(
+                        stream_element
+                            .checkpoint_event_position
+                            .checkpoint_sequence_number,
+                    );
+                }
             }
 
             if should_write {
@@ -1010,19 +1546,89 @@ impl StorageNode {
         Ok(())
     }
 
+    /// Processes the blob event, retrying it if it fails.
+    ///
+    /// If processing the event still fails after the configured number of attempts, the event is
+    /// moved to the dead-letter queue and marked as complete, such that processing continues with
+    /// the subsequent events. Storage errors are not specific to the event and are therefore
+    /// returned instead, which halts event processing.
+    ///
+    /// As storage errors are never dead-lettered, the blob info of an event in the dead-letter
+    /// queue has always been updated, and only the remaining handling is repeated on a retry.
     #[tracing::instrument(skip_all)]
     async fn process_blob_event(
         &self,
         event_handle: EventHandle,
         blob_event: BlobEvent,
     ) -> anyhow::Result<()> {
-        self.inner
-            .storage
-            .update_blob_info(event_handle.index(), &blob_event)?;
+        let config = &self.dead_letter_queue_config;
+        let event_index = event_handle.index();
+        let event_id = event_handle.event_id();
+        let mut event_handle = Some(event_handle);
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let result = match self
+                .inner
+                .storage
+                .update_blob_info(event_index, &blob_event)
+            {
+                Ok(()) => self.handle_blob_event(&mut event_handle, &blob_event).await,
+                Err(error) => Err(error.into()),
+            };
+            let Err(error) = result else {
+                return Ok(());
+            };
+            if config.max_attempts == 0 || event_handle.is_none() {
+                // Either the dead-letter queue is disabled or the event was already handed off for
+                // completion, in which case it must not be completed again.
+                return Err(error);
+            }
+            if attempts < config.max_attempts {
+                tracing::warn!(?error, attempts, "failed to process blob event; retrying");
+                tokio::time::sleep(config.retry_interval).await;
+                continue;
+            }
+            if is_storage_error(&error) {
+                tracing::error!(
+                    ?error,
+                    attempts,
+                    "failed to process blob event due to a storage error; halting event processing"
+                );
+                return Err(error);
+            }
+
+            tracing::error!(
+                ?error,
+                attempts,
+                "failed to process blob event; moving it to the dead-letter queue"
+            );
+            self.inner.storage.put_dead_letter_event(
+                event_index,
+                event_id,
+                blob_event,
+                attempts,
+                format!("{error:?}"),
+            )?;
+            self.inner.metrics.blob_events_dead_lettered_total.inc();
+            event_handle.take().mark_as_complete();
+            return Ok(());
+        }
+    }
+
+    /// Handles the blob event, after its blob info has been updated.
+    ///
+    /// The event handle is only taken once the event no longer fails, and is `None` when retrying
+    /// an event from the dead-letter queue, which was already marked as complete.
+    async fn handle_blob_event(
+        &self,
+        event_handle: &mut Option<EventHandle>,
+        blob_event: &BlobEvent,
+    ) -> anyhow::Result<()> {
         tracing::debug!(?blob_event, "{} event received", blob_event.name());
         match blob_event {
             BlobEvent::Registered(_) => {
-                event_handle.mark_as_complete();
+                event_handle.take().mark_as_complete();
             }
             BlobEvent::Certified(event) => {
                 self.process_blob_certified_event(event_handle, event)
@@ -1122,8 +1728,8 @@ impl StorageNode {
     #[tracing::instrument(skip_all)]
     async fn process_blob_certified_event(
         &self,
-        event_handle: EventHandle,
-        event: BlobCertified,
+        event_handle: &mut Option<EventHandle>,
+        event: &BlobCertified,
     ) -> anyhow::Result<()> {
         let start = tokio::time::Instant::now();
         let histogram_set = self.inner.metrics.recover_blob_duration_seconds.clone();
@@ -1132,7 +1738,7 @@ impl StorageNode {
             || self.inner.storage.node_status()? == NodeStatus::RecoveryCatchUp
             || self.inner.is_stored_at_all_shards(&event.blob_id).await?
         {
-            event_handle.mark_as_complete();
+            event_handle.take().mark_as_complete();
 
             walrus_utils::with_label!(histogram_set, metrics::STATUS_SKIPPED)
                 .observe(start.elapsed().as_secs_f64());
@@ -1142,7 +1748,7 @@ impl StorageNode {
 
         // Slivers and (possibly) metadata are not stored, so initiate blob sync.
         self.blob_sync_handler
-            .start_sync(event.blob_id, event.epoch, Some(event_handle))
+            .start_sync(event.blob_id, event.epoch, event_handle.take())
             .await?;
 
         Ok(())
@@ -1151,8 +1757,8 @@ impl StorageNode {
     #[tracing::instrument(skip_all)]
     async fn process_blob_deleted_event(
         &self,
-        event_handle: EventHandle,
-        event: BlobDeleted,
+        event_handle: &mut Option<EventHandle>,
+        event: &BlobDeleted,
     ) -> anyhow::Result<()> {
         let blob_id = event.blob_id;
 
@@ -1172,8 +1778,12 @@ impl StorageNode {
             // subsequent certify or delete events may update the `blob_info`; so we cannot remove
             // it even if it is no longer valid in the *current* epoch
             if !blob_info.is_registered(event.epoch) {
-                tracing::debug!("deleting data for deleted blob");
-                // TODO (WAL-201): Actually delete blob data.
+                if self.inner.storage.is_blob_pinned(&blob_id)? {
+                    tracing::debug!("retaining data for deleted blob as it is pinned");
+                } else {
+                    tracing::debug!("deleting data for deleted blob");
+                    self.inner.storage.delete_blob_data(&blob_id).await?;
+                }
             }
         } else {
             tracing::warn!(
@@ -1182,7 +1792,7 @@ impl StorageNode {
             );
         }
 
-        event_handle.mark_as_complete();
+        event_handle.take().mark_as_complete();
 
         Ok(())
     }
@@ -1190,8 +1800,8 @@ impl StorageNode {
     #[tracing::instrument(skip_all)]
     async fn process_blob_invalid_event(
         &self,
-        event_handle: EventHandle,
-        event: InvalidBlobId,
+        event_handle: &mut Option<EventHandle>,
+        event: &InvalidBlobId,
     ) -> anyhow::Result<()> {
         self.inner
             .blob_retirement_notifier
@@ -1201,10 +1811,42 @@ impl StorageNode {
             .await?;
         self.inner.storage.delete_blob_data(&event.blob_id).await?;
 
-        event_handle.mark_as_complete();
+        event_handle.take().mark_as_complete();
         Ok(())
     }
 
+    /// Starts the recovery of a blob whose data was found to be corrupted and was discarded.
+    fn recover_corrupted_blob(&self, blob_id: &BlobId) {
+        let certified_epoch = match self.inner.storage.get_blob_info(blob_id) {
+            Ok(blob_info) => blob_info.and_then(|info| info.initial_certified_epoch()),
+            Err(error) => {
+                tracing::warn!(?error, "unable to retrieve blob info of corrupted blob");
+                return;
+            }
+        };
+        let Some(certified_epoch) = certified_epoch else {
+            tracing::debug!(
+                walrus.blob_id = %blob_id,
+                "corrupted blob is not certified; not recovering it"
+            );
+            return;
+        };
+
+        let blob_sync_handler = self.blob_sync_handler.clone();
+        let blob_id = *blob_id;
+        tokio::spawn(
+            async move {
+                if let Err(error) = blob_sync_handler
+                    .start_sync(blob_id, certified_epoch, None)
+                    .await
+                {
+                    tracing::warn!(?error, "unable to start the recovery of corrupted blob");
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
     #[tracing::instrument(skip_all)]
     async fn process_epoch_change_start_event(
         &self,
@@ -1279,6 +1921,7 @@ impl StorageNode {
             .committee_service
             .begin_committee_change_to_latest_committee()
             .await?;
+        self.inner.persist_committee_history();
 
         // For blobs that are expired in the new epoch, sends a notification to all the tasks
         // that may be affected by the blob expiration.
@@ -1341,6 +1984,37 @@ impl StorageNode {
             return Ok(());
         }
 
+        // Shard syncs that are still running for a previous epoch fetch slivers from owners in
+        // the previous committee. Pause them while the shards are reassigned, and resume them
+        // against the new committee below.
+        let paused_shard_syncs = self
+            .shard_sync_handler
+            .pause_syncs_before_epoch(event.epoch)
+            .await;
+
+        let result = self
+            .process_committee_change_with_paused_syncs(event_handle, event, shard_map_lock)
+            .await;
+
+        // Resume the paused syncs even if processing the epoch change failed, as they would
+        // otherwise only be restarted when the node restarts.
+        let resumed = self
+            .shard_sync_handler
+            .resume_paused_syncs(paused_shard_syncs)
+            .await;
+        result?;
+        resumed?;
+        Ok(())
+    }
+
+    /// Processes the committee change of the epoch change start event while the shard syncs of
+    /// previous epochs are paused.
+    async fn process_committee_change_with_paused_syncs(
+        &self,
+        event_handle: EventHandle,
+        event: &EpochChangeStart,
+        shard_map_lock: StorageShardLock,
+    ) -> anyhow::Result<()> {
         // For blobs that are expired in the new epoch, sends a notification to all the tasks
         // that may be affected by the blob expiration.
         self.inner
@@ -1398,11 +2072,12 @@ impl StorageNode {
         self.inner
             .set_node_status(NodeStatus::RecoveryInProgress(event.epoch))?;
 
-        let public_key = self.inner.public_key();
         let storage = &self.inner.storage;
         let committees = self.inner.committee_service.active_committees();
-        let shard_diff_calculator =
-            ShardDiffCalculator::new(&committees, public_key, shard_map_lock.existing_shards());
+        let shard_diff_calculator = ShardDiffCalculator::new(
+            &self.inner.shard_assignment_diff_for(&committees),
+            shard_map_lock.existing_shards(),
+        );
 
         // Since the node is doing a full recovery, its local shards may be out of sync with the
         // contract for multiple epochs. Here we need to make sure that all the shards that is
@@ -1482,6 +2157,7 @@ impl StorageNode {
                     "successfully started a transition to a new epoch"
                 );
                 self.inner.current_epoch.send_replace(epoch);
+                self.inner.persist_committee_history();
                 Ok(true)
             }
             Err(BeginCommitteeChangeError::EpochIsTheSameAsCurrent) => {
@@ -1519,13 +2195,14 @@ impl StorageNode {
         new_node_joining_committee: bool,
         shard_map_lock: StorageShardLock,
     ) -> anyhow::Result<()> {
-        let public_key = self.inner.public_key();
         let storage = &self.inner.storage;
         let committees = self.inner.committee_service.active_committees();
         assert!(event.epoch <= committees.epoch());
 
-        let shard_diff_calculator =
-            ShardDiffCalculator::new(&committees, public_key, shard_map_lock.existing_shards());
+        let shard_diff_calculator = ShardDiffCalculator::new(
+            &self.inner.shard_assignment_diff_for(&committees),
+            shard_map_lock.existing_shards(),
+        );
 
         let shards_gained = shard_diff_calculator.gained_shards_from_prev_epoch();
         self.create_new_shards_and_start_sync(
@@ -1546,6 +2223,8 @@ impl StorageNode {
                 .lock_shard_for_epoch_change()
                 .context("failed to lock shard")?;
         }
+        self.shard_sync_handler
+            .start_metadata_push(shard_diff_calculator.shards_to_lock(), &committees);
 
         self.start_epoch_change_finisher
             .start_finish_epoch_change_tasks(
@@ -1717,6 +2396,199 @@ impl StorageNodeInner {
         self.node_capability
     }
 
+    /// Attests on chain that the node has finished syncing its shards for `epoch`.
+    ///
+    /// The contract service retries the submission until it succeeds or the epoch is superseded.
+    /// The progress of the submission is reported in the node's status report.
+    pub(crate) async fn attest_epoch_sync_done(&self, epoch: Epoch) {
+        self.epoch_sync_done_status
+            .lock()
+            .expect("mutex should not be poisoned")
+            .pending_epoch = Some(epoch);
+
+        self.contract_service
+            .epoch_sync_done(epoch, self.node_capability())
+            .await;
+
+        // The contract service only stops retrying early if the chain has moved past the epoch.
+        let superseded = epoch < self.contract_service.current_epoch();
+        let mut status = self
+            .epoch_sync_done_status
+            .lock()
+            .expect("mutex should not be poisoned");
+        if status.pending_epoch == Some(epoch) {
+            status.pending_epoch = None;
+        }
+        if superseded {
+            tracing::info!(
+                walrus.epoch = epoch,
+                "epoch sync done attestation was superseded by a later epoch"
+            );
+        } else if status.last_attested_epoch.is_none_or(|last| last < epoch) {
+            status.last_attested_epoch = Some(epoch);
+        }
+    }
+
+    /// Sets the per-shard gauges for the status and the estimated contents of each shard.
+    async fn report_shard_metrics(&self) {
+        let shards = self.storage.existing_shard_storages().await;
+
+        // Reset the gauges to remove the series of shards that are no longer stored.
+        self.metrics.shard_status.reset();
+        self.metrics.shard_stored_slivers.reset();
+        self.metrics.shard_stored_bytes.reset();
+
+        for shard in shards {
+            let shard_label = shard.id().to_string();
+            match shard.status() {
+                Ok(current_status) => {
+                    for status in ShardStatus::ALL {
+                        walrus_utils::with_label!(
+                            self.metrics.shard_status,
+                            &shard_label,
+                            status.as_str()
+                        )
+                        .set((status == current_status).into());
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(?error, walrus.shard_index = %shard.id(), "failed to get shard status");
+                }
+            }
+
+            for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+                match shard.estimated_sliver_count_and_bytes(sliver_type) {
+                    Ok((count, bytes)) => {
+                        let sliver_type = sliver_type.to_string();
+                        walrus_utils::with_label!(
+                            self.metrics.shard_stored_slivers,
+                            &shard_label,
+                            &sliver_type
+                        )
+                        .set(i64::try_from(count).unwrap_or(i64::MAX));
+                        walrus_utils::with_label!(
+                            self.metrics.shard_stored_bytes,
+                            &shard_label,
+                            &sliver_type
+                        )
+                        .set(i64::try_from(bytes).unwrap_or(i64::MAX));
+                    }
+                    Err(error) => {
+                        tracing::warn!(
+                            ?error,
+                            walrus.shard_index = %shard.id(),
+                            "failed to estimate the contents of the shard"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Discards an entry of the blob that failed checksum verification when read from disk.
+    ///
+    /// The entry is deleted such that it is considered missing; the caller is expected to trigger
+    /// the recovery of the blob.
+    fn discard_corrupted_entry(
+        &self,
+        blob_id: &BlobId,
+        kind: &str,
+        error: TypedStoreError,
+        discard: impl FnOnce() -> Result<(), TypedStoreError>,
+    ) -> anyhow::Result<()> {
+        tracing::error!(
+            ?error,
+            walrus.blob_id = %blob_id,
+            kind,
+            "stored data is corrupted; discarding it for recovery"
+        );
+        walrus_utils::with_label!(self.metrics.corrupted_entries_discarded_total, kind).inc();
+        discard().context("unable to discard corrupted data")
+    }
+
+    fn set_last_processed_checkpoint(&self, sequence_number: u64) {
+        *self
+            .last_processed_checkpoint
+            .lock()
+            .expect("mutex should not be poisoned") = Some(sequence_number);
+    }
+
+    /// Sets the gauges for the lag of event processing behind the latest checkpoint on chain.
+    ///
+    /// The lag is measured from the latest checkpoint that the node has fully processed. Returns
+    /// the lag in time, or `None` if no checkpoint has been processed yet, which is also the case
+    /// if the events are not obtained from checkpoints.
+    async fn report_event_processing_lag(&self) -> anyhow::Result<Option<Duration>> {
+        let Some(processed) = *self
+            .last_processed_checkpoint
+            .lock()
+            .expect("mutex should not be poisoned")
+        else {
+            return Ok(None);
+        };
+        let latest = self
+            .contract_service
+            .latest_checkpoint_sequence_number()
+            .await?;
+
+        let lag_checkpoints = latest.saturating_sub(processed);
+        let lag = if lag_checkpoints == 0 {
+            Duration::ZERO
+        } else {
+            let latest_time = self.contract_service.checkpoint_time(latest).await?;
+            let processed_time = self.contract_service.checkpoint_time(processed).await?;
+            (latest_time - processed_time).to_std().unwrap_or_default()
+        };
+
+        self.metrics
+            .event_processing_lag_checkpoints
+            .set(lag_checkpoints);
+        self.metrics.event_processing_lag_seconds.set(lag.as_secs());
+        *self
+            .event_processing_lag
+            .lock()
+            .expect("mutex should not be poisoned") = Some(EventProcessingLag {
+            checkpoints: lag_checkpoints,
+            seconds: lag.as_secs(),
+        });
+        Ok(Some(lag))
+    }
+
+    /// Returns the estimated amount of data stored across all shards of the node.
+    async fn storage_usage(&self) -> StorageUsage {
+        let shards = self.storage.existing_shard_storages().await;
+        let mut usage = StorageUsage {
+            n_shards: shards.len(),
+            ..Default::default()
+        };
+
+        for shard in shards {
+            for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+                match shard.estimated_sliver_count_and_bytes(sliver_type) {
+                    Ok((count, bytes)) => {
+                        usage.stored_slivers += count;
+                        usage.stored_bytes += bytes;
+                    }
+                    Err(error) => {
+                        tracing::warn!(
+                            ?error,
+                            walrus.shard_index = %shard.id(),
+                            "failed to estimate the contents of the shard"
+                        );
+                    }
+                }
+            }
+        }
+        usage
+    }
+
+    fn epoch_sync_done_status(&self) -> EpochSyncDoneStatus {
+        *self
+            .epoch_sync_done_status
+            .lock()
+            .expect("mutex should not be poisoned")
+    }
+
     pub(crate) fn owned_shards(&self) -> Vec<ShardIndex> {
         self.committee_service
             .active_committees()
@@ -1795,8 +2667,13 @@ impl StorageNodeInner {
         self.storage.reposition_event_cursor(event_index, event_id)
     }
 
-    fn is_blocked(&self, blob_id: &BlobId) -> bool {
-        self.blocklist.is_blocked(blob_id)
+    /// Returns true if the blob is on the deny list, recording the denied operation if it is.
+    fn is_blocked(&self, blob_id: &BlobId, operation: &str) -> bool {
+        let is_blocked = self.blocklist.is_blocked(blob_id);
+        if is_blocked {
+            walrus_utils::with_label!(self.metrics.denied_blob_requests_total, operation).inc();
+        }
+        is_blocked
     }
 
     async fn get_shard_for_sliver_pair(
@@ -1823,7 +2700,17 @@ impl StorageNodeInner {
     }
 
     fn public_key(&self) -> &PublicKey {
-        self.protocol_key_pair.as_ref().public()
+        self.protocol_signer.public_key()
+    }
+
+    /// Returns the changes to the shards of this node in the current epoch of the committees.
+    ///
+    /// The changes published by the committee service are used if they are for the same epoch.
+    fn shard_assignment_diff_for(&self, committees: &ActiveCommittees) -> ShardAssignmentDiff {
+        self.committee_service
+            .shard_assignment_diff()
+            .filter(|diff| diff.epoch == committees.epoch())
+            .unwrap_or_else(|| ShardAssignmentDiff::new(committees, self.public_key()))
     }
 
     fn shard_health_status(
@@ -1907,6 +2794,20 @@ impl StorageNodeInner {
             .context("unable to store sliver")?;
 
         walrus_utils::with_label!(self.metrics.slivers_stored_total, sliver.r#type()).inc();
+        let shard_label = shard_storage.id().to_string();
+        let sliver_type = sliver.r#type().to_string();
+        walrus_utils::with_label!(
+            self.metrics.shard_slivers_stored_total,
+            &shard_label,
+            &sliver_type
+        )
+        .inc();
+        walrus_utils::with_label!(
+            self.metrics.shard_sliver_bytes_stored_total,
+            &shard_label,
+            &sliver_type
+        )
+        .inc_by(sliver.len() as u64);
 
         Ok(true)
     }
@@ -1958,6 +2859,30 @@ impl StorageNodeInner {
         self.is_shutting_down.load(Ordering::SeqCst)
     }
 
+    fn is_in_maintenance(&self) -> bool {
+        *self.is_in_maintenance.borrow()
+    }
+
+    /// Persists the committee history of the committee service, such that shards can be synced
+    /// from their owners in earlier epochs after a restart.
+    fn persist_committee_history(&self) {
+        if let Err(error) = self
+            .storage
+            .replace_committee_history(&self.committee_service.committee_history())
+        {
+            tracing::warn!(?error, "failed to persist the committee history");
+        }
+    }
+
+    /// Waits until the node is not in maintenance mode.
+    pub(crate) async fn wait_until_not_in_maintenance(&self) {
+        self.is_in_maintenance
+            .subscribe()
+            .wait_for(|is_in_maintenance| !is_in_maintenance)
+            .await
+            .expect("the sender is owned by the node");
+    }
+
     async fn try_retrieve_recovery_symbol(
         &self,
         blob_id: &BlobId,
@@ -2035,12 +2960,23 @@ fn increment_shard_summary(
     }
 }
 
+/// Returns true if the error was caused by the node's database.
+fn is_storage_error(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.downcast_ref::<TypedStoreError>().is_some())
+}
+
 impl ServiceState for StorageNode {
     fn retrieve_metadata(
         &self,
         blob_id: &BlobId,
     ) -> Result<VerifiedBlobMetadataWithId, RetrieveMetadataError> {
-        self.inner.retrieve_metadata(blob_id)
+        let result = self.inner.retrieve_metadata(blob_id);
+        if matches!(result, Err(RetrieveMetadataError::Corrupted)) {
+            self.recover_corrupted_blob(blob_id);
+        }
+        result
     }
 
     fn store_metadata(
@@ -2063,8 +2999,16 @@ impl ServiceState for StorageNode {
         sliver_pair_index: SliverPairIndex,
         sliver_type: SliverType,
     ) -> impl Future<Output = Result<Sliver, RetrieveSliverError>> + Send {
-        self.inner
-            .retrieve_sliver(blob_id, sliver_pair_index, sliver_type)
+        async move {
+            let result = self
+                .inner
+                .retrieve_sliver(blob_id, sliver_pair_index, sliver_type)
+                .await;
+            if matches!(result, Err(RetrieveSliverError::Corrupted)) {
+                self.recover_corrupted_blob(blob_id);
+            }
+            result
+        }
     }
 
     fn store_sliver(
@@ -2119,6 +3063,13 @@ impl ServiceState for StorageNode {
         self.inner.blob_status(blob_id)
     }
 
+    fn blob_storage_status(
+        &self,
+        blob_id: &BlobId,
+    ) -> impl Future<Output = Result<BlobStorageStatus, BlobStatusError>> + Send {
+        self.inner.blob_storage_status(blob_id)
+    }
+
     fn n_shards(&self) -> NonZeroU16 {
         self.inner.n_shards()
     }
@@ -2142,6 +3093,124 @@ impl ServiceState for StorageNode {
     ) -> impl Future<Output = Result<SyncShardResponse, SyncShardServiceError>> + Send {
         self.inner.sync_shard(public_key, signed_request)
     }
+
+    fn pin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError> {
+        self.inner.pin_blob(blob_id)
+    }
+
+    fn unpin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError> {
+        self.inner.unpin_blob(blob_id)
+    }
+
+    fn list_pinned_blobs(
+        &self,
+    ) -> impl Future<Output = Result<Vec<PinnedBlob>, BlobPinningError>> + Send {
+        self.inner.list_pinned_blobs()
+    }
+
+    fn deny_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError> {
+        self.inner.deny_blob(blob_id)
+    }
+
+    fn allow_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError> {
+        self.inner.allow_blob(blob_id)
+    }
+
+    fn list_denied_blobs(&self) -> Vec<BlobId> {
+        self.inner.list_denied_blobs()
+    }
+
+    fn list_dead_letter_events(&self) -> Result<Vec<DeadLetterEvent>, DeadLetterQueueError> {
+        self.inner.list_dead_letter_events()
+    }
+
+    fn list_shard_blobs(
+        &self,
+        shard_index: ShardIndex,
+        after: Option<BlobId>,
+        limit: usize,
+    ) -> impl Future<Output = Result<ShardBlobs, StorageInspectionError>> + Send {
+        self.inner.list_shard_blobs(shard_index, after, limit)
+    }
+
+    fn inspect_blob_storage(
+        &self,
+        blob_id: &BlobId,
+    ) -> impl Future<Output = Result<BlobStorageReport, StorageInspectionError>> + Send {
+        self.inner.inspect_blob_storage(blob_id)
+    }
+
+    fn event_cursor_report(&self) -> Result<EventCursorReport, StorageInspectionError> {
+        self.inner.event_cursor_report()
+    }
+
+    fn column_family_sizes(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ColumnFamilySize>, StorageInspectionError>> + Send {
+        self.inner.column_family_sizes()
+    }
+
+    async fn retry_dead_letter_event(&self, event_index: u64) -> Result<(), DeadLetterQueueError> {
+        let _guard = self.event_processing_lock.lock().await;
+        let entry = self
+            .inner
+            .storage
+            .get_dead_letter_event(event_index)
+            .context("could not read the dead-letter queue")?
+            .ok_or(DeadLetterQueueError::NotFound(event_index))?;
+
+        tracing::info!(
+            walrus.event.index = event_index,
+            walrus.blob_id = %entry.event.blob_id(),
+            "retrying event from the dead-letter queue"
+        );
+        // The blob info was updated when the event was first processed, see
+        // `process_blob_event`. The event index has since been passed, so the update must not be
+        // repeated and only the handling of the event is retried.
+        match self.handle_blob_event(&mut None, &entry.event).await {
+            Ok(()) => {
+                self.inner
+                    .storage
+                    .remove_dead_letter_event(event_index)
+                    .context("could not remove the event from the dead-letter queue")?;
+                Ok(())
+            }
+            Err(error) => {
+                let message = format!("{error:#}");
+                self.inner
+                    .storage
+                    .put_dead_letter_event(
+                        event_index,
+                        entry.event_id,
+                        entry.event,
+                        entry.attempts + 1,
+                        format!("{error:?}"),
+                    )
+                    .context("could not update the dead-letter queue")?;
+                Err(DeadLetterQueueError::ProcessingFailed(message))
+            }
+        }
+    }
+
+    fn set_maintenance_mode(&self, enabled: bool) -> bool {
+        self.inner.set_maintenance_mode(enabled)
+    }
+
+    fn set_background_worker_paused(&self, worker: BackgroundWorker, paused: bool) -> bool {
+        self.inner.set_background_worker_paused(worker, paused)
+    }
+
+    fn background_worker_statuses(&self) -> Vec<BackgroundWorkerStatus> {
+        self.inner.background_worker_statuses()
+    }
+
+    fn status_report(&self) -> impl Future<Output = NodeStatusReport> + Send {
+        self.inner.status_report()
+    }
+
+    fn shard_assignment_diff(&self) -> ShardAssignmentDiff {
+        self.inner.shard_assignment_diff()
+    }
 }
 
 impl ServiceState for StorageNodeInner {
@@ -2161,16 +3230,27 @@ impl ServiceState for StorageNodeInner {
             }
         }
 
-        ensure!(!self.is_blocked(blob_id), RetrieveMetadataError::Forbidden);
+        ensure!(
+            !self.is_blocked(blob_id, "retrieve_metadata"),
+            RetrieveMetadataError::Forbidden
+        );
 
         ensure!(
             self.is_blob_registered(blob_id)?,
             RetrieveMetadataError::Unavailable,
         );
 
-        self.storage
-            .get_metadata(blob_id)
-            .context("database error when retrieving metadata")?
+        let metadata = match self.storage.get_metadata(blob_id) {
+            Err(error) if is_corruption_error(&error) => {
+                self.discard_corrupted_entry(blob_id, "metadata", error, || {
+                    self.storage.discard_metadata(blob_id)
+                })?;
+                return Err(RetrieveMetadataError::Corrupted);
+            }
+            result => result.context("database error when retrieving metadata")?,
+        };
+
+        metadata
             .ok_or(RetrieveMetadataError::Unavailable)
             .inspect(|_| self.metrics.metadata_retrieved_total.inc())
     }
@@ -2179,6 +3259,15 @@ impl ServiceState for StorageNodeInner {
         &self,
         metadata: UnverifiedBlobMetadataWithId,
     ) -> Result<bool, StoreMetadataError> {
+        ensure!(
+            !self.is_in_maintenance(),
+            StoreMetadataError::MaintenanceMode
+        );
+        ensure!(
+            !self.is_blocked(metadata.blob_id(), "store_metadata"),
+            StoreMetadataError::Forbidden
+        );
+
         let Some(blob_info) = self
             .storage
             .get_blob_info(metadata.blob_id())
@@ -2238,7 +3327,10 @@ impl ServiceState for StorageNodeInner {
     ) -> Result<Sliver, RetrieveSliverError> {
         self.check_index(sliver_pair_index)?;
 
-        ensure!(!self.is_blocked(blob_id), RetrieveSliverError::Forbidden);
+        ensure!(
+            !self.is_blocked(blob_id, "retrieve_sliver"),
+            RetrieveSliverError::Forbidden
+        );
 
         ensure!(
             self.is_blob_registered(blob_id)?,
@@ -2249,13 +3341,35 @@ impl ServiceState for StorageNodeInner {
             .get_shard_for_sliver_pair(sliver_pair_index, blob_id)
             .await?;
 
-        shard_storage
-            .get_sliver(blob_id, sliver_type)
-            .context("unable to retrieve sliver")?
+        let sliver = match shard_storage.get_sliver(blob_id, sliver_type) {
+            Err(error) if is_corruption_error(&error) => {
+                self.discard_corrupted_entry(blob_id, &sliver_type.to_string(), error, || {
+                    shard_storage.discard_sliver(blob_id, sliver_type)
+                })?;
+                return Err(RetrieveSliverError::Corrupted);
+            }
+            result => result.context("unable to retrieve sliver")?,
+        };
+
+        sliver
             .ok_or(RetrieveSliverError::Unavailable)
             .inspect(|sliver| {
                 walrus_utils::with_label!(self.metrics.slivers_retrieved_total, sliver.r#type())
                     .inc();
+                let shard_label = shard_storage.id().to_string();
+                let sliver_type = sliver.r#type().to_string();
+                walrus_utils::with_label!(
+                    self.metrics.shard_slivers_retrieved_total,
+                    &shard_label,
+                    &sliver_type
+                )
+                .inc();
+                walrus_utils::with_label!(
+                    self.metrics.shard_sliver_bytes_retrieved_total,
+                    &shard_label,
+                    &sliver_type
+                )
+                .inc_by(sliver.len() as u64);
             })
     }
 
@@ -2265,8 +3379,14 @@ impl ServiceState for StorageNodeInner {
         sliver_pair_index: SliverPairIndex,
         sliver: &Sliver,
     ) -> Result<bool, StoreSliverError> {
+        ensure!(!self.is_in_maintenance(), StoreSliverError::MaintenanceMode);
         self.check_index(sliver_pair_index)?;
 
+        ensure!(
+            !self.is_blocked(blob_id, "store_sliver"),
+            StoreSliverError::Forbidden
+        );
+
         ensure!(
             self.is_blob_registered(blob_id)?,
             StoreSliverError::NotCurrentlyRegistered,
@@ -2319,7 +3439,7 @@ impl ServiceState for StorageNodeInner {
 
         let confirmation =
             Confirmation::new(self.current_epoch(), *blob_id, *blob_persistence_type);
-        let signed = sign_message(confirmation, self.protocol_key_pair.clone()).await?;
+        let signed = sign_message(confirmation, self.protocol_signer.as_ref()).await?;
 
         self.metrics.storage_confirmations_issued_total.inc();
 
@@ -2335,17 +3455,77 @@ impl ServiceState for StorageNodeInner {
             .unwrap_or_default())
     }
 
+    async fn blob_storage_status(
+        &self,
+        blob_id: &BlobId,
+    ) -> Result<BlobStorageStatus, BlobStatusError> {
+        let stored_status = |is_stored: bool| {
+            if is_stored {
+                StoredOnNodeStatus::Stored
+            } else {
+                StoredOnNodeStatus::Nonexistent
+            }
+        };
+        let metadata = stored_status(
+            self.storage
+                .has_metadata(blob_id)
+                .context("could not check if the metadata is stored")?,
+        );
+
+        let mut owned_shards = self.owned_shards();
+        owned_shards.sort();
+        let mut shards = Vec::with_capacity(owned_shards.len());
+        for shard in owned_shards {
+            // A shard that is owned but not yet created does not store any slivers.
+            let (primary_stored, secondary_stored) = match self.storage.shard_storage(shard).await {
+                Some(shard_storage) => (
+                    shard_storage
+                        .is_sliver_type_stored(blob_id, SliverType::Primary)
+                        .context("could not check if the primary sliver is stored")?,
+                    shard_storage
+                        .is_sliver_type_stored(blob_id, SliverType::Secondary)
+                        .context("could not check if the secondary sliver is stored")?,
+                ),
+                None => (false, false),
+            };
+            shards.push(ShardBlobStorageStatus {
+                shard,
+                primary_sliver: stored_status(primary_stored),
+                secondary_sliver: stored_status(secondary_stored),
+            });
+        }
+
+        Ok(BlobStorageStatus { metadata, shards })
+    }
+
     async fn verify_inconsistency_proof(
         &self,
         blob_id: &BlobId,
         inconsistency_proof: InconsistencyProof,
     ) -> Result<InvalidBlobIdAttestation, InconsistencyProofError> {
-        let metadata = self.retrieve_metadata(blob_id)?;
+        self.metrics.inconsistency_proofs_received_total.inc();
 
-        inconsistency_proof.verify(metadata.as_ref(), &self.encoding_config)?;
+        let result: Result<_, InconsistencyProofError> = async {
+            let metadata = self.retrieve_metadata(blob_id)?;
+            inconsistency_proof.verify(metadata.as_ref(), &self.encoding_config)?;
+            self.metrics.inconsistency_proofs_verified_total.inc();
+
+            let message = InvalidBlobIdMsg::new(self.current_epoch(), blob_id.to_owned());
+            Ok(sign_message(message, self.protocol_signer.as_ref()).await?)
+        }
+        .await;
 
-        let message = InvalidBlobIdMsg::new(self.current_epoch(), blob_id.to_owned());
-        Ok(sign_message(message, self.protocol_key_pair.clone()).await?)
+        match &result {
+            Ok(_) => self.metrics.invalid_blob_attestations_issued_total.inc(),
+            Err(error) => {
+                walrus_utils::with_label!(
+                    self.metrics.inconsistency_proofs_rejected_total,
+                    error.label()
+                )
+                .inc();
+            }
+        }
+        result
     }
 
     #[tracing::instrument(skip(self))]
@@ -2483,6 +3663,7 @@ impl ServiceState for StorageNodeInner {
             uptime: self.start_time.elapsed(),
             epoch: self.current_epoch(),
             public_key: self.public_key().clone(),
+            network_public_key: Some(self.network_public_key.clone()),
             node_status: self
                 .storage
                 .node_status()
@@ -2526,40 +3707,194 @@ impl ServiceState for StorageNodeInner {
         let sync_shard_msg = signed_request.verify_signature_and_get_message(&public_key)?;
         let request = sync_shard_msg.as_ref().contents();
 
-        tracing::debug!(?request, "sync shard request received");
+        tracing::debug!(?request, "sync shard request received");
+
+        // If the epoch of the requester should not be older than the current epoch of the node.
+        // In a normal scenario, a storage node will never fetch shards from a future epoch.
+        if request.epoch() != self.current_epoch() {
+            return Err(InvalidEpochError {
+                request_epoch: request.epoch(),
+                server_epoch: self.current_epoch(),
+            }
+            .into());
+        }
+
+        // Only the node to which the shard is assigned in the requested epoch may fetch it.
+        let is_shard_owner = self
+            .committee_service
+            .active_committees()
+            .committee_for_epoch(request.epoch())
+            .is_some_and(|committee| {
+                committee
+                    .shards_for_node_public_key(&public_key)
+                    .contains(&request.shard_index())
+            });
+        if !is_shard_owner {
+            return Err(SyncShardServiceError::NotShardOwner(request.shard_index()));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        self.sync_shard_replay_guard
+            .check_and_record(request, now)?;
+
+        self.storage
+            .handle_sync_shard_request(request, self.current_epoch())
+            .await
+    }
+
+    fn pin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError> {
+        Ok(self
+            .storage
+            .pin_blob(blob_id)
+            .context("could not pin blob")?)
+    }
+
+    fn unpin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError> {
+        Ok(self
+            .storage
+            .unpin_blob(blob_id)
+            .context("could not unpin blob")?)
+    }
+
+    async fn list_pinned_blobs(&self) -> Result<Vec<PinnedBlob>, BlobPinningError> {
+        Ok(self
+            .storage
+            .list_pinned_blobs()
+            .await
+            .context("could not list pinned blobs")?)
+    }
+
+    fn deny_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError> {
+        ensure!(self.blocklist.is_persistent(), DenyListError::NotConfigured);
+        let inserted = self
+            .blocklist
+            .insert(*blob_id)
+            .context("could not add blob to the deny list")?;
+        if inserted {
+            tracing::info!(%blob_id, "added blob to the deny list");
+        }
+        Ok(inserted)
+    }
+
+    fn allow_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError> {
+        ensure!(self.blocklist.is_persistent(), DenyListError::NotConfigured);
+        let removed = self
+            .blocklist
+            .remove(blob_id)
+            .context("could not remove blob from the deny list")?;
+        if removed {
+            tracing::info!(%blob_id, "removed blob from the deny list");
+        }
+        Ok(removed)
+    }
+
+    fn list_denied_blobs(&self) -> Vec<BlobId> {
+        self.blocklist.list()
+    }
+
+    fn list_dead_letter_events(&self) -> Result<Vec<DeadLetterEvent>, DeadLetterQueueError> {
+        Ok(self
+            .storage
+            .list_dead_letter_events()
+            .context("could not list the dead-letter queue")?)
+    }
+
+    async fn list_shard_blobs(
+        &self,
+        shard_index: ShardIndex,
+        after: Option<BlobId>,
+        limit: usize,
+    ) -> Result<ShardBlobs, StorageInspectionError> {
+        self.storage
+            .list_shard_blobs(shard_index, after, limit)
+            .await
+            .context("could not list the blobs of the shard")?
+            .ok_or(StorageInspectionError::ShardNotStored(shard_index))
+    }
+
+    async fn inspect_blob_storage(
+        &self,
+        blob_id: &BlobId,
+    ) -> Result<BlobStorageReport, StorageInspectionError> {
+        Ok(self
+            .storage
+            .inspect_blob(blob_id)
+            .await
+            .context("could not inspect the stored blob")?)
+    }
+
+    fn event_cursor_report(&self) -> Result<EventCursorReport, StorageInspectionError> {
+        Ok(self
+            .storage
+            .event_cursor_report()
+            .context("could not read the event cursor")?)
+    }
+
+    async fn column_family_sizes(&self) -> Result<Vec<ColumnFamilySize>, StorageInspectionError> {
+        Ok(self
+            .storage
+            .column_family_sizes()
+            .await
+            .context("could not read the sizes of the column families")?)
+    }
+
+    /// Events can only be processed by the [`StorageNode`], which owns the event handlers.
+    async fn retry_dead_letter_event(&self, _event_index: u64) -> Result<(), DeadLetterQueueError> {
+        Err(anyhow!("events can only be retried through the storage node").into())
+    }
+
+    fn set_maintenance_mode(&self, enabled: bool) -> bool {
+        let changed = self.is_in_maintenance.send_replace(enabled) != enabled;
+        if changed {
+            tracing::info!(enabled, "maintenance mode changed");
+        }
+        changed
+    }
+
+    fn set_background_worker_paused(&self, worker: BackgroundWorker, paused: bool) -> bool {
+        self.background_workers.set_paused(worker, paused)
+    }
 
-        // If the epoch of the requester should not be older than the current epoch of the node.
-        // In a normal scenario, a storage node will never fetch shards from a future epoch.
-        if request.epoch() != self.current_epoch() {
-            return Err(InvalidEpochError {
-                request_epoch: request.epoch(),
-                server_epoch: self.current_epoch(),
-            }
-            .into());
+    fn background_worker_statuses(&self) -> Vec<BackgroundWorkerStatus> {
+        self.background_workers.statuses()
+    }
+
+    async fn status_report(&self) -> NodeStatusReport {
+        NodeStatusReport {
+            health: self.health_info(true),
+            maintenance_mode: self.is_in_maintenance(),
+            epoch_sync_done: self.epoch_sync_done_status(),
+            background_workers: self.background_workers.statuses(),
+            storage: self.storage_usage().await,
+            event_lag: *self
+                .event_processing_lag
+                .lock()
+                .expect("mutex should not be poisoned"),
+            peers: self.committee_service.peer_health(),
         }
+    }
 
-        self.storage
-            .handle_sync_shard_request(request, self.current_epoch())
-            .await
+    fn shard_assignment_diff(&self) -> ShardAssignmentDiff {
+        self.shard_assignment_diff_for(&self.committee_service.active_committees())
     }
 }
 
 #[tracing::instrument(skip_all, err)]
 async fn sign_message<T, I>(
     message: T,
-    signer: ProtocolKeyPair,
+    signer: &dyn ProtocolSigner,
 ) -> Result<SignedMessage<T>, anyhow::Error>
 where
-    T: AsRef<ProtocolMessage<I>> + Serialize + Send + Sync + 'static,
+    T: AsRef<ProtocolMessage<I>> + Serialize,
 {
-    let signed = tokio::task::spawn_blocking(move || signer.sign_message(&message))
-        .await
-        .with_context(|| {
-            format!(
-                "unexpected error while signing a {}",
-                std::any::type_name::<T>()
-            )
-        })?;
+    let signed = signer.sign_message(&message).await.with_context(|| {
+        format!(
+            "unexpected error while signing a {}",
+            std::any::type_name::<T>()
+        )
+    })?;
 
     Ok(signed)
 }
@@ -2580,6 +3915,7 @@ mod tests {
     use tokio::sync::{broadcast::Sender, Mutex};
     use walrus_core::{
         encoding::{EncodingConfigTrait as _, Primary, Secondary, SliverData, SliverPair},
+        keys::ProtocolKeyPair,
         messages::{SyncShardMsg, SyncShardRequest},
         test_utils::generate_config_metadata_and_valid_recovery_symbols,
         DEFAULT_ENCODING,
@@ -2727,9 +4063,8 @@ mod tests {
             storage_node
                 .as_ref()
                 .inner
-                .protocol_key_pair
-                .as_ref()
-                .public()
+                .protocol_signer
+                .public_key()
                 .verify(&signed.serialized_message, &signed.signature)
                 .expect("message should be verifiable");
 
@@ -2782,6 +4117,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn event_processing_lag_is_measured_from_processed_checkpoints() -> TestResult {
+        let node = StorageNodeHandle::builder()
+            .with_system_event_provider(vec![BlobRegistered::for_testing(BLOB_ID).into()])
+            .with_node_started(true)
+            .build()
+            .await?;
+        let inner = &node.as_ref().inner;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Processing events without a checkpoint boundary does not complete a checkpoint, so no
+        // lag is reported.
+        assert!(inner.storage.get_blob_info(&BLOB_ID)?.is_some());
+        assert_eq!(inner.report_event_processing_lag().await?, None);
+
+        inner.set_last_processed_checkpoint(0);
+        assert_eq!(
+            inner.report_event_processing_lag().await?,
+            Some(Duration::ZERO)
+        );
+
+        Ok(())
+    }
+
     // Test that `is_stored_at_all_shards` uses the committee assignment to determine if the blob
     // is stored at all shards.
     async_param_test! {
@@ -2828,13 +4187,11 @@ mod tests {
         deletes_blob_data_on_event -> TestResult: [
             invalid_blob_event_registered: (InvalidBlobId::for_testing(BLOB_ID).into(), false),
             invalid_blob_event_certified: (InvalidBlobId::for_testing(BLOB_ID).into(), true),
-            // TODO (WAL-201): Uncomment the following tests as soon as we actually delete blob
-            // data.
-            // blob_deleted_event_registered: (
-            //     BlobDeleted{was_certified: false, ..BlobDeleted::for_testing(BLOB_ID)}.into(),
-            //     false
-            // ),
-            // blob_deleted_event_certified: (BlobDeleted::for_testing(BLOB_ID).into(), true),
+            blob_deleted_event_registered: (
+                BlobDeleted{was_certified: false, ..BlobDeleted::for_testing(BLOB_ID)}.into(),
+                false
+            ),
+            blob_deleted_event_certified: (BlobDeleted::for_testing(BLOB_ID).into(), true),
         ]
     }
     async fn deletes_blob_data_on_event(event: BlobEvent, is_certified: bool) -> TestResult {
@@ -2880,6 +4237,87 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn retains_data_of_pinned_blob_on_delete() -> TestResult {
+        let events = Sender::new(48);
+        let node = StorageNodeHandle::builder()
+            .with_storage(
+                populated_storage(&[
+                    (SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)]),
+                    (OTHER_SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)]),
+                ])
+                .await?,
+            )
+            .with_system_event_provider(events.clone())
+            .with_node_started(true)
+            .build()
+            .await?;
+        let inner = node.as_ref().inner.clone();
+        assert!(inner.pin_blob(&BLOB_ID)?);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        events.send(
+            BlobRegistered {
+                deletable: true,
+                ..BlobRegistered::for_testing(BLOB_ID)
+            }
+            .into(),
+        )?;
+        events.send(
+            BlobCertified {
+                deletable: true,
+                ..BlobCertified::for_testing(BLOB_ID)
+            }
+            .into(),
+        )?;
+        events.send(BlobDeleted::for_testing(BLOB_ID).into())?;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(inner.is_stored_at_all_shards(&BLOB_ID).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retries_dead_letter_event_with_already_handled_index() -> TestResult {
+        let events = Sender::new(48);
+        let node = StorageNodeHandle::builder()
+            .with_storage(
+                populated_storage(&[
+                    (SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)]),
+                    (OTHER_SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)]),
+                ])
+                .await?,
+            )
+            .with_system_event_provider(events.clone())
+            .with_node_started(true)
+            .build()
+            .await?;
+        let storage_node = node.as_ref();
+        let inner = storage_node.inner.clone();
+
+        // Events 0 to 2 are processed, event 1 was moved to the dead-letter queue after its blob
+        // info had been updated.
+        events.send(BlobRegistered::for_testing(BLOB_ID).into())?;
+        events.send(BlobCertified::for_testing(BLOB_ID).into())?;
+        events.send(BlobRegistered::for_testing(OTHER_BLOB_ID).into())?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        inner.storage.put_dead_letter_event(
+            1,
+            event_id_for_testing(),
+            InvalidBlobId::for_testing(BLOB_ID).into(),
+            3,
+            "failed".into(),
+        )?;
+        assert!(inner.is_stored_at_all_shards(&BLOB_ID).await?);
+
+        storage_node.retry_dead_letter_event(1).await?;
+
+        assert!(!inner.is_stored_at_all_shards(&BLOB_ID).await?);
+        assert_eq!(inner.storage.get_dead_letter_event(1)?, None);
+        Ok(())
+    }
+
     async_param_test! {
         correctly_handles_blob_deletions_with_concurrent_instances -> TestResult: [
             same_epoch: (1),
@@ -3118,9 +4556,8 @@ mod tests {
             // The proof should be valid and we should receive a valid signature
             node.as_ref()
                 .inner
-                .protocol_key_pair
-                .as_ref()
-                .public()
+                .protocol_signer
+                .public_key()
                 .verify(&attestation.serialized_message, &attestation.signature)?;
 
             let invalid_blob_msg: InvalidBlobIdMsg =
@@ -3293,27 +4730,30 @@ mod tests {
         events: &[&Sender<ContractEvent>],
         epoch: Epoch,
     ) -> TestResult {
-        let lookup_service_handle = cluster.lookup_service_handle.clone().unwrap();
-        for epoch in lookup_service_handle.epoch() + 1..epoch + 1 {
-            let new_epoch = lookup_service_handle.advance_epoch();
-            assert_eq!(new_epoch, epoch);
-            for event_queue in events {
-                event_queue.send(ContractEvent::EpochChangeEvent(
-                    EpochChangeEvent::EpochChangeStart(EpochChangeStart {
-                        epoch,
-                        event_id: walrus_sui::test_utils::event_id_for_testing(),
-                    }),
-                ))?;
-                event_queue.send(ContractEvent::EpochChangeEvent(
-                    EpochChangeEvent::EpochChangeDone(EpochChangeDone {
-                        epoch,
-                        event_id: walrus_sui::test_utils::event_id_for_testing(),
-                    }),
-                ))?;
-            }
-            cluster.wait_for_nodes_to_reach_epoch(epoch).await;
-        }
+        cluster.advance_to_epoch(events, epoch).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn restarted_node_follows_epoch_changes() -> TestResult {
+        let mut cluster = {
+            // Lock to avoid race conditions.
+            let _lock = global_test_lock().lock().await;
+            let assignment: &[&[u16]] = &[&[0], &[1, 2], &[3, 4]];
+            TestCluster::<StorageNodeHandle>::builder()
+                .with_shard_assignment(assignment)
+                .with_mock_chain()
+                .build()
+                .await?
+        };
+        assert_eq!(cluster.advance_epoch().await?, 2);
 
+        cluster.restart_node(0).await?;
+        assert_eq!(cluster.advance_epoch().await?, 3);
+
+        let node = &cluster.nodes[0].storage_node;
+        assert_eq!(node.inner.current_epoch(), 3);
+        assert_eq!(node.existing_shards().await, vec![ShardIndex(0)]);
         Ok(())
     }
 
@@ -3891,6 +5331,87 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn rejects_uploads_and_defers_syncs_in_maintenance_mode() -> TestResult {
+        let (cluster, _, blob) =
+            cluster_with_partially_stored_blob(&[&[0, 1, 2, 3]], BLOB, |_, _| true).await?;
+        let node = &cluster.nodes[0].storage_node;
+        let assigned_sliver_pair = blob.assigned_sliver_pair(ShardIndex(0));
+        let sliver = Sliver::Primary(assigned_sliver_pair.primary.clone());
+
+        assert!(node.set_maintenance_mode(true));
+        assert!(!node.set_maintenance_mode(true));
+        let result = node
+            .store_sliver(blob.blob_id(), assigned_sliver_pair.index(), &sliver)
+            .await;
+        assert!(matches!(result, Err(StoreSliverError::MaintenanceMode)));
+        let result = node.store_metadata(blob.metadata.clone().into_unverified());
+        assert!(matches!(result, Err(StoreMetadataError::MaintenanceMode)));
+
+        // Reads are still served.
+        node.retrieve_metadata(blob.blob_id())?;
+
+        // Blob syncs and node recovery wait until maintenance mode is disabled.
+        let inner = node.inner.clone();
+        let mut wait = tokio::spawn(async move { inner.wait_until_not_in_maintenance().await });
+        assert!(tokio::time::timeout(Duration::from_millis(100), &mut wait)
+            .await
+            .is_err());
+
+        assert!(node.set_maintenance_mode(false));
+        tokio::time::timeout(TIMEOUT, wait).await??;
+        node.store_sliver(blob.blob_id(), assigned_sliver_pair.index(), &sliver)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn locks_shards_not_owned_on_startup() -> TestResult {
+        let (cluster, _, _) =
+            cluster_with_partially_stored_blob(&[&[0, 1], &[2, 3]], BLOB, |_, _| true).await?;
+        let node = &cluster.nodes[0].storage_node;
+        let storage = &node.inner.storage;
+        storage.create_storage_for_shards(&[ShardIndex(2)]).await?;
+        node.inner.set_node_status(NodeStatus::Active)?;
+
+        node.verify_shard_ownership().await?;
+
+        let not_owned = storage.shard_storage(ShardIndex(2)).await.unwrap();
+        assert_eq!(not_owned.status()?, ShardStatus::LockedToMove);
+        let owned = storage.shard_storage(ShardIndex(0)).await.unwrap();
+        assert_ne!(owned.status()?, ShardStatus::LockedToMove);
+
+        Ok(())
+    }
+
+    /// Requests the primary slivers of the shard from the node with the client, with the request
+    /// signed by the `requester`.
+    async fn sync_shard_requested_by(
+        client: &Client,
+        requester: &StorageNodeInner,
+        shard: ShardIndex,
+        starting_blob_id: BlobId,
+        sliver_count: u64,
+        epoch: Epoch,
+    ) -> Result<SyncShardResponse, walrus_sdk::error::NodeError> {
+        let request = SyncShardRequest::new(
+            shard,
+            SliverType::Primary,
+            starting_blob_id,
+            sliver_count,
+            epoch,
+        );
+        let signed_request = requester
+            .protocol_signer
+            .sign_message(&SyncShardMsg::new(epoch, request))
+            .await
+            .expect("signing with the local signer should succeed");
+        client
+            .sync_shard_with_signed_request(&signed_request, requester.protocol_signer.public_key())
+            .await
+    }
+
     // Tests the basic `sync_shard` API.
     #[tokio::test]
     async fn sync_shard_node_api_success() -> TestResult {
@@ -3901,16 +5422,15 @@ mod tests {
         let blob_id = *blob_detail[0].blob_id();
 
         // Tests successful sync shard operation.
-        let status = cluster.nodes[0]
-            .client
-            .sync_shard::<Primary>(
-                ShardIndex(0),
-                blob_id,
-                10,
-                2,
-                &cluster.nodes[0].as_ref().inner.protocol_key_pair,
-            )
-            .await;
+        let status = sync_shard_requested_by(
+            &cluster.nodes[0].client,
+            &cluster.nodes[0].as_ref().inner,
+            ShardIndex(0),
+            blob_id,
+            10,
+            2,
+        )
+        .await;
         assert!(status.is_ok(), "Unexpected sync shard error: {:?}", status);
 
         let SyncShardResponse::V1(response) = status.unwrap();
@@ -3945,16 +5465,15 @@ mod tests {
 
         let blob_id = *blob_detail[0].blob_id();
 
-        let status = cluster.nodes[0]
-            .client
-            .sync_shard::<Primary>(
-                ShardIndex(0),
-                blob_id,
-                10,
-                1,
-                &cluster.nodes[0].as_ref().inner.protocol_key_pair,
-            )
-            .await;
+        let status = sync_shard_requested_by(
+            &cluster.nodes[0].client,
+            &cluster.nodes[0].as_ref().inner,
+            ShardIndex(0),
+            blob_id,
+            10,
+            1,
+        )
+        .await;
         assert!(status.is_ok(), "Unexpected sync shard error: {:?}", status);
 
         let SyncShardResponse::V1(response) = status.unwrap();
@@ -3983,6 +5502,31 @@ mod tests {
         Ok(())
     }
 
+    // Tests sync shard operation requested by a storage node that does not own the shard.
+    #[tokio::test]
+    async fn sync_shard_node_api_not_shard_owner_error() -> TestResult {
+        let (cluster, _, blob_detail) =
+            cluster_with_initial_epoch_and_certified_blob(&[&[0, 1], &[2, 3]], &[BLOB], 1, None)
+                .await?;
+
+        let error: walrus_sdk::error::NodeError = sync_shard_requested_by(
+            &cluster.nodes[0].client,
+            &cluster.nodes[1].as_ref().inner,
+            ShardIndex(0),
+            *blob_detail[0].blob_id(),
+            10,
+            1,
+        )
+        .await
+        .expect_err("the request must fail");
+
+        let status = error.status().expect("response has error status");
+        assert_eq!(status.reason(), Some("REQUEST_UNAUTHORIZED"));
+        assert_eq!(status.domain(), Some(STORAGE_NODE_ERROR_DOMAIN));
+
+        Ok(())
+    }
+
     // Tests signed SyncShardRequest verification error.
     #[tokio::test]
     async fn sync_shard_node_api_request_verification_error() -> TestResult {
@@ -3995,8 +5539,9 @@ mod tests {
         let signed_request = cluster.nodes[0]
             .as_ref()
             .inner
-            .protocol_key_pair
-            .sign_message(&sync_shard_msg);
+            .protocol_signer
+            .sign_message(&sync_shard_msg)
+            .await?;
 
         let result = cluster.nodes[0]
             .storage_node
@@ -4004,9 +5549,8 @@ mod tests {
                 cluster.nodes[1]
                     .as_ref()
                     .inner
-                    .protocol_key_pair
-                    .0
-                    .public()
+                    .protocol_signer
+                    .public_key()
                     .clone(),
                 signed_request,
             )
@@ -4019,6 +5563,43 @@ mod tests {
         Ok(())
     }
 
+    // Tests that a signed sync shard request is only served once.
+    #[tokio::test]
+    async fn sync_shard_node_api_replayed_request_error() -> TestResult {
+        let (cluster, _, blob_detail) =
+            cluster_with_initial_epoch_and_certified_blob(&[&[0, 1], &[2, 3]], &[BLOB], 1, None)
+                .await?;
+
+        let requester = &cluster.nodes[0].as_ref().inner;
+        let request = SyncShardRequest::new_with_replay_protection(
+            ShardIndex(0),
+            SliverType::Primary,
+            *blob_detail[0].blob_id(),
+            10,
+            1,
+        );
+        let signed_request = requester
+            .protocol_signer
+            .sign_message(&SyncShardMsg::new(1, request))
+            .await?;
+        let client = &cluster.nodes[0].client;
+        let public_key = requester.protocol_signer.public_key();
+
+        client
+            .sync_shard_with_signed_request(&signed_request, public_key)
+            .await?;
+        let error = client
+            .sync_shard_with_signed_request(&signed_request, public_key)
+            .await
+            .expect_err("the replayed request must fail");
+
+        let status = error.status().expect("response has error status");
+        assert_eq!(status.reason(), Some("REQUEST_REPLAYED"));
+        assert_eq!(status.domain(), Some(STORAGE_NODE_ERROR_DOMAIN));
+
+        Ok(())
+    }
+
     // Tests SyncShardRequest with wrong epoch.
     async_param_test! {
         sync_shard_node_api_invalid_epoch -> TestResult: [
@@ -4040,17 +5621,16 @@ mod tests {
         .await?;
 
         // Requests a shard from epoch 0.
-        let error = cluster.nodes[0]
-            .client
-            .sync_shard::<Primary>(
-                ShardIndex(0),
-                *blob_detail[0].blob_id(),
-                10,
-                requester_epoch,
-                &cluster.nodes[0].as_ref().inner.protocol_key_pair,
-            )
-            .await
-            .expect_err("request should fail");
+        let error = sync_shard_requested_by(
+            &cluster.nodes[0].client,
+            &cluster.nodes[0].as_ref().inner,
+            ShardIndex(0),
+            *blob_detail[0].blob_id(),
+            10,
+            requester_epoch,
+        )
+        .await
+        .expect_err("request should fail");
         let status = error.status().expect("response has an error status");
         let error_info = status.error_info().expect("response has error details");
 
@@ -4410,6 +5990,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn sync_shard_verification_records_missing_slivers() -> TestResult {
+        let (cluster, blob_details, _, shard_storage_set) =
+            setup_cluster_for_shard_sync_tests(None, None).await?;
+        let node = cluster.nodes[1].storage_node.clone();
+        let shard_storage_dst = shard_storage_set.shard_storage[0].clone();
+
+        node.shard_sync_handler
+            .start_sync_shards(vec![ShardIndex(0)], false)
+            .await?;
+        wait_for_shards_in_active_state(&shard_storage_set).await?;
+
+        let epoch = node.inner.current_epoch();
+        assert_eq!(
+            shard_storage_dst.record_missing_slivers_after_sync(epoch, &node.inner)?,
+            0
+        );
+
+        let blob_id = *blob_details[0].blob_id();
+        shard_storage_dst.delete_sliver_pair_now(&blob_id)?;
+
+        assert_eq!(
+            shard_storage_dst.record_missing_slivers_after_sync(epoch, &node.inner)?,
+            2
+        );
+        let pending_recover_slivers = shard_storage_dst.all_pending_recover_slivers()?;
+        assert_eq!(pending_recover_slivers.len(), 2);
+        assert!(pending_recover_slivers.contains(&(SliverType::Primary, blob_id)));
+        assert!(pending_recover_slivers.contains(&(SliverType::Secondary, blob_id)));
+
+        Ok(())
+    }
+
     /// Sets up a test cluster for shard recovery tests.
     async fn setup_shard_recovery_test_cluster_with_blob_count<F, G, H>(
         blob_count: u8,
@@ -5517,6 +7130,9 @@ mod tests {
         contract_service
             .expect_get_epoch_and_state()
             .returning(move || Ok((0, EpochState::EpochChangeDone(Utc::now()))));
+        contract_service
+            .expect_current_chain_time()
+            .returning(|| Ok(Utc::now()));
         contract_service
             .expect_last_certified_event_blob()
             .returning(|| Ok(None));
@@ -5539,6 +7155,73 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn reports_epoch_sync_done_attestations_in_status_report() -> TestResult {
+        let mut contract_service = MockSystemContractService::new();
+        contract_service
+            .expect_sync_node_params()
+            .returning(|_config, _node_cap_id| Ok(()));
+        contract_service
+            .expect_epoch_sync_done()
+            .times(2)
+            .returning(|_epoch, _node_cap_id| ());
+        contract_service.expect_current_epoch().returning(|| 2);
+        contract_service
+            .expect_fixed_system_parameters()
+            .returning(|| {
+                Ok(FixedSystemParameters {
+                    n_shards: NonZeroU16::new(1000).expect("1000 > 0"),
+                    max_epochs_ahead: 200,
+                    epoch_duration: Duration::from_secs(600),
+                    epoch_zero_end: Utc::now() + Duration::from_secs(60),
+                })
+            });
+        contract_service
+            .expect_get_node_capability_object()
+            .returning(|capability_object_id| {
+                Ok(StorageNodeCap {
+                    id: capability_object_id.unwrap_or(ObjectID::random()),
+                    ..StorageNodeCap::new_for_testing()
+                })
+            });
+        contract_service
+            .expect_get_epoch_and_state()
+            .returning(move || Ok((0, EpochState::EpochChangeDone(Utc::now()))));
+        contract_service
+            .expect_current_chain_time()
+            .returning(|| Ok(Utc::now()));
+        contract_service
+            .expect_last_certified_event_blob()
+            .returning(|| Ok(None));
+        let node = StorageNodeHandle::builder()
+            .with_system_event_provider(vec![])
+            .with_system_contract_service(Arc::new(contract_service))
+            .build()
+            .await?;
+        let inner = &node.storage_node.inner;
+        assert_eq!(
+            inner.epoch_sync_done_status(),
+            EpochSyncDoneStatus::default()
+        );
+
+        // An attestation for an epoch that the chain has already moved past is not recorded.
+        inner.attest_epoch_sync_done(1).await;
+        assert_eq!(
+            inner.epoch_sync_done_status(),
+            EpochSyncDoneStatus::default()
+        );
+
+        inner.attest_epoch_sync_done(2).await;
+        let expected = EpochSyncDoneStatus {
+            last_attested_epoch: Some(2),
+            pending_epoch: None,
+        };
+        assert_eq!(inner.epoch_sync_done_status(), expected);
+        assert_eq!(inner.status_report().await.epoch_sync_done, expected);
+
+        Ok(())
+    }
+
     async_param_test! {
         process_epoch_change_start_idempotent -> TestResult: [
             wait_for_shard_active: (true),
@@ -0,0 +1,148 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runtime controls to pause and resume the background workers of the storage node.
+//!
+//! Operators pause workers during incident response or before taking storage snapshots. Paused
+//! workers finish the unit of work they are currently processing and then wait until they are
+//! resumed; pausing is not persisted across restarts.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+/// A background worker of the storage node that can be paused at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackgroundWorker {
+    /// The processing of the events emitted by the Walrus contracts.
+    ///
+    /// While paused, the node does not follow epoch changes or sync newly certified blobs.
+    EventProcessing,
+    /// The transfer of slivers of shards gained in an epoch change from their previous owners.
+    ShardSync,
+    /// The periodic demotion of rarely accessed slivers to the cold storage tier.
+    SliverTiering,
+    /// The periodic deletion of the data of blobs that were registered but never certified.
+    OrphanedSliverCollection,
+}
+
+impl BackgroundWorker {
+    /// All background workers that can be paused.
+    pub const ALL: [Self; 4] = [
+        Self::EventProcessing,
+        Self::ShardSync,
+        Self::SliverTiering,
+        Self::OrphanedSliverCollection,
+    ];
+}
+
+/// The pause state of a background worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundWorkerStatus {
+    /// The background worker.
+    pub worker: BackgroundWorker,
+    /// Whether the worker is paused.
+    pub paused: bool,
+}
+
+/// Tracks which background workers are paused and allows workers to wait until they are resumed.
+#[derive(Debug)]
+pub(crate) struct BackgroundWorkerControls {
+    paused: HashMap<BackgroundWorker, watch::Sender<bool>>,
+}
+
+impl Default for BackgroundWorkerControls {
+    fn default() -> Self {
+        Self {
+            paused: BackgroundWorker::ALL
+                .into_iter()
+                .map(|worker| (worker, watch::Sender::new(false)))
+                .collect(),
+        }
+    }
+}
+
+impl BackgroundWorkerControls {
+    /// Pauses or resumes the worker.
+    ///
+    /// Returns true if the state of the worker was changed, false if it was already paused or
+    /// running as requested.
+    pub(crate) fn set_paused(&self, worker: BackgroundWorker, paused: bool) -> bool {
+        let changed = self.sender(worker).send_replace(paused) != paused;
+        if changed {
+            tracing::info!(?worker, paused, "background worker paused state changed");
+        }
+        changed
+    }
+
+    /// Returns true if the worker is paused.
+    pub(crate) fn is_paused(&self, worker: BackgroundWorker) -> bool {
+        *self.sender(worker).borrow()
+    }
+
+    /// Returns the pause state of all background workers.
+    pub(crate) fn statuses(&self) -> Vec<BackgroundWorkerStatus> {
+        BackgroundWorker::ALL
+            .into_iter()
+            .map(|worker| BackgroundWorkerStatus {
+                worker,
+                paused: self.is_paused(worker),
+            })
+            .collect()
+    }
+
+    /// Completes immediately if the worker is running, and otherwise once it is resumed.
+    pub(crate) async fn wait_until_resumed(&self, worker: BackgroundWorker) {
+        let mut receiver = self.sender(worker).subscribe();
+        if *receiver.borrow_and_update() {
+            tracing::info!(
+                ?worker,
+                "background worker is paused; waiting to be resumed"
+            );
+        }
+        receiver
+            .wait_for(|paused| !paused)
+            .await
+            .expect("the sender is owned by self and cannot be dropped");
+    }
+
+    fn sender(&self, worker: BackgroundWorker) -> &watch::Sender<bool> {
+        self.paused
+            .get(&worker)
+            .expect("all background workers have a pause state")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{pin::pin, time::Duration};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn paused_workers_wait_until_resumed() {
+        let controls = BackgroundWorkerControls::default();
+        controls
+            .wait_until_resumed(BackgroundWorker::ShardSync)
+            .await;
+
+        assert!(controls.set_paused(BackgroundWorker::ShardSync, true));
+        assert!(!controls.set_paused(BackgroundWorker::ShardSync, true));
+        assert!(controls.is_paused(BackgroundWorker::ShardSync));
+        assert!(!controls.is_paused(BackgroundWorker::EventProcessing));
+
+        let mut wait = pin!(controls.wait_until_resumed(BackgroundWorker::ShardSync));
+        assert!(tokio::time::timeout(Duration::from_millis(50), &mut wait)
+            .await
+            .is_err());
+
+        assert!(controls.set_paused(BackgroundWorker::ShardSync, false));
+        tokio::time::timeout(Duration::from_secs(1), wait)
+            .await
+            .expect("the worker should be resumed");
+        assert!(controls.statuses().iter().all(|status| !status.paused));
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Invalidation of blobs that the node found to be inconsistently encoded.
+//!
+//! When the recovery of a sliver results in an inconsistency proof, the node collects an invalid
+//! blob certificate from the committee, submits it to the contract, and deletes its local data for
+//! the blob. Collecting the certificate and submitting it are retried until they succeed, or until
+//! the blob is retired, for example because another node invalidated it first.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use walrus_core::{BlobId, InconsistencyProof};
+
+use super::{
+    blob_retirement_notifier::ExecutionResultWithRetirementCheck,
+    metrics::{self, TelemetryLabel},
+    StorageNodeInner,
+};
+
+/// The outcome of invalidating a blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InvalidationOutcome {
+    /// The invalid blob certificate was submitted and the node's data for the blob was deleted.
+    Invalidated,
+    /// The invalidation of the blob is already driven by another task.
+    AlreadyInProgress,
+    /// The blob was retired before the certificate was submitted.
+    BlobRetired,
+}
+
+impl TelemetryLabel for InvalidationOutcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Invalidated => metrics::STATUS_SUCCESS,
+            Self::AlreadyInProgress => metrics::STATUS_SKIPPED,
+            Self::BlobRetired => metrics::STATUS_ABORTED,
+        }
+    }
+}
+
+/// Drives the invalidation of blobs for which the node obtained an inconsistency proof.
+///
+/// At most one invalidation is in progress for each blob, such that shards that recover slivers of
+/// the same inconsistent blob do not each collect a certificate.
+#[derive(Debug, Default)]
+pub(crate) struct BlobInvalidator {
+    in_progress: Mutex<HashSet<BlobId>>,
+}
+
+impl BlobInvalidator {
+    /// Invalidates the blob using the provided inconsistency proof.
+    ///
+    /// Returns once the certificate has been submitted and the local data has been deleted, once
+    /// the blob has been retired, or immediately if the blob is already being invalidated.
+    #[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id))]
+    pub(crate) async fn invalidate(
+        &self,
+        node: &Arc<StorageNodeInner>,
+        blob_id: BlobId,
+        inconsistency_proof: &InconsistencyProof,
+    ) -> anyhow::Result<InvalidationOutcome> {
+        let outcome = match self.try_start(blob_id) {
+            Some(_guard) => Self::run(node, blob_id, inconsistency_proof).await?,
+            None => {
+                tracing::debug!("invalidation of the blob is already in progress");
+                InvalidationOutcome::AlreadyInProgress
+            }
+        };
+
+        walrus_utils::with_label!(node.metrics.blob_invalidations_total, outcome.label()).inc();
+        Ok(outcome)
+    }
+
+    async fn run(
+        node: &Arc<StorageNodeInner>,
+        blob_id: BlobId,
+        inconsistency_proof: &InconsistencyProof,
+    ) -> anyhow::Result<InvalidationOutcome> {
+        tracing::info!("invalidating blob based on an inconsistency proof");
+        let start = Instant::now();
+
+        let result = node
+            .blob_retirement_notifier
+            .execute_with_retirement_check(node, blob_id, || async {
+                let certificate = node
+                    .committee_service
+                    .get_invalid_blob_certificate(blob_id, inconsistency_proof)
+                    .await;
+                node.contract_service.invalidate_blob_id(&certificate).await;
+                Ok::<_, anyhow::Error>(())
+            })
+            .await?;
+
+        if let ExecutionResultWithRetirementCheck::BlobRetired = result {
+            tracing::info!("blob was retired before it could be invalidated by this node");
+            return Ok(InvalidationOutcome::BlobRetired);
+        }
+        node.metrics
+            .blob_invalidation_duration_seconds
+            .observe(start.elapsed().as_secs_f64());
+
+        // Stop other work on the blob and delete its data, without waiting for the invalidation
+        // event. The event triggers the same cleanup again, which is idempotent.
+        node.blob_retirement_notifier
+            .notify_blob_retirement(&blob_id);
+        node.storage
+            .delete_blob_data(&blob_id)
+            .await
+            .context("could not delete the data of the invalidated blob")?;
+
+        tracing::info!("submitted invalid blob certificate and deleted the blob's data");
+        Ok(InvalidationOutcome::Invalidated)
+    }
+
+    /// Marks the invalidation of the blob as in progress, unless it already is.
+    ///
+    /// The invalidation remains in progress until the returned guard is dropped.
+    fn try_start(&self, blob_id: BlobId) -> Option<InProgressGuard<'_>> {
+        let mut in_progress = self
+            .in_progress
+            .lock()
+            .expect("mutex should not be poisoned");
+        in_progress.insert(blob_id).then(|| InProgressGuard {
+            invalidator: self,
+            blob_id,
+        })
+    }
+}
+
+/// Removes the blob from the in-progress invalidations when dropped.
+#[derive(Debug)]
+struct InProgressGuard<'a> {
+    invalidator: &'a BlobInvalidator,
+    blob_id: BlobId,
+}
+
+impl Drop for InProgressGuard<'_> {
+    fn drop(&mut self) {
+        self.invalidator
+            .in_progress
+            .lock()
+            .expect("mutex should not be poisoned")
+            .remove(&self.blob_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::test_utils::random_blob_id;
+
+    use super::*;
+
+    #[test]
+    fn allows_one_invalidation_per_blob_at_a_time() {
+        let invalidator = BlobInvalidator::default();
+        let blob_id = random_blob_id();
+
+        let guard = invalidator.try_start(blob_id);
+        assert!(guard.is_some());
+        assert!(invalidator.try_start(blob_id).is_none());
+        assert!(invalidator.try_start(random_blob_id()).is_some());
+
+        drop(guard);
+        assert!(invalidator.try_start(blob_id).is_some());
+    }
+}
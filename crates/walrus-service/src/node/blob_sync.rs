@@ -34,19 +34,21 @@ use walrus_core::{
 };
 
 use super::{
+    blob_invalidation::InvalidationOutcome,
     committee::CommitteeService,
-    contract_service::SystemContractService,
-    metrics::{self, NodeMetricSet, STATUS_IN_PROGRESS, STATUS_QUEUED},
+    metrics::{self, NodeMetricSet, TelemetryLabel, STATUS_IN_PROGRESS, STATUS_QUEUED},
     storage::Storage,
     system_events::{CompletableHandle, EventHandle},
     StorageNodeInner,
 };
-use crate::common::utils::FutureHelpers as _;
+use crate::common::utils::{resize_semaphore, FutureHelpers as _};
 
 #[derive(Debug, Clone)]
 struct Permits {
     blob: Arc<Semaphore>,
     sliver_pairs: Arc<Semaphore>,
+    // The configured sizes of the `blob` and `sliver_pairs` semaphores.
+    limits: Arc<Mutex<(usize, usize)>>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,10 +71,34 @@ impl BlobSyncHandler {
             permits: Permits {
                 blob: Arc::new(Semaphore::new(max_concurrent_blob_syncs)),
                 sliver_pairs: Arc::new(Semaphore::new(max_concurrent_sliver_syncs)),
+                limits: Arc::new(Mutex::new((
+                    max_concurrent_blob_syncs,
+                    max_concurrent_sliver_syncs,
+                ))),
             },
         }
     }
 
+    /// Updates the maximum number of blobs and slivers that are synchronized concurrently.
+    pub fn set_concurrency_limits(
+        &self,
+        max_concurrent_blob_syncs: usize,
+        max_concurrent_sliver_syncs: usize,
+    ) {
+        let mut limits = self
+            .permits
+            .limits
+            .lock()
+            .expect("should be able to acquire lock");
+        resize_semaphore(&self.permits.blob, limits.0, max_concurrent_blob_syncs);
+        resize_semaphore(
+            &self.permits.sliver_pairs,
+            limits.1,
+            max_concurrent_sliver_syncs,
+        );
+        *limits = (max_concurrent_blob_syncs, max_concurrent_sliver_syncs);
+    }
+
     // Periodically checks the status of all in-progress blob syncs and return any that panicked.
     pub fn spawn_task_monitor(&self) -> JoinHandle<()> {
         let blob_syncs = self.blob_syncs_in_progress.clone();
@@ -326,6 +352,9 @@ impl BlobSyncHandler {
             },
 
             guard = async {
+                // Slivers are not synced while the node is in maintenance mode.
+                self.node.wait_until_not_in_maintenance().await;
+
                 // Await claiming the permit inside this async closure, to enable cancellation to
                 // also cancel waiting for the permit.
                 let _permit = permits
@@ -434,10 +463,6 @@ impl BlobSynchronizer {
         self.node.committee_service.as_ref()
     }
 
-    fn contract_service(&self) -> &dyn SystemContractService {
-        self.node.contract_service.as_ref()
-    }
-
     fn metrics(&self) -> &NodeMetricSet {
         &self.node.metrics
     }
@@ -643,14 +668,15 @@ impl BlobSynchronizer {
         })
     }
 
-    async fn sync_inconsistency_proof(&self, inconsistency_proof: &InconsistencyProof) {
-        let invalid_blob_certificate = self
-            .committee_service()
-            .get_invalid_blob_certificate(self.blob_id, inconsistency_proof)
-            .await;
-        self.contract_service()
-            .invalidate_blob_id(&invalid_blob_certificate)
+    async fn sync_inconsistency_proof(
+        &self,
+        inconsistency_proof: &InconsistencyProof,
+    ) -> InvalidationOutcome {
+        self.node
+            .blob_invalidator
+            .invalidate(&self.node, self.blob_id, inconsistency_proof)
             .await
+            .expect("database operations should not fail")
     }
 }
 
@@ -685,14 +711,12 @@ const fn labels_from_sliver_result<A: EncodingAxis>(
     [part, status]
 }
 
-const fn labels_from_inconsistency_sync_result(result: Option<&()>) -> [&'static str; 2] {
+fn labels_from_inconsistency_sync_result(
+    result: Option<&InvalidationOutcome>,
+) -> [&'static str; 2] {
     const INCONSISTENCY_PROOF: &str = "inconsistency-proof";
 
-    let status = if result.is_none() {
-        metrics::STATUS_ABORTED
-    } else {
-        metrics::STATUS_SUCCESS
-    };
+    let status = result.map_or(metrics::STATUS_ABORTED, |outcome| outcome.label());
 
     [INCONSISTENCY_PROOF, status]
 }
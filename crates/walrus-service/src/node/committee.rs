@@ -3,12 +3,13 @@
 
 //! Walrus committee service and associated types.
 
-use std::{num::NonZeroU16, sync::Arc, time::Duration};
+use std::{collections::BTreeSet, num::NonZeroU16, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
 use walrus_core::{
     encoding::EncodingConfig,
-    keys::ProtocolKeyPair,
     merkle::MerkleProof,
     messages::InvalidBlobCertificate,
     metadata::VerifiedBlobMetadataWithId,
@@ -30,15 +31,23 @@ use walrus_sui::{
 use self::node_service::NodeService;
 use crate::common::active_committees::ActiveCommittees;
 
+mod bandwidth_limit;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod cached_lookup;
 mod committee_service;
+#[cfg(test)]
+mod fault_injection;
 mod node_service;
 mod request_futures;
 
 pub(crate) use self::{
+    bandwidth_limit::BackgroundTransferLimits,
+    cached_lookup::{CachedCommitteeLookupService, CommitteeRecord, COMMITTEE_SNAPSHOT_FILE_NAME},
     committee_service::NodeCommitteeService,
     node_service::DefaultNodeServiceFactory,
 };
-use super::errors::SyncShardClientError;
+use super::{config::CommitteeServiceConfig, errors::SyncShardClientError, ProtocolSigner};
 
 /// Alias to the default type used for recovery symbols.
 pub(crate) type DefaultRecoverySymbol = walrus_core::RecoverySymbol<MerkleProof>;
@@ -135,6 +144,81 @@ pub enum EndCommitteeChangeError {
     EpochChangeAlreadyDone,
 }
 
+/// The health of a member of the current committee, as observed by the local node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerHealth {
+    /// The name of the storage node.
+    pub name: String,
+    /// The public key of the storage node.
+    pub public_key: PublicKey,
+    /// The network address of the storage node.
+    pub network_address: String,
+    /// The number of shards assigned to the storage node in the current epoch.
+    pub n_shards: usize,
+    /// True if this is the local storage node.
+    pub is_local: bool,
+    /// True if a client for communicating with the storage node could be created.
+    pub connected: bool,
+    /// The number of responses from the storage node that failed verification.
+    pub invalid_responses: u64,
+}
+
+/// The changes to the shards assigned to a storage node in an epoch, relative to the previous
+/// epoch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardAssignmentDiff {
+    /// The epoch in which the shards are assigned.
+    pub epoch: Epoch,
+    /// The shards assigned to the node in the epoch, but not in the previous epoch.
+    pub gained: Vec<ShardIndex>,
+    /// The shards assigned to the node in the previous epoch, but not in the epoch.
+    pub lost: Vec<ShardIndex>,
+    /// The shards assigned to the node in both the epoch and the previous epoch.
+    pub retained: Vec<ShardIndex>,
+}
+
+impl ShardAssignmentDiff {
+    /// Computes the changes to the shards of the node with the given public key from the previous
+    /// to the current committee.
+    ///
+    /// All shards of the current committee are considered gained if there is no previous
+    /// committee. The shards in each list are in ascending order.
+    pub(crate) fn new(committees: &ActiveCommittees, id: &PublicKey) -> Self {
+        let previous: BTreeSet<_> = committees
+            .previous_committee()
+            .map(|committee| {
+                committee
+                    .shards_for_node_public_key(id)
+                    .iter()
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let current: BTreeSet<_> = committees
+            .current_committee()
+            .shards_for_node_public_key(id)
+            .iter()
+            .copied()
+            .collect();
+
+        Self {
+            epoch: committees.epoch(),
+            gained: current.difference(&previous).copied().collect(),
+            lost: previous.difference(&current).copied().collect(),
+            retained: current.intersection(&previous).copied().collect(),
+        }
+    }
+
+    /// Returns the shards assigned to the node in the epoch, in ascending order.
+    pub fn owned_shards(&self) -> Vec<ShardIndex> {
+        let mut owned = [self.gained.as_slice(), self.retained.as_slice()].concat();
+        owned.sort_unstable();
+        owned
+    }
+}
+
 /// A `CommitteeService` provides information on the current committee, as well as interactions
 /// with committee members.
 ///
@@ -213,11 +297,47 @@ pub trait CommitteeService: std::fmt::Debug + Send + Sync {
         sliver_type: SliverType,
         sliver_count: u64,
         epoch: Epoch,
-        key_pair: &ProtocolKeyPair,
+        signer: &Arc<dyn ProtocolSigner>,
     ) -> Result<Vec<(BlobId, Sliver)>, SyncShardClientError>;
 
+    /// Pushes the metadata of blobs to the member of the current committee with the given public
+    /// key.
+    ///
+    /// Returns the number of metadata newly stored by the storage node.
+    async fn push_metadata(
+        &self,
+        public_key: &PublicKey,
+        metadata: Arc<Vec<VerifiedBlobMetadataWithId>>,
+    ) -> Result<u64, anyhow::Error>;
+
     /// Checks if the given public key belongs to a Walrus storage node.
     fn is_walrus_storage_node(&self, public_key: &PublicKey) -> bool;
+
+    /// Returns the health of each member of the current committee.
+    fn peer_health(&self) -> Vec<PeerHealth>;
+
+    /// Returns the changes to the shards assigned to the local node in the current epoch.
+    ///
+    /// Returns `None` if the identity of the local node is unknown.
+    fn shard_assignment_diff(&self) -> Option<ShardAssignmentDiff>;
+
+    /// Subscribes to the changes to the shards assigned to the local node.
+    ///
+    /// A new [`ShardAssignmentDiff`] is published whenever the service observes a new committee.
+    fn subscribe_to_shard_assignment_changes(&self)
+        -> watch::Receiver<Option<ShardAssignmentDiff>>;
+
+    /// Returns the committees of recent epochs recorded by the service, including those that are
+    /// no longer active, ordered by epoch.
+    fn committee_history(&self) -> Vec<Arc<Committee>>;
+
+    /// Adds committees of earlier epochs, e.g., loaded from the database on startup, to the
+    /// committee history.
+    fn restore_committee_history(&self, committees: Vec<Committee>);
+
+    /// Applies the settings of a reloaded configuration that can be changed while the service is
+    /// running, see [`CommitteeServiceConfig::update_runtime_settings`].
+    fn update_config(&self, config: &CommitteeServiceConfig);
 }
 
 /// Interface for creating new [`NodeService`]s, such as during epoch change.
@@ -233,4 +353,7 @@ pub(crate) trait NodeServiceFactory: Send {
 
     /// Set the timeout for any new connections to the storage node.
     fn connect_timeout(&mut self, timeout: Duration);
+
+    /// Set the maximum size of responses read from any new connections to storage nodes.
+    fn max_response_size(&mut self, max_size: u64);
 }
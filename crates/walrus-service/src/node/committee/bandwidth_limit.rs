@@ -0,0 +1,318 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bandwidth limits for the background transfers between storage nodes.
+//!
+//! The [`BandwidthLimitingServiceFactory`] wraps the services used to communicate with other
+//! storage nodes, and throttles the shard-sync and recovery traffic according to the
+//! [`BackgroundTransferLimits`] shared by all services of the node. The limits only apply to
+//! requests made by this node to other nodes, and therefore do not affect the reads and writes
+//! served to clients.
+
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, FutureExt as _};
+use tokio::time::Instant;
+use tower::Service;
+use walrus_core::encoding::EncodingConfig;
+use walrus_sdk::error::ClientBuildError;
+use walrus_sui::types::StorageNode as SuiStorageNode;
+
+use super::{
+    node_service::{NodeService, NodeServiceError, Request, Response},
+    NodeServiceFactory,
+};
+
+/// Limits the rate at which data is transferred across all concurrent transfers.
+///
+/// Each transfer reserves a time slot proportional to its size, and waits until its slot starts,
+/// so that the total rate does not exceed the configured limit.
+#[derive(Debug)]
+pub(crate) struct BandwidthLimiter {
+    state: Mutex<BandwidthLimiterState>,
+}
+
+#[derive(Debug)]
+struct BandwidthLimiterState {
+    bytes_per_second: Option<u64>,
+    next_slot: Instant,
+}
+
+impl BandwidthLimiter {
+    /// Creates a new limiter. The rate is not limited if `bytes_per_second` is `None`.
+    pub(crate) fn new(bytes_per_second: Option<u64>) -> Self {
+        Self {
+            state: Mutex::new(BandwidthLimiterState {
+                bytes_per_second,
+                next_slot: Instant::now(),
+            }),
+        }
+    }
+
+    /// Sets the maximum rate. Takes effect for subsequent transfers.
+    pub(crate) fn set_rate(&self, bytes_per_second: Option<u64>) {
+        self.state
+            .lock()
+            .expect("bandwidth limiter lock should not be poisoned")
+            .bytes_per_second = bytes_per_second;
+    }
+
+    /// Accounts for a transfer of `bytes` bytes and waits until it fits within the rate limit.
+    pub(crate) async fn throttle(&self, bytes: u64) {
+        let slot_start = {
+            let mut state = self
+                .state
+                .lock()
+                .expect("bandwidth limiter lock should not be poisoned");
+            let Some(bytes_per_second) = state.bytes_per_second.filter(|rate| *rate > 0) else {
+                return;
+            };
+            let slot_start = state.next_slot.max(Instant::now());
+            state.next_slot =
+                slot_start + Duration::from_secs_f64(bytes as f64 / bytes_per_second as f64);
+            slot_start
+        };
+        tokio::time::sleep_until(slot_start).await;
+    }
+}
+
+/// The bandwidth limits for the background transfers of a storage node.
+#[derive(Debug)]
+pub(crate) struct BackgroundTransferLimits {
+    /// Limits the slivers fetched and the metadata pushed during shard syncs.
+    pub shard_sync: BandwidthLimiter,
+    /// Limits the metadata and recovery symbols fetched to recover slivers.
+    pub recovery: BandwidthLimiter,
+}
+
+impl BackgroundTransferLimits {
+    /// Creates new limits with the given rates, in bytes per second.
+    ///
+    /// A rate of `None` does not limit the corresponding traffic.
+    pub(crate) fn new(
+        shard_sync_bytes_per_second: Option<u64>,
+        recovery_bytes_per_second: Option<u64>,
+    ) -> Self {
+        Self {
+            shard_sync: BandwidthLimiter::new(shard_sync_bytes_per_second),
+            recovery: BandwidthLimiter::new(recovery_bytes_per_second),
+        }
+    }
+
+    fn limiter(&self, kind: TransferKind) -> &BandwidthLimiter {
+        match kind {
+            TransferKind::ShardSync => &self.shard_sync,
+            TransferKind::Recovery => &self.recovery,
+        }
+    }
+}
+
+impl Default for BackgroundTransferLimits {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+/// The kinds of background transfers with separate bandwidth limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransferKind {
+    ShardSync,
+    Recovery,
+}
+
+impl TransferKind {
+    /// Returns the kind of transfer of the request, or `None` if the request is not throttled.
+    fn of(request: &Request) -> Option<Self> {
+        match request {
+            Request::SyncShardAsOfEpoch { .. } | Request::PushMetadata(_) => Some(Self::ShardSync),
+            Request::GetVerifiedMetadata(_)
+            | Request::GetVerifiedRecoverySymbol { .. }
+            | Request::ListVerifiedRecoverySymbols { .. } => Some(Self::Recovery),
+            Request::SubmitProofForInvalidBlobAttestation { .. } => None,
+        }
+    }
+}
+
+/// Returns the number of bytes of data sent with the request.
+fn request_bytes(request: &Request) -> u64 {
+    match request {
+        Request::PushMetadata(metadata) => serialized_size(metadata.as_ref()),
+        _ => 0,
+    }
+}
+
+/// Returns the number of bytes of data received with the response.
+fn response_bytes(response: &Response) -> u64 {
+    match response {
+        Response::ShardSlivers(slivers) => {
+            slivers.iter().map(|(_, sliver)| sliver.len() as u64).sum()
+        }
+        Response::VerifiedMetadata(metadata) => serialized_size(metadata),
+        Response::VerifiedRecoverySymbol(symbol) => serialized_size(symbol),
+        Response::VerifiedRecoverySymbols(symbols) => serialized_size(symbols),
+        Response::InvalidBlobAttestation(_) | Response::MetadataPushed(_) => 0,
+    }
+}
+
+fn serialized_size<T: serde::Serialize + ?Sized>(value: &T) -> u64 {
+    bcs::serialized_size(value).map_or(0, |size| size as u64)
+}
+
+/// A [`NodeServiceFactory`] that wraps the services of another factory in a
+/// [`BandwidthLimitedService`].
+#[derive(Debug)]
+pub(crate) struct BandwidthLimitingServiceFactory<F> {
+    inner: F,
+    limits: Arc<BackgroundTransferLimits>,
+}
+
+impl<F> BandwidthLimitingServiceFactory<F> {
+    /// Creates a new factory wrapping `inner`, whose services share the provided limits.
+    pub fn new(inner: F, limits: Arc<BackgroundTransferLimits>) -> Self {
+        Self { inner, limits }
+    }
+}
+
+#[async_trait::async_trait]
+impl<F> NodeServiceFactory for BandwidthLimitingServiceFactory<F>
+where
+    F: NodeServiceFactory,
+    F::Service: 'static,
+    <F::Service as Service<Request>>::Future: 'static,
+{
+    type Service = BandwidthLimitedService<F::Service>;
+
+    async fn make_service(
+        &mut self,
+        info: &SuiStorageNode,
+        encoding_config: &Arc<EncodingConfig>,
+    ) -> Result<Self::Service, ClientBuildError> {
+        let inner = self.inner.make_service(info, encoding_config).await?;
+        Ok(BandwidthLimitedService {
+            inner,
+            limits: self.limits.clone(),
+        })
+    }
+
+    fn connect_timeout(&mut self, timeout: Duration) {
+        self.inner.connect_timeout(timeout);
+    }
+
+    fn max_response_size(&mut self, max_size: u64) {
+        self.inner.max_response_size(max_size);
+    }
+}
+
+/// A [`NodeService`] that throttles the background transfers to and from its node according to
+/// the [`BackgroundTransferLimits`].
+///
+/// Data sent with a request is accounted for before the request is sent, while data received is
+/// accounted for once the response arrives, which delays the response and thereby any subsequent
+/// requests of the same transfer.
+#[derive(Debug, Clone)]
+pub(crate) struct BandwidthLimitedService<S> {
+    inner: S,
+    limits: Arc<BackgroundTransferLimits>,
+}
+
+impl<S> Service<Request> for BandwidthLimitedService<S>
+where
+    S: NodeService + 'static,
+    S::Future: 'static,
+{
+    type Response = Response;
+    type Error = NodeServiceError;
+    type Future = BoxFuture<'static, Result<Response, NodeServiceError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let Some(kind) = TransferKind::of(&request) else {
+            return self.inner.call(request).boxed();
+        };
+        let sent_bytes = request_bytes(&request);
+        let response = self.inner.call(request);
+        let limits = self.limits.clone();
+
+        async move {
+            let limiter = limits.limiter(kind);
+            if sent_bytes > 0 {
+                limiter.throttle(sent_bytes).await;
+            }
+            let response = response.await?;
+            limiter.throttle(response_bytes(&response)).await;
+            Ok(response)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tower::{util::BoxCloneService, ServiceExt as _};
+    use walrus_core::BlobId;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn bandwidth_limiter_spreads_transfers_over_time() {
+        let limiter = BandwidthLimiter::new(Some(100));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.throttle(100).await;
+        }
+        // The first transfer starts immediately, the following ones wait for their slot.
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+
+        limiter.set_rate(None);
+        let start = Instant::now();
+        limiter.throttle(1_000_000).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn recovery_transfers_are_throttled_separately_from_shard_syncs() -> TestResult {
+        let metadata = walrus_core::test_utils::verified_blob_metadata();
+        let metadata_bytes = serialized_size(&metadata);
+        let inner = tower::service_fn(move |_request| {
+            std::future::ready(Ok(Response::VerifiedMetadata(metadata.clone())))
+        })
+        .boxed_clone();
+        let mut service: BandwidthLimitedService<
+            BoxCloneService<Request, Response, NodeServiceError>,
+        > = BandwidthLimitedService {
+            inner,
+            limits: Arc::new(BackgroundTransferLimits::new(None, Some(metadata_bytes))),
+        };
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            service
+                .ready()
+                .await?
+                .call(Request::GetVerifiedMetadata(BlobId([1; 32])))
+                .await?;
+        }
+        // The first response is returned immediately, the following ones after one second each.
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+
+        // Shard-sync transfers do not wait for the recovery transfers.
+        let start = Instant::now();
+        service
+            .ready()
+            .await?
+            .call(Request::PushMetadata(Arc::new(vec![])))
+            .await?;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        Ok(())
+    }
+}
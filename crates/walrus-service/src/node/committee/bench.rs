@@ -0,0 +1,667 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Harness for benchmarking the recovery path of the committee service.
+//!
+//! The [`RecoveryBench`] encodes a set of random blobs and stores their slivers with a committee of
+//! in-memory mock peers. A [`NodeCommitteeService`] communicates with these peers through the same
+//! requests as with remote storage nodes, which allows measuring the throughput of sliver recovery,
+//! metadata verification, and shard sync without a network or storage.
+//!
+//! The mock peers verify metadata and recovery symbols in the same way as the client used to
+//! communicate with remote storage nodes, but skip the authentication of sync-shard requests.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroU16,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use futures::{future::BoxFuture, FutureExt as _};
+use rand::{rngs::StdRng, RngCore as _, SeedableRng as _};
+use sui_types::base_types::ObjectID;
+use tower::Service;
+use walrus_core::{
+    encoding::{
+        EncodingConfig,
+        EncodingConfigTrait as _,
+        GeneralRecoverySymbol,
+        Primary,
+        Secondary,
+        SliverPair,
+    },
+    keys::{NetworkKeyPair, ProtocolKeyPair},
+    metadata::{BlobMetadataApi as _, VerifiedBlobMetadataWithId},
+    BlobId,
+    Epoch,
+    PublicKey,
+    ShardIndex,
+    Sliver,
+    SliverPairIndex,
+    SliverType,
+    SymbolId,
+};
+use walrus_sdk::{
+    client::SymbolIdFilter,
+    error::{ClientBuildError, NodeError},
+};
+use walrus_sui::types::{Committee, NetworkAddress, StorageNode as SuiStorageNode};
+
+use super::{
+    node_service::{NodeServiceError, Request, Response},
+    CommitteeLookupService,
+    CommitteeService,
+    DefaultRecoverySymbol,
+    NodeCommitteeService,
+    NodeServiceFactory,
+};
+use crate::{
+    common::active_committees::ActiveCommittees,
+    node::{LocalProtocolSigner, ProtocolSigner},
+};
+
+/// The epoch of the committee of mock peers.
+///
+/// The committee of the previous epoch consists of the same peers, such that shards can be synced
+/// as of this epoch.
+const BENCH_EPOCH: Epoch = 1;
+
+/// The configuration of a [`RecoveryBench`].
+#[derive(Debug, Clone)]
+pub struct RecoveryBenchConfig {
+    /// The number of shards in the system.
+    pub n_shards: NonZeroU16,
+    /// The number of mock peers, among which the shards are distributed evenly.
+    pub n_nodes: u16,
+    /// The number of blobs stored with the mock peers.
+    pub n_blobs: usize,
+    /// The size of each stored blob in bytes.
+    pub blob_size: usize,
+    /// The seed used to generate the blobs and keys.
+    pub seed: u64,
+}
+
+impl Default for RecoveryBenchConfig {
+    fn default() -> Self {
+        Self {
+            n_shards: NonZeroU16::new(100).expect("100 is non-zero"),
+            n_nodes: 10,
+            n_blobs: 10,
+            blob_size: 1 << 16,
+            seed: 0,
+        }
+    }
+}
+
+/// A committee of in-memory mock peers storing encoded blobs, along with a committee service used
+/// to communicate with them.
+#[derive(Debug)]
+pub struct RecoveryBench {
+    committee_service: NodeCommitteeService<MockPeerService>,
+    store: Arc<BlobStore>,
+    signer: Arc<dyn ProtocolSigner>,
+}
+
+impl RecoveryBench {
+    /// Encodes the blobs and creates the mock peers and committee service.
+    pub async fn new(config: &RecoveryBenchConfig) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            config.n_nodes > 0 && config.n_nodes <= config.n_shards.get(),
+            "the number of nodes must be between 1 and the number of shards"
+        );
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let encoding_config = Arc::new(EncodingConfig::new(config.n_shards));
+
+        let mut blobs = BTreeMap::new();
+        for _ in 0..config.n_blobs {
+            let mut blob = vec![0; config.blob_size];
+            rng.fill_bytes(&mut blob);
+            let (sliver_pairs, metadata) = encoding_config
+                .get_for_type(walrus_core::DEFAULT_ENCODING)
+                .encode_with_metadata(&blob)
+                .context("failed to encode the benchmark blob")?;
+            blobs.insert(
+                *metadata.blob_id(),
+                StoredBlob {
+                    metadata: Arc::new(metadata),
+                    sliver_pairs,
+                },
+            );
+        }
+        let store = Arc::new(BlobStore {
+            encoding_config,
+            blobs,
+        });
+
+        let members = committee_members(config.n_shards, config.n_nodes, &mut rng);
+        let peers = members
+            .iter()
+            .map(|member| {
+                let peer = MockPeer {
+                    shards: member.shard_ids.clone(),
+                    store: store.clone(),
+                };
+                (member.public_key.clone(), Arc::new(peer))
+            })
+            .collect();
+        let committees = ActiveCommittees::new(
+            Committee::new(members.clone(), BENCH_EPOCH, config.n_shards)?,
+            Some(Committee::new(members, BENCH_EPOCH - 1, config.n_shards)?),
+        );
+
+        let committee_service = NodeCommitteeService::builder()
+            .build_with_factory(
+                StaticLookupService(committees),
+                MockPeerFactory {
+                    peers: Arc::new(peers),
+                },
+            )
+            .await?;
+
+        Ok(Self {
+            committee_service,
+            store,
+            signer: Arc::new(LocalProtocolSigner::new(
+                ProtocolKeyPair::generate_with_rng(&mut rng),
+            )),
+        })
+    }
+
+    /// Returns the encoding configuration of the system.
+    pub fn encoding_config(&self) -> &EncodingConfig {
+        &self.store.encoding_config
+    }
+
+    /// Returns the IDs of the stored blobs in ascending order.
+    pub fn blob_ids(&self) -> impl Iterator<Item = &BlobId> {
+        self.store.blobs.keys()
+    }
+
+    /// Returns the metadata of the stored blob.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is not stored with the mock peers.
+    pub fn metadata(&self, blob_id: &BlobId) -> Arc<VerifiedBlobMetadataWithId> {
+        self.store.blobs[blob_id].metadata.clone()
+    }
+
+    /// Recovers the sliver of the stored blob from recovery symbols of the mock peers.
+    pub async fn recover_sliver(
+        &self,
+        blob_id: &BlobId,
+        sliver_pair_index: SliverPairIndex,
+        sliver_type: SliverType,
+    ) -> anyhow::Result<Sliver> {
+        self.committee_service
+            .recover_sliver(
+                self.metadata(blob_id),
+                sliver_pair_index,
+                sliver_type,
+                BENCH_EPOCH,
+            )
+            .await
+            .map_err(|_| anyhow::anyhow!("the benchmark blob is inconsistent"))
+    }
+
+    /// Retrieves the metadata of the stored blob from the mock peers, which verify it.
+    pub async fn get_and_verify_metadata(&self, blob_id: &BlobId) -> VerifiedBlobMetadataWithId {
+        self.committee_service
+            .get_and_verify_metadata(*blob_id, BENCH_EPOCH)
+            .await
+    }
+
+    /// Syncs the slivers of the specified type of all stored blobs in the shard from the mock peer
+    /// owning it, requesting at most `batch_size` slivers at a time.
+    ///
+    /// Returns the number of synced slivers.
+    pub async fn sync_shard(
+        &self,
+        shard: ShardIndex,
+        sliver_type: SliverType,
+        batch_size: u64,
+    ) -> anyhow::Result<usize> {
+        let mut n_synced = 0;
+        let mut starting_blob_id = BlobId([0; BlobId::LENGTH]);
+        loop {
+            let slivers = self
+                .committee_service
+                .sync_shard_before_epoch(
+                    shard,
+                    starting_blob_id,
+                    sliver_type,
+                    batch_size,
+                    BENCH_EPOCH,
+                    &self.signer,
+                )
+                .await?;
+            n_synced += slivers.len();
+
+            let Some((last_blob_id, _)) = slivers.last() else {
+                break;
+            };
+            if u64::try_from(slivers.len())? < batch_size {
+                break;
+            }
+            let Some(next_blob_id) = next_blob_id(last_blob_id) else {
+                break;
+            };
+            starting_blob_id = next_blob_id;
+        }
+        Ok(n_synced)
+    }
+}
+
+/// Returns the smallest blob ID larger than `blob_id`, if any.
+fn next_blob_id(blob_id: &BlobId) -> Option<BlobId> {
+    let mut bytes = blob_id.0;
+    for byte in bytes.iter_mut().rev() {
+        let (incremented, overflow) = byte.overflowing_add(1);
+        *byte = incremented;
+        if !overflow {
+            return Some(BlobId(bytes));
+        }
+    }
+    None
+}
+
+/// Returns committee members among which `n_shards` are distributed evenly.
+fn committee_members(n_shards: NonZeroU16, n_nodes: u16, rng: &mut StdRng) -> Vec<SuiStorageNode> {
+    let mut shards = ShardIndex::range(..n_shards.get());
+    (0..n_nodes)
+        .map(|index| {
+            let n_node_shards =
+                n_shards.get() / n_nodes + u16::from(index < n_shards.get() % n_nodes);
+            SuiStorageNode {
+                name: format!("mock-peer-{index}"),
+                node_id: ObjectID::random_from_rng(rng),
+                network_address: NetworkAddress(format!("mock-peer-{index}:0")),
+                public_key: ProtocolKeyPair::generate_with_rng(rng).public().clone(),
+                next_epoch_public_key: None,
+                network_public_key: NetworkKeyPair::generate_with_rng(rng).public().clone(),
+                metadata: ObjectID::random_from_rng(rng),
+                shard_ids: (&mut shards).take(n_node_shards.into()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// A [`CommitteeLookupService`] that always returns the same committees.
+#[derive(Debug)]
+struct StaticLookupService(ActiveCommittees);
+
+#[async_trait::async_trait]
+impl CommitteeLookupService for StaticLookupService {
+    async fn get_active_committees(&self) -> Result<ActiveCommittees, anyhow::Error> {
+        Ok(self.0.clone())
+    }
+}
+
+/// A blob stored with the mock peers.
+#[derive(Debug)]
+struct StoredBlob {
+    metadata: Arc<VerifiedBlobMetadataWithId>,
+    sliver_pairs: Vec<SliverPair>,
+}
+
+/// The blobs stored with the mock peers, each of which only serves the slivers of its shards.
+#[derive(Debug)]
+struct BlobStore {
+    encoding_config: Arc<EncodingConfig>,
+    blobs: BTreeMap<BlobId, StoredBlob>,
+}
+
+/// Errors returned by mock peers.
+#[derive(Debug, thiserror::Error)]
+enum MockPeerError {
+    #[error("the blob is not stored")]
+    BlobNotFound,
+    #[error("the sliver is not stored with the shards of this peer")]
+    SliverNotStored,
+    #[error("none of the requested symbols could be returned")]
+    NoSymbols,
+    #[error("the request is not supported by mock peers")]
+    Unsupported,
+}
+
+impl From<MockPeerError> for NodeServiceError {
+    fn from(error: MockPeerError) -> Self {
+        NodeServiceError::Node(NodeError::other(error))
+    }
+}
+
+/// An in-memory storage node holding the slivers of its shards.
+#[derive(Debug)]
+struct MockPeer {
+    shards: Vec<ShardIndex>,
+    store: Arc<BlobStore>,
+}
+
+impl MockPeer {
+    fn n_shards(&self) -> NonZeroU16 {
+        self.store.encoding_config.n_shards()
+    }
+
+    fn blob(&self, blob_id: &BlobId) -> Result<&StoredBlob, MockPeerError> {
+        self.store
+            .blobs
+            .get(blob_id)
+            .ok_or(MockPeerError::BlobNotFound)
+    }
+
+    /// Returns the sliver pair with the specified index, if it is stored with this peer's shards.
+    fn sliver_pair(
+        &self,
+        blob_id: &BlobId,
+        pair_index: SliverPairIndex,
+    ) -> Result<&SliverPair, MockPeerError> {
+        let blob = self.blob(blob_id)?;
+        if !self
+            .shards
+            .contains(&pair_index.to_shard_index(self.n_shards(), blob_id))
+        {
+            return Err(MockPeerError::SliverNotStored);
+        }
+        Ok(&blob.sliver_pairs[pair_index.as_usize()])
+    }
+
+    fn handle(&self, request: Request) -> Result<Response, NodeServiceError> {
+        let response = match request {
+            Request::GetVerifiedMetadata(blob_id) => {
+                let metadata = self
+                    .blob(&blob_id)?
+                    .metadata
+                    .as_ref()
+                    .clone()
+                    .into_unverified()
+                    .verify(&self.store.encoding_config)
+                    .map_err(NodeError::verification)?;
+                Response::VerifiedMetadata(metadata)
+            }
+            Request::GetVerifiedRecoverySymbol {
+                sliver_type,
+                metadata,
+                sliver_pair_at_remote,
+                intersecting_pair_index,
+            } => Response::VerifiedRecoverySymbol(self.verified_recovery_symbol(
+                &metadata,
+                sliver_type,
+                sliver_pair_at_remote,
+                intersecting_pair_index,
+            )?),
+            Request::ListVerifiedRecoverySymbols {
+                filter,
+                metadata,
+                target_index,
+                target_type,
+            } => {
+                let blob_id = metadata.blob_id();
+                let n_shards = self.n_shards();
+                let symbol_ids: Vec<_> = match filter.id_filter() {
+                    SymbolIdFilter::Ids(symbol_ids) => symbol_ids.clone(),
+                    SymbolIdFilter::Recovers {
+                        target_sliver,
+                        target_type,
+                    } => self
+                        .shards
+                        .iter()
+                        .map(|shard| {
+                            let pair_stored = shard.to_pair_index(n_shards, blob_id);
+                            match target_type {
+                                SliverType::Primary => SymbolId::new(
+                                    *target_sliver,
+                                    pair_stored.to_sliver_index::<Secondary>(n_shards),
+                                ),
+                                SliverType::Secondary => SymbolId::new(
+                                    pair_stored.to_sliver_index::<Primary>(n_shards),
+                                    *target_sliver,
+                                ),
+                            }
+                        })
+                        .collect(),
+                };
+
+                let symbols: Vec<_> = symbol_ids
+                    .into_iter()
+                    .filter_map(|symbol_id| self.recovery_symbol(blob_id, symbol_id, target_type))
+                    .filter(|symbol| {
+                        filter.accepts(symbol)
+                            && symbol
+                                .verify(
+                                    metadata.metadata(),
+                                    &self.store.encoding_config,
+                                    target_index,
+                                    target_type,
+                                )
+                                .is_ok()
+                    })
+                    .collect();
+                if symbols.is_empty() {
+                    return Err(MockPeerError::NoSymbols.into());
+                }
+                Response::VerifiedRecoverySymbols(symbols)
+            }
+            Request::SyncShardAsOfEpoch {
+                shard,
+                starting_blob_id,
+                sliver_count,
+                sliver_type,
+                ..
+            } => {
+                if !self.shards.contains(&shard) {
+                    return Err(MockPeerError::SliverNotStored.into());
+                }
+                let slivers = self
+                    .store
+                    .blobs
+                    .range(starting_blob_id..)
+                    .take(usize::try_from(sliver_count).unwrap_or(usize::MAX))
+                    .map(|(blob_id, blob)| {
+                        let pair = &blob.sliver_pairs
+                            [shard.to_pair_index(self.n_shards(), blob_id).as_usize()];
+                        let sliver = match sliver_type {
+                            SliverType::Primary => Sliver::Primary(pair.primary.clone()),
+                            SliverType::Secondary => Sliver::Secondary(pair.secondary.clone()),
+                        };
+                        (*blob_id, sliver)
+                    })
+                    .collect();
+                Response::ShardSlivers(slivers)
+            }
+            Request::SubmitProofForInvalidBlobAttestation { .. } | Request::PushMetadata(_) => {
+                return Err(MockPeerError::Unsupported.into());
+            }
+        };
+        Ok(response)
+    }
+
+    /// Returns the symbol for recovering the sliver of the specified type, computed from the
+    /// orthogonal sliver stored with this peer.
+    fn recovery_symbol(
+        &self,
+        blob_id: &BlobId,
+        symbol_id: SymbolId,
+        target_type: SliverType,
+    ) -> Option<GeneralRecoverySymbol> {
+        let n_shards = self.n_shards();
+        let config = self
+            .store
+            .encoding_config
+            .get_for_type(self.blob(blob_id).ok()?.metadata.metadata().encoding_type());
+        let primary_pair_index = symbol_id
+            .primary_sliver_index()
+            .to_pair_index::<Primary>(n_shards);
+        let secondary_pair_index = symbol_id
+            .secondary_sliver_index()
+            .to_pair_index::<Secondary>(n_shards);
+
+        match target_type {
+            SliverType::Primary => {
+                let pair = self.sliver_pair(blob_id, secondary_pair_index).ok()?;
+                let symbol = pair
+                    .secondary
+                    .recovery_symbol_for_sliver(primary_pair_index, &config)
+                    .ok()?;
+                Some(GeneralRecoverySymbol::from_recovery_symbol(
+                    symbol,
+                    symbol_id.primary_sliver_index(),
+                ))
+            }
+            SliverType::Secondary => {
+                let pair = self.sliver_pair(blob_id, primary_pair_index).ok()?;
+                let symbol = pair
+                    .primary
+                    .recovery_symbol_for_sliver(secondary_pair_index, &config)
+                    .ok()?;
+                Some(GeneralRecoverySymbol::from_recovery_symbol(
+                    symbol,
+                    symbol_id.secondary_sliver_index(),
+                ))
+            }
+        }
+    }
+
+    fn verified_recovery_symbol(
+        &self,
+        metadata: &VerifiedBlobMetadataWithId,
+        sliver_type: SliverType,
+        sliver_pair_at_remote: SliverPairIndex,
+        intersecting_pair_index: SliverPairIndex,
+    ) -> Result<DefaultRecoverySymbol, NodeServiceError> {
+        let blob_id = metadata.blob_id();
+        let n_shards = self.n_shards();
+        let pair = self.sliver_pair(blob_id, sliver_pair_at_remote)?;
+        let encoding_config = &self.store.encoding_config;
+        let config = encoding_config.get_for_type(metadata.metadata().encoding_type());
+
+        let symbol = match sliver_type {
+            SliverType::Primary => {
+                let symbol = pair
+                    .secondary
+                    .recovery_symbol_for_sliver(intersecting_pair_index, &config)
+                    .map_err(NodeError::other)?;
+                symbol
+                    .verify(
+                        metadata.metadata(),
+                        encoding_config,
+                        intersecting_pair_index.to_sliver_index::<Primary>(n_shards),
+                    )
+                    .map_err(NodeError::verification)?;
+                DefaultRecoverySymbol::Primary(symbol)
+            }
+            SliverType::Secondary => {
+                let symbol = pair
+                    .primary
+                    .recovery_symbol_for_sliver(intersecting_pair_index, &config)
+                    .map_err(NodeError::other)?;
+                symbol
+                    .verify(
+                        metadata.metadata(),
+                        encoding_config,
+                        intersecting_pair_index.to_sliver_index::<Secondary>(n_shards),
+                    )
+                    .map_err(NodeError::verification)?;
+                DefaultRecoverySymbol::Secondary(symbol)
+            }
+        };
+        Ok(symbol)
+    }
+}
+
+/// A [`NodeService`][super::NodeService] communicating with a [`MockPeer`].
+#[derive(Debug, Clone)]
+pub(crate) struct MockPeerService(Arc<MockPeer>);
+
+impl Service<Request> for MockPeerService {
+    type Response = Response;
+    type Error = NodeServiceError;
+    type Future = BoxFuture<'static, Result<Response, NodeServiceError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let peer = self.0.clone();
+        async move { peer.handle(request) }.boxed()
+    }
+}
+
+/// A [`NodeServiceFactory`] creating services for the mock peers of a [`RecoveryBench`].
+#[derive(Debug)]
+struct MockPeerFactory {
+    peers: Arc<HashMap<PublicKey, Arc<MockPeer>>>,
+}
+
+#[async_trait::async_trait]
+impl NodeServiceFactory for MockPeerFactory {
+    type Service = MockPeerService;
+
+    async fn make_service(
+        &mut self,
+        info: &SuiStorageNode,
+        _encoding_config: &Arc<EncodingConfig>,
+    ) -> Result<Self::Service, ClientBuildError> {
+        let peer = self
+            .peers
+            .get(&info.public_key)
+            .expect("all committee members are mock peers");
+        Ok(MockPeerService(peer.clone()))
+    }
+
+    fn connect_timeout(&mut self, _timeout: Duration) {}
+
+    fn max_response_size(&mut self, _max_size: u64) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    fn small_config() -> RecoveryBenchConfig {
+        RecoveryBenchConfig {
+            n_shards: NonZeroU16::new(10).unwrap(),
+            n_nodes: 4,
+            n_blobs: 3,
+            blob_size: 1024,
+            seed: 42,
+        }
+    }
+
+    #[tokio::test]
+    async fn recovers_slivers_and_syncs_shards() -> TestResult {
+        let bench = RecoveryBench::new(&small_config()).await?;
+        let blob_id = *bench.blob_ids().next().unwrap();
+        let metadata = bench.metadata(&blob_id);
+
+        for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+            let sliver = bench
+                .recover_sliver(&blob_id, SliverPairIndex(3), sliver_type)
+                .await?;
+            assert_eq!(sliver.r#type(), sliver_type);
+        }
+        assert_eq!(bench.get_and_verify_metadata(&blob_id).await, *metadata);
+        assert_eq!(
+            bench
+                .sync_shard(ShardIndex(5), SliverType::Primary, 2)
+                .await?,
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn next_blob_id_increments_with_carry() {
+        let mut bytes = [0; BlobId::LENGTH];
+        bytes[BlobId::LENGTH - 1] = u8::MAX;
+        let mut expected = [0; BlobId::LENGTH];
+        expected[BlobId::LENGTH - 2] = 1;
+        assert_eq!(next_blob_id(&BlobId(bytes)), Some(BlobId(expected)));
+        assert_eq!(next_blob_id(&BlobId([u8::MAX; BlobId::LENGTH])), None);
+    }
+}
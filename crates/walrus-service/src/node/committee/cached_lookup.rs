@@ -0,0 +1,327 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [`CommitteeLookupService`] that keeps a snapshot of the committees on disk.
+//!
+//! The [`CachedCommitteeLookupService`] persists the committees returned by the wrapped lookup
+//! service, and serves them from the snapshot while the chain cannot be reached, as long as the
+//! snapshot is not older than the configured maximum staleness. This allows a node to restart while
+//! its full nodes are briefly unavailable.
+
+use std::{
+    num::NonZeroU16,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::ObjectID;
+use walrus_core::{Epoch, NetworkPublicKey, PublicKey, ShardIndex};
+use walrus_sui::types::{Committee, NetworkAddress, StorageNode};
+
+use super::CommitteeLookupService;
+use crate::common::active_committees::ActiveCommittees;
+
+/// The name of the file in the storage directory in which the committees are persisted.
+pub(crate) const COMMITTEE_SNAPSHOT_FILE_NAME: &str = "committee_snapshot.bcs";
+
+/// A [`CommitteeLookupService`] that persists the committees returned by the wrapped service and
+/// serves them from disk if the wrapped service fails.
+#[derive(Debug)]
+pub(crate) struct CachedCommitteeLookupService<L> {
+    inner: L,
+    snapshot_path: PathBuf,
+    max_staleness: Option<Duration>,
+}
+
+impl<L> CachedCommitteeLookupService<L> {
+    /// Creates a new service wrapping `inner`, which persists the committees at `snapshot_path`.
+    ///
+    /// The snapshot is only served if it was last confirmed by the wrapped service within
+    /// `max_staleness`; it is never served if `max_staleness` is `None`.
+    pub(crate) fn new(inner: L, snapshot_path: PathBuf, max_staleness: Option<Duration>) -> Self {
+        Self {
+            inner,
+            snapshot_path,
+            max_staleness,
+        }
+    }
+
+    /// Returns the committees from the snapshot, if the snapshot exists and is recent enough.
+    fn load_snapshot(&self) -> anyhow::Result<Option<ActiveCommittees>> {
+        let Some(max_staleness) = self.max_staleness else {
+            return Ok(None);
+        };
+        let bytes = match std::fs::read(&self.snapshot_path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let snapshot: CommitteeSnapshot = bcs::from_bytes(&bytes)?;
+        let age = snapshot.confirmed_at.elapsed().unwrap_or_default();
+        if age > max_staleness {
+            tracing::info!(
+                ?age,
+                ?max_staleness,
+                "the snapshot of the committees is too old to be used"
+            );
+            return Ok(None);
+        }
+        snapshot.try_into().map(Some)
+    }
+
+    /// Replaces the snapshot with the committees, which were just confirmed by the wrapped service.
+    fn persist_snapshot(&self, committees: &ActiveCommittees) -> anyhow::Result<()> {
+        let bytes = bcs::to_bytes(&CommitteeSnapshot::new(committees, SystemTime::now()))?;
+        write_atomically(&self.snapshot_path, &bytes)
+            .context("failed to write the snapshot of the committees")
+    }
+}
+
+#[async_trait]
+impl<L: CommitteeLookupService> CommitteeLookupService for CachedCommitteeLookupService<L> {
+    async fn get_active_committees(&self) -> Result<ActiveCommittees, anyhow::Error> {
+        let error = match self.inner.get_active_committees().await {
+            Ok(committees) => {
+                if let Err(error) = self.persist_snapshot(&committees) {
+                    tracing::warn!(?error, "failed to persist the committees");
+                }
+                return Ok(committees);
+            }
+            Err(error) => error,
+        };
+
+        match self.load_snapshot() {
+            Ok(Some(committees)) => {
+                tracing::warn!(
+                    ?error,
+                    epoch = committees.epoch(),
+                    "failed to look up the committees, using the snapshot on disk"
+                );
+                Ok(committees)
+            }
+            Ok(None) => Err(error),
+            Err(snapshot_error) => {
+                tracing::warn!(
+                    ?snapshot_error,
+                    "failed to load the snapshot of the committees"
+                );
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Writes the bytes to a temporary file, which then replaces the file at `path`.
+fn write_atomically(path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, bytes)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// The committees persisted on disk, along with the time at which they were last confirmed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommitteeSnapshot {
+    /// The time at which the committees were last returned by the wrapped service.
+    confirmed_at: SystemTime,
+    current: CommitteeRecord,
+    previous: Option<CommitteeRecord>,
+    next: Option<CommitteeRecord>,
+    is_transitioning: bool,
+}
+
+impl CommitteeSnapshot {
+    fn new(committees: &ActiveCommittees, confirmed_at: SystemTime) -> Self {
+        Self {
+            confirmed_at,
+            current: committees.current_committee().as_ref().into(),
+            previous: committees
+                .previous_committee()
+                .map(|committee| committee.as_ref().into()),
+            next: committees
+                .next_committee()
+                .map(|committee| committee.as_ref().into()),
+            is_transitioning: committees.is_change_in_progress(),
+        }
+    }
+}
+
+impl TryFrom<CommitteeSnapshot> for ActiveCommittees {
+    type Error = anyhow::Error;
+
+    fn try_from(snapshot: CommitteeSnapshot) -> Result<Self, Self::Error> {
+        let to_committee = |record: CommitteeRecord| -> anyhow::Result<Arc<Committee>> {
+            Ok(Arc::new(record.try_into()?))
+        };
+        ActiveCommittees::try_new_with_next(
+            to_committee(snapshot.current)?,
+            snapshot.previous.map(to_committee).transpose()?,
+            snapshot.next.map(to_committee).transpose()?,
+            snapshot.is_transitioning,
+        )
+    }
+}
+
+/// The serialized form of a [`Committee`].
+///
+/// The deserialization of [`Committee`] and [`StorageNode`] follows the layout of the Move
+/// structs, which does not include the shards of the members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CommitteeRecord {
+    epoch: Epoch,
+    n_shards: NonZeroU16,
+    members: Vec<StorageNodeRecord>,
+}
+
+impl From<&Committee> for CommitteeRecord {
+    fn from(committee: &Committee) -> Self {
+        Self {
+            epoch: committee.epoch,
+            n_shards: committee.n_shards(),
+            members: committee.members().iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<CommitteeRecord> for Committee {
+    type Error = anyhow::Error;
+
+    fn try_from(record: CommitteeRecord) -> Result<Self, Self::Error> {
+        Ok(Committee::new(
+            record.members.into_iter().map(Into::into).collect(),
+            record.epoch,
+            record.n_shards,
+        )?)
+    }
+}
+
+/// The serialized form of a [`StorageNode`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StorageNodeRecord {
+    name: String,
+    node_id: ObjectID,
+    network_address: NetworkAddress,
+    public_key: PublicKey,
+    next_epoch_public_key: Option<PublicKey>,
+    network_public_key: NetworkPublicKey,
+    metadata: ObjectID,
+    shard_ids: Vec<ShardIndex>,
+}
+
+impl From<&StorageNode> for StorageNodeRecord {
+    fn from(node: &StorageNode) -> Self {
+        Self {
+            name: node.name.clone(),
+            node_id: node.node_id,
+            network_address: node.network_address.clone(),
+            public_key: node.public_key.clone(),
+            next_epoch_public_key: node.next_epoch_public_key.clone(),
+            network_public_key: node.network_public_key.clone(),
+            metadata: node.metadata,
+            shard_ids: node.shard_ids.clone(),
+        }
+    }
+}
+
+impl From<StorageNodeRecord> for StorageNode {
+    fn from(record: StorageNodeRecord) -> Self {
+        Self {
+            name: record.name,
+            node_id: record.node_id,
+            network_address: record.network_address,
+            public_key: record.public_key,
+            next_epoch_public_key: record.next_epoch_public_key,
+            network_public_key: record.network_public_key,
+            metadata: record.metadata,
+            shard_ids: record.shard_ids,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+    use crate::test_utils;
+
+    /// A lookup service that returns the stored committees, or fails if there are none.
+    #[derive(Debug, Default)]
+    struct FlakyLookupService(Mutex<Option<ActiveCommittees>>);
+
+    #[async_trait]
+    impl CommitteeLookupService for FlakyLookupService {
+        async fn get_active_committees(&self) -> Result<ActiveCommittees, anyhow::Error> {
+            self.0
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("the chain is unreachable"))
+        }
+    }
+
+    fn active_committees() -> ActiveCommittees {
+        ActiveCommittees::new(
+            test_utils::test_committee_with_epoch(&[1, 2, 3], 5),
+            Some(test_utils::test_committee_with_epoch(&[3, 3], 4)),
+        )
+    }
+
+    #[tokio::test]
+    async fn serves_persisted_committees_while_lookup_fails() -> TestResult {
+        let directory = tempfile::tempdir()?;
+        let snapshot_path = directory.path().join(COMMITTEE_SNAPSHOT_FILE_NAME);
+        let committees = active_committees();
+        let service = CachedCommitteeLookupService::new(
+            FlakyLookupService(Mutex::new(Some(committees.clone()))),
+            snapshot_path.clone(),
+            Some(Duration::from_secs(60)),
+        );
+
+        assert_eq!(service.get_active_committees().await?, committees);
+        assert!(snapshot_path.exists());
+
+        *service.inner.0.lock().unwrap() = None;
+        assert_eq!(service.get_active_committees().await?, committees);
+
+        // A restarted node serves the snapshot written before the restart.
+        let restarted = CachedCommitteeLookupService::new(
+            FlakyLookupService::default(),
+            snapshot_path.clone(),
+            Some(Duration::from_secs(60)),
+        );
+        assert_eq!(restarted.get_active_committees().await?, committees);
+
+        let without_fallback =
+            CachedCommitteeLookupService::new(FlakyLookupService::default(), snapshot_path, None);
+        assert!(without_fallback.get_active_committees().await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn does_not_serve_stale_snapshots() -> TestResult {
+        let directory = tempfile::tempdir()?;
+        let snapshot_path = directory.path().join(COMMITTEE_SNAPSHOT_FILE_NAME);
+        let stale = CommitteeSnapshot::new(
+            &active_committees(),
+            SystemTime::now() - Duration::from_secs(120),
+        );
+        std::fs::write(&snapshot_path, bcs::to_bytes(&stale)?)?;
+
+        let service = CachedCommitteeLookupService::new(
+            FlakyLookupService::default(),
+            snapshot_path,
+            Some(Duration::from_secs(60)),
+        );
+        assert!(service.get_active_committees().await.is_err());
+
+        Ok(())
+    }
+}
@@ -5,20 +5,28 @@
 //! Committee lookup and management.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     num::NonZeroU16,
-    sync::{Arc, Mutex as SyncMutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex as SyncMutex,
+        RwLock,
+    },
+    time::Duration,
 };
 
 use futures::TryFutureExt;
 use prometheus::Registry;
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use tokio::sync::{watch, Mutex as TokioMutex};
+use tokio::{
+    sync::{watch, Mutex as TokioMutex},
+    time::Instant,
+};
 use tower::ServiceExt as _;
 use walrus_core::{
     encoding::EncodingConfig,
     ensure,
-    keys::ProtocolKeyPair,
     merkle::MerkleProof,
     messages::InvalidBlobCertificate,
     metadata::VerifiedBlobMetadataWithId,
@@ -31,9 +39,14 @@ use walrus_core::{
     SliverPairIndex,
     SliverType,
 };
-use walrus_sui::types::Committee;
+use walrus_sui::types::{Committee, StorageNode as SuiStorageNode};
 
 use super::{
+    bandwidth_limit::{
+        BackgroundTransferLimits,
+        BandwidthLimitedService,
+        BandwidthLimitingServiceFactory,
+    },
     node_service::{NodeService, NodeServiceError, RemoteStorageNode, Request, Response},
     request_futures::{
         GetAndVerifyMetadata,
@@ -47,6 +60,8 @@ use super::{
     DefaultNodeServiceFactory,
     EndCommitteeChangeError,
     NodeServiceFactory,
+    PeerHealth,
+    ShardAssignmentDiff,
 };
 use crate::{
     common::active_committees::{
@@ -59,6 +74,7 @@ use crate::{
         config::CommitteeServiceConfig,
         errors::SyncShardClientError,
         metrics::CommitteeServiceMetricSet,
+        ProtocolSigner,
     },
 };
 
@@ -67,6 +83,7 @@ pub(crate) struct NodeCommitteeServiceBuilder {
     rng: StdRng,
     config: CommitteeServiceConfig,
     registry: Option<Registry>,
+    background_transfer_limits: Arc<BackgroundTransferLimits>,
 }
 
 impl Default for NodeCommitteeServiceBuilder {
@@ -76,6 +93,7 @@ impl Default for NodeCommitteeServiceBuilder {
             rng: StdRng::seed_from_u64(rand::thread_rng().gen()),
             config: CommitteeServiceConfig::default(),
             registry: None,
+            background_transfer_limits: Default::default(),
         }
     }
 }
@@ -96,6 +114,13 @@ impl NodeCommitteeServiceBuilder {
         self
     }
 
+    /// Sets the bandwidth limits applied to the shard-sync and recovery traffic of the services
+    /// created by [`Self::build`].
+    pub fn background_transfer_limits(mut self, limits: Arc<BackgroundTransferLimits>) -> Self {
+        self.background_transfer_limits = limits;
+        self
+    }
+
     #[cfg(test)]
     pub fn randomness(mut self, rng: StdRng) -> Self {
         self.rng = rng;
@@ -105,7 +130,7 @@ impl NodeCommitteeServiceBuilder {
     pub async fn build<S>(
         self,
         lookup_service: S,
-    ) -> Result<NodeCommitteeService<RemoteStorageNode>, anyhow::Error>
+    ) -> Result<NodeCommitteeService<BandwidthLimitedService<RemoteStorageNode>>, anyhow::Error>
     where
         S: CommitteeLookupService + std::fmt::Debug + 'static,
     {
@@ -114,6 +139,10 @@ impl NodeCommitteeServiceBuilder {
         } else {
             DefaultNodeServiceFactory::default()
         };
+        let service_factory = BandwidthLimitingServiceFactory::new(
+            service_factory,
+            self.background_transfer_limits.clone(),
+        );
 
         self.build_with_factory(lookup_service, service_factory)
             .await
@@ -140,9 +169,11 @@ impl NodeCommitteeServiceBuilder {
         ));
 
         service_factory.connect_timeout(self.config.node_connect_timeout);
+        service_factory.max_response_size(self.config.max_node_response_size_bytes);
 
         let inner = NodeCommitteeServiceInner::new(
             committee_tracker,
+            Box::new(lookup_service),
             Box::new(service_factory),
             self.config,
             encoding_config,
@@ -153,7 +184,7 @@ impl NodeCommitteeServiceBuilder {
         )
         .await?;
 
-        Ok(NodeCommitteeService::new(inner, Box::new(lookup_service)))
+        Ok(NodeCommitteeService::new(inner))
     }
 }
 
@@ -162,7 +193,6 @@ impl NodeCommitteeServiceBuilder {
 /// Requests the current committee state using a [`CommitteeLookupService`].
 pub(crate) struct NodeCommitteeService<T = RemoteStorageNode> {
     inner: NodeCommitteeServiceInner<T>,
-    committee_lookup: Box<dyn super::CommitteeLookupService>,
 }
 
 impl NodeCommitteeService<RemoteStorageNode> {
@@ -175,17 +205,33 @@ impl<T> NodeCommitteeService<T>
 where
     T: NodeService,
 {
-    fn new(
-        inner: NodeCommitteeServiceInner<T>,
-        committee_lookup: Box<dyn super::CommitteeLookupService>,
-    ) -> Self {
+    fn new(inner: NodeCommitteeServiceInner<T>) -> Self {
         inner.record_epoch_change_metrics(inner.committee_tracker.borrow().committees());
-        Self {
-            inner,
-            committee_lookup,
-        }
+        Self { inner }
+    }
+
+    /// Returns the number of responses from `node` that have failed verification.
+    #[cfg(test)]
+    pub(super) fn invalid_response_count(&self, node: &PublicKey) -> u64 {
+        self.inner.invalid_response_count(node)
     }
 
+    /// Reconciles the tracked committees as if they were suspected to be stale.
+    #[cfg(test)]
+    pub(super) async fn reconcile_suspected_stale_committee(&self) {
+        self.inner
+            .stale_committee_suspected
+            .store(true, Ordering::Relaxed);
+        self.inner.reconcile_if_committee_stale().await;
+    }
+
+    /// Fetches slivers of the shard from a node that owned it before `current_epoch`.
+    ///
+    /// The owner in the previous epoch is contacted first. If it cannot be reached or fails to
+    /// respond, the owners in up to [`CommitteeServiceConfig::shard_sync_max_epochs_back`] earlier
+    /// epochs with known committees are tried in turn. Owners in earlier epochs may not store
+    /// slivers of blobs certified after they lost the shard; these are detected as missing and
+    /// recovered by the caller.
     async fn sync_shard_as_of_epoch(
         &self,
         shard: ShardIndex,
@@ -193,22 +239,73 @@ where
         sliver_count: u64,
         sliver_type: SliverType,
         current_epoch: Epoch,
-        key_pair: &ProtocolKeyPair,
+        signer: &Arc<dyn ProtocolSigner>,
     ) -> Result<Vec<(BlobId, Sliver)>, SyncShardClientError> {
-        let committee = self
-            .inner
-            .committee_tracker
-            .borrow()
-            .committees()
-            .committee_for_epoch(current_epoch - 1)
-            .ok_or(SyncShardClientError::NoSyncClient)?
-            .clone();
+        let mut last_error = None;
+        let mut contacted = Vec::new();
+
+        for epoch in (1..=self.inner.config().shard_sync_max_epochs_back.get())
+            .map_while(|epochs_back| current_epoch.checked_sub(epochs_back))
+        {
+            let Some(committee) = self.inner.committee_for_epoch(epoch) else {
+                tracing::debug!(walrus.epoch = epoch, "committee for the epoch is unknown");
+                continue;
+            };
+            let Some(node_info) = committee
+                .member_index_for_shard(shard)
+                .map(|index| &committee.members()[index])
+            else {
+                tracing::debug!(walrus.epoch = epoch, "shard has no owner in the epoch");
+                last_error.get_or_insert(SyncShardClientError::NoOwnerForShard(shard));
+                continue;
+            };
+
+            if self.inner.is_local(&node_info.public_key)
+                || contacted.contains(&node_info.public_key)
+            {
+                continue;
+            }
+            contacted.push(node_info.public_key.clone());
+
+            match self
+                .sync_shard_from_node(
+                    node_info,
+                    shard,
+                    starting_blob_id,
+                    sliver_count,
+                    sliver_type,
+                    current_epoch,
+                    signer,
+                )
+                .await
+            {
+                Ok(slivers) => return Ok(slivers),
+                Err(error) => {
+                    tracing::warn!(
+                        walrus.epoch = epoch,
+                        walrus.node.public_key = %node_info.public_key,
+                        %error,
+                        "failed to sync the shard from its owner in the epoch"
+                    );
+                    last_error = Some(error);
+                }
+            }
+        }
 
-        let node_info = committee
-            .member_index_for_shard(shard)
-            .map(|index| &committee.members()[index])
-            .expect("shard is valid for the committee");
+        Err(last_error.unwrap_or(SyncShardClientError::NoSyncClient))
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn sync_shard_from_node(
+        &self,
+        node_info: &SuiStorageNode,
+        shard: ShardIndex,
+        starting_blob_id: BlobId,
+        sliver_count: u64,
+        sliver_type: SliverType,
+        current_epoch: Epoch,
+        signer: &Arc<dyn ProtocolSigner>,
+    ) -> Result<Vec<(BlobId, Sliver)>, SyncShardClientError> {
         let service =
             if let Some(service) = self.inner.get_node_service_by_id(&node_info.public_key) {
                 service
@@ -229,7 +326,8 @@ where
                 sliver_count,
                 sliver_type,
                 current_epoch,
-                key_pair: key_pair.clone(),
+                signer: signer.clone(),
+                replay_protection: self.inner.config().send_sync_shard_replay_protection,
             })
             .map_ok(Response::into_value)
             .map_err(|error| match error {
@@ -286,6 +384,9 @@ where
 
             if modify_result.is_ok() {
                 self.inner.record_epoch_change_metrics(tracker.committees());
+                self.inner.record_committee_history(tracker.committees());
+                self.inner
+                    .publish_shard_assignment_diff(tracker.committees());
             }
             modify_result.is_ok()
         };
@@ -356,25 +457,6 @@ where
 
         Ok(())
     }
-
-    async fn extend_services_from_committee(
-        &self,
-        committee: &Arc<Committee>,
-        service_factory: &mut Box<dyn NodeServiceFactory<Service = T>>,
-    ) -> Result<(), BeginCommitteeChangeError> {
-        let new_services =
-            create_services_from_committee(service_factory, committee, &self.inner.encoding_config)
-                .await
-                .map_err(BeginCommitteeChangeError::AllServicesFailed)?;
-
-        let mut services = self
-            .inner
-            .services
-            .lock()
-            .expect("thread did not panic with mutex");
-        services.extend(new_services);
-        Ok(())
-    }
 }
 
 pub(super) struct NodeCommitteeServiceInner<T> {
@@ -383,7 +465,9 @@ pub(super) struct NodeCommitteeServiceInner<T> {
     /// Services for members of the active read and write committees.
     pub services: SyncMutex<HashMap<PublicKey, T>>,
     /// Timeouts and other configuration for requests.
-    pub config: CommitteeServiceConfig,
+    ///
+    /// The settings that can be changed at runtime are replaced by [`Self::update_config`].
+    config: RwLock<Arc<CommitteeServiceConfig>>,
     /// System wide encoding parameters.
     pub encoding_config: Arc<EncodingConfig>,
     /// Shared randomness.
@@ -394,14 +478,34 @@ pub(super) struct NodeCommitteeServiceInner<T> {
     service_factory: TokioMutex<Box<dyn NodeServiceFactory<Service = T>>>,
     /// Exported metrics.
     metrics: Option<CommitteeServiceMetricSet>,
+    /// The number of responses that failed verification, by the node that sent them.
+    invalid_responses: SyncMutex<HashMap<PublicKey, u64>>,
+    /// Service used to look up the latest committees.
+    committee_lookup: Box<dyn super::CommitteeLookupService>,
+    /// Tracks request failures that indicate that the local view of the committee is stale.
+    stale_committee_detector: SyncMutex<StaleCommitteeDetector>,
+    /// Set when the local view of the committee is suspected to be stale.
+    stale_committee_suspected: AtomicBool,
+    /// The time of the last reconciliation with the latest committees.
+    ///
+    /// The lock is held for the duration of a reconciliation.
+    last_reconciliation: TokioMutex<Option<Instant>>,
+    /// Committees of recent epochs, including those no longer active, used to find earlier owners
+    /// of shards during shard sync.
+    committee_history: SyncMutex<BTreeMap<Epoch, Arc<Committee>>>,
+    /// The changes to the shards of the local node in the current epoch, which can be observed for
+    /// changes.
+    shard_assignment_diff: watch::Sender<Option<ShardAssignmentDiff>>,
 }
 
 impl<T> NodeCommitteeServiceInner<T>
 where
     T: NodeService,
 {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         committee_tracker: CommitteeTracker,
+        committee_lookup: Box<dyn super::CommitteeLookupService>,
         mut service_factory: Box<dyn NodeServiceFactory<Service = T>>,
         config: CommitteeServiceConfig,
         encoding_config: Arc<EncodingConfig>,
@@ -422,15 +526,45 @@ where
             services: SyncMutex::new(services),
             service_factory: TokioMutex::new(service_factory),
             local_identity,
-            config,
+            committee_lookup,
+            stale_committee_detector: SyncMutex::new(StaleCommitteeDetector::new(&config)),
+            stale_committee_suspected: AtomicBool::new(false),
+            last_reconciliation: TokioMutex::new(None),
+            config: RwLock::new(Arc::new(config)),
             rng: SyncMutex::new(rng),
             encoding_config,
             metrics,
+            invalid_responses: Default::default(),
+            committee_history: Default::default(),
+            shard_assignment_diff: watch::Sender::new(None),
         };
+        this.record_committee_history(this.committee_tracker.borrow().committees());
+        this.publish_shard_assignment_diff(this.committee_tracker.borrow().committees());
 
         Ok(this)
     }
 
+    /// Returns the current configuration of the service.
+    pub(super) fn config(&self) -> Arc<CommitteeServiceConfig> {
+        self.config
+            .read()
+            .expect("committee service config lock should not be poisoned")
+            .clone()
+    }
+
+    /// Applies the settings of `config` that can be changed at runtime, see
+    /// [`CommitteeServiceConfig::update_runtime_settings`]. They take effect for subsequent
+    /// requests.
+    fn update_config(&self, config: &CommitteeServiceConfig) {
+        let mut current = self
+            .config
+            .write()
+            .expect("committee service config lock should not be poisoned");
+        let mut updated = CommitteeServiceConfig::clone(&current);
+        updated.update_runtime_settings(config);
+        *current = Arc::new(updated);
+    }
+
     pub(super) fn is_local(&self, id: &PublicKey) -> bool {
         self.local_identity
             .as_ref()
@@ -438,6 +572,56 @@ where
             .unwrap_or(false)
     }
 
+    /// Returns the committee for the epoch, if it is active or recorded in the committee history.
+    pub(super) fn committee_for_epoch(&self, epoch: Epoch) -> Option<Arc<Committee>> {
+        if let Some(committee) = self
+            .committee_tracker
+            .borrow()
+            .committees()
+            .committee_for_epoch(epoch)
+        {
+            return Some(committee.clone());
+        }
+        self.committee_history
+            .lock()
+            .expect("thread did not panic with mutex")
+            .get(&epoch)
+            .cloned()
+    }
+
+    /// Records the active committees in the committee history.
+    ///
+    /// Only the committees of the most recent epochs needed to walk back
+    /// [`CommitteeServiceConfig::shard_sync_max_epochs_back`] epochs from the current or next
+    /// epoch are retained.
+    fn record_committee_history(&self, committees: &ActiveCommittees) {
+        let mut history = self
+            .committee_history
+            .lock()
+            .expect("thread did not panic with mutex");
+        for committee in committees
+            .previous_committee()
+            .into_iter()
+            .chain([committees.current_committee()])
+            .chain(committees.next_committee())
+        {
+            history.insert(committee.epoch, committee.clone());
+        }
+        self.trim_committee_history(&mut history);
+    }
+
+    /// Removes the committees of the oldest epochs from the history, such that only those of the
+    /// epochs needed to walk back [`CommitteeServiceConfig::shard_sync_max_epochs_back`] epochs
+    /// from the current or next epoch remain.
+    fn trim_committee_history(&self, history: &mut BTreeMap<Epoch, Arc<Committee>>) {
+        let max_len = usize::try_from(self.config().shard_sync_max_epochs_back.get())
+            .expect("u32 fits into usize")
+            + 2;
+        while history.len() > max_len {
+            history.pop_first();
+        }
+    }
+
     pub(super) fn get_node_service_by_id(&self, id: &PublicKey) -> Option<T> {
         self.services
             .lock()
@@ -450,6 +634,234 @@ where
         self.committee_tracker.subscribe()
     }
 
+    /// Computes the changes to the shards of the local node in the current epoch of the
+    /// committees, and publishes them to the subscribers and the metrics if they differ from the
+    /// last published changes.
+    fn publish_shard_assignment_diff(&self, committees: &ActiveCommittees) {
+        let Some(local_identity) = self.local_identity.as_ref() else {
+            return;
+        };
+        let diff = ShardAssignmentDiff::new(committees, local_identity);
+
+        if let Some(metrics) = self.metrics.as_ref() {
+            for (change, shards) in [
+                ("gained", &diff.gained),
+                ("lost", &diff.lost),
+                ("retained", &diff.retained),
+            ] {
+                walrus_utils::with_label!(metrics.shard_assignment_changes, change)
+                    .set(shards.len() as u64);
+            }
+        }
+
+        self.shard_assignment_diff.send_if_modified(|current| {
+            if current.as_ref() == Some(&diff) {
+                return false;
+            }
+            tracing::info!(
+                walrus.epoch = diff.epoch,
+                gained = ?diff.gained,
+                lost = ?diff.lost,
+                retained = diff.retained.len(),
+                "observed a new shard assignment for the local node"
+            );
+            *current = Some(diff);
+            true
+        });
+    }
+
+    /// Records the result of a request of type `request_type` sent to `node`.
+    pub(super) fn observe_response<R>(
+        &self,
+        node: &PublicKey,
+        request_type: &'static str,
+        result: &Result<R, NodeServiceError>,
+    ) {
+        let is_committee_mismatch = match result {
+            Ok(_) => false,
+            Err(error) => {
+                self.report_if_invalid_response(node, request_type, error);
+                error.is_committee_mismatch_error()
+            }
+        };
+
+        let is_stale = self
+            .stale_committee_detector
+            .lock()
+            .expect("thread did not panic with mutex")
+            .record(is_committee_mismatch);
+        if is_stale {
+            tracing::info!("suspecting that the local view of the committee is stale");
+            self.stale_committee_suspected
+                .store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Re-queries the latest committees if the local view of the committee is suspected to be
+    /// stale, and refreshes the services used to communicate with their members.
+    ///
+    /// If the latest committees are for the same epoch and the same stage of the committee change
+    /// as the tracked committees, but differ from them, the tracked committees are replaced. The
+    /// transition to a new epoch is not performed here, as it is driven by the node's processing
+    /// of epoch-change events.
+    pub(super) async fn reconcile_if_committee_stale(&self) {
+        if !self.stale_committee_suspected.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(mut last_reconciliation) = self.last_reconciliation.try_lock() else {
+            tracing::debug!("reconciliation with the latest committees already in progress");
+            return;
+        };
+        if last_reconciliation.is_some_and(|last| {
+            last.elapsed() < self.config().stale_committee_min_reconciliation_interval
+        }) {
+            // The suspicion is kept, such that the committees are refreshed once the interval has
+            // passed.
+            tracing::debug!("skipping reconciliation as the committees were recently refreshed");
+            return;
+        }
+        // The flag is only cleared while holding the lock, such that a suspicion raised while
+        // another reconciliation is in progress is not lost.
+        if !self
+            .stale_committee_suspected
+            .swap(false, Ordering::Relaxed)
+        {
+            return;
+        }
+        *last_reconciliation = Some(Instant::now());
+
+        let latest = match self.committee_lookup.get_active_committees().await {
+            Ok(latest) => latest,
+            Err(error) => {
+                tracing::warn!(?error, "failed to look up the latest committees");
+                return;
+            }
+        };
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.stale_committee_reconciliations_total.inc();
+        }
+        self.record_committee_history(&latest);
+
+        self.committee_tracker.send_if_modified(|tracker| {
+            let local = tracker.committees();
+            if latest.epoch() > local.epoch() {
+                tracing::warn!(
+                    local_epoch = local.epoch(),
+                    latest_epoch = latest.epoch(),
+                    "the local view of the committee is behind the latest committee"
+                );
+                return false;
+            }
+            if latest.epoch() != local.epoch()
+                || latest.is_change_in_progress() != local.is_change_in_progress()
+                || latest == *local
+            {
+                return false;
+            }
+            tracing::info!(
+                walrus.epoch = latest.epoch(),
+                "replacing the tracked committees with the latest committees of the same epoch"
+            );
+            tracker.update_active_committees(latest.clone());
+            self.publish_shard_assignment_diff(tracker.committees());
+            true
+        });
+
+        let mut service_factory = self.service_factory.lock().await;
+        for committee in latest
+            .previous_committee()
+            .into_iter()
+            .chain([latest.current_committee()])
+        {
+            if let Err(error) = self
+                .extend_services_from_committee(committee, &mut service_factory)
+                .await
+            {
+                tracing::warn!(
+                    ?error,
+                    walrus.epoch = committee.epoch,
+                    "failed to refresh services for the committee"
+                );
+            }
+        }
+    }
+
+    async fn extend_services_from_committee(
+        &self,
+        committee: &Arc<Committee>,
+        service_factory: &mut Box<dyn NodeServiceFactory<Service = T>>,
+    ) -> Result<(), BeginCommitteeChangeError> {
+        let new_services =
+            create_services_from_committee(service_factory, committee, &self.encoding_config)
+                .await
+                .map_err(BeginCommitteeChangeError::AllServicesFailed)?;
+
+        let mut services = self
+            .services
+            .lock()
+            .expect("thread did not panic with mutex");
+        services.extend(new_services);
+        Ok(())
+    }
+
+    /// Records that an invalid blob certificate was created after collecting attestations for
+    /// `duration`.
+    pub(super) fn record_invalid_blob_certificate(&self, duration: Duration) {
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.invalid_blob_certificates_total.inc();
+            metrics
+                .invalid_blob_certificate_duration_seconds
+                .observe(duration.as_secs_f64());
+        }
+    }
+
+    /// Records that `node` sent a response to a request of type `request_type` that failed
+    /// verification.
+    ///
+    /// Errors that do not indicate a faulty response, such as network errors, are ignored.
+    fn report_if_invalid_response(
+        &self,
+        node: &PublicKey,
+        request_type: &'static str,
+        error: &NodeServiceError,
+    ) {
+        if !error.is_verification_error() {
+            return;
+        }
+        tracing::warn!(
+            walrus.node.public_key = %node,
+            request_type,
+            %error,
+            "committee member sent a response that failed verification"
+        );
+
+        *self
+            .invalid_responses
+            .lock()
+            .expect("thread did not panic with mutex")
+            .entry(node.clone())
+            .or_default() += 1;
+
+        if let Some(metrics) = self.metrics.as_ref() {
+            walrus_utils::with_label!(
+                metrics.invalid_responses_total,
+                node.to_string(),
+                request_type
+            )
+            .inc();
+        }
+    }
+
+    /// Returns the number of responses from `node` that have failed verification.
+    pub(super) fn invalid_response_count(&self, node: &PublicKey) -> u64 {
+        self.invalid_responses
+            .lock()
+            .expect("thread did not panic with mutex")
+            .get(node)
+            .copied()
+            .unwrap_or_default()
+    }
+
     fn record_epoch_change_metrics(&self, committees: &ActiveCommittees) {
         let Some(metrics) = self.metrics.as_ref() else {
             return;
@@ -517,7 +929,7 @@ where
         sliver_type: SliverType,
         certified_epoch: Epoch,
     ) -> Result<Sliver, InconsistencyProofEnum<MerkleProof>> {
-        if self.inner.config.experimental_batch_symbol_recovery {
+        if self.inner.config().experimental_batch_symbol_recovery {
             RecoverSliver::new(
                 metadata,
                 sliver_id,
@@ -560,7 +972,7 @@ where
         sliver_type: SliverType,
         sliver_count: u64,
         epoch: Epoch,
-        key_pair: &ProtocolKeyPair,
+        signer: &Arc<dyn ProtocolSigner>,
     ) -> Result<Vec<(BlobId, Sliver)>, SyncShardClientError> {
         self.sync_shard_as_of_epoch(
             shard,
@@ -568,11 +980,30 @@ where
             sliver_count,
             sliver_type,
             epoch,
-            key_pair,
+            signer,
         )
         .await
     }
 
+    #[tracing::instrument(name = "push_metadata committee", skip_all)]
+    async fn push_metadata(
+        &self,
+        public_key: &PublicKey,
+        metadata: Arc<Vec<VerifiedBlobMetadataWithId>>,
+    ) -> Result<u64, anyhow::Error> {
+        let service = self
+            .inner
+            .get_node_service_by_id(public_key)
+            .ok_or_else(|| anyhow::anyhow!("no service is available for node {public_key}"))?;
+
+        let n_stored = service
+            .oneshot(Request::PushMetadata(metadata))
+            .map_ok(Response::into_value)
+            .await?;
+
+        Ok(n_stored)
+    }
+
     fn is_walrus_storage_node(&self, public_key: &PublicKey) -> bool {
         let committee_tracker = self.inner.committee_tracker.borrow();
 
@@ -592,6 +1023,74 @@ where
                 .unwrap_or(false)
     }
 
+    fn peer_health(&self) -> Vec<PeerHealth> {
+        let committee = self
+            .inner
+            .committee_tracker
+            .borrow()
+            .committees()
+            .current_committee()
+            .clone();
+        let services = self
+            .inner
+            .services
+            .lock()
+            .expect("thread did not panic with mutex");
+
+        committee
+            .members()
+            .iter()
+            .map(|member| PeerHealth {
+                name: member.name.clone(),
+                public_key: member.public_key.clone(),
+                network_address: member.network_address.to_string(),
+                n_shards: member.shard_ids.len(),
+                is_local: self.inner.is_local(&member.public_key),
+                connected: services.contains_key(&member.public_key),
+                invalid_responses: self.inner.invalid_response_count(&member.public_key),
+            })
+            .collect()
+    }
+
+    fn shard_assignment_diff(&self) -> Option<ShardAssignmentDiff> {
+        self.inner.shard_assignment_diff.borrow().clone()
+    }
+
+    fn subscribe_to_shard_assignment_changes(
+        &self,
+    ) -> watch::Receiver<Option<ShardAssignmentDiff>> {
+        self.inner.shard_assignment_diff.subscribe()
+    }
+
+    fn committee_history(&self) -> Vec<Arc<Committee>> {
+        self.inner
+            .committee_history
+            .lock()
+            .expect("thread did not panic with mutex")
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    fn restore_committee_history(&self, committees: Vec<Committee>) {
+        let mut history = self
+            .inner
+            .committee_history
+            .lock()
+            .expect("thread did not panic with mutex");
+        for committee in committees {
+            // Committees observed by the service take precedence over the restored ones.
+            history
+                .entry(committee.epoch)
+                .or_insert_with(|| Arc::new(committee));
+        }
+        self.inner.trim_committee_history(&mut history);
+    }
+
+    fn update_config(&self, config: &CommitteeServiceConfig) {
+        self.inner.update_config(config);
+    }
+
     async fn begin_committee_change(
         &self,
         new_epoch: Epoch,
@@ -614,6 +1113,7 @@ where
         debug_assert_eq!(new_epoch, expected_next_epoch);
 
         let latest = self
+            .inner
             .committee_lookup
             .get_active_committees()
             .await
@@ -637,6 +1137,7 @@ where
 
     async fn sync_committee_members(&self) -> Result<(), anyhow::Error> {
         let latest = self
+            .inner
             .committee_lookup
             .get_active_committees()
             .await
@@ -645,11 +1146,13 @@ where
         let mut service_factory = self.inner.service_factory.lock().await;
 
         if let Some(previous_committee) = latest.previous_committee() {
-            self.extend_services_from_committee(previous_committee, &mut service_factory)
+            self.inner
+                .extend_services_from_committee(previous_committee, &mut service_factory)
                 .await?;
         }
 
-        self.extend_services_from_committee(latest.current_committee(), &mut service_factory)
+        self.inner
+            .extend_services_from_committee(latest.current_committee(), &mut service_factory)
             .await?;
 
         Ok(())
@@ -659,6 +1162,7 @@ where
         &self,
     ) -> Result<(), BeginCommitteeChangeError> {
         let latest = self
+            .inner
             .committee_lookup
             .get_active_committees()
             .await
@@ -673,15 +1177,20 @@ where
             .clear();
 
         if let Some(previous_committee) = latest.previous_committee() {
-            self.extend_services_from_committee(previous_committee, &mut service_factory)
+            self.inner
+                .extend_services_from_committee(previous_committee, &mut service_factory)
                 .await?;
         }
 
-        self.extend_services_from_committee(latest.current_committee(), &mut service_factory)
+        self.inner
+            .extend_services_from_committee(latest.current_committee(), &mut service_factory)
             .await?;
 
+        self.inner.record_committee_history(&latest);
         self.inner.committee_tracker.send_modify(|tracker| {
             tracker.update_active_committees(latest);
+            self.inner
+                .publish_shard_assignment_diff(tracker.committees());
         });
 
         Ok(())
@@ -694,6 +1203,7 @@ impl<T> std::fmt::Debug for NodeCommitteeServiceInner<T> {
             .field("committee_tracker", &self.committee_tracker)
             .field("config", &self.config)
             .field("local_identity", &self.local_identity)
+            .field("committee_lookup", &self.committee_lookup)
             .field(
                 "encoding_config.n_shards",
                 &self.encoding_config.n_shards().get(),
@@ -706,11 +1216,57 @@ impl<T> std::fmt::Debug for NodeCommitteeService<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("NodeCommitteeService")
             .field("inner", &self.inner)
-            .field("committee_lookup", &self.committee_lookup)
             .finish_non_exhaustive()
     }
 }
 
+/// Detects when a large fraction of requests fail with errors indicating that the local view of
+/// the committee is stale, such as authentication or epoch errors.
+#[derive(Debug)]
+struct StaleCommitteeDetector {
+    /// The number of requests over which the fraction of failures is evaluated.
+    min_requests: usize,
+    /// The percentage of failed requests at which the committee is suspected to be stale.
+    failure_percent: u8,
+    n_requests: usize,
+    n_failures: usize,
+}
+
+impl StaleCommitteeDetector {
+    fn new(config: &CommitteeServiceConfig) -> Self {
+        Self {
+            min_requests: config.stale_committee_min_requests.get(),
+            failure_percent: config.stale_committee_failure_percent,
+            n_requests: 0,
+            n_failures: 0,
+        }
+    }
+
+    /// Records the outcome of a request, and returns true if the committee is suspected to be
+    /// stale.
+    ///
+    /// Outcomes are evaluated in windows of `min_requests` requests, after which the counts are
+    /// reset.
+    fn record(&mut self, is_committee_mismatch: bool) -> bool {
+        if self.failure_percent == 0 {
+            return false;
+        }
+
+        self.n_requests += 1;
+        if is_committee_mismatch {
+            self.n_failures += 1;
+        }
+        if self.n_requests < self.min_requests {
+            return false;
+        }
+
+        let is_stale = self.n_failures * 100 >= usize::from(self.failure_percent) * self.n_requests;
+        self.n_requests = 0;
+        self.n_failures = 0;
+        is_stale
+    }
+}
+
 /// Create services for each member of the committee.
 async fn create_services_from_committee<T: NodeService>(
     service_factory: &mut Box<dyn NodeServiceFactory<Service = T>>,
@@ -0,0 +1,398 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [`NodeServiceFactory`] that injects faults into the services it creates.
+//!
+//! The [`FaultInjectingServiceFactory`] wraps another factory and allows tests to drop, delay,
+//! corrupt, or duplicate the responses of individual storage nodes, optionally only for specific
+//! types of requests. Faults are configured through a [`FaultPlan`], which is shared with all
+//! services created by the factory, such that faults can be changed while requests are ongoing.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, FutureExt as _};
+use tower::{Service, ServiceExt as _};
+use walrus_core::{encoding::EncodingConfig, PublicKey};
+use walrus_sdk::error::{ClientBuildError, NodeError};
+use walrus_sui::types::StorageNode as SuiStorageNode;
+
+use super::{
+    node_service::{NodeService, NodeServiceError, Request, Response},
+    NodeServiceFactory,
+};
+
+/// The types of [`Request`]s, used to restrict faults to specific requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum RequestKind {
+    GetVerifiedMetadata,
+    GetVerifiedRecoverySymbol,
+    SubmitProofForInvalidBlobAttestation,
+    SyncShardAsOfEpoch,
+    ListVerifiedRecoverySymbols,
+    PushMetadata,
+}
+
+impl From<&Request> for RequestKind {
+    fn from(request: &Request) -> Self {
+        match request {
+            Request::GetVerifiedMetadata(_) => Self::GetVerifiedMetadata,
+            Request::GetVerifiedRecoverySymbol { .. } => Self::GetVerifiedRecoverySymbol,
+            Request::SubmitProofForInvalidBlobAttestation { .. } => {
+                Self::SubmitProofForInvalidBlobAttestation
+            }
+            Request::SyncShardAsOfEpoch { .. } => Self::SyncShardAsOfEpoch,
+            Request::ListVerifiedRecoverySymbols { .. } => Self::ListVerifiedRecoverySymbols,
+            Request::PushMetadata(_) => Self::PushMetadata,
+        }
+    }
+}
+
+/// A fault injected into the handling of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Fault {
+    /// The request is never answered.
+    Drop,
+    /// The response is returned after the specified delay.
+    Delay(Duration),
+    /// The request is sent to the node, but the response fails verification.
+    Corrupt,
+    /// The request is sent to the node twice, and the response to the first request is returned.
+    Duplicate,
+}
+
+/// A fault along with the number of requests to which it still applies.
+#[derive(Debug, Clone, Copy)]
+struct FaultEntry {
+    fault: Fault,
+    /// The remaining number of requests affected by the fault, or `None` if unlimited.
+    remaining: Option<usize>,
+}
+
+/// The faults injected into the services of a [`FaultInjectingServiceFactory`].
+///
+/// Faults are specified per target node and, optionally, per [`RequestKind`]. A fault for a
+/// specific request kind takes precedence over a fault for all requests to the same node. Clones
+/// of the plan share the same faults.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FaultPlan {
+    faults: Arc<Mutex<HashMap<(PublicKey, Option<RequestKind>), FaultEntry>>>,
+}
+
+impl FaultPlan {
+    /// Injects the fault into all requests of the specified kind to the target node, or all
+    /// requests to the node if `kind` is `None`.
+    pub fn inject(&self, target: PublicKey, kind: Option<RequestKind>, fault: Fault) {
+        self.insert(target, kind, fault, None);
+    }
+
+    /// Injects the fault into the next `count` requests of the specified kind to the target node,
+    /// or all requests to the node if `kind` is `None`.
+    pub fn inject_n(
+        &self,
+        target: PublicKey,
+        kind: Option<RequestKind>,
+        fault: Fault,
+        count: usize,
+    ) {
+        self.insert(target, kind, fault, Some(count));
+    }
+
+    /// Removes all faults injected into requests to the target node.
+    pub fn clear_node(&self, target: &PublicKey) {
+        self.faults
+            .lock()
+            .unwrap()
+            .retain(|(key, _), _| key != target);
+    }
+
+    /// Removes all faults.
+    pub fn clear(&self) {
+        self.faults.lock().unwrap().clear();
+    }
+
+    fn insert(
+        &self,
+        target: PublicKey,
+        kind: Option<RequestKind>,
+        fault: Fault,
+        remaining: Option<usize>,
+    ) {
+        self.faults
+            .lock()
+            .unwrap()
+            .insert((target, kind), FaultEntry { fault, remaining });
+    }
+
+    /// Returns the fault to inject into the request, and consumes it if it is limited.
+    fn take(&self, target: &PublicKey, kind: RequestKind) -> Option<Fault> {
+        let mut faults = self.faults.lock().unwrap();
+        let key = [Some(kind), None]
+            .into_iter()
+            .map(|kind| (target.clone(), kind))
+            .find(|key| faults.contains_key(key))?;
+
+        let entry = faults.get_mut(&key).expect("the key was found above");
+        let fault = entry.fault;
+        if let Some(remaining) = entry.remaining.as_mut() {
+            *remaining -= 1;
+            if *remaining == 0 {
+                faults.remove(&key);
+            }
+        }
+        Some(fault)
+    }
+}
+
+/// A [`NodeServiceFactory`] that wraps the services of another factory in a
+/// [`FaultInjectingService`].
+#[derive(Debug)]
+pub(crate) struct FaultInjectingServiceFactory<F> {
+    inner: F,
+    plan: FaultPlan,
+}
+
+impl<F> FaultInjectingServiceFactory<F> {
+    /// Creates a new factory wrapping `inner`, which initially does not inject any faults.
+    pub fn new(inner: F) -> Self {
+        Self::with_plan(inner, FaultPlan::default())
+    }
+
+    /// Creates a new factory wrapping `inner`, which injects the faults in the provided plan.
+    pub fn with_plan(inner: F, plan: FaultPlan) -> Self {
+        Self { inner, plan }
+    }
+
+    /// Returns the plan of the faults injected by the factory's services.
+    pub fn plan(&self) -> FaultPlan {
+        self.plan.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl<F> NodeServiceFactory for FaultInjectingServiceFactory<F>
+where
+    F: NodeServiceFactory,
+    F::Service: 'static,
+    <F::Service as Service<Request>>::Future: 'static,
+{
+    type Service = FaultInjectingService<F::Service>;
+
+    async fn make_service(
+        &mut self,
+        info: &SuiStorageNode,
+        encoding_config: &Arc<EncodingConfig>,
+    ) -> Result<Self::Service, ClientBuildError> {
+        let inner = self.inner.make_service(info, encoding_config).await?;
+        Ok(FaultInjectingService {
+            inner,
+            target: info.public_key.clone(),
+            plan: self.plan.clone(),
+        })
+    }
+
+    fn connect_timeout(&mut self, timeout: Duration) {
+        self.inner.connect_timeout(timeout);
+    }
+
+    fn max_response_size(&mut self, max_size: u64) {
+        self.inner.max_response_size(max_size);
+    }
+}
+
+/// A [`NodeService`] that injects the faults of a [`FaultPlan`] into the requests to its target
+/// node.
+#[derive(Debug, Clone)]
+pub(crate) struct FaultInjectingService<S> {
+    inner: S,
+    target: PublicKey,
+    plan: FaultPlan,
+}
+
+impl<S> Service<Request> for FaultInjectingService<S>
+where
+    S: NodeService + 'static,
+    S::Future: 'static,
+{
+    type Response = Response;
+    type Error = NodeServiceError;
+    type Future = BoxFuture<'static, Result<Response, NodeServiceError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let Some(fault) = self.plan.take(&self.target, RequestKind::from(&request)) else {
+            return self.inner.call(request).boxed();
+        };
+        tracing::trace!(?fault, target = %self.target, "injecting fault into request");
+
+        match fault {
+            Fault::Drop => std::future::pending().boxed(),
+            Fault::Delay(delay) => {
+                let response = self.inner.call(request);
+                async move {
+                    tokio::time::sleep(delay).await;
+                    response.await
+                }
+                .boxed()
+            }
+            Fault::Corrupt => {
+                let response = self.inner.call(request);
+                async move {
+                    response.await?;
+                    Err(NodeServiceError::Node(NodeError::verification(
+                        std::io::Error::other("injected fault: corrupted response"),
+                    )))
+                }
+                .boxed()
+            }
+            Fault::Duplicate => {
+                let first = self.inner.call(request.clone());
+                let mut inner = self.inner.clone();
+                async move {
+                    let second = async move { inner.ready().await?.call(request).await };
+                    let (first, _) = futures::join!(first, second);
+                    first
+                }
+                .boxed()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::time;
+    use tower::util::BoxCloneService;
+    use walrus_core::{keys::ProtocolKeyPair, BlobId};
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    type TestService = FaultInjectingService<BoxCloneService<Request, Response, NodeServiceError>>;
+
+    /// Returns a service for a random target that responds to metadata requests, along with the
+    /// number of requests received by the underlying service.
+    fn counting_service() -> (TestService, PublicKey, Arc<AtomicUsize>) {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let inner_counter = counter.clone();
+        let inner = tower::service_fn(move |_request| {
+            inner_counter.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(Ok(Response::VerifiedMetadata(
+                walrus_core::test_utils::verified_blob_metadata(),
+            )))
+        })
+        .boxed_clone();
+        let target = ProtocolKeyPair::generate().public().clone();
+
+        let service = FaultInjectingService {
+            inner,
+            target: target.clone(),
+            plan: FaultPlan::default(),
+        };
+        (service, target, counter)
+    }
+
+    fn metadata_request() -> Request {
+        Request::GetVerifiedMetadata(BlobId([7; 32]))
+    }
+
+    async fn send(service: &mut TestService) -> Result<Response, NodeServiceError> {
+        service.ready().await?.call(metadata_request()).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn forwards_requests_without_faults() -> TestResult {
+        let (mut service, _, counter) = counting_service();
+
+        assert!(matches!(
+            send(&mut service).await?,
+            Response::VerifiedMetadata(_)
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn drops_and_delays_responses() -> TestResult {
+        let (mut service, target, _) = counting_service();
+        let plan = service.plan.clone();
+
+        plan.inject(target.clone(), None, Fault::Drop);
+        time::timeout(Duration::from_secs(60), send(&mut service))
+            .await
+            .expect_err("dropped requests must not complete");
+
+        plan.inject(
+            target,
+            Some(RequestKind::GetVerifiedMetadata),
+            Fault::Delay(Duration::from_secs(10)),
+        );
+        time::timeout(Duration::from_secs(5), send(&mut service))
+            .await
+            .expect_err("delayed requests must not complete before the delay");
+        time::timeout(Duration::from_secs(11), send(&mut service)).await??;
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn corrupts_limited_number_of_responses() -> TestResult {
+        let (mut service, target, counter) = counting_service();
+        service.plan.inject_n(target, None, Fault::Corrupt, 2);
+
+        for _ in 0..2 {
+            let error = send(&mut service)
+                .await
+                .expect_err("response must be corrupted");
+            assert!(error.is_verification_error());
+        }
+        send(&mut service).await?;
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn duplicates_requests() -> TestResult {
+        let (mut service, target, counter) = counting_service();
+        service.plan.inject(target, None, Fault::Duplicate);
+
+        send(&mut service).await?;
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn faults_only_apply_to_their_target_and_request_kind() -> TestResult {
+        let (mut service, target, _) = counting_service();
+        let plan = service.plan.clone();
+        let other = ProtocolKeyPair::generate().public().clone();
+
+        plan.inject(other.clone(), None, Fault::Corrupt);
+        plan.inject(
+            target.clone(),
+            Some(RequestKind::ListVerifiedRecoverySymbols),
+            Fault::Corrupt,
+        );
+        send(&mut service).await?;
+
+        plan.clear_node(&other);
+        plan.inject(
+            target,
+            Some(RequestKind::GetVerifiedMetadata),
+            Fault::Corrupt,
+        );
+        assert!(send(&mut service).await.is_err());
+
+        plan.clear();
+        send(&mut service).await?;
+        Ok(())
+    }
+}
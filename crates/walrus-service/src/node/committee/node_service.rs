@@ -28,8 +28,7 @@ use prometheus::Registry;
 use tower::Service;
 use walrus_core::{
     encoding::{EncodingConfig, GeneralRecoverySymbol, Primary, Secondary},
-    keys::ProtocolKeyPair,
-    messages::InvalidBlobIdAttestation,
+    messages::{InvalidBlobIdAttestation, SyncShardMsg, SyncShardRequest},
     metadata::VerifiedBlobMetadataWithId,
     BlobId,
     Epoch,
@@ -43,11 +42,12 @@ use walrus_core::{
 };
 use walrus_sdk::{
     client::{Client, RecoverySymbolsFilter},
-    error::{ClientBuildError, NodeError},
+    error::{ClientBuildError, NodeError, ServiceError},
 };
 use walrus_sui::types::StorageNode as SuiStorageNode;
 
 use super::{DefaultRecoverySymbol, NodeServiceFactory};
+use crate::node::ProtocolSigner;
 
 /// Requests used with a [`NodeService`].
 #[derive(Debug, Clone)]
@@ -71,7 +71,9 @@ pub(crate) enum Request {
         sliver_count: u64,
         sliver_type: SliverType,
         current_epoch: Epoch,
-        key_pair: ProtocolKeyPair,
+        signer: Arc<dyn ProtocolSigner>,
+        /// Whether to send the request with a nonce and an expiry.
+        replay_protection: bool,
     },
     ListVerifiedRecoverySymbols {
         filter: RecoverySymbolsFilter,
@@ -79,6 +81,7 @@ pub(crate) enum Request {
         target_index: SliverIndex,
         target_type: SliverType,
     },
+    PushMetadata(Arc<Vec<VerifiedBlobMetadataWithId>>),
 }
 
 /// Responses to [`Request`]s sent to a node service.
@@ -92,6 +95,7 @@ pub(crate) enum Response {
     InvalidBlobAttestation(InvalidBlobIdAttestation),
     ShardSlivers(Vec<(BlobId, Sliver)>),
     VerifiedRecoverySymbols(Vec<GeneralRecoverySymbol>),
+    MetadataPushed(u64),
 }
 
 impl Response {
@@ -143,6 +147,7 @@ impl_response_conversion!(
 );
 impl_response_conversion!(InvalidBlobIdAttestation, Response::InvalidBlobAttestation);
 impl_response_conversion!(Vec<(BlobId, Sliver)>, Response::ShardSlivers);
+impl_response_conversion!(u64, Response::MetadataPushed);
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum NodeServiceError {
@@ -153,6 +158,30 @@ pub(crate) enum NodeServiceError {
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl NodeServiceError {
+    /// Returns true if the node responded, but its response failed verification or exceeded the
+    /// maximum response size.
+    pub fn is_verification_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Node(error) if error.is_verification_error() || error.is_response_too_large()
+        )
+    }
+
+    /// Returns true if the node rejected the request due to its authentication or epoch, which
+    /// indicates that the sender's view of the committee differs from that of the node.
+    pub fn is_committee_mismatch_error(&self) -> bool {
+        let Self::Node(error) = self else {
+            return false;
+        };
+        error.is_unauthorized()
+            || matches!(
+                error.service_error(),
+                Some(ServiceError::InvalidEpoch { .. } | ServiceError::RequestUnauthorized)
+            )
+    }
+}
+
 /// Marker trait for types implementing the [`tower::Service`] signature expected of services
 /// used for communication with the committee.
 pub(crate) trait NodeService
@@ -246,30 +275,29 @@ impl Service<Request> for RemoteStorageNode {
                     sliver_count,
                     sliver_type,
                     current_epoch,
-                    key_pair,
+                    signer,
+                    replay_protection,
                 } => {
-                    let result = if sliver_type == SliverType::Primary {
-                        client
-                            .sync_shard::<Primary>(
-                                shard,
-                                starting_blob_id,
-                                sliver_count,
-                                current_epoch,
-                                &key_pair,
-                            )
-                            .await
+                    let new_request = if replay_protection {
+                        SyncShardRequest::new_with_replay_protection
                     } else {
-                        client
-                            .sync_shard::<Secondary>(
-                                shard,
-                                starting_blob_id,
-                                sliver_count,
-                                current_epoch,
-                                &key_pair,
-                            )
-                            .await
+                        SyncShardRequest::new
                     };
-                    result.map(|value| Response::ShardSlivers(value.into()))?
+                    let request = new_request(
+                        shard,
+                        sliver_type,
+                        starting_blob_id,
+                        sliver_count,
+                        current_epoch,
+                    );
+                    let signed_request = signer
+                        .sign_message(&SyncShardMsg::new(current_epoch, request))
+                        .await
+                        .map_err(|error| NodeServiceError::Other(error.into()))?;
+                    client
+                        .sync_shard_with_signed_request(&signed_request, signer.public_key())
+                        .await
+                        .map(|value| Response::ShardSlivers(value.into()))?
                 }
 
                 Request::ListVerifiedRecoverySymbols {
@@ -287,6 +315,11 @@ impl Service<Request> for RemoteStorageNode {
                     )
                     .await
                     .map(Response::VerifiedRecoverySymbols)?,
+
+                Request::PushMetadata(metadata) => client
+                    .push_metadata(&metadata)
+                    .await
+                    .map(Response::MetadataPushed)?,
             };
             Ok(response)
         }
@@ -317,6 +350,9 @@ pub(crate) struct DefaultNodeServiceFactory {
     /// The timeout to configure when connecting to remote nodes.
     pub connect_timeout: Option<Duration>,
 
+    /// The maximum size of a response body read from remote nodes.
+    pub max_response_size: Option<u64>,
+
     /// The registry to use for registering node metrics.
     pub registry: Option<Registry>,
 }
@@ -335,6 +371,11 @@ impl DefaultNodeServiceFactory {
         self.connect_timeout = Some(timeout);
     }
 
+    /// Sets the maximum size of responses read from nodes, for all subsequently created nodes.
+    pub fn max_response_size(&mut self, max_size: u64) {
+        self.max_response_size = Some(max_size);
+    }
+
     /// Skips the use of proxies or the loading of native certificates, as these require interacting
     /// with the operating system and can significantly slow down the construction of new instances.
     pub fn avoid_system_services() -> Self {
@@ -367,6 +408,9 @@ impl NodeServiceFactory for DefaultNodeServiceFactory {
         if let Some(timeout) = self.connect_timeout.as_ref() {
             builder = builder.connect_timeout(*timeout);
         }
+        if let Some(max_size) = self.max_response_size {
+            builder = builder.max_response_size(max_size);
+        }
         if let Some(registry) = self.registry.as_ref() {
             builder = builder.metric_registry(registry.clone());
         }
@@ -382,4 +426,8 @@ impl NodeServiceFactory for DefaultNodeServiceFactory {
     fn connect_timeout(&mut self, timeout: Duration) {
         self.connect_timeout(timeout);
     }
+
+    fn max_response_size(&mut self, max_size: u64) {
+        self.max_response_size(max_size);
+    }
 }
@@ -16,12 +16,11 @@ use futures::{future::BoxFuture, stream::FuturesUnordered, Stream as _, TryFutur
 use rand::{rngs::StdRng, seq::SliceRandom as _};
 use tokio::{
     sync::watch,
-    time::{self, error::Elapsed},
+    time::{self, error::Elapsed, Instant},
 };
 use tower::ServiceExt as _;
 use tracing::Instrument as _;
 use walrus_core::{
-    bft,
     encoding::{
         self,
         EncodingAxis,
@@ -78,8 +77,8 @@ where
             blob_id,
             epoch_certified,
             backoff: ExponentialBackoffState::new_infinite(
-                shared.config.retry_interval_min,
-                shared.config.retry_interval_max,
+                shared.config().retry_interval_min,
+                shared.config().retry_interval_max,
             ),
             shared,
         }
@@ -89,23 +88,27 @@ where
         let mut committee_listener = self.shared.subscribe_to_committee_changes();
 
         loop {
-            let (n_members, weak_committee) = {
+            let (weak_committee, fallback_committee) = {
                 let committee_tracker = committee_listener.borrow_and_update();
                 let committee = committee_tracker
                     .committees()
                     .read_committee(self.epoch_certified)
                     .expect("epoch must not be in the future");
 
-                (committee.n_members(), Arc::downgrade(committee))
+                (
+                    Arc::downgrade(committee),
+                    fallback_read_committee(&committee_tracker, self.epoch_certified, self.shared),
+                )
             };
 
             // Check for the completed future or a notification that the committee has
             // changed. Only some changes to the committee will necessitate new requests.
             tokio::select! {
-                maybe_metadata = self.run_once(&weak_committee, n_members) => {
+                maybe_metadata = self.run_with_fallback(&weak_committee, &fallback_committee) => {
                     if let Some(metadata) = maybe_metadata {
                         return metadata;
                     }
+                    self.shared.reconcile_if_committee_stale().await;
                     wait_before_next_attempts(&mut self.backoff, &self.shared.rng).await;
                 }
                 () = wait_for_read_committee_change(
@@ -120,21 +123,48 @@ where
         }
     }
 
+    /// Requests the metadata from the read committee and, if unsuccessful, from the fallback
+    /// committee.
+    async fn run_with_fallback(
+        &self,
+        weak_committee: &Weak<Committee>,
+        fallback_committee: &Option<Weak<Committee>>,
+    ) -> Option<VerifiedBlobMetadataWithId> {
+        if let Some(metadata) = self.run_once(weak_committee).await {
+            return Some(metadata);
+        }
+        let fallback_committee = fallback_committee.as_ref()?;
+        tracing::debug!("falling back to the previous committee to request the metadata");
+        self.run_once(fallback_committee).await
+    }
+
     async fn run_once(
         &self,
         weak_committee: &Weak<Committee>,
-        n_committee_members: usize,
     ) -> Option<VerifiedBlobMetadataWithId> {
-        let n_requests = self.shared.config.max_concurrent_metadata_requests.get();
+        let Some(n_committee_members) = weak_committee.upgrade().map(|c| c.n_members()) else {
+            tracing::trace!("committee has been dropped, skipping requests");
+            return None;
+        };
+        let n_requests = self.shared.config().max_concurrent_metadata_requests.get();
 
-        let node_order = {
+        let mut node_order = {
             let mut rng_guard = self
                 .shared
                 .rng
                 .lock()
                 .expect("thread must not panic with lock");
             rand::seq::index::sample(&mut *rng_guard, n_committee_members, n_committee_members)
+                .into_vec()
         };
+        // Query nodes that have previously sent invalid responses last. The sort is stable, so the
+        // random order is preserved amongst nodes with equal counts.
+        if let Some(committee) = weak_committee.upgrade() {
+            node_order.sort_by_cached_key(|index| {
+                self.shared
+                    .invalid_response_count(&committee.members()[*index].public_key)
+            });
+        }
 
         let requests = node_order.into_iter().filter_map(|index| {
             let Some(committee) = weak_committee.upgrade() else {
@@ -155,13 +185,20 @@ where
                 return None;
             };
 
-            let request = async move {
-                client
-                    .oneshot(Request::GetVerifiedMetadata(self.blob_id))
-                    .map_ok(Response::into_value)
-                    .await
+            let request = {
+                let node_public_key = node_public_key.clone();
+                async move {
+                    client
+                        .oneshot(Request::GetVerifiedMetadata(self.blob_id))
+                        .map_ok(Response::into_value)
+                        .inspect(|result| {
+                            self.shared
+                                .observe_response(&node_public_key, "get_metadata", result)
+                        })
+                        .await
+                }
             };
-            let request = time::timeout(self.shared.config.metadata_request_timeout, request)
+            let request = time::timeout(self.shared.config().metadata_request_timeout, request)
                 .map(log_and_discard_timeout_or_error)
                 .instrument(tracing::info_span!(
                     "get_and_verify_metadata node", walrus.node.public_key = %node_public_key
@@ -204,8 +241,8 @@ where
             sliver_type,
             epoch_certified,
             backoff: ExponentialBackoffState::new_infinite(
-                shared.config.retry_interval_min,
-                shared.config.retry_interval_max,
+                shared.config().retry_interval_min,
+                shared.config().retry_interval_max,
             ),
             shared,
         }
@@ -236,20 +273,25 @@ where
             Default::default();
 
         loop {
-            let weak_committee = {
+            let (weak_committee, fallback_committee) = {
                 let committee_tracker = committee_listener.borrow_and_update();
-                Arc::downgrade(
-                    committee_tracker
-                        .committees()
-                        .read_committee(self.epoch_certified)
-                        .expect("epoch must not be in the future"),
+                let committee = committee_tracker
+                    .committees()
+                    .read_committee(self.epoch_certified)
+                    .expect("epoch must not be in the future");
+                (
+                    Arc::downgrade(committee),
+                    fallback_read_committee(&committee_tracker, self.epoch_certified, self.shared),
                 )
             };
 
             let epoch_certified = self.epoch_certified;
             tokio::select! {
-                result = self.collect_recovery_symbols(
-                    &mut collected_symbols, additional_symbols, &weak_committee
+                result = self.collect_recovery_symbols_with_fallback(
+                    &mut collected_symbols,
+                    additional_symbols,
+                    &weak_committee,
+                    &fallback_committee,
                 ) => {
                     match result {
                         Ok(n_symbols) => {
@@ -264,6 +306,7 @@ where
                                 %n_symbols_remaining,
                                 "failed to collect sufficient recovery symbols"
                             );
+                            self.shared.reconcile_if_committee_stale().await;
                             wait_before_next_attempts(&mut self.backoff, &self.shared.rng).await;
                         }
                     }
@@ -291,6 +334,34 @@ where
         usize::from(min_symbols_for_recovery(self.metadata.n_shards())) + additional_symbols
     }
 
+    /// Collects recovery symbols from the read committee and, if unsuccessful, from the fallback
+    /// committee.
+    async fn collect_recovery_symbols_with_fallback(
+        &mut self,
+        collected_symbols: &mut HashMap<ShardIndex, RecoverySymbol<MerkleProof>>,
+        additional_symbols: usize,
+        weak_committee: &Weak<Committee>,
+        fallback_committee: &Option<Weak<Committee>>,
+    ) -> Result<usize, usize> {
+        let result = self
+            .collect_recovery_symbols(collected_symbols, additional_symbols, weak_committee)
+            .await;
+        match (result, fallback_committee) {
+            (Err(_), Some(fallback_committee)) => {
+                tracing::debug!(
+                    "falling back to the previous committee to collect recovery symbols"
+                );
+                self.collect_recovery_symbols(
+                    collected_symbols,
+                    additional_symbols,
+                    fallback_committee,
+                )
+                .await
+            }
+            (result, _) => result,
+        }
+    }
+
     /// Request and store recovery symbols in `self.collected_symbols`.
     ///
     /// On success, returns the total number of collection symbols; on failure, the number of
@@ -388,6 +459,8 @@ where
                 let sliver_pair_at_remote =
                     shard_index.to_pair_index(self.metadata.n_shards(), self.metadata.blob_id());
 
+                let shared = self.shared;
+                let reporting_key = node_public_key.clone();
                 let request = client
                     .oneshot(Request::GetVerifiedRecoverySymbol {
                         sliver_type: self.sliver_type,
@@ -395,8 +468,11 @@ where
                         sliver_pair_at_remote,
                         intersecting_pair_index: sliver_id,
                     })
-                    .map_ok(move |symbol| (shard_index, symbol.into_value()));
-                let request = time::timeout(self.shared.config.sliver_request_timeout, request)
+                    .map_ok(move |symbol| (shard_index, symbol.into_value()))
+                    .inspect(move |result| {
+                        shared.observe_response(&reporting_key, "get_recovery_symbol", result)
+                    });
+                let request = time::timeout(self.shared.config().sliver_request_timeout, request)
                     .map(log_and_discard_timeout_or_error)
                     .instrument(tracing::info_span!("get_recovery_symbol",
                         walrus.node.public_key = %node_public_key,
@@ -511,8 +587,8 @@ where
             target_sliver_type,
             epoch_certified,
             backoff: ExponentialBackoffState::new_infinite(
-                shared.config.retry_interval_min,
-                shared.config.retry_interval_max,
+                shared.config().retry_interval_min,
+                shared.config().retry_interval_max,
             ),
             shared,
             metadata,
@@ -554,26 +630,29 @@ where
         );
 
         loop {
-            let weak_committee = {
+            let (weak_committee, fallback_committee) = {
                 let committee_tracker = committee_listener.borrow_and_update();
-                Arc::downgrade(
-                    committee_tracker
-                        .committees()
-                        .read_committee(self.epoch_certified)
-                        .expect("epoch must not be in the future"),
+                let committee = committee_tracker
+                    .committees()
+                    .read_committee(self.epoch_certified)
+                    .expect("epoch must not be in the future");
+                (
+                    Arc::downgrade(committee),
+                    fallback_read_committee(&committee_tracker, self.epoch_certified, self.shared),
                 )
             };
 
             let epoch_certified = self.epoch_certified;
-            let worker = CollectRecoverySymbols::new(
+            let worker = CollectRecoverySymbols::run_with_fallback(
                 self.metadata.clone(),
                 &mut symbol_tracker,
                 weak_committee.clone(),
+                fallback_committee,
                 self.shared,
             );
 
             tokio::select! {
-                result = worker.run() => {
+                result = worker => {
                     match result {
                         Ok(n_symbols) => {
                             tracing::trace!(
@@ -587,6 +666,7 @@ where
                                 %n_symbols_remaining,
                                 "failed to collect sufficient recovery symbols"
                             );
+                            self.shared.reconcile_if_committee_stale().await;
                             wait_before_next_attempts(&mut self.backoff, &self.shared.rng).await;
                         }
                     }
@@ -727,6 +807,32 @@ impl<'a, T: NodeService> CollectRecoverySymbols<'a, T> {
         }
     }
 
+    /// Collects recovery symbols from the read committee and, if unsuccessful, from the fallback
+    /// committee.
+    async fn run_with_fallback(
+        metadata: Arc<VerifiedBlobMetadataWithId>,
+        tracker: &'a mut SymbolTracker,
+        committee: Weak<Committee>,
+        fallback_committee: Option<Weak<Committee>>,
+        shared: &'a NodeCommitteeServiceInner<T>,
+    ) -> Result<usize, usize> {
+        let result =
+            CollectRecoverySymbols::new(metadata.clone(), &mut *tracker, committee, shared)
+                .run()
+                .await;
+        match (result, fallback_committee) {
+            (Err(_), Some(fallback_committee)) => {
+                tracing::debug!(
+                    "falling back to the previous committee to collect recovery symbols"
+                );
+                CollectRecoverySymbols::new(metadata, tracker, fallback_committee, shared)
+                    .run()
+                    .await
+            }
+            (result, _) => result,
+        }
+    }
+
     async fn run(mut self) -> Result<usize, usize> {
         self.refill_pending_requests();
 
@@ -814,9 +920,16 @@ impl<'a, T: NodeService> CollectRecoverySymbols<'a, T> {
                 target_type: self.target_sliver_type(),
             };
 
+            let shared = self.shared;
+            let node_public_key = node_info.public_key.clone();
             let request = time::timeout(
-                self.shared.config.sliver_request_timeout,
-                client.oneshot(request).map_ok(|symbol| symbol.into_value()),
+                self.shared.config().sliver_request_timeout,
+                client
+                    .oneshot(request)
+                    .map_ok(|symbol| symbol.into_value())
+                    .inspect(move |result| {
+                        shared.observe_response(&node_public_key, "list_recovery_symbols", result)
+                    }),
             )
             .map(log_and_discard_timeout_or_error)
             .map(move |symbol| (symbols_count, symbol))
@@ -1079,6 +1192,7 @@ where
     }
     pub async fn run(mut self) -> InvalidBlobCertificate {
         let mut committee_listener = self.shared.subscribe_to_committee_changes();
+        let start = Instant::now();
 
         loop {
             let committee = committee_listener
@@ -1089,6 +1203,7 @@ where
 
             tokio::select! {
                 certificate = self.get_certificate_from_committee(committee.clone()) => {
+                    self.shared.record_invalid_blob_certificate(start.elapsed());
                     return certificate;
                 }
                 () = wait_for_write_committee_change(
@@ -1115,8 +1230,8 @@ where
         );
         let mut collected_signatures = HashMap::new();
         let mut backoff = ExponentialBackoffState::new_infinite(
-            self.shared.config.retry_interval_min,
-            self.shared.config.retry_interval_max,
+            self.shared.config().retry_interval_min,
+            self.shared.config().retry_interval_max,
         );
         let mut node_order: Vec<_> = (0..committee.n_members()).collect();
 
@@ -1142,6 +1257,7 @@ where
                 }
                 Err(partially_collected) => {
                     collected_signatures = partially_collected;
+                    self.shared.reconcile_if_committee_stale().await;
                     wait_before_next_attempts(&mut backoff, &self.shared.rng).await;
                 }
             }
@@ -1296,7 +1412,7 @@ where
             inconsistency_proof,
             shared,
             nodes,
-            required_weight: bft::min_n_correct(committee.n_shards()).get(),
+            required_weight: committee.quorum_threshold(),
             committee,
             pending_weight: 0,
             pending_requests: Default::default(),
@@ -1350,7 +1466,7 @@ where
                 })
                 .map_ok(Response::into_value);
 
-            let request = time::timeout(self.shared.config.invalidity_sync_timeout, request)
+            let request = time::timeout(self.shared.config().invalidity_sync_timeout, request)
                 .map(move |output| (index, weight, log_and_discard_timeout_or_error(output)))
                 .instrument(tracing::info_span!(
                     "get_invalid_blob_certificate node",
@@ -1416,6 +1532,27 @@ fn are_shard_addresses_equivalent(committee: &Committee, other: &Committee) -> b
     true
 }
 
+/// Returns the committee to which reads of a blob certified in `epoch_certified` fall back if
+/// they cannot be served by the read committee.
+///
+/// This is the previous committee, if the fallback is enabled, the blob was certified before the
+/// current epoch, and the previous committee is not already the read committee.
+fn fallback_read_committee<T>(
+    committee_tracker: &CommitteeTracker,
+    epoch_certified: Epoch,
+    shared: &NodeCommitteeServiceInner<T>,
+) -> Option<Weak<Committee>> {
+    if !shared.config().read_fallback_to_previous_committee {
+        return None;
+    }
+    let committees = committee_tracker.committees();
+    let previous_committee = committees.previous_committee()?;
+    let read_committee = committees.read_committee(epoch_certified)?;
+    (epoch_certified <= previous_committee.epoch
+        && !Arc::ptr_eq(previous_committee, read_committee))
+    .then(|| Arc::downgrade(previous_committee))
+}
+
 async fn wait_before_next_attempts(backoff: &mut ExponentialBackoffState, rng: &SyncMutex<StdRng>) {
     let delay = backoff
         .next_delay(&mut *rng.lock().expect("mutex is not poisoned"))
@@ -17,7 +17,6 @@ use rand::{
 use tokio::time;
 use tower::{util::BoxCloneService, ServiceExt as _};
 use walrus_core::{
-    bft,
     encoding::{
         self,
         EncodingConfig,
@@ -31,16 +30,18 @@ use walrus_core::{
     merkle::MerkleProof,
     messages::InvalidBlobIdMsg,
     metadata::VerifiedBlobMetadataWithId,
+    BlobId,
     Epoch,
     InconsistencyProof,
     PublicKey,
     RecoverySymbol,
+    ShardIndex,
     SliverIndex,
     SliverPairIndex,
     SliverType,
     DEFAULT_ENCODING,
 };
-use walrus_sdk::error::ClientBuildError;
+use walrus_sdk::error::{ClientBuildError, NodeError};
 use walrus_sui::types::{Committee, StorageNode as SuiStorageNode};
 use walrus_test_utils::{async_param_test, Result as TestResult};
 
@@ -49,13 +50,17 @@ use crate::{
     node::{
         self,
         committee::{
-            committee_service::NodeCommitteeService,
+            committee_service::{NodeCommitteeService, StaleCommitteeDetector},
+            fault_injection::{Fault, FaultInjectingServiceFactory, RequestKind},
             node_service::{NodeServiceError, Request, Response},
             CommitteeLookupService,
             CommitteeService,
             NodeServiceFactory,
+            ShardAssignmentDiff,
         },
         config::CommitteeServiceConfig,
+        LocalProtocolSigner,
+        ProtocolSigner,
     },
     test_utils,
 };
@@ -112,6 +117,8 @@ impl NodeServiceFactory for ServiceFactoryMap {
     }
 
     fn connect_timeout(&mut self, _timeout: Duration) {}
+
+    fn max_response_size(&mut self, _max_size: u64) {}
 }
 
 /// Returns true if there are any members that share the same public key.
@@ -274,6 +281,150 @@ async fn metadata_request_succeeds_if_available(
     Ok(())
 }
 
+#[tokio::test(start_paused = true)]
+async fn records_nodes_returning_invalid_metadata() -> TestResult {
+    let expected_metadata = walrus_core::test_utils::verified_blob_metadata();
+    let committee = test_utils::test_committee(&[1; 10]);
+    let correct_node = committee.members()[7].public_key.clone();
+    let faulty_nodes: Vec<_> = committee
+        .members()
+        .iter()
+        .map(|member| member.public_key.clone())
+        .filter(|key| *key != correct_node)
+        .collect();
+
+    let mut service_map = ServiceFactoryMap::default();
+    for key in faulty_nodes.iter() {
+        service_map.insert_ready(key.clone(), |_request| {
+            Err(NodeServiceError::Node(NodeError::verification(
+                std::io::Error::other("metadata does not match the blob ID"),
+            )))
+        });
+    }
+    let response_metadata = expected_metadata.clone();
+    service_map.insert_ready(correct_node.clone(), move |_request| {
+        Ok(Response::VerifiedMetadata(response_metadata.clone()))
+    });
+
+    let committee_service = NodeCommitteeService::builder()
+        .randomness(StdRng::seed_from_u64(0))
+        .build_with_factory(
+            ActiveCommittees::new(
+                committee,
+                Some(Committee::new(vec![], 0, NonZeroU16::new(10).unwrap()).unwrap()),
+            ),
+            service_map,
+        )
+        .await?;
+
+    // Request repeatedly, so that faulty nodes are queried before the correct node.
+    for _ in 0..10 {
+        let returned_metadata = time::timeout(
+            Duration::from_secs(60),
+            committee_service.get_and_verify_metadata(*expected_metadata.blob_id(), 1),
+        )
+        .await?;
+        assert_eq!(expected_metadata, returned_metadata);
+    }
+
+    assert!(faulty_nodes
+        .iter()
+        .any(|key| committee_service.invalid_response_count(key) > 0));
+    assert_eq!(committee_service.invalid_response_count(&correct_node), 0);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn metadata_request_tolerates_faulty_nodes() -> TestResult {
+    let expected_metadata = walrus_core::test_utils::verified_blob_metadata();
+    let committee = test_utils::test_committee(&[1; 10]);
+    let keys: Vec<_> = committee
+        .members()
+        .iter()
+        .map(|member| member.public_key.clone())
+        .collect();
+
+    let mut service_map = ServiceFactoryMap::default();
+    for key in keys.iter() {
+        let response_metadata = expected_metadata.clone();
+        service_map.insert_ready(key.clone(), move |_request| {
+            Ok(Response::VerifiedMetadata(response_metadata.clone()))
+        });
+    }
+    let factory = FaultInjectingServiceFactory::new(service_map);
+    let plan = factory.plan();
+
+    // Only the last node responds correctly and in time.
+    let faults = [
+        Fault::Drop,
+        Fault::Corrupt,
+        Fault::Delay(Duration::from_secs(3600)),
+    ];
+    for (key, fault) in keys
+        .iter()
+        .zip(faults.into_iter().cycle())
+        .take(keys.len() - 1)
+    {
+        plan.inject(key.clone(), Some(RequestKind::GetVerifiedMetadata), fault);
+    }
+
+    let committee_service = NodeCommitteeService::builder()
+        .randomness(StdRng::seed_from_u64(0))
+        .build_with_factory(
+            ActiveCommittees::new(
+                committee,
+                Some(Committee::new(vec![], 0, NonZeroU16::new(10).unwrap()).unwrap()),
+            ),
+            factory,
+        )
+        .await?;
+
+    for _ in 0..5 {
+        let returned_metadata = time::timeout(
+            Duration::from_secs(60),
+            committee_service.get_and_verify_metadata(*expected_metadata.blob_id(), 1),
+        )
+        .await?;
+        assert_eq!(expected_metadata, returned_metadata);
+    }
+    assert_eq!(committee_service.invalid_response_count(&keys[9]), 0);
+
+    Ok(())
+}
+
+#[test]
+fn stale_committee_is_suspected_once_failures_reach_threshold() {
+    let mut detector = StaleCommitteeDetector::new(&CommitteeServiceConfig {
+        stale_committee_min_requests: NonZero::new(4).unwrap(),
+        stale_committee_failure_percent: 50,
+        ..Default::default()
+    });
+
+    // A single failure in the window is below the threshold.
+    let outcomes = [true, false, false, false].map(|failed| detector.record(failed));
+    assert_eq!(outcomes, [false; 4]);
+
+    // Two failures out of four reach the threshold at the end of the window.
+    let outcomes = [false, true, true, false].map(|failed| detector.record(failed));
+    assert_eq!(outcomes, [false, false, false, true]);
+
+    // The counts are reset after each window.
+    let outcomes = [false, false, false, false].map(|failed| detector.record(failed));
+    assert_eq!(outcomes, [false; 4]);
+}
+
+#[test]
+fn stale_committee_detection_can_be_disabled() {
+    let mut detector = StaleCommitteeDetector::new(&CommitteeServiceConfig {
+        stale_committee_min_requests: NonZero::new(1).unwrap(),
+        stale_committee_failure_percent: 0,
+        ..Default::default()
+    });
+
+    assert!(!detector.record(true));
+}
+
 #[tokio::test(start_paused = true)]
 async fn new_committee_unavailable_for_reads_until_transition_completes() -> TestResult {
     let expected_metadata = walrus_core::test_utils::verified_blob_metadata();
@@ -325,6 +476,69 @@ async fn new_committee_unavailable_for_reads_until_transition_completes() -> Tes
     Ok(())
 }
 
+#[tokio::test(start_paused = true)]
+async fn publishes_shard_assignment_diff_on_committee_change() -> TestResult {
+    let mut local = walrus_sui::test_utils::new_move_storage_node_for_testing();
+    let mut other = walrus_sui::test_utils::new_move_storage_node_for_testing();
+    let mut committee_with_local_shards = |epoch: Epoch, local_shards: &[u16]| {
+        local.shard_ids = local_shards.iter().copied().map(ShardIndex).collect();
+        other.shard_ids = (0..6)
+            .filter(|shard| !local_shards.contains(shard))
+            .map(ShardIndex)
+            .collect();
+        Committee::new(
+            vec![local.clone(), other.clone()],
+            epoch,
+            NonZeroU16::new(6).unwrap(),
+        )
+    };
+    let previous_committee = committee_with_local_shards(6, &[0, 1])?;
+    let current_committee = committee_with_local_shards(7, &[0, 1, 2])?;
+    let next_committee = committee_with_local_shards(8, &[2, 3])?;
+
+    let (committee_lookup, committee_handle) = lookup_service_pair(ActiveCommittees::new(
+        current_committee,
+        Some(previous_committee),
+    ));
+    let committee_service = NodeCommitteeService::builder()
+        .local_identity(local.public_key.clone())
+        .randomness(StdRng::seed_from_u64(5))
+        .build_with_factory(committee_lookup, ServiceFactoryMap::default())
+        .await?;
+
+    let initial_diff = ShardAssignmentDiff {
+        epoch: 7,
+        gained: vec![ShardIndex(2)],
+        lost: vec![],
+        retained: vec![ShardIndex(0), ShardIndex(1)],
+    };
+    assert_eq!(
+        committee_service.shard_assignment_diff(),
+        Some(initial_diff)
+    );
+
+    let mut changes = committee_service.subscribe_to_shard_assignment_changes();
+    committee_handle.begin_transition_to(next_committee);
+    committee_service.begin_committee_change(8).await?;
+
+    assert!(changes.has_changed()?);
+    let expected_diff = ShardAssignmentDiff {
+        epoch: 8,
+        gained: vec![ShardIndex(3)],
+        lost: vec![ShardIndex(0), ShardIndex(1)],
+        retained: vec![ShardIndex(2)],
+    };
+    assert_eq!(*changes.borrow_and_update(), Some(expected_diff.clone()));
+    assert_eq!(expected_diff.owned_shards(), [ShardIndex(2), ShardIndex(3)]);
+
+    // Ending the change does not change the assignment.
+    committee_handle.finish_transition();
+    committee_service.end_committee_change(8)?;
+    assert!(!changes.has_changed()?);
+
+    Ok(())
+}
+
 async_param_test! {
     #[tokio::test(start_paused = true)]
     rejects_non_incremental_epochs -> TestResult: [
@@ -435,6 +649,57 @@ async fn metadata_requests_do_not_query_self() -> TestResult {
     Ok(())
 }
 
+async_param_test! {
+    #[tokio::test(start_paused = true)]
+    metadata_requests_fall_back_to_previous_committee -> TestResult: [
+        enabled: (true),
+        disabled: (false),
+    ]
+}
+async fn metadata_requests_fall_back_to_previous_committee(fallback_enabled: bool) -> TestResult {
+    let expected_metadata = walrus_core::test_utils::verified_blob_metadata();
+    let blob_id = *expected_metadata.blob_id();
+
+    let (committees, _) = valid_committees(6, ShardAssignment::Varied);
+    let previous_committee = committees.previous_committee().unwrap();
+    debug_assert!(!has_members_in_common(
+        committees.current_committee(),
+        previous_committee
+    ));
+
+    // Only a member of the previous committee, which still holds its former shards, has the
+    // metadata, as if the current committee had not finished syncing.
+    let service_map = ServiceFactoryMap::single_ready(
+        previous_committee.members()[2].public_key.clone(),
+        move |_request| Ok(Response::VerifiedMetadata(expected_metadata.clone())),
+    );
+
+    let committee_service = NodeCommitteeService::builder()
+        .randomness(StdRng::seed_from_u64(5))
+        .config(CommitteeServiceConfig {
+            read_fallback_to_previous_committee: fallback_enabled,
+            ..Default::default()
+        })
+        .build_with_factory(committees, service_map)
+        .await?;
+
+    let request = time::timeout(
+        Duration::from_secs(60),
+        committee_service.get_and_verify_metadata(blob_id, 4),
+    );
+    if fallback_enabled {
+        request
+            .await
+            .expect("request must succeed since the previous committee has the metadata");
+    } else {
+        request
+            .await
+            .expect_err("must not succeed since the current committee lacks the metadata");
+    }
+
+    Ok(())
+}
+
 /// For the 0th primary sliver for an arbitrary blob, return a map of a random subset of
 /// secondary sliver ID that generated the symbol -> recovery symbols.
 fn recovery_symbols_by_shard(
@@ -587,8 +852,8 @@ async fn restarts_inconsistency_proof_collection_across_epoch_change() -> TestRe
     let initial_committee = committees.current_committee();
     debug_assert!(!has_members_in_common(initial_committee, &next_committee));
 
-    let weight_required = bft::min_n_correct(committees.current_committee().n_shards());
-    let nodes_required = weight_required.get() as usize; // Since we use 1 shard per node.
+    let weight_required = committees.current_committee().quorum_threshold();
+    let nodes_required = usize::from(weight_required); // Since we use 1 shard per node.
 
     let mut service_map = ServiceFactoryMap::default();
 
@@ -600,7 +865,8 @@ async fn restarts_inconsistency_proof_collection_across_epoch_change() -> TestRe
             let message = InvalidBlobIdMsg::new(committee.epoch, blob_id);
             // Use an arbitrary key pair since we skip verification in these tests.
             let arbitrary_key_pair = ProtocolKeyPair::generate_with_rng(&mut rng);
-            let attestation = node::sign_message(message, arbitrary_key_pair).await?;
+            let attestation =
+                node::sign_message(message, &LocalProtocolSigner::new(arbitrary_key_pair)).await?;
 
             service_map.insert_ready(node.public_key.clone(), move |_request| {
                 Ok(Response::InvalidBlobAttestation(attestation.clone()))
@@ -653,8 +919,8 @@ async fn collects_inconsistency_proof_despite_epoch_change() -> TestResult {
     let initial_committee = committees.current_committee();
     debug_assert!(!has_members_in_common(initial_committee, &next_committee));
 
-    let weight_required = bft::min_n_correct(committees.current_committee().n_shards());
-    let nodes_required = weight_required.get() as usize; // Since we use 1 shard per node.
+    let weight_required = committees.current_committee().quorum_threshold();
+    let nodes_required = usize::from(weight_required); // Since we use 1 shard per node.
 
     let mut service_map = ServiceFactoryMap::default();
     for (committee, n_responders) in [
@@ -665,7 +931,8 @@ async fn collects_inconsistency_proof_despite_epoch_change() -> TestResult {
             let message = InvalidBlobIdMsg::new(committee.epoch, blob_id);
             // Use an arbitrary key pair since we skip verification in these tests.
             let arbitrary_key_pair = ProtocolKeyPair::generate_with_rng(&mut rng);
-            let attestation = node::sign_message(message, arbitrary_key_pair).await?;
+            let attestation =
+                node::sign_message(message, &LocalProtocolSigner::new(arbitrary_key_pair)).await?;
 
             service_map.insert_ready(node.public_key.clone(), move |_request| {
                 Ok(Response::InvalidBlobAttestation(attestation.clone()))
@@ -703,3 +970,174 @@ async fn collects_inconsistency_proof_despite_epoch_change() -> TestResult {
 
     Ok(())
 }
+
+async_param_test! {
+    #[tokio::test(start_paused = true)]
+    syncs_shard_from_owners_in_earlier_epochs -> TestResult: [
+        previous_epoch_only: (1, false),
+        walks_back_to_earlier_epoch: (2, true),
+    ]
+}
+async fn syncs_shard_from_owners_in_earlier_epochs(
+    max_epochs_back: u32,
+    expect_success: bool,
+) -> TestResult {
+    let new_epoch: Epoch = 4;
+    let shard = ShardIndex(0);
+    let (committees, next_committee) = valid_committees(new_epoch - 1, ShardAssignment::Varied);
+    let outgoing_owner = committees.current_committee().members()[0]
+        .public_key
+        .clone();
+    let earlier_owner = committees.previous_committee().unwrap().members()[0]
+        .public_key
+        .clone();
+    let expected_slivers = vec![(BlobId([1; 32]), walrus_core::test_utils::sliver())];
+
+    let mut service_map = ServiceFactoryMap::default();
+    service_map.insert_ready(outgoing_owner, |_request| {
+        Err(NodeServiceError::Node(NodeError::other(
+            std::io::Error::other("the node is unavailable"),
+        )))
+    });
+    let response_slivers = expected_slivers.clone();
+    service_map.insert_ready(earlier_owner, move |request| {
+        assert!(matches!(
+            request,
+            Request::SyncShardAsOfEpoch {
+                current_epoch: 4,
+                ..
+            }
+        ));
+        Ok(Response::ShardSlivers(response_slivers.clone()))
+    });
+
+    let (committee_lookup, committee_handle) = lookup_service_pair(committees);
+    let committee_service = NodeCommitteeService::builder()
+        .randomness(StdRng::seed_from_u64(3))
+        .config(CommitteeServiceConfig {
+            shard_sync_max_epochs_back: NonZero::new(max_epochs_back).unwrap(),
+            ..Default::default()
+        })
+        .build_with_factory(committee_lookup, service_map)
+        .await?;
+
+    // After the epoch change, the committee of the earlier epoch is no longer active.
+    committee_handle.begin_transition_to(next_committee);
+    committee_service.begin_committee_change(new_epoch).await?;
+    committee_handle.finish_transition();
+    committee_service.end_committee_change(new_epoch)?;
+
+    let signer: Arc<dyn ProtocolSigner> =
+        Arc::new(LocalProtocolSigner::new(ProtocolKeyPair::generate()));
+    let result = committee_service
+        .sync_shard_before_epoch(
+            shard,
+            BlobId([0; 32]),
+            SliverType::Primary,
+            10,
+            new_epoch,
+            &signer,
+        )
+        .await;
+
+    if expect_success {
+        assert_eq!(result?, expected_slivers);
+    } else {
+        assert!(matches!(
+            result,
+            Err(node::errors::SyncShardClientError::RequestError(_))
+        ));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn restores_committee_history_of_earlier_epochs() -> TestResult {
+    let (committees, _) = valid_committees(5, ShardAssignment::Varied);
+    let (committee_lookup, _) = lookup_service_pair(committees);
+    let committee_service = NodeCommitteeService::builder()
+        .config(CommitteeServiceConfig {
+            shard_sync_max_epochs_back: NonZero::new(2).unwrap(),
+            ..Default::default()
+        })
+        .build_with_factory(committee_lookup, ServiceFactoryMap::default())
+        .await?;
+    let observed_current = committee_service.committee_history()[1].clone();
+
+    committee_service.restore_committee_history(
+        (1..=5)
+            .map(|epoch| test_utils::test_committee_with_epoch(&[1; 10], epoch))
+            .collect(),
+    );
+
+    let history = committee_service.committee_history();
+    // Only the committees of the epochs needed to walk back two epochs from the current epoch are
+    // retained, and the committees observed by the service are not replaced.
+    assert_eq!(
+        history
+            .iter()
+            .map(|committee| committee.epoch)
+            .collect::<Vec<_>>(),
+        [2, 3, 4, 5]
+    );
+    assert_eq!(history[3], observed_current);
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_runtime_settings_are_updated() -> TestResult {
+    let (committees, _) = valid_committees(5, ShardAssignment::Varied);
+    let (committee_lookup, _) = lookup_service_pair(committees);
+    let committee_service = NodeCommitteeService::builder()
+        .build_with_factory(committee_lookup, ServiceFactoryMap::default())
+        .await?;
+
+    let new_config = CommitteeServiceConfig {
+        sliver_request_timeout: Duration::from_secs(10),
+        retry_interval_max: Duration::from_secs(60),
+        node_connect_timeout: Duration::from_secs(10),
+        ..Default::default()
+    };
+    committee_service.update_config(&new_config);
+
+    let config = committee_service.inner.config();
+    assert_eq!(config.sliver_request_timeout, Duration::from_secs(10));
+    assert_eq!(config.retry_interval_max, Duration::from_secs(60));
+    // The connection timeout is only applied when the service is created.
+    assert_eq!(
+        config.node_connect_timeout,
+        CommitteeServiceConfig::default().node_connect_timeout
+    );
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn reconciles_tracked_committees_of_the_same_epoch() -> TestResult {
+    let (committees, _) = valid_committees(5, ShardAssignment::Varied);
+    let (committee_lookup, committee_handle) = lookup_service_pair(committees.clone());
+    let committee_service = NodeCommitteeService::builder()
+        .config(CommitteeServiceConfig {
+            stale_committee_min_reconciliation_interval: Duration::ZERO,
+            ..Default::default()
+        })
+        .build_with_factory(committee_lookup, ServiceFactoryMap::default())
+        .await?;
+
+    // Committees of a later epoch are only adopted through the epoch change.
+    let (later_committees, _) = valid_committees(6, ShardAssignment::Varied);
+    committee_handle.set_active_committees(later_committees);
+    committee_service
+        .reconcile_suspected_stale_committee()
+        .await;
+    assert_eq!(committee_service.active_committees(), committees);
+
+    let (updated_committees, _) = valid_committees(5, ShardAssignment::OneEach);
+    assert_ne!(updated_committees, committees);
+    committee_handle.set_active_committees(updated_committees.clone());
+    committee_service
+        .reconcile_suspected_stale_committee()
+        .await;
+    assert_eq!(committee_service.active_committees(), updated_committees);
+
+    Ok(())
+}
@@ -7,7 +7,7 @@ use std::{
     collections::HashMap,
     fmt::Display,
     net::{IpAddr, SocketAddr},
-    num::NonZeroUsize,
+    num::{NonZeroU32, NonZeroUsize},
     path::{Path, PathBuf},
     str::FromStr as _,
     time::Duration,
@@ -15,6 +15,7 @@ use std::{
 
 use anyhow::{anyhow, Context};
 use p256::pkcs8::DecodePrivateKey;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use serde_with::{
     base64::Base64,
@@ -42,7 +43,11 @@ use walrus_sui::types::{
 
 use super::storage::DatabaseConfig;
 use crate::{
-    common::{config::SuiConfig, utils},
+    common::{
+        balance_monitor::{BalanceMonitorConfig, BalanceThresholds},
+        config::{SuiConfig, TraceSamplingConfig},
+        utils,
+    },
     node::events::EventProcessorConfig,
 };
 
@@ -79,15 +84,22 @@ pub struct StorageNodeConfig {
     #[serde(deserialize_with = "walrus_utils::config::resolve_home_dir")]
     pub storage_path: PathBuf,
     /// File path to the blocklist.
+    ///
+    /// The node refuses to store or serve the data of the blobs on the blocklist. Blobs added to
+    /// or removed from the deny list through the admin API are persisted to this file.
     #[serde(default, skip_serializing_if = "defaults::is_none")]
     pub blocklist_path: Option<PathBuf>,
     /// Optional "config" to tune storage database.
     #[serde(default, skip_serializing_if = "defaults::is_default")]
     pub db_config: DatabaseConfig,
     /// Key pair used in Walrus protocol messages.
+    ///
+    /// May be omitted if protocol messages are signed by the `remote_protocol_signer` and its
+    /// `public_key` is configured.
     // Important: this name should be in-sync with the name used in `rotate_protocol_key_pair()`
-    #[serde_as(as = "PathOrInPlace<Base64>")]
-    pub protocol_key_pair: PathOrInPlace<ProtocolKeyPair>,
+    #[serde_as(as = "Option<PathOrInPlace<Base64>>")]
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub protocol_key_pair: Option<PathOrInPlace<ProtocolKeyPair>>,
     /// The next protocol key pair to use for the storage node.
     // Important: this name should be in-sync with the name used in `rotate_protocol_key_pair()`
     #[serde_as(as = "Option<PathOrInPlace<Base64>>")]
@@ -104,9 +116,20 @@ pub struct StorageNodeConfig {
     /// Socket address on which the Prometheus server should export its metrics.
     #[serde(default = "defaults::metrics_address")]
     pub metrics_address: SocketAddr,
+    /// Additional socket addresses on which the metrics are served, for example to also serve them
+    /// on an IPv6 address or on a separate interface.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub metrics_listeners: Vec<SocketAddr>,
     /// Socket address on which the REST API listens.
     #[serde(default = "defaults::rest_api_address")]
     pub rest_api_address: SocketAddr,
+    /// Additional listeners of the REST API, for example to also listen on an IPv6 address or on a
+    /// separate interface.
+    ///
+    /// If the REST API has multiple listeners, listeners on IPv6 addresses only accept IPv6
+    /// connections, such that IPv4 and IPv6 listeners can share a port.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub rest_api_listeners: Vec<ListenerConfig>,
     /// Configuration for the connections establishing in the REST API.
     #[serde(default, skip_serializing_if = "defaults::is_default")]
     pub rest_server: RestServerConfig,
@@ -161,6 +184,14 @@ pub struct StorageNodeConfig {
     /// The capability object ID of the storage node.
     #[serde(default, skip_serializing_if = "defaults::is_none")]
     pub storage_node_cap: Option<ObjectID>,
+    /// Whether the node registers itself on-chain when it is started and not yet registered.
+    ///
+    /// The registration uses the name, public host and port, keys, commission rate, voting
+    /// parameters, and metadata from this configuration, including environment overrides. The
+    /// configuration file is not modified; if `storage_node_cap` is not set, the node looks up its
+    /// capability object through the address of its wallet.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub register_on_first_start: bool,
     /// The number of uncertified blobs before the node will reset the local
     /// state in event blob writer.
     #[serde(default, skip_serializing_if = "defaults::is_none")]
@@ -168,9 +199,44 @@ pub struct StorageNodeConfig {
     /// Configuration for background SUI balance checks and alerting.
     #[serde(default, skip_serializing_if = "defaults::is_default")]
     pub balance_check: BalanceCheckConfig,
+    /// Configuration for monitoring and alerting on the lag of event processing behind the chain.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub event_lag_monitor: EventLagMonitorConfig,
+    /// Configuration for retrying blob events that fail to be processed.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub dead_letter_queue: DeadLetterQueueConfig,
     /// Configuration for the blocking thread pool.
     #[serde(default, skip_serializing_if = "defaults::is_default")]
     pub thread_pool: ThreadPoolConfig,
+    /// Whether the node starts in maintenance mode.
+    ///
+    /// In maintenance mode, the node keeps serving reads and shard-sync requests, but rejects new
+    /// metadata and slivers with a retryable error, defers blob syncs and node recovery, and pauses
+    /// its periodic background jobs. Maintenance mode can also be toggled through the
+    /// administrative API.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub maintenance_mode: bool,
+    /// Tracing filter directives, in the same format as the `RUST_LOG` environment variable.
+    ///
+    /// If set, these directives replace the filter from the environment on startup and whenever
+    /// the configuration is reloaded.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub log_filter: Option<String>,
+    /// The rates at which traces are sampled for export.
+    ///
+    /// The rates can also be changed through the administrative API, and are replaced when they
+    /// change in a reloaded configuration.
+    #[serde(default, skip_serializing_if = "defaults::is_default")]
+    pub trace_sampling: TraceSamplingConfig,
+    /// The remote service signing protocol messages, if any.
+    ///
+    /// If set, storage confirmations, invalid-blob attestations, and shard-sync requests are
+    /// signed by the remote service instead of with the `protocol_key_pair`, such that the key can
+    /// be held by an HSM-backed signer process. The service must sign with the key pair whose
+    /// public key is the one of the `protocol_key_pair`, or the configured public key if the
+    /// `protocol_key_pair` is omitted.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub remote_protocol_signer: Option<RemoteProtocolSignerConfig>,
 }
 
 impl Default for StorageNodeConfig {
@@ -179,13 +245,15 @@ impl Default for StorageNodeConfig {
             storage_path: PathBuf::from("/opt/walrus/db"),
             blocklist_path: Default::default(),
             db_config: Default::default(),
-            protocol_key_pair: PathOrInPlace::from_path("/opt/walrus/config/protocol.key"),
+            protocol_key_pair: Some(PathOrInPlace::from_path("/opt/walrus/config/protocol.key")),
             next_protocol_key_pair: None,
             network_key_pair: PathOrInPlace::from_path("/opt/walrus/config/network.key"),
             public_host: defaults::rest_api_address().ip().to_string(),
             public_port: defaults::rest_api_port(),
             metrics_address: defaults::metrics_address(),
+            metrics_listeners: vec![],
             rest_api_address: defaults::rest_api_address(),
+            rest_api_listeners: vec![],
             rest_graceful_shutdown_period_secs: defaults::rest_graceful_shutdown_period_secs(),
             rest_server: Default::default(),
             sui: Default::default(),
@@ -206,18 +274,30 @@ impl Default for StorageNodeConfig {
             metadata: Default::default(),
             config_synchronizer: Default::default(),
             storage_node_cap: None,
+            register_on_first_start: false,
             num_uncertified_blob_threshold: None,
             balance_check: Default::default(),
+            event_lag_monitor: Default::default(),
+            dead_letter_queue: Default::default(),
             thread_pool: Default::default(),
+            maintenance_mode: false,
+            log_filter: None,
+            trace_sampling: Default::default(),
+            remote_protocol_signer: None,
         }
     }
 }
 
+/// The key of the protocol key pair in the node configuration file.
+const PROTOCOL_KEY_PAIR_KEY: &str = "protocol_key_pair";
+/// The key of the next protocol key pair in the node configuration file.
+const NEXT_PROTOCOL_KEY_PAIR_KEY: &str = "next_protocol_key_pair";
+
 impl StorageNodeConfig {
     /// Rotates the protocol key pair.
     pub fn rotate_protocol_key_pair(&mut self) {
         if let Some(next_key_pair) = self.next_protocol_key_pair.clone() {
-            self.protocol_key_pair = next_key_pair;
+            self.protocol_key_pair = Some(next_key_pair);
             self.next_protocol_key_pair = None;
         }
     }
@@ -227,23 +307,14 @@ impl StorageNodeConfig {
     /// The protocol_key_pair is set to the new key pair, and the
     /// next_protocol_key_pair is cleared.
     pub fn rotate_protocol_key_pair_persist(path: impl AsRef<Path>) -> anyhow::Result<()> {
-        // Load config from path, preserving the raw Value to maintain all fields
-        let config_str = std::fs::read_to_string(path.as_ref())?;
-        let mut original_value: serde_yaml::Value = serde_yaml::from_str(&config_str)?;
-        let mut config: StorageNodeConfig = serde_yaml::from_str(&config_str)?;
-        // Constants for config key strings
-        const PROTOCOL_KEY_PAIR_KEY: &str = "protocol_key_pair";
-        const NEXT_PROTOCOL_KEY_PAIR_KEY: &str = "next_protocol_key_pair";
-
-        if config.next_protocol_key_pair.is_none() {
-            return Err(anyhow::anyhow!("{} is not set", NEXT_PROTOCOL_KEY_PAIR_KEY));
-        }
+        Self::update_persisted_config(path, |config, map| {
+            if config.next_protocol_key_pair.is_none() {
+                return Err(anyhow::anyhow!("{} is not set", NEXT_PROTOCOL_KEY_PAIR_KEY));
+            }
 
-        // Rotate the protocol key pair
-        config.rotate_protocol_key_pair();
+            // Rotate the protocol key pair
+            config.rotate_protocol_key_pair();
 
-        // Update only the relevant fields in the original Value
-        if let serde_yaml::Value::Mapping(ref mut map) = original_value {
             // Update protocol_key_pair
             if let Ok(protocol_key_pair) = serde_yaml::to_value(&config.protocol_key_pair) {
                 map.insert(
@@ -255,7 +326,62 @@ impl StorageNodeConfig {
             map.remove(serde_yaml::Value::String(
                 NEXT_PROTOCOL_KEY_PAIR_KEY.to_string(),
             ));
-        }
+            Ok(())
+        })
+    }
+
+    /// Sets the next protocol key pair and persists the config to disk.
+    ///
+    /// This is the first step of a protocol key rotation. Once the public key of the next key
+    /// pair has been registered on-chain and becomes active at the start of an epoch, the node
+    /// switches to it using [`Self::rotate_protocol_key_pair_persist`].
+    ///
+    /// Fails if a next protocol key pair is already set, unless `force` is true.
+    pub fn set_next_protocol_key_pair_persist(
+        path: impl AsRef<Path>,
+        next_protocol_key_pair: PathOrInPlace<ProtocolKeyPair>,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        Self::update_persisted_config(path, |config, map| {
+            if config.next_protocol_key_pair.is_some() && !force {
+                return Err(anyhow::anyhow!(
+                    "{} is already set, use the '--force' option to overwrite it",
+                    NEXT_PROTOCOL_KEY_PAIR_KEY
+                ));
+            }
+            config.next_protocol_key_pair = Some(next_protocol_key_pair);
+
+            // Serialize the full config to use the same representation as the config file.
+            let key = serde_yaml::Value::String(NEXT_PROTOCOL_KEY_PAIR_KEY.to_string());
+            let next_protocol_key_pair = serde_yaml::to_value(&*config)
+                .map_err(|e| anyhow::anyhow!("failed to serialize config: {e}"))?
+                .get(&key)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("{} is not set", NEXT_PROTOCOL_KEY_PAIR_KEY))?;
+            map.insert(key, next_protocol_key_pair);
+            Ok(())
+        })
+    }
+
+    /// Loads the config at `path`, applies `update` to both the parsed config and the raw YAML
+    /// mapping, and atomically writes the mapping back to disk.
+    ///
+    /// Only the entries modified by `update` change in the file; all other fields are preserved
+    /// as they were written.
+    fn update_persisted_config<F>(path: impl AsRef<Path>, update: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&mut StorageNodeConfig, &mut serde_yaml::Mapping) -> anyhow::Result<()>,
+    {
+        // Load config from path, preserving the raw Value to maintain all fields
+        let config_str = std::fs::read_to_string(path.as_ref())?;
+        let mut original_value: serde_yaml::Value = serde_yaml::from_str(&config_str)?;
+        let mut config: StorageNodeConfig = serde_yaml::from_str(&config_str)?;
+
+        // Update only the relevant fields in the original Value
+        let serde_yaml::Value::Mapping(ref mut map) = original_value else {
+            return Err(anyhow::anyhow!("config file is not a YAML mapping"));
+        };
+        update(&mut config, map)?;
 
         // Write to temporary file first
         let temp_path = path.as_ref().with_extension("tmp");
@@ -277,7 +403,9 @@ impl StorageNodeConfig {
 
     /// Loads the keys from disk into memory.
     pub fn load_keys(&mut self) -> Result<(), anyhow::Error> {
-        self.protocol_key_pair.load()?;
+        if let Some(protocol_key_pair) = self.protocol_key_pair.as_mut() {
+            protocol_key_pair.load()?;
+        }
         if let Some(next_protocol_key_pair) = self.next_protocol_key_pair.as_mut() {
             next_protocol_key_pair.load()?;
         }
@@ -300,13 +428,69 @@ impl StorageNodeConfig {
     ///
     /// # Panics
     ///
-    /// Panics if the key has not yet been loaded from disk.
+    /// Panics if no protocol key pair is configured or if the key has not yet been loaded from
+    /// disk.
     pub fn protocol_key_pair(&self) -> &ProtocolKeyPair {
         self.protocol_key_pair
+            .as_ref()
+            .expect("a protocol key pair should be configured")
             .get()
             .expect("key pair should already be loaded into memory")
     }
 
+    /// Returns the protocol key pair, or an error if no protocol key pair is configured because
+    /// protocol messages are signed by the remote protocol signer.
+    ///
+    /// `operation` describes the operation requiring the local key pair for the error message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key pair is configured but has not yet been loaded from disk.
+    pub fn local_protocol_key_pair(&self, operation: &str) -> anyhow::Result<&ProtocolKeyPair> {
+        if self.protocol_key_pair.is_none() {
+            return Err(anyhow!(
+                "{operation} requires a local protocol key pair, but only a remote protocol \
+                signer is configured"
+            ));
+        }
+        Ok(self.protocol_key_pair())
+    }
+
+    /// Returns the public key used in Walrus protocol messages.
+    ///
+    /// This is the public key of the protocol key pair or, if no key pair is configured, the
+    /// public key configured for the remote protocol signer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither is configured, or if both are configured and differ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the protocol key pair is configured but has not yet been loaded from disk.
+    pub fn protocol_public_key(&self) -> anyhow::Result<&PublicKey> {
+        let remote_public_key = self
+            .remote_protocol_signer
+            .as_ref()
+            .and_then(|signer| signer.public_key.as_ref());
+        match (&self.protocol_key_pair, remote_public_key) {
+            (Some(_), Some(remote_public_key))
+                if self.protocol_key_pair().public() != remote_public_key =>
+            {
+                Err(anyhow!(
+                    "the public key of the remote protocol signer differs from the one of the \
+                    protocol key pair"
+                ))
+            }
+            (Some(_), _) => Ok(self.protocol_key_pair().public()),
+            (None, Some(remote_public_key)) => Ok(remote_public_key),
+            (None, None) => Err(anyhow!(
+                "either a protocol key pair or the public key of the remote protocol signer must \
+                be configured"
+            )),
+        }
+    }
+
     /// Returns the next protocol key pair, if it exists.
     ///
     /// # Panics
@@ -319,27 +503,135 @@ impl StorageNodeConfig {
         })
     }
 
+    /// Compares this configuration to a newly loaded configuration and classifies the fields that
+    /// differ between the two into those that can be applied to a running node and those that
+    /// require a restart.
+    pub fn reload_changes(&self, new: &StorageNodeConfig) -> ConfigReloadChanges {
+        let mut changes = ConfigReloadChanges::default();
+
+        macro_rules! classify {
+            ($list:ident: $($field:ident),+ $(,)?) => {
+                $(
+                    if self.$field != new.$field {
+                        changes.$list.push(stringify!($field));
+                    }
+                )+
+            };
+        }
+
+        if self.blob_recovery.max_concurrent_blob_syncs
+            != new.blob_recovery.max_concurrent_blob_syncs
+            || self.blob_recovery.max_concurrent_sliver_syncs
+                != new.blob_recovery.max_concurrent_sliver_syncs
+        {
+            changes.runtime.push("blob_recovery.max_concurrent_*_syncs");
+        }
+        if self.blob_recovery.max_recovery_bytes_per_second
+            != new.blob_recovery.max_recovery_bytes_per_second
+        {
+            changes
+                .runtime
+                .push("blob_recovery.max_recovery_bytes_per_second");
+        }
+        // Compare the committee service config with the runtime settings of the current one, such
+        // that only changes of the remaining settings require a restart.
+        let mut committee_service_config = new.blob_recovery.committee_service_config.clone();
+        committee_service_config
+            .update_runtime_settings(&self.blob_recovery.committee_service_config);
+        if committee_service_config != new.blob_recovery.committee_service_config {
+            changes
+                .runtime
+                .push("blob_recovery.committee_service_config");
+        }
+        if self.blob_recovery.max_proof_cache_elements != new.blob_recovery.max_proof_cache_elements
+            || self.blob_recovery.max_recovery_symbol_cache_elements
+                != new.blob_recovery.max_recovery_symbol_cache_elements
+            || self.blob_recovery.recovery_symbol_cache_ttl
+                != new.blob_recovery.recovery_symbol_cache_ttl
+            || self.blob_recovery.recovery_order != new.blob_recovery.recovery_order
+            || self.blob_recovery.committee_service_config != committee_service_config
+        {
+            changes.restart_required.push("blob_recovery");
+        }
+
+        let mut db_config = new.db_config.clone();
+        db_config.orphaned_sliver_collection = self.db_config.orphaned_sliver_collection.clone();
+        if self.db_config.orphaned_sliver_collection != new.db_config.orphaned_sliver_collection {
+            changes.runtime.push("db_config.orphaned_sliver_collection");
+        }
+        if self.db_config != db_config {
+            changes.restart_required.push("db_config");
+        }
+
+        classify!(
+            runtime: shard_sync_config,
+            log_filter,
+            trace_sampling,
+            maintenance_mode,
+            // The following are picked up by the config synchronizer.
+            name,
+            commission_rate,
+            voting_params,
+            metadata,
+        );
+        classify!(
+            restart_required: storage_path,
+            blocklist_path,
+            protocol_key_pair,
+            next_protocol_key_pair,
+            network_key_pair,
+            public_host,
+            public_port,
+            metrics_address,
+            metrics_listeners,
+            rest_api_address,
+            rest_api_listeners,
+            rest_server,
+            rest_graceful_shutdown_period_secs,
+            sui,
+            tls,
+            event_processor_config,
+            use_legacy_event_provider,
+            disable_event_blob_writer,
+            metrics_push,
+            config_synchronizer,
+            storage_node_cap,
+            register_on_first_start,
+            num_uncertified_blob_threshold,
+            balance_check,
+            event_lag_monitor,
+            dead_letter_queue,
+            thread_pool,
+            remote_protocol_signer,
+        );
+
+        changes
+    }
+
     /// Converts the configuration into registration parameters used for node registration.
-    pub fn to_registration_params(&self) -> NodeRegistrationParams {
+    ///
+    /// The public key is taken from the protocol key pair or the remote protocol signer, see
+    /// [`Self::protocol_public_key`].
+    pub fn to_registration_params(&self) -> anyhow::Result<NodeRegistrationParams> {
         let network_key_pair = self.network_key_pair();
-        let protocol_key_pair = self.protocol_key_pair();
+        let public_key = self.protocol_public_key()?.clone();
         let public_port = self.public_port;
         let public_address = if let Ok(ip_addr) = IpAddr::from_str(&self.public_host) {
             NetworkAddress(SocketAddr::new(ip_addr, public_port).to_string())
         } else {
             NetworkAddress(format!("{}:{}", self.public_host, public_port))
         };
-        NodeRegistrationParams {
+        Ok(NodeRegistrationParams {
             name: self.name.clone(),
             network_address: public_address,
-            public_key: protocol_key_pair.public().clone(),
+            public_key,
             network_public_key: network_key_pair.public().clone(),
             commission_rate: self.commission_rate,
             storage_price: self.voting_params.storage_price,
             write_price: self.voting_params.write_price,
             node_capacity: self.voting_params.node_capacity,
             metadata: self.metadata.clone(),
-        }
+        })
     }
 
     /// Calculates the next commission rate for the storage node.
@@ -394,6 +686,22 @@ impl StorageNodeConfig {
     }
 }
 
+/// The configuration fields that changed when reloading the configuration of a running node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigReloadChanges {
+    /// Changed fields that can be applied without restarting the node.
+    pub runtime: Vec<&'static str>,
+    /// Changed fields that only take effect after the node is restarted.
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ConfigReloadChanges {
+    /// Returns true if no fields changed.
+    pub fn is_empty(&self) -> bool {
+        self.runtime.is_empty() && self.restart_required.is_empty()
+    }
+}
+
 /// The commission rate data for the storage node.
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct CommissionRateData {
@@ -451,6 +759,26 @@ pub struct MetricsPushConfig {
     pub labels: Option<HashMap<String, String>>,
 }
 
+/// Configuration of a remote service signing protocol messages with the node's protocol key.
+///
+/// See [`RemoteProtocolSigner`][crate::node::RemoteProtocolSigner] for the API expected from the
+/// service.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RemoteProtocolSignerConfig {
+    /// The URL to which signing requests are sent.
+    pub url: Url,
+    /// The public key of the key pair with which the service signs.
+    ///
+    /// Required if no `protocol_key_pair` is configured for the node.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub public_key: Option<PublicKey>,
+    /// The timeout for signing requests.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "timeout_secs", default = "defaults::remote_signer_timeout")]
+    pub timeout: Duration,
+}
+
 /// Identifies a role to attach to metrics.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ServiceRole {
@@ -509,7 +837,7 @@ impl MetricsPushConfig {
 }
 
 /// Configuration for TLS of the rest API.
-#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(default)]
 pub struct TlsConfig {
     /// Do not use TLS on the REST API.
@@ -521,6 +849,18 @@ pub struct TlsConfig {
     pub certificate_path: Option<PathBuf>,
 }
 
+/// Configuration of an additional listener of the REST API.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ListenerConfig {
+    /// The socket address on which to listen.
+    pub address: SocketAddr,
+    /// The TLS settings of the listener.
+    ///
+    /// Defaults to the `tls` settings of the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+}
+
 /// Configuration of a Walrus storage node.
 #[serde_as]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -533,6 +873,22 @@ pub struct BlobRecoveryConfig {
     /// The maximum number of elements stored in the proof cache for serving remote recovery
     /// requests.
     pub max_proof_cache_elements: u64,
+    /// The maximum number of recovery symbols cached for serving remote recovery requests.
+    pub max_recovery_symbol_cache_elements: u64,
+    /// The time for which a recovery symbol constructed for a remote recovery request is cached.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "recovery_symbol_cache_ttl_secs")]
+    pub recovery_symbol_cache_ttl: Duration,
+    /// The order in which blobs are recovered when the node recovers all certified blobs, for
+    /// example after it was offline for multiple epochs.
+    pub recovery_order: RecoveryOrder,
+    /// The maximum rate, in bytes per second, at which metadata and recovery symbols are fetched
+    /// from other storage nodes to recover slivers. The rate is not limited if unset.
+    ///
+    /// The limit applies to the background recovery of this node and is independent of the
+    /// traffic of clients reading from or writing to the node.
+    #[serde(skip_serializing_if = "defaults::is_none")]
+    pub max_recovery_bytes_per_second: Option<u64>,
     /// Configuration of the committee service timeouts and retries
     #[serde(flatten)]
     pub committee_service_config: CommitteeServiceConfig,
@@ -544,11 +900,31 @@ impl Default for BlobRecoveryConfig {
             max_concurrent_blob_syncs: 100,
             max_concurrent_sliver_syncs: 2_000,
             max_proof_cache_elements: 7_500,
+            max_recovery_symbol_cache_elements: 10_000,
+            recovery_symbol_cache_ttl: Duration::from_secs(60),
+            recovery_order: RecoveryOrder::default(),
+            max_recovery_bytes_per_second: None,
             committee_service_config: CommitteeServiceConfig::default(),
         }
     }
 }
 
+/// The order in which a storage node recovers blobs during node recovery.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryOrder {
+    /// Recovers the blobs in the order of their blob IDs.
+    #[default]
+    BlobId,
+    /// Recovers the most recently certified blobs first.
+    RecentlyCertifiedFirst,
+    /// Recovers the blobs that expire the soonest first.
+    ///
+    /// Blobs that are only stored as deletable blobs are recovered after all other blobs, as the
+    /// expiry of deletable blobs is not tracked by the node.
+    ExpiringFirst,
+}
+
 /// Configuration of a Walrus storage node.
 #[serde_as]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -583,6 +959,50 @@ pub struct CommitteeServiceConfig {
     /// Use the experimental batch recovery service endpoint.
     // TODO: Remove (WAL-594).
     pub experimental_batch_symbol_recovery: bool,
+    /// The number of requests over which the fraction of authentication and epoch errors is
+    /// evaluated, to detect that the local view of the committee is stale.
+    pub stale_committee_min_requests: NonZeroUsize,
+    /// The percentage of requests failing with authentication or epoch errors, at which the local
+    /// view of the committee is suspected to be stale and the latest committees are re-fetched.
+    ///
+    /// Set to 0 to disable stale-committee detection.
+    pub stale_committee_failure_percent: u8,
+    /// The minimum time between two re-fetches of the latest committees due to a suspected stale
+    /// committee.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "stale_committee_min_reconciliation_interval_secs")]
+    pub stale_committee_min_reconciliation_interval: Duration,
+    /// The maximum size in bytes of a response body read from a remote storage node, such as
+    /// metadata, slivers, or shard-sync batches. Larger responses are rejected without being read
+    /// in full.
+    pub max_node_response_size_bytes: u64,
+    /// The maximum number of epochs to walk back when looking for a node from which to sync a
+    /// shard.
+    ///
+    /// The owner of the shard in the epoch before the one being synced is contacted first; if it is
+    /// unavailable, the owners in earlier epochs for which the committee is known are contacted.
+    pub shard_sync_max_epochs_back: NonZeroU32,
+    /// Whether to fall back to the owners of shards in the previous committee when metadata or
+    /// recovery symbols for a blob certified before the current epoch cannot be obtained from the
+    /// current committee.
+    ///
+    /// After an epoch change, members of the current committee may not yet have finished syncing
+    /// the shards they gained, while the previous owners of these shards still hold the data.
+    pub read_fallback_to_previous_committee: bool,
+    /// The maximum age of the snapshot of the committees persisted in the storage directory, up to
+    /// which the snapshot is used while the committees cannot be looked up on chain.
+    ///
+    /// The snapshot is never used if this is not set. The age is measured from the last time the
+    /// committees were successfully looked up on chain.
+    #[serde_as(as = "Option<DurationSeconds<u64>>")]
+    #[serde(rename = "committee_snapshot_max_staleness_secs")]
+    pub committee_snapshot_max_staleness: Option<Duration>,
+    /// Whether to send sync shard requests with a nonce and an expiry (version 2), which protects
+    /// them against replays.
+    ///
+    /// Nodes running older versions cannot decode such requests, so this should only be enabled
+    /// once all nodes from which shards are synced support them.
+    pub send_sync_shard_replay_protection: bool,
 }
 
 impl Default for CommitteeServiceConfig {
@@ -596,10 +1016,32 @@ impl Default for CommitteeServiceConfig {
             max_concurrent_metadata_requests: NonZeroUsize::new(1).unwrap(),
             node_connect_timeout: Duration::from_secs(1),
             experimental_batch_symbol_recovery: true,
+            stale_committee_min_requests: NonZeroUsize::new(20).unwrap(),
+            stale_committee_failure_percent: 50,
+            stale_committee_min_reconciliation_interval: Duration::from_secs(60),
+            max_node_response_size_bytes: 1 << 30,
+            shard_sync_max_epochs_back: NonZeroU32::new(3).unwrap(),
+            read_fallback_to_previous_committee: true,
+            committee_snapshot_max_staleness: Some(Duration::from_secs(60 * 60)),
+            send_sync_shard_replay_protection: false,
         }
     }
 }
 
+impl CommitteeServiceConfig {
+    /// Sets the settings that can be changed while the node is running to those of `new`.
+    ///
+    /// These are the retry intervals and the request timeouts, which are read for each request.
+    /// All other settings are only read when the committee service is created.
+    pub fn update_runtime_settings(&mut self, new: &Self) {
+        self.retry_interval_min = new.retry_interval_min;
+        self.retry_interval_max = new.retry_interval_max;
+        self.metadata_request_timeout = new.metadata_request_timeout;
+        self.sliver_request_timeout = new.sliver_request_timeout;
+        self.invalidity_sync_timeout = new.invalidity_sync_timeout;
+    }
+}
+
 /// Configuration for Walrus storage node shard synchronization.
 #[serde_as]
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -629,6 +1071,27 @@ pub struct ShardSyncConfig {
     #[serde_as(as = "DurationSeconds<u64>")]
     #[serde(rename = "shard_sync_retry_switch_to_recovery_interval_secs")]
     pub shard_sync_retry_switch_to_recovery_interval: Duration,
+    /// Whether to verify that a synced shard stores both slivers of all certified blobs before
+    /// marking it as active. Missing slivers found during verification are recovered.
+    pub verify_shard_after_sync: bool,
+    /// Whether to push the metadata of all certified blobs to the nodes that join the committee
+    /// and obtain shards from this node during epoch change, so that they do not need to fetch
+    /// the metadata blob by blob.
+    pub push_metadata_to_new_owners: bool,
+    /// The maximum rate, in bytes per second, at which slivers are fetched and metadata is pushed
+    /// across all concurrent shard syncs. The rate is not limited if unset.
+    #[serde(skip_serializing_if = "defaults::is_none")]
+    pub max_sync_bytes_per_second: Option<u64>,
+    /// The maximum time until the expiry of a sync shard request served by this node.
+    ///
+    /// Requests that expire later are rejected. The nonces of served requests are remembered until
+    /// the requests expire, to reject replayed requests.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "max_sync_shard_request_validity_secs")]
+    pub max_sync_shard_request_validity: Duration,
+    /// Whether to reject sync shard requests without a nonce and an expiry, which are sent by
+    /// nodes running older versions.
+    pub require_sync_shard_replay_protection: bool,
 }
 
 impl Default for ShardSyncConfig {
@@ -642,6 +1105,11 @@ impl Default for ShardSyncConfig {
             max_concurrent_metadata_fetch: 10,
             shard_sync_concurrency: 10,
             shard_sync_retry_switch_to_recovery_interval: Duration::from_secs(2 * 60 * 60), // 2hr
+            verify_shard_after_sync: true,
+            push_metadata_to_new_owners: true,
+            max_sync_bytes_per_second: None,
+            max_sync_shard_request_validity: Duration::from_secs(15 * 60),
+            require_sync_shard_replay_protection: false,
         }
     }
 }
@@ -667,6 +1135,24 @@ pub mod defaults {
     pub const BALANCE_CHECK_FREQUENCY: Duration = Duration::from_secs(60 * 60);
     /// SUI MIST threshold under which balance checks log a warning.
     pub const BALANCE_CHECK_WARNING_THRESHOLD_MIST: u64 = 5_000_000_000;
+    /// Default interval at which the lag of event processing is checked.
+    pub const EVENT_LAG_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+    /// Default event-processing lag above which a warning is logged.
+    pub const EVENT_LAG_WARNING_THRESHOLD: Duration = Duration::from_secs(5 * 60);
+    /// Default event-processing lag above which an error is logged.
+    pub const EVENT_LAG_ERROR_THRESHOLD: Duration = Duration::from_secs(30 * 60);
+    /// Default number of attempts to process a blob event before moving it to the dead-letter
+    /// queue.
+    pub const DEAD_LETTER_MAX_ATTEMPTS: u32 = 5;
+    /// Default time to wait between attempts to process a blob event.
+    pub const DEAD_LETTER_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+    /// Default timeout for requests to the remote protocol signer.
+    pub const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Returns the default timeout for requests to the remote protocol signer.
+    pub fn remote_signer_timeout() -> Duration {
+        REMOTE_SIGNER_TIMEOUT
+    }
 
     /// Returns the default metrics port.
     pub fn metrics_port() -> u16 {
@@ -947,6 +1433,18 @@ pub struct RestServerConfig {
     /// Configuration for incoming HTTP/2 connections.
     #[serde(flatten, skip_serializing_if = "defaults::is_default")]
     pub http2_config: Http2Config,
+    /// Bearer token required to access the administrative endpoints of the REST API, such as
+    /// pinning blobs.
+    ///
+    /// If None, the administrative endpoints are disabled.
+    #[serde(skip_serializing_if = "defaults::is_none")]
+    pub admin_api_token: Option<String>,
+    /// Dedicated listeners for the administrative endpoints.
+    ///
+    /// If not empty, the administrative endpoints are only served on these listeners, for example
+    /// on a loopback or management interface, instead of on the listeners of the REST API.
+    #[serde(skip_serializing_if = "defaults::is_default")]
+    pub admin_listeners: Vec<ListenerConfig>,
 }
 
 /// Configuration of the HTTP/2 connections established by the REST API.
@@ -990,6 +1488,15 @@ pub struct BalanceCheckConfig {
     pub interval: Duration,
     /// The amount of MIST for which a lower balance triggers a warning.
     pub warning_threshold_mist: u64,
+    /// The amount of MIST for which a lower balance triggers an error.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub error_threshold_mist: Option<u64>,
+    /// The amount of FROST for which a lower WAL balance triggers a warning.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub wal_warning_threshold_frost: Option<u64>,
+    /// The amount of FROST for which a lower WAL balance triggers an error.
+    #[serde(default, skip_serializing_if = "defaults::is_none")]
+    pub wal_error_threshold_frost: Option<u64>,
 }
 
 impl Default for BalanceCheckConfig {
@@ -997,6 +1504,117 @@ impl Default for BalanceCheckConfig {
         Self {
             interval: defaults::BALANCE_CHECK_FREQUENCY,
             warning_threshold_mist: defaults::BALANCE_CHECK_WARNING_THRESHOLD_MIST,
+            error_threshold_mist: None,
+            wal_warning_threshold_frost: None,
+            wal_error_threshold_frost: None,
+        }
+    }
+}
+
+impl From<&BalanceCheckConfig> for BalanceMonitorConfig {
+    fn from(config: &BalanceCheckConfig) -> Self {
+        Self {
+            interval: config.interval,
+            sui: BalanceThresholds {
+                warning: Some(config.warning_threshold_mist),
+                error: config.error_threshold_mist,
+            },
+            wal: BalanceThresholds {
+                warning: config.wal_warning_threshold_frost,
+                error: config.wal_error_threshold_frost,
+            },
+        }
+    }
+}
+
+/// Configuration for monitoring the lag of event processing.
+///
+/// The lag is the difference between the latest checkpoint on chain and the latest checkpoint whose
+/// events have all been processed by the node, both in the number of checkpoints and in time. As
+/// every checkpoint is processed, regardless of whether it contains Walrus events, the lag does not
+/// grow while no events are emitted. The lag is only monitored if the node obtains events from
+/// checkpoints, i.e., if the event processor is used.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventLagMonitorConfig {
+    /// The interval at which the lag is checked.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "interval_secs")]
+    pub interval: Duration,
+    /// A lag above which a warning is logged.
+    #[serde_as(as = "Option<DurationSeconds<u64>>")]
+    #[serde(rename = "warning_threshold_secs")]
+    pub warning_threshold: Option<Duration>,
+    /// A lag above which an error is logged.
+    #[serde_as(as = "Option<DurationSeconds<u64>>")]
+    #[serde(rename = "error_threshold_secs")]
+    pub error_threshold: Option<Duration>,
+}
+
+impl Default for EventLagMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: defaults::EVENT_LAG_CHECK_INTERVAL,
+            warning_threshold: Some(defaults::EVENT_LAG_WARNING_THRESHOLD),
+            error_threshold: Some(defaults::EVENT_LAG_ERROR_THRESHOLD),
+        }
+    }
+}
+
+/// The severity of an event-processing lag with respect to the thresholds of an
+/// [`EventLagMonitorConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EventLagStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl EventLagMonitorConfig {
+    /// Classifies the lag according to the configured thresholds.
+    pub(crate) fn status(&self, lag: Duration) -> EventLagStatus {
+        if self
+            .error_threshold
+            .is_some_and(|threshold| lag > threshold)
+        {
+            EventLagStatus::Error
+        } else if self
+            .warning_threshold
+            .is_some_and(|threshold| lag > threshold)
+        {
+            EventLagStatus::Warning
+        } else {
+            EventLagStatus::Ok
+        }
+    }
+}
+
+/// Configuration for retrying blob events that fail to be processed.
+///
+/// A blob event that still fails after the maximum number of attempts is moved to a persisted
+/// dead-letter queue, from which it can be inspected and retried through the administrative API,
+/// and processing continues with the subsequent events.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeadLetterQueueConfig {
+    /// The number of attempts to process a blob event before it is moved to the dead-letter queue.
+    ///
+    /// If zero, blob events are never moved to the dead-letter queue, and an event that fails to be
+    /// processed stops event processing.
+    pub max_attempts: u32,
+    /// The time to wait between attempts.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "retry_interval_secs")]
+    pub retry_interval: Duration,
+}
+
+impl Default for DeadLetterQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: defaults::DEAD_LETTER_MAX_ATTEMPTS,
+            retry_interval: defaults::DEAD_LETTER_RETRY_INTERVAL,
         }
     }
 }
@@ -1052,6 +1670,8 @@ mod tests {
                 backoff_config: Default::default(),
                 gas_budget: None,
                 rpc_fallback_config: None,
+                graphql_url: None,
+                quorum_read_config: None,
             }),
             config_synchronizer: ConfigSynchronizerConfig {
                 interval: Duration::from_secs(defaults::CONFIG_SYNCHRONIZER_INTERVAL_SECS),
@@ -1117,6 +1737,66 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn protocol_public_key_can_be_configured_for_the_remote_signer() -> TestResult {
+        let key_pair = test_utils::protocol_key_pair();
+        let remote_signer = |public_key: Option<&ProtocolKeyPair>| {
+            Some(RemoteProtocolSignerConfig {
+                url: Url::parse("http://localhost:9000/sign").expect("valid URL"),
+                timeout: Duration::from_secs(5),
+                public_key: public_key.map(|key_pair| key_pair.public().clone()),
+            })
+        };
+        let mut config = StorageNodeConfig {
+            protocol_key_pair: None,
+            ..Default::default()
+        };
+        assert!(config.protocol_public_key().is_err());
+
+        config.remote_protocol_signer = remote_signer(None);
+        assert!(config.protocol_public_key().is_err());
+
+        config.remote_protocol_signer = remote_signer(Some(&key_pair));
+        assert_eq!(config.protocol_public_key()?, key_pair.public());
+
+        config.protocol_key_pair = Some(PathOrInPlace::InPlace(key_pair.clone()));
+        assert_eq!(config.protocol_public_key()?, key_pair.public());
+
+        config.remote_protocol_signer = remote_signer(Some(&ProtocolKeyPair::generate()));
+        assert!(config.protocol_public_key().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn registration_params_can_be_created_with_only_a_remote_signer() -> TestResult {
+        let key_pair = test_utils::protocol_key_pair();
+        let mut config = StorageNodeConfig {
+            protocol_key_pair: None,
+            network_key_pair: PathOrInPlace::InPlace(test_utils::network_key_pair()),
+            remote_protocol_signer: Some(RemoteProtocolSignerConfig {
+                url: Url::parse("http://localhost:9000/sign").expect("valid URL"),
+                timeout: Duration::from_secs(5),
+                public_key: Some(key_pair.public().clone()),
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            &config.to_registration_params()?.public_key,
+            key_pair.public()
+        );
+        let error = config
+            .local_protocol_key_pair("signing the proof of possession")
+            .expect_err("no local key pair is configured");
+        assert!(error.to_string().contains("remote protocol signer"));
+
+        config.remote_protocol_signer = None;
+        assert!(config.to_registration_params().is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn loads_base64_protocol_keypair() -> TestResult {
         let key = test_utils::protocol_key_pair();
@@ -1281,7 +1961,7 @@ mod tests {
             name: "new-name".to_string(),
             public_host: "192.168.1.1".to_string(),
             public_port: 9090,
-            protocol_key_pair: PathOrInPlace::InPlace(test_utils::protocol_key_pair()),
+            protocol_key_pair: Some(PathOrInPlace::InPlace(test_utils::protocol_key_pair())),
             network_key_pair: PathOrInPlace::InPlace(test_utils::network_key_pair()),
             voting_params: new_voting_params,
             metadata: new_metadata,
@@ -1451,6 +2131,99 @@ mod tests {
         test_cases
     }
 
+    #[test]
+    fn reload_changes_separates_runtime_and_restart_changes() {
+        let config = create_test_config();
+        assert!(config.reload_changes(&config.clone()).is_empty());
+
+        let mut new_config = config.clone();
+        new_config.log_filter = Some("info,walrus_service=debug".to_string());
+        new_config.shard_sync_config.shard_sync_concurrency += 1;
+        new_config.trace_sampling.default_rate = 0.1;
+        new_config.storage_path = PathBuf::from("/opt/walrus/other-db");
+        new_config.network_key_pair = PathOrInPlace::InPlace(NetworkKeyPair::generate());
+
+        let changes = config.reload_changes(&new_config);
+        assert_eq!(
+            changes.runtime,
+            vec!["shard_sync_config", "log_filter", "trace_sampling"]
+        );
+        assert_eq!(
+            changes.restart_required,
+            vec!["storage_path", "network_key_pair"]
+        );
+    }
+
+    #[test]
+    fn reload_changes_applies_recovery_rate_at_runtime() {
+        let config = create_test_config();
+        let mut new_config = config.clone();
+        new_config.blob_recovery.max_recovery_bytes_per_second = Some(10_000_000);
+
+        let changes = config.reload_changes(&new_config);
+        assert_eq!(
+            changes.runtime,
+            vec!["blob_recovery.max_recovery_bytes_per_second"]
+        );
+        assert!(changes.restart_required.is_empty());
+    }
+
+    #[test]
+    fn reload_changes_applies_timeouts_and_orphan_collection_at_runtime() {
+        let config = create_test_config();
+        let mut new_config = config.clone();
+        let committee_service_config = &mut new_config.blob_recovery.committee_service_config;
+        committee_service_config.metadata_request_timeout = Duration::from_secs(10);
+        committee_service_config.retry_interval_max = Duration::from_secs(60);
+        new_config
+            .db_config
+            .orphaned_sliver_collection
+            .collection_interval = Duration::from_secs(10 * 60);
+
+        let changes = config.reload_changes(&new_config);
+        assert_eq!(
+            changes.runtime,
+            vec![
+                "blob_recovery.committee_service_config",
+                "db_config.orphaned_sliver_collection"
+            ]
+        );
+        assert!(changes.restart_required.is_empty());
+
+        new_config
+            .blob_recovery
+            .committee_service_config
+            .node_connect_timeout = Duration::from_secs(10);
+        new_config.db_config.sliver_tiering.enabled = true;
+        let changes = config.reload_changes(&new_config);
+        assert_eq!(changes.restart_required, vec!["blob_recovery", "db_config"]);
+    }
+
+    #[test]
+    fn event_lag_status_respects_thresholds() {
+        let config = EventLagMonitorConfig {
+            warning_threshold: Some(Duration::from_secs(10)),
+            error_threshold: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        assert_eq!(config.status(Duration::from_secs(10)), EventLagStatus::Ok);
+        assert_eq!(
+            config.status(Duration::from_secs(11)),
+            EventLagStatus::Warning
+        );
+        assert_eq!(
+            config.status(Duration::from_secs(61)),
+            EventLagStatus::Error
+        );
+
+        let config = EventLagMonitorConfig {
+            warning_threshold: None,
+            error_threshold: None,
+            ..Default::default()
+        };
+        assert_eq!(config.status(Duration::MAX), EventLagStatus::Ok);
+    }
+
     #[test]
     fn test_rotate_protocol_key_pair_persist() -> TestResult {
         // Create temporary directory for test
@@ -1462,7 +2235,7 @@ mod tests {
         create_protocol_key_file(&next_key_path)?;
 
         let config = StorageNodeConfig {
-            protocol_key_pair: PathOrInPlace::from_path(key_path),
+            protocol_key_pair: Some(PathOrInPlace::from_path(key_path)),
             next_protocol_key_pair: Some(PathOrInPlace::from_path(next_key_path.clone())),
             name: "test-node".to_string(),
             storage_path: temp_dir.path().to_path_buf(),
@@ -1491,7 +2264,7 @@ mod tests {
         // Verify that the protocol key pair was rotated
         assert_eq!(
             loaded_config.protocol_key_pair,
-            PathOrInPlace::from_path(next_key_path.clone()),
+            Some(PathOrInPlace::from_path(next_key_path.clone())),
             "Protocol key pair should be rotated to next key pair"
         );
         assert_eq!(
@@ -1502,6 +2275,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_set_next_protocol_key_pair_persist() -> TestResult {
+        let temp_dir = TempDir::new()?;
+        let config_path = temp_dir.path().join("config.yaml");
+        let key_path = temp_dir.path().join("protocol_key.key");
+        let next_key_path = temp_dir.path().join("next_protocol_key.key");
+        create_protocol_key_file(&key_path)?;
+        create_protocol_key_file(&next_key_path)?;
+
+        let config = StorageNodeConfig {
+            protocol_key_pair: Some(PathOrInPlace::from_path(key_path.clone())),
+            name: "test-node".to_string(),
+            storage_path: temp_dir.path().to_path_buf(),
+            network_key_pair: PathOrInPlace::InPlace(test_utils::network_key_pair()),
+            ..Default::default()
+        };
+        std::fs::write(&config_path, serde_yaml::to_string(&config)?)?;
+
+        StorageNodeConfig::set_next_protocol_key_pair_persist(
+            &config_path,
+            PathOrInPlace::from_path(next_key_path.clone()),
+            false,
+        )?;
+
+        let loaded_config: StorageNodeConfig =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path)?)?;
+        assert_eq!(
+            loaded_config.protocol_key_pair,
+            Some(PathOrInPlace::from_path(key_path))
+        );
+        assert_eq!(
+            loaded_config.next_protocol_key_pair,
+            Some(PathOrInPlace::from_path(next_key_path.clone()))
+        );
+        assert_eq!(loaded_config.name, config.name);
+
+        // Overwriting an existing next key pair requires `force`.
+        assert!(StorageNodeConfig::set_next_protocol_key_pair_persist(
+            &config_path,
+            PathOrInPlace::from_path(next_key_path.clone()),
+            false,
+        )
+        .is_err());
+        StorageNodeConfig::set_next_protocol_key_pair_persist(
+            &config_path,
+            PathOrInPlace::from_path(next_key_path),
+            true,
+        )?;
+
+        Ok(())
+    }
+
     fn create_protocol_key_file(path: &Path) -> Result<(), anyhow::Error> {
         let mut file = std::fs::File::create(path)
             .with_context(|| format!("Cannot create the keyfile '{}'", path.display()))?;
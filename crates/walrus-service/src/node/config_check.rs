@@ -0,0 +1,438 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validation of storage node configurations before the node is started.
+
+use std::{
+    fmt::{self, Display},
+    fs::OpenOptions,
+    path::Path,
+};
+
+use serde::Serialize;
+use walrus_sui::{
+    client::{retry_client::RetriableSuiClient, ReadClient as _, SuiReadClient},
+    config::WalletConfig,
+};
+
+use super::config::StorageNodeConfig;
+use crate::common::{config::SuiConfig, utils};
+
+/// The name of the file created to check that the storage directory is writable.
+const WRITE_CHECK_FILE_NAME: &str = ".walrus-check-config";
+
+/// The outcome of a single configuration check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+    /// The check passed.
+    Ok,
+    /// The check passed, but the node may not work as expected.
+    Warning,
+    /// The check failed; the node cannot run with this configuration.
+    Failed,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Ok => write!(f, "OK"),
+            CheckStatus::Warning => write!(f, "WARN"),
+            CheckStatus::Failed => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// The result of a single configuration check.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    /// The name of the check.
+    pub name: &'static str,
+    /// The outcome of the check.
+    pub status: CheckStatus,
+    /// A human-readable description of the outcome.
+    pub message: String,
+}
+
+/// A report containing the results of all checks run on a storage node configuration.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigCheckReport {
+    /// The results of the individual checks, in the order in which they were run.
+    pub checks: Vec<CheckResult>,
+}
+
+impl ConfigCheckReport {
+    /// Returns true if any of the checks failed.
+    pub fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.status == CheckStatus::Failed)
+    }
+
+    fn push(&mut self, name: &'static str, status: CheckStatus, message: impl Into<String>) {
+        self.checks.push(CheckResult {
+            name,
+            status,
+            message: message.into(),
+        });
+    }
+
+    fn ok(&mut self, name: &'static str, message: impl Into<String>) {
+        self.push(name, CheckStatus::Ok, message);
+    }
+
+    fn warn(&mut self, name: &'static str, message: impl Into<String>) {
+        self.push(name, CheckStatus::Warning, message);
+    }
+
+    fn fail(&mut self, name: &'static str, message: impl Into<String>) {
+        self.push(name, CheckStatus::Failed, message);
+    }
+}
+
+impl Display for ConfigCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{:>4}] {}: {}", check.status, check.name, check.message)?;
+        }
+        let count = |status| {
+            self.checks
+                .iter()
+                .filter(|check| check.status == status)
+                .count()
+        };
+        write!(
+            f,
+            "{} checks: {} passed, {} warnings, {} failed",
+            self.checks.len(),
+            count(CheckStatus::Ok),
+            count(CheckStatus::Warning),
+            count(CheckStatus::Failed),
+        )
+    }
+}
+
+/// Runs all checks on the storage node configuration at `config_path`.
+///
+/// Checks that require a valid configuration or a reachable Sui RPC node are skipped if the
+/// respective earlier check fails. A warning is emitted if the free space in the storage
+/// directory is below `min_free_space` bytes.
+pub async fn check_config(config_path: &Path, min_free_space: u64) -> ConfigCheckReport {
+    let mut report = ConfigCheckReport::default();
+
+    let mut config: StorageNodeConfig = match utils::load_from_yaml_with_env_overrides(
+        config_path,
+        utils::NODE_CONFIG_ENV_PREFIX,
+    ) {
+        Ok(config) => {
+            report.ok("config", format!("parsed '{}'", config_path.display()));
+            config
+        }
+        Err(error) => {
+            report.fail("config", format!("{error:#}"));
+            return report;
+        }
+    };
+
+    check_keys(&mut config, &mut report);
+    check_storage_path(&config.storage_path, min_free_space, &mut report);
+    if let Some(blocklist_path) = config.blocklist_path.as_ref() {
+        if blocklist_path.is_file() {
+            report.ok("blocklist", format!("found '{}'", blocklist_path.display()));
+        } else {
+            report.warn(
+                "blocklist",
+                format!("'{}' does not exist yet", blocklist_path.display()),
+            );
+        }
+    }
+
+    match config.sui.as_ref() {
+        Some(sui_config) => check_sui(sui_config, &config, &mut report).await,
+        None => report.fail("sui", "the configuration does not contain a `sui` section"),
+    }
+
+    report
+}
+
+fn check_keys(config: &mut StorageNodeConfig, report: &mut ConfigCheckReport) {
+    if let Err(error) = config.load_keys() {
+        report.fail("keys", format!("failed to load the node's keys: {error:#}"));
+        return;
+    }
+    let public_key = match config.protocol_public_key() {
+        Ok(public_key) => public_key.clone(),
+        Err(error) => {
+            report.fail("protocol key", format!("{error:#}"));
+            return;
+        }
+    };
+    report.ok("protocol key", format!("public key {public_key}"));
+    if let Some(next_protocol_key_pair) = config.next_protocol_key_pair() {
+        if next_protocol_key_pair.public() == &public_key {
+            report.fail(
+                "next protocol key",
+                "the next protocol key is identical to the current protocol key",
+            );
+        } else {
+            report.ok(
+                "next protocol key",
+                format!("public key {}", next_protocol_key_pair.public()),
+            );
+        }
+    }
+    report.ok(
+        "network key",
+        format!("public key {}", config.network_key_pair().public()),
+    );
+}
+
+fn check_storage_path(storage_path: &Path, min_free_space: u64, report: &mut ConfigCheckReport) {
+    // The node creates the storage directory on startup, so check the closest existing ancestor.
+    let Some(existing_path) = storage_path
+        .ancestors()
+        .map(|path| {
+            if path.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                path
+            }
+        })
+        .find(|path| path.exists())
+    else {
+        report.fail(
+            "storage path",
+            format!("no ancestor of '{}' exists", storage_path.display()),
+        );
+        return;
+    };
+    if !existing_path.is_dir() {
+        report.fail(
+            "storage path",
+            format!("'{}' is not a directory", existing_path.display()),
+        );
+        return;
+    }
+
+    let write_check_path = existing_path.join(WRITE_CHECK_FILE_NAME);
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&write_check_path)
+    {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&write_check_path);
+            if existing_path == storage_path {
+                report.ok(
+                    "storage path",
+                    format!("'{}' is writable", storage_path.display()),
+                );
+            } else {
+                report.ok(
+                    "storage path",
+                    format!(
+                        "'{}' will be created in '{}'",
+                        storage_path.display(),
+                        existing_path.display()
+                    ),
+                );
+            }
+        }
+        Err(error) => report.fail(
+            "storage path",
+            format!("'{}' is not writable: {error}", existing_path.display()),
+        ),
+    }
+
+    match available_space(existing_path) {
+        Ok(Some(available)) if available < min_free_space => report.warn(
+            "free space",
+            format!("{available} bytes available, below the minimum of {min_free_space} bytes"),
+        ),
+        Ok(Some(available)) => report.ok("free space", format!("{available} bytes available")),
+        Ok(None) => report.warn("free space", "not supported on this platform"),
+        Err(error) => report.warn("free space", format!("failed to query free space: {error}")),
+    }
+}
+
+async fn check_sui(
+    sui_config: &SuiConfig,
+    config: &StorageNodeConfig,
+    report: &mut ConfigCheckReport,
+) {
+    let sui_client =
+        match RetriableSuiClient::new_for_rpc(&sui_config.rpc, sui_config.backoff_config.clone())
+            .await
+        {
+            Ok(client) => client,
+            Err(error) => {
+                report.fail(
+                    "sui rpc",
+                    format!("failed to connect to '{}': {error}", sui_config.rpc),
+                );
+                return;
+            }
+        };
+    match sui_client.get_chain_identifier().await {
+        Ok(chain_id) => report.ok(
+            "sui rpc",
+            format!(
+                "'{}' is reachable, chain identifier {chain_id}",
+                sui_config.rpc
+            ),
+        ),
+        Err(error) => {
+            report.fail(
+                "sui rpc",
+                format!("'{}' is not reachable: {error}", sui_config.rpc),
+            );
+            return;
+        }
+    }
+
+    let read_client = match SuiReadClient::new(sui_client, &sui_config.contract_config).await {
+        Ok(read_client) => read_client,
+        Err(error) => {
+            report.fail(
+                "system object",
+                format!(
+                    "failed to read system object {}: {error}",
+                    sui_config.contract_config.system_object
+                ),
+            );
+            return;
+        }
+    };
+    match read_client.current_epoch().await {
+        Ok(epoch) => report.ok(
+            "system object",
+            format!(
+                "found system object {}, current epoch {epoch}",
+                sui_config.contract_config.system_object
+            ),
+        ),
+        Err(error) => report.fail(
+            "system object",
+            format!(
+                "failed to read the current epoch from system object {}: {error}",
+                sui_config.contract_config.system_object
+            ),
+        ),
+    }
+
+    let address = match WalletConfig::load_wallet_context(Some(&sui_config.wallet_config))
+        .and_then(|mut wallet| wallet.active_address())
+    {
+        Ok(address) => {
+            report.ok("wallet", format!("active address {address}"));
+            address
+        }
+        Err(error) => {
+            report.fail("wallet", format!("failed to load the wallet: {error:#}"));
+            return;
+        }
+    };
+
+    match read_client.get_address_capability_object(address).await {
+        Ok(Some(capability)) => match config.storage_node_cap {
+            Some(configured) if configured != capability.id => report.fail(
+                "node capability",
+                format!(
+                    "configured capability {configured} differs from the capability {} owned by \
+                    the wallet",
+                    capability.id
+                ),
+            ),
+            _ => report.ok(
+                "node capability",
+                format!(
+                    "node {} with capability {}",
+                    capability.node_id, capability.id
+                ),
+            ),
+        },
+        Ok(None) if config.register_on_first_start => report.ok(
+            "node capability",
+            "the node is not registered yet and registers itself when it is started",
+        ),
+        Ok(None) => report.warn(
+            "node capability",
+            "the wallet does not own a storage node capability; the node is not registered yet",
+        ),
+        Err(error) => report.warn(
+            "node capability",
+            format!("failed to look up the storage node capability: {error}"),
+        ),
+    }
+}
+
+/// Returns the number of bytes available to unprivileged users on the file system containing
+/// `path`, or `None` if this is not supported on the current platform.
+#[cfg(unix)]
+fn available_space(path: &Path) -> std::io::Result<Option<u64>> {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt as _};
+
+    let c_path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` points to writable memory of
+    // the correct size.
+    if unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: `statvfs` succeeded and therefore initialized `stat`.
+    let stat = unsafe { stat.assume_init() };
+    // The field types differ between platforms.
+    #[allow(clippy::unnecessary_cast)]
+    Ok(Some(stat.f_bavail as u64 * stat.f_frsize as u64))
+}
+
+#[cfg(not(unix))]
+fn available_space(_path: &Path) -> std::io::Result<Option<u64>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    #[test]
+    fn storage_path_check_accepts_missing_directory_in_writable_parent() -> TestResult {
+        let temp_dir = TempDir::new()?;
+        let mut report = ConfigCheckReport::default();
+
+        check_storage_path(&temp_dir.path().join("db"), 0, &mut report);
+
+        assert!(!report.has_failures(), "{report}");
+        assert!(!temp_dir.path().join(WRITE_CHECK_FILE_NAME).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn storage_path_check_fails_for_file() -> TestResult {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("file");
+        std::fs::write(&file_path, b"")?;
+        let mut report = ConfigCheckReport::default();
+
+        check_storage_path(&file_path, 0, &mut report);
+
+        assert!(report.has_failures(), "{report}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn check_config_fails_for_missing_file() -> TestResult {
+        let temp_dir = TempDir::new()?;
+
+        let report = check_config(&temp_dir.path().join("missing.yaml"), 0).await;
+
+        assert!(report.has_failures());
+        assert_eq!(report.checks.len(), 1);
+        Ok(())
+    }
+}
@@ -16,7 +16,7 @@ use super::{
     contract_service::SystemContractService,
     SyncNodeConfigError,
 };
-use crate::utils::load_from_yaml;
+use crate::utils::{load_from_yaml_with_env_overrides, NODE_CONFIG_ENV_PREFIX};
 
 /// Trait for loading config from some source.
 #[async_trait]
@@ -25,7 +25,7 @@ pub trait ConfigLoader: std::fmt::Debug + Sync + Send {
     async fn load_storage_node_config(&self) -> anyhow::Result<StorageNodeConfig>;
 }
 
-/// Loads config from a file.
+/// Loads config from a file, applying overrides from `WALRUS_NODE__*` environment variables.
 #[derive(Debug, Clone)]
 pub struct StorageNodeConfigLoader {
     config_path: PathBuf,
@@ -41,7 +41,8 @@ impl StorageNodeConfigLoader {
 #[async_trait]
 impl ConfigLoader for StorageNodeConfigLoader {
     async fn load_storage_node_config(&self) -> anyhow::Result<StorageNodeConfig> {
-        let mut config: StorageNodeConfig = load_from_yaml(&self.config_path)?;
+        let mut config: StorageNodeConfig =
+            load_from_yaml_with_env_overrides(&self.config_path, NODE_CONFIG_ENV_PREFIX)?;
         config.load_keys()?;
         Ok(config)
     }
@@ -209,7 +210,7 @@ mod tests {
         create_network_key_file(&network_key_path)?;
 
         let config = StorageNodeConfig {
-            protocol_key_pair: PathOrInPlace::from_path(key_path),
+            protocol_key_pair: Some(PathOrInPlace::from_path(key_path)),
             next_protocol_key_pair: None,
             name: "test-node".to_string(),
             storage_path: temp_dir.path().to_path_buf(),
@@ -311,7 +312,7 @@ mod tests {
 
         // Create storage node config with TLS enabled.
         let config = StorageNodeConfig {
-            protocol_key_pair: PathOrInPlace::from_path(key_path),
+            protocol_key_pair: Some(PathOrInPlace::from_path(key_path)),
             next_protocol_key_pair: None,
             name: "test-node".to_string(),
             storage_path: temp_dir.path().to_path_buf(),
@@ -11,20 +11,17 @@ use std::{
 
 use anyhow::Context as _;
 use async_trait::async_trait;
-use prometheus::{
-    core::{AtomicU64, GenericGaugeVec},
-    Registry,
-};
+use chrono::{DateTime, Utc};
+use prometheus::Registry;
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use sui_types::base_types::ObjectID;
-use tokio::{sync::Mutex as TokioMutex, task::JoinSet, time::MissedTickBehavior};
-use tracing::Instrument as _;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use tokio::{sync::Mutex as TokioMutex, task::JoinSet};
 use walrus_core::{messages::InvalidBlobCertificate, Epoch, PublicKey};
 use walrus_sui::{
     client::{
         BlobObjectMetadata,
-        CoinType,
         FixedSystemParameters,
+        QuorumReadClient,
         ReadClient as _,
         SuiClientError,
         SuiContractClient,
@@ -40,14 +37,16 @@ use walrus_utils::backoff::{self, ExponentialBackoff};
 
 use super::{
     committee::CommitteeService,
-    config::{defaults, CommissionRateData, StorageNodeConfig, SyncedNodeConfigSet},
+    config::{BalanceCheckConfig, CommissionRateData, StorageNodeConfig, SyncedNodeConfigSet},
     errors::SyncNodeConfigError,
 };
-use crate::common::config::SuiConfig;
+use crate::common::{
+    balance_monitor::{monitor_balances, BalanceMonitorConfig, BalanceMonitorMetrics},
+    config::SuiConfig,
+};
 
 const MIN_BACKOFF: Duration = Duration::from_secs(1);
 const MAX_BACKOFF: Duration = Duration::from_secs(3600);
-type UIntGaugeVec = GenericGaugeVec<AtomicU64>;
 
 enum ProtocolKeyAction {
     UpdateRemoteNextPublicKey(PublicKey),
@@ -72,6 +71,17 @@ pub trait SystemContractService: std::fmt::Debug + Sync + Send {
     /// Returns the current epoch.
     fn current_epoch(&self) -> Epoch;
 
+    /// Returns the current time according to the on-chain clock.
+    ///
+    /// This is the timestamp of the latest checkpoint known to the full node.
+    async fn current_chain_time(&self) -> Result<DateTime<Utc>, anyhow::Error>;
+
+    /// Returns the sequence number of the latest checkpoint known to the full node.
+    async fn latest_checkpoint_sequence_number(&self) -> Result<u64, anyhow::Error>;
+
+    /// Returns the time of the checkpoint with the given sequence number.
+    async fn checkpoint_time(&self, sequence_number: u64) -> Result<DateTime<Utc>, anyhow::Error>;
+
     /// Returns the non-variable system parameters.
     async fn fixed_system_parameters(&self) -> Result<FixedSystemParameters, anyhow::Error>;
 
@@ -124,30 +134,22 @@ pub trait SystemContractService: std::fmt::Debug + Sync + Send {
     async fn last_certified_event_blob(&self) -> Result<Option<EventBlob>, SuiClientError>;
 }
 
-walrus_utils::metrics::define_metric_set! {
-    #[namespace = "walrus"]
-    struct ContractServiceMetrics {
-        #[help = "The observed balance (in MIST) of a SUI address"]
-        sui_balance_mist: UIntGaugeVec["sui_address"],
-    }
-}
-
 /// Builder for creating a new [`SuiSystemContractService`].
 #[derive(Debug, Clone)]
 pub struct SuiSystemContractServiceBuilder {
     seed: u64,
-    balance_check_frequency: Duration,
-    balance_check_warning_threshold: u64,
+    balance_check: BalanceCheckConfig,
     metrics_registry: Option<Registry>,
+    quorum_read_client: Option<QuorumReadClient>,
 }
 
 impl Default for SuiSystemContractServiceBuilder {
     fn default() -> Self {
         Self {
             seed: rand::thread_rng().gen(),
-            balance_check_frequency: defaults::BALANCE_CHECK_FREQUENCY,
-            balance_check_warning_threshold: defaults::BALANCE_CHECK_WARNING_THRESHOLD_MIST,
+            balance_check: BalanceCheckConfig::default(),
             metrics_registry: None,
+            quorum_read_client: None,
         }
     }
 }
@@ -159,24 +161,30 @@ impl SuiSystemContractServiceBuilder {
         self
     }
 
-    /// Perform a SUI balance check with the specified frequency.
+    /// Perform a SUI and WAL balance check with the specified frequency.
     ///
-    /// Logs the available balance as an info or warning log message based on the threshold
-    /// specified with [`Self::balance_check_warning_threshold`].
+    /// Logs the available balances as info, warning, or error log messages based on the
+    /// thresholds specified with [`Self::balance_check_config`].
     ///
-    /// If a metrics registry was provided then the balance is also exported as a metric.
+    /// If a metrics registry was provided then the balances are also exported as metrics.
     ///
-    /// The default is [`defaults::BALANCE_CHECK_FREQUENCY`].
+    /// The default is [`super::config::defaults::BALANCE_CHECK_FREQUENCY`].
     pub fn balance_check_frequency(&mut self, frequency: Duration) -> &mut Self {
-        self.balance_check_frequency = frequency;
+        self.balance_check.interval = frequency;
         self
     }
 
     /// Logs a warning if the SUI balance is below the provided MIST threshold.
     ///
-    /// The default is [`defaults::BALANCE_CHECK_WARNING_THRESHOLD_MIST`].
+    /// The default is [`super::config::defaults::BALANCE_CHECK_WARNING_THRESHOLD_MIST`].
     pub fn balance_check_warning_threshold(&mut self, mist_threshold: u64) -> &mut Self {
-        self.balance_check_warning_threshold = mist_threshold;
+        self.balance_check.warning_threshold_mist = mist_threshold;
+        self
+    }
+
+    /// Sets the frequency and all SUI and WAL thresholds of the balance check.
+    pub fn balance_check_config(&mut self, config: BalanceCheckConfig) -> &mut Self {
+        self.balance_check = config;
         self
     }
 
@@ -188,14 +196,34 @@ impl SuiSystemContractServiceBuilder {
         self
     }
 
+    /// Cross-checks the reads of the epoch state and the system parameters against multiple full
+    /// nodes with the provided client.
+    ///
+    /// By default, these reads are only issued to the full node of the contract client.
+    pub fn quorum_read_client(&mut self, client: QuorumReadClient) -> &mut Self {
+        self.quorum_read_client = Some(client);
+        self
+    }
+
     /// Creates a new [`SuiSystemContractService`] with a [`SuiContractClient`] constructed from
     /// the config.
+    ///
+    /// If the config contains a [`QuorumReadConfig`][crate::common::config::QuorumReadConfig],
+    /// the reads of the epoch state and the system parameters are cross-checked against the
+    /// configured full nodes.
     pub async fn build_from_config(
         &mut self,
         config: &SuiConfig,
         committee_service: Arc<dyn CommitteeService>,
     ) -> Result<SuiSystemContractService, anyhow::Error> {
-        Ok(self.build(config.new_contract_client().await?, committee_service))
+        let contract_client = config.new_contract_client().await?;
+        if let Some(quorum_read_config) = &config.quorum_read_config {
+            let quorum_read_client = quorum_read_config
+                .new_quorum_read_client(contract_client.read_client.as_ref().clone(), config)
+                .await?;
+            self.quorum_read_client(quorum_read_client);
+        }
+        Ok(self.build(contract_client, committee_service))
     }
 
     /// Creates a new [`SuiSystemContractService`] with the provided [`SuiContractClient`].
@@ -204,8 +232,10 @@ impl SuiSystemContractServiceBuilder {
         contract_client: SuiContractClient,
         committee_service: Arc<dyn CommitteeService>,
     ) -> SuiSystemContractService {
+        let address = contract_client.address();
         let mut service = SuiSystemContractService {
             read_client: contract_client.read_client.clone(),
+            quorum_read_client: self.quorum_read_client.take().map(Arc::new),
             contract_tx_client: Arc::new(TokioMutex::new(contract_client)),
             committee_service,
             rng: Arc::new(StdMutex::new(StdRng::seed_from_u64(self.seed))),
@@ -213,8 +243,8 @@ impl SuiSystemContractServiceBuilder {
         };
 
         service.start_balance_monitor(
-            self.balance_check_frequency,
-            self.balance_check_warning_threshold,
+            (&self.balance_check).into(),
+            address,
             self.metrics_registry
                 .as_ref()
                 .unwrap_or_else(|| prometheus::default_registry()),
@@ -232,6 +262,9 @@ pub struct SuiSystemContractService {
     // A client for reading from the contract. Note that reading from the contract can be done
     // concurrently.
     read_client: Arc<SuiReadClient>,
+    // A client for reads of the system state that are cross-checked against multiple full nodes,
+    // if configured.
+    quorum_read_client: Option<Arc<QuorumReadClient>>,
     committee_service: Arc<dyn CommitteeService>,
     rng: Arc<StdMutex<StdRng>>,
 
@@ -281,7 +314,7 @@ impl SuiSystemContractService {
         })
     }
 
-    /// Starts a background task to monitor SUI balance.
+    /// Starts a background task to monitor the SUI and WAL balances.
     ///
     /// # Panics
     ///
@@ -289,72 +322,24 @@ impl SuiSystemContractService {
     /// has been cloned.
     fn start_balance_monitor(
         &mut self,
-        frequency: Duration,
-        warning_threshold_mist: u64,
+        config: BalanceMonitorConfig,
+        address: SuiAddress,
         metrics_registry: &Registry,
     ) {
-        let metrics = ContractServiceMetrics::new(metrics_registry);
+        let metrics = BalanceMonitorMetrics::new(metrics_registry);
 
         let background_tasks = Arc::get_mut(&mut self.background_tasks)
             .expect("called from constructor so no clones yet exist");
 
-        background_tasks.spawn(monitor_sui_balance(
-            frequency,
-            warning_threshold_mist,
+        background_tasks.spawn(monitor_balances(
+            config,
             metrics,
-            self.contract_tx_client.clone(),
+            self.read_client.clone(),
+            address,
         ));
     }
 }
 
-async fn monitor_sui_balance(
-    frequency: Duration,
-    warning_threshold_mist: u64,
-    metrics: ContractServiceMetrics,
-    contract_client: Arc<TokioMutex<SuiContractClient>>,
-) {
-    tracing::info!(?frequency, %warning_threshold_mist, "starting monitor for SUI balance");
-    let mut interval = tokio::time::interval(frequency);
-    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
-
-    loop {
-        let _now = interval.tick().await;
-        let mut client = contract_client.lock().await;
-
-        let address = client.wallet_mut().active_address().ok();
-        let address = address
-            .as_ref()
-            .map(ToString::to_string)
-            .unwrap_or_default();
-        let span = tracing::info_span!("check_sui_balance", sui.address = %address);
-
-        async {
-            tracing::trace!("querying wallet SUI balance");
-
-            let balance_mist = match client.balance(CoinType::Sui).await {
-                Ok(balance_mist) => balance_mist,
-                Err(error) => {
-                    tracing::warn!(?error, "failed to get SUI balance, skipping interval");
-                    return; // Exit the async closure, does not exit the outer function.
-                }
-            };
-            tracing::info!(sui.balance_mist = %balance_mist, "retrieved sui balance");
-
-            walrus_utils::with_label!(metrics.sui_balance_mist, address).set(balance_mist);
-
-            if balance_mist <= warning_threshold_mist {
-                tracing::warn!(
-                    sui.balance_mist = %balance_mist,
-                    %warning_threshold_mist,
-                    "SUI balance is below warning threshold, please add SUI to your account"
-                );
-            }
-        }
-        .instrument(span)
-        .await
-    }
-}
-
 #[async_trait]
 impl SystemContractService for SuiSystemContractService {
     /// Syncs the node parameters with the on-chain values.
@@ -374,7 +359,7 @@ impl SystemContractService for SuiSystemContractService {
 
         let mut update_params = config.generate_update_params(&synced_config);
         let action = calculate_protocol_key_action(
-            config.protocol_key_pair().public().clone(),
+            config.protocol_public_key()?.clone(),
             config
                 .next_protocol_key_pair()
                 .map(|kp| kp.public().clone()),
@@ -548,15 +533,47 @@ impl SystemContractService for SuiSystemContractService {
     // contract client.
 
     async fn get_epoch_and_state(&self) -> Result<(Epoch, EpochState), anyhow::Error> {
-        let committees = self.read_client.get_committees_and_state().await?;
+        let committees = match &self.quorum_read_client {
+            Some(client) => client.get_committees_and_state().await?,
+            None => self.read_client.get_committees_and_state().await?,
+        };
         Ok((committees.current.epoch, committees.epoch_state))
     }
 
-    async fn fixed_system_parameters(&self) -> Result<FixedSystemParameters, anyhow::Error> {
+    async fn current_chain_time(&self) -> Result<DateTime<Utc>, anyhow::Error> {
+        let timestamp_ms = self
+            .read_client
+            .sui_client()
+            .get_latest_checkpoint_timestamp_ms()
+            .await
+            .context("failed to retrieve the latest checkpoint")?;
+        checkpoint_timestamp_to_date_time(timestamp_ms)
+    }
+
+    async fn latest_checkpoint_sequence_number(&self) -> Result<u64, anyhow::Error> {
         self.read_client
-            .fixed_system_parameters()
+            .sui_client()
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .context("failed to retrieve the latest checkpoint sequence number")
+    }
+
+    async fn checkpoint_time(&self, sequence_number: u64) -> Result<DateTime<Utc>, anyhow::Error> {
+        let timestamp_ms = self
+            .read_client
+            .sui_client()
+            .get_checkpoint_timestamp_ms(sequence_number)
             .await
-            .context("failed to retrieve system parameters")
+            .with_context(|| format!("failed to retrieve checkpoint {sequence_number}"))?;
+        checkpoint_timestamp_to_date_time(timestamp_ms)
+    }
+
+    async fn fixed_system_parameters(&self) -> Result<FixedSystemParameters, anyhow::Error> {
+        let parameters = match &self.quorum_read_client {
+            Some(client) => client.fixed_system_parameters().await,
+            None => self.read_client.fixed_system_parameters().await,
+        };
+        parameters.context("failed to retrieve system parameters")
     }
 
     async fn get_node_capability_object(
@@ -588,6 +605,14 @@ impl SystemContractService for SuiSystemContractService {
     }
 }
 
+/// Converts a checkpoint timestamp in milliseconds since the Unix epoch to a date.
+fn checkpoint_timestamp_to_date_time(timestamp_ms: u64) -> Result<DateTime<Utc>, anyhow::Error> {
+    let timestamp_ms =
+        i64::try_from(timestamp_ms).context("checkpoint timestamp is out of range")?;
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .context("checkpoint timestamp is not a valid date")
+}
+
 /// Calculates the protocol key action based on the local and remote public keys.
 #[tracing::instrument]
 fn calculate_protocol_key_action(
@@ -3,55 +3,75 @@
 
 //! Tools for inspecting and maintaining the RocksDB database.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{anyhow, Context as _, Result};
 use bincode::Options;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
+use prometheus::Registry;
 use rocksdb::{Options as RocksdbOptions, ReadOptions, DB};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sui_types::base_types::ObjectID;
-use typed_store::rocks::be_fix_int_ser;
-use walrus_core::{BlobId, BlobMetadata, Epoch, ShardIndex};
-
-use crate::node::{
-    events::{
-        event_blob_writer::{
-            attested_cf_name,
-            certified_cf_name,
-            failed_to_attest_cf_name,
-            pending_cf_name,
-            AttestedEventBlobMetadata,
-            CertifiedEventBlobMetadata,
-            FailedToAttestEventBlobMetadata,
-            PendingEventBlobMetadata,
+use tempfile::TempDir;
+use typed_store::rocks::{be_fix_int_ser, MetricConf};
+use walrus_core::{encoding::EncodingConfig, BlobId, BlobMetadata, Epoch, ShardIndex};
+use walrus_sdk::{api::ServiceResponse, client::Client};
+use walrus_sui::client::ReadClient as _;
+
+use crate::{
+    node::{
+        config::StorageNodeConfig,
+        events::{
+            event_blob_writer::{
+                attested_cf_name,
+                certified_cf_name,
+                failed_to_attest_cf_name,
+                pending_cf_name,
+                AttestedEventBlobMetadata,
+                CertifiedEventBlobMetadata,
+                FailedToAttestEventBlobMetadata,
+                PendingEventBlobMetadata,
+            },
+            event_processor::event_store_cf_name,
+            PositionedStreamEvent,
         },
-        event_processor::event_store_cf_name,
-        PositionedStreamEvent,
-    },
-    storage::{
-        blob_info::{
-            blob_info_cf_options,
-            per_object_blob_info_cf_options,
-            BlobInfo,
-            BlobInfoApi,
-            PerObjectBlobInfo,
+        server::{
+            STORAGE_BLOB_ENDPOINT,
+            STORAGE_COLUMN_FAMILIES_ENDPOINT,
+            STORAGE_EVENT_CURSOR_ENDPOINT,
+            STORAGE_SHARD_BLOBS_ENDPOINT,
         },
-        constants::{
-            aggregate_blob_info_cf_name,
-            metadata_cf_name,
-            per_object_blob_info_cf_name,
-            primary_slivers_column_family_name,
-            secondary_slivers_column_family_name,
+        storage::{
+            blob_info::{
+                blob_info_cf_options,
+                per_object_blob_info_cf_options,
+                BlobInfo,
+                BlobInfoApi,
+                PerObjectBlobInfo,
+            },
+            constants::{
+                aggregate_blob_info_cf_name,
+                metadata_cf_name,
+                per_object_blob_info_cf_name,
+                primary_slivers_column_family_name,
+                secondary_slivers_column_family_name,
+            },
+            export_shard,
+            import_shard,
+            metadata_options,
+            primary_slivers_column_family_options,
+            repair_storage,
+            secondary_slivers_column_family_options,
+            PrimarySliverData,
+            SecondarySliverData,
+            ShardArchiveSummary,
         },
-        metadata_options,
-        primary_slivers_column_family_options,
-        secondary_slivers_column_family_options,
-        PrimarySliverData,
-        SecondarySliverData,
+        DatabaseConfig,
+        Storage,
+        DEFAULT_SHARD_BLOBS_LIMIT,
     },
-    DatabaseConfig,
+    utils::load_from_yaml,
 };
 
 /// Database inspection and maintenance tools.
@@ -181,6 +201,48 @@ pub enum DbToolCommands {
         shard_index: u16,
     },
 
+    /// Export the slivers and metadata of a shard to a portable, checksummed archive.
+    ///
+    /// The storage node must be stopped during the export. The database is opened read-only.
+    ExportShard {
+        /// Path to the configuration file of the node, from which the database options and the
+        /// sliver encryption key are read.
+        #[clap(long)]
+        config_path: PathBuf,
+        /// Path to the RocksDB database directory [default: the `storage_path` of the node].
+        #[clap(long)]
+        db_path: Option<PathBuf>,
+        /// Shard index to export.
+        #[clap(long)]
+        shard_index: u16,
+        /// Path of the archive to create.
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Import a shard from an archive created with `export-shard`.
+    ///
+    /// The storage node must be stopped during the import. The archive, including the metadata of
+    /// all blobs, is verified before the database is modified. The number of shards required to
+    /// verify the metadata is read from the Sui network configured for the node.
+    ImportShard {
+        /// Path to the configuration file of the node, from which the database options, the
+        /// sliver encryption key, and the Sui network are read.
+        #[clap(long)]
+        config_path: PathBuf,
+        /// Path to the RocksDB database directory [default: the `storage_path` of the node].
+        #[clap(long)]
+        db_path: Option<PathBuf>,
+        /// Path of the archive to import.
+        #[clap(long)]
+        input: PathBuf,
+        /// Mark the shard as active after the import, so that the node does not sync it from
+        /// other nodes. Only use this if the archive was exported after the shard was locked on
+        /// the source node.
+        #[clap(long)]
+        mark_active: bool,
+    },
+
     /// Read event blob writer metadata from the RocksDB database.
     EventBlobWriter {
         /// Path to the RocksDB database directory.
@@ -260,6 +322,18 @@ impl DbToolCommands {
                 count,
                 shard_index,
             } => read_secondary_slivers(db_path, start_blob_id, count, shard_index),
+            Self::ExportShard {
+                config_path,
+                db_path,
+                shard_index,
+                output,
+            } => export_shard_archive(config_path, db_path, shard_index, output),
+            Self::ImportShard {
+                config_path,
+                db_path,
+                input,
+                mark_active,
+            } => import_shard_archive(config_path, db_path, input, mark_active),
             Self::EventBlobWriter { db_path, command } => match command {
                 EventBlobWriterCommands::ReadCertified => read_certified_event_blobs(db_path),
                 EventBlobWriterCommands::ReadAttested => read_attested_event_blobs(db_path),
@@ -607,6 +681,100 @@ fn read_secondary_slivers(
     Ok(())
 }
 
+/// Loads the configuration of the node and returns it together with the path of its database,
+/// unless the path is overridden by `db_path`.
+fn load_node_config(
+    config_path: &Path,
+    db_path: Option<PathBuf>,
+) -> Result<(StorageNodeConfig, PathBuf)> {
+    let config: StorageNodeConfig = load_from_yaml(config_path)?;
+    let db_path = db_path.unwrap_or_else(|| config.storage_path.clone());
+    Ok((config, db_path))
+}
+
+/// Opens the storage of a stopped node for writing, running any pending schema migrations.
+fn open_storage(db_path: &Path, db_config: DatabaseConfig) -> Result<Storage> {
+    let registry = Registry::new();
+    typed_store::metrics::DBMetrics::init(&registry);
+    Storage::open(db_path, db_config, MetricConf::default(), registry)
+}
+
+/// Opens the storage of a stopped node without modifying it.
+///
+/// The returned directory holds the files of the RocksDB secondary instances and must be kept
+/// until the storage is dropped.
+fn open_storage_read_only(db_path: &Path, db_config: DatabaseConfig) -> Result<(Storage, TempDir)> {
+    let registry = Registry::new();
+    typed_store::metrics::DBMetrics::init(&registry);
+    let secondary_dir = tempfile::tempdir()?;
+    let storage = Storage::open_read_only(db_path, secondary_dir.path(), db_config, registry)?;
+    Ok((storage, secondary_dir))
+}
+
+fn print_shard_archive_summary(summary: &ShardArchiveSummary) {
+    println!(
+        "Shard: {}, Metadata: {}, Primary slivers: {}, Secondary slivers: {}",
+        summary.shard_index,
+        summary.metadata_count,
+        summary.primary_sliver_count,
+        summary.secondary_sliver_count
+    );
+}
+
+fn export_shard_archive(
+    config_path: PathBuf,
+    db_path: Option<PathBuf>,
+    shard_index: u16,
+    output: PathBuf,
+) -> Result<()> {
+    let (config, db_path) = load_node_config(&config_path, db_path)?;
+    let (storage, _secondary_dir) = open_storage_read_only(&db_path, config.db_config)?;
+    let summary = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(export_shard(
+            &storage,
+            ShardIndex::from(shard_index),
+            &output,
+        ))?;
+
+    println!("Exported shard archive to {}", output.display());
+    print_shard_archive_summary(&summary);
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn import_shard_archive(
+    config_path: PathBuf,
+    db_path: Option<PathBuf>,
+    input: PathBuf,
+    mark_active: bool,
+) -> Result<()> {
+    let (config, db_path) = load_node_config(&config_path, db_path)?;
+    let n_shards = config
+        .sui
+        .as_ref()
+        .context("verifying the archive requires the `sui` section of the node configuration")?
+        .new_read_client()
+        .await?
+        .fixed_system_parameters()
+        .await
+        .context("unable to read the number of shards from Sui")?
+        .n_shards;
+    let storage = open_storage(&db_path, config.db_config)?;
+    let summary = import_shard(
+        &storage,
+        &input,
+        &EncodingConfig::new(n_shards),
+        mark_active,
+    )
+    .await?;
+
+    println!("Imported shard archive from {}", input.display());
+    print_shard_archive_summary(&summary);
+    Ok(())
+}
+
 fn read_certified_event_blobs(db_path: PathBuf) -> Result<()> {
     let db = DB::open_cf_for_read_only(
         &RocksdbOptions::default(),
@@ -725,3 +893,226 @@ fn read_failed_to_attest_event_blobs(db_path: PathBuf) -> Result<()> {
 
     Ok(())
 }
+
+/// Commands for inspecting and repairing the storage of a node.
+#[derive(Subcommand, Debug, Clone)]
+#[clap(rename_all = "kebab-case")]
+pub enum DbCommands {
+    /// Inspect the data stored by the node and print it as JSON.
+    ///
+    /// With `--db-path`, the database is opened read-only and read directly, which requires the
+    /// node to be stopped. If `--config-path` is provided as well, the database options and the
+    /// sliver encryption key are taken from the node's configuration. With only `--config-path`,
+    /// the administrative API of the running node is queried, which requires the
+    /// `admin_api_token` to be set in the node's REST server configuration.
+    Inspect {
+        #[clap(flatten)]
+        source: InspectSource,
+        #[command(subcommand)]
+        command: InspectCommands,
+    },
+
+    /// Repair the database of a stopped node.
+    ///
+    /// Runs RocksDB's repair on the database, moves entries that cannot be decoded to a
+    /// quarantine file, and rebuilds the indexes derived from other tables. Data that is
+    /// quarantined is treated as missing by the node. Back up the database before running the
+    /// repair.
+    Repair {
+        /// Path to the RocksDB database directory.
+        #[clap(long)]
+        db_path: PathBuf,
+        /// Path of the file to which the quarantined entries are appended, as JSON lines
+        /// [default: <DB_PATH>.quarantine.jsonl].
+        #[clap(long)]
+        quarantine_path: Option<PathBuf>,
+    },
+}
+
+/// Where to read the data to inspect from.
+#[derive(Args, Debug, Clone)]
+#[group(required = true, multiple = true)]
+pub struct InspectSource {
+    /// Path to the RocksDB database directory of a stopped node.
+    #[clap(long)]
+    db_path: Option<PathBuf>,
+    /// Path to the configuration file of the node.
+    #[clap(long)]
+    config_path: Option<PathBuf>,
+}
+
+/// The data to inspect.
+#[derive(Subcommand, Debug, Clone)]
+#[clap(rename_all = "kebab-case")]
+pub enum InspectCommands {
+    /// List the IDs of the blobs for which slivers are stored in a shard.
+    ShardBlobs {
+        /// Shard index to list the blobs of.
+        #[clap(long)]
+        shard_index: u16,
+        /// List the blobs after this blob ID, in URL-safe base64 format (no padding).
+        #[clap(long)]
+        after: Option<BlobId>,
+        /// Maximum number of blob IDs to list.
+        #[clap(long, default_value_t = DEFAULT_SHARD_BLOBS_LIMIT)]
+        limit: usize,
+    },
+
+    /// Show the metadata, blob info, and sizes of the slivers stored for a blob.
+    Blob {
+        /// Blob ID in URL-safe base64 format (no padding).
+        #[clap(long)]
+        blob_id: BlobId,
+    },
+
+    /// Show the stored event cursor.
+    EventCursor,
+
+    /// Show RocksDB's estimates of the sizes of the column families.
+    ColumnFamilies,
+}
+
+impl DbCommands {
+    /// Execute the database command.
+    pub fn execute(self) -> Result<()> {
+        match self {
+            Self::Inspect { source, command } => match (source.db_path, source.config_path) {
+                (Some(db_path), config_path) => inspect_db(db_path, config_path, command),
+                (None, Some(config_path)) => inspect_running_node(config_path, command),
+                (None, None) => unreachable!("clap requires one of the sources"),
+            },
+            Self::Repair {
+                db_path,
+                quarantine_path,
+            } => {
+                let quarantine_path = quarantine_path.unwrap_or_else(|| {
+                    let mut path = db_path.components().as_path().as_os_str().to_owned();
+                    path.push(".quarantine.jsonl");
+                    path.into()
+                });
+                repair_storage_db(db_path, quarantine_path)
+            }
+        }
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn inspect_db(
+    db_path: PathBuf,
+    config_path: Option<PathBuf>,
+    command: InspectCommands,
+) -> Result<()> {
+    let db_config = match config_path {
+        Some(config_path) => load_node_config(&config_path, None)?.0.db_config,
+        None => DatabaseConfig::default(),
+    };
+    let (storage, _secondary_dir) = open_storage_read_only(&db_path, db_config)?;
+    match command {
+        InspectCommands::ShardBlobs {
+            shard_index,
+            after,
+            limit,
+        } => {
+            let shard_index = ShardIndex(shard_index);
+            let shard_blobs = storage
+                .list_shard_blobs(shard_index, after, limit)
+                .await?
+                .ok_or_else(|| anyhow!("shard {shard_index} is not stored in the database"))?;
+            print_json(&shard_blobs)
+        }
+        InspectCommands::Blob { blob_id } => print_json(&storage.inspect_blob(&blob_id).await?),
+        InspectCommands::EventCursor => print_json(&storage.event_cursor_report()?),
+        InspectCommands::ColumnFamilies => print_json(&storage.column_family_sizes().await?),
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn repair_storage_db(db_path: PathBuf, quarantine_path: PathBuf) -> Result<()> {
+    let registry = Registry::new();
+    typed_store::metrics::DBMetrics::init(&registry);
+    let report = repair_storage(&db_path, &DatabaseConfig::default(), &quarantine_path).await?;
+    print_json(&report)?;
+    if !report.quarantined_entries.is_empty() {
+        println!(
+            "Quarantined entries were written to {}",
+            quarantine_path.display()
+        );
+    }
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn inspect_running_node(config_path: PathBuf, command: InspectCommands) -> Result<()> {
+    let mut config: StorageNodeConfig = load_from_yaml(&config_path)?;
+    let token = config
+        .rest_server
+        .admin_api_token
+        .clone()
+        .context("the administrative API requires `rest_server.admin_api_token` to be set")?;
+    config.network_key_pair.load()?;
+    // The administrative endpoints are only served on the dedicated listeners, if there are any.
+    let (address, tls) = match config.rest_server.admin_listeners.first() {
+        Some(listener) => (
+            listener.address.to_string(),
+            listener.tls.as_ref().unwrap_or(&config.tls),
+        ),
+        None => (
+            format!("{}:{}", config.public_host, config.public_port),
+            &config.tls,
+        ),
+    };
+    // The storage-node client only connects via HTTPS, so listeners without TLS are accessed
+    // with a plain HTTP client.
+    let (client, scheme) = if tls.disable_tls {
+        (reqwest::Client::builder().no_proxy().build()?, "http")
+    } else {
+        (
+            Client::for_storage_node(&address, config.network_key_pair().public())?.into_inner(),
+            "https",
+        )
+    };
+
+    let get = |path: String| {
+        let request = client
+            .get(format!("{scheme}://{address}{path}"))
+            .bearer_auth(&token);
+        async move {
+            let response: ServiceResponse<serde_json::Value> = request.send().await?.json().await?;
+            match response {
+                ServiceResponse::Success { data, .. } => Ok(data),
+                ServiceResponse::Error(status) => Err(anyhow!(
+                    "the node returned an error ({}): {}",
+                    status.code(),
+                    status.message()
+                )),
+            }
+        }
+    };
+
+    let data = match command {
+        InspectCommands::ShardBlobs {
+            shard_index,
+            after,
+            limit,
+        } => {
+            let mut path =
+                STORAGE_SHARD_BLOBS_ENDPOINT.replace("{shard_index}", &shard_index.to_string());
+            path.push_str(&format!("?limit={limit}"));
+            if let Some(after) = after {
+                path.push_str(&format!("&after={after}"));
+            }
+            get(path).await?
+        }
+        InspectCommands::Blob { blob_id } => {
+            get(STORAGE_BLOB_ENDPOINT.replace("{blob_id}", &blob_id.to_string())).await?
+        }
+        InspectCommands::EventCursor => get(STORAGE_EVENT_CURSOR_ENDPOINT.to_owned()).await?,
+        InspectCommands::ColumnFamilies => get(STORAGE_COLUMN_FAMILIES_ENDPOINT.to_owned()).await?,
+    };
+    print_json(&data)
+}
@@ -35,6 +35,38 @@ const MAX_BACKOFF: Duration = Duration::from_secs(300);
 /// Function returning the current time in Utc.
 type UtcNowFn = Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>;
 
+/// The source of the current time used to schedule epoch operations.
+#[derive(Clone)]
+enum EpochClock {
+    /// Uses the time of the on-chain clock, as reported by the contract service.
+    ///
+    /// The contracts check the time of calls against the on-chain clock, so scheduling against it
+    /// avoids calls that fail due to a skew between the local clock and the chain. The provided
+    /// function is used as a fallback if the on-chain time cannot be retrieved.
+    OnChain(UtcNowFn),
+    /// Uses only the provided function.
+    Local(UtcNowFn),
+}
+
+impl EpochClock {
+    /// Returns the current time according to this clock.
+    async fn now(&self, contract_service: &dyn SystemContractService) -> DateTime<Utc> {
+        match self {
+            EpochClock::OnChain(fallback) => match contract_service.current_chain_time().await {
+                Ok(chain_time) => chain_time,
+                Err(error) => {
+                    tracing::warn!(
+                        ?error,
+                        "failed to get the on-chain time, falling back to the local clock"
+                    );
+                    fallback()
+                }
+            },
+            EpochClock::Local(utc_now_fn) => utc_now_fn(),
+        }
+    }
+}
+
 /// Worker that drives epoch change by invoking Walrus contract calls.
 // TODO(jsmith): Add metrics for this module
 // - time that futures are scheduled for
@@ -46,27 +78,50 @@ pub(super) struct EpochChangeDriver {
     contract_service: Arc<dyn SystemContractService>,
     /// Epoch duration and the end time for the 0th epoch.
     system_parameters: FixedSystemParameters,
-    /// Function used to get the current time in Utc.
-    utc_now_fn: UtcNowFn,
+    /// The clock used to determine when scheduled operations are due.
+    clock: EpochClock,
 }
 
 impl EpochChangeDriver {
     /// Returns a new instance of the `EpochChangeDriver`.
+    ///
+    /// Operations are scheduled according to the on-chain clock, falling back to the local clock
+    /// if the on-chain time is unavailable.
     pub fn new(
         system_parameters: FixedSystemParameters,
         contract_service: Arc<dyn SystemContractService>,
         rng: StdRng,
     ) -> Self {
-        Self::new_with_time_provider(system_parameters, contract_service, rng, Arc::new(Utc::now))
+        Self::new_with_clock(
+            system_parameters,
+            contract_service,
+            rng,
+            EpochClock::OnChain(Arc::new(Utc::now)),
+        )
     }
 
     /// Returns a new instance of the `EpochChangeDriver` which uses `utc_now_fn` to get the current
     /// time when required.
+    #[cfg(test)]
     fn new_with_time_provider(
         system_parameters: FixedSystemParameters,
         contract_service: Arc<dyn SystemContractService>,
         rng: StdRng,
         utc_now_fn: UtcNowFn,
+    ) -> Self {
+        Self::new_with_clock(
+            system_parameters,
+            contract_service,
+            rng,
+            EpochClock::Local(utc_now_fn),
+        )
+    }
+
+    fn new_with_clock(
+        system_parameters: FixedSystemParameters,
+        contract_service: Arc<dyn SystemContractService>,
+        rng: StdRng,
+        clock: EpochClock,
     ) -> Self {
         Self {
             inner: Arc::new(Mutex::new(Box::pin(EpochChangeDriverInner {
@@ -77,7 +132,7 @@ impl EpochChangeDriver {
             }))),
             system_parameters,
             contract_service,
-            utc_now_fn,
+            clock,
         }
     }
 
@@ -151,7 +206,7 @@ impl EpochChangeDriver {
             },
             self.system_parameters.epoch_duration,
             self.contract_service.clone(),
-            self.utc_now_fn.clone(),
+            self.clock.clone(),
             &mut inner.rng,
         )
         .wait_then_call();
@@ -205,7 +260,7 @@ impl EpochChangeDriver {
             },
             self.system_parameters.epoch_duration,
             self.contract_service.clone(),
-            self.utc_now_fn.clone(),
+            self.clock.clone(),
             &mut inner.rng,
         )
         .wait_then_call();
@@ -259,7 +314,7 @@ impl std::fmt::Debug for EpochChangeDriver {
             .field("inner", &self.inner)
             .field("contract_service", &self.contract_service)
             .field("system_parameters", &self.system_parameters)
-            .field("utc_now_fn", &"UtcNowFn")
+            .field("clock", &"EpochClock")
             .finish()
     }
 }
@@ -372,7 +427,7 @@ struct ScheduledEpochOperation<T> {
     max_jitter: Duration,
 
     contract_service: Arc<dyn SystemContractService>,
-    time_fn: UtcNowFn,
+    clock: EpochClock,
     rng: StdRng,
 }
 
@@ -381,13 +436,13 @@ impl<T: EpochOperation> ScheduledEpochOperation<T> {
         operation: T,
         epoch_duration: Duration,
         contract_service: Arc<dyn SystemContractService>,
-        time_fn: UtcNowFn,
+        clock: EpochClock,
         rng: &mut StdRng,
     ) -> Self {
         Self {
             operation,
             contract_service,
-            time_fn,
+            clock,
             max_jitter: std::cmp::min(MAX_SCHEDULE_JITTER, epoch_duration / 10),
             rng: StdRng::seed_from_u64(rng.gen()),
         }
@@ -417,10 +472,8 @@ impl<T: EpochOperation> ScheduledEpochOperation<T> {
             "successfully retrieved current epoch and committee state"
         );
 
-        let Some(wait_duration) =
-            self.operation
-                .time_until_ready((self.time_fn)(), current_epoch, state)
-        else {
+        let now = self.clock.now(self.contract_service.as_ref()).await;
+        let Some(wait_duration) = self.operation.time_until_ready(now, current_epoch, state) else {
             tracing::debug!("operation signalled that it is already complete");
             return Ok(());
         };
@@ -765,6 +818,64 @@ mod tests {
             Ok(())
         }
 
+        #[tokio::test(start_paused = true)]
+        async fn waits_for_on_chain_clock_when_local_clock_is_ahead() -> TestResult {
+            let start = UtcInstant::now();
+
+            let upcoming_epoch: NonZero<Epoch> = nonzero!(2);
+            let voting_duration = default_voting_duration(EPOCH_DURATION);
+            let voting_duration_elapsed = voting_duration / 10;
+            let voting_duration_remaining = voting_duration - voting_duration_elapsed;
+            let epoch_change_completed_at = start.utc - voting_duration_elapsed;
+            // The local clock runs ahead of the on-chain clock by this amount.
+            let clock_skew = Duration::from_secs(600);
+
+            let mut service = MockSystemContractService::new();
+            // Voting must not be ended before the on-chain clock reaches the end of voting, even
+            // though the local clock reaches it earlier.
+            service.expect_end_voting().once().returning(move || {
+                let called_at = Instant::now();
+                let voting_can_end_at = start.instant + voting_duration_remaining + clock_skew;
+                assert!(called_at >= voting_can_end_at);
+                assert!(called_at <= voting_can_end_at + MAX_SCHEDULE_JITTER);
+                Ok(())
+            });
+            service
+                .expect_current_chain_time()
+                .returning(move || Ok(start.utc - clock_skew + start.instant.elapsed()));
+            service.expect_get_epoch_and_state().returning(move || {
+                Ok((
+                    upcoming_epoch.get() - 1,
+                    EpochState::EpochChangeDone(epoch_change_completed_at),
+                ))
+            });
+
+            let driver = EpochChangeDriver::new_with_clock(
+                FixedSystemParameters {
+                    n_shards: NonZeroU16::new(1000).expect("1000 > 0"),
+                    max_epochs_ahead: 200,
+                    epoch_duration: EPOCH_DURATION,
+                    epoch_zero_end: start.utc,
+                },
+                Arc::new(service),
+                StdRng::seed_from_u64(3),
+                EpochClock::OnChain(Arc::new(move || start.utc + start.instant.elapsed())),
+            );
+
+            driver.schedule_voting_end(upcoming_epoch);
+
+            let _ = tokio::time::timeout(
+                voting_duration_remaining + clock_skew + MAX_SCHEDULE_JITTER,
+                driver.run(),
+            )
+            .await;
+
+            // Drop the driver to ensure that all conditions of the mock service have been met.
+            drop(driver);
+
+            Ok(())
+        }
+
         async_param_test! {
             #[tokio::test(start_paused = true)]
             skips_voting_end -> TestResult: [
@@ -34,7 +34,7 @@ use walrus_sdk::{
 };
 use walrus_sui::client::SuiClientError;
 
-use crate::common::api::RestApiError;
+use crate::common::{api::RestApiError, config::InvalidSamplingRate};
 
 /// Type used for internal errors.
 pub type InternalError = anyhow::Error;
@@ -120,6 +120,12 @@ pub enum RetrieveMetadataError {
     #[rest_api_error(reason = "FORBIDDEN_BLOB", status = ApiStatusCode::UnavailableForLegalReasons)]
     Forbidden,
 
+    /// The stored metadata failed verification when read from disk. It was discarded and is being
+    /// recovered from other storage nodes.
+    #[error("the stored metadata is corrupted and is being recovered")]
+    #[rest_api_error(reason = "METADATA_CORRUPTED", status = ApiStatusCode::Unavailable)]
+    Corrupted,
+
     #[error(transparent)]
     #[rest_api_error(delegate)]
     Internal(#[from] InternalError),
@@ -139,6 +145,12 @@ pub enum RetrieveSliverError {
     #[rest_api_error(reason = "FORBIDDEN_BLOB", status = ApiStatusCode::UnavailableForLegalReasons)]
     Forbidden,
 
+    /// The stored sliver failed verification when read from disk. It was discarded and is being
+    /// recovered from other storage nodes.
+    #[error("the stored sliver is corrupted and is being recovered")]
+    #[rest_api_error(reason = "SLIVER_CORRUPTED", status = ApiStatusCode::Unavailable)]
+    Corrupted,
+
     /// The index of the identified sliver is out of range for the system.
     #[error("the requested sliver index is out of range: {0}")]
     #[rest_api_error(delegate)]
@@ -195,6 +207,16 @@ pub enum StoreMetadataError {
     #[rest_api_error(reason = "UNSUPPORTED_ENCODING_TYPE", status = ApiStatusCode::InvalidArgument)]
     UnsupportedEncodingType(EncodingType),
 
+    /// The node is in maintenance mode and temporarily does not accept new metadata.
+    #[error("the node is in maintenance mode; retry later")]
+    #[rest_api_error(reason = "MAINTENANCE_MODE", status = ApiStatusCode::Unavailable)]
+    MaintenanceMode,
+
+    /// The metadata cannot be stored, as the associated blob has been blocked on this storage node.
+    #[error("the blob for this metadata is blocked")]
+    #[rest_api_error(reason = "FORBIDDEN_BLOB", status = ApiStatusCode::UnavailableForLegalReasons)]
+    Forbidden,
+
     #[error(transparent)]
     #[rest_api_error(delegate)]
     Internal(#[from] InternalError),
@@ -278,6 +300,16 @@ pub enum StoreSliverError {
     #[rest_api_error(reason = "UNSUPPORTED_ENCODING_TYPE", status = ApiStatusCode::InvalidArgument)]
     UnsupportedEncodingType(EncodingType),
 
+    /// The node is in maintenance mode and temporarily does not accept new slivers.
+    #[error("the node is in maintenance mode; retry later")]
+    #[rest_api_error(reason = "MAINTENANCE_MODE", status = ApiStatusCode::Unavailable)]
+    MaintenanceMode,
+
+    /// The sliver cannot be stored, as the associated blob has been blocked on this storage node.
+    #[error("the blob for this sliver is blocked")]
+    #[rest_api_error(reason = "FORBIDDEN_BLOB", status = ApiStatusCode::UnavailableForLegalReasons)]
+    Forbidden,
+
     #[error(transparent)]
     #[rest_api_error(delegate)]
     Internal(#[from] InternalError),
@@ -306,6 +338,7 @@ impl From<RetrieveMetadataError> for InconsistencyProofError {
         match value {
             RetrieveMetadataError::Unavailable => Self::MissingMetadata,
             RetrieveMetadataError::Forbidden => Self::MissingMetadata,
+            RetrieveMetadataError::Corrupted => Self::MissingMetadata,
             RetrieveMetadataError::Internal(error) => Self::Internal(error),
         }
     }
@@ -319,6 +352,79 @@ pub enum BlobStatusError {
     Internal(#[from] InternalError),
 }
 
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum BlobPinningError {
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum DenyListError {
+    /// The node has no blocklist file configured, to which changes could be persisted.
+    #[error("no blocklist path is configured for this node")]
+    #[rest_api_error(
+        reason = "DENY_LIST_NOT_CONFIGURED", status = ApiStatusCode::FailedPrecondition,
+    )]
+    NotConfigured,
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] InternalError),
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum StorageInspectionError {
+    /// The shard is not stored on this node.
+    #[error("shard {0} is not stored on this node")]
+    #[rest_api_error(reason = "SHARD_NOT_STORED", status = ApiStatusCode::NotFound)]
+    ShardNotStored(ShardIndex),
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] InternalError),
+}
+
+/// Error returned when a request to the administrative API is not authenticated.
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum AdminAuthError {
+    /// The request does not carry the bearer token configured for the administrative API.
+    #[error("missing or invalid admin token")]
+    #[rest_api_error(reason = "INVALID_ADMIN_TOKEN", status = ApiStatusCode::Unauthenticated)]
+    InvalidToken,
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum TraceSamplingError {
+    /// A sampling rate is not between 0 and 1.
+    #[error(transparent)]
+    #[rest_api_error(reason = "INVALID_SAMPLING_RATE", status = ApiStatusCode::InvalidArgument)]
+    InvalidRate(#[from] InvalidSamplingRate),
+}
+
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum DeadLetterQueueError {
+    /// There is no event with the requested index in the dead-letter queue.
+    #[error("there is no event with index {0} in the dead-letter queue")]
+    #[rest_api_error(reason = "DEAD_LETTER_EVENT_NOT_FOUND", status = ApiStatusCode::NotFound)]
+    NotFound(u64),
+
+    /// Processing the event failed again; it remains in the dead-letter queue.
+    #[error("processing the event failed again: {0}")]
+    #[rest_api_error(reason = "EVENT_PROCESSING_FAILED", status = ApiStatusCode::Unavailable)]
+    ProcessingFailed(String),
+
+    #[error(transparent)]
+    #[rest_api_error(delegate)]
+    Internal(#[from] InternalError),
+}
+
 /// Error returned when the epoch in a request is invalid.
 #[derive(Debug, Clone, thiserror::Error, Serialize, RestApiError)]
 #[error("the request's epoch ({request_epoch}) is invalid, server epoch {server_epoch}")]
@@ -342,6 +448,14 @@ pub enum SyncShardServiceError {
     #[rest_api_error(reason = "REQUEST_UNAUTHORIZED", status = ApiStatusCode::PermissionDenied)]
     Unauthorized,
 
+    /// The client cannot sync the shard as the shard is not assigned to it in the requested epoch.
+    #[error(
+        "the client is not authorized to sync shard {0} as the shard is not assigned to it in \
+        the requested epoch"
+    )]
+    #[rest_api_error(reason = "REQUEST_UNAUTHORIZED", status = ApiStatusCode::PermissionDenied)]
+    NotShardOwner(ShardIndex),
+
     /// The client cannot initiate a sync as it is not a storage node in the current committee.
     #[error("verification of the request to start the sync failed: {0}")]
     #[rest_api_error(
@@ -353,6 +467,28 @@ pub enum SyncShardServiceError {
     #[rest_api_error(delegate)]
     ShardNotAssigned(#[from] ShardNotAssigned),
 
+    /// The request does not include a nonce and an expiry, which the server requires.
+    #[error("the sync shard request must include a nonce and an expiry")]
+    #[rest_api_error(
+        reason = "REPLAY_PROTECTION_REQUIRED", status = ApiStatusCode::InvalidArgument
+    )]
+    ReplayProtectionRequired,
+
+    /// The request has expired.
+    #[error("the sync shard request expired at {0}")]
+    #[rest_api_error(reason = "REQUEST_EXPIRED", status = ApiStatusCode::InvalidArgument)]
+    RequestExpired(u64),
+
+    /// The request expires later than the server accepts.
+    #[error("the sync shard request expires at {0}, which is later than accepted by the server")]
+    #[rest_api_error(reason = "REQUEST_EXPIRY_TOO_LATE", status = ApiStatusCode::InvalidArgument)]
+    RequestExpiryTooLate(u64),
+
+    /// A request with the same nonce was already served.
+    #[error("a sync shard request with the same nonce was already served")]
+    #[rest_api_error(reason = "REQUEST_REPLAYED", status = ApiStatusCode::PermissionDenied)]
+    RequestReplayed,
+
     /// The client is attempting to sync the state for an epoch that differs from the servers.
     #[error("the request's epoch must be the same as the server's epoch: {0}")]
     #[rest_api_error(delegate)]
@@ -22,7 +22,7 @@ use walrus_sui::types::{
 use crate::{
     client::ClientErrorKind,
     common::telemetry::{CurrentEpochMetric, CurrentEpochStateMetric},
-    node::events::EventStreamElement,
+    node::{errors::InconsistencyProofError, events::EventStreamElement},
 };
 
 pub(crate) const STATUS_FAILURE: &str = "failure";
@@ -53,6 +53,18 @@ walrus_utils::metrics::define_metric_set! {
         #[help = "The total number of storage confirmations issued"]
         storage_confirmations_issued_total: IntCounter[],
 
+        #[help = "The total number of inconsistency proofs received"]
+        inconsistency_proofs_received_total: IntCounter[],
+
+        #[help = "The total number of received inconsistency proofs that were verified as valid"]
+        inconsistency_proofs_verified_total: IntCounter[],
+
+        #[help = "The total number of received inconsistency proofs that were rejected"]
+        inconsistency_proofs_rejected_total: IntCounterVec["reason"],
+
+        #[help = "The total number of invalid blob attestations issued"]
+        invalid_blob_attestations_issued_total: IntCounter[],
+
         #[help = "The number of shard sync per status"]
         shard_sync_total: IntCounterVec["status"],
 
@@ -80,15 +92,70 @@ walrus_utils::metrics::define_metric_set! {
         #[help = "Total number of cancelled sliver recoveries during shard sync"]
         sync_shard_recover_sliver_cancellation_total: IntCounterVec["shard", "sliver_type"],
 
+        #[help = "Total number of slivers found missing when verifying a synced shard"]
+        sync_shard_verify_missing_sliver_total: IntCounterVec["shard", "sliver_type"],
+
+        #[help = "The total number of metadata and sliver entries that failed checksum verification \
+        when read from disk and were discarded for recovery"]
+        corrupted_entries_discarded_total: IntCounterVec["kind"],
+
+        #[help = "The total number of requests to store or read data of blobs on the deny list"]
+        denied_blob_requests_total: IntCounterVec["operation"],
+
+        #[help = "The total number of blob events that repeatedly failed to be processed and were \
+        moved to the dead-letter queue"]
+        blob_events_dead_lettered_total: IntCounter[],
+
+        #[help = "The total number of blobs whose data was deleted because they were registered \
+        but never certified"]
+        orphaned_blobs_deleted_total: IntCounter[],
+
+        #[help = "The total size (in bytes) of the metadata and slivers deleted because their \
+        blobs were registered but never certified"]
+        orphaned_blob_bytes_reclaimed_total: IntCounter[],
+
         #[help = "The total number of slivers stored"]
         slivers_stored_total: IntCounterVec["sliver_type"],
 
         #[help = "Total number of sliver instances returned"]
         slivers_retrieved_total: IntCounterVec["sliver_type"],
 
+        #[help = "The total number of slivers stored per shard"]
+        shard_slivers_stored_total: IntCounterVec["shard", "sliver_type"],
+
+        #[help = "The total size (in bytes) of the slivers stored per shard"]
+        shard_sliver_bytes_stored_total: IntCounterVec["shard", "sliver_type"],
+
+        #[help = "The total number of sliver instances returned per shard"]
+        shard_slivers_retrieved_total: IntCounterVec["shard", "sliver_type"],
+
+        #[help = "The total size (in bytes) of the sliver instances returned per shard"]
+        shard_sliver_bytes_retrieved_total: IntCounterVec["shard", "sliver_type"],
+
+        #[help = "The estimated number of slivers stored in each shard"]
+        shard_stored_slivers: IntGaugeVec["shard", "sliver_type"],
+
+        #[help = "The estimated size (in bytes) of the slivers stored in each shard"]
+        shard_stored_bytes: IntGaugeVec["shard", "sliver_type"],
+
+        #[help = "The status of each shard, which is 1 for the current status and 0 otherwise"]
+        shard_status: IntGaugeVec["shard", "status"],
+
+        #[help = "The Unix timestamp (in seconds) of the last check that a shard stores the slivers \
+        of all certified blobs"]
+        shard_last_scrub_timestamp_seconds: IntGaugeVec["shard"],
+
         #[help = "The number of Walrus events processed"]
         event_cursor_progress: U64GaugeVec["state"],
 
+        #[help = "The number of checkpoints between the latest checkpoint on chain and the \
+        latest fully processed checkpoint"]
+        event_processing_lag_checkpoints: U64Gauge[],
+
+        #[help = "The time (in seconds) between the latest checkpoint on chain and the latest \
+        fully processed checkpoint"]
+        event_processing_lag_seconds: U64Gauge[],
+
         #[help = "The number of blob recoveries currently pending"]
         recover_blob_backlog: IntGaugeVec["state"],
 
@@ -123,6 +190,10 @@ walrus_utils::metrics::define_metric_set! {
         #[help = "The number of blob metadata skipped"]
         sync_blob_metadata_skipped: IntCounter[],
 
+        #[help = "The number of blob metadata pushed to nodes that obtained shards from this node, \
+        by whether the node newly stored the metadata"]
+        sync_blob_metadata_pushed: IntCounterVec["status"],
+
         #[help = "The progress of the blob metadata sync. It is represented by the first two bytes \
         of the blob ID since the sync job is sequential over blob IDs."]
         sync_blob_metadata_progress: IntGauge[],
@@ -163,6 +234,35 @@ walrus_utils::metrics::define_metric_set! {
 
         #[help = "The number shards currently owned by this node"]
         shards_owned: U64Gauge[],
+
+        #[help = "The number of shards gained, lost, and retained by this node in the current \
+        epoch, relative to the previous epoch"]
+        shard_assignment_changes: U64GaugeVec["change"],
+
+        #[help = "The number of responses from committee members that failed verification"]
+        invalid_responses_total: IntCounterVec["node", "request_type"],
+
+        #[help = "The number of times the latest committees were re-fetched due to a suspected \
+        stale committee"]
+        stale_committee_reconciliations_total: IntCounter[],
+
+        #[help = "The total number of invalid blob certificates created"]
+        invalid_blob_certificates_total: IntCounter[],
+
+        #[help = "Time (in seconds) spent collecting a quorum of attestations for an invalid blob \
+        certificate"]
+        invalid_blob_certificate_duration_seconds: Histogram {
+            buckets: default_buckets_for_slow_operations(),
+        },
+
+        #[help = "The total number of blobs that the node attempted to invalidate based on an \
+        inconsistency proof, by outcome"]
+        blob_invalidations_total: IntCounterVec["status"],
+
+        #[help = "Time (in seconds) spent collecting and submitting an invalid blob certificate"]
+        blob_invalidation_duration_seconds: Histogram {
+            buckets: default_buckets_for_slow_operations(),
+        },
     }
 }
 
@@ -259,7 +359,18 @@ impl TelemetryLabel for ClientErrorKind {
             ClientErrorKind::CommitteeChangeNotified => "committee-change-notified",
             ClientErrorKind::StakeBelowThreshold(_) => "stake-below-threshold",
             ClientErrorKind::FailedToLoadCerts(_) => "failed-to-load-certs",
+            ClientErrorKind::Timeout { .. } => "timeout",
             ClientErrorKind::Other(_) => "unknown",
         }
     }
 }
+
+impl TelemetryLabel for InconsistencyProofError {
+    fn label(&self) -> &'static str {
+        match self {
+            InconsistencyProofError::MissingMetadata => "missing-metadata",
+            InconsistencyProofError::InvalidProof(_) => "invalid-proof",
+            InconsistencyProofError::Internal(_) => "internal",
+        }
+    }
+}
@@ -1,30 +1,152 @@
 // Copyright (c) Walrus Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BinaryHeap,
+    ops::Bound,
+    sync::{Arc, Mutex},
+};
 
 use futures::future::join_all;
 use sui_macros::fail_point_async;
 use typed_store::TypedStoreError;
-use walrus_core::Epoch;
+use walrus_core::{BlobId, Epoch};
+use walrus_sdk::api::BlobStatus;
 
-use super::{blob_sync::BlobSyncHandler, StorageNodeInner};
-use crate::node::{storage::blob_info::BlobInfoApi, NodeStatus};
+use super::{blob_sync::BlobSyncHandler, config::RecoveryOrder, StorageNodeInner};
+use crate::node::{
+    storage::blob_info::{BlobInfo, BlobInfoApi},
+    NodeStatus,
+};
+
+/// The maximum number of blobs for which recovery is started at once.
+const RECOVERY_PAGE_SIZE: usize = 1_000;
+
+/// The position of a blob in the recovery order, consisting of its rank and blob ID.
+type RecoveryKey = (Epoch, BlobId);
+
+/// Selects the blobs to recover in pages, ordered according to a [`RecoveryOrder`].
+///
+/// Blobs with lower ranks are recovered first; blobs with equal ranks are recovered in the order of
+/// their blob IDs. To bound the memory used, only a page of blobs is held at a time. Each page is
+/// selected by scanning the blob info table for the blobs following the last blob of the previous
+/// page: when recovering in the order of blob IDs, the scan starts after that blob and stops once
+/// the page is full; for the other orders, the whole table is scanned while only the lowest-ranked
+/// blobs are kept.
+#[derive(Debug)]
+struct RecoveryQueue {
+    order: RecoveryOrder,
+    page_size: usize,
+    /// The key of the last blob of the previous page.
+    last_key: Option<RecoveryKey>,
+}
+
+impl RecoveryQueue {
+    fn new(order: RecoveryOrder, page_size: usize) -> Self {
+        Self {
+            order,
+            page_size,
+            last_key: None,
+        }
+    }
+
+    /// Returns the bound of the blob IDs from which the blob info table must be scanned to select
+    /// the next page.
+    fn scan_start(&self) -> Bound<BlobId> {
+        match (self.order, self.last_key) {
+            (RecoveryOrder::BlobId, Some((_, blob_id))) => Bound::Excluded(blob_id),
+            _ => Bound::Unbounded,
+        }
+    }
+
+    /// Returns the next page of blobs to recover from the `blobs` yielded by a scan of the blob
+    /// info table, along with their initial certified epoch.
+    ///
+    /// Blobs that are no longer certified in the current epoch are skipped. An empty page is
+    /// returned once all blobs have been returned.
+    fn next_page(
+        &mut self,
+        blobs: impl IntoIterator<Item = (BlobId, BlobInfo)>,
+        current_epoch: Epoch,
+    ) -> Vec<(BlobId, Epoch)> {
+        // A max-heap holding the lowest-ranked blobs seen so far.
+        let mut heap = BinaryHeap::with_capacity(self.page_size.saturating_add(1));
+        for (blob_id, blob_info) in blobs {
+            if self.order == RecoveryOrder::BlobId && heap.len() >= self.page_size {
+                // The remaining blobs follow the blobs in the page.
+                break;
+            }
+            // Note that here we need to use the current epoch to check if the blob is still
+            // certified. If the blob is retired, we don't need to recover it anymore.
+            if !blob_info.is_certified(current_epoch) {
+                // Skip blobs that are not certified in the given epoch. This includes blobs that
+                // are invalid or expired.
+                tracing::debug!(
+                    walrus.blob_id = %blob_id,
+                    walrus.current_epoch = current_epoch,
+                    "skip non-certified blob"
+                );
+                continue;
+            }
+            let key = (self.rank(&blob_info, current_epoch), blob_id);
+            if self.last_key.is_some_and(|last_key| key <= last_key) {
+                continue;
+            }
+            let initial_certified_epoch = blob_info
+                .initial_certified_epoch()
+                .expect("certified blob should have an initial certified epoch set");
+            heap.push((key, initial_certified_epoch));
+            if heap.len() > self.page_size {
+                heap.pop();
+            }
+        }
+
+        let page = heap.into_sorted_vec();
+        if let Some((key, _)) = page.last() {
+            self.last_key = Some(*key);
+        }
+        page.into_iter()
+            .map(|((_, blob_id), initial_certified_epoch)| (blob_id, initial_certified_epoch))
+            .collect()
+    }
+
+    fn rank(&self, blob_info: &BlobInfo, current_epoch: Epoch) -> Epoch {
+        match self.order {
+            RecoveryOrder::BlobId => 0,
+            RecoveryOrder::RecentlyCertifiedFirst => {
+                Epoch::MAX
+                    - blob_info
+                        .initial_certified_epoch()
+                        .expect("certified blob should have an initial certified epoch set")
+            }
+            RecoveryOrder::ExpiringFirst => match blob_info.to_blob_status(current_epoch) {
+                BlobStatus::Permanent { end_epoch, .. } => end_epoch,
+                _ => Epoch::MAX,
+            },
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct NodeRecoveryHandler {
     node: Arc<StorageNodeInner>,
     blob_sync_handler: Arc<BlobSyncHandler>,
+    recovery_order: RecoveryOrder,
 
     // There can be at most one background shard removal task at a time.
     task_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
 }
 
 impl NodeRecoveryHandler {
-    pub fn new(node: Arc<StorageNodeInner>, blob_sync_handler: Arc<BlobSyncHandler>) -> Self {
+    pub fn new(
+        node: Arc<StorageNodeInner>,
+        blob_sync_handler: Arc<BlobSyncHandler>,
+        recovery_order: RecoveryOrder,
+    ) -> Self {
         Self {
             node,
             blob_sync_handler,
+            recovery_order,
             task_handle: Arc::new(Mutex::new(None)),
         }
     }
@@ -41,96 +163,97 @@ impl NodeRecoveryHandler {
 
         let node = self.node.clone();
         let blob_sync_handler = self.blob_sync_handler.clone();
+        let recovery_order = self.recovery_order;
         let task_handle = tokio::spawn(async move {
             fail_point_async!("start_node_recovery_entry");
             loop {
-                let mut all_blob_syncs = Vec::new();
                 tracing::info!(
+                    ?recovery_order,
                     "scanning blobs to recover certified blobs before epoch {}",
                     certified_before_epoch
                 );
-                for (blob_id, blob_info) in node
-                    .storage
-                    .certified_blob_info_iter_before_epoch(certified_before_epoch)
-                    .filter_map(|blob_result| {
-                        blob_result
-                            .inspect_err(|error| {
-                                tracing::error!(?error, "failed to read certified blob")
-                            })
-                            .ok()
-                    })
-                {
-                    // Note that here we need to use the current epoch to check if the blob is
-                    // still certified. If the blob is retired, we don't need to recover it anymore.
-                    if !blob_info.is_certified(node.current_epoch()) {
-                        // Skip blobs that are not certified in the given epoch. This
-                        // includes blobs that are invalid or expired.
-                        tracing::debug!(
-                            walrus.blob_id = %blob_id,
-                            walrus.blob_certified_before_epoch = certified_before_epoch,
-                            walrus.current_epoch = node.current_epoch(),
-                            "skip non-certified blob"
-                        );
-                        continue;
+                let mut recovery_queue = RecoveryQueue::new(recovery_order, RECOVERY_PAGE_SIZE);
+                let mut started_blob_syncs = false;
+                loop {
+                    // The blob syncs would wait for the end of maintenance mode anyway, so do
+                    // not start them before.
+                    node.wait_until_not_in_maintenance().await;
+                    let blobs = node
+                        .storage
+                        .certified_blob_info_iter_before_epoch_from(
+                            certified_before_epoch,
+                            recovery_queue.scan_start(),
+                        )
+                        .filter_map(|blob_result| {
+                            blob_result
+                                .inspect_err(|error| {
+                                    tracing::error!(?error, "failed to read certified blob")
+                                })
+                                .ok()
+                        });
+                    let page = recovery_queue.next_page(blobs, node.current_epoch());
+                    if page.is_empty() {
+                        break;
                     }
 
-                    if let Ok(stored_at_all_shards) = node.is_stored_at_all_shards(&blob_id).await {
-                        if stored_at_all_shards {
-                            tracing::debug!(
-                                walrus.blob_certified_before_epoch = certified_before_epoch,
-                                walrus.current_epoch = node.current_epoch(),
-                                "blob is stored at all shards; skip recovery"
+                    let mut page_blob_syncs = Vec::new();
+                    for (blob_id, initial_certified_epoch) in page {
+                        if let Ok(stored_at_all_shards) =
+                            node.is_stored_at_all_shards(&blob_id).await
+                        {
+                            if stored_at_all_shards {
+                                tracing::debug!(
+                                    walrus.blob_certified_before_epoch = certified_before_epoch,
+                                    walrus.current_epoch = node.current_epoch(),
+                                    "blob is stored at all shards; skip recovery"
+                                );
+                                continue;
+                            }
+                        } else {
+                            tracing::warn!(
+                                walrus.blob_id = %blob_id,
+                                "failed to check if blob is stored at all shards; start blob sync"
                             );
-                            continue;
                         }
-                    } else {
-                        tracing::warn!(
+
+                        tracing::debug!(
                             walrus.blob_id = %blob_id,
-                            "failed to check if blob is stored at all shards; start blob sync"
+                            "start recovery sync for blob"
                         );
-                    }
-
-                    tracing::debug!(
-                        walrus.blob_id = %blob_id,
-                        "start recovery sync for blob"
-                    );
-                    // TODO: rate limit start sync to avoid OOM.
-                    let start_sync_result = blob_sync_handler
-                        .start_sync(
-                            blob_id,
-                            blob_info.initial_certified_epoch().expect(
-                                "certified blob should have an initial certified epoch set",
-                            ),
-                            None,
-                        )
-                        .await;
-                    match start_sync_result {
-                        Ok(notify) => {
-                            all_blob_syncs.push(notify);
-                        }
-                        Err(err) => {
-                            // The only place where start_sync can fail is when marking the
-                            // event complete, which is not applicable here since the there
-                            // is no event associated with the recovery task.
-                            panic!(
-                                "failed to start recovery sync for blob {}: {}",
-                                blob_id, err,
-                            );
+                        let start_sync_result = blob_sync_handler
+                            .start_sync(blob_id, initial_certified_epoch, None)
+                            .await;
+                        match start_sync_result {
+                            Ok(notify) => {
+                                page_blob_syncs.push(notify);
+                            }
+                            Err(err) => {
+                                // The only place where start_sync can fail is when marking the
+                                // event complete, which is not applicable here since the there
+                                // is no event associated with the recovery task.
+                                panic!(
+                                    "failed to start recovery sync for blob {}: {}",
+                                    blob_id, err,
+                                );
+                            }
                         }
                     }
+
+                    // Wait for the syncs of the page to finish before selecting the next page, such
+                    // that the number of blobs being recovered at once is bounded.
+                    started_blob_syncs |= !page_blob_syncs.is_empty();
+                    let notify_futures: Vec<_> = page_blob_syncs
+                        .iter()
+                        .map(|notify| notify.notified())
+                        .collect();
+                    join_all(notify_futures).await;
                 }
 
-                if all_blob_syncs.is_empty() {
+                if !started_blob_syncs {
                     tracing::info!("no recovery blob found; stop recovery task");
                     break;
                 }
 
-                let notify_futures: Vec<_> = all_blob_syncs
-                    .iter()
-                    .map(|notify| notify.notified())
-                    .collect();
-                join_all(notify_futures).await;
-
                 // TODO(WAL-669): right now, we have to do one more loop to check if all the blobs
                 // are recovered. This is not efficient because checking blob existence is
                 // expensive. It's better that blob sync handler can return the blob sync status
@@ -144,11 +267,7 @@ impl NodeRecoveryHandler {
             if current_node_status == NodeStatus::RecoveryInProgress(certified_before_epoch) {
                 tracing::info!("node recovery task finished; set node status to active");
                 match node.set_node_status(NodeStatus::Active) {
-                    Ok(()) => {
-                        node.contract_service
-                            .epoch_sync_done(certified_before_epoch, node.node_capability())
-                            .await
-                    }
+                    Ok(()) => node.attest_epoch_sync_done(certified_before_epoch).await,
                     Err(error) => {
                         tracing::error!(?error, "failed to set node status to active");
                     }
@@ -185,3 +304,68 @@ impl NodeRecoveryHandler {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeBounds as _;
+
+    use walrus_sui::test_utils::event_id_for_testing;
+    use walrus_test_utils::param_test;
+
+    use super::*;
+    use crate::node::storage::blob_info::BlobCertificationStatus;
+
+    param_test! {
+        recovery_queue_orders_blobs: [
+            blob_id: (RecoveryOrder::BlobId, &[0, 1, 2]),
+            recently_certified_first: (RecoveryOrder::RecentlyCertifiedFirst, &[2, 0, 1]),
+            expiring_first: (RecoveryOrder::ExpiringFirst, &[1, 0, 2]),
+        ]
+    }
+    fn recovery_queue_orders_blobs(order: RecoveryOrder, expected_order: &[usize]) {
+        // The (end epoch, certified epoch) of the blobs, in the order of their blob IDs.
+        let blobs: Vec<_> = [(10, 3), (5, 2), (20, 4)]
+            .into_iter()
+            .enumerate()
+            .map(|(index, (end_epoch, certified_epoch))| {
+                let blob_info = BlobInfo::new_for_testing(
+                    end_epoch,
+                    BlobCertificationStatus::Certified,
+                    event_id_for_testing(),
+                    Some(1),
+                    Some(certified_epoch),
+                    None,
+                );
+                (BlobId([u8::try_from(index).unwrap(); 32]), blob_info)
+            })
+            .collect();
+
+        // The blob info table yields the blobs in the order of their blob IDs.
+        let scan = |start: Bound<BlobId>| {
+            blobs
+                .iter()
+                .filter(move |(blob_id, _)| (start, Bound::Unbounded).contains(blob_id))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let mut queue = RecoveryQueue::new(order, 2);
+        let mut pages = vec![];
+        loop {
+            let page = queue.next_page(scan(queue.scan_start()), 1);
+            if page.is_empty() {
+                break;
+            }
+            pages.push(page);
+        }
+
+        // The blobs are returned in pages of at most two blobs, in the expected order overall.
+        assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
+        let recovered: Vec<_> = pages
+            .into_iter()
+            .flatten()
+            .map(|(blob_id, _)| blob_id)
+            .collect();
+        let expected: Vec<_> = expected_order.iter().map(|&index| blobs[index].0).collect();
+        assert_eq!(recovered, expected);
+    }
+}
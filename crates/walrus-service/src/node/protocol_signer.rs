@@ -0,0 +1,232 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing of protocol messages with the node's protocol key.
+//!
+//! The node signs storage confirmations, invalid-blob attestations, and shard-sync requests through
+//! a [`ProtocolSigner`]. The [`LocalProtocolSigner`] holds the protocol key pair in memory, while
+//! the [`RemoteProtocolSigner`] forwards the messages to a signing service, which allows keeping
+//! the key in an HSM-backed signer process.
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use fastcrypto::traits::{Signer as _, VerifyingKey as _};
+use reqwest::{Client as ReqwestClient, Url};
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use walrus_core::{
+    keys::ProtocolKeyPair,
+    messages::{ProtocolMessage, SignedMessage},
+    PublicKey,
+    Signature,
+};
+
+use super::config::RemoteProtocolSignerConfig;
+
+/// Errors returned when signing a protocol message.
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolSignerError {
+    /// The request to the remote signer failed.
+    #[error("the request to the remote signer failed: {0}")]
+    Request(#[from] reqwest::Error),
+    /// The signature returned by the remote signer is not valid for the node's public key.
+    #[error("the remote signer returned a signature that is invalid for the node's public key")]
+    InvalidSignature,
+    /// The blocking task signing the message failed.
+    #[error("the signing task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
+
+/// Signs protocol messages with the node's protocol key.
+#[async_trait]
+pub trait ProtocolSigner: Debug + Send + Sync {
+    /// Returns the public key of the protocol key pair.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Signs the BCS-encoded message.
+    async fn sign_bytes(&self, message: &[u8]) -> Result<Signature, ProtocolSignerError>;
+}
+
+impl dyn ProtocolSigner + '_ {
+    /// Signs `message` and returns the resulting [`SignedMessage`].
+    pub async fn sign_message<T, I>(
+        &self,
+        message: &T,
+    ) -> Result<SignedMessage<T>, ProtocolSignerError>
+    where
+        T: AsRef<ProtocolMessage<I>> + Serialize,
+    {
+        let serialized_message =
+            bcs::to_bytes(message).expect("bcs encoding a message should not fail");
+        let signature = self.sign_bytes(&serialized_message).await?;
+        Ok(SignedMessage::new_from_encoded(
+            serialized_message,
+            signature,
+        ))
+    }
+}
+
+/// A [`ProtocolSigner`] holding the protocol key pair in memory.
+#[derive(Debug, Clone)]
+pub struct LocalProtocolSigner {
+    key_pair: ProtocolKeyPair,
+}
+
+impl LocalProtocolSigner {
+    /// Creates a new signer with the given key pair.
+    pub fn new(key_pair: ProtocolKeyPair) -> Self {
+        Self { key_pair }
+    }
+}
+
+#[async_trait]
+impl ProtocolSigner for LocalProtocolSigner {
+    fn public_key(&self) -> &PublicKey {
+        self.key_pair.as_ref().public()
+    }
+
+    async fn sign_bytes(&self, message: &[u8]) -> Result<Signature, ProtocolSignerError> {
+        let key_pair = self.key_pair.clone();
+        let message = message.to_vec();
+        Ok(tokio::task::spawn_blocking(move || key_pair.as_ref().sign(&message)).await?)
+    }
+}
+
+/// A [`ProtocolSigner`] that sends the messages to a remote signing service.
+///
+/// The service is expected to accept `POST` requests to the configured URL with a JSON body of the
+/// form `{"publicKey": "<base64>", "message": "<base64>"}`, and to respond with a JSON body of the
+/// form `{"signature": "<base64>"}` containing the BLS signature over the message. Signatures are
+/// verified against the node's public key before they are used.
+#[derive(Debug, Clone)]
+pub struct RemoteProtocolSigner {
+    client: ReqwestClient,
+    url: Url,
+    public_key: PublicKey,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignRequest<'a> {
+    public_key: &'a PublicKey,
+    #[serde_as(as = "Base64")]
+    message: &'a [u8],
+}
+
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    signature: Signature,
+}
+
+impl RemoteProtocolSigner {
+    /// Creates a new signer for the service in the config, signing with the key pair with the
+    /// given public key.
+    pub fn new(
+        config: &RemoteProtocolSignerConfig,
+        public_key: PublicKey,
+    ) -> Result<Self, ProtocolSignerError> {
+        let client = ReqwestClient::builder().timeout(config.timeout).build()?;
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            public_key,
+        })
+    }
+}
+
+#[async_trait]
+impl ProtocolSigner for RemoteProtocolSigner {
+    fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    async fn sign_bytes(&self, message: &[u8]) -> Result<Signature, ProtocolSignerError> {
+        let response: SignResponse = self
+            .client
+            .post(self.url.clone())
+            .json(&SignRequest {
+                public_key: &self.public_key,
+                message,
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        self.public_key
+            .verify(message, &response.signature)
+            .map_err(|_| ProtocolSignerError::InvalidSignature)?;
+        Ok(response.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{routing::post, Json, Router};
+    use walrus_core::{messages::InvalidBlobIdMsg, BlobId};
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    #[serde_as]
+    #[derive(Deserialize)]
+    struct ReceivedSignRequest {
+        #[serde_as(as = "Base64")]
+        message: Vec<u8>,
+    }
+
+    /// Serves a signing service that signs with the key pair, and returns its URL.
+    async fn serve_signer(key_pair: ProtocolKeyPair) -> TestResult<Url> {
+        let app = Router::new().route(
+            "/sign",
+            post(move |Json(request): Json<ReceivedSignRequest>| {
+                let key_pair = key_pair.clone();
+                async move {
+                    Json(serde_json::json!({
+                        "signature": key_pair.as_ref().sign(&request.message)
+                    }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = Url::parse(&format!("http://{}/sign", listener.local_addr()?))?;
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        Ok(url)
+    }
+
+    #[tokio::test]
+    async fn remote_signer_only_accepts_signatures_by_the_node_key() -> TestResult {
+        let key_pair = ProtocolKeyPair::generate();
+        let config = RemoteProtocolSignerConfig {
+            url: serve_signer(key_pair.clone()).await?,
+            timeout: Duration::from_secs(5),
+            public_key: None,
+        };
+        let message = InvalidBlobIdMsg::new(1, BlobId([7; 32]));
+
+        let remote: Arc<dyn ProtocolSigner> = Arc::new(RemoteProtocolSigner::new(
+            &config,
+            key_pair.as_ref().public().clone(),
+        )?);
+        let local: Arc<dyn ProtocolSigner> = Arc::new(LocalProtocolSigner::new(key_pair));
+        assert_eq!(
+            remote.sign_message(&message).await?.signature,
+            local.sign_message(&message).await?.signature
+        );
+
+        let other_key: Arc<dyn ProtocolSigner> = Arc::new(RemoteProtocolSigner::new(
+            &config,
+            ProtocolKeyPair::generate().as_ref().public().clone(),
+        )?);
+        assert!(matches!(
+            other_key.sign_message(&message).await,
+            Err(ProtocolSignerError::InvalidSignature)
+        ));
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@
 use std::{
     sync::Arc,
     task::{self, Context, Poll},
+    time::Duration,
 };
 
 use fastcrypto::hash::Blake2b256;
@@ -35,6 +36,9 @@ walrus_utils::metrics::define_metric_set! {
 
         #[help = "The total number of cache misses in the `RecoverySymbolService`."]
         cache_miss_total: IntCounter[],
+
+        #[help = "The total number of requests served from the cache of recovery symbols."]
+        symbol_cache_hit_total: IntCounter[],
     }
 }
 
@@ -47,6 +51,16 @@ struct CacheKey {
     source_id: SliverId,
 }
 
+/// The key into the cache of recovery symbols.
+///
+/// The recovery symbols are cached for each source sliver in a blob and each target index.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct SymbolCacheKey {
+    blob_id: BlobId,
+    source_id: SliverId,
+    target_pair_index: SliverPairIndex,
+}
+
 /// A request to construct a recovery symbol from a sliver.
 #[derive(Debug, Clone)]
 pub(crate) struct RecoverySymbolRequest {
@@ -67,6 +81,9 @@ pub(crate) struct RecoverySymbolRequest {
 /// The service also caches the merkle trees used to construct the proofs from a sliver. This
 /// allows faster construction of any other recovery symbols from that same source sliver.
 ///
+/// In addition, the constructed recovery symbols are cached for a short time, as many nodes
+/// recovering at the same time request overlapping symbols from the same node.
+///
 /// The sliver itself is not cached and must be provided, which ensures that the storage node is
 /// still storing the sliver.
 ///
@@ -87,6 +104,7 @@ pub(crate) struct RecoverySymbolRequest {
 #[derive(Clone, Debug)]
 pub(crate) struct RecoverySymbolService {
     cache: Cache<CacheKey, Arc<MerkleTree<Blake2b256>>>,
+    symbol_cache: Cache<SymbolCacheKey, GeneralRecoverySymbol>,
     thread_pool: BoundedThreadPool,
     encoding_config: Arc<EncodingConfig>,
     metrics: RecoverySymbolCacheMetrics,
@@ -94,8 +112,13 @@ pub(crate) struct RecoverySymbolService {
 
 impl RecoverySymbolService {
     /// Create a new instance of `RecoverySymbolService` with the specified capacity.
+    ///
+    /// Up to `max_symbol_cache_capacity` constructed recovery symbols are cached for
+    /// `symbol_cache_ttl` each.
     pub(crate) fn new(
         max_cache_capacity: u64,
+        max_symbol_cache_capacity: u64,
+        symbol_cache_ttl: Duration,
         encoding_config: Arc<EncodingConfig>,
         thread_pool: BoundedThreadPool,
         registry: &Registry,
@@ -104,8 +127,14 @@ impl RecoverySymbolService {
             .name("recovery_symbol_cache")
             .max_capacity(max_cache_capacity)
             .build();
+        let symbol_cache = Cache::builder()
+            .name("recovery_symbol_result_cache")
+            .max_capacity(max_symbol_cache_capacity)
+            .time_to_live(symbol_cache_ttl)
+            .build();
         Self {
             cache,
+            symbol_cache,
             thread_pool,
             encoding_config,
             metrics: RecoverySymbolCacheMetrics::new(registry),
@@ -115,6 +144,29 @@ impl RecoverySymbolService {
     async fn handle_request_and_cache(
         &mut self,
         req: RecoverySymbolRequest,
+    ) -> Result<GeneralRecoverySymbol, RecoverySymbolError> {
+        let symbol_cache_key = SymbolCacheKey {
+            blob_id: req.blob_id,
+            source_id: by_axis::map!(req.source_sliver.as_ref(), |s| s.index),
+            target_pair_index: req.target_pair_index,
+        };
+        if let Some(recovery_symbol) = self.symbol_cache.get(&symbol_cache_key).await {
+            self.metrics.requests_total.inc();
+            self.metrics.symbol_cache_hit_total.inc();
+            return Ok(recovery_symbol);
+        }
+
+        let recovery_symbol = self.construct_recovery_symbol(req).await?;
+        self.symbol_cache
+            .insert(symbol_cache_key, recovery_symbol.clone())
+            .await;
+
+        Ok(recovery_symbol)
+    }
+
+    async fn construct_recovery_symbol(
+        &mut self,
+        req: RecoverySymbolRequest,
     ) -> Result<GeneralRecoverySymbol, RecoverySymbolError> {
         let n_shards = self.encoding_config.n_shards();
         let config = self.encoding_config.get_for_type(req.encoding_type);
@@ -247,7 +299,14 @@ mod tests {
             }
         };
 
-        RecoverySymbolService::new(10, config, builder.build_bounded(), &Registry::default())
+        RecoverySymbolService::new(
+            10,
+            10,
+            Duration::from_secs(60),
+            config,
+            builder.build_bounded(),
+            &Registry::default(),
+        )
     }
 
     struct TestBlobInfo {
@@ -405,6 +464,32 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn repeated_request_is_served_from_symbol_cache() -> TestResult {
+        let blob_info = TestBlobInfo::new();
+        let n_shards = blob_info.config.n_shards();
+
+        let source_id = SliverId::Primary(SliverIndex(0));
+        let target_id = SliverId::Secondary(SliverIndex(1));
+        let request = RecoverySymbolRequest {
+            blob_id: *blob_info.metadata.blob_id(),
+            source_sliver: blob_info.primary_sliver(source_id.index()).into(),
+            target_pair_index: target_id.pair_index(n_shards),
+            encoding_type: blob_info.encoding_type(),
+        };
+
+        let mut service = symbol_service(blob_info.config.clone(), ThreadPoolType::Tokio);
+        let first = service.ready().await?.call(request.clone()).await?;
+        let second = service.ready().await?.call(request).await?;
+
+        assert_eq!(first, second);
+        let metrics = &service.metrics;
+        assert_eq!(metrics.requests_total.get(), 2);
+        assert_eq!(metrics.symbol_cache_hit_total.get(), 1);
+
+        Ok(())
+    }
+
     async_param_test! {
         recovery_symbol_for_different_sliver_uses_different_proof -> TestResult: [
             #[cfg(not(msim))]
@@ -3,7 +3,7 @@
 
 //! Server for the Walrus service.
 
-use std::{net::SocketAddr, ops::Deref, sync::Arc, time::Duration};
+use std::{net::SocketAddr, num::NonZeroU16, ops::Deref, sync::Arc, time::Duration};
 
 use anyhow::{anyhow, Context};
 use axum::{
@@ -12,9 +12,15 @@ use axum::{
     routing::{get, post, put},
     Router,
 };
-use axum_server::{tls_rustls::RustlsConfig, Handle};
+use axum_server::{
+    tls_rustls::{from_tcp_rustls, RustlsConfig},
+    Handle,
+};
 use fastcrypto::{secp256r1::Secp256r1PrivateKey, traits::ToFromBytes};
-use futures::{future::Either, FutureExt};
+use futures::{
+    future::{self, Either},
+    FutureExt,
+};
 use openapi::RestApiDoc;
 use p256::{elliptic_curve::pkcs8::EncodePrivateKey as _, SecretKey};
 use prometheus::Registry;
@@ -31,7 +37,10 @@ use walrus_core::{encoding, keys::NetworkKeyPair};
 use self::telemetry::HttpServerMetrics;
 use super::config::{defaults, Http2Config, PathOrInPlace, StorageNodeConfig, TlsConfig};
 use crate::{
-    common::telemetry::{self, MakeHttpSpan},
+    common::{
+        telemetry::{self, MakeHttpSpan},
+        utils::bind_tcp_listener,
+    },
     node::ServiceState,
 };
 
@@ -39,6 +48,14 @@ mod extract;
 mod openapi;
 mod responses;
 mod routes;
+mod status_page;
+
+pub(crate) use routes::{
+    STORAGE_BLOB_ENDPOINT,
+    STORAGE_COLUMN_FAMILIES_ENDPOINT,
+    STORAGE_EVENT_CURSOR_ENDPOINT,
+    STORAGE_SHARD_BLOBS_ENDPOINT,
+};
 
 /// Additional space to be added to the maximum body size accepted by the server.
 ///
@@ -47,10 +64,36 @@ mod routes;
 /// the additional information encoded with the slivers.
 const HEADROOM: usize = 128;
 
+/// The maximum number of blob metadata that can be pushed to the node in a single request.
+pub(crate) const MAX_PUSHED_METADATA_PER_REQUEST: usize = 16;
+
 /// Configuration for the rest API.
 #[derive(Debug)]
 pub struct RestApiConfig {
-    /// The socket address on which the server should listen.
+    /// The listeners on which the server accepts connections.
+    ///
+    /// Listeners on IPv6 addresses only accept IPv6 connections if there is also a listener on the
+    /// IPv4 wildcard address with the same port.
+    pub listeners: Vec<RestApiListener>,
+
+    /// Duration for which to wait for connections to close, when shutting down the server.
+    ///
+    /// Zero waits indefinitely and None immediately closes the connections.
+    pub graceful_shutdown_period: Option<Duration>,
+
+    /// Configuration of HTTP/2 connections.
+    pub http2_config: Http2Config,
+
+    /// Bearer token required to access the administrative endpoints.
+    ///
+    /// If None, the administrative endpoints are not served.
+    pub admin_api_token: Option<String>,
+}
+
+/// A socket address on which the REST API listens, along with its TLS settings.
+#[derive(Debug)]
+pub struct RestApiListener {
+    /// The socket address on which to listen.
     pub bind_address: SocketAddr,
 
     /// Source of the TLS certificate used to secure connections.
@@ -60,40 +103,69 @@ pub struct RestApiConfig {
     /// and TLS is not possible between the middleware and the server.
     pub tls_certificate: Option<TlsCertificateSource>,
 
-    /// Duration for which to wait for connections to close, when shutting down the server.
-    ///
-    /// Zero waits indefinitely and None immediately closes the connections.
-    pub graceful_shutdown_period: Option<Duration>,
+    /// The endpoints served on the listener.
+    pub endpoints: ListenerEndpoints,
+}
 
-    /// Configuration of HTTP/2 connections.
-    pub http2_config: Http2Config,
+/// Returns true if the address is an IPv6 address and one of the listeners is bound to the IPv4
+/// wildcard address with the same port.
+///
+/// A dual-stack listener on such an address would conflict with the IPv4 listener, so it must only
+/// accept IPv6 connections.
+fn shares_port_with_ipv4_wildcard(address: SocketAddr, listeners: &[RestApiListener]) -> bool {
+    address.is_ipv6()
+        && listeners.iter().any(|listener| {
+            let other = listener.bind_address;
+            other.is_ipv4() && other.ip().is_unspecified() && other.port() == address.port()
+        })
+}
+
+/// The endpoints served on a listener of the REST API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListenerEndpoints {
+    /// The public endpoints and, if enabled, the administrative endpoints.
+    All,
+    /// Only the public endpoints.
+    Public,
+    /// Only the administrative endpoints.
+    Admin,
 }
 
 impl From<&StorageNodeConfig> for RestApiConfig {
     fn from(config: &StorageNodeConfig) -> Self {
-        let tls_certificate = match config.tls {
-            TlsConfig {
-                disable_tls: true, ..
-            } => None,
-
-            TlsConfig {
-                certificate_path: Some(ref path),
-                ..
-            } => Some(TlsCertificateSource::Pem {
-                certificate: PathOrInPlace::from_path(path),
-                key: match config.network_key_pair {
-                    PathOrInPlace::InPlace(ref value) => {
-                        PathOrInPlace::InPlace(value.to_pem().as_bytes().to_vec())
-                    }
-                    PathOrInPlace::Path { ref path, .. } => PathOrInPlace::from_path(path),
-                },
-            }),
-
-            _ => Some(TlsCertificateSource::GenerateSelfSigned {
-                server_name: config.public_host.clone(),
-                network_key_pair: config.network_key_pair().clone(),
-            }),
+        // The administrative endpoints are only served on the dedicated listeners, if there are
+        // any, and if the endpoints are enabled.
+        let admin_listeners = if config.rest_server.admin_api_token.is_some() {
+            config.rest_server.admin_listeners.as_slice()
+        } else {
+            &[]
         };
+        let public_endpoints = if admin_listeners.is_empty() {
+            ListenerEndpoints::All
+        } else {
+            ListenerEndpoints::Public
+        };
+        let listeners = std::iter::once((config.rest_api_address, &config.tls, public_endpoints))
+            .chain(config.rest_api_listeners.iter().map(|listener| {
+                (
+                    listener.address,
+                    listener.tls.as_ref().unwrap_or(&config.tls),
+                    public_endpoints,
+                )
+            }))
+            .chain(admin_listeners.iter().map(|listener| {
+                (
+                    listener.address,
+                    listener.tls.as_ref().unwrap_or(&config.tls),
+                    ListenerEndpoints::Admin,
+                )
+            }))
+            .map(|(bind_address, tls, endpoints)| RestApiListener {
+                bind_address,
+                tls_certificate: tls_certificate_source(config, tls),
+                endpoints,
+            })
+            .collect();
 
         let graceful_shutdown_period = config
             .rest_graceful_shutdown_period_secs
@@ -101,14 +173,44 @@ impl From<&StorageNodeConfig> for RestApiConfig {
             .map(Duration::from_secs);
 
         RestApiConfig {
-            bind_address: config.rest_api_address,
-            tls_certificate,
+            listeners,
             graceful_shutdown_period,
             http2_config: config.rest_server.http2_config.clone(),
+            admin_api_token: config.rest_server.admin_api_token.clone(),
         }
     }
 }
 
+/// Returns the source of the TLS certificate of a listener with the given TLS settings.
+fn tls_certificate_source(
+    config: &StorageNodeConfig,
+    tls: &TlsConfig,
+) -> Option<TlsCertificateSource> {
+    match *tls {
+        TlsConfig {
+            disable_tls: true, ..
+        } => None,
+
+        TlsConfig {
+            certificate_path: Some(ref path),
+            ..
+        } => Some(TlsCertificateSource::Pem {
+            certificate: PathOrInPlace::from_path(path),
+            key: match config.network_key_pair {
+                PathOrInPlace::InPlace(ref value) => {
+                    PathOrInPlace::InPlace(value.to_pem().as_bytes().to_vec())
+                }
+                PathOrInPlace::Path { ref path, .. } => PathOrInPlace::from_path(path),
+            },
+        }),
+
+        _ => Some(TlsCertificateSource::GenerateSelfSigned {
+            server_name: config.public_host.clone(),
+            network_key_pair: config.network_key_pair().clone(),
+        }),
+    }
+}
+
 /// Source of the TLS private key and certificate.
 #[derive(Debug)]
 pub enum TlsCertificateSource {
@@ -148,7 +250,7 @@ pub struct RestApiServer<S> {
     config: RestApiConfig,
     metrics: HttpServerMetrics,
     cancel_token: CancellationToken,
-    handle: Mutex<Option<Handle>>,
+    handles: Mutex<Vec<Handle>>,
 }
 
 impl<S> RestApiServer<S>
@@ -166,7 +268,7 @@ where
             state,
             metrics: HttpServerMetrics::new(registry),
             cancel_token,
-            handle: Default::default(),
+            handles: Default::default(),
             config,
         }
     }
@@ -174,8 +276,8 @@ where
     /// Runs the server, may only be called once for a given instance.
     pub async fn run(&self) -> Result<(), anyhow::Error> {
         {
-            let handle = self.handle.lock().await;
-            assert!(handle.is_none(), "run can only be called once");
+            let handles = self.handles.lock().await;
+            assert!(handles.is_empty(), "run can only be called once");
         }
 
         let request_layers = ServiceBuilder::new()
@@ -183,6 +285,7 @@ where
                 self.metrics.clone(),
                 telemetry::metrics_middleware,
             ))
+            .layer(middleware::from_fn(telemetry::request_id_middleware))
             .layer(
                 TraceLayer::new_for_http()
                     .make_span_with(MakeHttpSpan::new())
@@ -193,35 +296,62 @@ where
                     .on_response(MakeHttpSpan::new()),
             );
 
-        let app = self
-            .define_routes()
-            .with_state(self.state.clone())
-            .layer(request_layers)
-            .into_make_service_with_connect_info::<SocketAddr>();
-
-        let handle = self.init_handle().await;
+        let public_routes = self.define_routes();
+        let admin_routes = self.define_admin_routes();
 
-        let server = if let Some(tls_config) = self.configure_tls().await? {
-            let server = axum_server::bind_rustls(self.config.bind_address, tls_config)
-                .handle(handle.clone());
-            Either::Left(self.configure_server(server).serve(app))
-        } else {
-            let server = axum_server::bind(self.config.bind_address).handle(handle.clone());
-            Either::Right(self.configure_server(server).serve(app))
-        };
+        let mut servers = Vec::with_capacity(self.config.listeners.len());
+        let mut handles = Vec::with_capacity(self.config.listeners.len());
+        for listener in &self.config.listeners {
+            let routes = match listener.endpoints {
+                ListenerEndpoints::All => public_routes.clone().merge(admin_routes.clone()),
+                ListenerEndpoints::Public => public_routes.clone(),
+                ListenerEndpoints::Admin => admin_routes.clone(),
+            };
+            let app = routes
+                .with_state(self.state.clone())
+                .layer(request_layers.clone())
+                .into_make_service_with_connect_info::<SocketAddr>();
+
+            let only_v6 =
+                shares_port_with_ipv4_wildcard(listener.bind_address, &self.config.listeners);
+            let tcp_listener =
+                bind_tcp_listener(listener.bind_address, only_v6).with_context(|| {
+                    format!("failed to bind the REST API on {}", listener.bind_address)
+                })?;
+            let handle = Handle::new();
+            let server = if let Some(tls_config) = self
+                .configure_tls(listener.tls_certificate.as_ref())
+                .await?
+            {
+                let server = from_tcp_rustls(tcp_listener, tls_config).handle(handle.clone());
+                Either::Left(self.configure_server(server).serve(app))
+            } else {
+                let server = axum_server::from_tcp(tcp_listener).handle(handle.clone());
+                Either::Right(self.configure_server(server).serve(app))
+            };
+            tracing::info!(
+                address = %listener.bind_address,
+                endpoints = ?listener.endpoints,
+                "serving the REST API"
+            );
 
-        tokio::spawn(
-            Self::handle_shutdown_signal(
-                handle,
-                self.cancel_token.clone(),
-                self.config.graceful_shutdown_period,
-            )
-            .in_current_span(),
-        );
+            tokio::spawn(
+                Self::handle_shutdown_signal(
+                    handle.clone(),
+                    self.cancel_token.clone(),
+                    self.config.graceful_shutdown_period,
+                )
+                .in_current_span(),
+            );
+            servers.push(server);
+            handles.push(handle);
+        }
+        *self.handles.lock().await = handles;
 
-        server
+        future::try_join_all(servers)
             .inspect(|_| tracing::info!("server run has completed"))
             .await
+            .map(|_| ())
             .map_err(|error| anyhow!(error))
     }
 
@@ -262,15 +392,11 @@ where
         handle.graceful_shutdown(shutdown_duration);
     }
 
-    async fn init_handle(&self) -> Handle {
-        let new_handle = Handle::new();
-        let mut handle = self.handle.lock().await;
-        *handle = Some(new_handle.clone());
-        new_handle
-    }
-
-    async fn configure_tls(&self) -> Result<Option<RustlsConfig>, anyhow::Error> {
-        let Some(ref tls_certificate) = self.config.tls_certificate else {
+    async fn configure_tls(
+        &self,
+        tls_certificate: Option<&TlsCertificateSource>,
+    ) -> Result<Option<RustlsConfig>, anyhow::Error> {
+        let Some(tls_certificate) = tls_certificate else {
             return Ok(None);
         };
 
@@ -310,23 +436,22 @@ where
 
     #[cfg(test)]
     async fn ready(&self) {
-        let handle = loop {
-            let handle = self.handle.lock().await;
-            if handle.is_none() {
-                drop(handle);
+        let handles = loop {
+            let handles = self.handles.lock().await;
+            if handles.is_empty() {
+                drop(handles);
                 tokio::time::sleep(Duration::from_millis(5)).await;
             } else {
-                break handle;
+                break handles;
             }
         };
-        // Returns only once bind has been completed
-        let _ = handle
-            .as_ref()
-            .expect("we only break if the handle is some")
-            .listening()
-            .await;
+        // Returns only once all listeners are accepting connections
+        for handle in handles.iter() {
+            let _ = handle.listening().await;
+        }
     }
 
+    /// Defines the public routes.
     fn define_routes(&self) -> Router<Arc<S>> {
         Router::new()
             .merge(Redoc::with_url(
@@ -385,11 +510,116 @@ where
                 post(routes::inconsistency_proof),
             )
             .route(routes::BLOB_STATUS_ENDPOINT, get(routes::get_blob_status))
+            .route(
+                routes::BLOB_STORAGE_STATUS_ENDPOINT,
+                get(routes::get_blob_storage_status),
+            )
             .route(routes::HEALTH_ENDPOINT, get(routes::health_info))
             .route(routes::SYNC_SHARD_ENDPOINT, post(routes::sync_shard))
+            .route(
+                routes::PUSH_METADATA_ENDPOINT,
+                put(routes::push_metadata).route_layer(DefaultBodyLimit::max(
+                    max_push_metadata_request_size(self.state.n_shards()),
+                )),
+            )
+    }
+
+    /// Defines the administrative routes, which are only served if an admin token is configured.
+    fn define_admin_routes(&self) -> Router<Arc<S>> {
+        let Some(token) = self.config.admin_api_token.as_deref() else {
+            return Router::new();
+        };
+        let token = routes::AdminToken::new(token);
+
+        Router::new()
+            .route(
+                routes::PINNED_BLOBS_ENDPOINT,
+                get(routes::list_pinned_blobs),
+            )
+            .route(
+                routes::PINNED_BLOB_ENDPOINT,
+                put(routes::pin_blob).delete(routes::unpin_blob),
+            )
+            .route(
+                routes::DENIED_BLOBS_ENDPOINT,
+                get(routes::list_denied_blobs),
+            )
+            .route(
+                routes::DENIED_BLOB_ENDPOINT,
+                put(routes::deny_blob).delete(routes::allow_blob),
+            )
+            .route(
+                routes::MAINTENANCE_ENDPOINT,
+                put(routes::enable_maintenance_mode).delete(routes::disable_maintenance_mode),
+            )
+            .route(
+                routes::BACKGROUND_WORKERS_ENDPOINT,
+                get(routes::list_background_workers),
+            )
+            .route(
+                routes::BACKGROUND_WORKER_PAUSE_ENDPOINT,
+                put(routes::pause_background_worker).delete(routes::resume_background_worker),
+            )
+            .route(
+                routes::DEAD_LETTER_EVENTS_ENDPOINT,
+                get(routes::list_dead_letter_events),
+            )
+            .route(
+                routes::DEAD_LETTER_EVENT_RETRY_ENDPOINT,
+                post(routes::retry_dead_letter_event),
+            )
+            .route(routes::NODE_STATUS_ENDPOINT, get(routes::get_node_status))
+            .route(
+                routes::NODE_STATUS_PAGE_ENDPOINT,
+                get(routes::get_node_status_page),
+            )
+            .route(
+                routes::SHARD_ASSIGNMENT_ENDPOINT,
+                get(routes::get_shard_assignment),
+            )
+            .route(
+                routes::STORAGE_SHARD_BLOBS_ENDPOINT,
+                get(routes::list_shard_blobs),
+            )
+            .route(
+                routes::STORAGE_BLOB_ENDPOINT,
+                get(routes::inspect_blob_storage),
+            )
+            .route(
+                routes::STORAGE_EVENT_CURSOR_ENDPOINT,
+                get(routes::get_event_cursor),
+            )
+            .route(
+                routes::STORAGE_COLUMN_FAMILIES_ENDPOINT,
+                get(routes::list_column_family_sizes),
+            )
+            .route(
+                routes::TRACE_SAMPLING_ENDPOINT,
+                get(routes::get_trace_sampling).put(routes::set_trace_sampling),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                token.clone(),
+                routes::require_admin_token,
+            ))
+            .route(
+                routes::ADMIN_LOGIN_ENDPOINT,
+                get(routes::get_admin_login)
+                    .post(routes::admin_login)
+                    .with_state(token),
+            )
     }
 }
 
+/// Returns the maximum body size of requests pushing metadata to the node.
+fn max_push_metadata_request_size(n_shards: NonZeroU16) -> usize {
+    // The hashes in the metadata are additionally prefixed by the variant of their `MerkleNode`.
+    let max_metadata_size =
+        encoding::metadata_length_for_n_shards(n_shards) + 2 * u64::from(n_shards.get());
+    (usize::try_from(max_metadata_size).expect("running on 64bit arch (see hardware requirements)")
+        + HEADROOM)
+        * MAX_PUSHED_METADATA_PER_REQUEST
+}
+
 fn create_self_signed_certificate(
     key_pair: &NetworkKeyPair,
     public_server_name: String,
@@ -453,6 +683,7 @@ mod tests {
         InconsistencyProof,
         PublicKey,
         RecoverySymbol,
+        ShardIndex,
         Sliver,
         SliverIndex,
         SliverPairIndex,
@@ -462,27 +693,53 @@ mod tests {
     use walrus_sdk::{
         api::{
             BlobStatus,
+            BlobStorageStatus,
             DeletableCounts,
             ServiceHealthInfo,
+            ShardBlobStorageStatus,
             ShardStatusSummary,
             StoredOnNodeStatus,
         },
         client::{Client, ClientBuilder, RecoverySymbolsFilter},
+        request_id::REQUEST_ID_HEADER,
+    };
+    use walrus_sui::{
+        test_utils::{event_id_for_testing, EventForTesting},
+        types::{BlobEvent, BlobRegistered},
     };
-    use walrus_sui::test_utils::event_id_for_testing;
     use walrus_test_utils::{async_param_test, Result as TestResult, WithTempDir};
 
     use super::*;
     use crate::{
+        common::telemetry::TraceSampler,
         node::{
-            config::StorageNodeConfig,
-            errors::ListSymbolsError,
+            committee::ShardAssignmentDiff,
+            config::{ListenerConfig, StorageNodeConfig},
+            errors::{
+                BlobPinningError,
+                DeadLetterQueueError,
+                DenyListError,
+                ListSymbolsError,
+                StorageInspectionError,
+            },
+            BackgroundWorker,
+            BackgroundWorkerStatus,
             BlobStatusError,
+            BlobStorageReport,
+            ColumnFamilySize,
             ComputeStorageConfirmationError,
+            DeadLetterEvent,
+            EpochSyncDoneStatus,
+            EventCursorReport,
             InconsistencyProofError,
+            NodeStatusReport,
+            PinnedBlob,
             RetrieveMetadataError,
             RetrieveSliverError,
             RetrieveSymbolError,
+            ShardBlobs,
+            ShardSliverReport,
+            StorageUsage,
             StoreMetadataError,
             StoreSliverError,
             SyncShardServiceError,
@@ -637,6 +894,26 @@ mod tests {
             }
         }
 
+        /// Returns a storage status with only the primary sliver of shard 0 stored for blob IDs
+        /// starting with zero, and otherwise an error.
+        async fn blob_storage_status(
+            &self,
+            blob_id: &BlobId,
+        ) -> Result<BlobStorageStatus, BlobStatusError> {
+            if blob_id.0[0] == 0 {
+                Ok(BlobStorageStatus {
+                    metadata: StoredOnNodeStatus::Stored,
+                    shards: vec![ShardBlobStorageStatus {
+                        shard: ShardIndex(0),
+                        primary_sliver: StoredOnNodeStatus::Stored,
+                        secondary_sliver: StoredOnNodeStatus::Nonexistent,
+                    }],
+                })
+            } else {
+                Err(anyhow::anyhow!("Internal error").into())
+            }
+        }
+
         async fn sliver_status<A: EncodingAxis>(
             &self,
             _blob_id: &BlobId,
@@ -676,6 +953,7 @@ mod tests {
                 uptime: Duration::from_secs(0),
                 epoch: 0,
                 public_key: ProtocolKeyPair::generate().as_ref().public().clone(),
+                network_public_key: None,
                 node_status: "Active".to_string(),
                 event_progress: walrus_sdk::api::EventProgress::default(),
                 shard_detail: None,
@@ -690,6 +968,156 @@ mod tests {
         ) -> Result<SyncShardResponse, SyncShardServiceError> {
             Ok(SyncShardResponse::V1(vec![]))
         }
+
+        /// Reports blob IDs starting with zero as newly pinned, and all others as already pinned.
+        fn pin_blob(&self, blob_id: &BlobId) -> Result<bool, BlobPinningError> {
+            Ok(blob_id.0[0] == 0)
+        }
+
+        fn unpin_blob(&self, _blob_id: &BlobId) -> Result<bool, BlobPinningError> {
+            Ok(true)
+        }
+
+        async fn list_pinned_blobs(&self) -> Result<Vec<PinnedBlob>, BlobPinningError> {
+            Ok(vec![PinnedBlob {
+                blob_id: BlobId([0; 32]),
+                pinned_at: 0,
+                metadata_bytes: 100,
+                stored_slivers: 2,
+                sliver_bytes: 1024,
+            }])
+        }
+
+        /// Reports blob IDs starting with zero as newly denied, and all others as already denied.
+        fn deny_blob(&self, blob_id: &BlobId) -> Result<bool, DenyListError> {
+            Ok(blob_id.0[0] == 0)
+        }
+
+        fn allow_blob(&self, _blob_id: &BlobId) -> Result<bool, DenyListError> {
+            Ok(true)
+        }
+
+        fn list_denied_blobs(&self) -> Vec<BlobId> {
+            vec![BlobId([0; 32])]
+        }
+
+        fn list_dead_letter_events(&self) -> Result<Vec<DeadLetterEvent>, DeadLetterQueueError> {
+            Ok(vec![DeadLetterEvent {
+                event_index: 7,
+                event_id: event_id_for_testing(),
+                event: BlobEvent::Registered(BlobRegistered::for_testing(BlobId([0; 32]))),
+                attempts: 5,
+                error: "metadata unavailable".to_owned(),
+                last_attempt_at: 0,
+            }])
+        }
+
+        /// Successfully retries the event with index 7, which is the only event in the queue.
+        async fn retry_dead_letter_event(
+            &self,
+            event_index: u64,
+        ) -> Result<(), DeadLetterQueueError> {
+            if event_index == 7 {
+                Ok(())
+            } else {
+                Err(DeadLetterQueueError::NotFound(event_index))
+            }
+        }
+
+        /// Lists the blob ID with all bytes set to the shard index, and fails for shards other than
+        /// 0 and 1, which are not stored.
+        async fn list_shard_blobs(
+            &self,
+            shard_index: ShardIndex,
+            _after: Option<BlobId>,
+            _limit: usize,
+        ) -> Result<ShardBlobs, StorageInspectionError> {
+            if shard_index.0 > 1 {
+                return Err(StorageInspectionError::ShardNotStored(shard_index));
+            }
+            Ok(ShardBlobs {
+                shard_index,
+                blob_ids: vec![BlobId([shard_index.0 as u8; 32])],
+                next_after: None,
+            })
+        }
+
+        async fn inspect_blob_storage(
+            &self,
+            blob_id: &BlobId,
+        ) -> Result<BlobStorageReport, StorageInspectionError> {
+            Ok(BlobStorageReport {
+                blob_id: *blob_id,
+                metadata_bytes: Some(100),
+                blob_info: None,
+                shards: vec![ShardSliverReport {
+                    shard_index: ShardIndex(0),
+                    primary_sliver_bytes: Some(512),
+                    secondary_sliver_bytes: None,
+                }],
+            })
+        }
+
+        fn event_cursor_report(&self) -> Result<EventCursorReport, StorageInspectionError> {
+            Ok(EventCursorReport {
+                event_id: Some(event_id_for_testing()),
+                next_event_index: 42,
+            })
+        }
+
+        async fn column_family_sizes(
+            &self,
+        ) -> Result<Vec<ColumnFamilySize>, StorageInspectionError> {
+            Ok(vec![])
+        }
+
+        /// Reports a change when enabling maintenance mode, as the mock is never in maintenance.
+        fn set_maintenance_mode(&self, enabled: bool) -> bool {
+            enabled
+        }
+
+        /// Reports a change when pausing a worker, as the mock never pauses its workers.
+        fn set_background_worker_paused(&self, _worker: BackgroundWorker, paused: bool) -> bool {
+            paused
+        }
+
+        fn background_worker_statuses(&self) -> Vec<BackgroundWorkerStatus> {
+            BackgroundWorker::ALL
+                .into_iter()
+                .map(|worker| BackgroundWorkerStatus {
+                    worker,
+                    paused: false,
+                })
+                .collect()
+        }
+
+        async fn status_report(&self) -> NodeStatusReport {
+            NodeStatusReport {
+                health: self.health_info(true),
+                maintenance_mode: false,
+                epoch_sync_done: EpochSyncDoneStatus {
+                    last_attested_epoch: Some(1),
+                    pending_epoch: None,
+                },
+                background_workers: self.background_worker_statuses(),
+                storage: StorageUsage {
+                    n_shards: 1,
+                    stored_slivers: 2,
+                    stored_bytes: 1024,
+                },
+                event_lag: None,
+                peers: vec![],
+            }
+        }
+
+        fn shard_assignment_diff(&self) -> ShardAssignmentDiff {
+            ShardAssignmentDiff {
+                epoch: 1,
+                gained: vec![ShardIndex(2)],
+                lost: vec![ShardIndex(3)],
+                retained: vec![ShardIndex(0), ShardIndex(1)],
+            }
+        }
     }
 
     async fn start_rest_api_with_config(
@@ -845,6 +1273,32 @@ mod tests {
         assert_eq!(result, BlobStatus::Nonexistent);
     }
 
+    #[tokio::test]
+    async fn get_blob_storage_status() {
+        let (config, _handle) = start_rest_api_with_test_config().await;
+        let client = storage_node_client(config.as_ref());
+
+        let storage_status = client
+            .get_blob_storage_status(&blob_id_for_valid_response())
+            .await
+            .expect("should successfully return the storage status");
+        assert_eq!(storage_status.metadata, StoredOnNodeStatus::Stored);
+        assert_eq!(storage_status.shards.len(), 1);
+        assert_eq!(
+            storage_status.shards[0].secondary_sliver,
+            StoredOnNodeStatus::Nonexistent
+        );
+
+        let err = client
+            .get_blob_storage_status(&blob_id_for_internal_server_error())
+            .await
+            .expect_err("storage status request must fail");
+        assert_eq!(
+            err.http_status_code(),
+            Some(StatusCode::INTERNAL_SERVER_ERROR)
+        );
+    }
+
     #[tokio::test]
     async fn get_blob_status_internal_error() {
         let (config, _handle) = start_rest_api_with_test_config().await;
@@ -883,6 +1337,565 @@ mod tests {
         assert_eq!(res.status(), StatusCode::CREATED);
     }
 
+    #[tokio::test]
+    async fn push_metadata() {
+        let (config, _handle) = start_rest_api_with_test_config().await;
+        let client = storage_node_client(config.as_ref());
+
+        let metadata = vec![walrus_core::test_utils::verified_blob_metadata(); 2];
+        let n_stored = client
+            .push_metadata(&metadata)
+            .await
+            .expect("should successfully push the metadata");
+
+        assert_eq!(n_stored, 2);
+    }
+
+    async fn start_rest_api_with_admin_token(
+        token: &str,
+    ) -> (
+        WithTempDir<StorageNodeConfig>,
+        JoinHandle<Result<(), anyhow::Error>>,
+    ) {
+        let mut config = test_utils::storage_node_config();
+        config.as_mut().rest_server.admin_api_token = Some(token.to_owned());
+        let handle = start_rest_api_with_config(config.as_ref()).await;
+        (config, handle)
+    }
+
+    #[tokio::test]
+    async fn pin_blob_requires_admin_token() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+
+        let blob_id = blob_id_for_valid_response().to_string();
+        let path = routes::PINNED_BLOB_ENDPOINT.replace("{blob_id}", &blob_id);
+        let url = format!("https://{}{path}", config.as_ref().rest_api_address);
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client.put(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client.put(&url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let status: walrus_sdk::api::errors::Status = res.json().await.unwrap();
+        assert_eq!(status.reason(), Some("INVALID_ADMIN_TOKEN"));
+        assert!(!status.is_retryable());
+
+        let res = client.put(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn error_responses_include_request_id() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+
+        let blob_id = blob_id_for_valid_response().to_string();
+        let path = routes::PINNED_BLOB_ENDPOINT.replace("{blob_id}", &blob_id);
+        let url = format!("https://{}{path}", config.as_ref().rest_api_address);
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client
+            .put(&url)
+            .header(REQUEST_ID_HEADER, "req-1234")
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.headers()[REQUEST_ID_HEADER], "req-1234");
+        let status: walrus_sdk::api::errors::Status = res.json().await.unwrap();
+        assert_eq!(status.request_id(), Some("req-1234"));
+
+        // Requests without a valid ID are assigned one.
+        let res = client
+            .put(&url)
+            .header(REQUEST_ID_HEADER, "")
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .unwrap();
+        let assigned = res.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        assert!(!assigned.is_empty());
+        let status: walrus_sdk::api::errors::Status = res.json().await.unwrap();
+        assert_eq!(status.request_id(), Some(assigned.as_str()));
+    }
+
+    #[tokio::test]
+    async fn list_pinned_blobs() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+
+        let url = format!(
+            "https://{}{}",
+            config.as_ref().rest_api_address,
+            routes::PINNED_BLOBS_ENDPOINT
+        );
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let body: serde_json::Value = res.json().await.unwrap();
+        let pinned = &body["success"]["data"];
+        assert_eq!(pinned.as_array().map(Vec::len), Some(1));
+        assert_eq!(pinned[0]["sliverBytes"], 1024);
+    }
+
+    #[tokio::test]
+    async fn deny_and_list_denied_blobs() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let path = routes::DENIED_BLOB_ENDPOINT.replace("{blob_id}", &BlobId([0; 32]).to_string());
+        let url = format!("https://{}{path}", config.as_ref().rest_api_address);
+
+        let res = client.put(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client.put(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        let res = client
+            .delete(&url)
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let url = format!(
+            "https://{}{}",
+            config.as_ref().rest_api_address,
+            routes::DENIED_BLOBS_ENDPOINT
+        );
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(
+            body["success"]["data"],
+            serde_json::json!([BlobId([0; 32]).to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn toggle_maintenance_mode() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+
+        let url = format!(
+            "https://{}{}",
+            config.as_ref().rest_api_address,
+            routes::MAINTENANCE_ENDPOINT
+        );
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client.put(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client.put(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"], "maintenance mode enabled");
+
+        let res = client
+            .delete(&url)
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"], "maintenance mode already disabled");
+    }
+
+    #[tokio::test]
+    async fn pause_and_resume_background_workers() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let address = config.as_ref().rest_api_address;
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let url = format!("https://{address}{}", routes::BACKGROUND_WORKERS_ENDPOINT);
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"][1]["worker"], "shardSync");
+
+        let path = routes::BACKGROUND_WORKER_PAUSE_ENDPOINT.replace("{worker}", "shardSync");
+        let url = format!("https://{address}{path}");
+        let res = client.put(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client.put(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"], "worker paused");
+
+        let res = client
+            .delete(&url)
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"], "worker already running");
+
+        let path = routes::BACKGROUND_WORKER_PAUSE_ENDPOINT.replace("{worker}", "unknown");
+        let url = format!("https://{address}{path}");
+        let res = client.put(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn list_and_retry_dead_letter_events() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let address = config.as_ref().rest_api_address;
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let url = format!("https://{address}{}", routes::DEAD_LETTER_EVENTS_ENDPOINT);
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        let events = &body["success"]["data"];
+        assert_eq!(events.as_array().map(Vec::len), Some(1));
+        assert_eq!(events[0]["eventIndex"], 7);
+        assert_eq!(events[0]["error"], "metadata unavailable");
+
+        let retry_url = |event_index: u64| {
+            let path = routes::DEAD_LETTER_EVENT_RETRY_ENDPOINT
+                .replace("{event_index}", &event_index.to_string());
+            format!("https://{address}{path}")
+        };
+        let res = client.post(retry_url(7)).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client
+            .post(retry_url(7))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client
+            .post(retry_url(8))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn inspect_storage_through_admin_api() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let address = config.as_ref().rest_api_address;
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let shard_blobs_url = |shard_index: u16| {
+            let path = routes::STORAGE_SHARD_BLOBS_ENDPOINT
+                .replace("{shard_index}", &shard_index.to_string());
+            format!("https://{address}{path}?limit=10")
+        };
+        let res = client
+            .get(shard_blobs_url(1))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"]["shardIndex"], 1);
+        assert_eq!(
+            body["success"]["data"]["blobIds"][0],
+            BlobId([1; 32]).to_string()
+        );
+
+        let res = client
+            .get(shard_blobs_url(2))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let url = format!("https://{address}{}", routes::STORAGE_EVENT_CURSOR_ENDPOINT);
+        let res = client.get(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"]["nextEventIndex"], 42);
+    }
+
+    #[tokio::test]
+    async fn get_shard_assignment_through_admin_api() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let url = format!(
+            "https://{}{}",
+            config.as_ref().rest_api_address,
+            routes::SHARD_ASSIGNMENT_ENDPOINT
+        );
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client.get(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        let assignment: ShardAssignmentDiff =
+            serde_json::from_value(body["success"]["data"].clone()).unwrap();
+        assert_eq!(assignment, MockServiceState.shard_assignment_diff());
+    }
+
+    #[tokio::test]
+    async fn update_trace_sampling_through_admin_api() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let url = format!(
+            "https://{}{}",
+            config.as_ref().rest_api_address,
+            routes::TRACE_SAMPLING_ENDPOINT
+        );
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client
+            .put(&url)
+            .bearer_auth("secret")
+            .json(&serde_json::json!({ "span_rates": { "blob_store receive": 1.5 } }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let sampling = serde_json::json!({
+            "default_rate": 1.0,
+            "span_rates": { "blob_store receive": 0.01 },
+        });
+        let res = client
+            .put(&url)
+            .bearer_auth("secret")
+            .json(&sampling)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"], sampling);
+
+        TraceSampler::global()
+            .set_config(Default::default())
+            .expect("the default rates are valid");
+    }
+
+    #[tokio::test]
+    async fn get_node_status_and_status_page() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let address = config.as_ref().rest_api_address;
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let url = format!("https://{address}{}", routes::NODE_STATUS_ENDPOINT);
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["success"]["data"]["storage"]["storedBytes"], 1024);
+        assert_eq!(body["success"]["data"]["health"]["nodeStatus"], "Active");
+        assert_eq!(
+            body["success"]["data"]["epochSyncDone"]["lastAttestedEpoch"],
+            1
+        );
+        assert!(body["success"]["data"]["health"]
+            .get("epochSyncDone")
+            .is_none());
+
+        // Unauthenticated requests for the page are redirected to the login form.
+        let url = format!("https://{address}{}", routes::NODE_STATUS_PAGE_ENDPOINT);
+        let res = client.get(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.url().path(), routes::ADMIN_LOGIN_ENDPOINT);
+        assert!(res.text().await.unwrap().contains("name=\"token\""));
+
+        let res = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/html")));
+        assert!(res.text().await.unwrap().contains("1.00 KiB"));
+    }
+
+    #[tokio::test]
+    async fn status_page_can_be_opened_after_logging_in() {
+        let (config, _handle) = start_rest_api_with_admin_token("secret").await;
+        let address = config.as_ref().rest_api_address;
+        let network_public_key = config
+            .as_ref()
+            .network_key_pair
+            .get()
+            .unwrap()
+            .public()
+            .clone();
+        let client = ClientBuilder::from_reqwest(
+            reqwest::Client::builder().redirect(reqwest::redirect::Policy::none()),
+        )
+        .no_proxy()
+        .tls_built_in_root_certs(false)
+        .authenticate_with_public_key(network_public_key)
+        .build(&address.to_string())
+        .unwrap()
+        .into_inner();
+        let login_url = format!("https://{address}{}", routes::ADMIN_LOGIN_ENDPOINT);
+        let page_url = format!("https://{address}{}", routes::NODE_STATUS_PAGE_ENDPOINT);
+
+        let res = client
+            .post(&login_url)
+            .form(&[("token", "wrong")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client
+            .post(&login_url)
+            .form(&[("token", "secret")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::SEE_OTHER);
+        let cookie = res
+            .headers()
+            .get(axum::http::header::SET_COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .expect("the session cookie is set")
+            .to_owned();
+
+        let res = client
+            .get(&page_url)
+            .header(axum::http::header::COOKIE, &cookie)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.text().await.unwrap().contains("1.00 KiB"));
+
+        // The session cookie does not authorize requests that modify the state of the node.
+        let url = format!("https://{address}{}", routes::MAINTENANCE_ENDPOINT);
+        let res = client
+            .put(&url)
+            .header(axum::http::header::COOKIE, &cookie)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn admin_routes_are_not_served_without_token() {
+        let (config, _handle) = start_rest_api_with_test_config().await;
+
+        let url = format!(
+            "https://{}{}",
+            config.as_ref().rest_api_address,
+            routes::PINNED_BLOBS_ENDPOINT
+        );
+        let client = storage_node_client(config.as_ref()).into_inner();
+
+        let res = client.get(&url).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn admin_routes_are_only_served_on_admin_listeners() {
+        let admin_address = test_utils::unused_socket_address(false);
+        let mut config = test_utils::storage_node_config();
+        config.as_mut().rest_server.admin_api_token = Some("secret".to_owned());
+        config.as_mut().rest_server.admin_listeners = vec![ListenerConfig {
+            address: admin_address,
+            tls: Some(TlsConfig {
+                disable_tls: true,
+                ..Default::default()
+            }),
+        }];
+        let _handle = start_rest_api_with_config(config.as_ref()).await;
+
+        let public_address = config.as_ref().rest_api_address;
+        let public_client = storage_node_client(config.as_ref()).into_inner();
+        let res = public_client
+            .get(format!(
+                "https://{public_address}{}",
+                routes::NODE_STATUS_ENDPOINT
+            ))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let res = public_client
+            .get(format!(
+                "https://{public_address}{}",
+                routes::HEALTH_ENDPOINT
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // The admin listener does not use TLS.
+        let admin_client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let res = admin_client
+            .get(format!(
+                "http://{admin_address}{}",
+                routes::NODE_STATUS_ENDPOINT
+            ))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = admin_client
+            .get(format!("http://{admin_address}{}", routes::HEALTH_ENDPOINT))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn ipv6_listeners_are_only_v6_if_sharing_the_port_with_the_ipv4_wildcard() {
+        let listener = |address: &str| RestApiListener {
+            bind_address: address.parse().unwrap(),
+            tls_certificate: None,
+            endpoints: ListenerEndpoints::All,
+        };
+        let v6_address = "[::]:9185".parse().unwrap();
+
+        assert!(!shares_port_with_ipv4_wildcard(
+            v6_address,
+            &[listener("[::]:9185")]
+        ));
+        assert!(!shares_port_with_ipv4_wildcard(
+            v6_address,
+            &[listener("[::]:9185"), listener("0.0.0.0:9186")]
+        ));
+        assert!(!shares_port_with_ipv4_wildcard(
+            v6_address,
+            &[listener("[::]:9185"), listener("127.0.0.1:9185")]
+        ));
+        assert!(shares_port_with_ipv4_wildcard(
+            v6_address,
+            &[listener("0.0.0.0:9185"), listener("[::]:9185")]
+        ));
+        assert!(!shares_port_with_ipv4_wildcard(
+            "0.0.0.0:9185".parse().unwrap(),
+            &[listener("0.0.0.0:9185"), listener("[::]:9185")]
+        ));
+    }
+
     #[tokio::test]
     async fn retrieve_sliver() {
         let (config, _handle) = start_rest_api_with_test_config().await;
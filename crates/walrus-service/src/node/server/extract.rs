@@ -5,7 +5,7 @@ use anyhow::anyhow;
 use axum::{
     body::Bytes,
     extract::{rejection::BytesRejection, FromRequest, FromRequestParts, Request},
-    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
+    http::{header, request::Parts, HeaderMap, HeaderValue},
     response::{IntoResponse, Response},
 };
 use fastcrypto::traits::EncodeDecodeBase64 as _;
@@ -111,6 +111,18 @@ where
     }
 }
 
+#[derive(Debug, thiserror::Error, RestApiError)]
+#[rest_api_error(domain = ERROR_DOMAIN)]
+pub enum AuthorizationRejection {
+    #[error("missing Authorization header")]
+    #[rest_api_error(reason = "MISSING_AUTHORIZATION", status = ApiStatusCode::Unauthenticated)]
+    Missing,
+
+    #[error("invalid Authorization header, expected a Base64-encoded public key")]
+    #[rest_api_error(reason = "INVALID_AUTHORIZATION", status = ApiStatusCode::InvalidArgument)]
+    Invalid,
+}
+
 // The following code is used to extract the public key in the Authorization header from a request.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 #[must_use]
@@ -120,21 +132,20 @@ impl<S> FromRequestParts<S> for Authorization
 where
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, &'static str);
+    type Rejection = AuthorizationRejection;
 
     async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
-        const INVALID_AUTH_ERROR: (StatusCode, &str) =
-            (StatusCode::BAD_REQUEST, "Invalid Authorization header");
-
         let auth_header = parts
             .headers
             .get(AUTHORIZATION)
-            .ok_or((StatusCode::UNAUTHORIZED, "Missing Authorization header"))?;
+            .ok_or(AuthorizationRejection::Missing)?;
 
-        let key_bytes = auth_header.to_str().map_err(|_| INVALID_AUTH_ERROR)?;
+        let key_bytes = auth_header
+            .to_str()
+            .map_err(|_| AuthorizationRejection::Invalid)?;
 
         Ok(Authorization(
-            PublicKey::decode_base64(key_bytes).map_err(|_| INVALID_AUTH_ERROR)?,
+            PublicKey::decode_base64(key_bytes).map_err(|_| AuthorizationRejection::Invalid)?,
         ))
     }
 }
@@ -4,7 +4,9 @@
 use walrus_core::{messages::SignedMessage, EpochSchema, SliverPairIndex, SliverType, SymbolId};
 use walrus_sdk::api::{
     errors::Status,
+    BlobStorageStatus,
     ServiceHealthInfo,
+    ShardBlobStorageStatus,
     ShardHealthInfo,
     ShardStatus,
     ShardStatusDetail,
@@ -24,6 +26,7 @@ pub(super) const GROUP_SYNC_SHARD: &str = "Sync Shard";
 #[openapi(
     paths(
         routes::get_blob_status,
+        routes::get_blob_storage_status,
         routes::get_deletable_blob_confirmation,
         routes::get_metadata,
         routes::get_permanent_blob_confirmation,
@@ -36,10 +39,12 @@ pub(super) const GROUP_SYNC_SHARD: &str = "Sync Shard";
         routes::put_sliver,
     ),
     components(schemas(
+        BlobStorageStatus,
         EpochSchema,
         EventIdSchema,
         ObjectIdSchema,
         ServiceHealthInfo,
+        ShardBlobStorageStatus,
         ShardHealthInfo,
         ShardStatus,
         ShardStatusDetail,
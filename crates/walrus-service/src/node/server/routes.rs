@@ -4,9 +4,17 @@
 use std::{num::NonZeroU16, sync::Arc};
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    extract::{Path, Query, Request, State},
+    http::{
+        header::{AUTHORIZATION, COOKIE, SET_COOKIE},
+        HeaderMap,
+        Method,
+        StatusCode,
+    },
+    middleware::Next,
+    response::{Html, IntoResponse, Redirect, Response},
+    Form,
+    Json,
 };
 use axum_extra::extract::Query as ExtraQuery;
 use serde::Deserialize;
@@ -26,6 +34,7 @@ use walrus_core::{
     BlobId,
     InconsistencyProof,
     RecoverySymbol,
+    ShardIndex,
     Sliver,
     SliverIndex,
     SliverPairIndex,
@@ -33,7 +42,7 @@ use walrus_core::{
     SymbolId,
 };
 use walrus_sdk::{
-    api::{BlobStatus, ServiceHealthInfo, StoredOnNodeStatus},
+    api::{BlobStatus, BlobStorageStatus, ServiceHealthInfo, StoredOnNodeStatus},
     client::RecoverySymbolsFilter,
 };
 use walrus_sui::ObjectIdSchema;
@@ -42,21 +51,46 @@ use super::{
     extract::{Authorization, Bcs},
     openapi::{self},
     responses::OrRejection,
+    status_page,
 };
 use crate::{
-    common::api::{ApiSuccess, BlobIdString},
+    common::{
+        api::{ApiSuccess, BlobIdString},
+        config::TraceSamplingConfig,
+        telemetry::TraceSampler,
+    },
     node::{
-        errors::{IndexOutOfRange, ListSymbolsError},
+        committee::ShardAssignmentDiff,
+        errors::{
+            AdminAuthError,
+            BlobPinningError,
+            DeadLetterQueueError,
+            DenyListError,
+            IndexOutOfRange,
+            ListSymbolsError,
+            StorageInspectionError,
+            TraceSamplingError,
+        },
+        BackgroundWorker,
+        BackgroundWorkerStatus,
         BlobStatusError,
+        BlobStorageReport,
+        ColumnFamilySize,
         ComputeStorageConfirmationError,
+        DeadLetterEvent,
+        EventCursorReport,
         InconsistencyProofError,
+        NodeStatusReport,
+        PinnedBlob,
         RetrieveMetadataError,
         RetrieveSliverError,
         RetrieveSymbolError,
         ServiceState,
+        ShardBlobs,
         StoreMetadataError,
         StoreSliverError,
         SyncShardServiceError,
+        DEFAULT_SHARD_BLOBS_LIMIT,
     },
 };
 
@@ -88,8 +122,48 @@ pub const INCONSISTENCY_PROOF_ENDPOINT: &str =
     "/v1/blobs/{blob_id}/inconsistencyProof/{sliver_type}";
 /// The path to get the status of a blob.
 pub const BLOB_STATUS_ENDPOINT: &str = "/v1/blobs/{blob_id}/status";
+/// The path to check which data of a blob is stored in the shards of the node.
+pub const BLOB_STORAGE_STATUS_ENDPOINT: &str = "/v1/blobs/{blob_id}/storage-status";
 pub const HEALTH_ENDPOINT: &str = "/v1/health";
 pub const SYNC_SHARD_ENDPOINT: &str = "/v1/migrate/sync_shard";
+/// The path to push the metadata of multiple blobs to a node.
+pub const PUSH_METADATA_ENDPOINT: &str = "/v1/migrate/metadata";
+/// The path to list the blobs pinned on the node.
+pub const PINNED_BLOBS_ENDPOINT: &str = "/v1/admin/pinned";
+/// The path to pin and unpin a blob on the node.
+pub const PINNED_BLOB_ENDPOINT: &str = "/v1/admin/pinned/{blob_id}";
+/// The path to list the blobs on the deny list of the node.
+pub const DENIED_BLOBS_ENDPOINT: &str = "/v1/admin/denied";
+/// The path to add a blob to and remove it from the deny list of the node.
+pub const DENIED_BLOB_ENDPOINT: &str = "/v1/admin/denied/{blob_id}";
+/// The path to enable and disable maintenance mode.
+pub const MAINTENANCE_ENDPOINT: &str = "/v1/admin/maintenance";
+/// The path to list the background workers of the node and whether they are paused.
+pub const BACKGROUND_WORKERS_ENDPOINT: &str = "/v1/admin/workers";
+/// The path to pause and resume a background worker of the node.
+pub const BACKGROUND_WORKER_PAUSE_ENDPOINT: &str = "/v1/admin/workers/{worker}/pause";
+/// The path to list the events in the dead-letter queue of the node.
+pub const DEAD_LETTER_EVENTS_ENDPOINT: &str = "/v1/admin/deadLetter";
+/// The path to retry an event from the dead-letter queue of the node.
+pub const DEAD_LETTER_EVENT_RETRY_ENDPOINT: &str = "/v1/admin/deadLetter/{event_index}/retry";
+/// The path to get a summary of the state of the node.
+pub const NODE_STATUS_ENDPOINT: &str = "/v1/admin/status";
+/// The path to get a summary of the state of the node as an HTML page.
+pub const NODE_STATUS_PAGE_ENDPOINT: &str = "/v1/admin/status/page";
+/// The path to log in to the node status page from a browser.
+pub const ADMIN_LOGIN_ENDPOINT: &str = "/v1/admin/login";
+/// The path to get the shards gained, lost, and retained by the node in the current epoch.
+pub const SHARD_ASSIGNMENT_ENDPOINT: &str = "/v1/admin/shards/assignment";
+/// The path to list the blobs stored in a shard of the node.
+pub const STORAGE_SHARD_BLOBS_ENDPOINT: &str = "/v1/admin/storage/shards/{shard_index}/blobs";
+/// The path to inspect the data stored for a blob on the node.
+pub const STORAGE_BLOB_ENDPOINT: &str = "/v1/admin/storage/blobs/{blob_id}";
+/// The path to get the event cursor stored by the node.
+pub const STORAGE_EVENT_CURSOR_ENDPOINT: &str = "/v1/admin/storage/eventCursor";
+/// The path to list the sizes of the column families of the node's database.
+pub const STORAGE_COLUMN_FAMILIES_ENDPOINT: &str = "/v1/admin/storage/columnFamilies";
+/// The path to get and set the rates at which traces are sampled.
+pub const TRACE_SAMPLING_ENDPOINT: &str = "/v1/admin/tracing/sampling";
 
 /// Convenience trait to apply bounds on the ServiceState.
 trait SyncServiceState: ServiceState + Send + Sync + 'static {}
@@ -637,6 +711,33 @@ pub async fn get_blob_status<S: SyncServiceState>(
     Ok(ApiSuccess::ok(state.blob_status(&blob_id)?))
 }
 
+/// Get the storage status of a blob.
+///
+/// Gets whether the metadata of the blob is stored on this storage node and, for each shard that
+/// the node owns in the current epoch, whether the primary and secondary slivers of the blob are
+/// stored. This allows determining which nodes are missing data of a blob.
+#[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+#[utoipa::path(
+    get,
+    path = BLOB_STORAGE_STATUS_ENDPOINT,
+    params(("blob_id" = BlobId,)),
+    responses(
+        (
+            status = 200,
+            description = "The storage status of the metadata and slivers of the blob",
+            body = ApiSuccess<BlobStorageStatus>,
+        ),
+        BlobStatusError
+    ),
+    tag = openapi::GROUP_STATUS
+)]
+pub async fn get_blob_storage_status<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Result<ApiSuccess<BlobStorageStatus>, BlobStatusError> {
+    Ok(ApiSuccess::ok(state.blob_storage_status(&blob_id).await?))
+}
+
 #[derive(Debug, Clone, serde::Deserialize, utoipa::IntoParams)]
 #[serde(rename_all = "camelCase")]
 pub struct HealthInfoQuery {
@@ -686,3 +787,480 @@ pub async fn sync_shard<S: SyncServiceState>(
 ) -> Result<Response, OrRejection<SyncShardServiceError>> {
     Ok(Bcs(state.sync_shard(public_key, signed_request).await?).into_response())
 }
+
+/// Push blob metadata.
+///
+/// Stores the metadata of multiple registered Walrus blobs at this storage node. This is used by
+/// storage nodes to seed the metadata at nodes that obtain new shards during epoch change. Metadata
+/// of blobs that are not registered at this node or that have been marked as invalid is skipped.
+///
+/// Returns the number of metadata that were newly stored.
+#[tracing::instrument(skip_all, fields(walrus.metadata_count = metadata.len()))]
+#[utoipa::path(
+    put,
+    path = PUSH_METADATA_ENDPOINT,
+    request_body(content = [u8], description = "BCS-encoded vector of metadata with blob IDs"),
+    responses(
+        (status = OK, description = "Number of newly stored metadata", body = ApiSuccess<u64>),
+        StoreMetadataError,
+    ),
+    tag = openapi::GROUP_SYNC_SHARD
+)]
+pub async fn push_metadata<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Bcs(metadata): Bcs<Vec<UnverifiedBlobMetadataWithId>>,
+) -> Result<ApiSuccess<u64>, StoreMetadataError> {
+    let mut n_stored = 0;
+    for metadata in metadata {
+        let blob_id = *metadata.blob_id();
+        match state.store_metadata(metadata) {
+            Ok(true) => n_stored += 1,
+            Ok(false) => (),
+            Err(
+                error @ (StoreMetadataError::NotCurrentlyRegistered
+                | StoreMetadataError::InvalidBlob(_)
+                | StoreMetadataError::Forbidden),
+            ) => {
+                tracing::debug!(%blob_id, %error, "skipping pushed metadata");
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(ApiSuccess::ok(n_stored))
+}
+
+/// The name of the cookie that authenticates a browser session with the administrative API.
+const ADMIN_SESSION_COOKIE: &str = "walrus-admin-session";
+
+/// The token configured for the administrative API.
+#[derive(Debug, Clone)]
+pub struct AdminToken {
+    token: Arc<str>,
+    /// The value of the session cookie, which is the hex encoding of the token, such that it only
+    /// contains characters that are valid in a cookie.
+    session_cookie_value: Arc<str>,
+}
+
+impl AdminToken {
+    /// Creates a new admin token.
+    pub fn new(token: &str) -> Self {
+        let session_cookie_value: String =
+            token.bytes().map(|byte| format!("{byte:02x}")).collect();
+        Self {
+            token: token.into(),
+            session_cookie_value: session_cookie_value.into(),
+        }
+    }
+
+    fn is_token(&self, provided: &str) -> bool {
+        constant_time_eq(provided.as_bytes(), self.token.as_bytes())
+    }
+
+    fn is_bearer_of(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|provided| self.is_token(provided))
+    }
+
+    fn has_session_cookie(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .any(|(name, value)| {
+                name == ADMIN_SESSION_COOKIE
+                    && constant_time_eq(value.as_bytes(), self.session_cookie_value.as_bytes())
+            })
+    }
+}
+
+/// Rejects requests that do not carry the bearer token configured for the administrative API.
+///
+/// `GET` requests are also accepted with the session cookie set by [`admin_login`], such that the
+/// status page can be opened in a browser; requests that modify the state of the node always
+/// require the bearer token. Unauthenticated requests for the status page are redirected to the
+/// login form.
+pub async fn require_admin_token(
+    State(token): State<AdminToken>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_authorized = token.is_bearer_of(request.headers())
+        || (request.method() == Method::GET && token.has_session_cookie(request.headers()));
+
+    if is_authorized {
+        next.run(request).await
+    } else if request.uri().path() == NODE_STATUS_PAGE_ENDPOINT {
+        Redirect::to(ADMIN_LOGIN_ENDPOINT).into_response()
+    } else {
+        AdminAuthError::InvalidToken.into_response()
+    }
+}
+
+/// The form submitted to log in to the administrative API from a browser.
+#[derive(Debug, Deserialize)]
+pub struct AdminLoginForm {
+    token: String,
+}
+
+/// Get the login form.
+///
+/// Renders a form to log in to the node status page with the admin token. This is an
+/// administrative endpoint and is not part of the public API.
+pub async fn get_admin_login() -> Html<String> {
+    Html(status_page::render_login(false))
+}
+
+/// Log in to the administrative API.
+///
+/// If the submitted token is the admin token, sets a session cookie that authenticates subsequent
+/// `GET` requests to the administrative endpoints, and redirects to the node status page. This is
+/// an administrative endpoint and is not part of the public API.
+pub async fn admin_login(
+    State(token): State<AdminToken>,
+    Form(form): Form<AdminLoginForm>,
+) -> Response {
+    if !token.is_token(&form.token) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Html(status_page::render_login(true)),
+        )
+            .into_response();
+    }
+    // The cookie is not marked as secure, as the admin listeners may not use TLS.
+    let cookie = format!(
+        "{ADMIN_SESSION_COOKIE}={}; Path=/v1/admin; HttpOnly; SameSite=Strict",
+        token.session_cookie_value
+    );
+    (
+        [(SET_COOKIE, cookie)],
+        Redirect::to(NODE_STATUS_PAGE_ENDPOINT),
+    )
+        .into_response()
+}
+
+/// Compares the two byte strings in time independent of the position of the first difference.
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// List pinned blobs.
+///
+/// Lists the blobs pinned on this node, along with the amount of data stored for each. This is an
+/// administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all, err(level = Level::DEBUG))]
+pub async fn list_pinned_blobs<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> Result<ApiSuccess<Vec<PinnedBlob>>, BlobPinningError> {
+    Ok(ApiSuccess::ok(state.list_pinned_blobs().await?))
+}
+
+/// Enable maintenance mode.
+///
+/// While in maintenance mode, the node keeps serving reads and shard-sync requests, but rejects new
+/// metadata and slivers with a retryable error, defers blob syncs and node recovery, and pauses its
+/// periodic background jobs. This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn enable_maintenance_mode<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> ApiSuccess<&'static str> {
+    if state.set_maintenance_mode(true) {
+        ApiSuccess::ok("maintenance mode enabled")
+    } else {
+        ApiSuccess::ok("maintenance mode already enabled")
+    }
+}
+
+/// Disable maintenance mode.
+///
+/// This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn disable_maintenance_mode<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> ApiSuccess<&'static str> {
+    if state.set_maintenance_mode(false) {
+        ApiSuccess::ok("maintenance mode disabled")
+    } else {
+        ApiSuccess::ok("maintenance mode already disabled")
+    }
+}
+
+/// List background workers.
+///
+/// Lists the background workers of the node and whether each is paused. This is an administrative
+/// endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn list_background_workers<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> ApiSuccess<Vec<BackgroundWorkerStatus>> {
+    ApiSuccess::ok(state.background_worker_statuses())
+}
+
+/// Pause a background worker.
+///
+/// The worker finishes the unit of work it is currently processing and then waits until it is
+/// resumed. Pausing is not persisted across restarts of the node. This is an administrative
+/// endpoint and is not part of the public API.
+#[tracing::instrument(skip_all, fields(worker = ?worker))]
+pub async fn pause_background_worker<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(worker): Path<BackgroundWorker>,
+) -> ApiSuccess<&'static str> {
+    if state.set_background_worker_paused(worker, true) {
+        ApiSuccess::ok("worker paused")
+    } else {
+        ApiSuccess::ok("worker already paused")
+    }
+}
+
+/// Resume a background worker.
+///
+/// This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all, fields(worker = ?worker))]
+pub async fn resume_background_worker<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(worker): Path<BackgroundWorker>,
+) -> ApiSuccess<&'static str> {
+    if state.set_background_worker_paused(worker, false) {
+        ApiSuccess::ok("worker resumed")
+    } else {
+        ApiSuccess::ok("worker already running")
+    }
+}
+
+/// List dead-letter events.
+///
+/// Lists the blob events that repeatedly failed to be processed and were moved to the dead-letter
+/// queue, along with the error of their last attempt. This is an administrative endpoint and is not
+/// part of the public API.
+#[tracing::instrument(skip_all, err(level = Level::DEBUG))]
+pub async fn list_dead_letter_events<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> Result<ApiSuccess<Vec<DeadLetterEvent>>, DeadLetterQueueError> {
+    Ok(ApiSuccess::ok(state.list_dead_letter_events()?))
+}
+
+/// Retry a dead-letter event.
+///
+/// Processes the event from the dead-letter queue again, and removes it from the queue if it
+/// succeeds. If it fails again, the event remains in the queue with the new error. This is an
+/// administrative endpoint and is not part of the public API.
+#[tracing::instrument(
+    skip_all,
+    fields(walrus.event.index = event_index),
+    err(level = Level::DEBUG)
+)]
+pub async fn retry_dead_letter_event<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(event_index): Path<u64>,
+) -> Result<ApiSuccess<&'static str>, DeadLetterQueueError> {
+    state.retry_dead_letter_event(event_index).await?;
+    Ok(ApiSuccess::ok("event processed"))
+}
+
+/// Get the node status.
+///
+/// Summarizes the state of the node, including its epoch, the status of its shards, the amount of
+/// data it stores, the lag of its event processing, and the health of its peers. This is an
+/// administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn get_node_status<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> ApiSuccess<NodeStatusReport> {
+    ApiSuccess::ok(state.status_report().await)
+}
+
+/// Get the node status page.
+///
+/// Renders the same summary as [`get_node_status`] as a human-readable HTML page. This is an
+/// administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn get_node_status_page<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> Html<String> {
+    Html(status_page::render(&state.status_report().await))
+}
+
+/// Get the shard assignment.
+///
+/// Reports the shards gained, lost, and retained by this node in the current epoch, relative to the
+/// previous epoch. This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn get_shard_assignment<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> ApiSuccess<ShardAssignmentDiff> {
+    ApiSuccess::ok(state.shard_assignment_diff())
+}
+
+/// Pin a blob.
+///
+/// Exempts the blob's data on this node from garbage collection. Responds with `201 Created` if
+/// the blob was newly pinned, and with `200 OK` if it was already pinned.
+#[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+pub async fn pin_blob<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Result<ApiSuccess<&'static str>, BlobPinningError> {
+    if state.pin_blob(&blob_id)? {
+        Ok(ApiSuccess::new(StatusCode::CREATED, "blob pinned"))
+    } else {
+        Ok(ApiSuccess::ok("blob already pinned"))
+    }
+}
+
+/// Unpin a blob.
+///
+/// Makes the blob's data on this node subject to garbage collection again.
+#[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+pub async fn unpin_blob<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Result<ApiSuccess<&'static str>, BlobPinningError> {
+    if state.unpin_blob(&blob_id)? {
+        Ok(ApiSuccess::ok("blob unpinned"))
+    } else {
+        Ok(ApiSuccess::ok("blob was not pinned"))
+    }
+}
+
+/// List denied blobs.
+///
+/// Lists the blobs on the deny list of this node, whose data the node refuses to store or serve.
+/// This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn list_denied_blobs<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> ApiSuccess<Vec<BlobId>> {
+    ApiSuccess::ok(state.list_denied_blobs())
+}
+
+/// Deny a blob.
+///
+/// Adds the blob to the deny list of this node, which is persisted to the configured blocklist
+/// file. The node then refuses to store or serve the blob's metadata and slivers. Responds with
+/// `201 Created` if the blob was newly denied, and with `200 OK` if it was already denied.
+#[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+pub async fn deny_blob<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Result<ApiSuccess<&'static str>, DenyListError> {
+    if state.deny_blob(&blob_id)? {
+        Ok(ApiSuccess::new(StatusCode::CREATED, "blob denied"))
+    } else {
+        Ok(ApiSuccess::ok("blob already denied"))
+    }
+}
+
+/// Allow a blob.
+///
+/// Removes the blob from the deny list of this node.
+#[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+pub async fn allow_blob<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Result<ApiSuccess<&'static str>, DenyListError> {
+    if state.allow_blob(&blob_id)? {
+        Ok(ApiSuccess::ok("blob removed from the deny list"))
+    } else {
+        Ok(ApiSuccess::ok("blob was not denied"))
+    }
+}
+
+/// The query parameters for listing the blobs stored in a shard.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListShardBlobsQuery {
+    /// List the blobs with IDs greater than this blob ID.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    after: Option<BlobId>,
+    /// The maximum number of blob IDs to return.
+    #[serde(default = "default_shard_blobs_limit")]
+    limit: usize,
+}
+
+fn default_shard_blobs_limit() -> usize {
+    DEFAULT_SHARD_BLOBS_LIMIT
+}
+
+/// List the blobs stored in a shard.
+///
+/// Lists the IDs of the blobs for which slivers are stored in the shard, in ascending order. This
+/// is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(
+    skip_all,
+    fields(walrus.shard_index = shard_index),
+    err(level = Level::DEBUG)
+)]
+pub async fn list_shard_blobs<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(shard_index): Path<u16>,
+    Query(query): Query<ListShardBlobsQuery>,
+) -> Result<ApiSuccess<ShardBlobs>, StorageInspectionError> {
+    Ok(ApiSuccess::ok(
+        state
+            .list_shard_blobs(ShardIndex(shard_index), query.after, query.limit)
+            .await?,
+    ))
+}
+
+/// Inspect the storage of a blob.
+///
+/// Reports the metadata, blob info, and the sizes of the slivers stored for the blob in each shard
+/// of the node. This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id), err(level = Level::DEBUG))]
+pub async fn inspect_blob_storage<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+    Path(BlobIdString(blob_id)): Path<BlobIdString>,
+) -> Result<ApiSuccess<BlobStorageReport>, StorageInspectionError> {
+    Ok(ApiSuccess::ok(state.inspect_blob_storage(&blob_id).await?))
+}
+
+/// Get the event cursor.
+///
+/// Reports the event cursor stored by the node. This is an administrative endpoint and is not part
+/// of the public API.
+#[tracing::instrument(skip_all, err(level = Level::DEBUG))]
+pub async fn get_event_cursor<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> Result<ApiSuccess<EventCursorReport>, StorageInspectionError> {
+    Ok(ApiSuccess::ok(state.event_cursor_report()?))
+}
+
+/// List the sizes of the column families.
+///
+/// Reports RocksDB's estimates of the sizes of the node's column families. This is an
+/// administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all, err(level = Level::DEBUG))]
+pub async fn list_column_family_sizes<S: SyncServiceState>(
+    State(state): State<Arc<S>>,
+) -> Result<ApiSuccess<Vec<ColumnFamilySize>>, StorageInspectionError> {
+    Ok(ApiSuccess::ok(state.column_family_sizes().await?))
+}
+
+/// Get the trace sampling rates.
+///
+/// Returns the rates at which new traces are sampled for export. This is an administrative endpoint
+/// and is not part of the public API.
+#[tracing::instrument(skip_all)]
+pub async fn get_trace_sampling() -> ApiSuccess<TraceSamplingConfig> {
+    ApiSuccess::ok(TraceSampler::global().config())
+}
+
+/// Set the trace sampling rates.
+///
+/// Replaces the rates at which new traces are sampled for export, until they are changed again or
+/// the node is restarted. This is an administrative endpoint and is not part of the public API.
+#[tracing::instrument(skip_all, err(level = Level::DEBUG))]
+pub async fn set_trace_sampling(
+    Json(config): Json<TraceSamplingConfig>,
+) -> Result<ApiSuccess<&'static str>, TraceSamplingError> {
+    TraceSampler::global().set_config(config)?;
+    Ok(ApiSuccess::ok("trace sampling rates updated"))
+}
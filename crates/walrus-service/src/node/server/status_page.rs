@@ -0,0 +1,327 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rendering of the node status report as a human-readable HTML page, and of the form to log in
+//! to it from a browser.
+
+use std::{
+    fmt::{Display, Write as _},
+    time::Duration,
+};
+
+use walrus_sdk::api::{ShardHealthInfo, ShardStatus};
+
+use super::routes::ADMIN_LOGIN_ENDPOINT;
+use crate::node::NodeStatusReport;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+    table{border-collapse:collapse;margin-bottom:1.5em}\
+    th,td{border:1px solid #ccc;padding:.25em .75em;text-align:left}\
+    .bad{color:#b00}";
+
+/// Renders the report as a self-contained HTML page.
+pub(super) fn render(report: &NodeStatusReport) -> String {
+    let health = &report.health;
+    let mut page = String::new();
+
+    page.push_str("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+    page.push_str("<title>Walrus storage node status</title>");
+    push_fmt(
+        &mut page,
+        format_args!("<style>{STYLE}</style></head><body>"),
+    );
+    page.push_str("<h1>Walrus storage node status</h1>");
+
+    page.push_str("<h2>Node</h2><table>");
+    row(&mut page, "Public key", &health.public_key);
+    row(&mut page, "Epoch", health.epoch);
+    row(&mut page, "Status", &health.node_status);
+    row(
+        &mut page,
+        "Maintenance mode",
+        if report.maintenance_mode { "on" } else { "off" },
+    );
+    let paused_workers: Vec<_> = report
+        .background_workers
+        .iter()
+        .filter(|status| status.paused)
+        .map(|status| format!("{:?}", status.worker))
+        .collect();
+    row(
+        &mut page,
+        "Paused workers",
+        if paused_workers.is_empty() {
+            "none".to_owned()
+        } else {
+            paused_workers.join(", ")
+        },
+    );
+    row(
+        &mut page,
+        "Uptime",
+        humantime::format_duration(Duration::from_secs(health.uptime.as_secs())),
+    );
+    row(
+        &mut page,
+        "Last attested epoch sync",
+        display_option(report.epoch_sync_done.last_attested_epoch),
+    );
+    page.push_str("</table>");
+
+    page.push_str("<h2>Shards</h2><table>");
+    let summary = &health.shard_summary;
+    row(&mut page, "Owned", summary.owned);
+    row(&mut page, "Ready", summary.owned_shard_status.ready);
+    row(
+        &mut page,
+        "In transfer",
+        summary.owned_shard_status.in_transfer,
+    );
+    row(
+        &mut page,
+        "In recovery",
+        summary.owned_shard_status.in_recovery,
+    );
+    row(&mut page, "Unknown", summary.owned_shard_status.unknown);
+    row(&mut page, "Read only", summary.read_only);
+    page.push_str("</table>");
+    if let Some(detail) = &health.shard_detail {
+        let syncing: Vec<_> = detail
+            .owned
+            .iter()
+            .filter(|info| info.status != ShardStatus::Ready)
+            .collect();
+        if !syncing.is_empty() {
+            page.push_str("<table><tr><th>Shard</th><th>Status</th></tr>");
+            for ShardHealthInfo { shard, status } in syncing {
+                push_fmt(
+                    &mut page,
+                    format_args!(
+                        "<tr><td>{shard}</td><td>{}</td></tr>",
+                        escape(&format!("{status:?}"))
+                    ),
+                );
+            }
+            page.push_str("</table>");
+        }
+    }
+
+    page.push_str("<h2>Storage</h2><table>");
+    row(&mut page, "Stored shards", report.storage.n_shards);
+    row(
+        &mut page,
+        "Stored slivers (est.)",
+        report.storage.stored_slivers,
+    );
+    row(
+        &mut page,
+        "Stored sliver data (est.)",
+        format_bytes(report.storage.stored_bytes),
+    );
+    page.push_str("</table>");
+
+    page.push_str("<h2>Events</h2><table>");
+    row(&mut page, "Persisted", health.event_progress.persisted);
+    row(&mut page, "Pending", health.event_progress.pending);
+    match report.event_lag {
+        Some(lag) => {
+            row(&mut page, "Lag (checkpoints)", lag.checkpoints);
+            row(&mut page, "Lag (seconds)", lag.seconds);
+        }
+        None => row(&mut page, "Lag", "unknown"),
+    }
+    page.push_str("</table>");
+
+    page.push_str("<h2>Peers</h2><table>");
+    page.push_str(
+        "<tr><th>Name</th><th>Address</th><th>Shards</th><th>Connected</th>\
+        <th>Invalid responses</th></tr>",
+    );
+    for peer in &report.peers {
+        let name = if peer.is_local {
+            format!("{} (this node)", peer.name)
+        } else {
+            peer.name.clone()
+        };
+        let class = if !peer.connected || peer.invalid_responses > 0 {
+            " class=\"bad\""
+        } else {
+            ""
+        };
+        push_fmt(
+            &mut page,
+            format_args!(
+                "<tr{class}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&name),
+                escape(&peer.network_address),
+                peer.n_shards,
+                if peer.connected { "yes" } else { "no" },
+                peer.invalid_responses,
+            ),
+        );
+    }
+    page.push_str("</table></body></html>");
+
+    page
+}
+
+/// Renders the form to log in with the admin token, noting if a previous attempt failed.
+pub(super) fn render_login(failed: bool) -> String {
+    let mut page = String::new();
+
+    page.push_str("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+    page.push_str("<title>Walrus storage node status</title>");
+    push_fmt(
+        &mut page,
+        format_args!("<style>{STYLE}</style></head><body>"),
+    );
+    page.push_str("<h1>Walrus storage node status</h1>");
+    if failed {
+        page.push_str("<p class=\"bad\">The admin token is invalid.</p>");
+    }
+    push_fmt(
+        &mut page,
+        format_args!(
+            "<form method=\"post\" action=\"{ADMIN_LOGIN_ENDPOINT}\"><label>Admin token \
+            <input type=\"password\" name=\"token\" autofocus></label> \
+            <button type=\"submit\">Log in</button></form>"
+        ),
+    );
+    page.push_str("</body></html>");
+
+    page
+}
+
+fn row(page: &mut String, label: &str, value: impl Display) {
+    push_fmt(
+        page,
+        format_args!(
+            "<tr><th>{label}</th><td>{}</td></tr>",
+            escape(&value.to_string())
+        ),
+    );
+}
+
+fn push_fmt(page: &mut String, args: std::fmt::Arguments<'_>) {
+    page.write_fmt(args)
+        .expect("writing to a string does not fail");
+}
+
+fn display_option<T: Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "none".to_owned(), |value| value.to_string())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
+/// Escapes the characters with special meaning in HTML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::keys::ProtocolKeyPair;
+    use walrus_sdk::api::ServiceHealthInfo;
+
+    use super::*;
+    use crate::node::{
+        committee::PeerHealth,
+        BackgroundWorker,
+        BackgroundWorkerStatus,
+        EpochSyncDoneStatus,
+        EventProcessingLag,
+        StorageUsage,
+    };
+
+    #[test]
+    fn formats_bytes_with_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.00 GiB");
+    }
+
+    #[test]
+    fn renders_report_and_escapes_peer_names() {
+        let public_key = ProtocolKeyPair::generate().public().clone();
+        let report = NodeStatusReport {
+            health: ServiceHealthInfo {
+                uptime: Duration::from_secs(90),
+                epoch: 7,
+                public_key: public_key.clone(),
+                network_public_key: None,
+                node_status: "Active".to_owned(),
+                event_progress: Default::default(),
+                shard_summary: Default::default(),
+                shard_detail: None,
+            },
+            maintenance_mode: true,
+            epoch_sync_done: EpochSyncDoneStatus {
+                last_attested_epoch: Some(6),
+                pending_epoch: Some(7),
+            },
+            background_workers: vec![
+                BackgroundWorkerStatus {
+                    worker: BackgroundWorker::EventProcessing,
+                    paused: false,
+                },
+                BackgroundWorkerStatus {
+                    worker: BackgroundWorker::ShardSync,
+                    paused: true,
+                },
+            ],
+            storage: StorageUsage {
+                n_shards: 2,
+                stored_slivers: 10,
+                stored_bytes: 2048,
+            },
+            event_lag: Some(EventProcessingLag {
+                checkpoints: 3,
+                seconds: 1,
+            }),
+            peers: vec![PeerHealth {
+                name: "<script>".to_owned(),
+                public_key,
+                network_address: "localhost:9185".to_owned(),
+                n_shards: 2,
+                is_local: false,
+                connected: false,
+                invalid_responses: 0,
+            }],
+        };
+
+        let page = render(&report);
+
+        assert!(page.contains("<tr><th>Epoch</th><td>7</td></tr>"));
+        assert!(page.contains("<tr><th>Maintenance mode</th><td>on</td></tr>"));
+        assert!(page.contains("<tr><th>Last attested epoch sync</th><td>6</td></tr>"));
+        assert!(page.contains("<tr><th>Paused workers</th><td>ShardSync</td></tr>"));
+        assert!(page.contains("2.00 KiB"));
+        assert!(page.contains("<tr><th>Lag (checkpoints)</th><td>3</td></tr>"));
+        assert!(page.contains("&lt;script&gt;"));
+        assert!(!page.contains("<script>"));
+    }
+}
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{hash_map::Entry, HashMap},
-    sync::Arc,
+    cmp::Reverse,
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::{Arc, RwLock},
 };
 
 use futures::{stream::FuturesUnordered, StreamExt};
@@ -13,7 +14,8 @@ use tokio::{
     sync::{Mutex, Semaphore},
     time::Instant,
 };
-use walrus_core::{BlobId, Epoch, ShardIndex};
+use tracing::Instrument as _;
+use walrus_core::{metadata::VerifiedBlobMetadataWithId, BlobId, Epoch, PublicKey, ShardIndex};
 use walrus_sdk::error::ServiceError;
 use walrus_utils::backoff::{BackoffStrategy, ExponentialBackoff};
 
@@ -21,11 +23,16 @@ use super::{
     blob_retirement_notifier::ExecutionResultWithRetirementCheck,
     config::ShardSyncConfig,
     errors::SyncShardClientError,
+    metrics,
+    server::MAX_PUSHED_METADATA_PER_REQUEST,
     storage::{blob_info::BlobInfo, ShardStatus, ShardStorage},
     NodeStatus,
     StorageNodeInner,
 };
-use crate::node::{errors::ShardNotAssigned, storage::blob_info::BlobInfoApi};
+use crate::{
+    common::{active_committees::ActiveCommittees, utils::resize_semaphore},
+    node::{errors::ShardNotAssigned, storage::blob_info::BlobInfoApi},
+};
 
 /// The result of syncing a shard.
 enum SyncShardResult {
@@ -38,14 +45,22 @@ enum SyncShardResult {
     Failed,
 }
 
+/// A running shard sync task.
+#[derive(Debug)]
+struct ShardSyncTask {
+    /// The epoch at the beginning of which the shard is synced.
+    epoch: Epoch,
+    handle: tokio::task::JoinHandle<()>,
+}
+
 /// Manages tasks for syncing shards during epoch change.
 #[derive(Debug, Clone)]
 pub struct ShardSyncHandler {
     node: Arc<StorageNodeInner>,
-    shard_sync_in_progress: Arc<Mutex<HashMap<ShardIndex, tokio::task::JoinHandle<()>>>>,
+    shard_sync_in_progress: Arc<Mutex<HashMap<ShardIndex, ShardSyncTask>>>,
     task_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
     shard_sync_semaphore: Arc<Semaphore>,
-    config: ShardSyncConfig,
+    config: Arc<RwLock<Arc<ShardSyncConfig>>>,
 }
 
 impl ShardSyncHandler {
@@ -55,10 +70,39 @@ impl ShardSyncHandler {
             shard_sync_in_progress: Arc::new(Mutex::new(HashMap::new())),
             task_handle: Arc::new(Mutex::new(None)),
             shard_sync_semaphore: Arc::new(Semaphore::new(config.shard_sync_concurrency)),
-            config,
+            config: Arc::new(RwLock::new(Arc::new(config))),
         }
     }
 
+    /// Returns the current shard sync configuration.
+    fn config(&self) -> Arc<ShardSyncConfig> {
+        self.config
+            .read()
+            .expect("shard sync config lock should not be poisoned")
+            .clone()
+    }
+
+    /// Replaces the shard sync configuration.
+    ///
+    /// The new concurrency and bandwidth limits are applied immediately, all other settings take
+    /// effect for subsequent sync attempts.
+    pub fn update_config(&self, config: ShardSyncConfig) {
+        let mut current = self
+            .config
+            .write()
+            .expect("shard sync config lock should not be poisoned");
+        resize_semaphore(
+            &self.shard_sync_semaphore,
+            current.shard_sync_concurrency,
+            config.shard_sync_concurrency,
+        );
+        self.node
+            .background_transfer_limits
+            .shard_sync
+            .set_rate(config.max_sync_bytes_per_second);
+        *current = Arc::new(config);
+    }
+
     /// Starts sync shards. If `recover_metadata` is true, syncs certified blob metadata before
     /// syncing shards.
     pub async fn start_sync_shards(
@@ -162,7 +206,7 @@ impl ShardSyncHandler {
             }
 
             // Wait for a task to complete if we've reached max concurrent limit
-            while active_count >= self.config.max_concurrent_metadata_fetch {
+            while active_count >= self.config().max_concurrent_metadata_fetch {
                 // Process one completed future
                 if let Some(result) = futures.next().await {
                     result.map_err(|e| SyncShardClientError::Internal(e.into()))?;
@@ -216,6 +260,127 @@ impl ShardSyncHandler {
         Ok(())
     }
 
+    /// Pushes the metadata of all certified blobs to the nodes that joined the committee in the
+    /// current epoch and obtained any of the `lost_shards` from this node.
+    ///
+    /// Nodes joining the committee sync the metadata of all certified blobs before syncing their
+    /// shards. Pushing the metadata in batches saves them from fetching it blob by blob. The
+    /// metadata is pushed in the background; nodes to which a push fails fall back to fetching
+    /// the metadata themselves.
+    pub fn start_metadata_push(&self, lost_shards: &[ShardIndex], committees: &ActiveCommittees) {
+        if !self.config().push_metadata_to_new_owners {
+            return;
+        }
+        let Some(previous_committee) = committees.previous_committee() else {
+            return;
+        };
+        let current_committee = committees.current_committee();
+        let recipients: HashSet<_> = lost_shards
+            .iter()
+            .filter_map(|shard| current_committee.member_index_for_shard(*shard))
+            .map(|index| &current_committee.members()[index].public_key)
+            .filter(|public_key| !previous_committee.contains(public_key))
+            .cloned()
+            .collect();
+        if recipients.is_empty() {
+            return;
+        }
+
+        let sync_handler = self.clone();
+        tokio::spawn(
+            async move {
+                sync_handler
+                    .push_metadata_task(recipients.into_iter().collect())
+                    .await
+            }
+            .in_current_span(),
+        );
+    }
+
+    async fn push_metadata_task(&self, mut recipients: Vec<PublicKey>) {
+        tracing::info!(
+            n_recipients = recipients.len(),
+            "start pushing blob metadata to new shard owners"
+        );
+        let blob_infos = self
+            .node
+            .storage
+            .certified_blob_info_iter_before_epoch(self.node.current_epoch());
+
+        let mut batch = Vec::with_capacity(MAX_PUSHED_METADATA_PER_REQUEST);
+        for blob_info in blob_infos {
+            let metadata =
+                blob_info.and_then(|(blob_id, _)| self.node.storage.get_metadata(&blob_id));
+            match metadata {
+                Ok(Some(metadata)) => batch.push(metadata),
+                Ok(None) => continue,
+                Err(error) => {
+                    tracing::warn!(?error, "failed to read blob metadata; stopping the push");
+                    return;
+                }
+            }
+
+            if batch.len() == MAX_PUSHED_METADATA_PER_REQUEST {
+                self.push_metadata_batch(&mut recipients, std::mem::take(&mut batch))
+                    .await;
+                if recipients.is_empty() {
+                    return;
+                }
+            }
+        }
+        if !batch.is_empty() {
+            self.push_metadata_batch(&mut recipients, batch).await;
+        }
+
+        tracing::info!("finished pushing blob metadata to new shard owners");
+    }
+
+    /// Pushes the batch of metadata to all recipients, and removes the recipients to which the
+    /// push failed.
+    async fn push_metadata_batch(
+        &self,
+        recipients: &mut Vec<PublicKey>,
+        batch: Vec<VerifiedBlobMetadataWithId>,
+    ) {
+        let batch = Arc::new(batch);
+        let results = futures::future::join_all(recipients.iter().map(|public_key| {
+            self.node
+                .committee_service
+                .push_metadata(public_key, batch.clone())
+        }))
+        .await;
+
+        let mut results = results.into_iter();
+        recipients.retain(|public_key| {
+            match results.next().expect("there is one result per recipient") {
+                Ok(n_stored) => {
+                    let n_skipped = u64::try_from(batch.len())
+                        .expect("batch size fits into u64")
+                        .saturating_sub(n_stored);
+                    walrus_utils::with_label!(
+                        self.node.metrics.sync_blob_metadata_pushed,
+                        metrics::STATUS_SUCCESS
+                    )
+                    .inc_by(n_stored);
+                    walrus_utils::with_label!(
+                        self.node.metrics.sync_blob_metadata_pushed,
+                        metrics::STATUS_SKIPPED
+                    )
+                    .inc_by(n_skipped);
+                    true
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        walrus.node.public_key = %public_key,
+                        ?error,
+                        "failed to push blob metadata; the node fetches the metadata itself"
+                    );
+                    false
+                }
+            }
+        });
+    }
+
     /// Starts syncing a new shard. This method is used when a new shard is assigned to the node.
     async fn start_new_shard_sync(
         &self,
@@ -291,20 +456,119 @@ impl ShardSyncHandler {
                         .await
                 }));
         } else {
+            // Restart the syncing task for shards that were previously syncing (in ActiveSync or
+            // ActiveRecover status).
+            let mut shards_to_restart = Vec::new();
             for shard_storage in self.node.storage.existing_shard_storages().await {
-                // Restart the syncing task for shards that were previously syncing (in ActiveSync
-                // status).
                 let shard_status = shard_storage.status()?;
                 if shard_status == ShardStatus::ActiveSync
                     || shard_status == ShardStatus::ActiveRecover
                 {
-                    self.start_shard_sync_impl(shard_storage.clone()).await;
+                    shards_to_restart.push((shard_storage.sync_priority_key()?, shard_storage));
                 }
             }
+
+            self.start_shard_syncs_by_priority(shards_to_restart).await;
         }
         Ok(())
     }
 
+    /// Pauses the syncs of shards to the beginning of an epoch before `epoch`.
+    ///
+    /// This is called when the node starts the transition to `epoch`, such that syncs against the
+    /// previous committee do not continue, and fail, while the committee changes. The progress of
+    /// each sync is persisted after every batch of fetched slivers, which is where a paused sync
+    /// resumes. Returns the shards whose sync was paused; they are resumed against the new
+    /// committee with [`Self::resume_paused_syncs`].
+    pub async fn pause_syncs_before_epoch(&self, epoch: Epoch) -> Vec<ShardIndex> {
+        let (paused, tasks): (Vec<_>, Vec<_>) = {
+            let mut shard_sync_in_progress = self.shard_sync_in_progress.lock().await;
+            let paused = shard_sync_in_progress
+                .iter()
+                .filter(|(_, task)| task.epoch < epoch)
+                .map(|(shard_index, _)| *shard_index)
+                .collect::<Vec<_>>();
+            paused
+                .into_iter()
+                .filter_map(|shard_index| {
+                    let task = shard_sync_in_progress.remove(&shard_index)?;
+                    task.handle.abort();
+                    Some((shard_index, task))
+                })
+                .unzip()
+        };
+
+        // Wait for the aborted tasks to stop, such that they cannot modify the shards or the
+        // task map after the syncs are resumed.
+        for task in tasks {
+            let _ = task.handle.await;
+        }
+        if !paused.is_empty() {
+            tracing::info!(
+                walrus.epoch = epoch,
+                shards = ?paused,
+                "paused shard syncs from the previous epoch for the committee change"
+            );
+        }
+        paused
+    }
+
+    /// Resumes the syncs paused by [`Self::pause_syncs_before_epoch`] against the current
+    /// committee.
+    ///
+    /// Syncs are only resumed for shards that are still owned by the node and have not finished
+    /// syncing; shards that moved out are handled by the epoch change like any other lost shard.
+    pub async fn resume_paused_syncs(
+        &self,
+        paused: Vec<ShardIndex>,
+    ) -> Result<(), SyncShardClientError> {
+        if paused.is_empty() {
+            return Ok(());
+        }
+        let owned_shards = self.node.owned_shards();
+        let mut shards_to_resume = Vec::new();
+        for shard_index in paused {
+            if !owned_shards.contains(&shard_index) {
+                tracing::info!(
+                    walrus.shard_index = %shard_index,
+                    "shard is no longer owned in the new epoch; not resuming its sync"
+                );
+                continue;
+            }
+            if self
+                .shard_sync_in_progress
+                .lock()
+                .await
+                .contains_key(&shard_index)
+            {
+                // The sync was already restarted for the new epoch.
+                continue;
+            }
+            let Some(shard_storage) = self.node.storage.shard_storage(shard_index).await else {
+                continue;
+            };
+            let shard_status = shard_storage.status()?;
+            if shard_status == ShardStatus::ActiveSync || shard_status == ShardStatus::ActiveRecover
+            {
+                shards_to_resume.push((shard_storage.sync_priority_key()?, shard_storage));
+            }
+        }
+        self.start_shard_syncs_by_priority(shards_to_resume).await;
+        Ok(())
+    }
+
+    /// Starts the syncs of the shards, starting with the shards closest to completion, so that
+    /// they acquire the shard sync permits first and finish early.
+    async fn start_shard_syncs_by_priority(
+        &self,
+        mut shards: Vec<((u8, Reverse<u16>), Arc<ShardStorage>)>,
+    ) {
+        shards.sort_by_key(|(priority_key, _)| *priority_key);
+        for (_, shard_storage) in shards {
+            self.start_shard_sync_impl(shard_storage).await;
+        }
+    }
+
     async fn start_shard_sync_impl(&self, shard_storage: Arc<ShardStorage>) {
         // This epoch must be the same as the epoch in the committee we refreshed when processing
         // epoch start event, or when the node starts up.
@@ -332,9 +596,10 @@ impl ShardSyncHandler {
             let shard_index = shard_storage.id();
             let start_time = Instant::now();
 
+            let config = shard_sync_handler_clone.config();
             let mut backoff = ExponentialBackoff::new_with_seed(
-                shard_sync_handler_clone.config.shard_sync_retry_min_backoff,
-                shard_sync_handler_clone.config.shard_sync_retry_max_backoff,
+                config.shard_sync_retry_min_backoff,
+                config.shard_sync_retry_max_backoff,
                 None,
                 shard_index.0 as u64, // Seed the backoff with the shard index.
             );
@@ -371,7 +636,7 @@ impl ShardSyncHandler {
                         tokio::time::sleep(backoff_duration).await;
                         if start_time.elapsed()
                             > shard_sync_handler_clone
-                                .config
+                                .config()
                                 .shard_sync_retry_switch_to_recovery_interval
                             || force_recovery
                         {
@@ -394,15 +659,14 @@ impl ShardSyncHandler {
             if epoch_sync_done {
                 shard_sync_handler_clone
                     .node
-                    .contract_service
-                    .epoch_sync_done(
-                        current_epoch,
-                        shard_sync_handler_clone.node.node_capability(),
-                    )
+                    .attest_epoch_sync_done(current_epoch)
                     .await;
             }
         });
-        entry.insert(shard_sync_task);
+        entry.insert(ShardSyncTask {
+            epoch: current_epoch,
+            handle: shard_sync_task,
+        });
     }
 
     /// Syncs a shard using shard sync. If `directly_recover_shard` is true, the shard will be
@@ -426,7 +690,7 @@ impl ShardSyncHandler {
             .start_sync_shard_before_epoch(
                 current_epoch,
                 self.node.clone(),
-                &self.config,
+                &self.config(),
                 directly_recover_shard,
             )
             .await;
@@ -632,6 +896,58 @@ mod tests {
             .contains_key(&ShardIndex(2)));
     }
 
+    #[tokio::test(start_paused = false)]
+    async fn syncs_are_paused_and_resumed_across_epoch_change() {
+        let cluster = create_test_cluster(&[&[0, 1, 2]]).await;
+        let node = cluster.nodes[0].storage_node.inner.clone();
+        for i in [0, 2] {
+            node.storage
+                .shard_storage(ShardIndex(i))
+                .await
+                .expect("Failed to get shard storage")
+                .update_status_in_test(ShardStatus::ActiveSync)
+                .expect("Failed to update shard status");
+        }
+        let shard_sync_handler = ShardSyncHandler::new(node.clone(), ShardSyncConfig::default());
+        shard_sync_handler
+            .restart_syncs()
+            .await
+            .expect("Failed to restart syncs");
+        assert_eq!(shard_sync_handler.current_sync_task_count().await, 2);
+
+        // Syncs to the current epoch are not paused when the epoch does not change.
+        let current_epoch = node.current_epoch();
+        assert!(shard_sync_handler
+            .pause_syncs_before_epoch(current_epoch)
+            .await
+            .is_empty());
+
+        let mut paused = shard_sync_handler
+            .pause_syncs_before_epoch(current_epoch + 1)
+            .await;
+        paused.sort();
+        assert_eq!(paused, [ShardIndex(0), ShardIndex(2)]);
+        assert_eq!(shard_sync_handler.current_sync_task_count().await, 0);
+
+        // A shard that finished syncing in the meantime is not resumed.
+        node.storage
+            .shard_storage(ShardIndex(2))
+            .await
+            .expect("Failed to get shard storage")
+            .update_status_in_test(ShardStatus::Active)
+            .expect("Failed to update shard status");
+        shard_sync_handler
+            .resume_paused_syncs(paused)
+            .await
+            .expect("Failed to resume syncs");
+        assert_eq!(shard_sync_handler.current_sync_task_count().await, 1);
+        assert!(shard_sync_handler
+            .shard_sync_in_progress
+            .lock()
+            .await
+            .contains_key(&ShardIndex(0)));
+    }
+
     #[tokio::test(start_paused = false)]
     async fn test_start_new_shard_sync() {
         let cluster = create_test_cluster(&[&[0]]).await;
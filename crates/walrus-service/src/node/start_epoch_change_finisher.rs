@@ -103,10 +103,7 @@ impl StartEpochChangeFinisher {
             // We are in the current committee, but no shards were gained. Directly signal that
             // the epoch sync is done.
             tracing::info!("no shards gained, so signalling that epoch sync is done");
-            self.node
-                .contract_service
-                .epoch_sync_done(event.epoch, self.node.node_capability())
-                .await;
+            self.node.attest_epoch_sync_done(event.epoch).await;
         } else {
             // Since we just refreshed the committee after receiving the event, the committees'
             // epoch must be at least the event's epoch.
@@ -0,0 +1,64 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Summary of the state of a storage node for operators.
+
+use serde::Serialize;
+use walrus_core::Epoch;
+use walrus_sdk::api::ServiceHealthInfo;
+
+use super::{background_workers::BackgroundWorkerStatus, committee::PeerHealth};
+
+/// A summary of the state of the storage node, served on the administrative API.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatusReport {
+    /// The health information of the node, including the status of each shard.
+    pub health: ServiceHealthInfo,
+    /// Whether the node is in maintenance mode.
+    pub maintenance_mode: bool,
+    /// The status of the node's attestation that it has synced all shards for an epoch.
+    pub epoch_sync_done: EpochSyncDoneStatus,
+    /// Whether each of the background workers is paused.
+    pub background_workers: Vec<BackgroundWorkerStatus>,
+    /// The estimated amount of data stored by the node.
+    pub storage: StorageUsage,
+    /// The lag of event processing behind the chain, if it has been determined yet.
+    pub event_lag: Option<EventProcessingLag>,
+    /// The health of the members of the current committee.
+    pub peers: Vec<PeerHealth>,
+}
+
+/// The status of the attestation that the node has finished syncing its shards for an epoch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochSyncDoneStatus {
+    /// The most recent epoch for which the attestation was submitted successfully.
+    pub last_attested_epoch: Option<Epoch>,
+    /// The epoch for which the attestation is currently being submitted, if any.
+    pub pending_epoch: Option<Epoch>,
+}
+
+/// The estimated amount of data stored by the node.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage {
+    /// The number of shards stored by the node.
+    pub n_shards: usize,
+    /// The estimated number of slivers stored across all shards.
+    pub stored_slivers: u64,
+    /// The estimated number of bytes of sliver data stored across all shards.
+    pub stored_bytes: u64,
+}
+
+/// The lag of event processing behind the latest checkpoint on chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventProcessingLag {
+    /// The number of checkpoints between the latest checkpoint and the latest checkpoint that has
+    /// been fully processed.
+    pub checkpoints: u64,
+    /// The time (in seconds) between the latest checkpoint and the latest checkpoint that has been
+    /// fully processed.
+    pub seconds: u64,
+}
@@ -3,15 +3,16 @@
 
 use core::fmt::{self, Display};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     fmt::Debug,
     ops::Bound::{Excluded, Included},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Instant,
 };
 
-use blob_info::{BlobInfoIterator, PerObjectBlobInfo};
+use anyhow::Context as _;
+use blob_info::{BlobInfoIterator, PerObjectBlobInfo, PerObjectBlobInfoApi};
 use event_cursor_table::EventIdWithProgress;
 use itertools::Itertools;
 use metrics::{CommonDatabaseMetrics, Labels, OperationType};
@@ -32,48 +33,79 @@ use walrus_core::{
     BlobId,
     Epoch,
     ShardIndex,
+    SliverType,
 };
-use walrus_sui::types::BlobEvent;
+use walrus_sui::types::{BlobEvent, Committee};
 
 use self::{
     blob_info::{BlobInfo, BlobInfoApi, BlobInfoTable},
     constants::{
+        committee_history_cf_name,
+        dead_letter_events_cf_name,
+        event_cursor_cf_name,
         metadata_cf_name,
         node_status_cf_name,
         pending_recover_slivers_column_family_name,
+        pinned_blobs_cf_name,
+        primary_slivers_cold_column_family_name,
         primary_slivers_column_family_name,
+        schema_version_cf_name,
+        secondary_slivers_cold_column_family_name,
         secondary_slivers_column_family_name,
         shard_status_column_family_name,
         shard_sync_progress_column_family_name,
+        sliver_last_access_column_family_name,
     },
     event_cursor_table::EventCursorTable,
 };
-use super::errors::{ShardNotAssigned, SyncShardServiceError};
+use super::{
+    committee::CommitteeRecord,
+    errors::{ShardNotAssigned, SyncShardServiceError},
+};
 
 pub(crate) mod blob_info;
 pub(crate) mod constants;
 
 mod database_config;
-pub use database_config::DatabaseConfig;
+pub use database_config::{DatabaseConfig, OrphanedSliverCollectionConfig, SliverTieringConfig};
+
+mod encryption;
+use encryption::SliverCipher;
+pub use encryption::StorageEncryptionConfig;
 
 mod event_cursor_table;
 pub(super) use event_cursor_table::EventProgress;
 
 mod event_sequencer;
+mod inspection;
+pub use inspection::{
+    BlobStorageReport,
+    ColumnFamilySize,
+    EventCursorReport,
+    ShardBlobs,
+    ShardSliverReport,
+    DEFAULT_SHARD_BLOBS_LIMIT,
+};
 mod metrics;
+mod migrations;
+mod repair;
+pub use repair::{repair_storage, QuarantinedEntry, RepairReport};
 mod shard;
-
+mod shard_archive;
 pub(crate) use shard::{
+    cold_slivers_column_family_options,
     pending_recover_slivers_column_family_options,
     primary_slivers_column_family_options,
     secondary_slivers_column_family_options,
     shard_status_column_family_options,
     shard_sync_progress_column_family_options,
+    sliver_last_access_column_family_options,
     PrimarySliverData,
     SecondarySliverData,
     ShardStatus,
     ShardStorage,
 };
+pub use shard_archive::{export_shard, import_shard, ShardArchiveSummary};
 
 pub(crate) fn metadata_options(db_config: &DatabaseConfig) -> Options {
     db_config.metadata().to_options()
@@ -83,6 +115,256 @@ pub(crate) fn node_status_options(db_config: &DatabaseConfig) -> Options {
     db_config.node_status().to_options()
 }
 
+/// The directory, within the main database directory, holding the databases of shards that are
+/// stored in separate databases.
+const SHARD_DATABASES_DIRECTORY: &str = "shards";
+
+/// Returns the names and options of the column families of the shard.
+fn shard_column_families(id: ShardIndex, db_config: &DatabaseConfig) -> [(String, Options); 8] {
+    [
+        (
+            primary_slivers_column_family_name(id),
+            primary_slivers_column_family_options(db_config),
+        ),
+        (
+            secondary_slivers_column_family_name(id),
+            secondary_slivers_column_family_options(db_config),
+        ),
+        (
+            shard_status_column_family_name(id),
+            shard_status_column_family_options(db_config),
+        ),
+        (
+            shard_sync_progress_column_family_name(id),
+            shard_sync_progress_column_family_options(db_config),
+        ),
+        (
+            pending_recover_slivers_column_family_name(id),
+            pending_recover_slivers_column_family_options(db_config),
+        ),
+        (
+            primary_slivers_cold_column_family_name(id),
+            cold_slivers_column_family_options(db_config),
+        ),
+        (
+            secondary_slivers_cold_column_family_name(id),
+            cold_slivers_column_family_options(db_config),
+        ),
+        (
+            sliver_last_access_column_family_name(id),
+            sliver_last_access_column_family_options(db_config),
+        ),
+    ]
+}
+
+/// Returns the names and options of the column families of the main database that do not belong
+/// to individual shards.
+fn main_column_families(db_config: &DatabaseConfig) -> Vec<(&'static str, Options)> {
+    [
+        (node_status_cf_name(), node_status_options(db_config)),
+        (metadata_cf_name(), metadata_options(db_config)),
+        (pinned_blobs_cf_name(), node_status_options(db_config)),
+        (dead_letter_events_cf_name(), node_status_options(db_config)),
+        (committee_history_cf_name(), node_status_options(db_config)),
+        (
+            schema_version_cf_name(),
+            migrations::schema_version_options(db_config),
+        ),
+        EventCursorTable::options(db_config),
+    ]
+    .into_iter()
+    .chain(BlobInfoTable::options(db_config))
+    .collect()
+}
+
+/// Returns the options for opening the main database and the databases of individual shards.
+fn database_options(db_config: &DatabaseConfig) -> Options {
+    let mut db_opts = Options::from(&db_config.global);
+    db_opts.create_missing_column_families(true);
+    db_opts.create_if_missing(true);
+    db_opts
+}
+
+/// Returns the names and options of the column families of the separate database of the shard.
+fn shard_database_column_families(
+    id: ShardIndex,
+    db_config: &DatabaseConfig,
+) -> Vec<(String, Options)> {
+    shard_column_families(id, db_config)
+        .into_iter()
+        .chain([(
+            schema_version_cf_name().to_owned(),
+            migrations::schema_version_options(db_config),
+        )])
+        .collect()
+}
+
+/// Opens the separate database of the shard in the provided directory, creating it if absent, and
+/// migrates it to the current schema version.
+///
+/// If a `secondary_path` is provided, the database is instead opened as a read-only RocksDB
+/// secondary instance below it and must already have the current schema version.
+fn open_shard_database(
+    shard_databases_path: &Path,
+    secondary_path: Option<&Path>,
+    id: ShardIndex,
+    db_config: &DatabaseConfig,
+) -> Result<Arc<RocksDB>, anyhow::Error> {
+    let path = shard_databases_path.join(id.to_string());
+    let is_new_database = !path.exists();
+    let database = open_database(
+        &path,
+        secondary_path
+            .map(|secondary_path| secondary_path.join(format!("shard-{id}")))
+            .as_deref(),
+        database_options(db_config),
+        MetricConf::new(&format!("storage_shard_{id}")),
+        &shard_database_column_families(id, db_config),
+    )?;
+    if secondary_path.is_some() {
+        migrations::ensure_schema_version(&database, migrations::SHARD_DATABASE_MIGRATIONS)
+    } else {
+        migrations::run_migrations(
+            &database,
+            migrations::SHARD_DATABASE_MIGRATIONS,
+            &[id],
+            is_new_database,
+            db_config
+                .migration_backup_path
+                .as_ref()
+                .map(|backup_path| backup_path.join(format!("shard-{id}")))
+                .as_deref(),
+        )
+    }
+    .with_context(|| format!("unable to migrate the database of shard {id}"))?;
+    Ok(database)
+}
+
+/// Opens the database at `path`, or opens it as a read-only RocksDB secondary instance if a
+/// `secondary_path` is provided.
+fn open_database(
+    path: &Path,
+    secondary_path: Option<&Path>,
+    db_options: Options,
+    metrics_config: MetricConf,
+    column_families: &[(String, Options)],
+) -> Result<Arc<RocksDB>, TypedStoreError> {
+    let column_families = column_families
+        .iter()
+        .map(|(name, options)| (name.as_str(), options.clone()))
+        .collect::<Vec<_>>();
+    match secondary_path {
+        Some(secondary_path) => rocks::open_cf_opts_secondary(
+            path,
+            Some(secondary_path),
+            Some(db_options),
+            metrics_config,
+            &column_families,
+        ),
+        None => rocks::open_cf_opts(path, Some(db_options), metrics_config, &column_families),
+    }
+}
+
+/// Returns the indices of the shards with fully initialized separate databases in the provided
+/// directory.
+///
+/// Databases of shards that were removed or not fully created are deleted, unless `read_only` is
+/// set.
+fn existing_shard_database_ids(
+    shard_databases_path: &Path,
+    read_only: bool,
+) -> Result<HashSet<ShardIndex>, anyhow::Error> {
+    let entries = match std::fs::read_dir(shard_databases_path) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut ids = HashSet::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(id) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse().ok())
+            .map(ShardIndex)
+        else {
+            continue;
+        };
+        let options = Options::default();
+        if ShardStorage::existing_cf_shards_ids(&path, &options).contains(&id) {
+            ids.insert(id);
+        } else if !read_only {
+            tracing::info!(walrus.shard_index = %id, "deleting database of removed shard");
+            std::fs::remove_dir_all(&path)?;
+        }
+    }
+    Ok(ids)
+}
+
+/// Returns true if the error reports data that failed checksum verification or is otherwise
+/// corrupted on disk.
+pub(crate) fn is_corruption_error(error: &TypedStoreError) -> bool {
+    matches!(error, TypedStoreError::RocksDBError(message) if message.starts_with("Corruption"))
+}
+
+/// Information recorded for a blob that was pinned by the node operator.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PinnedBlobInfo {
+    /// The Unix timestamp, in seconds, at which the blob was pinned.
+    pub pinned_at: u64,
+}
+
+/// A pinned blob, together with the amount of data stored for it on this node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedBlob {
+    /// The ID of the pinned blob.
+    pub blob_id: BlobId,
+    /// The Unix timestamp, in seconds, at which the blob was pinned.
+    pub pinned_at: u64,
+    /// The number of bytes stored for the metadata of the blob.
+    pub metadata_bytes: u64,
+    /// The number of slivers stored for the blob across all shards of this node.
+    pub stored_slivers: u64,
+    /// The number of bytes stored for the slivers of the blob across all shards of this node.
+    pub sliver_bytes: u64,
+}
+
+impl PinnedBlob {
+    /// Returns the total number of bytes stored for the blob on this node.
+    pub fn total_bytes(&self) -> u64 {
+        self.metadata_bytes + self.sliver_bytes
+    }
+}
+
+/// The data of abandoned uploads deleted in a run of the orphaned-sliver collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CollectedOrphanedData {
+    /// The number of blobs whose data was deleted.
+    pub deleted_blobs: usize,
+    /// The number of bytes of metadata and slivers that were deleted.
+    pub reclaimed_bytes: u64,
+}
+
+/// A blob event that repeatedly failed to be processed and was moved to the dead-letter queue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetterEvent {
+    /// The index of the event in the node's event stream.
+    pub event_index: u64,
+    /// The ID of the event on chain.
+    pub event_id: EventID,
+    /// The event that failed to be processed.
+    pub event: BlobEvent,
+    /// The number of times processing the event was attempted.
+    pub attempts: u32,
+    /// The error of the last attempt, including its context.
+    pub error: String,
+    /// The Unix timestamp, in seconds, of the last attempt.
+    pub last_attempt_at: u64,
+}
+
 /// Error returned if a requested operation would block.
 #[derive(Debug, Clone, Copy)]
 pub struct WouldBlockError;
@@ -145,10 +427,18 @@ pub struct Storage {
     database: Arc<RocksDB>,
     node_status: DBMap<(), NodeStatus>,
     metadata: DBMap<BlobId, BlobMetadata>,
+    pinned_blobs: DBMap<BlobId, PinnedBlobInfo>,
+    dead_letter_events: DBMap<u64, DeadLetterEvent>,
+    /// The committees of recent epochs, which are used to find earlier owners of shards.
+    committee_history: DBMap<Epoch, CommitteeRecord>,
     blob_info: BlobInfoTable,
     event_cursor: EventCursorTable,
     shards: Arc<RwLock<HashMap<ShardIndex, Arc<ShardStorage>>>>,
+    /// The directory holding the databases of shards that are stored in separate databases.
+    shard_databases_path: PathBuf,
     config: DatabaseConfig,
+    /// The cipher used to encrypt slivers at rest, if storage encryption is enabled.
+    cipher: Option<Arc<SliverCipher>>,
     metrics: Arc<CommonDatabaseMetrics>,
     metrics_registry: Registry,
 }
@@ -166,6 +456,11 @@ impl StorageShardLock {
     pub fn existing_shards(&self) -> &[ShardIndex] {
         &self.existing_shards
     }
+
+    /// Returns the storage of the shard, if it is present.
+    pub fn shard_storage(&self, shard: ShardIndex) -> Option<&Arc<ShardStorage>> {
+        self.shards_guard.get(&shard)
+    }
 }
 
 impl Storage {
@@ -176,10 +471,46 @@ impl Storage {
         metrics_config: MetricConf,
         registry: Registry,
     ) -> Result<Self, anyhow::Error> {
-        let mut db_opts = Options::from(&db_config.global);
-        db_opts.create_missing_column_families(true);
-        db_opts.create_if_missing(true);
+        Self::open_inner(path, None, db_config, metrics_config, registry)
+    }
 
+    /// Opens the existing storage database located at the specified path without modifying it,
+    /// e.g., to inspect or export the data of a stopped node.
+    ///
+    /// The databases are opened as RocksDB secondary instances, which keep their own files below
+    /// `secondary_path`. Unlike [`Self::open`], no schema migrations are run and no shards are
+    /// migrated to separate databases; the database must already have the current schema version.
+    /// Any write to the returned storage fails.
+    pub fn open_read_only(
+        path: &Path,
+        secondary_path: &Path,
+        db_config: DatabaseConfig,
+        registry: Registry,
+    ) -> Result<Self, anyhow::Error> {
+        Self::open_inner(
+            path,
+            Some(secondary_path),
+            db_config,
+            MetricConf::default(),
+            registry,
+        )
+    }
+
+    fn open_inner(
+        path: &Path,
+        secondary_path: Option<&Path>,
+        db_config: DatabaseConfig,
+        metrics_config: MetricConf,
+        registry: Registry,
+    ) -> Result<Self, anyhow::Error> {
+        let read_only = secondary_path.is_some();
+        let cipher = db_config
+            .encryption
+            .as_ref()
+            .map(SliverCipher::load)
+            .transpose()?
+            .map(Arc::new);
+        let db_opts = database_options(&db_config);
         let existing_shards_ids = ShardStorage::existing_cf_shards_ids(path, &db_opts);
         tracing::info!(
             "open storage for existing shards IDs: {}",
@@ -188,56 +519,27 @@ impl Storage {
                 .map(ToString::to_string)
                 .join(", ")
         );
-        let mut shard_column_families = existing_shards_ids
-            .iter()
-            .copied()
-            .flat_map(|id| {
-                [
-                    (
-                        primary_slivers_column_family_name(id),
-                        primary_slivers_column_family_options(&db_config),
-                    ),
-                    (
-                        secondary_slivers_column_family_name(id),
-                        secondary_slivers_column_family_options(&db_config),
-                    ),
-                    (
-                        shard_status_column_family_name(id),
-                        shard_status_column_family_options(&db_config),
-                    ),
-                    (
-                        shard_sync_progress_column_family_name(id),
-                        shard_sync_progress_column_family_options(&db_config),
-                    ),
-                    (
-                        pending_recover_slivers_column_family_name(id),
-                        pending_recover_slivers_column_family_options(&db_config),
-                    ),
-                ]
-            })
-            .collect::<Vec<_>>();
-
         let node_status_cf_name = node_status_cf_name();
-        let node_status_options = node_status_options(&db_config);
-        let metadata_options = metadata_options(&db_config);
         let metadata_cf_name = metadata_cf_name();
-        let blob_info_column_families = BlobInfoTable::options(&db_config);
-        let (event_cursor_cf_name, event_cursor_options) = EventCursorTable::options(&db_config);
-
-        let expected_column_families: Vec<_> = shard_column_families
-            .iter_mut()
-            .map(|(name, opts)| (name.as_str(), std::mem::take(opts)))
-            .chain([
-                (node_status_cf_name, node_status_options),
-                (metadata_cf_name, metadata_options),
-                (event_cursor_cf_name, event_cursor_options),
-            ])
-            .chain(blob_info_column_families)
+        let pinned_blobs_cf_name = pinned_blobs_cf_name();
+        let dead_letter_events_cf_name = dead_letter_events_cf_name();
+
+        let expected_column_families: Vec<_> = existing_shards_ids
+            .iter()
+            .flat_map(|&id| shard_column_families(id, &db_config))
+            .chain(
+                main_column_families(&db_config)
+                    .into_iter()
+                    .map(|(name, options)| (name.to_owned(), options)),
+            )
             .collect::<Vec<_>>();
 
-        let database = rocks::open_cf_opts(
+        let database = open_database(
             path,
-            Some(db_opts),
+            secondary_path
+                .map(|secondary_path| secondary_path.join("main"))
+                .as_deref(),
+            db_opts,
             metrics_config,
             &expected_column_families,
         )?;
@@ -248,8 +550,25 @@ impl Storage {
             &ReadWriteOptions::default(),
             false,
         )?;
-        if node_status.get(&())?.is_none() {
-            node_status.insert(&(), &NodeStatus::Standby)?;
+        let is_new_database = node_status.get(&())?.is_none();
+        if read_only {
+            anyhow::ensure!(
+                !is_new_database,
+                "there is no storage node database at {}",
+                path.display()
+            );
+            migrations::ensure_schema_version(&database, migrations::MIGRATIONS)?;
+        } else {
+            if is_new_database {
+                node_status.insert(&(), &NodeStatus::Standby)?;
+            }
+            migrations::run_migrations(
+                &database,
+                migrations::MIGRATIONS,
+                &existing_shards_ids.iter().copied().collect::<Vec<_>>(),
+                is_new_database,
+                db_config.migration_backup_path.as_deref(),
+            )?;
         }
 
         let metadata = DBMap::reopen(
@@ -259,26 +578,97 @@ impl Storage {
             false,
         )?;
 
+        let pinned_blobs = DBMap::reopen(
+            &database,
+            Some(pinned_blobs_cf_name),
+            &ReadWriteOptions::default(),
+            false,
+        )?;
+
+        let dead_letter_events = DBMap::reopen(
+            &database,
+            Some(dead_letter_events_cf_name),
+            &ReadWriteOptions::default(),
+            false,
+        )?;
+
+        let committee_history = DBMap::reopen(
+            &database,
+            Some(committee_history_cf_name()),
+            &ReadWriteOptions::default(),
+            false,
+        )?;
+
         let event_cursor = EventCursorTable::reopen(&database)?;
         let blob_info = BlobInfoTable::reopen(&database)?;
-        let shards = Arc::new(RwLock::new(
-            existing_shards_ids
-                .into_iter()
-                .map(|id| {
-                    ShardStorage::create_or_reopen(id, &database, &db_config, None, &registry)
-                        .map(|shard| (id, Arc::new(shard)))
-                })
-                .collect::<Result<_, _>>()?,
-        ));
+        let shard_databases_path = path.join(SHARD_DATABASES_DIRECTORY);
+        let separate_shard_ids = existing_shard_database_ids(&shard_databases_path, read_only)?;
+        let open_separate_shard_database = |id: ShardIndex| {
+            open_shard_database(&shard_databases_path, secondary_path, id, &db_config)
+        };
+        let mut shards = HashMap::new();
+        for id in existing_shards_ids {
+            let shard = ShardStorage::create_or_reopen(
+                id,
+                &database,
+                &db_config,
+                cipher.clone(),
+                None,
+                &registry,
+            )?;
+            // Also complete migrations that were interrupted before the option was disabled. In
+            // read-only mode, the shard in the main database is authoritative until the migration
+            // completes.
+            if read_only || (!db_config.per_shard_databases && !separate_shard_ids.contains(&id)) {
+                shards.insert(id, Arc::new(shard));
+                continue;
+            }
+
+            tracing::info!(walrus.shard_index = %id, "migrating shard to a separate database");
+            let shard_database = open_separate_shard_database(id)?;
+            let migrated_shard = ShardStorage::create_or_reopen(
+                id,
+                &shard_database,
+                &db_config,
+                cipher.clone(),
+                None,
+                &registry,
+            )?;
+            shard.copy_into(&migrated_shard)?;
+            shard.delete_shard_storage()?;
+            tracing::info!(walrus.shard_index = %id, "migrated shard to a separate database");
+            shards.insert(id, Arc::new(migrated_shard));
+        }
+        for id in separate_shard_ids {
+            if shards.contains_key(&id) {
+                continue;
+            }
+            let shard_database = open_separate_shard_database(id)?;
+            let shard = ShardStorage::create_or_reopen(
+                id,
+                &shard_database,
+                &db_config,
+                cipher.clone(),
+                None,
+                &registry,
+            )?;
+            shards.insert(id, Arc::new(shard));
+        }
+        let shards = Arc::new(RwLock::new(shards));
 
         Ok(Self {
             database,
             node_status,
             metadata,
+            pinned_blobs,
+            dead_letter_events,
+            committee_history,
             blob_info,
             event_cursor,
             shards,
+            shard_databases_path,
             config: db_config,
+            cipher,
             metrics: Arc::new(CommonDatabaseMetrics::new_with_id(
                 &registry,
                 "storage".to_owned(),
@@ -334,17 +724,34 @@ impl Storage {
         for &shard_index in new_shards {
             match locked_map.shards_guard.entry(shard_index) {
                 Entry::Vacant(entry) => {
-                    let shard_storage = ShardStorage::create_or_reopen(
-                        shard_index,
-                        &self.database,
-                        &self.config,
-                        Some(ShardStatus::None),
-                        &self.metrics_registry,
-                    )
-                    .inspect_err(|error| {
-                        self.metrics
-                            .observe_operation_duration(labels.with_error(error), start.elapsed());
-                    })?;
+                    let database = if self.config.per_shard_databases {
+                        open_shard_database(
+                            &self.shard_databases_path,
+                            None,
+                            shard_index,
+                            &self.config,
+                        )
+                        .map_err(|error| TypedStoreError::RocksDBError(format!("{error:#}")))
+                    } else {
+                        Ok(self.database.clone())
+                    };
+                    let shard_storage = database
+                        .and_then(|database| {
+                            ShardStorage::create_or_reopen(
+                                shard_index,
+                                &database,
+                                &self.config,
+                                self.cipher.clone(),
+                                Some(ShardStatus::None),
+                                &self.metrics_registry,
+                            )
+                        })
+                        .inspect_err(|error| {
+                            self.metrics.observe_operation_duration(
+                                labels.with_error(error),
+                                start.elapsed(),
+                            );
+                        })?;
 
                     tracing::info!(
                         walrus.shard_index = %shard_index,
@@ -396,6 +803,124 @@ impl Storage {
             .collect::<Vec<_>>()
     }
 
+    /// Returns the policy for moving slivers between the hot and cold storage tiers.
+    pub(crate) fn sliver_tiering_config(&self) -> &SliverTieringConfig {
+        &self.config.sliver_tiering
+    }
+
+    /// Moves slivers of all shards that have not been accessed within the configured period to the
+    /// cold tier.
+    ///
+    /// Returns the number of slivers that were moved.
+    pub(crate) async fn demote_cold_slivers(&self) -> Result<usize, TypedStoreError> {
+        let config = &self.config.sliver_tiering;
+        let accessed_before = shard::unix_time_secs().saturating_sub(config.cold_after.as_secs());
+
+        let mut total_demoted = 0;
+        for shard in self.existing_shard_storages().await {
+            total_demoted +=
+                shard.demote_cold_slivers(accessed_before, config.max_demotions_per_shard)?;
+            tokio::task::yield_now().await;
+        }
+        Ok(total_demoted)
+    }
+
+    /// Returns the policy for deleting the data of blobs that were registered but never certified,
+    /// as configured when the storage was opened.
+    pub(crate) fn orphaned_sliver_collection_config(&self) -> &OrphanedSliverCollectionConfig {
+        &self.config.orphaned_sliver_collection
+    }
+
+    /// Deletes the metadata and slivers of abandoned uploads according to `config`.
+    ///
+    /// A blob's data is deleted if none of its registrations was certified, none of them is still
+    /// registered (i.e., all expired or were deleted), the latest of them is at least the
+    /// configured number of epochs older than `current_epoch`, and the blob is not pinned. The
+    /// data of at most the configured number of blobs is deleted per call.
+    ///
+    /// The `event_processing_lock` is held while checking and deleting the data of each blob, such
+    /// that a blob cannot be certified concurrently.
+    ///
+    /// Returns the number of blobs whose data was deleted and the number of bytes reclaimed.
+    pub(crate) async fn collect_orphaned_slivers(
+        &self,
+        config: &OrphanedSliverCollectionConfig,
+        current_epoch: Epoch,
+        event_processing_lock: &tokio::sync::Mutex<()>,
+    ) -> Result<CollectedOrphanedData, TypedStoreError> {
+        // The epoch of the latest uncertified registration of each blob.
+        let mut latest_registrations = BTreeMap::new();
+        for info in self.blob_info.per_object_blob_info_iter() {
+            let info = info?;
+            if info.certified_epoch().is_none() {
+                let latest = latest_registrations
+                    .entry(info.blob_id())
+                    .or_insert(info.registered_epoch());
+                *latest = info.registered_epoch().max(*latest);
+            }
+        }
+
+        let shards = self.existing_shard_storages().await;
+        let mut collected = CollectedOrphanedData::default();
+        for (blob_id, registered_epoch) in latest_registrations {
+            if collected.deleted_blobs >= config.max_deletions_per_run {
+                break;
+            }
+            if registered_epoch.saturating_add(config.grace_epochs) > current_epoch {
+                continue;
+            }
+            let _guard = event_processing_lock.lock().await;
+            // Other registrations of the blob may have been certified or may still be valid, in
+            // which case they can still be certified.
+            if self.get_blob_info(&blob_id)?.is_some_and(|blob_info| {
+                blob_info.initial_certified_epoch().is_some()
+                    || blob_info.is_registered(current_epoch)
+            }) || self.is_blob_pinned(&blob_id)?
+            {
+                continue;
+            }
+
+            let stored_bytes = self.stored_blob_bytes(&blob_id, &shards)?;
+            if stored_bytes == 0 {
+                continue;
+            }
+            self.delete_blob_data(&blob_id).await?;
+            tracing::debug!(
+                %blob_id,
+                registered_epoch,
+                stored_bytes,
+                "deleted the data of an abandoned upload"
+            );
+            collected.deleted_blobs += 1;
+            collected.reclaimed_bytes += stored_bytes;
+            tokio::task::yield_now().await;
+        }
+        Ok(collected)
+    }
+
+    /// Returns the number of bytes of metadata and slivers stored for the blob in the shards.
+    ///
+    /// The sizes of the stored values are used, such that slivers are not decrypted.
+    fn stored_blob_bytes(
+        &self,
+        blob_id: &BlobId,
+        shards: &[Arc<ShardStorage>],
+    ) -> Result<u64, TypedStoreError> {
+        let mut bytes = self
+            .metadata
+            .get_raw_bytes(blob_id)?
+            .map(|metadata| metadata.len() as u64)
+            .unwrap_or_default();
+        for shard in shards {
+            for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+                bytes += shard
+                    .stored_sliver_bytes(blob_id, sliver_type)?
+                    .unwrap_or_default();
+            }
+        }
+        Ok(bytes)
+    }
+
     /// Returns a handle over the storage for a single shard.
     pub async fn shard_storage(&self, shard: ShardIndex) -> Option<Arc<ShardStorage>> {
         self.shards.read().await.get(&shard).cloned()
@@ -584,14 +1109,27 @@ impl Storage {
             .map(|inner| VerifiedBlobMetadataWithId::new_verified_unchecked(*blob_id, inner)))
     }
 
+    /// Deletes the metadata for the provided [`BlobId`], such that it is considered missing and is
+    /// synced again.
+    ///
+    /// This is used to discard metadata that was found to be corrupted.
+    #[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id))]
+    pub fn discard_metadata(&self, blob_id: &BlobId) -> Result<(), TypedStoreError> {
+        let mut batch = self.metadata.batch();
+        self.delete_metadata(&mut batch, blob_id, true)?;
+        batch.write()
+    }
+
     /// Deletes the metadata and slivers for the provided [`BlobId`] from the storage.
     #[tracing::instrument(skip_all)]
     pub async fn delete_blob_data(&self, blob_id: &BlobId) -> Result<(), TypedStoreError> {
+        // Shards may be stored in separate databases, so the slivers are deleted separately from
+        // the metadata. The metadata is deleted last, such that the deletion is retried if it is
+        // interrupted.
+        self.delete_slivers(blob_id).await?;
         let mut batch = self.metadata.batch();
         self.delete_metadata(&mut batch, blob_id, true)?;
-        self.delete_slivers(&mut batch, blob_id).await?;
-        batch.write()?;
-        Ok(())
+        batch.write()
     }
 
     /// Deletes the metadata for the provided [`BlobId`].
@@ -609,17 +1147,275 @@ impl Storage {
     }
 
     /// Deletes the slivers on all shards for the provided [`BlobId`].
-    async fn delete_slivers(
-        &self,
-        batch: &mut DBBatch,
-        blob_id: &BlobId,
-    ) -> Result<(), TypedStoreError> {
+    async fn delete_slivers(&self, blob_id: &BlobId) -> Result<(), TypedStoreError> {
         for shard in self.existing_shard_storages().await {
-            shard.delete_sliver_pair(batch, blob_id)?;
+            shard.delete_sliver_pair_now(blob_id)?;
         }
         Ok(())
     }
 
+    /// Pins the blob, exempting its data on this node from being garbage collected once the blob
+    /// expires or is deleted.
+    ///
+    /// Returns true if the blob was newly pinned, false if it was already pinned.
+    #[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id))]
+    pub fn pin_blob(&self, blob_id: &BlobId) -> Result<bool, TypedStoreError> {
+        if self.pinned_blobs.contains_key(blob_id)? {
+            return Ok(false);
+        }
+        let info = PinnedBlobInfo {
+            pinned_at: shard::unix_time_secs(),
+        };
+        self.pinned_blobs.insert(blob_id, &info)?;
+        tracing::info!("pinned blob");
+        Ok(true)
+    }
+
+    /// Unpins the blob, such that its data is again subject to garbage collection.
+    ///
+    /// Returns true if the blob was pinned, false otherwise.
+    #[tracing::instrument(skip_all, fields(walrus.blob_id = %blob_id))]
+    pub fn unpin_blob(&self, blob_id: &BlobId) -> Result<bool, TypedStoreError> {
+        if !self.pinned_blobs.contains_key(blob_id)? {
+            return Ok(false);
+        }
+        self.pinned_blobs.remove(blob_id)?;
+        tracing::info!("unpinned blob");
+        Ok(true)
+    }
+
+    /// Returns true if the blob is pinned on this node.
+    pub fn is_blob_pinned(&self, blob_id: &BlobId) -> Result<bool, TypedStoreError> {
+        self.pinned_blobs.contains_key(blob_id)
+    }
+
+    /// Records a blob event that repeatedly failed to be processed in the dead-letter queue,
+    /// replacing any previous entry for the same event.
+    #[tracing::instrument(skip_all, fields(walrus.event.index = event_index))]
+    pub fn put_dead_letter_event(
+        &self,
+        event_index: u64,
+        event_id: EventID,
+        event: BlobEvent,
+        attempts: u32,
+        error: String,
+    ) -> Result<(), TypedStoreError> {
+        let entry = DeadLetterEvent {
+            event_index,
+            event_id,
+            event,
+            attempts,
+            error,
+            last_attempt_at: shard::unix_time_secs(),
+        };
+        self.dead_letter_events.insert(&event_index, &entry)
+    }
+
+    /// Returns the event with the given index from the dead-letter queue, if present.
+    pub fn get_dead_letter_event(
+        &self,
+        event_index: u64,
+    ) -> Result<Option<DeadLetterEvent>, TypedStoreError> {
+        self.dead_letter_events.get(&event_index)
+    }
+
+    /// Removes the event with the given index from the dead-letter queue.
+    pub fn remove_dead_letter_event(&self, event_index: u64) -> Result<(), TypedStoreError> {
+        self.dead_letter_events.remove(&event_index)
+    }
+
+    /// Returns all events in the dead-letter queue, ordered by their event index.
+    pub fn list_dead_letter_events(&self) -> Result<Vec<DeadLetterEvent>, TypedStoreError> {
+        self.dead_letter_events
+            .safe_iter()
+            .map(|result| result.map(|(_, event)| event))
+            .collect()
+    }
+
+    /// Returns the committees recorded in the committee history, ordered by epoch.
+    pub(crate) fn committee_history(&self) -> anyhow::Result<Vec<Committee>> {
+        self.committee_history
+            .safe_iter()
+            .map(|result| Ok(result?.1.try_into()?))
+            .collect()
+    }
+
+    /// Replaces the recorded committee history with the provided committees.
+    pub(crate) fn replace_committee_history(
+        &self,
+        committees: &[Arc<Committee>],
+    ) -> Result<(), TypedStoreError> {
+        let epochs: HashSet<_> = committees.iter().map(|committee| committee.epoch).collect();
+        let removed = self
+            .committee_history
+            .safe_iter()
+            .filter_map(|result| match result {
+                Ok((epoch, _)) if epochs.contains(&epoch) => None,
+                result => Some(result.map(|(epoch, _)| epoch)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut batch = self.committee_history.batch();
+        batch.delete_batch(&self.committee_history, removed)?;
+        batch.insert_batch(
+            &self.committee_history,
+            committees
+                .iter()
+                .map(|committee| (committee.epoch, CommitteeRecord::from(committee.as_ref()))),
+        )?;
+        batch.write()
+    }
+
+    /// Returns all pinned blobs, along with the amount of data stored for each on this node.
+    ///
+    /// The sizes of the stored values are used, such that slivers are neither deserialized nor
+    /// decrypted, and are not promoted to the hot tier.
+    pub async fn list_pinned_blobs(&self) -> Result<Vec<PinnedBlob>, TypedStoreError> {
+        let pinned = self
+            .pinned_blobs
+            .safe_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        let shards = self.existing_shard_storages().await;
+
+        let mut result = Vec::with_capacity(pinned.len());
+        for (blob_id, info) in pinned {
+            let metadata_bytes = self
+                .metadata
+                .get_raw_bytes(&blob_id)?
+                .map(|metadata| metadata.len() as u64)
+                .unwrap_or_default();
+
+            let mut stored_slivers = 0;
+            let mut sliver_bytes = 0;
+            for shard in shards.iter() {
+                for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+                    if let Some(bytes) = shard.stored_sliver_bytes(&blob_id, sliver_type)? {
+                        stored_slivers += 1;
+                        sliver_bytes += bytes;
+                    }
+                }
+            }
+
+            result.push(PinnedBlob {
+                blob_id,
+                pinned_at: info.pinned_at,
+                metadata_bytes,
+                stored_slivers,
+                sliver_bytes,
+            });
+            tokio::task::yield_now().await;
+        }
+        Ok(result)
+    }
+
+    /// Returns up to `limit` IDs of blobs with slivers stored in the shard, in ascending order,
+    /// starting after the blob ID `after`, if provided.
+    ///
+    /// Returns `None` if the shard is not stored on this node.
+    pub async fn list_shard_blobs(
+        &self,
+        shard_index: ShardIndex,
+        after: Option<BlobId>,
+        limit: usize,
+    ) -> Result<Option<ShardBlobs>, TypedStoreError> {
+        let Some(shard) = self.shard_storage(shard_index).await else {
+            return Ok(None);
+        };
+        let blob_ids = shard.blob_ids(after, limit)?;
+        let next_after = if blob_ids.len() == limit {
+            blob_ids.last().copied()
+        } else {
+            None
+        };
+        Ok(Some(ShardBlobs {
+            shard_index,
+            blob_ids,
+            next_after,
+        }))
+    }
+
+    /// Returns the metadata, blob info, and slivers stored for the blob on this node.
+    ///
+    /// Reading the slivers to determine their size neither promotes them to the hot tier nor
+    /// records them as accessed.
+    pub async fn inspect_blob(
+        &self,
+        blob_id: &BlobId,
+    ) -> Result<BlobStorageReport, TypedStoreError> {
+        let metadata_bytes = self.metadata.get(blob_id)?.map(|metadata| {
+            bcs::serialized_size(&metadata).expect("metadata can be serialized") as u64
+        });
+        let blob_info = self
+            .get_blob_info(blob_id)?
+            .map(|blob_info| format!("{blob_info:?}"));
+
+        let mut shards = self.existing_shard_storages().await;
+        shards.sort_by_key(|shard| shard.id());
+        let mut shard_reports = vec![];
+        for shard in shards {
+            let sliver_bytes = |sliver_type: SliverType| -> Result<Option<u64>, TypedStoreError> {
+                Ok(shard
+                    .fetch_slivers(sliver_type, &[*blob_id])?
+                    .first()
+                    .map(|(_, sliver)| sliver.len() as u64))
+            };
+            let primary_sliver_bytes = sliver_bytes(SliverType::Primary)?;
+            let secondary_sliver_bytes = sliver_bytes(SliverType::Secondary)?;
+            if primary_sliver_bytes.is_some() || secondary_sliver_bytes.is_some() {
+                shard_reports.push(ShardSliverReport {
+                    shard_index: shard.id(),
+                    primary_sliver_bytes,
+                    secondary_sliver_bytes,
+                });
+            }
+        }
+
+        Ok(BlobStorageReport {
+            blob_id: *blob_id,
+            metadata_bytes,
+            blob_info,
+            shards: shard_reports,
+        })
+    }
+
+    /// Returns the stored event cursor.
+    pub fn event_cursor_report(&self) -> Result<EventCursorReport, TypedStoreError> {
+        let cursor = self.get_event_cursor_and_next_index()?;
+        Ok(EventCursorReport {
+            event_id: cursor.as_ref().map(|cursor| cursor.event_id()),
+            next_event_index: cursor.map_or(0, |cursor| cursor.next_event_index()),
+        })
+    }
+
+    /// Returns RocksDB's estimates of the sizes of the column families of the node, including the
+    /// column families of shards stored in separate databases.
+    pub async fn column_family_sizes(&self) -> Result<Vec<ColumnFamilySize>, TypedStoreError> {
+        let mut sizes = vec![];
+        for cf_name in [
+            node_status_cf_name(),
+            metadata_cf_name(),
+            pinned_blobs_cf_name(),
+            dead_letter_events_cf_name(),
+            committee_history_cf_name(),
+            event_cursor_cf_name(),
+        ]
+        .into_iter()
+        .chain(
+            BlobInfoTable::options(&self.config)
+                .into_iter()
+                .map(|(name, _)| name),
+        ) {
+            sizes.extend(ColumnFamilySize::read(&self.database, cf_name)?);
+        }
+
+        let mut shards = self.existing_shard_storages().await;
+        shards.sort_by_key(|shard| shard.id());
+        for shard in shards {
+            sizes.extend(shard.column_family_sizes()?);
+        }
+        Ok(sizes)
+    }
+
     /// Returns true if the provided blob-id is stored at the specified shard.
     #[tracing::instrument(skip_all)]
     pub async fn is_stored_at_shard(
@@ -719,6 +1515,17 @@ impl Storage {
             .certified_blob_info_iter_before_epoch(epoch, std::ops::Bound::Unbounded)
     }
 
+    /// Returns an iterator over the certified blob info before the specified epoch, starting with
+    /// the `starting_blob_id_bound` bound.
+    pub(crate) fn certified_blob_info_iter_before_epoch_from(
+        &self,
+        epoch: Epoch,
+        starting_blob_id_bound: std::ops::Bound<BlobId>,
+    ) -> BlobInfoIterator {
+        self.blob_info
+            .certified_blob_info_iter_before_epoch(epoch, starting_blob_id_bound)
+    }
+
     /// Returns the current event cursor.
     pub(crate) fn get_event_cursor_progress(&self) -> Result<EventProgress, TypedStoreError> {
         self.event_cursor.get_event_cursor_progress()
@@ -775,7 +1582,7 @@ pub(crate) mod tests {
     use tempfile::TempDir;
     use tokio::runtime::Runtime;
     use walrus_core::{
-        encoding::{EncodingAxis, SliverData},
+        encoding::{EncodingAxis, Primary, Secondary, SliverData},
         Sliver,
         SliverIndex,
         SliverType,
@@ -876,6 +1683,267 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn pins_and_lists_blobs_with_disk_usage() -> TestResult {
+        let storage = populated_storage(&[
+            (SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)]),
+            (OTHER_SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Primary)]),
+        ])
+        .await?;
+        let storage = storage.as_ref();
+        let other_blob_id = BlobId([8; 32]);
+
+        assert!(!storage.is_blob_pinned(&BLOB_ID)?);
+        assert!(storage.pin_blob(&BLOB_ID)?);
+        assert!(!storage.pin_blob(&BLOB_ID)?);
+        assert!(storage.pin_blob(&other_blob_id)?);
+        assert!(storage.is_blob_pinned(&BLOB_ID)?);
+
+        let pinned = storage.list_pinned_blobs().await?;
+        assert_eq!(pinned.len(), 2);
+
+        let blob = &pinned[0];
+        assert_eq!(blob.blob_id, BLOB_ID);
+        assert_eq!(blob.stored_slivers, 3);
+        assert_eq!(blob.metadata_bytes, 0);
+        // The stored values are at least as large as the slivers they hold.
+        let sliver_data_bytes: u64 = [10u8, 11, 12].iter().map(|&seed| seed as u64 * 512).sum();
+        assert!(blob.sliver_bytes >= sliver_data_bytes);
+        let shards = storage.existing_shard_storages().await;
+        assert_eq!(
+            blob.total_bytes(),
+            storage.stored_blob_bytes(&BLOB_ID, &shards)?
+        );
+
+        let other_blob = &pinned[1];
+        assert_eq!(other_blob.blob_id, other_blob_id);
+        assert_eq!(other_blob.stored_slivers, 0);
+        assert_eq!(other_blob.total_bytes(), 0);
+
+        assert!(storage.unpin_blob(&BLOB_ID)?);
+        assert!(!storage.unpin_blob(&BLOB_ID)?);
+        assert!(!storage.is_blob_pinned(&BLOB_ID)?);
+        assert_eq!(storage.list_pinned_blobs().await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn collects_data_of_abandoned_uploads() -> TestResult {
+        let certified_blob_id = BlobId([8; 32]);
+        let pinned_blob_id = BlobId([9; 32]);
+        let recent_blob_id = BlobId([10; 32]);
+        let registered_blob_id = BlobId([11; 32]);
+        let storage = populated_storage(&[(
+            SHARD_INDEX,
+            [
+                BLOB_ID,
+                certified_blob_id,
+                pinned_blob_id,
+                recent_blob_id,
+                registered_blob_id,
+            ]
+            .into_iter()
+            .map(|blob_id| (blob_id, WhichSlivers::Both))
+            .collect(),
+        )])
+        .await?;
+        let storage = storage.as_ref();
+
+        let mut event_index = 0;
+        for (blob_id, epoch, end_epoch) in [
+            (BLOB_ID, 1, 3),
+            (certified_blob_id, 1, 3),
+            (pinned_blob_id, 1, 3),
+            (recent_blob_id, 3, 4),
+            // The registration is still valid, so the blob can still be certified.
+            (registered_blob_id, 1, 42),
+        ] {
+            let registered = BlobRegistered {
+                epoch,
+                end_epoch,
+                ..BlobRegistered::for_testing(blob_id)
+            };
+            storage.update_blob_info(event_index, &registered.clone().into())?;
+            event_index += 1;
+            if blob_id == certified_blob_id {
+                let certified = BlobCertified {
+                    object_id: registered.object_id,
+                    end_epoch,
+                    ..BlobCertified::for_testing(blob_id)
+                };
+                storage.update_blob_info(event_index, &certified.into())?;
+                event_index += 1;
+            }
+        }
+        storage.pin_blob(&pinned_blob_id)?;
+
+        let shards = storage.existing_shard_storages().await;
+        let expected_bytes = storage.stored_blob_bytes(&BLOB_ID, &shards)?;
+        // The stored values are at least as large as the slivers they hold.
+        let sliver_bytes: u64 = storage
+            .inspect_blob(&BLOB_ID)
+            .await?
+            .shards
+            .iter()
+            .map(|shard| {
+                shard.primary_sliver_bytes.unwrap_or_default()
+                    + shard.secondary_sliver_bytes.unwrap_or_default()
+            })
+            .sum();
+        assert!(expected_bytes >= sliver_bytes && sliver_bytes > 0);
+        let lock = tokio::sync::Mutex::new(());
+        let config = storage.orphaned_sliver_collection_config().clone();
+
+        // The registrations are still within the grace period.
+        assert_eq!(
+            storage.collect_orphaned_slivers(&config, 2, &lock).await?,
+            CollectedOrphanedData::default()
+        );
+
+        assert_eq!(
+            storage.collect_orphaned_slivers(&config, 3, &lock).await?,
+            CollectedOrphanedData {
+                deleted_blobs: 1,
+                reclaimed_bytes: expected_bytes,
+            }
+        );
+        let shard = storage
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        assert!(!shard.is_sliver_stored::<Primary>(&BLOB_ID)?);
+        assert!(!shard.is_sliver_stored::<Secondary>(&BLOB_ID)?);
+        for blob_id in [
+            certified_blob_id,
+            pinned_blob_id,
+            recent_blob_id,
+            registered_blob_id,
+        ] {
+            assert!(shard.is_sliver_pair_stored(&blob_id)?);
+        }
+
+        // Blobs without any stored data are not counted again.
+        assert_eq!(
+            storage.collect_orphaned_slivers(&config, 3, &lock).await?,
+            CollectedOrphanedData::default()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn inspects_blobs_and_shards() -> TestResult {
+        let other_blob_id = BlobId([8; 32]);
+        let storage = populated_storage(&[
+            (
+                SHARD_INDEX,
+                vec![
+                    (BLOB_ID, WhichSlivers::Both),
+                    (other_blob_id, WhichSlivers::Secondary),
+                ],
+            ),
+            (OTHER_SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Primary)]),
+        ])
+        .await?;
+        let storage = storage.as_ref();
+
+        let page = storage
+            .list_shard_blobs(SHARD_INDEX, None, 1)
+            .await?
+            .expect("shard is stored");
+        assert_eq!(page.blob_ids, vec![BLOB_ID]);
+        assert_eq!(page.next_after, Some(BLOB_ID));
+        let page = storage
+            .list_shard_blobs(SHARD_INDEX, page.next_after, 10)
+            .await?
+            .expect("shard is stored");
+        assert_eq!(page.blob_ids, vec![other_blob_id]);
+        assert_eq!(page.next_after, None);
+        assert!(storage
+            .list_shard_blobs(ShardIndex(1), None, 10)
+            .await?
+            .is_none());
+
+        let report = storage.inspect_blob(&BLOB_ID).await?;
+        assert_eq!(report.metadata_bytes, None);
+        assert_eq!(
+            report.shards,
+            vec![
+                ShardSliverReport {
+                    shard_index: SHARD_INDEX,
+                    primary_sliver_bytes: Some(10 * 512),
+                    secondary_sliver_bytes: Some(11 * 512),
+                },
+                ShardSliverReport {
+                    shard_index: OTHER_SHARD_INDEX,
+                    primary_sliver_bytes: Some(13 * 512),
+                    secondary_sliver_bytes: None,
+                },
+            ]
+        );
+
+        assert_eq!(storage.event_cursor_report()?.next_event_index, 0);
+        let sizes = storage.column_family_sizes().await?;
+        assert!(sizes.iter().any(|size| size.name == metadata_cf_name()));
+        assert!(sizes
+            .iter()
+            .any(|size| size.name == primary_slivers_column_family_name(OTHER_SHARD_INDEX)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn records_and_removes_dead_letter_events() -> TestResult {
+        let storage = empty_storage().await;
+        let storage = storage.as_ref();
+        let event = BlobEvent::Certified(BlobCertified::for_testing(BLOB_ID));
+
+        storage.put_dead_letter_event(12, event_id_for_testing(), event.clone(), 5, "a".into())?;
+        storage.put_dead_letter_event(3, event_id_for_testing(), event.clone(), 5, "b".into())?;
+        storage.put_dead_letter_event(12, event_id_for_testing(), event.clone(), 6, "c".into())?;
+
+        let events = storage.list_dead_letter_events()?;
+        assert_eq!(
+            events
+                .iter()
+                .map(|entry| (entry.event_index, entry.attempts, entry.error.as_str()))
+                .collect::<Vec<_>>(),
+            [(3, 5, "b"), (12, 6, "c")]
+        );
+        assert_eq!(
+            storage.get_dead_letter_event(12)?.map(|entry| entry.event),
+            Some(event)
+        );
+
+        storage.remove_dead_letter_event(12)?;
+        assert_eq!(storage.get_dead_letter_event(12)?, None);
+        assert_eq!(storage.list_dead_letter_events()?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replaces_committee_history() -> TestResult {
+        let storage = empty_storage().await;
+        let storage = storage.as_ref();
+        let committees: Vec<_> = (1..=4)
+            .map(|epoch| Arc::new(crate::test_utils::test_committee_with_epoch(&[2, 3], epoch)))
+            .collect();
+
+        storage.replace_committee_history(&committees[..3])?;
+        storage.replace_committee_history(&committees[1..])?;
+
+        assert_eq!(
+            storage.committee_history()?,
+            committees[1..]
+                .iter()
+                .map(|committee| committee.as_ref().clone())
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn stores_and_deletes_metadata() -> TestResult {
         let storage = empty_storage().await;
@@ -1003,6 +2071,17 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[test]
+    fn identifies_corruption_errors() {
+        assert!(is_corruption_error(&TypedStoreError::RocksDBError(
+            "Corruption: block checksum mismatch: stored = 1, computed = 2".to_owned()
+        )));
+        assert!(!is_corruption_error(&TypedStoreError::RocksDBError(
+            "IO error: No space left on device".to_owned()
+        )));
+        assert!(!is_corruption_error(&TypedStoreError::CrossDBBatch));
+    }
+
     #[tokio::test]
     async fn update_blob_info_metadata_stored() -> TestResult {
         let storage = empty_storage().await;
@@ -1214,6 +2293,112 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn migrates_shards_to_separate_databases() -> TestResult {
+        let directory = populate_storage_then_close(
+            &[
+                (SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)]),
+                (OTHER_SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Primary)]),
+            ],
+            Some(SHARD_INDEX),
+        )?;
+        let db_config = DatabaseConfig {
+            per_shard_databases: true,
+            ..Default::default()
+        };
+
+        // The first opening migrates the shards, the second one opens the separate databases.
+        for _ in 0..2 {
+            let db_config = db_config.clone();
+            let path = directory.path().to_owned();
+            Runtime::new()?.block_on(async move {
+                let storage =
+                    Storage::open(&path, db_config, MetricConf::default(), Registry::default())?;
+                assert!(
+                    ShardStorage::existing_cf_shards_ids(&path, &Options::default()).is_empty()
+                );
+                assert_eq!(
+                    existing_shard_database_ids(&path.join(SHARD_DATABASES_DIRECTORY), false)?,
+                    HashSet::from([SHARD_INDEX, OTHER_SHARD_INDEX])
+                );
+
+                let shard = storage
+                    .shard_storage(SHARD_INDEX)
+                    .await
+                    .expect("shard should exist");
+                assert_eq!(shard.status()?, ShardStatus::LockedToMove);
+                assert!(shard.is_sliver_pair_stored(&BLOB_ID)?);
+                let other_shard = storage
+                    .shard_storage(OTHER_SHARD_INDEX)
+                    .await
+                    .expect("shard should exist");
+                assert!(other_shard.is_sliver_stored::<Primary>(&BLOB_ID)?);
+                assert!(!other_shard.is_sliver_stored::<Secondary>(&BLOB_ID)?);
+
+                storage.delete_blob_data(&BLOB_ID).await?;
+                assert!(!shard.is_sliver_stored::<Primary>(&BLOB_ID)?);
+                anyhow::Ok(())
+            })?;
+        }
+
+        for id in [SHARD_INDEX, OTHER_SHARD_INDEX] {
+            let shard_database = open_database(
+                &directory
+                    .path()
+                    .join(SHARD_DATABASES_DIRECTORY)
+                    .join(id.to_string()),
+                None,
+                database_options(&db_config),
+                MetricConf::default(),
+                &shard_database_column_families(id, &db_config),
+            )?;
+            migrations::ensure_schema_version(
+                &shard_database,
+                migrations::SHARD_DATABASE_MIGRATIONS,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn opens_storage_read_only_without_migrating_shards() -> TestResult {
+        let directory = populate_storage_then_close(
+            &[(SHARD_INDEX, vec![(BLOB_ID, WhichSlivers::Both)])],
+            None,
+        )?;
+        let secondary_dir = tempfile::tempdir()?;
+        let db_config = DatabaseConfig {
+            per_shard_databases: true,
+            ..Default::default()
+        };
+
+        Runtime::new()?.block_on(async {
+            let storage = Storage::open_read_only(
+                directory.path(),
+                secondary_dir.path(),
+                db_config,
+                Registry::default(),
+            )?;
+            let shard = storage
+                .shard_storage(SHARD_INDEX)
+                .await
+                .expect("shard should exist");
+            assert!(shard.is_sliver_pair_stored(&BLOB_ID)?);
+            assert!(storage.set_node_status(NodeStatus::Active).is_err());
+            anyhow::Ok(())
+        })?;
+
+        assert_eq!(
+            ShardStorage::existing_cf_shards_ids(directory.path(), &Options::default()),
+            HashSet::from([SHARD_INDEX])
+        );
+        assert!(!directory.path().join(SHARD_DATABASES_DIRECTORY).exists());
+        Ok(())
+    }
+
     // Tests that shard status can be restored upon restart.
     #[test]
     #[cfg_attr(msim, ignore)]
@@ -179,6 +179,22 @@ impl BlobInfoTable {
         self.aggregate_blob_info.get(blob_id)
     }
 
+    /// Returns an iterator over the aggregate blob info of all blobs.
+    pub fn safe_iter(
+        &self,
+    ) -> impl Iterator<Item = Result<(BlobId, BlobInfo), TypedStoreError>> + '_ {
+        self.aggregate_blob_info.safe_iter()
+    }
+
+    /// Returns an iterator over the per-object blob info of all blob objects.
+    pub fn per_object_blob_info_iter(
+        &self,
+    ) -> impl Iterator<Item = Result<PerObjectBlobInfo, TypedStoreError>> + '_ {
+        self.per_object_blob_info
+            .safe_iter()
+            .map(|result| result.map(|(_, info)| info))
+    }
+
     /// Returns the per-object blob info for `object_id`.
     pub fn get_per_object_info(
         &self,
@@ -1116,7 +1132,7 @@ impl Mergeable for BlobInfoV1 {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[repr(u8)]
 #[cfg(test)]
-pub(super) enum BlobCertificationStatus {
+pub(crate) enum BlobCertificationStatus {
     Registered,
     Certified,
     Invalid,
@@ -1155,7 +1171,7 @@ pub(crate) enum BlobInfo {
 impl BlobInfo {
     /// Creates a new (permanent) blob for testing purposes.
     #[cfg(test)]
-    pub(super) fn new_for_testing(
+    pub(crate) fn new_for_testing(
         end_epoch: Epoch,
         status: BlobCertificationStatus,
         current_status_event: EventID,
@@ -1251,6 +1267,8 @@ mod per_object_blob_info {
         /// Returns true iff the object is certified and not expired and not deleted.
         fn is_certified(&self, current_epoch: Epoch) -> bool;
 
+        /// Returns the epoch in which the object was registered.
+        fn registered_epoch(&self) -> Epoch;
         /// Returns the epoch at which this blob was first certified.
         ///
         /// Returns `None` if it was never certified.
@@ -1314,6 +1332,10 @@ mod per_object_blob_info {
             self.is_registered(current_epoch) && self.certified_epoch.is_some()
         }
 
+        fn registered_epoch(&self) -> Epoch {
+            self.registered_epoch
+        }
+
         fn certified_epoch(&self) -> Option<Epoch> {
             self.certified_epoch
         }
@@ -11,11 +11,18 @@ const METADATA_COLUMN_FAMILY_NAME: &str = "metadata";
 const EVENT_INDEX_COLUMN_FAMILY_NAME: &str = "latest_handled_event_index";
 const EVENT_CURSOR_COLUMN_FAMILY_NAME: &str = "event_cursor";
 const EVENT_CURSOR_KEY: [u8; 6] = *b"cursor";
+const PINNED_BLOBS_COLUMN_FAMILY_NAME: &str = "pinned_blobs";
+const DEAD_LETTER_EVENTS_COLUMN_FAMILY_NAME: &str = "dead_letter_events";
+const SCHEMA_VERSION_COLUMN_FAMILY_NAME: &str = "schema_version";
+const COMMITTEE_HISTORY_COLUMN_FAMILY_NAME: &str = "committee_history";
 
 // Base name for shard-related column families
 const SHARD_BASE_COLUMN_FAMILY_NAME: &str = "shard";
 const SHARD_PRIMARY_SLIVERS_COLUMN_FAMILY_NAME: &str = "primary-slivers";
 const SHARD_SECONDARY_SLIVERS_COLUMN_FAMILY_NAME: &str = "secondary-slivers";
+const SHARD_PRIMARY_SLIVERS_COLD_COLUMN_FAMILY_NAME: &str = "primary-slivers-cold";
+const SHARD_SECONDARY_SLIVERS_COLD_COLUMN_FAMILY_NAME: &str = "secondary-slivers-cold";
+const SHARD_SLIVER_LAST_ACCESS_COLUMN_FAMILY_NAME: &str = "sliver-last-access";
 const SHARD_STATUS_COLUMN_FAMILY_NAME: &str = "status";
 const SHARD_SYNC_PROGRESS_COLUMN_FAMILY_NAME: &str = "sync-progress";
 const SHARD_PENDING_RECOVER_SLIVERS_COLUMN_FAMILY_NAME: &str = "pending-recover-slivers";
@@ -59,6 +66,26 @@ pub fn event_cursor_key() -> &'static [u8; 6] {
     &EVENT_CURSOR_KEY
 }
 
+/// Returns the name of the pinned blobs column family.
+pub fn pinned_blobs_cf_name() -> &'static str {
+    PINNED_BLOBS_COLUMN_FAMILY_NAME
+}
+
+/// Returns the name of the dead-letter events column family.
+pub fn dead_letter_events_cf_name() -> &'static str {
+    DEAD_LETTER_EVENTS_COLUMN_FAMILY_NAME
+}
+
+/// Returns the name of the committee history column family.
+pub fn committee_history_cf_name() -> &'static str {
+    COMMITTEE_HISTORY_COLUMN_FAMILY_NAME
+}
+
+/// Returns the name of the schema version column family.
+pub fn schema_version_cf_name() -> &'static str {
+    SCHEMA_VERSION_COLUMN_FAMILY_NAME
+}
+
 /// Returns the column family name for primary slivers of a shard.
 pub fn primary_slivers_column_family_name(id: ShardIndex) -> String {
     format!(
@@ -77,6 +104,33 @@ pub fn secondary_slivers_column_family_name(id: ShardIndex) -> String {
     )
 }
 
+/// Returns the column family name for the cold tier of primary slivers of a shard.
+pub fn primary_slivers_cold_column_family_name(id: ShardIndex) -> String {
+    format!(
+        "{}/{}",
+        base_column_family_name(id),
+        SHARD_PRIMARY_SLIVERS_COLD_COLUMN_FAMILY_NAME
+    )
+}
+
+/// Returns the column family name for the cold tier of secondary slivers of a shard.
+pub fn secondary_slivers_cold_column_family_name(id: ShardIndex) -> String {
+    format!(
+        "{}/{}",
+        base_column_family_name(id),
+        SHARD_SECONDARY_SLIVERS_COLD_COLUMN_FAMILY_NAME
+    )
+}
+
+/// Returns the column family name for the last access times of the hot slivers of a shard.
+pub fn sliver_last_access_column_family_name(id: ShardIndex) -> String {
+    format!(
+        "{}/{}",
+        base_column_family_name(id),
+        SHARD_SLIVER_LAST_ACCESS_COLUMN_FAMILY_NAME
+    )
+}
+
 /// Returns the column family name for status of a shard.
 pub fn shard_status_column_family_name(id: ShardIndex) -> String {
     format!(
@@ -116,6 +170,8 @@ mod tests {
         assert_eq!(per_object_blob_info_cf_name(), "per_object_blob_info");
         assert_eq!(node_status_cf_name(), "node_status");
         assert_eq!(event_index_cf_name(), "latest_handled_event_index");
+        assert_eq!(pinned_blobs_cf_name(), "pinned_blobs");
+        assert_eq!(schema_version_cf_name(), "schema_version");
 
         let shard = ShardIndex(900);
         assert_eq!(base_column_family_name(shard), "shard-900");
@@ -136,6 +192,18 @@ mod tests {
             secondary_slivers_column_family_name(shard),
             "shard-900/secondary-slivers"
         );
+        assert_eq!(
+            primary_slivers_cold_column_family_name(shard),
+            "shard-900/primary-slivers-cold"
+        );
+        assert_eq!(
+            secondary_slivers_cold_column_family_name(shard),
+            "shard-900/secondary-slivers-cold"
+        );
+        assert_eq!(
+            sliver_last_access_column_family_name(shard),
+            "shard-900/sliver-last-access"
+        );
     }
 
     #[test]
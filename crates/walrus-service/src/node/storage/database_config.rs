@@ -1,8 +1,13 @@
 // Copyright (c) Walrus Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use rocksdb::{DBCompressionType, Options};
+use std::{path::PathBuf, time::Duration};
+
+use rocksdb::{BlockBasedOptions, ChecksumType, DBCompressionType, Options};
 use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DurationSeconds};
+
+use super::StorageEncryptionConfig;
 
 /// Options for configuring a column family.
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
@@ -37,6 +42,9 @@ pub struct DatabaseTableOptions {
     target_file_size_base: Option<u64>,
     /// The maximum total data size for level 1 in bytes.
     max_bytes_for_level_base: Option<u64>,
+    /// The checksum stored with each block of the table files, which is verified whenever a block
+    /// is read. One of `none`, `crc32c`, `xxhash`, `xxhash64`, or `xxh3`.
+    block_checksum_type: Option<String>,
 }
 
 impl DatabaseTableOptions {
@@ -53,6 +61,7 @@ impl DatabaseTableOptions {
             write_buffer_size: Some(64 << 20),
             target_file_size_base: Some(64 << 20),
             max_bytes_for_level_base: Some(512 << 20),
+            block_checksum_type: Some("xxh3".to_string()),
         }
     }
 
@@ -69,6 +78,24 @@ impl DatabaseTableOptions {
             write_buffer_size: Some(256 << 20),
             target_file_size_base: Some(64 << 20),
             max_bytes_for_level_base: Some(512 << 20),
+            block_checksum_type: Some("xxh3".to_string()),
+        }
+    }
+
+    fn optimized_for_cold_blobs() -> Self {
+        Self {
+            enable_blob_files: Some(true),
+            min_blob_size: Some(0),
+            blob_file_size: Some(1 << 30),
+            blob_compression_type: Some("zstd".to_string()),
+            enable_blob_garbage_collection: Some(true),
+            blob_garbage_collection_age_cutoff: Some(0.25),
+            blob_garbage_collection_force_threshold: Some(0.8),
+            blob_compaction_read_ahead_size: Some(10 << 20),
+            write_buffer_size: Some(64 << 20),
+            target_file_size_base: Some(64 << 20),
+            max_bytes_for_level_base: Some(512 << 20),
+            block_checksum_type: Some("xxh3".to_string()),
         }
     }
 
@@ -118,6 +145,18 @@ impl DatabaseTableOptions {
         if let Some(max_bytes_for_level_base) = self.max_bytes_for_level_base {
             options.set_max_bytes_for_level_base(max_bytes_for_level_base);
         }
+        if let Some(block_checksum_type) = &self.block_checksum_type {
+            let checksum_type = match block_checksum_type.as_str() {
+                "none" => ChecksumType::NoChecksum,
+                "crc32c" => ChecksumType::CRC32c,
+                "xxhash" => ChecksumType::xxHash,
+                "xxhash64" => ChecksumType::xxHash64,
+                _ => ChecksumType::XXH3,
+            };
+            let mut block_options = BlockBasedOptions::default();
+            block_options.set_checksum_type(checksum_type);
+            options.set_block_based_table_factory(&block_options);
+        }
 
         options
     }
@@ -128,12 +167,16 @@ impl DatabaseTableOptions {
 pub struct GlobalDatabaseOptions {
     /// The maximum number of open files
     pub max_open_files: Option<i32>,
+    /// Whether to aggressively check the consistency of the data, such that the database fails
+    /// on corrupted files instead of silently skipping them.
+    pub paranoid_checks: Option<bool>,
 }
 
 impl Default for GlobalDatabaseOptions {
     fn default() -> Self {
         Self {
             max_open_files: Some(512_000),
+            paranoid_checks: Some(true),
         }
     }
 }
@@ -145,19 +188,91 @@ impl From<&GlobalDatabaseOptions> for Options {
         if let Some(max_files) = value.max_open_files {
             options.set_max_open_files(max_files);
         }
+        if let Some(paranoid_checks) = value.paranoid_checks {
+            options.set_paranoid_checks(paranoid_checks);
+        }
 
         options
     }
 }
 
+/// Policy for moving slivers between the hot and cold storage tiers of a shard.
+///
+/// When enabled, slivers that have been neither written nor read for `cold_after` are periodically
+/// moved to the compressed cold tier. Slivers read from the cold tier are moved back to the hot
+/// tier. Slivers stored before tiering was enabled are tracked from their first access.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SliverTieringConfig {
+    /// Whether slivers are moved to the cold tier.
+    pub enabled: bool,
+    /// The time since the last access after which a sliver is moved to the cold tier.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "cold_after_secs")]
+    pub cold_after: Duration,
+    /// The interval at which cold slivers are moved to the cold tier.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "demotion_interval_secs")]
+    pub demotion_interval: Duration,
+    /// The maximum number of slivers moved to the cold tier per shard and run.
+    pub max_demotions_per_shard: usize,
+}
+
+impl Default for SliverTieringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cold_after: Duration::from_secs(7 * 24 * 60 * 60),
+            demotion_interval: Duration::from_secs(60 * 60),
+            max_demotions_per_shard: 10_000,
+        }
+    }
+}
+
+/// Policy for deleting the data of blobs that were registered but never certified.
+///
+/// Uploads that are abandoned after registering the blob leave their metadata and slivers on the
+/// node. Once all registrations of a blob are older than the grace period and none of them was
+/// certified, the blob's data is deleted unless the blob is pinned.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct OrphanedSliverCollectionConfig {
+    /// Whether the data of abandoned uploads is deleted.
+    pub enabled: bool,
+    /// The number of epochs after the latest registration of a blob within which it must be
+    /// certified for its data to be kept. The data is never deleted while a registration of the
+    /// blob is still valid.
+    pub grace_epochs: u32,
+    /// The interval at which the data of abandoned uploads is collected.
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(rename = "collection_interval_secs")]
+    pub collection_interval: Duration,
+    /// The maximum number of blobs whose data is deleted per run.
+    pub max_deletions_per_run: usize,
+}
+
+impl Default for OrphanedSliverCollectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            grace_epochs: 2,
+            collection_interval: Duration::from_secs(60 * 60),
+            max_deletions_per_run: 1_000,
+        }
+    }
+}
+
 /// Database configuration for Walrus storage nodes.
 ///
 /// The `standard` options are applied to all tables except for slivers and metadata. The
-/// `optimized_for_blobs` options are applied to sliver and metadata tables.
+/// `optimized_for_blobs` options are applied to sliver and metadata tables, and the
+/// `optimized_for_cold_blobs` options are applied to the cold tier of the sliver tables.
 ///
 /// Options for all individual tables can be set as well through the `node_status`, `metadata`,
-/// `blob_info`, `per_object_blob_info`, `event_cursor`, `shard`, `shard_status`,
-/// `shard_sync_progress`, and `pending_recover_slivers` fields.
+/// `blob_info`, `per_object_blob_info`, `event_cursor`, `shard`, `cold_shard`, `shard_status`,
+/// `shard_sync_progress`, `pending_recover_slivers`, and `sliver_last_access` fields.
 ///
 /// **Warning:** Note that the configuration is currently not properly hierarchical. For example, if
 /// the `metadata` options are defined, they are *not* merged with the `optimized_for_blobs` or
@@ -173,6 +288,8 @@ pub struct DatabaseConfig {
     pub(super) standard: DatabaseTableOptions,
     /// Database table options applied to sliver and metadata tables.
     pub(super) optimized_for_blobs: DatabaseTableOptions,
+    /// Database table options applied to the cold tier of sliver tables.
+    pub(super) optimized_for_cold_blobs: DatabaseTableOptions,
     /// Node status database options.
     pub(super) node_status: Option<DatabaseTableOptions>,
     /// Metadata database options.
@@ -185,12 +302,37 @@ pub struct DatabaseConfig {
     pub(super) event_cursor: Option<DatabaseTableOptions>,
     /// Shard database options.
     pub(super) shard: Option<DatabaseTableOptions>,
+    /// Shard cold tier database options.
+    pub(super) cold_shard: Option<DatabaseTableOptions>,
     /// Shard status database options.
     pub(super) shard_status: Option<DatabaseTableOptions>,
     /// Shard sync progress database options.
     pub(super) shard_sync_progress: Option<DatabaseTableOptions>,
     /// Pending recover slivers database options.
     pub(super) pending_recover_slivers: Option<DatabaseTableOptions>,
+    /// Sliver last access database options.
+    pub(super) sliver_last_access: Option<DatabaseTableOptions>,
+    /// Policy for moving slivers between the hot and cold tiers.
+    pub sliver_tiering: SliverTieringConfig,
+    /// Policy for deleting the data of blobs that were registered but never certified.
+    pub orphaned_sliver_collection: OrphanedSliverCollectionConfig,
+    /// The source of the key used to encrypt slivers at rest. Slivers are stored unencrypted if
+    /// this is not set.
+    pub encryption: Option<StorageEncryptionConfig>,
+    /// Whether shards are stored in separate databases, each with its own write-ahead log, such
+    /// that flushes and compactions of busy shards do not stall other shards.
+    ///
+    /// When enabled, newly created shards are stored in separate databases, and shards stored in
+    /// the main database are migrated to separate databases when the node starts. Shards stored in
+    /// separate databases remain there if the option is disabled again.
+    pub per_shard_databases: bool,
+    /// The directory in which a checkpoint of the database is created before migrating the
+    /// database to a new schema version. No checkpoint is created if this is not set. Checkpoints
+    /// of separate shard databases are created in a `shard-<index>` subdirectory.
+    ///
+    /// Checkpoints hard-link the immutable database files if the directory is on the same file
+    /// system as the database, and copy them otherwise.
+    pub migration_backup_path: Option<PathBuf>,
 }
 
 impl DatabaseConfig {
@@ -224,6 +366,13 @@ impl DatabaseConfig {
         self.shard.as_ref().unwrap_or(&self.optimized_for_blobs)
     }
 
+    /// Returns the shard cold tier database option.
+    pub fn cold_shard(&self) -> &DatabaseTableOptions {
+        self.cold_shard
+            .as_ref()
+            .unwrap_or(&self.optimized_for_cold_blobs)
+    }
+
     /// Returns the shard status database option.
     pub fn shard_status(&self) -> &DatabaseTableOptions {
         self.shard_status.as_ref().unwrap_or(&self.standard)
@@ -240,6 +389,11 @@ impl DatabaseConfig {
             .as_ref()
             .unwrap_or(&self.standard)
     }
+
+    /// Returns the sliver last access database option.
+    pub fn sliver_last_access(&self) -> &DatabaseTableOptions {
+        self.sliver_last_access.as_ref().unwrap_or(&self.standard)
+    }
 }
 
 impl Default for DatabaseConfig {
@@ -248,15 +402,23 @@ impl Default for DatabaseConfig {
             global: GlobalDatabaseOptions::default(),
             standard: DatabaseTableOptions::standard(),
             optimized_for_blobs: DatabaseTableOptions::optimized_for_blobs(),
+            optimized_for_cold_blobs: DatabaseTableOptions::optimized_for_cold_blobs(),
             node_status: None,
             metadata: None,
             blob_info: None,
             per_object_blob_info: None,
             event_cursor: None,
             shard: None,
+            cold_shard: None,
             shard_status: None,
             shard_sync_progress: None,
             pending_recover_slivers: None,
+            sliver_last_access: None,
+            sliver_tiering: SliverTieringConfig::default(),
+            orphaned_sliver_collection: OrphanedSliverCollectionConfig::default(),
+            encryption: None,
+            per_shard_databases: false,
+            migration_backup_path: None,
         }
     }
 }
@@ -0,0 +1,197 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encryption of the slivers stored by the node.
+//!
+//! When a storage encryption key is configured, slivers are encrypted with AES-256-GCM before being
+//! written to the database. Each sliver is encrypted with a random nonce and bound to its blob ID
+//! and sliver type, such that encrypted slivers cannot be swapped between blobs. Slivers stored
+//! before encryption was enabled remain readable and are encrypted when they are written again.
+//!
+//! Metadata and blob info are not encrypted, as they are derived from public data.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use typed_store::TypedStoreError;
+use walrus_core::{BlobId, SliverType};
+
+/// The length of the storage encryption key in bytes.
+const KEY_LENGTH: usize = 32;
+
+/// The source of the key used to encrypt the slivers stored by the node.
+///
+/// The key is a hex-encoded 256-bit key.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEncryptionConfig {
+    /// Read the key from the file at the given path.
+    KeyPath(PathBuf),
+    /// Read the key from the standard output of the given shell command.
+    ///
+    /// This allows fetching the key from a key management service at startup, without storing it
+    /// on the node's disk.
+    KeyCommand(String),
+}
+
+/// A sliver encrypted with the storage encryption key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedSliver {
+    nonce: [u8; NONCE_LEN],
+    /// The encrypted BCS-serialized sliver, followed by the authentication tag.
+    ciphertext: Vec<u8>,
+}
+
+/// Encrypts and decrypts slivers with the storage encryption key.
+#[derive(Debug)]
+pub(crate) struct SliverCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl SliverCipher {
+    /// Loads the storage encryption key from the configured source.
+    pub(crate) fn load(config: &StorageEncryptionConfig) -> anyhow::Result<Self> {
+        let encoded_key = match config {
+            StorageEncryptionConfig::KeyPath(path) => {
+                std::fs::read_to_string(path).with_context(|| {
+                    format!(
+                        "unable to read the storage encryption key from {}",
+                        path.display()
+                    )
+                })?
+            }
+            StorageEncryptionConfig::KeyCommand(command) => {
+                let output = std::process::Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .output()
+                    .context("unable to run the storage encryption key command")?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "the storage encryption key command failed with {}",
+                    output.status
+                );
+                String::from_utf8(output.stdout)
+                    .context("the storage encryption key command returned invalid UTF-8")?
+            }
+        };
+        let key = hex::decode(encoded_key.trim())
+            .context("the storage encryption key must be hex-encoded")?;
+        Self::new(&key)
+    }
+
+    fn new(key: &[u8]) -> anyhow::Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| anyhow!("the storage encryption key must be {KEY_LENGTH} bytes long"))?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Encrypts the sliver of the given type of the blob.
+    pub(crate) fn encrypt<T: Serialize>(
+        &self,
+        blob_id: &BlobId,
+        sliver_type: SliverType,
+        sliver: &T,
+    ) -> Result<EncryptedSliver, TypedStoreError> {
+        let mut ciphertext = bcs::to_bytes(sliver)
+            .map_err(|error| TypedStoreError::SerializationError(error.to_string()))?;
+        let mut nonce = [0; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| encryption_error("unable to generate a nonce"))?;
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(associated_data(blob_id, sliver_type)),
+                &mut ciphertext,
+            )
+            .map_err(|_| encryption_error("unable to encrypt the sliver"))?;
+        Ok(EncryptedSliver { nonce, ciphertext })
+    }
+
+    /// Decrypts the sliver of the given type of the blob.
+    ///
+    /// Fails if the sliver was encrypted with a different key or for a different blob or sliver
+    /// type.
+    pub(crate) fn decrypt<T: DeserializeOwned>(
+        &self,
+        blob_id: &BlobId,
+        sliver_type: SliverType,
+        encrypted: EncryptedSliver,
+    ) -> Result<T, TypedStoreError> {
+        let EncryptedSliver {
+            nonce,
+            mut ciphertext,
+        } = encrypted;
+        let plaintext = self
+            .key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(associated_data(blob_id, sliver_type)),
+                &mut ciphertext,
+            )
+            .map_err(|_| {
+                encryption_error("unable to decrypt the sliver; the encryption key may be wrong")
+            })?;
+        bcs::from_bytes(plaintext)
+            .map_err(|error| TypedStoreError::SerializationError(error.to_string()))
+    }
+}
+
+pub(crate) fn encryption_error(message: &str) -> TypedStoreError {
+    TypedStoreError::SerializationError(message.to_owned())
+}
+
+fn associated_data(blob_id: &BlobId, sliver_type: SliverType) -> [u8; BlobId::LENGTH + 1] {
+    let mut data = [0; BlobId::LENGTH + 1];
+    data[..BlobId::LENGTH].copy_from_slice(&blob_id.0);
+    data[BlobId::LENGTH] = match sliver_type {
+        SliverType::Primary => 0,
+        SliverType::Secondary => 1,
+    };
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::test_utils::random_blob_id;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+
+    #[test]
+    fn decrypts_only_with_same_key_and_blob() -> TestResult {
+        let cipher = SliverCipher::new(&[1; KEY_LENGTH])?;
+        let blob_id = random_blob_id();
+        let sliver = vec![7u8; 100];
+
+        let encrypted = cipher.encrypt(&blob_id, SliverType::Primary, &sliver)?;
+        assert_ne!(encrypted.ciphertext[..sliver.len()], sliver[..]);
+        assert_eq!(
+            cipher.decrypt::<Vec<u8>>(&blob_id, SliverType::Primary, encrypted.clone())?,
+            sliver
+        );
+
+        assert!(cipher
+            .decrypt::<Vec<u8>>(&blob_id, SliverType::Secondary, encrypted.clone())
+            .is_err());
+        assert!(cipher
+            .decrypt::<Vec<u8>>(&random_blob_id(), SliverType::Primary, encrypted.clone())
+            .is_err());
+        assert!(SliverCipher::new(&[2; KEY_LENGTH])?
+            .decrypt::<Vec<u8>>(&blob_id, SliverType::Primary, encrypted)
+            .is_err());
+        assert!(SliverCipher::new(&[1; 16]).is_err());
+
+        Ok(())
+    }
+}
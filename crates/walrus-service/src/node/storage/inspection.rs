@@ -0,0 +1,120 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read-only inspection of the storage for node operators.
+//!
+//! The reports are produced by the [`Storage`][super::Storage] of a stopped node, when inspecting
+//! the database directly, and are served by the administrative API of a running node.
+
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sui_types::event::EventID;
+use typed_store::{
+    rocks::{errors::typed_store_err_from_rocks_err, RocksDB},
+    TypedStoreError,
+};
+use walrus_core::{BlobId, ShardIndex};
+
+/// The default number of blob IDs returned when listing the blobs of a shard.
+pub const DEFAULT_SHARD_BLOBS_LIMIT: usize = 100;
+
+/// A page of the IDs of the blobs for which slivers are stored in a shard, in ascending order.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardBlobs {
+    /// The index of the shard.
+    pub shard_index: ShardIndex,
+    /// The IDs of the blobs with at least one sliver stored in the shard.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub blob_ids: Vec<BlobId>,
+    /// The blob ID after which to continue listing, if there may be further blobs.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_after: Option<BlobId>,
+}
+
+/// The data stored for a blob on this node.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobStorageReport {
+    /// The ID of the blob.
+    #[serde_as(as = "DisplayFromStr")]
+    pub blob_id: BlobId,
+    /// The number of bytes of metadata stored for the blob, if the metadata is stored.
+    pub metadata_bytes: Option<u64>,
+    /// The debug representation of the aggregate blob info, if any.
+    pub blob_info: Option<String>,
+    /// The slivers stored for the blob in each shard of this node.
+    pub shards: Vec<ShardSliverReport>,
+}
+
+/// The slivers stored for a blob in a shard.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardSliverReport {
+    /// The index of the shard.
+    pub shard_index: ShardIndex,
+    /// The size in bytes of the primary sliver, if it is stored.
+    pub primary_sliver_bytes: Option<u64>,
+    /// The size in bytes of the secondary sliver, if it is stored.
+    pub secondary_sliver_bytes: Option<u64>,
+}
+
+/// The event cursor stored by the node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCursorReport {
+    /// The ID of the last event that was fully processed, if any.
+    pub event_id: Option<EventID>,
+    /// The index of the next event to be processed.
+    pub next_event_index: u64,
+}
+
+/// RocksDB's estimates of the size of a column family.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnFamilySize {
+    /// The name of the column family.
+    pub name: String,
+    /// The estimated number of keys.
+    pub estimated_keys: u64,
+    /// The estimated size in bytes of the live data, including data in blob files.
+    pub live_data_bytes: u64,
+    /// The total size in bytes of the SST files.
+    pub sst_files_bytes: u64,
+}
+
+impl ColumnFamilySize {
+    /// Returns the estimated size of the column family in the database, or `None` if the column
+    /// family does not exist.
+    pub(crate) fn read(rocksdb: &RocksDB, name: &str) -> Result<Option<Self>, TypedStoreError> {
+        if rocksdb.cf_handle(name).is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            name: name.to_owned(),
+            estimated_keys: int_property(rocksdb, name, "rocksdb.estimate-num-keys")?,
+            live_data_bytes: int_property(rocksdb, name, "rocksdb.estimate-live-data-size")?
+                + int_property(rocksdb, name, "rocksdb.live-blob-file-size")?,
+            sst_files_bytes: int_property(rocksdb, name, "rocksdb.total-sst-files-size")?,
+        }))
+    }
+}
+
+/// Returns the value of the integer RocksDB property for the column family, or 0 if the column
+/// family or property does not exist.
+pub(crate) fn int_property(
+    rocksdb: &RocksDB,
+    cf_name: &str,
+    property: &str,
+) -> Result<u64, TypedStoreError> {
+    let Some(cf) = rocksdb.cf_handle(cf_name) else {
+        return Ok(0);
+    };
+    Ok(rocksdb
+        .property_int_value_cf(&cf, property)
+        .map_err(typed_store_err_from_rocks_err)?
+        .unwrap_or_default())
+}
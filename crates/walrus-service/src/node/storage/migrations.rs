@@ -0,0 +1,263 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versioning of the storage schema and migrations between schema versions.
+//!
+//! The main database and every separate shard database store their schema version in a dedicated
+//! column family. When the storage is opened, the migrations from the stored version to the latest
+//! version are run in order. The stored version is updated after each migration, such that an
+//! interrupted upgrade resumes with the first migration that did not complete. Migrations run on
+//! the main database before the shards are opened, and on a separate shard database when it is
+//! opened.
+//!
+//! To change the encoding of keys or values, or the set of column families, add a [`Migration`]
+//! with the next version to [`MIGRATIONS`] and increase [`CURRENT_SCHEMA_VERSION`]. Changes to the
+//! column families of shards also need a migration in [`SHARD_DATABASE_MIGRATIONS`], as shards may
+//! be stored in the main database or in separate databases.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use rocksdb::Options;
+use typed_store::{
+    rocks::{DBMap, ReadWriteOptions, RocksDB},
+    Map,
+    TypedStoreError,
+};
+use walrus_core::ShardIndex;
+
+use super::{constants::schema_version_cf_name, shard::unix_time_secs, DatabaseConfig};
+
+/// The schema version of main databases created or migrated by this version of the node.
+pub(super) const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// The schema version of separate shard databases created or migrated by this version of the node.
+pub(super) const CURRENT_SHARD_DATABASE_SCHEMA_VERSION: u32 = 1;
+
+/// The migrations of the schema of the main database, ordered by version.
+///
+/// Databases created before the schema was versioned have version 0.
+pub(super) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "record the schema version",
+    migrate: |_, _| Ok(()),
+    rollback: None,
+}];
+
+/// The migrations of the schema of separate shard databases, ordered by version.
+///
+/// Shard databases created before the schema was versioned have version 0.
+pub(super) const SHARD_DATABASE_MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "record the schema version of the shard database",
+    migrate: |_, _| Ok(()),
+    rollback: None,
+}];
+
+/// A migration of the database to a schema version.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Migration {
+    /// The schema version of the database after the migration.
+    pub version: u32,
+    /// A short description of the changes made by the migration.
+    pub description: &'static str,
+    /// Migrates the database from the previous schema version.
+    ///
+    /// The migration is called with the shards whose column families are stored in the database.
+    pub migrate: fn(&Arc<RocksDB>, &[ShardIndex]) -> Result<(), TypedStoreError>,
+    /// Undoes the changes of a migration that failed part-way, if the migration is not atomic.
+    pub rollback: Option<fn(&Arc<RocksDB>, &[ShardIndex]) -> Result<(), TypedStoreError>>,
+}
+
+/// Returns the options for the schema version column family.
+pub(super) fn schema_version_options(db_config: &DatabaseConfig) -> Options {
+    db_config.node_status().to_options()
+}
+
+/// Brings the schema of the database to the version of the last of the `migrations`.
+///
+/// A new database is marked with the latest version without running any migrations. Before
+/// running the migrations of an existing database, a checkpoint of the database is created in
+/// `backup_path`, if it is provided. If a migration fails, its rollback is run and the schema
+/// version remains at that of the last successful migration.
+///
+/// `shards` are the shards whose column families are stored in the database.
+pub(super) fn run_migrations(
+    database: &Arc<RocksDB>,
+    migrations: &[Migration],
+    shards: &[ShardIndex],
+    is_new_database: bool,
+    backup_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let schema_version: DBMap<(), u32> = DBMap::reopen(
+        database,
+        Some(schema_version_cf_name()),
+        &ReadWriteOptions::default(),
+        false,
+    )?;
+    let latest_version = migrations.last().map_or(0, |migration| migration.version);
+    let stored_version = match schema_version.get(&())? {
+        Some(version) => version,
+        None if is_new_database => {
+            schema_version.insert(&(), &latest_version)?;
+            return Ok(());
+        }
+        None => 0,
+    };
+    anyhow::ensure!(
+        stored_version <= latest_version,
+        "the database has schema version {stored_version}, which is newer than the latest version \
+        {latest_version} supported by this node; downgrading the node is not supported"
+    );
+
+    let pending = migrations
+        .iter()
+        .filter(|migration| migration.version > stored_version)
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let backup = backup_path
+        .map(|backup_path| {
+            let checkpoint_path =
+                backup_path.join(format!("schema-v{stored_version}-{}", unix_time_secs()));
+            tracing::info!(
+                path = %checkpoint_path.display(),
+                "creating a checkpoint of the database before migrating the schema"
+            );
+            schema_version
+                .checkpoint_db(&checkpoint_path)
+                .context("unable to create a checkpoint of the database")
+                .map(|()| checkpoint_path)
+        })
+        .transpose()?;
+
+    for migration in pending {
+        tracing::info!(
+            version = migration.version,
+            description = migration.description,
+            "migrating the database schema"
+        );
+        if let Err(error) = (migration.migrate)(database, shards) {
+            if let Some(rollback) = migration.rollback {
+                if let Err(rollback_error) = rollback(database, shards) {
+                    tracing::error!(
+                        ?rollback_error,
+                        version = migration.version,
+                        "failed to roll back the schema migration"
+                    );
+                }
+            }
+            let backup_hint = backup.as_ref().map_or_else(String::new, |path| {
+                format!("; a checkpoint of the database is at {}", path.display())
+            });
+            return Err(error).with_context(|| {
+                format!(
+                    "the migration to schema version {} ({}) failed{backup_hint}",
+                    migration.version, migration.description
+                )
+            });
+        }
+        schema_version.insert(&(), &migration.version)?;
+    }
+    Ok(())
+}
+
+/// Checks that the database has the schema version of the last of the `migrations`, without
+/// modifying it.
+pub(super) fn ensure_schema_version(
+    database: &Arc<RocksDB>,
+    migrations: &[Migration],
+) -> anyhow::Result<()> {
+    let schema_version: DBMap<(), u32> = DBMap::reopen(
+        database,
+        Some(schema_version_cf_name()),
+        &ReadWriteOptions::default(),
+        false,
+    )?;
+    let latest_version = migrations.last().map_or(0, |migration| migration.version);
+    let stored_version = schema_version.get(&())?.unwrap_or(0);
+    anyhow::ensure!(
+        stored_version == latest_version,
+        "the database has schema version {stored_version}, but this version of the node requires \
+        version {latest_version}; start the node once to migrate the database"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+    use crate::node::storage::tests::empty_storage;
+
+    static ROLLED_BACK: AtomicBool = AtomicBool::new(false);
+
+    #[test]
+    fn migrations_are_ordered_and_end_at_current_version() {
+        for (migrations, current_version) in [
+            (MIGRATIONS, CURRENT_SCHEMA_VERSION),
+            (
+                SHARD_DATABASE_MIGRATIONS,
+                CURRENT_SHARD_DATABASE_SCHEMA_VERSION,
+            ),
+        ] {
+            assert!(migrations
+                .windows(2)
+                .all(|pair| pair[0].version < pair[1].version));
+            assert_eq!(
+                migrations.last().map(|migration| migration.version),
+                Some(current_version)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_pending_migrations_and_rolls_back_failures() -> TestResult {
+        let storage = empty_storage().await;
+        let database = &storage.as_ref().database;
+        let schema_version: DBMap<(), u32> = DBMap::reopen(
+            database,
+            Some(schema_version_cf_name()),
+            &ReadWriteOptions::default(),
+            false,
+        )?;
+        assert_eq!(schema_version.get(&())?, Some(CURRENT_SCHEMA_VERSION));
+
+        // Simulate a database created before the schema was versioned.
+        schema_version.remove(&())?;
+        let backup_dir = tempfile::tempdir()?;
+        let migrations = [
+            Migration {
+                version: 1,
+                description: "succeeds",
+                migrate: |_, _| Ok(()),
+                rollback: None,
+            },
+            Migration {
+                version: 2,
+                description: "fails",
+                migrate: |_, _| Err(TypedStoreError::RocksDBError("failure".to_owned())),
+                rollback: Some(|_, _| {
+                    ROLLED_BACK.store(true, Ordering::SeqCst);
+                    Ok(())
+                }),
+            },
+        ];
+        let error = run_migrations(database, &migrations, &[], false, Some(backup_dir.path()))
+            .expect_err("the second migration fails");
+        assert!(format!("{error:#}").contains("schema version 2 (fails)"));
+        assert!(ROLLED_BACK.load(Ordering::SeqCst));
+        assert_eq!(schema_version.get(&())?, Some(1));
+        assert_eq!(std::fs::read_dir(backup_dir.path())?.count(), 1);
+
+        // Newer schema versions are rejected.
+        assert!(run_migrations(database, &migrations[..0], &[], false, None).is_err());
+
+        Ok(())
+    }
+}
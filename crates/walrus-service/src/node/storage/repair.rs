@@ -0,0 +1,437 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline repair of the storage of a stopped node.
+//!
+//! The repair runs in three steps. First, RocksDB's repair is run on the main database and on the
+//! separate databases of shards, which recovers the databases after unclean shutdowns or lost
+//! files. Then, every entry is decoded with the key and value types of its column family, and the
+//! entries that cannot be decoded are moved to a quarantine file. Finally, the indexes that are
+//! derived from other tables are rebuilt, such that the node treats the quarantined data as
+//! missing.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write as _},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use bincode::Options as _;
+use prometheus::Registry;
+use rocksdb::{ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, DB};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use sui_types::base_types::ObjectID;
+use typed_store::{rocks::MetricConf, Map as _};
+use walrus_core::{BlobId, BlobMetadata, ShardIndex};
+
+use super::{
+    blob_info::{BlobInfo, BlobInfoApi, PerObjectBlobInfo},
+    constants::{
+        aggregate_blob_info_cf_name,
+        dead_letter_events_cf_name,
+        event_cursor_cf_name,
+        event_index_cf_name,
+        metadata_cf_name,
+        node_status_cf_name,
+        per_object_blob_info_cf_name,
+        pinned_blobs_cf_name,
+        schema_version_cf_name,
+    },
+    database_options,
+    event_cursor_table::EventIdWithProgress,
+    main_column_families,
+    shard_column_families,
+    shard_database_column_families,
+    DatabaseConfig,
+    DeadLetterEvent,
+    NodeStatus,
+    PinnedBlobInfo,
+    ShardStorage,
+    Storage,
+    SHARD_DATABASES_DIRECTORY,
+};
+
+/// Checks that the key and value of an entry of a column family can be decoded.
+pub(super) type EntryDecoder = fn(&[u8], &[u8]) -> anyhow::Result<()>;
+
+/// Checks that the key and value can be decoded as `K` and `V`, with the encodings used by
+/// `DBMap`.
+pub(super) fn decode_entry<K, V>(key: &[u8], value: &[u8]) -> anyhow::Result<()>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    bincode::DefaultOptions::new()
+        .with_big_endian()
+        .with_fixint_encoding()
+        .deserialize::<K>(key)
+        .context("the key cannot be decoded")?;
+    bcs::from_bytes::<V>(value).context("the value cannot be decoded")?;
+    Ok(())
+}
+
+/// An entry that could not be decoded, as written to the quarantine file.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantinedEntry {
+    /// The path of the database containing the entry.
+    pub database: PathBuf,
+    /// The column family containing the entry.
+    pub column_family: String,
+    /// The raw key of the entry.
+    #[serde_as(as = "Base64")]
+    pub key: Vec<u8>,
+    /// The raw value of the entry.
+    #[serde_as(as = "Base64")]
+    pub value: Vec<u8>,
+    /// The reason why the entry could not be decoded.
+    pub error: String,
+}
+
+/// The outcome of [`repair_storage`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairReport {
+    /// The databases on which RocksDB's repair was run.
+    pub repaired_databases: Vec<PathBuf>,
+    /// The number of entries that were checked.
+    pub checked_entries: u64,
+    /// The number of entries moved to the quarantine file, per column family.
+    pub quarantined_entries: BTreeMap<String, u64>,
+    /// The number of blobs whose blob info wrongly recorded whether their metadata is stored.
+    pub corrected_metadata_flags: u64,
+    /// The number of hot slivers that were added to the index of last access times.
+    pub added_sliver_access_entries: u64,
+    /// The number of slivers no longer in the hot tier that were removed from the index of last
+    /// access times.
+    pub removed_sliver_access_entries: u64,
+}
+
+/// Repairs the storage database at `path`, appending the entries that cannot be decoded to the
+/// file at `quarantine_path`.
+///
+/// The storage node must be stopped during the repair.
+pub async fn repair_storage(
+    path: &Path,
+    db_config: &DatabaseConfig,
+    quarantine_path: &Path,
+) -> anyhow::Result<RepairReport> {
+    let mut report = RepairReport::default();
+    let mut quarantine = BufWriter::new(
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(quarantine_path)
+            .with_context(|| {
+                format!(
+                    "unable to open the quarantine file {}",
+                    quarantine_path.display()
+                )
+            })?,
+    );
+
+    let db_options = database_options(db_config);
+    tracing::info!(path = %path.display(), "repairing the main database");
+    DB::repair(&db_options, path)?;
+    report.repaired_databases.push(path.to_owned());
+    let shard_ids = ShardStorage::existing_cf_shards_ids(path, &db_options);
+    let mut decoders = main_entry_decoders();
+    let mut column_families: HashMap<String, Options> = main_column_families(db_config)
+        .into_iter()
+        .map(|(name, options)| (name.to_owned(), options))
+        .collect();
+    for &id in &shard_ids {
+        ShardStorage::add_entry_decoders(id, &mut decoders);
+        column_families.extend(shard_column_families(id, db_config));
+    }
+    quarantine_undecodable_entries(
+        path,
+        &db_options,
+        column_families,
+        &decoders,
+        &mut quarantine,
+        &mut report,
+    )?;
+
+    for (id, shard_path) in shard_database_paths(&path.join(SHARD_DATABASES_DIRECTORY))? {
+        tracing::info!(walrus.shard_index = %id, "repairing the shard database");
+        DB::repair(&db_options, &shard_path)?;
+        report.repaired_databases.push(shard_path.clone());
+        let mut decoders: HashMap<String, EntryDecoder> = HashMap::new();
+        ShardStorage::add_entry_decoders(id, &mut decoders);
+        decoders.insert(schema_version_cf_name().to_owned(), decode_entry::<(), u32>);
+        quarantine_undecodable_entries(
+            &shard_path,
+            &db_options,
+            shard_database_column_families(id, db_config)
+                .into_iter()
+                .collect(),
+            &decoders,
+            &mut quarantine,
+            &mut report,
+        )?;
+    }
+
+    let storage = Storage::open(
+        path,
+        db_config.clone(),
+        MetricConf::default(),
+        Registry::new(),
+    )?;
+    report.corrected_metadata_flags = correct_metadata_flags(&storage)?;
+    for shard in storage.existing_shard_storages().await {
+        let (added, removed) = shard.rebuild_sliver_last_access()?;
+        report.added_sliver_access_entries += added;
+        report.removed_sliver_access_entries += removed;
+    }
+    Ok(report)
+}
+
+/// Returns the decoders of the entries of the column families of the main database that do not
+/// belong to individual shards.
+fn main_entry_decoders() -> HashMap<String, EntryDecoder> {
+    let mut decoders: HashMap<String, EntryDecoder> = HashMap::new();
+    decoders.insert(
+        node_status_cf_name().to_owned(),
+        decode_entry::<(), NodeStatus>,
+    );
+    decoders.insert(
+        metadata_cf_name().to_owned(),
+        decode_entry::<BlobId, BlobMetadata>,
+    );
+    decoders.insert(
+        pinned_blobs_cf_name().to_owned(),
+        decode_entry::<BlobId, PinnedBlobInfo>,
+    );
+    decoders.insert(
+        dead_letter_events_cf_name().to_owned(),
+        decode_entry::<u64, DeadLetterEvent>,
+    );
+    decoders.insert(
+        event_cursor_cf_name().to_owned(),
+        decode_entry::<[u8; 6], EventIdWithProgress>,
+    );
+    decoders.insert(
+        aggregate_blob_info_cf_name().to_owned(),
+        decode_entry::<BlobId, BlobInfo>,
+    );
+    decoders.insert(
+        per_object_blob_info_cf_name().to_owned(),
+        decode_entry::<ObjectID, PerObjectBlobInfo>,
+    );
+    decoders.insert(event_index_cf_name().to_owned(), decode_entry::<(), u64>);
+    decoders.insert(schema_version_cf_name().to_owned(), decode_entry::<(), u32>);
+    decoders
+}
+
+/// Returns the indices and paths of the separate databases of shards in the directory.
+///
+/// Unlike when opening the storage, databases that appear incomplete are kept, as they may only
+/// become readable after the repair.
+fn shard_database_paths(
+    shard_databases_path: &Path,
+) -> anyhow::Result<BTreeMap<ShardIndex, PathBuf>> {
+    let entries = match std::fs::read_dir(shard_databases_path) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(error) => return Err(error.into()),
+    };
+    let mut paths = BTreeMap::new();
+    for entry in entries {
+        let path = entry?.path();
+        if let Some(id) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse().ok())
+        {
+            paths.insert(ShardIndex(id), path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Moves the entries of the database that cannot be decoded to the quarantine file.
+///
+/// The column families in `column_families` are opened with the provided options, which include
+/// the merge operators needed to read them, and all others with the default options.
+fn quarantine_undecodable_entries(
+    path: &Path,
+    db_options: &Options,
+    mut column_families: HashMap<String, Options>,
+    decoders: &HashMap<String, EntryDecoder>,
+    quarantine: &mut BufWriter<File>,
+    report: &mut RepairReport,
+) -> anyhow::Result<()> {
+    let descriptors = DB::list_cf(db_options, path)?
+        .into_iter()
+        .map(|name| {
+            let options = column_families.remove(&name).unwrap_or_default();
+            ColumnFamilyDescriptor::new(name, options)
+        })
+        .collect::<Vec<_>>();
+    let db = DB::open_cf_descriptors(db_options, path, descriptors)?;
+
+    for (cf_name, decode) in decoders {
+        let Some(cf) = db.cf_handle(cf_name) else {
+            continue;
+        };
+        let mut batch = WriteBatch::default();
+        for entry in db.iterator_cf(cf, IteratorMode::Start) {
+            let (key, value) =
+                entry.with_context(|| format!("unable to read the column family {cf_name}"))?;
+            report.checked_entries += 1;
+            let Err(error) = decode(&key, &value) else {
+                continue;
+            };
+            tracing::warn!(column_family = %cf_name, ?error, "quarantining undecodable entry");
+            let entry = QuarantinedEntry {
+                database: path.to_owned(),
+                column_family: cf_name.clone(),
+                key: key.to_vec(),
+                value: value.to_vec(),
+                error: format!("{error:#}"),
+            };
+            serde_json::to_writer(&mut *quarantine, &entry)?;
+            writeln!(quarantine)?;
+            batch.delete_cf(cf, &key);
+            *report
+                .quarantined_entries
+                .entry(cf_name.clone())
+                .or_default() += 1;
+        }
+
+        if !batch.is_empty() {
+            // Only delete the entries once they are safely stored in the quarantine file.
+            quarantine.flush()?;
+            quarantine.get_ref().sync_data()?;
+            db.write(batch)?;
+        }
+    }
+    Ok(())
+}
+
+/// Corrects the blob info of blobs for which it wrongly records whether the metadata is stored.
+///
+/// Returns the number of corrected blobs.
+fn correct_metadata_flags(storage: &Storage) -> anyhow::Result<u64> {
+    let mut batch = storage.metadata.batch();
+    let mut corrected = 0;
+    for entry in storage.blob_info.safe_iter() {
+        let (blob_id, blob_info) = entry?;
+        let metadata_stored = storage.metadata.contains_key(&blob_id)?;
+        if blob_info.is_metadata_stored() != metadata_stored {
+            storage
+                .blob_info
+                .set_metadata_stored(&mut batch, &blob_id, metadata_stored)?;
+            corrected += 1;
+        }
+    }
+    batch.write()?;
+    Ok(corrected)
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::SliverType;
+    use walrus_test_utils::{Result as TestResult, WithTempDir};
+
+    use super::*;
+    use crate::{
+        node::storage::{
+            blob_info::{BlobInfoMergeOperand, BlobStatusChangeType},
+            constants::primary_slivers_column_family_name,
+            tests::{get_sliver, SHARD_INDEX},
+        },
+        test_utils::empty_storage_with_shards,
+    };
+
+    #[tokio::test]
+    async fn quarantines_undecodable_entries_and_rebuilds_indexes() -> TestResult {
+        let storage = empty_storage_with_shards(&[SHARD_INDEX]).await;
+        let metadata = walrus_core::test_utils::verified_blob_metadata();
+        let blob_id = *metadata.blob_id();
+        storage.as_ref().blob_info.merge_blob_info(
+            &blob_id,
+            &BlobInfoMergeOperand::new_change_for_testing(
+                BlobStatusChangeType::Register,
+                false,
+                1,
+                42,
+                walrus_sui::test_utils::event_id_for_testing(),
+            ),
+        )?;
+        storage.as_ref().put_verified_metadata(&metadata)?;
+        let shard = storage
+            .as_ref()
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        shard.put_sliver(&blob_id, &get_sliver(SliverType::Primary, 1))?;
+        drop(shard);
+        let WithTempDir { inner, temp_dir } = storage;
+        drop(inner);
+
+        let path = temp_dir.path();
+        let db_config = DatabaseConfig::default();
+        {
+            let mut column_families: HashMap<String, Options> = main_column_families(&db_config)
+                .into_iter()
+                .map(|(name, options)| (name.to_owned(), options))
+                .collect();
+            column_families.extend(shard_column_families(SHARD_INDEX, &db_config));
+            let descriptors = DB::list_cf(&Options::default(), path)?
+                .into_iter()
+                .map(|name| {
+                    let options = column_families.remove(&name).unwrap_or_default();
+                    ColumnFamilyDescriptor::new(name, options)
+                })
+                .collect::<Vec<_>>();
+            let db = DB::open_cf_descriptors(&Options::default(), path, descriptors)?;
+            let metadata_cf = db.cf_handle(metadata_cf_name()).expect("cf exists");
+            db.put_cf(metadata_cf, blob_id.0, b"not metadata")?;
+            let primary_cf = db
+                .cf_handle(&primary_slivers_column_family_name(SHARD_INDEX))
+                .expect("cf exists");
+            db.put_cf(primary_cf, b"short key", b"not a sliver")?;
+        }
+
+        let quarantine_path = path.join("quarantine.jsonl");
+        let report = repair_storage(path, &db_config, &quarantine_path).await?;
+        assert_eq!(
+            report.quarantined_entries,
+            BTreeMap::from([
+                (metadata_cf_name().to_owned(), 1),
+                (primary_slivers_column_family_name(SHARD_INDEX), 1),
+            ])
+        );
+        assert_eq!(report.corrected_metadata_flags, 1);
+        assert_eq!(report.added_sliver_access_entries, 1);
+        assert_eq!(report.removed_sliver_access_entries, 0);
+
+        let quarantined = std::fs::read_to_string(&quarantine_path)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<QuarantinedEntry>, _>>()?;
+        assert_eq!(quarantined.len(), 2);
+        assert!(quarantined
+            .iter()
+            .any(|entry| entry.value == b"not metadata".to_vec()));
+
+        let storage = Storage::open(path, db_config, MetricConf::default(), Registry::new())?;
+        assert_eq!(storage.get_metadata(&blob_id)?, None);
+        let blob_info = storage.get_blob_info(&blob_id)?.expect("blob info exists");
+        assert!(!blob_info.is_metadata_stored());
+        assert!(blob_info.is_registered(1));
+        let shard = storage
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        assert!(shard.get_sliver(&blob_id, SliverType::Primary)?.is_some());
+
+        Ok(())
+    }
+}
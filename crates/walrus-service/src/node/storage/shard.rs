@@ -5,11 +5,12 @@
 
 use core::fmt::{self, Display};
 use std::{
-    collections::HashSet,
-    ops::Bound::{Excluded, Unbounded},
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap, HashSet},
+    ops::Bound::{self, Excluded, Unbounded},
     path::Path,
     sync::{Arc, OnceLock},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use fastcrypto::traits::KeyPair;
@@ -17,7 +18,7 @@ use futures::{stream::FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use regex::Regex;
 use rocksdb::{Options, DB};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use typed_store::{
     rocks::{
         be_fix_int_ser as to_rocks_db_key,
@@ -44,10 +45,15 @@ use walrus_core::{
 use super::{
     blob_info::{BlobInfo, BlobInfoIterator},
     constants,
+    encryption::{encryption_error, EncryptedSliver, SliverCipher},
+    inspection::{self, ColumnFamilySize},
     metrics::{CommonDatabaseMetrics, Labels, OperationType},
+    repair::{decode_entry, EntryDecoder},
     DatabaseConfig,
+    SliverTieringConfig,
 };
 use crate::node::{
+    background_workers::BackgroundWorker,
     blob_retirement_notifier::ExecutionResultWithRetirementCheck,
     config::ShardSyncConfig,
     errors::SyncShardClientError,
@@ -63,6 +69,9 @@ struct ShardColumnFamilyNames {
     pending_recover_slivers: String,
     primary_slivers: String,
     secondary_slivers: String,
+    primary_slivers_cold: String,
+    secondary_slivers_cold: String,
+    sliver_last_access: String,
     shard_status: String,
     shard_sync_progress: String,
 }
@@ -78,11 +87,27 @@ impl ShardColumnFamilyNames {
 }
 
 impl ShardColumnFamilyNames {
+    fn all(&self) -> [&str; 8] {
+        [
+            &self.primary_slivers,
+            &self.secondary_slivers,
+            &self.primary_slivers_cold,
+            &self.secondary_slivers_cold,
+            &self.sliver_last_access,
+            &self.shard_status,
+            &self.shard_sync_progress,
+            &self.pending_recover_slivers,
+        ]
+    }
+
     fn new(id: ShardIndex) -> Self {
         Self {
             pending_recover_slivers: constants::pending_recover_slivers_column_family_name(id),
             primary_slivers: constants::primary_slivers_column_family_name(id),
             secondary_slivers: constants::secondary_slivers_column_family_name(id),
+            primary_slivers_cold: constants::primary_slivers_cold_column_family_name(id),
+            secondary_slivers_cold: constants::secondary_slivers_cold_column_family_name(id),
+            sliver_last_access: constants::sliver_last_access_column_family_name(id),
             shard_status: constants::shard_status_column_family_name(id),
             shard_sync_progress: constants::shard_sync_progress_column_family_name(id),
         }
@@ -118,6 +143,15 @@ impl Display for ShardStatus {
 }
 
 impl ShardStatus {
+    /// All variants of the enum.
+    pub const ALL: [ShardStatus; 5] = [
+        ShardStatus::None,
+        ShardStatus::Active,
+        ShardStatus::ActiveSync,
+        ShardStatus::ActiveRecover,
+        ShardStatus::LockedToMove,
+    ];
+
     pub fn is_owned_by_node(&self) -> bool {
         self != &ShardStatus::LockedToMove
     }
@@ -174,6 +208,7 @@ enum ShardLastSyncStatus {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrimarySliverData {
     V1(PrimarySliver),
+    Encrypted(EncryptedSliver),
 }
 
 impl From<PrimarySliver> for PrimarySliverData {
@@ -182,18 +217,11 @@ impl From<PrimarySliver> for PrimarySliverData {
     }
 }
 
-impl From<PrimarySliverData> for PrimarySliver {
-    fn from(data: PrimarySliverData) -> Self {
-        match data {
-            PrimarySliverData::V1(sliver) => sliver,
-        }
-    }
-}
-
 /// Secondary sliver data stored in the database.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SecondarySliverData {
     V1(SecondarySliver),
+    Encrypted(EncryptedSliver),
 }
 
 impl From<SecondarySliver> for SecondarySliverData {
@@ -202,10 +230,58 @@ impl From<SecondarySliver> for SecondarySliverData {
     }
 }
 
-impl From<SecondarySliverData> for SecondarySliver {
-    fn from(data: SecondarySliverData) -> Self {
-        match data {
-            SecondarySliverData::V1(sliver) => sliver,
+/// Sliver data stored in the database, which holds the sliver either in plain text or encrypted.
+trait StoredSliverData: Sized {
+    type Sliver: Clone + Serialize + DeserializeOwned;
+
+    const SLIVER_TYPE: SliverType;
+
+    fn encrypted(encrypted: EncryptedSliver) -> Self;
+
+    fn plain(sliver: Self::Sliver) -> Self;
+
+    /// Returns the plain-text sliver, or the encrypted sliver if it is encrypted.
+    fn into_inner(self) -> Result<Self::Sliver, EncryptedSliver>;
+}
+
+impl StoredSliverData for PrimarySliverData {
+    type Sliver = PrimarySliver;
+
+    const SLIVER_TYPE: SliverType = SliverType::Primary;
+
+    fn encrypted(encrypted: EncryptedSliver) -> Self {
+        Self::Encrypted(encrypted)
+    }
+
+    fn plain(sliver: Self::Sliver) -> Self {
+        Self::V1(sliver)
+    }
+
+    fn into_inner(self) -> Result<Self::Sliver, EncryptedSliver> {
+        match self {
+            Self::V1(sliver) => Ok(sliver),
+            Self::Encrypted(encrypted) => Err(encrypted),
+        }
+    }
+}
+
+impl StoredSliverData for SecondarySliverData {
+    type Sliver = SecondarySliver;
+
+    const SLIVER_TYPE: SliverType = SliverType::Secondary;
+
+    fn encrypted(encrypted: EncryptedSliver) -> Self {
+        Self::Encrypted(encrypted)
+    }
+
+    fn plain(sliver: Self::Sliver) -> Self {
+        Self::V1(sliver)
+    }
+
+    fn into_inner(self) -> Result<Self::Sliver, EncryptedSliver> {
+        match self {
+            Self::V1(sliver) => Ok(sliver),
+            Self::Encrypted(encrypted) => Err(encrypted),
         }
     }
 }
@@ -216,10 +292,20 @@ pub struct ShardStorage {
     shard_status: DBMap<(), ShardStatus>,
     primary_slivers: DBMap<BlobId, PrimarySliverData>,
     secondary_slivers: DBMap<BlobId, SecondarySliverData>,
+    /// The cold tier of the primary slivers, which is compressed and read less frequently.
+    primary_slivers_cold: DBMap<BlobId, PrimarySliverData>,
+    /// The cold tier of the secondary slivers, which is compressed and read less frequently.
+    secondary_slivers_cold: DBMap<BlobId, SecondarySliverData>,
+    /// The time of the last write or read, in seconds since the Unix epoch, of the slivers in the
+    /// hot tier. Only maintained if sliver tiering is enabled.
+    sliver_last_access: DBMap<(SliverType, BlobId), u64>,
     shard_sync_progress: DBMap<(), ShardSyncProgress>,
     pending_recover_slivers: DBMap<(SliverType, BlobId), ()>,
     metrics: ShardMetrics,
     cf_names: Arc<ShardColumnFamilyNames>,
+    tiering: SliverTieringConfig,
+    /// The cipher used to encrypt slivers at rest, if storage encryption is enabled.
+    cipher: Option<Arc<SliverCipher>>,
 }
 
 macro_rules! reopen_cf {
@@ -242,6 +328,7 @@ impl ShardStorage {
         id: ShardIndex,
         database: &Arc<RocksDB>,
         db_config: &DatabaseConfig,
+        cipher: Option<Arc<SliverCipher>>,
         initial_shard_status: Option<ShardStatus>,
         registry: &Registry,
     ) -> Result<Self, TypedStoreError> {
@@ -260,6 +347,7 @@ impl ShardStorage {
             id,
             database,
             db_config,
+            cipher,
             initial_shard_status,
             metrics.clone(),
         );
@@ -276,6 +364,7 @@ impl ShardStorage {
         id: ShardIndex,
         database: &Arc<RocksDB>,
         db_config: &DatabaseConfig,
+        cipher: Option<Arc<SliverCipher>>,
         initial_shard_status: Option<ShardStatus>,
         metrics: ShardMetrics,
     ) -> Result<Self, TypedStoreError> {
@@ -307,6 +396,31 @@ impl ShardStorage {
             rw_options
         );
 
+        let primary_slivers_cold = reopen_cf!(
+            (
+                &cf_names.primary_slivers_cold,
+                cold_slivers_column_family_options(db_config),
+            ),
+            database,
+            rw_options
+        );
+        let secondary_slivers_cold = reopen_cf!(
+            (
+                &cf_names.secondary_slivers_cold,
+                cold_slivers_column_family_options(db_config),
+            ),
+            database,
+            rw_options
+        );
+        let sliver_last_access = reopen_cf!(
+            (
+                &cf_names.sliver_last_access,
+                sliver_last_access_column_family_options(db_config),
+            ),
+            database,
+            rw_options
+        );
+
         // Make sure that sliver column families are created last. They are used to identify
         // whether the shard storage is initialized in `existing_cf_shards_ids`.
         let primary_slivers = reopen_cf!(
@@ -335,10 +449,15 @@ impl ShardStorage {
             shard_status,
             primary_slivers,
             secondary_slivers,
+            primary_slivers_cold,
+            secondary_slivers_cold,
+            sliver_last_access,
             shard_sync_progress,
             pending_recover_slivers,
             metrics,
             cf_names: Arc::new(cf_names),
+            tiering: db_config.sliver_tiering.clone(),
+            cipher,
         })
     }
 
@@ -357,14 +476,18 @@ impl ShardStorage {
             ..Default::default()
         };
 
+        let mut batch = self.primary_slivers.batch();
         let response = match sliver {
             Sliver::Primary(primary) => self
-                .primary_slivers
-                .insert(blob_id, &PrimarySliverData::from(primary.clone())),
+                .encode_sliver::<PrimarySliverData>(blob_id, primary)
+                .and_then(|data| batch.insert_batch(&self.primary_slivers, [(blob_id, data)])),
             Sliver::Secondary(secondary) => self
-                .secondary_slivers
-                .insert(blob_id, &SecondarySliverData::from(secondary.clone())),
-        };
+                .encode_sliver::<SecondarySliverData>(blob_id, secondary)
+                .and_then(|data| batch.insert_batch(&self.secondary_slivers, [(blob_id, data)])),
+        }
+        .map(|_| ())
+        .and_then(|()| self.record_sliver_access(&mut batch, sliver.r#type(), blob_id))
+        .and_then(|()| batch.write());
 
         self.metrics
             .observe_operation_duration(labels.with_response(response.as_ref()), start.elapsed());
@@ -407,10 +530,8 @@ impl ShardStorage {
             ..Labels::default()
         };
 
-        let response = self
-            .primary_slivers
-            .get(blob_id)
-            .map(|s| s.map(|s| s.into()));
+        let response =
+            self.get_sliver_from_tiers(&self.primary_slivers, &self.primary_slivers_cold, blob_id);
 
         self.metrics
             .observe_operation_duration(labels.with_response(response.as_ref()), start.elapsed());
@@ -432,10 +553,11 @@ impl ShardStorage {
             ..Labels::default()
         };
 
-        let response = self
-            .secondary_slivers
-            .get(blob_id)
-            .map(|s| s.map(|s| s.into()));
+        let response = self.get_sliver_from_tiers(
+            &self.secondary_slivers,
+            &self.secondary_slivers_cold,
+            blob_id,
+        );
 
         self.metrics
             .observe_operation_duration(labels.with_response(response.as_ref()), start.elapsed());
@@ -472,10 +594,7 @@ impl ShardStorage {
             ..Labels::default()
         };
 
-        let response = match type_ {
-            SliverType::Primary => self.primary_slivers.contains_key(blob_id),
-            SliverType::Secondary => self.secondary_slivers.contains_key(blob_id),
-        };
+        let response = self.contains_sliver_in_any_tier(blob_id, type_);
 
         self.metrics
             .observe_operation_duration(labels.with_response(response.as_ref()), start.elapsed());
@@ -483,6 +602,28 @@ impl ShardStorage {
         response
     }
 
+    /// Returns the number of bytes stored for the sliver in either tier, or `None` if it is not
+    /// stored.
+    ///
+    /// The size is that of the stored value, which is neither deserialized nor decrypted.
+    pub(crate) fn stored_sliver_bytes(
+        &self,
+        blob_id: &BlobId,
+        sliver_type: SliverType,
+    ) -> Result<Option<u64>, TypedStoreError> {
+        let value = match sliver_type {
+            SliverType::Primary => match self.primary_slivers.get_raw_bytes(blob_id)? {
+                Some(value) => Some(value),
+                None => self.primary_slivers_cold.get_raw_bytes(blob_id)?,
+            },
+            SliverType::Secondary => match self.secondary_slivers.get_raw_bytes(blob_id)? {
+                Some(value) => Some(value),
+                None => self.secondary_slivers_cold.get_raw_bytes(blob_id)?,
+            },
+        };
+        Ok(value.map(|value| value.len() as u64))
+    }
+
     /// Deletes the sliver pair for the given [`BlobId`].
     #[tracing::instrument(skip_all, fields(walrus.shard_index = %self.id), err)]
     pub(crate) fn delete_sliver_pair(
@@ -492,9 +633,400 @@ impl ShardStorage {
     ) -> Result<(), TypedStoreError> {
         batch.delete_batch(&self.primary_slivers, std::iter::once(blob_id))?;
         batch.delete_batch(&self.secondary_slivers, std::iter::once(blob_id))?;
+        batch.delete_batch(&self.primary_slivers_cold, std::iter::once(blob_id))?;
+        batch.delete_batch(&self.secondary_slivers_cold, std::iter::once(blob_id))?;
+        batch.delete_batch(
+            &self.sliver_last_access,
+            [
+                (SliverType::Primary, *blob_id),
+                (SliverType::Secondary, *blob_id),
+            ],
+        )?;
         Ok(())
     }
 
+    /// Deletes the sliver of the given type from both tiers, such that it is considered missing and
+    /// is recovered again.
+    ///
+    /// This is used to discard slivers that were found to be corrupted.
+    #[tracing::instrument(skip_all, fields(walrus.shard_index = %self.id), err)]
+    pub(crate) fn discard_sliver(
+        &self,
+        blob_id: &BlobId,
+        sliver_type: SliverType,
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = self.sliver_last_access.batch();
+        match sliver_type {
+            SliverType::Primary => {
+                batch.delete_batch(&self.primary_slivers, [blob_id])?;
+                batch.delete_batch(&self.primary_slivers_cold, [blob_id])?;
+            }
+            SliverType::Secondary => {
+                batch.delete_batch(&self.secondary_slivers, [blob_id])?;
+                batch.delete_batch(&self.secondary_slivers_cold, [blob_id])?;
+            }
+        }
+        batch.delete_batch(&self.sliver_last_access, [(sliver_type, *blob_id)])?;
+        batch.write()
+    }
+
+    /// Returns true if the sliver is stored in either the hot or the cold tier.
+    fn contains_sliver_in_any_tier(
+        &self,
+        blob_id: &BlobId,
+        sliver_type: SliverType,
+    ) -> Result<bool, TypedStoreError> {
+        match sliver_type {
+            SliverType::Primary => Ok(self.primary_slivers.contains_key(blob_id)?
+                || self.primary_slivers_cold.contains_key(blob_id)?),
+            SliverType::Secondary => Ok(self.secondary_slivers.contains_key(blob_id)?
+                || self.secondary_slivers_cold.contains_key(blob_id)?),
+        }
+    }
+
+    /// Reads a sliver from the hot tier, falling back to the cold tier.
+    ///
+    /// Slivers found in the cold tier are moved back to the hot tier.
+    fn get_sliver_from_tiers<D>(
+        &self,
+        hot: &DBMap<BlobId, D>,
+        cold: &DBMap<BlobId, D>,
+        blob_id: &BlobId,
+    ) -> Result<Option<D::Sliver>, TypedStoreError>
+    where
+        D: StoredSliverData + Serialize + DeserializeOwned,
+    {
+        let sliver_type = D::SLIVER_TYPE;
+        if let Some(value) = hot.get(blob_id)? {
+            if self.tiering.enabled {
+                self.sliver_last_access
+                    .insert(&(sliver_type, *blob_id), &unix_time_secs())?;
+            }
+            return self.decode_sliver(blob_id, value).map(Some);
+        }
+        let Some(value) = cold.get(blob_id)? else {
+            return Ok(None);
+        };
+
+        tracing::debug!(walrus.blob_id = %blob_id, %sliver_type, "promoting cold sliver");
+        let mut batch = hot.batch();
+        batch.insert_batch(hot, [(blob_id, &value)])?;
+        batch.delete_batch(cold, [blob_id])?;
+        self.record_sliver_access(&mut batch, sliver_type, blob_id)?;
+        batch.write()?;
+        self.decode_sliver(blob_id, value).map(Some)
+    }
+
+    /// Returns the data to store for the sliver, which is encrypted if storage encryption is
+    /// enabled.
+    fn encode_sliver<D: StoredSliverData>(
+        &self,
+        blob_id: &BlobId,
+        sliver: &D::Sliver,
+    ) -> Result<D, TypedStoreError> {
+        match &self.cipher {
+            Some(cipher) => Ok(D::encrypted(cipher.encrypt(
+                blob_id,
+                D::SLIVER_TYPE,
+                sliver,
+            )?)),
+            None => Ok(D::plain(sliver.clone())),
+        }
+    }
+
+    /// Returns the sliver held by the stored data, decrypting it if necessary.
+    fn decode_sliver<D: StoredSliverData>(
+        &self,
+        blob_id: &BlobId,
+        data: D,
+    ) -> Result<D::Sliver, TypedStoreError> {
+        match data.into_inner() {
+            Ok(sliver) => Ok(sliver),
+            Err(encrypted) => self
+                .cipher
+                .as_ref()
+                .ok_or_else(|| {
+                    encryption_error("the sliver is encrypted, but no encryption key is configured")
+                })?
+                .decrypt(blob_id, D::SLIVER_TYPE, encrypted),
+        }
+    }
+
+    /// Adds an update of the last access time of the sliver to the batch, if tiering is enabled.
+    fn record_sliver_access(
+        &self,
+        batch: &mut DBBatch,
+        sliver_type: SliverType,
+        blob_id: &BlobId,
+    ) -> Result<(), TypedStoreError> {
+        if self.tiering.enabled {
+            batch.insert_batch(
+                &self.sliver_last_access,
+                [((sliver_type, *blob_id), unix_time_secs())],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the slivers of the specified type stored in either tier.
+    pub(crate) fn sliver_entries(
+        &self,
+        sliver_type: SliverType,
+    ) -> Box<dyn Iterator<Item = Result<(BlobId, Sliver), TypedStoreError>> + '_> {
+        match sliver_type {
+            SliverType::Primary => Box::new(
+                self.primary_slivers
+                    .safe_iter()
+                    .chain(self.primary_slivers_cold.safe_iter())
+                    .map(|entry| {
+                        let (blob_id, data) = entry?;
+                        Ok((
+                            blob_id,
+                            Sliver::Primary(self.decode_sliver(&blob_id, data)?),
+                        ))
+                    }),
+            ),
+            SliverType::Secondary => Box::new(
+                self.secondary_slivers
+                    .safe_iter()
+                    .chain(self.secondary_slivers_cold.safe_iter())
+                    .map(|entry| {
+                        let (blob_id, data) = entry?;
+                        Ok((
+                            blob_id,
+                            Sliver::Secondary(self.decode_sliver(&blob_id, data)?),
+                        ))
+                    }),
+            ),
+        }
+    }
+
+    /// Returns RocksDB's estimates of the number of slivers of the specified type stored in the
+    /// shard, and of their total size in bytes, across both storage tiers.
+    pub(crate) fn estimated_sliver_count_and_bytes(
+        &self,
+        sliver_type: SliverType,
+    ) -> Result<(u64, u64), TypedStoreError> {
+        let cf_names = match sliver_type {
+            SliverType::Primary => [
+                &self.cf_names.primary_slivers,
+                &self.cf_names.primary_slivers_cold,
+            ],
+            SliverType::Secondary => [
+                &self.cf_names.secondary_slivers,
+                &self.cf_names.secondary_slivers_cold,
+            ],
+        };
+
+        let mut count = 0;
+        let mut bytes = 0;
+        for cf_name in cf_names {
+            count += self.int_property(cf_name, "rocksdb.estimate-num-keys")?;
+            bytes += self.int_property(cf_name, "rocksdb.estimate-live-data-size")?;
+            bytes += self.int_property(cf_name, "rocksdb.live-blob-file-size")?;
+        }
+        Ok((count, bytes))
+    }
+
+    /// Returns the value of the integer RocksDB property for the column family, or 0 if the column
+    /// family or property does not exist.
+    fn int_property(&self, cf_name: &str, property: &str) -> Result<u64, TypedStoreError> {
+        inspection::int_property(&self.primary_slivers.rocksdb, cf_name, property)
+    }
+
+    /// Returns up to `limit` IDs of blobs with at least one sliver stored in the shard, in
+    /// ascending order, starting after the blob ID `after`, if provided.
+    pub(crate) fn blob_ids(
+        &self,
+        after: Option<BlobId>,
+        limit: usize,
+    ) -> Result<Vec<BlobId>, TypedStoreError> {
+        let lower_bound = after.map_or(Unbounded, Excluded);
+        // Each table is sorted, so the first `limit` blob IDs of the union are among the first
+        // `limit` blob IDs of each table.
+        let mut blob_ids = BTreeSet::new();
+        for keys in [
+            Self::first_keys(&self.primary_slivers, lower_bound, limit),
+            Self::first_keys(&self.primary_slivers_cold, lower_bound, limit),
+            Self::first_keys(&self.secondary_slivers, lower_bound, limit),
+            Self::first_keys(&self.secondary_slivers_cold, lower_bound, limit),
+        ] {
+            blob_ids.extend(keys?);
+        }
+        Ok(blob_ids.into_iter().take(limit).collect())
+    }
+
+    fn first_keys<V>(
+        table: &DBMap<BlobId, V>,
+        lower_bound: Bound<BlobId>,
+        limit: usize,
+    ) -> Result<Vec<BlobId>, TypedStoreError>
+    where
+        V: Serialize + DeserializeOwned,
+    {
+        table
+            .safe_range_iter((lower_bound, Unbounded))
+            .take(limit)
+            .map(|entry| entry.map(|(blob_id, _)| blob_id))
+            .collect()
+    }
+
+    /// Returns RocksDB's estimates of the sizes of the column families of the shard.
+    pub(crate) fn column_family_sizes(&self) -> Result<Vec<ColumnFamilySize>, TypedStoreError> {
+        let rocksdb = &self.primary_slivers.rocksdb;
+        let mut sizes = vec![];
+        for cf_name in self.cf_names.all() {
+            sizes.extend(ColumnFamilySize::read(rocksdb, cf_name)?);
+        }
+        Ok(sizes)
+    }
+
+    /// Moves up to `limit` slivers that were last accessed before `accessed_before`, in seconds
+    /// since the Unix epoch, from the hot to the cold tier.
+    ///
+    /// Returns the number of slivers that were moved.
+    #[tracing::instrument(skip_all, fields(walrus.shard_index = %self.id), err)]
+    pub(crate) fn demote_cold_slivers(
+        &self,
+        accessed_before: u64,
+        limit: usize,
+    ) -> Result<usize, TypedStoreError> {
+        let candidates = self
+            .sliver_last_access
+            .safe_iter()
+            .filter_map(|entry| match entry {
+                Ok((key, last_access)) => (last_access < accessed_before).then_some(Ok(key)),
+                Err(error) => Some(Err(error)),
+            })
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut demoted = 0;
+        for (sliver_type, blob_id) in candidates {
+            // Write each sliver in a separate batch to bound the size of the batches.
+            let mut batch = self.sliver_last_access.batch();
+            let moved = match sliver_type {
+                SliverType::Primary => Self::move_sliver(
+                    &mut batch,
+                    &self.primary_slivers,
+                    &self.primary_slivers_cold,
+                    &blob_id,
+                )?,
+                SliverType::Secondary => Self::move_sliver(
+                    &mut batch,
+                    &self.secondary_slivers,
+                    &self.secondary_slivers_cold,
+                    &blob_id,
+                )?,
+            };
+            batch.delete_batch(&self.sliver_last_access, [(sliver_type, blob_id)])?;
+            batch.write()?;
+            demoted += usize::from(moved);
+        }
+
+        if demoted > 0 {
+            tracing::debug!(demoted, "moved slivers to the cold tier");
+        }
+        Ok(demoted)
+    }
+
+    /// Adds moving the sliver from one tier to the other to the batch.
+    ///
+    /// Returns false if the sliver is not stored in the source tier.
+    fn move_sliver<V>(
+        batch: &mut DBBatch,
+        from: &DBMap<BlobId, V>,
+        to: &DBMap<BlobId, V>,
+        blob_id: &BlobId,
+    ) -> Result<bool, TypedStoreError>
+    where
+        V: Serialize + DeserializeOwned,
+    {
+        let Some(value) = from.get(blob_id)? else {
+            return Ok(false);
+        };
+        batch.insert_batch(to, [(blob_id, &value)])?;
+        batch.delete_batch(from, [blob_id])?;
+        Ok(true)
+    }
+
+    /// Rebuilds the index of the last access times of the hot slivers.
+    ///
+    /// Removes the entries of slivers that are no longer in the hot tier, and adds entries with the
+    /// current time for hot slivers without an entry. Returns the number of added and removed
+    /// entries.
+    #[tracing::instrument(skip_all, fields(walrus.shard_index = %self.id), err)]
+    pub(crate) fn rebuild_sliver_last_access(&self) -> Result<(u64, u64), TypedStoreError> {
+        let mut batch = self.sliver_last_access.batch();
+        let mut removed = 0;
+        for entry in self.sliver_last_access.safe_iter() {
+            let ((sliver_type, blob_id), _) = entry?;
+            let is_hot = match sliver_type {
+                SliverType::Primary => self.primary_slivers.contains_key(&blob_id)?,
+                SliverType::Secondary => self.secondary_slivers.contains_key(&blob_id)?,
+            };
+            if !is_hot {
+                batch.delete_batch(&self.sliver_last_access, [(sliver_type, blob_id)])?;
+                removed += 1;
+            }
+        }
+
+        let now = unix_time_secs();
+        let mut added = 0;
+        let hot_blob_ids = self
+            .primary_slivers
+            .safe_iter()
+            .map(|entry| entry.map(|(blob_id, _)| (SliverType::Primary, blob_id)))
+            .chain(
+                self.secondary_slivers
+                    .safe_iter()
+                    .map(|entry| entry.map(|(blob_id, _)| (SliverType::Secondary, blob_id))),
+            );
+        for entry in hot_blob_ids {
+            let key = entry?;
+            if !self.sliver_last_access.contains_key(&key)? {
+                batch.insert_batch(&self.sliver_last_access, [(key, now)])?;
+                added += 1;
+            }
+        }
+        batch.write()?;
+        Ok((added, removed))
+    }
+
+    /// Adds the decoders of the entries of the column families of the shard to `decoders`.
+    pub(super) fn add_entry_decoders(id: ShardIndex, decoders: &mut HashMap<String, EntryDecoder>) {
+        let cf_names = ShardColumnFamilyNames::new(id);
+        decoders.insert(
+            cf_names.primary_slivers,
+            decode_entry::<BlobId, PrimarySliverData>,
+        );
+        decoders.insert(
+            cf_names.primary_slivers_cold,
+            decode_entry::<BlobId, PrimarySliverData>,
+        );
+        decoders.insert(
+            cf_names.secondary_slivers,
+            decode_entry::<BlobId, SecondarySliverData>,
+        );
+        decoders.insert(
+            cf_names.secondary_slivers_cold,
+            decode_entry::<BlobId, SecondarySliverData>,
+        );
+        decoders.insert(
+            cf_names.sliver_last_access,
+            decode_entry::<(SliverType, BlobId), u64>,
+        );
+        decoders.insert(cf_names.shard_status, decode_entry::<(), ShardStatus>);
+        decoders.insert(
+            cf_names.shard_sync_progress,
+            decode_entry::<(), ShardSyncProgress>,
+        );
+        decoders.insert(
+            cf_names.pending_recover_slivers,
+            decode_entry::<(SliverType, BlobId), ()>,
+        );
+    }
+
     /// Returns the ids of existing shards that are fully initialized in the database at the
     /// provided path.
     pub(crate) fn existing_cf_shards_ids(path: &Path, options: &Options) -> HashSet<ShardIndex> {
@@ -557,11 +1089,25 @@ impl ShardStorage {
             }
         }
 
+        // Slivers read for shard sync are neither promoted nor recorded as accessed, as the
+        // access does not indicate that they are read frequently.
         let response = ByAxis::from(sliver_type)
             .map(
                 // TODO(#648): compare multi_get with scan for large value size.
-                |_| self.primary_slivers.multi_get(slivers_to_fetch),
-                |_| self.secondary_slivers.multi_get(slivers_to_fetch),
+                |_| {
+                    Self::multi_get_from_tiers(
+                        &self.primary_slivers,
+                        &self.primary_slivers_cold,
+                        slivers_to_fetch,
+                    )
+                },
+                |_| {
+                    Self::multi_get_from_tiers(
+                        &self.secondary_slivers,
+                        &self.secondary_slivers_cold,
+                        slivers_to_fetch,
+                    )
+                },
             )
             .transpose();
 
@@ -570,33 +1116,58 @@ impl ShardStorage {
             start.elapsed(),
         );
 
-        let output = match response? {
+        match response? {
             ByAxis::Primary(slivers) => slivers_to_fetch
                 .iter()
                 .zip(slivers)
                 .filter_map(|(&blob_id, sliver)| {
-                    let PrimarySliverData::V1(sliver) = sliver?;
-                    Some((blob_id, Sliver::Primary(sliver)))
+                    let sliver = self.decode_sliver(&blob_id, sliver?);
+                    Some(sliver.map(|sliver| (blob_id, Sliver::Primary(sliver))))
                 })
                 .collect(),
             ByAxis::Secondary(slivers) => slivers_to_fetch
                 .iter()
                 .zip(slivers)
                 .filter_map(|(&blob_id, sliver)| {
-                    let SecondarySliverData::V1(sliver) = sliver?;
-                    Some((blob_id, Sliver::Secondary(sliver)))
+                    let sliver = self.decode_sliver(&blob_id, sliver?);
+                    Some(sliver.map(|sliver| (blob_id, Sliver::Secondary(sliver))))
                 })
                 .collect(),
-        };
+        }
+    }
 
-        Ok(output)
+    /// Reads the values for the provided blob IDs from the hot tier, and those not found there from
+    /// the cold tier.
+    fn multi_get_from_tiers<V>(
+        hot: &DBMap<BlobId, V>,
+        cold: &DBMap<BlobId, V>,
+        blob_ids: &[BlobId],
+    ) -> Result<Vec<Option<V>>, TypedStoreError>
+    where
+        V: Serialize + DeserializeOwned,
+    {
+        let mut values = hot.multi_get(blob_ids)?;
+        let missing: Vec<_> = blob_ids
+            .iter()
+            .zip(&values)
+            .filter_map(|(blob_id, value)| value.is_none().then_some(*blob_id))
+            .collect();
+        if missing.is_empty() {
+            return Ok(values);
+        }
+
+        let mut cold_values = cold.multi_get(&missing)?.into_iter();
+        for value in values.iter_mut().filter(|value| value.is_none()) {
+            *value = cold_values.next().flatten();
+        }
+        Ok(values)
     }
 
     /// Syncs the shard to the current epoch from the previous shard owner.
     #[tracing::instrument(
         skip_all,
         fields(
-            walrus.node = %node.protocol_key_pair.as_ref().public(),
+            walrus.node = %node.protocol_signer.public_key(),
             walrus.shard_index = %self.id
         ),
         err
@@ -666,9 +1237,21 @@ impl ShardStorage {
             ShardLastSyncStatus::Recovery => {}
         }
 
-        self.recovery_any_missing_slivers(node, config, epoch)
+        self.recovery_any_missing_slivers(node.clone(), config, epoch)
             .await?;
 
+        if config.verify_shard_after_sync {
+            let missing_slivers = self.record_missing_slivers_after_sync(epoch, &node)?;
+            if missing_slivers > 0 {
+                tracing::warn!(
+                    missing_slivers,
+                    "shard verification found missing slivers; recovering them"
+                );
+                self.recovery_any_missing_slivers(node, config, epoch)
+                    .await?;
+            }
+        }
+
         let mut batch = self.shard_status.batch();
         batch.insert_batch(&self.shard_status, [((), ShardStatus::Active)])?;
         batch.delete_batch(&self.shard_sync_progress, [()])?;
@@ -677,6 +1260,31 @@ impl ShardStorage {
         Ok(())
     }
 
+    /// Returns a key for ordering shard syncs, such that shards that are closer to completing
+    /// their sync sort first.
+    pub(crate) fn sync_priority_key(&self) -> Result<(u8, Reverse<u16>), TypedStoreError> {
+        if self.status()? == ShardStatus::ActiveRecover {
+            // Only the missing slivers remain to be recovered.
+            return Ok((0, Reverse(u16::MAX)));
+        }
+        let key = match self.shard_sync_progress.get(&())? {
+            Some(ShardSyncProgress::V1(ShardSyncProgressV1 {
+                last_synced_blob_id,
+                sliver_type,
+            })) => {
+                // Blobs are synced in the order of their IDs, so the first bytes of the last
+                // synced blob ID approximate the progress.
+                let stage = match sliver_type {
+                    SliverType::Secondary => 1,
+                    SliverType::Primary => 2,
+                };
+                (stage, Reverse(last_synced_blob_id.first_two_bytes()))
+            }
+            None => (3, Reverse(0)),
+        };
+        Ok(key)
+    }
+
     /// Returns the last sync status for the shard.
     fn get_last_sync_status(
         &self,
@@ -762,6 +1370,9 @@ impl ShardStorage {
                 )
                 .set(next_starting_blob_id.first_two_bytes() as i64);
 
+                node.background_workers
+                    .wait_until_resumed(BackgroundWorker::ShardSync)
+                    .await;
                 let fetched_slivers = node
                     .committee_service
                     .sync_shard_before_epoch(
@@ -770,7 +1381,7 @@ impl ShardStorage {
                         sliver_type,
                         config.sliver_count_per_sync_request,
                         epoch,
-                        &node.protocol_key_pair,
+                        &node.protocol_signer,
                     )
                     .await?;
 
@@ -865,17 +1476,24 @@ impl ShardStorage {
                     assert_eq!(sliver_type, SliverType::Primary);
                     batch.insert_batch(
                         &self.primary_slivers,
-                        [(blob_id, &PrimarySliverData::from(primary.clone()))],
+                        [(
+                            blob_id,
+                            &self.encode_sliver::<PrimarySliverData>(blob_id, primary)?,
+                        )],
                     )?;
                 }
                 Sliver::Secondary(secondary) => {
                     assert_eq!(sliver_type, SliverType::Secondary);
                     batch.insert_batch(
                         &self.secondary_slivers,
-                        [(blob_id, &SecondarySliverData::from(secondary.clone()))],
+                        [(
+                            blob_id,
+                            &self.encode_sliver::<SecondarySliverData>(blob_id, secondary)?,
+                        )],
                     )?;
                 }
             }
+            self.record_sliver_access(batch, sliver_type, blob_id)?;
 
             next_blob_info = self.check_and_record_missing_blobs(
                 blob_info_iter,
@@ -936,6 +1554,57 @@ impl ShardStorage {
         Ok(next_blob_info)
     }
 
+    /// Checks that the shard stores both slivers of all blobs certified before `epoch`, and records
+    /// the missing slivers in the `pending_recover_slivers` table.
+    ///
+    /// Returns the number of missing slivers.
+    pub(crate) fn record_missing_slivers_after_sync(
+        &self,
+        epoch: Epoch,
+        node: &StorageNodeInner,
+    ) -> Result<usize, SyncShardClientError> {
+        let mut checked_blobs = 0;
+        let mut missing_slivers = 0;
+        let mut batch = self.pending_recover_slivers.batch();
+        for blob_info in node
+            .storage
+            .blob_info
+            .certified_blob_info_iter_before_epoch(epoch, Unbounded)
+        {
+            let (blob_id, _) = blob_info?;
+            checked_blobs += 1;
+            for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+                if self.contains_sliver_in_any_tier(&blob_id, sliver_type)? {
+                    continue;
+                }
+                batch.insert_batch(
+                    &self.pending_recover_slivers,
+                    [((sliver_type, blob_id), ())],
+                )?;
+                walrus_utils::with_label!(
+                    node.metrics.sync_shard_verify_missing_sliver_total,
+                    &self.id.to_string(),
+                    &sliver_type.to_string()
+                )
+                .inc();
+                missing_slivers += 1;
+            }
+        }
+        batch.write()?;
+
+        walrus_utils::with_label!(
+            node.metrics.shard_last_scrub_timestamp_seconds,
+            &self.id.to_string()
+        )
+        .set(i64::try_from(unix_time_secs()).unwrap_or(i64::MAX));
+        tracing::info!(
+            checked_blobs,
+            missing_slivers,
+            "verified slivers of synced shard"
+        );
+        Ok(missing_slivers)
+    }
+
     /// Recovers any missing blobs stored in `pending_recover_slivers` table.
     async fn recovery_any_missing_slivers(
         &self,
@@ -995,6 +1664,9 @@ impl ShardStorage {
 
         for recover_blob in self.pending_recover_slivers.safe_iter() {
             let ((sliver_type, blob_id), _) = recover_blob?;
+            node.background_workers
+                .wait_until_resumed(BackgroundWorker::ShardSync)
+                .await;
 
             #[allow(unused_mut)]
             let mut skip_certified_check_in_test = false;
@@ -1147,7 +1819,7 @@ impl ShardStorage {
                     }
                     Err(inconsistency_proof) => {
                         self.handle_inconsistency(&node, sliver_type, blob_id, inconsistency_proof)
-                            .await;
+                            .await?;
                     }
                 }
                 self.pending_recover_slivers
@@ -1178,14 +1850,14 @@ impl ShardStorage {
         self.put_sliver(&blob_id, &sliver)
     }
 
-    /// Handles the inconsistency of a blob.
+    /// Handles the inconsistency of a blob by invalidating it.
     async fn handle_inconsistency(
         &self,
         node: &Arc<StorageNodeInner>,
         sliver_type: SliverType,
         blob_id: BlobId,
         inconsistency_proof: InconsistencyProof,
-    ) {
+    ) -> Result<(), SyncShardClientError> {
         tracing::debug!("received an inconsistency proof when recovering sliver");
         walrus_utils::with_label!(
             node.metrics.sync_shard_recover_sliver_error_total,
@@ -1194,13 +1866,29 @@ impl ShardStorage {
         )
         .inc();
 
-        let invalid_blob_certificate = node
-            .committee_service
-            .get_invalid_blob_certificate(blob_id, &inconsistency_proof)
-            .await;
-        node.contract_service
-            .invalidate_blob_id(&invalid_blob_certificate)
-            .await
+        node.blob_invalidator
+            .invalidate(node, blob_id, &inconsistency_proof)
+            .await?;
+        Ok(())
+    }
+
+    /// Copies all data of the shard to the storage of the same shard in another database.
+    ///
+    /// Existing entries in the target storage are overwritten, so the copy can be repeated if it
+    /// was interrupted.
+    pub(crate) fn copy_into(&self, target: &ShardStorage) -> Result<(), TypedStoreError> {
+        copy_table(&self.shard_status, &target.shard_status)?;
+        copy_table(&self.shard_sync_progress, &target.shard_sync_progress)?;
+        copy_table(
+            &self.pending_recover_slivers,
+            &target.pending_recover_slivers,
+        )?;
+        copy_table(&self.sliver_last_access, &target.sliver_last_access)?;
+        copy_table(&self.primary_slivers_cold, &target.primary_slivers_cold)?;
+        copy_table(&self.secondary_slivers_cold, &target.secondary_slivers_cold)?;
+        copy_table(&self.primary_slivers, &target.primary_slivers)?;
+        copy_table(&self.secondary_slivers, &target.secondary_slivers)?;
+        Ok(())
     }
 
     /// Deletes the storage for the shard.
@@ -1214,6 +1902,15 @@ impl ShardStorage {
         rocksdb
             .drop_cf(&self.cf_names.primary_slivers)
             .map_err(typed_store_err_from_rocks_err)?;
+        rocksdb
+            .drop_cf(&self.cf_names.sliver_last_access)
+            .map_err(typed_store_err_from_rocks_err)?;
+        rocksdb
+            .drop_cf(&self.cf_names.secondary_slivers_cold)
+            .map_err(typed_store_err_from_rocks_err)?;
+        rocksdb
+            .drop_cf(&self.cf_names.primary_slivers_cold)
+            .map_err(typed_store_err_from_rocks_err)?;
         rocksdb
             .drop_cf(&self.cf_names.pending_recover_slivers)
             .map_err(typed_store_err_from_rocks_err)?;
@@ -1270,6 +1967,13 @@ impl ShardStorage {
         Ok(next_blob_info)
     }
 
+    /// Deletes the sliver pair for the given [`BlobId`] in a separate batch.
+    pub(crate) fn delete_sliver_pair_now(&self, blob_id: &BlobId) -> Result<(), TypedStoreError> {
+        let mut batch = self.primary_slivers.batch();
+        self.delete_sliver_pair(&mut batch, blob_id)?;
+        batch.write()
+    }
+
     #[cfg(test)]
     pub(crate) fn all_pending_recover_slivers(
         &self,
@@ -1301,6 +2005,33 @@ fn id_from_column_family_name(name: &str) -> Option<(ShardIndex, SliverType)> {
     })
 }
 
+/// The number of entries written per batch when copying the tables of a shard.
+const COPY_BATCH_SIZE: usize = 100;
+
+/// Copies all entries of the table to another table with the same types, in batches of
+/// [`COPY_BATCH_SIZE`] entries.
+fn copy_table<K, V>(from: &DBMap<K, V>, to: &DBMap<K, V>) -> Result<(), TypedStoreError>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    let mut entries = Vec::with_capacity(COPY_BATCH_SIZE);
+    for entry in from.safe_iter() {
+        entries.push(entry?);
+        if entries.len() == COPY_BATCH_SIZE {
+            let mut batch = to.batch();
+            batch.insert_batch(to, entries.drain(..))?;
+            batch.write()?;
+        }
+    }
+    if !entries.is_empty() {
+        let mut batch = to.batch();
+        batch.insert_batch(to, entries)?;
+        batch.write()?;
+    }
+    Ok(())
+}
+
 /// Returns the name and options for the column families for a shard's primary
 /// sliver with the specified index.
 pub fn primary_slivers_column_family_options(db_config: &DatabaseConfig) -> Options {
@@ -1313,6 +2044,24 @@ pub fn secondary_slivers_column_family_options(db_config: &DatabaseConfig) -> Op
     db_config.shard().to_options()
 }
 
+/// Returns the options for the column families for the cold tier of a shard's slivers.
+pub fn cold_slivers_column_family_options(db_config: &DatabaseConfig) -> Options {
+    db_config.cold_shard().to_options()
+}
+
+/// Returns the options for the column family for the last access times of a shard's slivers.
+pub fn sliver_last_access_column_family_options(db_config: &DatabaseConfig) -> Options {
+    db_config.sliver_last_access().to_options()
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+pub(super) fn unix_time_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 /// Returns the name and options for the column families for a shard's operating status
 /// with the specified index.
 pub fn shard_status_column_family_options(db_config: &DatabaseConfig) -> Options {
@@ -1357,6 +2106,7 @@ fn inject_failure(scan_count: u64, sliver_type: SliverType) -> Result<(), anyhow
 mod tests {
     use std::collections::HashMap;
 
+    use typed_store::rocks::MetricConf;
     use walrus_core::test_utils::random_blob_id;
     use walrus_sui::test_utils::event_id_for_testing;
     use walrus_test_utils::{async_param_test, param_test, Result as TestResult, WithTempDir};
@@ -1367,6 +2117,7 @@ mod tests {
             storage::{
                 blob_info::BlobCertificationStatus,
                 tests::{empty_storage, get_sliver, BLOB_ID, OTHER_SHARD_INDEX, SHARD_INDEX},
+                StorageEncryptionConfig,
             },
             Storage,
         },
@@ -1396,6 +2147,89 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn moves_slivers_between_hot_and_cold_tiers() -> TestResult {
+        let temp_dir = tempfile::tempdir()?;
+        let db_config = DatabaseConfig {
+            sliver_tiering: SliverTieringConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let storage = Storage::open(
+            temp_dir.path(),
+            db_config,
+            MetricConf::default(),
+            Registry::default(),
+        )?;
+        storage.create_storage_for_shards(&[SHARD_INDEX]).await?;
+        let shard = storage
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        let sliver = get_sliver(SliverType::Primary, 1);
+
+        shard.put_sliver(&BLOB_ID, &sliver)?;
+        assert_eq!(shard.demote_cold_slivers(0, usize::MAX)?, 0);
+
+        assert_eq!(shard.demote_cold_slivers(u64::MAX, usize::MAX)?, 1);
+        assert_eq!(shard.sliver_count(SliverType::Primary)?, 0);
+        assert!(shard.is_sliver_stored::<Primary>(&BLOB_ID)?);
+        assert_eq!(
+            shard.fetch_slivers(SliverType::Primary, &[BLOB_ID])?,
+            vec![(BLOB_ID, sliver.clone())]
+        );
+
+        assert_eq!(
+            shard.get_sliver(&BLOB_ID, SliverType::Primary)?,
+            Some(sliver)
+        );
+        assert_eq!(shard.sliver_count(SliverType::Primary)?, 1);
+        assert_eq!(shard.demote_cold_slivers(0, usize::MAX)?, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn encrypts_slivers_at_rest() -> TestResult {
+        let temp_dir = tempfile::tempdir()?;
+        let key_path = temp_dir.path().join("storage.key");
+        std::fs::write(&key_path, hex::encode([3u8; 32]))?;
+        let db_config = DatabaseConfig {
+            encryption: Some(StorageEncryptionConfig::KeyPath(key_path)),
+            ..Default::default()
+        };
+        let storage = Storage::open(
+            &temp_dir.path().join("db"),
+            db_config,
+            MetricConf::default(),
+            Registry::default(),
+        )?;
+        storage.create_storage_for_shards(&[SHARD_INDEX]).await?;
+        let shard = storage
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        let sliver = get_sliver(SliverType::Secondary, 1);
+
+        shard.put_sliver(&BLOB_ID, &sliver)?;
+        assert!(matches!(
+            shard.secondary_slivers.get(&BLOB_ID)?,
+            Some(SecondarySliverData::Encrypted(_))
+        ));
+        assert_eq!(
+            shard.get_sliver(&BLOB_ID, SliverType::Secondary)?,
+            Some(sliver.clone())
+        );
+        assert_eq!(
+            shard.fetch_slivers(SliverType::Secondary, &[BLOB_ID])?,
+            vec![(BLOB_ID, sliver)]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn stores_separate_primary_and_secondary_sliver() -> TestResult {
         let storage = empty_storage().await;
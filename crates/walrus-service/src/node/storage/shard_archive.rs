@@ -0,0 +1,469 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Portable archives of the slivers and metadata of a shard.
+//!
+//! Archives allow operators to migrate a shard between nodes out-of-band, e.g., by shipping disks,
+//! instead of syncing it over the network. An archive consists of a header identifying the shard,
+//! a sequence of length-prefixed BCS-encoded records, and a final record with the number of
+//! preceding records and a SHA-256 checksum over the header and all preceding records.
+//!
+//! The checksum protects against corruption in transit, but does not authenticate the contents.
+//! On import, the metadata of each blob is verified against its blob ID, but the slivers are only
+//! verified once they are served. Only import archives obtained from a trusted source.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::Path,
+};
+
+use anyhow::{bail, ensure, Context};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walrus_core::{
+    encoding::EncodingConfig,
+    metadata::{UnverifiedBlobMetadataWithId, VerifiedBlobMetadataWithId},
+    BlobId,
+    BlobMetadata,
+    ShardIndex,
+    Sliver,
+    SliverType,
+};
+
+use super::Storage;
+
+const ARCHIVE_MAGIC: [u8; 8] = *b"WALSHARD";
+const ARCHIVE_VERSION: u8 = 1;
+
+/// A single record of a shard archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ArchiveRecord {
+    Metadata {
+        blob_id: BlobId,
+        metadata: BlobMetadata,
+    },
+    Sliver {
+        blob_id: BlobId,
+        sliver: Sliver,
+    },
+    End {
+        n_records: u64,
+        checksum: [u8; 32],
+    },
+}
+
+/// Summary of the contents of an exported or imported shard archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardArchiveSummary {
+    /// The shard contained in the archive.
+    pub shard_index: ShardIndex,
+    /// The number of blob metadata entries in the archive.
+    pub metadata_count: u64,
+    /// The number of primary slivers in the archive.
+    pub primary_sliver_count: u64,
+    /// The number of secondary slivers in the archive.
+    pub secondary_sliver_count: u64,
+}
+
+impl ShardArchiveSummary {
+    fn new(shard_index: ShardIndex) -> Self {
+        Self {
+            shard_index,
+            metadata_count: 0,
+            primary_sliver_count: 0,
+            secondary_sliver_count: 0,
+        }
+    }
+
+    fn record(&mut self, record: &ArchiveRecord) {
+        match record {
+            ArchiveRecord::Metadata { .. } => self.metadata_count += 1,
+            ArchiveRecord::Sliver { sliver, .. } => match sliver.r#type() {
+                SliverType::Primary => self.primary_sliver_count += 1,
+                SliverType::Secondary => self.secondary_sliver_count += 1,
+            },
+            ArchiveRecord::End { .. } => (),
+        }
+    }
+}
+
+struct ArchiveWriter<W> {
+    writer: W,
+    hasher: Sha256,
+    n_records: u64,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    fn new(mut writer: W, shard_index: ShardIndex) -> anyhow::Result<Self> {
+        let mut hasher = Sha256::new();
+        let header = archive_header(shard_index);
+        writer.write_all(&header)?;
+        hasher.update(header);
+
+        Ok(Self {
+            writer,
+            hasher,
+            n_records: 0,
+        })
+    }
+
+    fn write(&mut self, record: &ArchiveRecord) -> anyhow::Result<()> {
+        let frame = encode_frame(record)?;
+        self.writer.write_all(&frame)?;
+        self.hasher.update(&frame);
+        self.n_records += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<()> {
+        let end = ArchiveRecord::End {
+            n_records: self.n_records,
+            checksum: self.hasher.finalize().into(),
+        };
+        self.writer.write_all(&encode_frame(&end)?)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+struct ArchiveReader<R> {
+    reader: R,
+    shard_index: ShardIndex,
+    hasher: Sha256,
+    n_records: u64,
+    finished: bool,
+}
+
+impl<R: Read> ArchiveReader<R> {
+    fn new(mut reader: R) -> anyhow::Result<Self> {
+        let mut magic = [0u8; ARCHIVE_MAGIC.len()];
+        reader
+            .read_exact(&mut magic)
+            .context("unable to read the archive header")?;
+        ensure!(magic == ARCHIVE_MAGIC, "the file is not a shard archive");
+
+        let mut version_and_shard = [0u8; 3];
+        reader
+            .read_exact(&mut version_and_shard)
+            .context("unable to read the archive header")?;
+        ensure!(
+            version_and_shard[0] == ARCHIVE_VERSION,
+            "unsupported shard archive version {}",
+            version_and_shard[0]
+        );
+        let shard_index = ShardIndex(u16::from_be_bytes([
+            version_and_shard[1],
+            version_and_shard[2],
+        ]));
+
+        let mut hasher = Sha256::new();
+        hasher.update(archive_header(shard_index));
+
+        Ok(Self {
+            reader,
+            shard_index,
+            hasher,
+            n_records: 0,
+            finished: false,
+        })
+    }
+
+    /// Returns the next record of the archive, or `None` after the end of the archive has been
+    /// reached and the checksum has been verified.
+    fn next_record(&mut self) -> anyhow::Result<Option<ArchiveRecord>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let mut length = [0u8; 4];
+        match self.reader.read_exact(&mut length) {
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => {
+                bail!("the archive is truncated")
+            }
+            result => result?,
+        }
+        let mut body = vec![0u8; u32::from_be_bytes(length) as usize];
+        self.reader
+            .read_exact(&mut body)
+            .context("the archive is truncated")?;
+        let record: ArchiveRecord =
+            bcs::from_bytes(&body).context("the archive contains an invalid record")?;
+
+        if let ArchiveRecord::End {
+            n_records,
+            checksum,
+        } = record
+        {
+            ensure!(
+                n_records == self.n_records,
+                "the archive contains {} records, but expected {n_records}",
+                self.n_records,
+            );
+            let computed: [u8; 32] = std::mem::take(&mut self.hasher).finalize().into();
+            ensure!(computed == checksum, "the archive checksum does not match");
+            self.finished = true;
+            return Ok(None);
+        }
+
+        self.hasher.update(length);
+        self.hasher.update(&body);
+        self.n_records += 1;
+        Ok(Some(record))
+    }
+}
+
+fn archive_header(shard_index: ShardIndex) -> Vec<u8> {
+    let mut header = ARCHIVE_MAGIC.to_vec();
+    header.push(ARCHIVE_VERSION);
+    header.extend_from_slice(&shard_index.0.to_be_bytes());
+    header
+}
+
+fn encode_frame(record: &ArchiveRecord) -> anyhow::Result<Vec<u8>> {
+    let body = bcs::to_bytes(record)?;
+    let length = u32::try_from(body.len()).context("archive record is too large")?;
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&length.to_be_bytes());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
+/// Exports the slivers of the shard stored in either tier, together with the metadata of the
+/// corresponding blobs, to a new archive at `path`.
+///
+/// The shard must not be modified during the export.
+pub async fn export_shard(
+    storage: &Storage,
+    shard_index: ShardIndex,
+    path: &Path,
+) -> anyhow::Result<ShardArchiveSummary> {
+    let shard = storage
+        .shard_storage(shard_index)
+        .await
+        .with_context(|| format!("{shard_index} is not stored in the database"))?;
+    let file = File::create_new(path)
+        .with_context(|| format!("unable to create the archive at {}", path.display()))?;
+
+    let mut writer = ArchiveWriter::new(BufWriter::new(file), shard_index)?;
+    let mut summary = ShardArchiveSummary::new(shard_index);
+    let mut exported_metadata = HashSet::new();
+
+    for sliver_type in [SliverType::Primary, SliverType::Secondary] {
+        for entry in shard.sliver_entries(sliver_type) {
+            let (blob_id, sliver) = entry?;
+
+            if exported_metadata.insert(blob_id) {
+                if let Some(metadata) = storage.get_metadata(&blob_id)? {
+                    let record = ArchiveRecord::Metadata {
+                        blob_id,
+                        metadata: metadata.metadata().clone(),
+                    };
+                    writer.write(&record)?;
+                    summary.record(&record);
+                } else {
+                    tracing::warn!(walrus.blob_id = %blob_id, "exporting sliver without metadata");
+                }
+            }
+
+            let record = ArchiveRecord::Sliver { blob_id, sliver };
+            writer.write(&record)?;
+            summary.record(&record);
+        }
+    }
+
+    writer.finish()?;
+    Ok(summary)
+}
+
+/// Imports the shard archive at `path` into the storage, creating the shard storage if necessary.
+///
+/// The archive is verified completely before the storage is modified, including the metadata of
+/// all blobs against their blob IDs under the `encoding_config` of the system. If `mark_active` is
+/// true, the shard is marked as active afterwards, so that the node does not sync it from other
+/// nodes.
+pub async fn import_shard(
+    storage: &Storage,
+    path: &Path,
+    encoding_config: &EncodingConfig,
+    mark_active: bool,
+) -> anyhow::Result<ShardArchiveSummary> {
+    let open = || -> anyhow::Result<_> {
+        let file = File::open(path)
+            .with_context(|| format!("unable to open the archive at {}", path.display()))?;
+        ArchiveReader::new(BufReader::new(file))
+    };
+
+    let mut reader = open()?;
+    let shard_index = reader.shard_index;
+    let mut summary = ShardArchiveSummary::new(shard_index);
+    while let Some(record) = reader.next_record()? {
+        if let ArchiveRecord::Metadata { blob_id, metadata } = &record {
+            verify_metadata(*blob_id, metadata.clone(), encoding_config)?;
+        }
+        summary.record(&record);
+    }
+    tracing::info!(?summary, "verified shard archive");
+
+    storage.create_storage_for_shards(&[shard_index]).await?;
+    let shard = storage
+        .shard_storage(shard_index)
+        .await
+        .expect("the shard storage was just created");
+
+    let mut reader = open()?;
+    while let Some(record) = reader.next_record()? {
+        match record {
+            ArchiveRecord::Metadata { blob_id, metadata } => storage
+                .put_verified_metadata(&verify_metadata(blob_id, metadata, encoding_config)?)?,
+            ArchiveRecord::Sliver { blob_id, sliver } => shard.put_sliver(&blob_id, &sliver)?,
+            ArchiveRecord::End { .. } => unreachable!("the end record is never returned"),
+        }
+    }
+
+    if mark_active {
+        shard.set_active_status()?;
+    }
+    Ok(summary)
+}
+
+fn verify_metadata(
+    blob_id: BlobId,
+    metadata: BlobMetadata,
+    encoding_config: &EncodingConfig,
+) -> anyhow::Result<VerifiedBlobMetadataWithId> {
+    UnverifiedBlobMetadataWithId::new(blob_id, metadata)
+        .verify(encoding_config)
+        .with_context(|| format!("the archive contains invalid metadata for blob {blob_id}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::test_utils::encoding_config;
+    use walrus_test_utils::Result as TestResult;
+
+    use super::*;
+    use crate::{
+        node::storage::tests::{get_sliver, BLOB_ID, SHARD_INDEX},
+        test_utils::empty_storage_with_shards,
+    };
+
+    #[tokio::test]
+    async fn exports_and_imports_shard() -> TestResult {
+        let source = empty_storage_with_shards(&[SHARD_INDEX]).await;
+        let metadata = walrus_core::test_utils::verified_blob_metadata();
+        let blob_id = *metadata.blob_id();
+        source.as_ref().put_verified_metadata(&metadata)?;
+        let shard = source
+            .as_ref()
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        let primary = get_sliver(SliverType::Primary, 1);
+        let secondary = get_sliver(SliverType::Secondary, 2);
+        shard.put_sliver(&blob_id, &primary)?;
+        shard.put_sliver(&blob_id, &secondary)?;
+
+        let archive_dir = tempfile::tempdir()?;
+        let archive_path = archive_dir.path().join("shard.archive");
+        let exported = export_shard(source.as_ref(), SHARD_INDEX, &archive_path).await?;
+
+        let target = empty_storage_with_shards(&[]).await;
+        let imported =
+            import_shard(target.as_ref(), &archive_path, &encoding_config(), true).await?;
+
+        assert_eq!(exported, imported);
+        assert_eq!(
+            imported,
+            ShardArchiveSummary {
+                shard_index: SHARD_INDEX,
+                metadata_count: 1,
+                primary_sliver_count: 1,
+                secondary_sliver_count: 1,
+            }
+        );
+        let imported_shard = target
+            .as_ref()
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should have been created");
+        assert_eq!(
+            imported_shard.get_sliver(&blob_id, SliverType::Primary)?,
+            Some(primary)
+        );
+        assert_eq!(
+            imported_shard.get_sliver(&blob_id, SliverType::Secondary)?,
+            Some(secondary)
+        );
+        assert_eq!(target.as_ref().get_metadata(&blob_id)?, Some(metadata));
+        assert_eq!(
+            imported_shard.status()?,
+            crate::node::storage::ShardStatus::Active
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_corrupted_archive_without_modifying_storage() -> TestResult {
+        let source = empty_storage_with_shards(&[SHARD_INDEX]).await;
+        let shard = source
+            .as_ref()
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        shard.put_sliver(&BLOB_ID, &get_sliver(SliverType::Primary, 1))?;
+
+        let archive_dir = tempfile::tempdir()?;
+        let archive_path = archive_dir.path().join("shard.archive");
+        export_shard(source.as_ref(), SHARD_INDEX, &archive_path).await?;
+
+        let mut bytes = std::fs::read(&archive_path)?;
+        let last_data_byte = bytes.len() / 2;
+        bytes[last_data_byte] ^= 0xff;
+        std::fs::write(&archive_path, bytes)?;
+
+        let target = empty_storage_with_shards(&[]).await;
+        assert!(
+            import_shard(target.as_ref(), &archive_path, &encoding_config(), false)
+                .await
+                .is_err()
+        );
+        assert!(target.as_ref().shard_storage(SHARD_INDEX).await.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_archive_with_invalid_metadata_without_modifying_storage() -> TestResult {
+        let source = empty_storage_with_shards(&[SHARD_INDEX]).await;
+        // The metadata does not match the blob ID it is stored under.
+        source.as_ref().put_verified_metadata(
+            &VerifiedBlobMetadataWithId::new_verified_unchecked(
+                BLOB_ID,
+                walrus_core::test_utils::blob_metadata(),
+            ),
+        )?;
+        let shard = source
+            .as_ref()
+            .shard_storage(SHARD_INDEX)
+            .await
+            .expect("shard should exist");
+        shard.put_sliver(&BLOB_ID, &get_sliver(SliverType::Primary, 1))?;
+
+        let archive_dir = tempfile::tempdir()?;
+        let archive_path = archive_dir.path().join("shard.archive");
+        export_shard(source.as_ref(), SHARD_INDEX, &archive_path).await?;
+
+        let target = empty_storage_with_shards(&[]).await;
+        let error = import_shard(target.as_ref(), &archive_path, &encoding_config(), false)
+            .await
+            .expect_err("the metadata is invalid");
+        assert!(error.to_string().contains("invalid metadata"));
+        assert!(target.as_ref().shard_storage(SHARD_INDEX).await.is_none());
+        assert_eq!(target.as_ref().get_metadata(&BLOB_ID)?, None);
+
+        Ok(())
+    }
+}
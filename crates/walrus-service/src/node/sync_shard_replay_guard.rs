@@ -0,0 +1,161 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rejection of replayed sync shard requests.
+//!
+//! Sync shard requests include a random nonce and an expiry, which are covered by the requester's
+//! signature. The serving node rejects expired requests, and remembers the nonces of the requests
+//! it served until they expire, such that a captured request cannot be used to pull shard data
+//! again later.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Mutex,
+    time::Duration,
+};
+
+use walrus_core::messages::SyncShardRequest;
+
+use super::{config::ShardSyncConfig, errors::SyncShardServiceError};
+
+/// Rejects sync shard requests that have expired or that were already served.
+#[derive(Debug)]
+pub(crate) struct SyncShardReplayGuard {
+    max_validity: Duration,
+    require_replay_protection: bool,
+    /// The expiry of the served requests that have not yet expired, by their nonce.
+    served: Mutex<HashMap<u64, u64>>,
+}
+
+impl SyncShardReplayGuard {
+    /// Creates a new guard with the limits of the shard-sync configuration.
+    pub(crate) fn new(config: &ShardSyncConfig) -> Self {
+        Self {
+            max_validity: config.max_sync_shard_request_validity,
+            require_replay_protection: config.require_sync_shard_replay_protection,
+            served: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Checks that the request has neither expired nor been served before, and records it as
+    /// served.
+    ///
+    /// `now` is the current Unix timestamp in seconds. Requests without a nonce and an expiry are
+    /// only accepted if replay protection is not required.
+    pub(crate) fn check_and_record(
+        &self,
+        request: &SyncShardRequest,
+        now: u64,
+    ) -> Result<(), SyncShardServiceError> {
+        let (Some(nonce), Some(expires_at)) = (request.nonce(), request.expires_at()) else {
+            if self.require_replay_protection {
+                return Err(SyncShardServiceError::ReplayProtectionRequired);
+            }
+            return Ok(());
+        };
+        if expires_at < now {
+            return Err(SyncShardServiceError::RequestExpired(expires_at));
+        }
+        if expires_at > now.saturating_add(self.max_validity.as_secs()) {
+            return Err(SyncShardServiceError::RequestExpiryTooLate(expires_at));
+        }
+
+        let mut served = self
+            .served
+            .lock()
+            .expect("replay guard lock should not be poisoned");
+        served.retain(|_, served_expires_at| *served_expires_at >= now);
+        match served.entry(nonce) {
+            Entry::Occupied(_) => Err(SyncShardServiceError::RequestReplayed),
+            Entry::Vacant(entry) => {
+                entry.insert(expires_at);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use walrus_core::{BlobId, ShardIndex, SliverType};
+
+    use super::*;
+
+    const NOW: u64 = 1_000_000;
+
+    fn request(nonce: u64, expires_at: u64) -> SyncShardRequest {
+        SyncShardRequest::new_with_expiry(
+            ShardIndex(0),
+            SliverType::Primary,
+            BlobId([1; 32]),
+            10,
+            1,
+            nonce,
+            expires_at,
+        )
+    }
+
+    #[test]
+    fn rejects_expired_and_replayed_requests() {
+        let guard = SyncShardReplayGuard::new(&ShardSyncConfig::default());
+
+        assert!(guard.check_and_record(&request(1, NOW + 60), NOW).is_ok());
+        assert!(matches!(
+            guard.check_and_record(&request(1, NOW + 60), NOW + 30),
+            Err(SyncShardServiceError::RequestReplayed)
+        ));
+        assert!(guard.check_and_record(&request(2, NOW + 60), NOW).is_ok());
+
+        assert!(matches!(
+            guard.check_and_record(&request(3, NOW - 1), NOW),
+            Err(SyncShardServiceError::RequestExpired(_))
+        ));
+        assert!(matches!(
+            guard.check_and_record(&request(3, NOW + 24 * 60 * 60), NOW),
+            Err(SyncShardServiceError::RequestExpiryTooLate(_))
+        ));
+
+        // Once the requests have expired, they are rejected and their nonces are forgotten.
+        assert!(matches!(
+            guard.check_and_record(&request(1, NOW + 60), NOW + 61),
+            Err(SyncShardServiceError::RequestExpired(_))
+        ));
+        assert!(guard
+            .check_and_record(&request(4, NOW + 120), NOW + 61)
+            .is_ok());
+        assert_eq!(guard.served.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn accepts_requests_without_nonce_unless_required() {
+        // Version 1 requests, as sent by nodes that do not support replay protection yet.
+        let legacy_request: SyncShardRequest = bcs::from_bytes(
+            &bcs::to_bytes(&(
+                0u8,
+                ShardIndex(0),
+                SliverType::Primary,
+                BlobId([1; 32]),
+                10u64,
+                1u32,
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(legacy_request.nonce(), None);
+        assert_eq!(
+            SyncShardRequest::new(ShardIndex(0), SliverType::Primary, BlobId([1; 32]), 10, 1),
+            legacy_request
+        );
+
+        let mut config = ShardSyncConfig::default();
+        assert!(SyncShardReplayGuard::new(&config)
+            .check_and_record(&legacy_request, NOW)
+            .is_ok());
+
+        config.require_sync_shard_replay_protection = true;
+        assert!(matches!(
+            SyncShardReplayGuard::new(&config).check_and_record(&legacy_request, NOW),
+            Err(SyncShardServiceError::ReplayProtectionRequired)
+        ));
+    }
+}
@@ -9,17 +9,21 @@
 
 use std::{
     borrow::Borrow,
+    collections::HashSet,
     default::Default,
     net::{SocketAddr, TcpStream},
     num::NonZeroU16,
     path::PathBuf,
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 use anyhow::Context;
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::{future, stream::FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sui_macros::nondeterministic;
@@ -27,8 +31,11 @@ use sui_types::base_types::ObjectID;
 use tempfile::TempDir;
 #[cfg(msim)]
 use tokio::sync::RwLock;
-use tokio::{task::JoinHandle, time::Duration};
-use tokio_stream::{wrappers::BroadcastStream, Stream};
+use tokio::{sync::watch, task::JoinHandle, time::Duration};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream,
+};
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument as _;
 use typed_store::{rocks::MetricConf, Map};
@@ -61,6 +68,9 @@ use walrus_sui::{
         move_structs::{EpochState, EventBlob, NodeMetadata, VotingParams},
         Committee,
         ContractEvent,
+        EpochChangeDone,
+        EpochChangeEvent,
+        EpochChangeStart,
         NetworkAddress,
         NodeRegistrationParams,
         StorageNode as SuiStorageNode,
@@ -85,8 +95,16 @@ use crate::{
             DefaultNodeServiceFactory,
             EndCommitteeChangeError,
             NodeCommitteeService,
+            PeerHealth,
+            ShardAssignmentDiff,
+        },
+        config::{
+            self,
+            CommitteeServiceConfig,
+            ConfigSynchronizerConfig,
+            ShardSyncConfig,
+            StorageNodeConfig,
         },
-        config::{self, ConfigSynchronizerConfig, ShardSyncConfig, StorageNodeConfig},
         contract_service::SystemContractService,
         errors::{SyncNodeConfigError, SyncShardClientError},
         events::{
@@ -99,6 +117,7 @@ use crate::{
         server::{RestApiConfig, RestApiServer},
         system_events::{EventManager, EventRetentionManager, SystemEventProvider},
         DatabaseConfig,
+        ProtocolSigner,
         Storage,
         StorageNode,
     },
@@ -356,13 +375,7 @@ impl SimStorageNodeHandle {
             .ip(ip)
             .name(&format!(
                 "{:?}",
-                config
-                    .read()
-                    .await
-                    .protocol_key_pair
-                    .get()
-                    .expect("config must contain protocol key pair")
-                    .public()
+                config.read().await.protocol_key_pair().public()
             ))
             .init(move || {
                 tracing::info!(?ip, "starting simulator node");
@@ -865,7 +878,7 @@ impl StorageNodeHandleBuilder {
         let config = StorageNodeConfig {
             name: self.name.unwrap_or_else(|| "node".to_string()),
             storage_path: temp_dir.path().to_path_buf(),
-            protocol_key_pair: node_info.key_pair.into(),
+            protocol_key_pair: Some(node_info.key_pair.into()),
             next_protocol_key_pair: None,
             network_key_pair: node_info.network_key_pair.into(),
             rest_api_address: node_info.rest_api_address,
@@ -1010,7 +1023,7 @@ impl StorageNodeHandleBuilder {
         // Builds the storage node config used to run the node.
         let storage_node_config = StorageNodeConfig {
             storage_path: storage_dir.path().to_path_buf(),
-            protocol_key_pair: node_info.key_pair.into(),
+            protocol_key_pair: Some(node_info.key_pair.into()),
             next_protocol_key_pair: None,
             network_key_pair: node_info.network_key_pair.into(),
             rest_api_address: node_info.rest_api_address,
@@ -1032,6 +1045,8 @@ impl StorageNodeHandleBuilder {
                 backoff_config: ExponentialBackoffConfig::default(),
                 gas_budget: None,
                 rpc_fallback_config: None,
+                graphql_url: None,
+                quorum_read_config: None,
             }),
             config_synchronizer: ConfigSynchronizerConfig {
                 interval: Duration::from_secs(5),
@@ -1060,12 +1075,7 @@ impl StorageNodeHandleBuilder {
 
         Ok(SimStorageNodeHandle {
             storage_directory: storage_dir,
-            public_key: storage_node_config
-                .protocol_key_pair
-                .get()
-                .expect("protocol key must be set")
-                .public()
-                .clone(),
+            public_key: storage_node_config.protocol_key_pair().public().clone(),
             network_public_key: storage_node_config
                 .network_key_pair
                 .get()
@@ -1360,7 +1370,7 @@ impl CommitteeService for StubCommitteeService {
         _sliver_type: SliverType,
         _sliver_count: u64,
         _epoch: Epoch,
-        _key_pair: &ProtocolKeyPair,
+        _signer: &Arc<dyn ProtocolSigner>,
     ) -> Result<Vec<(BlobId, Sliver)>, SyncShardClientError> {
         std::future::pending().await
     }
@@ -1372,6 +1382,14 @@ impl CommitteeService for StubCommitteeService {
         )
     }
 
+    async fn push_metadata(
+        &self,
+        _public_key: &PublicKey,
+        _metadata: Arc<Vec<VerifiedBlobMetadataWithId>>,
+    ) -> Result<u64, anyhow::Error> {
+        std::future::pending().await
+    }
+
     fn is_walrus_storage_node(&self, public_key: &PublicKey) -> bool {
         self.committee
             .members()
@@ -1379,6 +1397,28 @@ impl CommitteeService for StubCommitteeService {
             .any(|node| node.public_key == *public_key)
     }
 
+    fn peer_health(&self) -> Vec<PeerHealth> {
+        vec![]
+    }
+
+    fn shard_assignment_diff(&self) -> Option<ShardAssignmentDiff> {
+        None
+    }
+
+    fn subscribe_to_shard_assignment_changes(
+        &self,
+    ) -> watch::Receiver<Option<ShardAssignmentDiff>> {
+        watch::channel(None).1
+    }
+
+    fn committee_history(&self) -> Vec<Arc<Committee>> {
+        vec![]
+    }
+
+    fn restore_committee_history(&self, _committees: Vec<Committee>) {}
+
+    fn update_config(&self, _config: &CommitteeServiceConfig) {}
+
     async fn begin_committee_change(
         &self,
         _new_epoch: Epoch,
@@ -1434,6 +1474,18 @@ impl SystemContractService for StubContractService {
         1
     }
 
+    async fn current_chain_time(&self) -> Result<DateTime<Utc>, anyhow::Error> {
+        Ok(Utc::now())
+    }
+
+    async fn latest_checkpoint_sequence_number(&self) -> Result<u64, anyhow::Error> {
+        Ok(0)
+    }
+
+    async fn checkpoint_time(&self, _sequence_number: u64) -> Result<DateTime<Utc>, anyhow::Error> {
+        Ok(Utc::now())
+    }
+
     async fn fixed_system_parameters(&self) -> Result<FixedSystemParameters, anyhow::Error> {
         Ok(self.system_parameters.clone())
     }
@@ -1563,6 +1615,123 @@ impl SystemEventProvider for tokio::sync::broadcast::Sender<ContractEvent> {
     }
 }
 
+/// The default capacity of the channel through which the mock chain broadcasts events.
+const MOCK_CHAIN_CAPACITY: usize = 1024;
+/// The maximum number of attempts to reopen the storage of a restarted node.
+const STORAGE_REOPEN_ATTEMPTS: usize = 100;
+
+/// Error returned by the streams of a [`MockChain`] when a subscriber fell behind by more events
+/// than the capacity of the chain, and the oldest events were dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("the subscriber lagged behind and {dropped} events were dropped")]
+pub struct MockChainLagged {
+    /// The number of events that were dropped for the subscriber.
+    pub dropped: u64,
+}
+
+/// A mock of the Walrus contracts, which broadcasts the contract events sent to it to all storage
+/// nodes of a [`TestCluster`].
+///
+/// The events are broadcast through a bounded channel. Subscribers that fall behind by more than
+/// its capacity miss the oldest events; instead of dropping them silently, the chain records the
+/// dropped events and surfaces a [`MockChainLagged`] error in the subscriber's stream. When used
+/// as a [`SystemEventProvider`], the event stream ends after such an error, as the stream of a
+/// real provider does when it fails.
+#[derive(Debug, Clone)]
+pub struct MockChain {
+    sender: tokio::sync::broadcast::Sender<ContractEvent>,
+    dropped_events: Arc<AtomicU64>,
+}
+
+impl Default for MockChain {
+    fn default() -> Self {
+        Self::new(MOCK_CHAIN_CAPACITY)
+    }
+}
+
+impl MockChain {
+    /// Creates a new mock chain that buffers up to `capacity` events for each subscriber.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            sender: tokio::sync::broadcast::Sender::new(capacity),
+            dropped_events: Default::default(),
+        }
+    }
+
+    /// Sends the event to all current subscribers, and returns the number of subscribers.
+    pub fn send(
+        &self,
+        event: ContractEvent,
+    ) -> Result<usize, tokio::sync::broadcast::error::SendError<ContractEvent>> {
+        self.sender.send(event)
+    }
+
+    /// Returns the sender through which the events are broadcast.
+    pub fn sender(&self) -> &tokio::sync::broadcast::Sender<ContractEvent> {
+        &self.sender
+    }
+
+    /// Returns a stream of the events sent after this call.
+    ///
+    /// If the subscriber falls behind by more events than the capacity of the chain, the stream
+    /// yields a [`MockChainLagged`] error and continues with the oldest events still buffered.
+    pub fn subscribe(&self) -> impl Stream<Item = Result<ContractEvent, MockChainLagged>> {
+        let dropped_events = self.dropped_events.clone();
+        BroadcastStream::new(self.sender.subscribe()).map(move |result| {
+            result.map_err(|BroadcastStreamRecvError::Lagged(dropped)| {
+                tracing::warn!(dropped, "subscriber of the mock chain lagged behind");
+                dropped_events.fetch_add(dropped, Ordering::SeqCst);
+                MockChainLagged { dropped }
+            })
+        })
+    }
+
+    /// Returns the total number of events dropped for all subscribers.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::SeqCst)
+    }
+
+    /// Panics if any events were dropped for any subscriber.
+    #[track_caller]
+    pub fn assert_no_dropped_events(&self) {
+        let dropped = self.dropped_events();
+        assert_eq!(
+            dropped, 0,
+            "{dropped} events of the mock chain were dropped"
+        );
+    }
+}
+
+#[async_trait::async_trait]
+impl SystemEventProvider for MockChain {
+    async fn events(
+        &self,
+        _cursor: EventStreamCursor,
+    ) -> Result<Box<dyn Stream<Item = PositionedStreamEvent> + Send + Sync + 'life0>, anyhow::Error>
+    {
+        Ok(Box::new(
+            self.subscribe()
+                .take_while(|result| future::ready(result.is_ok()))
+                .filter_map(|result| {
+                    future::ready(result.ok().map(|event| {
+                        PositionedStreamEvent::new(event, CheckpointEventPosition::new(0, 0))
+                    }))
+                }),
+        ))
+    }
+
+    async fn init_state(
+        &self,
+        _from: EventStreamCursor,
+    ) -> Result<Option<InitState>, anyhow::Error> {
+        Ok(None)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 /// A cluster of [`StorageNodeHandle`]s corresponding to several running storage nodes.
 #[derive(Debug)]
 pub struct TestCluster<T: StorageNodeHandleTrait = StorageNodeHandle> {
@@ -1572,6 +1741,24 @@ pub struct TestCluster<T: StorageNodeHandleTrait = StorageNodeHandle> {
     pub lookup_service_handle: Option<StubLookupServiceHandle>,
     /// The number of shards in the system.
     pub n_shards: usize,
+    /// The mock chain providing the events to all nodes, if the cluster was built with
+    /// [`TestClusterBuilder::with_mock_chain`].
+    pub mock_chain: Option<MockChain>,
+    /// The indices of the nodes stopped with [`Self::cancel_node`].
+    cancelled_nodes: HashSet<usize>,
+    /// The configurations with which the nodes were started, used to restart them.
+    restart_configs: Vec<NodeRestartConfig>,
+}
+
+/// The parts of the configuration of a node in a [`TestCluster`] that are required to restart it.
+#[derive(Debug)]
+struct NodeRestartConfig {
+    name: String,
+    test_config: StorageNodeTestConfig,
+    committee_service: Arc<dyn CommitteeService>,
+    contract_service: Option<Arc<dyn SystemContractService>>,
+    storage_node_capability: Option<StorageNodeCap>,
+    shard_sync_config: ShardSyncConfig,
 }
 
 impl<T: StorageNodeHandleTrait> TestCluster<T> {
@@ -1593,6 +1780,7 @@ impl<T: StorageNodeHandleTrait> TestCluster<T> {
             "the index of the node to be dropped must be within the node vector"
         );
         self.nodes[idx].cancel();
+        self.cancelled_nodes.insert(idx);
     }
 
     /// Returns the client for the node at the specified index.
@@ -1600,15 +1788,139 @@ impl<T: StorageNodeHandleTrait> TestCluster<T> {
         self.nodes[index].client()
     }
 
-    /// Wait for all nodes to arrive at at least the specified epoch.
+    /// Wait for all nodes that are not cancelled to arrive at at least the specified epoch.
     pub async fn wait_for_nodes_to_reach_epoch(&self, epoch: Epoch) {
         let waits: FuturesUnordered<_> = self
             .nodes
             .iter()
-            .map(|handle| handle.storage_node().wait_for_epoch(epoch))
+            .enumerate()
+            .filter(|(idx, _)| !self.cancelled_nodes.contains(idx))
+            .map(|(_, handle)| handle.storage_node().wait_for_epoch(epoch))
             .collect();
         waits.for_each(|_| std::future::ready(())).await;
     }
+
+    /// Advances the cluster to the specified epoch, one epoch at a time.
+    ///
+    /// For each epoch, this advances the stub lookup service, sends the epoch change start and
+    /// done events to each of the provided event queues, and waits for the nodes to reach the
+    /// epoch.
+    pub async fn advance_to_epoch(
+        &self,
+        events: &[&tokio::sync::broadcast::Sender<ContractEvent>],
+        epoch: Epoch,
+    ) -> anyhow::Result<()> {
+        let lookup_service_handle = self
+            .lookup_service_handle
+            .as_ref()
+            .context("advancing epochs requires the stub lookup service")?;
+        for epoch in lookup_service_handle.epoch() + 1..epoch + 1 {
+            let new_epoch = lookup_service_handle.advance_epoch();
+            assert_eq!(new_epoch, epoch);
+            for event_queue in events {
+                event_queue.send(ContractEvent::EpochChangeEvent(
+                    EpochChangeEvent::EpochChangeStart(EpochChangeStart {
+                        epoch,
+                        event_id: walrus_sui::test_utils::event_id_for_testing(),
+                    }),
+                ))?;
+                event_queue.send(ContractEvent::EpochChangeEvent(
+                    EpochChangeEvent::EpochChangeDone(EpochChangeDone {
+                        epoch,
+                        event_id: walrus_sui::test_utils::event_id_for_testing(),
+                    }),
+                ))?;
+            }
+            self.wait_for_nodes_to_reach_epoch(epoch).await;
+        }
+        Ok(())
+    }
+
+    /// Advances a cluster built with a mock chain to the next epoch, and returns the new epoch.
+    pub async fn advance_epoch(&self) -> anyhow::Result<Epoch> {
+        let mock_chain = self
+            .mock_chain
+            .as_ref()
+            .context("the cluster was not built with a mock chain")?;
+        let lookup_service_handle = self
+            .lookup_service_handle
+            .as_ref()
+            .context("advancing epochs requires the stub lookup service")?;
+        let next_epoch = lookup_service_handle.epoch() + 1;
+        self.advance_to_epoch(&[mock_chain.sender()], next_epoch)
+            .await?;
+        Ok(next_epoch)
+    }
+}
+
+impl TestCluster<StorageNodeHandle> {
+    /// Restarts the storage node with index `idx` in a cluster built with a mock chain.
+    ///
+    /// The node is cancelled if it is still running, and started again with the same keys,
+    /// storage, and committee service. The restarted node only receives the events sent to the
+    /// mock chain after the restart.
+    pub async fn restart_node(&mut self, idx: usize) -> anyhow::Result<()> {
+        let mock_chain = self
+            .mock_chain
+            .clone()
+            .context("restarting nodes requires a mock chain")?;
+        self.cancel_node(idx);
+
+        let mut handle = self.nodes.remove(idx);
+        if let Some(node_runtime_handle) = handle.node_runtime_handle.take() {
+            node_runtime_handle.await?;
+        }
+        // The task running the REST API holds a reference to it until the server has stopped and
+        // released its address.
+        while Arc::strong_count(&handle.rest_api) > 1 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let StorageNodeHandle {
+            storage_directory, ..
+        } = handle;
+
+        // The storage can only be reopened once all references to the previous node are dropped.
+        let mut attempts = 0;
+        let storage = loop {
+            match Storage::open(
+                storage_directory.path(),
+                DatabaseConfig::default(),
+                MetricConf::default(),
+                Registry::default(),
+            ) {
+                Ok(storage) => break storage,
+                Err(error) if attempts < STORAGE_REOPEN_ATTEMPTS => {
+                    tracing::debug!(?error, "waiting for the storage to be released");
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+                Err(error) => return Err(error).context("failed to reopen the node's storage"),
+            }
+        };
+
+        let restart_config = &self.restart_configs[idx];
+        let mut builder = StorageNodeHandle::builder()
+            .with_storage(WithTempDir {
+                inner: storage,
+                temp_dir: storage_directory,
+            })
+            .with_test_config(restart_config.test_config.clone())
+            .with_system_event_provider(mock_chain)
+            .with_committee_service(restart_config.committee_service.clone())
+            .with_storage_node_capability(restart_config.storage_node_capability.clone())
+            .with_shard_sync_config(restart_config.shard_sync_config.clone())
+            .with_rest_api_started(true)
+            .with_node_started(true)
+            .with_name(restart_config.name.clone());
+        if let Some(contract_service) = &restart_config.contract_service {
+            builder = builder.with_system_contract_service(contract_service.clone());
+        }
+        let node = builder.build().await?;
+
+        self.nodes.insert(idx, node);
+        self.cancelled_nodes.remove(&idx);
+        Ok(())
+    }
 }
 
 /// Builds a new [`TestCluster`] with custom configuration values.
@@ -1638,6 +1950,7 @@ pub struct TestClusterBuilder {
     blocklist_files: Vec<Option<PathBuf>>,
     disable_event_blob_writer: Vec<bool>,
     enable_node_config_synchronizer: bool,
+    mock_chain: Option<MockChain>,
 }
 
 impl TestClusterBuilder {
@@ -1739,6 +2052,24 @@ impl TestClusterBuilder {
         self
     }
 
+    /// Provides the events to all storage nodes from a new [`MockChain`], which is available in
+    /// the built cluster to send events, advance epochs, and restart nodes.
+    ///
+    /// Takes precedence over any event providers set for the storage nodes.
+    pub fn with_mock_chain(self) -> Self {
+        self.with_mock_chain_capacity(MOCK_CHAIN_CAPACITY)
+    }
+
+    /// Like [`with_mock_chain`][Self::with_mock_chain], but buffers at most `capacity` events for
+    /// each node.
+    ///
+    /// Nodes that fall behind by more events see their event stream end, and the dropped events
+    /// are recorded in the [`MockChain`].
+    pub fn with_mock_chain_capacity(mut self, capacity: usize) -> Self {
+        self.mock_chain = Some(MockChain::new(capacity));
+        self
+    }
+
     /// Sets the [`CommitteeService`] used for each storage node.
     ///
     /// Should be called after the storage nodes have been specified.
@@ -1839,6 +2170,7 @@ impl TestClusterBuilder {
         // Create the stub lookup service and handles that may be used if none is provided.
         let mut lookup_service_and_handle = None;
         let mut node_futures = vec![];
+        let mut restart_configs = vec![];
 
         for (
             idx,
@@ -1870,18 +2202,20 @@ impl TestClusterBuilder {
         {
             let storage = empty_storage_with_shards(&config.shards).await;
             let local_identity = config.key_pair.public().clone();
+            let name = format!("node-{}", idx);
+            let test_config = config.clone();
             let builder = StorageNodeHandle::builder()
                 .with_storage(storage)
                 .with_test_config(config)
                 .with_rest_api_started(true)
                 .with_node_started(true)
-                .with_storage_node_capability(capability)
+                .with_storage_node_capability(capability.clone())
                 .with_node_wallet_dir(node_wallet_dir)
                 .with_blocklist_file(blocklist_file)
                 .with_shard_sync_config(self.shard_sync_config.clone().unwrap_or_default())
                 .with_disabled_event_blob_writer(disable_event_blob_writer)
                 .with_enable_node_config_synchronizer(self.enable_node_config_synchronizer)
-                .with_name(format!("node-{}", idx));
+                .with_name(name.clone());
             tracing::info!(
                 "test cluster builder build enable_node_config_synchronizer: {}",
                 self.enable_node_config_synchronizer
@@ -1894,12 +2228,14 @@ impl TestClusterBuilder {
                 builder
             };
 
-            if let Some(provider) = event_provider {
+            if let Some(mock_chain) = &self.mock_chain {
+                builder = builder.with_system_event_provider(mock_chain.clone());
+            } else if let Some(provider) = event_provider {
                 builder = builder.with_boxed_system_event_provider(provider);
             }
 
-            builder = if let Some(service) = service {
-                builder.with_committee_service(service)
+            let committee_service = if let Some(service) = service {
+                service
             } else {
                 let (lookup_service, _) = lookup_service_and_handle.get_or_insert_with(|| {
                     let committee = committee_from_members(committee_members.clone(), Some(1));
@@ -1915,13 +2251,23 @@ impl TestClusterBuilder {
                         DefaultNodeServiceFactory::avoid_system_services(),
                     )
                     .await?;
-                builder.with_committee_service(Arc::new(service))
+                Arc::new(service)
             };
+            builder = builder.with_committee_service(committee_service.clone());
 
-            if let Some(service) = contract_service {
+            if let Some(service) = contract_service.clone() {
                 builder = builder.with_system_contract_service(service);
             }
 
+            restart_configs.push(NodeRestartConfig {
+                name,
+                test_config,
+                committee_service,
+                contract_service,
+                storage_node_capability: capability,
+                shard_sync_config: self.shard_sync_config.clone().unwrap_or_default(),
+            });
+
             // Build and run the storage nodes in parallel.
             node_futures.push(T::build_and_run(
                 builder,
@@ -1949,12 +2295,15 @@ impl TestClusterBuilder {
             nodes,
             lookup_service_handle: lookup_service_and_handle.map(|(_, handle)| handle),
             n_shards,
+            mock_chain: self.mock_chain,
+            cancelled_nodes: HashSet::new(),
+            restart_configs,
         })
     }
 }
 
 /// Configuration for a test cluster storage node.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StorageNodeTestConfig {
     key_pair: ProtocolKeyPair,
     network_key_pair: NetworkKeyPair,
@@ -2065,6 +2414,7 @@ impl Default for TestClusterBuilder {
             use_distinct_ip: false,
             num_checkpoints_per_blob: None,
             enable_node_config_synchronizer: false,
+            mock_chain: None,
         }
     }
 }
@@ -2608,7 +2958,9 @@ pub fn storage_node_config() -> WithTempDir<StorageNodeConfig> {
             next_protocol_key_pair: None,
             network_key_pair: walrus_core::test_utils::network_key_pair().into(),
             rest_api_address,
+            rest_api_listeners: vec![],
             metrics_address: unused_socket_address(false),
+            metrics_listeners: vec![],
             storage_path: temp_dir.path().to_path_buf(),
             db_config: Default::default(),
             rest_server: Default::default(),
@@ -2637,9 +2989,16 @@ pub fn storage_node_config() -> WithTempDir<StorageNodeConfig> {
             metadata: Default::default(),
             config_synchronizer: Default::default(),
             storage_node_cap: None,
+            register_on_first_start: false,
             num_uncertified_blob_threshold: Some(3),
             balance_check: Default::default(),
+            event_lag_monitor: Default::default(),
+            dead_letter_queue: Default::default(),
             thread_pool: Default::default(),
+            maintenance_mode: false,
+            log_filter: None,
+            trace_sampling: Default::default(),
+            remote_protocol_signer: None,
         },
         temp_dir,
     }
@@ -2696,7 +3055,41 @@ fn committee_from_members(members: Vec<SuiStorageNode>, initial_epoch: Option<Ep
 mod test {
     use std::collections::HashSet;
 
-    use super::unused_socket_address;
+    use futures::StreamExt as _;
+
+    use super::{
+        unused_socket_address,
+        ContractEvent,
+        EpochChangeEvent,
+        EpochChangeStart,
+        MockChain,
+        MockChainLagged,
+    };
+
+    #[tokio::test]
+    async fn mock_chain_surfaces_dropped_events() {
+        let mock_chain = MockChain::new(2);
+        let mut events = Box::pin(mock_chain.subscribe());
+        let sent: Vec<_> = (1..=5)
+            .map(|epoch| {
+                let event = ContractEvent::EpochChangeEvent(EpochChangeEvent::EpochChangeStart(
+                    EpochChangeStart {
+                        epoch,
+                        event_id: walrus_sui::test_utils::event_id_for_testing(),
+                    },
+                ));
+                mock_chain.send(event.clone()).unwrap();
+                event
+            })
+            .collect();
+
+        assert_eq!(
+            events.next().await,
+            Some(Err(MockChainLagged { dropped: 3 }))
+        );
+        assert_eq!(events.next().await, Some(Ok(sent[3].clone())));
+        assert_eq!(mock_chain.dropped_events(), 3);
+    }
 
     #[test]
     #[ignore = "ignore to not bind sockets unnecessarily"]
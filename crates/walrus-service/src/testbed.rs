@@ -583,6 +583,7 @@ pub async fn create_backup_config(
             backoff_config: ExponentialBackoffConfig::default(),
             event_polling_interval: defaults::polling_interval(),
             rpc_fallback_config,
+            graphql_url: None,
         },
         database_url.to_string(),
     ))
@@ -709,6 +710,8 @@ pub async fn create_storage_node_configs(
             backoff_config: ExponentialBackoffConfig::default(),
             gas_budget: None,
             rpc_fallback_config: rpc_fallback_config.clone(),
+            graphql_url: None,
+            quorum_read_config: None,
         });
 
         let storage_path = set_db_path
@@ -727,7 +730,7 @@ pub async fn create_storage_node_configs(
             name: node.name.clone(),
             storage_path,
             blocklist_path: None,
-            protocol_key_pair,
+            protocol_key_pair: Some(protocol_key_pair),
             next_protocol_key_pair: None,
             network_key_pair: node.network_keypair.into(),
             public_host: node.network_address.get_host().to_owned(),
@@ -736,7 +739,9 @@ pub async fn create_storage_node_configs(
                 node.network_address
             ))?,
             metrics_address,
+            metrics_listeners: vec![],
             rest_api_address,
+            rest_api_listeners: vec![],
             sui,
             db_config: Default::default(),
             rest_server: Default::default(),
@@ -757,9 +762,16 @@ pub async fn create_storage_node_configs(
             metadata: Default::default(),
             config_synchronizer: Default::default(),
             storage_node_cap: None,
+            register_on_first_start: false,
             num_uncertified_blob_threshold: Some(10),
             balance_check: Default::default(),
+            event_lag_monitor: Default::default(),
+            dead_letter_queue: Default::default(),
             thread_pool: Default::default(),
+            maintenance_mode: false,
+            log_filter: None,
+            trace_sampling: Default::default(),
+            remote_protocol_signer: None,
         });
     }
 
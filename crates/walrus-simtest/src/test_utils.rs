@@ -13,6 +13,7 @@ pub mod simtest_utils {
 
     use anyhow::Context;
     use rand::Rng;
+    use sui_simulator::{net::NetSim, task::NodeId};
     use sui_types::base_types::ObjectID;
     use tokio::task::JoinHandle;
     use walrus_core::{
@@ -22,7 +23,7 @@ pub mod simtest_utils {
     };
     use walrus_sdk::api::ServiceHealthInfo;
     use walrus_service::{
-        client::{responses::BlobStoreResult, Client, StoreWhen},
+        client::{responses::BlobStoreResult, Client, MetadataReplication, StoreWhen},
         test_utils::SimStorageNodeHandle,
     };
     use walrus_sui::client::{BlobPersistence, PostStoreAction, SuiContractClient};
@@ -67,6 +68,7 @@ pub mod simtest_utils {
                 StoreWhen::Always,
                 BlobPersistence::Permanent,
                 PostStoreAction::Keep,
+                MetadataReplication::WithSlivers,
             )
             .await
             .context("store blob should not fail")?;
@@ -238,6 +240,62 @@ pub mod simtest_utils {
         }
     }
 
+    /// A network partition between two groups of simulator nodes.
+    ///
+    /// While the partition is in place, all messages between a node in one group and a node in
+    /// the other group are dropped, in both directions; messages within a group, and to nodes in
+    /// neither group, are unaffected. The partition is healed when the value is dropped.
+    #[derive(Debug)]
+    pub struct NetworkPartition {
+        links: Vec<(NodeId, NodeId)>,
+    }
+
+    impl NetworkPartition {
+        /// Partitions the network between the nodes in `side_a` and the nodes in `side_b`.
+        pub fn new(side_a: &[NodeId], side_b: &[NodeId]) -> Self {
+            let links: Vec<_> = side_a
+                .iter()
+                .flat_map(|a| side_b.iter().flat_map(move |b| [(*a, *b), (*b, *a)]))
+                .collect();
+            tracing::warn!(?side_a, ?side_b, "partitioning the network");
+            let net = sui_simulator::plugin::simulator::<NetSim>();
+            for (src, dst) in &links {
+                net.clog_link(*src, *dst);
+            }
+            Self { links }
+        }
+
+        /// Isolates the storage node from all other storage nodes, while keeping its connection
+        /// to the Sui cluster.
+        pub fn isolate_storage_node(
+            node: &SimStorageNodeHandle,
+            all_nodes: &[SimStorageNodeHandle],
+        ) -> Self {
+            let node_id = node.node_id.expect("node id should be set");
+            let others: Vec<_> = all_nodes
+                .iter()
+                .filter_map(|other| other.node_id)
+                .filter(|other_id| *other_id != node_id)
+                .collect();
+            Self::new(&[node_id], &others)
+        }
+
+        /// Heals the partition.
+        pub fn heal(self) {
+            drop(self);
+        }
+    }
+
+    impl Drop for NetworkPartition {
+        fn drop(&mut self) {
+            tracing::info!("healing the network partition");
+            let net = sui_simulator::plugin::simulator::<NetSim>();
+            for (src, dst) in &self.links {
+                net.unclog_link(*src, *dst);
+            }
+        }
+    }
+
     /// Helper function to get health info for a single node.
     pub async fn wait_until_node_is_active(
         node: &SimStorageNodeHandle,
@@ -9,24 +9,30 @@
 mod tests {
     use std::{
         collections::HashSet,
-        sync::{atomic::AtomicBool, Arc, Mutex},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+            Mutex,
+        },
         time::{Duration, Instant},
     };
 
     use rand::Rng;
+    use sui_macros::{clear_fail_point, register_fail_point_async};
     use sui_protocol_config::ProtocolConfig;
+    use tokio::task::JoinHandle;
     use walrus_proc_macros::walrus_simtest;
     use walrus_sdk::api::ShardStatus;
     use walrus_service::{
         client::ClientCommunicationConfig,
-        test_utils::{test_cluster, SimStorageNodeHandle, TestNodesConfig},
+        test_utils::{test_cluster, SimStorageNodeHandle, TestCluster, TestNodesConfig},
     };
     use walrus_simtest::test_utils::simtest_utils::{
         self,
         BlobInfoConsistencyCheck,
         DB_FAIL_POINTS,
     };
-    use walrus_sui::client::ReadClient;
+    use walrus_sui::{client::ReadClient, test_utils::TestClusterHandle};
 
     const FAILURE_TRIGGER_PROBABILITY: f64 = 0.01;
 
@@ -467,4 +473,140 @@ mod tests {
 
         blob_info_consistency_check.check_storage_node_consistency();
     }
+
+    // Sets up a cluster with a short epoch duration, stakes with the first node such that it
+    // gains shards in the next epoch change, and starts a background workload.
+    async fn setup_cluster_with_shard_move_to_first_node() -> (
+        Arc<tokio::sync::Mutex<TestClusterHandle>>,
+        TestCluster<SimStorageNodeHandle>,
+        JoinHandle<()>,
+    ) {
+        let (sui_cluster, walrus_cluster, client) =
+            test_cluster::default_setup_with_num_checkpoints_generic::<SimStorageNodeHandle>(
+                Duration::from_secs(30),
+                TestNodesConfig {
+                    node_weights: vec![1, 2, 3, 3, 4],
+                    ..Default::default()
+                },
+                None,
+                ClientCommunicationConfig::default_for_test_with_reqwest_timeout(
+                    Duration::from_secs(2),
+                ),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let client = Arc::new(client);
+        let workload_handle = simtest_utils::start_background_workload(client.clone(), false);
+
+        client
+            .as_ref()
+            .as_ref()
+            .stake_with_node_pool(
+                walrus_cluster.nodes[0]
+                    .storage_node_capability
+                    .as_ref()
+                    .unwrap()
+                    .node_id,
+                test_cluster::FROST_PER_NODE_WEIGHT * 3,
+            )
+            .await
+            .expect("stake with node pool should not fail");
+
+        (sui_cluster, walrus_cluster, workload_handle)
+    }
+
+    // Registers a fail point at the start of each epoch change, and returns a flag that is set
+    // when a storage node starts processing an epoch change after the fail point is registered.
+    fn flag_on_epoch_change_start() -> Arc<AtomicBool> {
+        let epoch_change_started = Arc::new(AtomicBool::new(false));
+        let epoch_change_started_clone = epoch_change_started.clone();
+        register_fail_point_async("epoch_change_start_entry", move || {
+            epoch_change_started_clone.store(true, Ordering::SeqCst);
+            async {}
+        });
+        epoch_change_started
+    }
+
+    // Checks that the node is active and that all of its shards are ready.
+    async fn assert_node_recovered(node: &SimStorageNodeHandle) {
+        let node_health_info =
+            simtest_utils::wait_until_node_is_active(node, Duration::from_secs(120))
+                .await
+                .expect("node should become active");
+        for shard in &node_health_info
+            .shard_detail
+            .expect("shard detail should be present")
+            .owned
+        {
+            assert_eq!(shard.status, ShardStatus::Ready);
+        }
+    }
+
+    // Tests that a node that crashes while processing an epoch change in which it gains shards
+    // restarts, finishes the epoch change, and recovers its shards.
+    #[ignore = "ignore integration simtests by default"]
+    #[walrus_simtest]
+    async fn test_node_crash_during_epoch_change() {
+        let blob_info_consistency_check = BlobInfoConsistencyCheck::new();
+        let (_sui_cluster, walrus_cluster, workload_handle) =
+            setup_cluster_with_shard_move_to_first_node().await;
+
+        // Crash the first node when it starts processing the next epoch change.
+        let fail_triggered = Arc::new(AtomicBool::new(false));
+        let target_fail_node_id = walrus_cluster.nodes[0]
+            .node_id
+            .expect("node id should be set");
+        let fail_triggered_clone = fail_triggered.clone();
+        register_fail_point_async("epoch_change_start_entry", move || {
+            crash_target_node(
+                target_fail_node_id,
+                fail_triggered_clone.clone(),
+                Duration::from_secs(20),
+            );
+            async {}
+        });
+
+        // Wait for the crash, and for the node to go through two more epoch changes.
+        tokio::time::sleep(Duration::from_secs(120)).await;
+        assert!(fail_triggered.load(Ordering::SeqCst));
+        clear_fail_point("epoch_change_start_entry");
+
+        assert_node_recovered(&walrus_cluster.nodes[0]).await;
+        workload_handle.abort();
+        blob_info_consistency_check.check_storage_node_consistency();
+    }
+
+    // Tests that a node that is partitioned from all other storage nodes during an epoch change in
+    // which it gains shards recovers its shards once the partition heals. The node keeps its
+    // connection to the Sui cluster, such that it observes the epoch change but cannot sync the
+    // shards from the previous owners.
+    #[ignore = "ignore integration simtests by default"]
+    #[walrus_simtest]
+    async fn test_network_partition_during_epoch_change() {
+        let blob_info_consistency_check = BlobInfoConsistencyCheck::new();
+        let (_sui_cluster, walrus_cluster, workload_handle) =
+            setup_cluster_with_shard_move_to_first_node().await;
+
+        let epoch_change_started = flag_on_epoch_change_start();
+        while !epoch_change_started.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        clear_fail_point("epoch_change_start_entry");
+
+        let partition = simtest_utils::NetworkPartition::isolate_storage_node(
+            &walrus_cluster.nodes[0],
+            &walrus_cluster.nodes,
+        );
+        tokio::time::sleep(Duration::from_secs(45)).await;
+        partition.heal();
+
+        // Wait for the node to go through at least one more epoch change.
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        assert_node_recovered(&walrus_cluster.nodes[0]).await;
+        workload_handle.abort();
+        blob_info_consistency_check.check_storage_node_consistency();
+    }
 }
@@ -23,6 +23,7 @@ use walrus_service::client::{
     ClientError,
     CommitteesRefresherHandle,
     Config,
+    MetadataReplication,
     Refiller,
     StoreWhen,
 };
@@ -100,6 +101,7 @@ impl WriteClient {
                 StoreWhen::Always,
                 BlobPersistence::Permanent,
                 PostStoreAction::Keep,
+                MetadataReplication::WithSlivers,
             )
             .await?
             .first()
@@ -200,6 +202,7 @@ impl WriteClient {
                 &metadata,
                 &pairs,
                 &blob_sui_object.blob_persistence_type(),
+                MetadataReplication::WithSlivers,
                 &MultiProgress::new(),
             )
             .await?;
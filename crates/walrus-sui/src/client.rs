@@ -67,7 +67,10 @@ use crate::{
             EpochState,
             SharedBlob,
             StorageNode,
+            VotingParams,
         },
+        BlobAttributeChanged,
+        BlobCertified,
         BlobEvent,
         Committee,
         ContractEvent,
@@ -89,6 +92,9 @@ pub use read_client::{
     Subsidies,
     SuiReadClient,
 };
+pub mod graphql_client;
+mod quorum_read_client;
+pub use quorum_read_client::QuorumReadClient;
 pub mod retry_client;
 pub mod rpc_config;
 
@@ -117,6 +123,22 @@ pub enum SuiClientError {
     /// Other errors resulting from Sui crates.
     #[error(transparent)]
     SuiError(#[from] sui_types::error::SuiError),
+    /// Error resulting from a query to the Sui GraphQL service.
+    #[error(transparent)]
+    GraphQlError(#[from] graphql_client::GraphQlError),
+    /// The full nodes queried for a cross-checked read returned different values.
+    #[error("full nodes returned diverging values for the {0}")]
+    QuorumDivergence(&'static str),
+    /// Too few full nodes returned a value for a cross-checked read.
+    #[error("only {responses} full nodes returned the {what}, but {quorum} are required")]
+    QuorumNotReached {
+        /// The value that was read.
+        what: &'static str,
+        /// The number of full nodes that returned a value.
+        responses: usize,
+        /// The number of full nodes required to agree.
+        quorum: usize,
+    },
     /// Error in a transaction execution.
     #[error("transaction execution failed: {0}")]
     TransactionExecutionError(MoveExecutionError),
@@ -184,9 +206,28 @@ pub enum SuiClientError {
         FROST for staking"
     )]
     StakeBelowThreshold(u64),
+    /// The object does not exist or was deleted or wrapped.
+    #[error("the object {0} does not exist")]
+    ObjectNotFound(ObjectID),
+    /// The struct is not defined by the Walrus package deployed on the network.
+    #[error(
+        "the struct {0} is not defined by the deployed Walrus package; the package needs to be \
+        upgraded"
+    )]
+    StructNotDeployed(String),
 }
 
 impl SuiClientError {
+    /// Returns true if the error signals that the requested object does not exist, or was deleted
+    /// or wrapped.
+    pub fn is_object_not_found(&self) -> bool {
+        matches!(
+            self,
+            Self::ObjectNotFound(_)
+                | Self::GraphQlError(graphql_client::GraphQlError::ObjectNotFound(_))
+        )
+    }
+
     /// Attempts to parse a shared object congestion error from an error string.
     /// Returns None if the string does not match the expected format.
     pub fn parse_congestion_error(error: &str) -> Result<Self, anyhow::Error> {
@@ -226,6 +267,15 @@ pub struct CertifyAndExtendBlobParams<'a> {
     pub epochs_extended: Option<EpochCount>,
 }
 
+/// The result of certifying and extending blobs in a single transaction.
+#[derive(Debug, Clone, Default)]
+pub struct CertifyAndExtendBlobResult {
+    /// The IDs of the shared blob objects, if the post store action is Share.
+    pub shared_blob_objects: HashMap<BlobId, ObjectID>,
+    /// The IDs of the `BlobCertified` events emitted for the blobs certified in the transaction.
+    pub certification_events: HashMap<BlobId, EventID>,
+}
+
 /// Metadata for a blob object on Sui.
 #[derive(Debug, Clone)]
 pub struct BlobObjectMetadata {
@@ -316,6 +366,18 @@ impl BlobPersistence {
             Self::Permanent
         }
     }
+
+    /// Constructs [`Self`] from the values of the mutually exclusive `deletable` and `permanent`
+    /// flags.
+    ///
+    /// Blobs are permanent unless `deletable` is set. Returns `None` if both flags are set.
+    pub fn from_flags(deletable: bool, permanent: bool) -> Option<Self> {
+        if deletable && permanent {
+            None
+        } else {
+            Some(Self::from_deletable(deletable))
+        }
+    }
 }
 
 /// The action to be performed for newly-created blobs.
@@ -804,6 +866,20 @@ impl SuiContractClient {
             .collect())
     }
 
+    /// Returns the list of [`StakedWal`] objects owned by the wallet currently in use.
+    ///
+    /// If `owner` is `None`, the current wallet address is used.
+    pub async fn owned_staked_wal(
+        &self,
+        owner: Option<SuiAddress>,
+    ) -> SuiClientResult<Vec<StakedWal>> {
+        Ok(self
+            .read_client
+            .get_owned_objects::<StakedWal>(owner.unwrap_or(self.wallet_address), &[])
+            .await?
+            .collect())
+    }
+
     /// Returns the list of [`StorageResource`] objects owned by the wallet currently in use.
     pub async fn owned_storage(
         &self,
@@ -944,6 +1020,25 @@ impl SuiContractClient {
         .await
     }
 
+    /// Extends the owned blob object with the owned storage resource.
+    ///
+    /// The storage resource must have the same size as the storage of the blob and start in the
+    /// epoch in which the blob expires.
+    pub async fn extend_blob_with_resource(
+        &self,
+        blob_obj_id: ObjectID,
+        storage_obj_id: ObjectID,
+    ) -> SuiClientResult<()> {
+        self.retry_on_wrong_version(|| async {
+            self.inner
+                .lock()
+                .await
+                .extend_blob_with_resource(blob_obj_id, storage_obj_id)
+                .await
+        })
+        .await
+    }
+
     /// Updates the parameters for a storage node.
     pub async fn update_node_params(
         &self,
@@ -960,6 +1055,27 @@ impl SuiContractClient {
         .await
     }
 
+    /// Votes for the storage price, write price, and node capacity of the next epoch.
+    ///
+    /// The votes are tallied when the committee for the next epoch is selected; see
+    /// [`SuiReadClient::epoch_params_tally`] for the current state of the vote.
+    pub async fn vote_for_epoch_params(
+        &self,
+        voting_params: &VotingParams,
+        node_capability_object_id: ObjectID,
+    ) -> SuiClientResult<()> {
+        self.update_node_params(
+            NodeUpdateParams {
+                storage_price: Some(voting_params.storage_price),
+                write_price: Some(voting_params.write_price),
+                node_capacity: Some(voting_params.node_capacity),
+                ..Default::default()
+            },
+            node_capability_object_id,
+        )
+        .await
+    }
+
     /// Collects the commission for the pool with id `node_id` and returns the
     /// withdrawn amount in FROST.
     pub async fn collect_commission(&self, node_id: ObjectID) -> SuiClientResult<u64> {
@@ -969,6 +1085,30 @@ impl SuiContractClient {
         .await
     }
 
+    /// Requests the withdrawal of the staked WAL with id `staked_wal_id`.
+    ///
+    /// The stake can be withdrawn with [`Self::withdraw_stake`] once the withdrawal epoch stored
+    /// in the updated [`StakedWal`] object is reached.
+    pub async fn request_withdraw_stake(&self, staked_wal_id: ObjectID) -> SuiClientResult<()> {
+        self.retry_on_wrong_version(|| async {
+            self.inner
+                .lock()
+                .await
+                .request_withdraw_stake(staked_wal_id)
+                .await
+        })
+        .await
+    }
+
+    /// Withdraws the staked WAL with id `staked_wal_id` and returns the withdrawn amount, including
+    /// rewards, in FROST.
+    pub async fn withdraw_stake(&self, staked_wal_id: ObjectID) -> SuiClientResult<u64> {
+        self.retry_on_wrong_version(|| async {
+            self.inner.lock().await.withdraw_stake(staked_wal_id).await
+        })
+        .await
+    }
+
     /// Adds attribute to a blob object.
     ///
     /// If attribute does not exist, it is created with the given key-value pairs.
@@ -1124,13 +1264,14 @@ impl SuiContractClient {
 
     /// Certifies and extends the specified blob on Sui in a single transaction.
     ///
-    /// Returns the shared blob object ID if the post store action is Share.
+    /// Returns the IDs of the certification events of the certified blobs and the shared blob
+    /// object IDs if the post store action is Share.
     /// See [`CertifyAndExtendBlobParams`] for the details of the parameters.
     pub async fn certify_and_extend_blobs(
         &self,
         blobs_with_certificates: &[CertifyAndExtendBlobParams<'_>],
         post_store: PostStoreAction,
-    ) -> SuiClientResult<HashMap<BlobId, ObjectID>> {
+    ) -> SuiClientResult<CertifyAndExtendBlobResult> {
         self.retry_on_wrong_version(|| async {
             self.inner
                 .lock()
@@ -2365,6 +2506,21 @@ impl SuiContractClientInner {
         }
     }
 
+    /// Extends the owned blob object with the owned storage resource.
+    pub async fn extend_blob_with_resource(
+        &mut self,
+        blob_obj_id: ObjectID,
+        storage_obj_id: ObjectID,
+    ) -> SuiClientResult<()> {
+        let mut pt_builder = self.transaction_builder()?;
+        pt_builder
+            .extend_blob_with_resource(blob_obj_id.into(), storage_obj_id.into())
+            .await?;
+        let (ptb, _) = pt_builder.finish().await?;
+        self.sign_and_send_ptb(ptb).await?;
+        Ok(())
+    }
+
     /// Updates the parameters for a storage node.
     pub async fn update_node_params(
         &mut self,
@@ -2395,6 +2551,34 @@ impl SuiContractClientInner {
         pt_builder.collect_commission(node_id).await?;
         let (ptb, _) = pt_builder.finish().await?;
         let response = self.sign_and_send_ptb(ptb).await?;
+        self.wal_received_by_sender(response)
+    }
+
+    /// Requests the withdrawal of the staked WAL with id `staked_wal_id`.
+    pub async fn request_withdraw_stake(&mut self, staked_wal_id: ObjectID) -> SuiClientResult<()> {
+        let mut pt_builder = self.transaction_builder()?;
+        pt_builder
+            .request_withdraw_stake(staked_wal_id.into())
+            .await?;
+        let (ptb, _) = pt_builder.finish().await?;
+        self.sign_and_send_ptb(ptb).await?;
+        Ok(())
+    }
+
+    /// Withdraws the staked WAL with id `staked_wal_id` and returns the withdrawn amount in FROST.
+    pub async fn withdraw_stake(&mut self, staked_wal_id: ObjectID) -> SuiClientResult<u64> {
+        let mut pt_builder = self.transaction_builder()?;
+        pt_builder.withdraw_stake(staked_wal_id).await?;
+        let (ptb, _) = pt_builder.finish().await?;
+        let response = self.sign_and_send_ptb(ptb).await?;
+        self.wal_received_by_sender(response)
+    }
+
+    /// Returns the amount of WAL received by the sender in the transaction.
+    fn wal_received_by_sender(
+        &self,
+        response: SuiTransactionBlockResponse,
+    ) -> SuiClientResult<u64> {
         let wal_type_tag = TypeTag::from_str(self.read_client.wal_coin_type())?;
         let sender_address = self.wallet.active_address()?;
         let Some(balance_change) = response
@@ -2437,12 +2621,13 @@ impl SuiContractClientInner {
     }
 
     /// Certifies and extends the specified blob on Sui in a single transaction.
-    /// Returns the shared blob object ID if the post store action is Share.
+    /// Returns the certification event IDs and the shared blob object IDs if the post store action
+    /// is Share.
     pub async fn certify_and_extend_blobs(
         &mut self,
         blobs_with_certificates: &[CertifyAndExtendBlobParams<'_>],
         post_store: PostStoreAction,
-    ) -> SuiClientResult<HashMap<BlobId, ObjectID>> {
+    ) -> SuiClientResult<CertifyAndExtendBlobResult> {
         let subsidies_package_id = self.read_client.get_subsidies_package_id();
         match subsidies_package_id {
             Some(pkg_id) => {
@@ -2484,7 +2669,7 @@ impl SuiContractClientInner {
         blobs_with_certificates: &[CertifyAndExtendBlobParams<'_>],
         post_store: PostStoreAction,
         subsidies_package_id: ObjectID,
-    ) -> SuiClientResult<HashMap<BlobId, ObjectID>> {
+    ) -> SuiClientResult<CertifyAndExtendBlobResult> {
         self.certify_and_extend_blobs_impl(
             blobs_with_certificates,
             post_store,
@@ -2508,7 +2693,7 @@ impl SuiContractClientInner {
         &mut self,
         blobs_with_certificates: &[CertifyAndExtendBlobParams<'_>],
         post_store: PostStoreAction,
-    ) -> SuiClientResult<HashMap<BlobId, ObjectID>> {
+    ) -> SuiClientResult<CertifyAndExtendBlobResult> {
         self.certify_and_extend_blobs_impl(
             blobs_with_certificates,
             post_store,
@@ -2533,7 +2718,7 @@ impl SuiContractClientInner {
         blobs_with_certificates: &[CertifyAndExtendBlobParams<'_>],
         post_store: PostStoreAction,
         extend_blob_fn: F,
-    ) -> SuiClientResult<HashMap<BlobId, ObjectID>>
+    ) -> SuiClientResult<CertifyAndExtendBlobResult>
     where
         F: for<'a> Fn(
                 &'a mut WalrusPtbBuilder,
@@ -2577,20 +2762,37 @@ impl SuiContractClientInner {
             return Err(anyhow!("could not certify/extend blob: {:?}", res.errors).into());
         }
 
+        let certification_events = res
+            .events
+            .iter()
+            .flat_map(|events| events.data.iter())
+            .filter_map(|event| BlobCertified::try_from(event.clone()).ok())
+            .filter(|certified| !certified.is_extension)
+            .map(|certified| (certified.blob_id, certified.event_id))
+            .collect();
+
         if post_store != PostStoreAction::Share {
-            return Ok(HashMap::new());
+            return Ok(CertifyAndExtendBlobResult {
+                shared_blob_objects: HashMap::new(),
+                certification_events,
+            });
         }
 
         // If the blobs are shared, create a mapping blob ID -> shared_blob_object_id.
-        self.create_blob_id_to_shared_mapping(
-            &res,
-            blobs_with_certificates
-                .iter()
-                .map(|blob_params| blob_params.blob.blob_id)
-                .collect::<Vec<_>>()
-                .as_slice(),
-        )
-        .await
+        let shared_blob_objects = self
+            .create_blob_id_to_shared_mapping(
+                &res,
+                blobs_with_certificates
+                    .iter()
+                    .map(|blob_params| blob_params.blob.blob_id)
+                    .collect::<Vec<_>>()
+                    .as_slice(),
+            )
+            .await?;
+        Ok(CertifyAndExtendBlobResult {
+            shared_blob_objects,
+            certification_events,
+        })
     }
 
     /// Helper function to create a mapping from blob IDs to shared blob object IDs.
@@ -2694,6 +2896,10 @@ impl ReadClient for SuiContractClient {
             .await
     }
 
+    async fn total_and_used_capacity_size(&self) -> SuiClientResult<(u64, u64)> {
+        self.read_client.total_and_used_capacity_size().await
+    }
+
     async fn event_stream(
         &self,
         polling_interval: Duration,
@@ -2704,6 +2910,26 @@ impl ReadClient for SuiContractClient {
             .await
     }
 
+    async fn event_stream_from_checkpoint(
+        &self,
+        polling_interval: Duration,
+        checkpoint: u64,
+    ) -> SuiClientResult<impl Stream<Item = ContractEvent>> {
+        self.read_client
+            .event_stream_from_checkpoint(polling_interval, checkpoint)
+            .await
+    }
+
+    async fn blob_attribute_event_stream(
+        &self,
+        polling_interval: Duration,
+        cursor: Option<EventID>,
+    ) -> SuiClientResult<impl Stream<Item = BlobAttributeChanged>> {
+        self.read_client
+            .blob_attribute_event_stream(polling_interval, cursor)
+            .await
+    }
+
     async fn get_blob_event(&self, event_id: EventID) -> SuiClientResult<BlobEvent> {
         self.read_client.get_blob_event(event_id).await
     }
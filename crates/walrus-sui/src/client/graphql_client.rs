@@ -0,0 +1,544 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal client for the Sui GraphQL RPC service.
+//!
+//! Only the object and event queries needed by the [`ReadClient`][super::ReadClient] are
+//! implemented. Objects and events are returned in the same representation as the JSON-RPC API,
+//! so that the rest of the crate can handle both backends uniformly.
+
+use std::{str::FromStr, time::Duration};
+
+use chrono::DateTime;
+use fastcrypto::encoding::{Base64, Encoding};
+use move_core_types::language_storage::StructTag;
+use reqwest::Url;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use sui_sdk::rpc_types::{BcsEvent, SuiEvent};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    event::EventID,
+    parse_sui_struct_tag,
+    Identifier,
+};
+use thiserror::Error;
+use tracing::Level;
+
+use super::retry_client::RetriableRpcError;
+
+/// The default timeout for requests to the GraphQL service.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// The maximum number of events requested in a single page.
+const EVENT_PAGE_SIZE: usize = 50;
+
+const OBJECT_QUERY: &str = r#"
+query ($address: SuiAddress!) {
+  object(address: $address) {
+    asMoveObject {
+      contents {
+        type { repr }
+        bcs
+      }
+    }
+  }
+}"#;
+
+const EVENTS_QUERY: &str = r#"
+query ($filter: EventFilter!, $after: String, $first: Int) {
+  events(filter: $filter, after: $after, first: $first) {
+    pageInfo { hasNextPage }
+    edges {
+      cursor
+      node {
+        sendingModule { package { address } name }
+        sender { address }
+        timestamp
+        type { repr }
+        bcs
+        json
+        transactionBlock { digest }
+      }
+    }
+  }
+}"#;
+
+/// Error returned by the [`GraphQlClient`].
+#[derive(Debug, Error)]
+pub enum GraphQlError {
+    /// The HTTP request to the GraphQL service failed.
+    #[error("request to the GraphQL service failed: {0}")]
+    RequestFailed(#[from] reqwest::Error),
+    /// The GraphQL service returned one or more errors.
+    #[error("the GraphQL service returned errors: {}", .0.join("; "))]
+    Query(Vec<String>),
+    /// The response could not be interpreted.
+    #[error("invalid response from the GraphQL service: {0}")]
+    InvalidResponse(String),
+    /// The requested object does not exist or is not a Move object.
+    #[error("object {0} not found or not a Move object")]
+    ObjectNotFound(ObjectID),
+}
+
+impl RetriableRpcError for GraphQlError {
+    fn is_retriable_rpc_error(&self) -> bool {
+        match self {
+            GraphQlError::RequestFailed(error) => {
+                error.is_timeout()
+                    || error.is_connect()
+                    || error
+                        .status()
+                        .map(|status| status.is_server_error() || status.as_u16() == 429)
+                        .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The BCS contents and type of a Move object, as returned by the GraphQL service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveObjectContents {
+    /// The type of the Move object.
+    pub type_: StructTag,
+    /// The BCS-encoded contents of the Move object.
+    pub bcs_bytes: Vec<u8>,
+}
+
+/// A page of events returned by the GraphQL service.
+#[derive(Debug, Clone)]
+pub struct GraphQlEventPage {
+    /// The events in the page, each paired with its opaque GraphQL cursor.
+    pub data: Vec<(SuiEvent, String)>,
+    /// Whether there are more events after this page.
+    pub has_next_page: bool,
+}
+
+/// Client for the Sui GraphQL RPC service.
+#[derive(Debug, Clone)]
+pub struct GraphQlClient {
+    url: Url,
+    http_client: reqwest::Client,
+}
+
+impl GraphQlClient {
+    /// Creates a new client for the GraphQL service at the given URL.
+    pub fn new(url: Url) -> Result<Self, GraphQlError> {
+        let http_client = reqwest::Client::builder()
+            .timeout(DEFAULT_REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self { url, http_client })
+    }
+
+    /// Returns the URL of the GraphQL service.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the type and BCS contents of the Move object with the given ID.
+    #[tracing::instrument(level = Level::DEBUG, skip(self))]
+    pub async fn get_move_object(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<MoveObjectContents, GraphQlError> {
+        let response: ObjectResponse = self
+            .query(OBJECT_QUERY, json!({ "address": object_id.to_string() }))
+            .await?;
+        let contents = response
+            .object
+            .and_then(|object| object.as_move_object)
+            .ok_or(GraphQlError::ObjectNotFound(object_id))?
+            .contents;
+        Ok(MoveObjectContents {
+            type_: parse_struct_tag(&contents.type_.repr)?,
+            bcs_bytes: decode_base64(&contents.bcs)?,
+        })
+    }
+
+    /// Returns the next page of events emitted by the given module, starting after the provided
+    /// GraphQL cursor.
+    ///
+    /// The module is identified as `<package>::<module>`.
+    #[tracing::instrument(level = Level::DEBUG, skip(self))]
+    pub async fn query_module_events(
+        &self,
+        emitting_module: &str,
+        after: Option<&str>,
+    ) -> Result<GraphQlEventPage, GraphQlError> {
+        self.query_events(json!({ "emittingModule": emitting_module }), after)
+            .await
+    }
+
+    /// Returns the events emitted by the given module in the transaction with the given digest.
+    #[tracing::instrument(level = Level::DEBUG, skip(self))]
+    pub async fn transaction_events(
+        &self,
+        emitting_module: &str,
+        tx_digest: TransactionDigest,
+    ) -> Result<Vec<(SuiEvent, String)>, GraphQlError> {
+        let filter = json!({
+            "emittingModule": emitting_module,
+            "transactionDigest": tx_digest.to_string(),
+        });
+        let mut events = vec![];
+        let mut after = None;
+        loop {
+            let page = self.query_events(filter.clone(), after.as_deref()).await?;
+            after = page.data.last().map(|(_, cursor)| cursor.clone());
+            events.extend(page.data);
+            if !page.has_next_page || after.is_none() {
+                return Ok(events);
+            }
+        }
+    }
+
+    /// Translates a JSON-RPC event ID into the GraphQL cursor of the same event.
+    ///
+    /// If the exact event is not found, the cursor of the closest preceding event in the same
+    /// transaction is returned instead. This allows resuming from synthetic event IDs, for example
+    /// those pointing past the last event of a transaction.
+    ///
+    /// Returns `None` if the transaction did not emit any such event from the module, in which case
+    /// there is no GraphQL cursor corresponding to the event ID.
+    pub async fn cursor_for_event_id(
+        &self,
+        emitting_module: &str,
+        event_id: &EventID,
+    ) -> Result<Option<String>, GraphQlError> {
+        let events = self
+            .transaction_events(emitting_module, event_id.tx_digest)
+            .await?;
+        Ok(closest_preceding_cursor(
+            events.into_iter().map(|(event, cursor)| (event.id, cursor)),
+            event_id,
+        ))
+    }
+
+    async fn query_events(
+        &self,
+        filter: Value,
+        after: Option<&str>,
+    ) -> Result<GraphQlEventPage, GraphQlError> {
+        let response: EventsResponse = self
+            .query(
+                EVENTS_QUERY,
+                json!({ "filter": filter, "after": after, "first": EVENT_PAGE_SIZE }),
+            )
+            .await?;
+        let data = response
+            .events
+            .edges
+            .into_iter()
+            .map(|edge| Ok((edge.node.into_sui_event(&edge.cursor)?, edge.cursor)))
+            .collect::<Result<_, GraphQlError>>()?;
+        Ok(GraphQlEventPage {
+            data,
+            has_next_page: response.events.page_info.has_next_page,
+        })
+    }
+
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, GraphQlError> {
+        let response: GraphQlResponse<T> = self
+            .http_client
+            .post(self.url.clone())
+            .json(&GraphQlRequest { query, variables })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        response.into_result()
+    }
+}
+
+/// Returns the cursor of the last of the events that does not come after `event_id`.
+fn closest_preceding_cursor(
+    events: impl IntoIterator<Item = (EventID, String)>,
+    event_id: &EventID,
+) -> Option<String> {
+    events
+        .into_iter()
+        .filter(|(id, _)| id.event_seq <= event_id.event_seq)
+        .last()
+        .map(|(_, cursor)| cursor)
+}
+
+#[derive(Debug, Serialize)]
+struct GraphQlRequest<'a> {
+    query: &'a str,
+    variables: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlResponseError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponseError {
+    message: String,
+}
+
+impl<T> GraphQlResponse<T> {
+    fn into_result(self) -> Result<T, GraphQlError> {
+        if !self.errors.is_empty() {
+            return Err(GraphQlError::Query(
+                self.errors.into_iter().map(|error| error.message).collect(),
+            ));
+        }
+        self.data
+            .ok_or_else(|| GraphQlError::InvalidResponse("response contains no data".to_owned()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TypeRepr {
+    repr: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddressField {
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ObjectResponse {
+    object: Option<ObjectNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectNode {
+    as_move_object: Option<MoveObjectNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MoveObjectNode {
+    contents: MoveValueNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct MoveValueNode {
+    #[serde(rename = "type")]
+    type_: TypeRepr,
+    bcs: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventsResponse {
+    events: EventConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventConnection {
+    page_info: PageInfo,
+    edges: Vec<EventEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventEdge {
+    cursor: String,
+    node: EventNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendingModule {
+    package: AddressField,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionBlockField {
+    digest: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventNode {
+    sending_module: SendingModule,
+    sender: Option<AddressField>,
+    timestamp: Option<String>,
+    #[serde(rename = "type")]
+    type_: TypeRepr,
+    bcs: String,
+    #[serde(default)]
+    json: Value,
+    transaction_block: TransactionBlockField,
+}
+
+impl EventNode {
+    fn into_sui_event(self, cursor: &str) -> Result<SuiEvent, GraphQlError> {
+        let id = EventID {
+            tx_digest: TransactionDigest::from_str(&self.transaction_block.digest)
+                .map_err(|error| invalid_response("transaction digest", error))?,
+            event_seq: event_seq_from_cursor(cursor)?,
+        };
+        let sender = self
+            .sender
+            .map(|sender| SuiAddress::from_str(&sender.address))
+            .transpose()
+            .map_err(|error| invalid_response("sender", error))?
+            .unwrap_or(SuiAddress::ZERO);
+        let timestamp_ms = self
+            .timestamp
+            .map(|timestamp| DateTime::parse_from_rfc3339(&timestamp))
+            .transpose()
+            .map_err(|error| invalid_response("timestamp", error))?
+            .map(|timestamp| timestamp.timestamp_millis() as u64);
+
+        Ok(SuiEvent {
+            id,
+            package_id: ObjectID::from_str(&self.sending_module.package.address)
+                .map_err(|error| invalid_response("package ID", error))?,
+            transaction_module: Identifier::new(self.sending_module.name)
+                .map_err(|error| invalid_response("module name", error))?,
+            sender,
+            type_: parse_struct_tag(&self.type_.repr)?,
+            parsed_json: self.json,
+            bcs: BcsEvent::new(decode_base64(&self.bcs)?),
+            timestamp_ms,
+        })
+    }
+}
+
+/// Extracts the sequence number of the event within its transaction from a GraphQL event cursor.
+///
+/// Event cursors are base64-encoded JSON objects, in which the field `e` holds the index of the
+/// event within its transaction.
+fn event_seq_from_cursor(cursor: &str) -> Result<u64, GraphQlError> {
+    let decoded: Value = serde_json::from_slice(&decode_base64(cursor)?)
+        .map_err(|error| invalid_response("event cursor", error))?;
+    decoded
+        .get("e")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| GraphQlError::InvalidResponse(format!("invalid event cursor {cursor}")))
+}
+
+fn parse_struct_tag(repr: &str) -> Result<StructTag, GraphQlError> {
+    parse_sui_struct_tag(repr).map_err(|error| invalid_response("Move type", error))
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, GraphQlError> {
+    Base64::decode(value).map_err(|error| invalid_response("base64 value", error))
+}
+
+fn invalid_response(what: &str, error: impl std::fmt::Display) -> GraphQlError {
+    GraphQlError::InvalidResponse(format!("invalid {what}: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor(value: Value) -> String {
+        Base64::encode(serde_json::to_vec(&value).unwrap())
+    }
+
+    #[test]
+    fn extracts_event_seq_from_cursor() {
+        let cursor = cursor(json!({ "c": 1000, "t": 42, "e": 3 }));
+        assert_eq!(event_seq_from_cursor(&cursor).unwrap(), 3);
+        assert!(event_seq_from_cursor(&Base64::encode(b"{}")).is_err());
+        assert!(event_seq_from_cursor("not base64!").is_err());
+    }
+
+    #[test]
+    fn converts_event_node_to_sui_event() {
+        let package = ObjectID::from_single_byte(0x42);
+        let tx_digest = TransactionDigest::random();
+        let response: GraphQlResponse<EventsResponse> = serde_json::from_value(json!({
+            "data": {
+                "events": {
+                    "pageInfo": { "hasNextPage": false },
+                    "edges": [{
+                        "cursor": cursor(json!({ "c": 1, "t": 2, "e": 5 })),
+                        "node": {
+                            "sendingModule": {
+                                "package": { "address": package.to_string() },
+                                "name": "system",
+                            },
+                            "sender": { "address": SuiAddress::ZERO.to_string() },
+                            "timestamp": "2025-01-01T00:00:00Z",
+                            "type": { "repr": format!("{package}::events::BlobCertified") },
+                            "bcs": Base64::encode([1u8, 2, 3]),
+                            "json": { "epoch": "1" },
+                            "transactionBlock": { "digest": tx_digest.to_string() },
+                        }
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let edge = response
+            .into_result()
+            .unwrap()
+            .events
+            .edges
+            .into_iter()
+            .next()
+            .unwrap();
+        let cursor = edge.cursor.clone();
+        let event = edge.node.into_sui_event(&cursor).unwrap();
+
+        assert_eq!(
+            event.id,
+            EventID {
+                tx_digest,
+                event_seq: 5
+            }
+        );
+        assert_eq!(event.package_id, package);
+        assert_eq!(event.type_.name.as_str(), "BlobCertified");
+        assert_eq!(event.bcs.bytes(), &[1u8, 2, 3]);
+        assert_eq!(event.timestamp_ms, Some(1_735_689_600_000));
+    }
+
+    #[test]
+    fn resolves_closest_preceding_cursor() {
+        let tx_digest = TransactionDigest::random();
+        let event_id = |event_seq| EventID {
+            tx_digest,
+            event_seq,
+        };
+        let events = || [(event_id(1), "a".to_owned()), (event_id(3), "b".to_owned())];
+
+        assert_eq!(
+            closest_preceding_cursor(events(), &event_id(3)).as_deref(),
+            Some("b")
+        );
+        assert_eq!(
+            closest_preceding_cursor(events(), &event_id(2)).as_deref(),
+            Some("a")
+        );
+        assert_eq!(closest_preceding_cursor(events(), &event_id(0)), None);
+        // A transaction without any events from the module has no corresponding cursor.
+        assert_eq!(closest_preceding_cursor([], &event_id(2)), None);
+    }
+
+    #[test]
+    fn surfaces_graphql_errors() {
+        let response: GraphQlResponse<ObjectResponse> = serde_json::from_value(json!({
+            "data": null,
+            "errors": [{ "message": "boom" }],
+        }))
+        .unwrap();
+        assert!(matches!(
+            response.into_result(),
+            Err(GraphQlError::Query(messages)) if messages == ["boom"]
+        ));
+    }
+}
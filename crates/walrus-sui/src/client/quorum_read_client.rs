@@ -0,0 +1,386 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [`ReadClient`] that cross-checks critical reads against multiple independent full nodes.
+//!
+//! Reads of the committees, the epoch state, and the system object are issued to all configured
+//! full nodes and only returned if a quorum of them agrees and none disagrees. This prevents a
+//! single malicious or faulty RPC provider from feeding a false committee to the caller. All other
+//! reads are served by the primary full node.
+
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use anyhow::Result;
+use futures::future;
+use rand::{thread_rng, Rng as _};
+use sui_types::{base_types::ObjectID, event::EventID};
+use tokio_stream::Stream;
+use walrus_core::Epoch;
+use walrus_utils::backoff::{ExponentialBackoff, ExponentialBackoffConfig};
+
+use super::{
+    contract_config::ContractConfig,
+    CommitteesAndState,
+    FixedSystemParameters,
+    ReadClient,
+    SuiClientError,
+    SuiClientResult,
+    SuiReadClient,
+};
+use crate::types::{
+    move_structs::{BlobAttribute, BlobWithAttribute, EpochState, EventBlob},
+    BlobAttributeChanged,
+    BlobEvent,
+    Committee,
+    ContractEvent,
+    StorageNode,
+    SystemObject,
+};
+
+/// Minimum delay before repeating a cross-checked read whose responses did not agree.
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Maximum delay before repeating a cross-checked read whose responses did not agree.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Number of times a cross-checked read is repeated before giving up.
+///
+/// Full nodes may lag behind each other by a few checkpoints, so responses that disagree are
+/// re-queried a few times before the disagreement is reported as a divergence.
+const MAX_RETRIES: u32 = 5;
+
+/// The outcome of comparing the responses of the full nodes for a single read.
+#[derive(Debug, PartialEq, Eq)]
+enum Agreement<T> {
+    /// At least a quorum of full nodes returned the same value, and no full node returned a
+    /// different value.
+    Agreed(T),
+    /// At least two full nodes returned different values.
+    Diverged,
+    /// Fewer than a quorum of full nodes returned a value.
+    Insufficient { responses: usize },
+}
+
+fn check_agreement<T: PartialEq>(
+    responses: Vec<SuiClientResult<T>>,
+    quorum: usize,
+) -> Agreement<T> {
+    let mut values = responses.into_iter().filter_map(|response| {
+        response
+            .inspect_err(|error| {
+                tracing::debug!(?error, "full node failed to respond to cross-checked read")
+            })
+            .ok()
+    });
+    let Some(first) = values.next() else {
+        return Agreement::Insufficient { responses: 0 };
+    };
+    let mut responses = 1;
+    for value in values {
+        if value != first {
+            return Agreement::Diverged;
+        }
+        responses += 1;
+    }
+    if responses >= quorum {
+        Agreement::Agreed(first)
+    } else {
+        Agreement::Insufficient { responses }
+    }
+}
+
+/// A [`ReadClient`] that cross-checks critical reads against multiple full nodes.
+#[derive(Debug, Clone)]
+pub struct QuorumReadClient {
+    clients: Vec<SuiReadClient>,
+    quorum: usize,
+}
+
+impl QuorumReadClient {
+    /// Creates a new client from the given read clients, each of which should be connected to a
+    /// different full node.
+    ///
+    /// The first client is the primary one, which serves all reads that are not cross-checked.
+    /// Cross-checked reads succeed only if at least `quorum` clients return the same value.
+    pub fn new(clients: Vec<SuiReadClient>, quorum: usize) -> SuiClientResult<Self> {
+        if quorum == 0 || quorum > clients.len() {
+            return Err(anyhow::anyhow!(
+                "the quorum must be between 1 and the number of full nodes ({}), but is {quorum}",
+                clients.len()
+            )
+            .into());
+        }
+        Ok(Self { clients, quorum })
+    }
+
+    /// Creates a new client with read clients connected to each of the given RPC addresses.
+    ///
+    /// The first RPC address is used for the primary client.
+    pub async fn new_for_rpcs<S: AsRef<str>>(
+        rpc_addresses: &[S],
+        contract_config: &ContractConfig,
+        backoff_config: ExponentialBackoffConfig,
+        quorum: usize,
+    ) -> SuiClientResult<Self> {
+        let clients = future::try_join_all(rpc_addresses.iter().map(|rpc_address| {
+            SuiReadClient::new_for_rpc(rpc_address, contract_config, backoff_config.clone())
+        }))
+        .await?;
+        Self::new(clients, quorum)
+    }
+
+    /// Returns the primary read client.
+    pub fn primary(&self) -> &SuiReadClient {
+        &self.clients[0]
+    }
+
+    /// Returns the number of full nodes that must agree on a cross-checked read.
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+
+    /// Returns the system object, cross-checked against all full nodes.
+    pub async fn get_system_object(&self) -> SuiClientResult<SystemObject> {
+        self.cross_checked("system object", |client| async move {
+            client.get_system_object().await
+        })
+        .await
+    }
+
+    /// Issues the read to all full nodes and returns the agreed-upon value.
+    #[tracing::instrument(skip(self, read))]
+    async fn cross_checked<T, F, Fut>(&self, what: &'static str, read: F) -> SuiClientResult<T>
+    where
+        T: PartialEq + Send,
+        F: Fn(SuiReadClient) -> Fut + Send + Sync,
+        Fut: Future<Output = SuiClientResult<T>> + Send,
+    {
+        let mut backoff = ExponentialBackoff::new_with_seed(
+            MIN_RETRY_DELAY,
+            MAX_RETRY_DELAY,
+            Some(MAX_RETRIES),
+            thread_rng().gen(),
+        );
+        loop {
+            let responses =
+                future::join_all(self.clients.iter().map(|client| read(client.clone()))).await;
+            let agreement = check_agreement(responses, self.quorum);
+            let error = match agreement {
+                Agreement::Agreed(value) => return Ok(value),
+                Agreement::Diverged => SuiClientError::QuorumDivergence(what),
+                Agreement::Insufficient { responses } => SuiClientError::QuorumNotReached {
+                    what,
+                    responses,
+                    quorum: self.quorum,
+                },
+            };
+            let Some(delay) = backoff.next() else {
+                tracing::error!(%error, "cross-checked read failed");
+                return Err(error);
+            };
+            tracing::warn!(%error, ?delay, "cross-checked read failed, retrying after delay");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+impl ReadClient for QuorumReadClient {
+    async fn storage_price_per_unit_size(&self) -> SuiClientResult<u64> {
+        self.primary().storage_price_per_unit_size().await
+    }
+
+    async fn write_price_per_unit_size(&self) -> SuiClientResult<u64> {
+        self.primary().write_price_per_unit_size().await
+    }
+
+    async fn storage_and_write_price_per_unit_size(&self) -> SuiClientResult<(u64, u64)> {
+        self.primary().storage_and_write_price_per_unit_size().await
+    }
+
+    async fn total_and_used_capacity_size(&self) -> SuiClientResult<(u64, u64)> {
+        self.primary().total_and_used_capacity_size().await
+    }
+
+    async fn event_stream(
+        &self,
+        polling_interval: Duration,
+        cursor: Option<EventID>,
+    ) -> SuiClientResult<impl Stream<Item = ContractEvent>> {
+        self.primary().event_stream(polling_interval, cursor).await
+    }
+
+    async fn event_stream_from_checkpoint(
+        &self,
+        polling_interval: Duration,
+        checkpoint: u64,
+    ) -> SuiClientResult<impl Stream<Item = ContractEvent>> {
+        self.primary()
+            .event_stream_from_checkpoint(polling_interval, checkpoint)
+            .await
+    }
+
+    async fn blob_attribute_event_stream(
+        &self,
+        polling_interval: Duration,
+        cursor: Option<EventID>,
+    ) -> SuiClientResult<impl Stream<Item = BlobAttributeChanged>> {
+        self.primary()
+            .blob_attribute_event_stream(polling_interval, cursor)
+            .await
+    }
+
+    async fn get_blob_event(&self, event_id: EventID) -> SuiClientResult<BlobEvent> {
+        self.primary().get_blob_event(event_id).await
+    }
+
+    async fn current_committee(&self) -> SuiClientResult<Committee> {
+        self.cross_checked("current committee", |client| async move {
+            client.current_committee().await
+        })
+        .await
+    }
+
+    async fn previous_committee(&self) -> SuiClientResult<Committee> {
+        self.cross_checked("previous committee", |client| async move {
+            client.previous_committee().await
+        })
+        .await
+    }
+
+    async fn next_committee(&self) -> SuiClientResult<Option<Committee>> {
+        self.cross_checked("next committee", |client| async move {
+            client.next_committee().await
+        })
+        .await
+    }
+
+    async fn get_storage_nodes_from_active_set(&self) -> Result<Vec<StorageNode>> {
+        self.primary().get_storage_nodes_from_active_set().await
+    }
+
+    async fn get_storage_nodes_from_committee(&self) -> SuiClientResult<Vec<StorageNode>> {
+        self.primary().get_storage_nodes_from_committee().await
+    }
+
+    async fn get_storage_nodes_by_ids(&self, node_ids: &[ObjectID]) -> Result<Vec<StorageNode>> {
+        self.primary().get_storage_nodes_by_ids(node_ids).await
+    }
+
+    async fn get_blob_attribute(
+        &self,
+        blob_obj_id: &ObjectID,
+    ) -> SuiClientResult<Option<BlobAttribute>> {
+        self.primary().get_blob_attribute(blob_obj_id).await
+    }
+
+    async fn get_blob_by_object_id(
+        &self,
+        blob_obj_id: &ObjectID,
+    ) -> SuiClientResult<BlobWithAttribute> {
+        self.primary().get_blob_by_object_id(blob_obj_id).await
+    }
+
+    async fn epoch_state(&self) -> SuiClientResult<EpochState> {
+        self.cross_checked(
+            "epoch state",
+            |client| async move { client.epoch_state().await },
+        )
+        .await
+    }
+
+    async fn current_epoch(&self) -> SuiClientResult<Epoch> {
+        self.cross_checked("current epoch", |client| async move {
+            client.current_epoch().await
+        })
+        .await
+    }
+
+    async fn get_committees_and_state(&self) -> SuiClientResult<CommitteesAndState> {
+        self.cross_checked("committees and epoch state", |client| async move {
+            client.get_committees_and_state().await
+        })
+        .await
+    }
+
+    async fn fixed_system_parameters(&self) -> SuiClientResult<FixedSystemParameters> {
+        self.cross_checked("fixed system parameters", |client| async move {
+            client.fixed_system_parameters().await
+        })
+        .await
+    }
+
+    async fn stake_assignment(&self) -> SuiClientResult<HashMap<ObjectID, u64>> {
+        self.primary().stake_assignment().await
+    }
+
+    async fn last_certified_event_blob(&self) -> SuiClientResult<Option<EventBlob>> {
+        self.primary().last_certified_event_blob().await
+    }
+
+    async fn refresh_package_id(&self) -> SuiClientResult<()> {
+        future::try_join_all(
+            self.clients
+                .iter()
+                .map(|client| client.refresh_package_id()),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn refresh_subsidies_package_id(&self) -> SuiClientResult<()> {
+        future::try_join_all(
+            self.clients
+                .iter()
+                .map(|client| client.refresh_subsidies_package_id()),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn system_object_version(&self) -> SuiClientResult<u64> {
+        self.primary().system_object_version().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure() -> SuiClientResult<u64> {
+        Err(anyhow::anyhow!("unavailable").into())
+    }
+
+    #[test]
+    fn agrees_if_quorum_returns_the_same_value() {
+        assert_eq!(
+            check_agreement(vec![Ok(1), Ok(1), Ok(1)], 3),
+            Agreement::Agreed(1)
+        );
+        assert_eq!(
+            check_agreement(vec![Ok(1), failure(), Ok(1)], 2),
+            Agreement::Agreed(1)
+        );
+    }
+
+    #[test]
+    fn diverges_if_any_values_differ() {
+        assert_eq!(
+            check_agreement(vec![Ok(1), Ok(1), Ok(2)], 2),
+            Agreement::Diverged
+        );
+        assert_eq!(
+            check_agreement(vec![failure(), Ok(2), Ok(1)], 1),
+            Agreement::Diverged
+        );
+    }
+
+    #[test]
+    fn is_insufficient_without_quorum() {
+        assert_eq!(
+            check_agreement(vec![Ok(1), failure(), failure()], 2),
+            Agreement::Insufficient { responses: 1 }
+        );
+        assert_eq!(
+            check_agreement(vec![failure(), failure()], 1),
+            Agreement::Insufficient { responses: 0 }
+        );
+    }
+}
@@ -44,7 +44,8 @@ use walrus_utils::backoff::ExponentialBackoffConfig;
 
 use super::{
     contract_config::ContractConfig,
-    retry_client::{RetriableSuiClient, MULTI_GET_OBJ_LIMIT},
+    graphql_client::GraphQlClient,
+    retry_client::{RetriableRpcError as _, RetriableSuiClient, MULTI_GET_OBJ_LIMIT},
     SuiClientError,
     SuiClientResult,
 };
@@ -66,9 +67,12 @@ use crate::{
             SystemStateInnerV1Enum,
             SystemStateInnerV1Testnet,
         },
+        BlobAttributeChanged,
         BlobEvent,
         Committee,
         ContractEvent,
+        EpochParamsTally,
+        EpochParamsVote,
         StakingObject,
         StorageNode,
         StorageNodeCap,
@@ -92,7 +96,7 @@ pub enum CoinType {
 ///
 /// This struct is only used to pass the information on committees and state. No invariants are
 /// checked here, but possibly enforced by the crators and consumers of the struct.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct CommitteesAndState {
     /// The current committee.
     pub current: Committee,
@@ -118,6 +122,18 @@ pub struct FixedSystemParameters {
     pub epoch_zero_end: DateTime<Utc>,
 }
 
+impl FixedSystemParameters {
+    /// Returns the time at which the given epoch is scheduled to start.
+    ///
+    /// The schedule is derived from the on-chain end of epoch 0 and the epoch duration. An epoch
+    /// may start later than scheduled if the preceding epoch change is delayed, but, unlike
+    /// estimates based on the current epoch state, the result is the same whenever it is computed.
+    /// The genesis epoch is reported to start at the same time as epoch 1.
+    pub fn scheduled_epoch_start(&self, epoch: Epoch) -> DateTime<Utc> {
+        self.epoch_zero_end + self.epoch_duration * epoch.saturating_sub(1)
+    }
+}
+
 /// Trait to read system state information and events from chain.
 pub trait ReadClient: Send + Sync {
     /// Returns the price for one unit of storage per epoch.
@@ -131,6 +147,11 @@ pub trait ReadClient: Send + Sync {
         &self,
     ) -> impl Future<Output = SuiClientResult<(u64, u64)>> + Send;
 
+    /// Returns the total and the used storage capacity of the system in bytes.
+    fn total_and_used_capacity_size(
+        &self,
+    ) -> impl Future<Output = SuiClientResult<(u64, u64)>> + Send;
+
     /// Returns a stream of new blob events.
     ///
     /// The `polling_interval` defines how often the connected full node is polled for events.
@@ -144,6 +165,32 @@ pub trait ReadClient: Send + Sync {
         cursor: Option<EventID>,
     ) -> impl Future<Output = SuiClientResult<impl Stream<Item = ContractEvent> + Send>> + Send;
 
+    /// Returns a stream of blob events, starting with the events emitted in the Sui checkpoint with
+    /// the given sequence number.
+    ///
+    /// This allows replaying events from a point in the chain's history for which no [`EventID`]
+    /// cursor is known, for example because the original cursor has been pruned. As with
+    /// [`Self::event_stream`], the connected full node must still have the events for the
+    /// checkpoint available.
+    fn event_stream_from_checkpoint(
+        &self,
+        polling_interval: Duration,
+        checkpoint: u64,
+    ) -> impl Future<Output = SuiClientResult<impl Stream<Item = ContractEvent> + Send>> + Send;
+
+    /// Returns a stream of the events signaling changes to the attributes of blob objects.
+    ///
+    /// The `polling_interval` and `cursor` have the same meaning as for [`Self::event_stream`].
+    ///
+    /// The event was added in an upgrade of the Walrus package. Returns a
+    /// [`SuiClientError::StructNotDeployed`] error if the package known to the client does not
+    /// define it yet.
+    fn blob_attribute_event_stream(
+        &self,
+        polling_interval: Duration,
+        cursor: Option<EventID>,
+    ) -> impl Future<Output = SuiClientResult<impl Stream<Item = BlobAttributeChanged> + Send>> + Send;
+
     /// Returns the blob event with the given Event ID.
     fn get_blob_event(
         &self,
@@ -180,6 +227,9 @@ pub trait ReadClient: Send + Sync {
     ) -> impl Future<Output = Result<Vec<StorageNode>>> + Send;
 
     /// Returns the metadata associated with a blob object.
+    ///
+    /// Returns `None` if the blob object has no metadata; any other error reading the metadata is
+    /// returned.
     fn get_blob_attribute(
         &self,
         blob_object_id: &ObjectID,
@@ -503,6 +553,46 @@ impl SuiReadClient {
         self.sui_client.get_sui_object(node_id).await
     }
 
+    /// Returns the tally of the votes of the committee members on the parameters of the next
+    /// epoch.
+    ///
+    /// Until the committee of the next epoch is selected, the votes are weighted by the shards
+    /// held in the current committee. Once selected, the votes are weighted by the shards in the
+    /// next committee and the tally includes the parameters selected by the contracts.
+    pub async fn epoch_params_tally(&self) -> SuiClientResult<EpochParamsTally> {
+        let staking = self.get_staking_object().await?.inner;
+        let shard_assignment = staking
+            .next_committee
+            .as_ref()
+            .unwrap_or(&staking.committee);
+        let node_ids: Vec<_> = shard_assignment.iter().map(|(id, _)| *id).collect();
+        let pools = self
+            .sui_client
+            .get_sui_objects::<StakingPool>(&node_ids)
+            .await?;
+
+        let votes = shard_assignment
+            .iter()
+            .zip(pools)
+            .map(|((node_id, shards), pool)| {
+                ensure!(
+                    *node_id == pool.node_info.node_id,
+                    anyhow!("the object id of the staking pool does not match the node id")
+                );
+                Ok(EpochParamsVote {
+                    node_id: *node_id,
+                    name: pool.node_info.name,
+                    weight: u16::try_from(shards.len()).context("too many shards for a node")?,
+                    voting_params: pool.voting_params,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut tally = EpochParamsTally::new(votes, staking.n_shards);
+        tally.selected = staking.next_epoch_params.map(Into::into);
+        Ok(tally)
+    }
+
     fn walrus_package_id(&self) -> RwLockReadGuard<ObjectID> {
         self.walrus_package_id
             .read()
@@ -533,7 +623,7 @@ impl SuiReadClient {
     }
 
     /// Returns the balance of the owner for the given coin type.
-    pub(crate) async fn balance(
+    pub async fn balance(
         &self,
         owner_address: SuiAddress,
         coin_type: CoinType,
@@ -872,6 +962,39 @@ impl SuiReadClient {
     ) -> SuiClientResult<SystemObjectForDeserialization> {
         self.sui_client.get_sui_object(self.system_object_id).await
     }
+
+    /// Returns the module emitting the Walrus events, in the form `<package>::<module>`.
+    fn event_module(&self) -> String {
+        format!("{}::{EVENT_MODULE}", *self.walrus_package_id())
+    }
+
+    /// Returns an event cursor that directly precedes all events emitted in the given checkpoint.
+    ///
+    /// Event queries return the events *after* the cursor, so the cursor is placed after all
+    /// events of the last transaction of the preceding checkpoint. Returns `None` for the genesis
+    /// checkpoint, such that the events are read from the very beginning.
+    async fn event_cursor_before_checkpoint(
+        &self,
+        checkpoint: u64,
+    ) -> SuiClientResult<Option<EventID>> {
+        let Some(previous_checkpoint) = checkpoint.checked_sub(1) else {
+            return Ok(None);
+        };
+        let last_transaction = self
+            .sui_client
+            .get_checkpoint_transactions(previous_checkpoint)
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                SuiClientError::Internal(anyhow::anyhow!(
+                    "checkpoint {previous_checkpoint} contains no transactions"
+                ))
+            })?;
+        Ok(Some(EventID {
+            tx_digest: last_transaction,
+            event_seq: u64::MAX,
+        }))
+    }
 }
 
 enum WhichCommittee {
@@ -901,6 +1024,14 @@ impl ReadClient for SuiReadClient {
         ))
     }
 
+    async fn total_and_used_capacity_size(&self) -> SuiClientResult<(u64, u64)> {
+        let system_object = self.get_system_object().await?;
+        Ok((
+            system_object.total_capacity_size(),
+            system_object.used_capacity_size(),
+        ))
+    }
+
     async fn event_stream(
         &self,
         polling_interval: Duration,
@@ -917,12 +1048,61 @@ impl ReadClient for SuiReadClient {
                 .expect("lock should not be poisoned"),
             module: Identifier::new(EVENT_MODULE)?,
         };
+
+        if let Some(graphql_client) = self.sui_client.graphql_client().cloned() {
+            let emitting_module = self.event_module();
+            tokio::spawn(async move {
+                poll_for_graphql_events(
+                    tx_event,
+                    polling_interval,
+                    graphql_client,
+                    emitting_module,
+                    cursor,
+                    (event_api, event_filter),
+                )
+                .await
+            });
+            return Ok(ReceiverStream::new(rx_event));
+        }
+
         tokio::spawn(async move {
             poll_for_events(tx_event, polling_interval, event_api, event_filter, cursor).await
         });
         Ok(ReceiverStream::new(rx_event))
     }
 
+    async fn blob_attribute_event_stream(
+        &self,
+        polling_interval: Duration,
+        cursor: Option<EventID>,
+    ) -> SuiClientResult<impl Stream<Item = BlobAttributeChanged>> {
+        let event_struct = contracts::blob::BlobAttributeChanged;
+        // The event was added in a package upgrade, so it is defined under the ID of the package
+        // version that introduced it, if the network has been upgraded to that version.
+        let event_type = event_struct
+            .to_move_struct_tag_with_type_map(&self.type_origin_map(), &[])
+            .map_err(|_| SuiClientError::StructNotDeployed(event_struct.to_string()))?;
+        let (tx_event, rx_event) = mpsc::channel::<BlobAttributeChanged>(EVENT_CHANNEL_CAPACITY);
+
+        let event_api = self.sui_client.event_api().clone();
+        let event_filter = EventFilter::MoveEventType(event_type);
+
+        tokio::spawn(async move {
+            poll_for_events(tx_event, polling_interval, event_api, event_filter, cursor).await
+        });
+        Ok(ReceiverStream::new(rx_event))
+    }
+
+    async fn event_stream_from_checkpoint(
+        &self,
+        polling_interval: Duration,
+        checkpoint: u64,
+    ) -> SuiClientResult<impl Stream<Item = ContractEvent>> {
+        let cursor = self.event_cursor_before_checkpoint(checkpoint).await?;
+        tracing::debug!(checkpoint, ?cursor, "replaying events from checkpoint");
+        self.event_stream(polling_interval, cursor).await
+    }
+
     async fn last_certified_event_blob(&self) -> SuiClientResult<Option<EventBlob>> {
         let blob = self
             .get_system_object()
@@ -932,6 +1112,15 @@ impl ReadClient for SuiReadClient {
     }
 
     async fn get_blob_event(&self, event_id: EventID) -> SuiClientResult<BlobEvent> {
+        if let Some(graphql_client) = self.sui_client.graphql_client() {
+            return graphql_client
+                .transaction_events(&self.event_module(), event_id.tx_digest)
+                .await?
+                .into_iter()
+                .find(|(event, _)| event.id == event_id)
+                .and_then(|(event, _)| event.try_into().ok())
+                .ok_or(SuiClientError::NoCorrespondingBlobEvent(event_id));
+        }
         self.sui_client
             .event_api()
             .get_events(event_id.tx_digest)
@@ -998,7 +1187,13 @@ impl ReadClient for SuiReadClient {
             )
             .await
             .map(Some)
-            .or_else(|_| Ok(None))
+            .or_else(|error| {
+                if error.is_object_not_found() {
+                    Ok(None)
+                } else {
+                    Err(error)
+                }
+            })
     }
 
     async fn get_blob_by_object_id(
@@ -1173,36 +1368,7 @@ where
 
                 for event in events.data {
                     last_event = Some(event.id);
-                    let span = tracing::error_span!(
-                        "sui-event",
-                        event_id = ?event.id,
-                        event_type = ?event.type_
-                    );
-
-                    let continue_or_exit = async move {
-                        let event_obj = match event.try_into() {
-                            Ok(event_obj) => event_obj,
-                            Err(_) => {
-                                tracing::error!("could not convert event");
-                                return ControlFlow::Continue(());
-                            }
-                        };
-
-                        match tx_event_ref.send(event_obj).await {
-                            Ok(()) => {
-                                tracing::debug!("received event");
-                                ControlFlow::Continue(())
-                            }
-                            Err(_) => {
-                                tracing::debug!("channel was closed by receiver");
-                                ControlFlow::Break(())
-                            }
-                        }
-                    }
-                    .instrument(span)
-                    .await;
-
-                    if continue_or_exit.is_break() {
+                    if forward_event(tx_event_ref, event).await.is_break() {
                         return Ok(());
                     }
                 }
@@ -1218,10 +1384,8 @@ where
                 // Ideally, we cut off the stream after retrying for a few times and then switch to
                 // a different full node. This logic would need to be handled by a consumer of the
                 // stream. Until that is in place, retry indefinitely.
-                polling_interval = polling_interval
-                    .saturating_mul(2)
-                    .min(MAX_POLLING_INTERVAL)
-                    .max(initial_polling_interval);
+                polling_interval =
+                    next_polling_interval(polling_interval, initial_polling_interval);
                 page_available = false;
                 tracing::warn!(
                     event_cursor = ?last_event,
@@ -1239,3 +1403,146 @@ where
     tracing::debug!("channel was closed by receiver");
     return Ok(());
 }
+
+/// Polls the events emitted by the module through GraphQL.
+///
+/// If the initial event ID has no corresponding GraphQL cursor, e.g., because the transaction did
+/// not emit any Walrus events, this falls back to polling through the JSON-RPC `fallback`.
+#[tracing::instrument(err, skip_all)]
+async fn poll_for_graphql_events<U>(
+    tx_event: mpsc::Sender<U>,
+    initial_polling_interval: Duration,
+    graphql_client: GraphQlClient,
+    emitting_module: String,
+    mut last_event: Option<EventID>,
+    fallback: (EventApi, EventFilter),
+) -> Result<()>
+where
+    U: TryFrom<SuiEvent> + Send + Sync + Debug + 'static,
+{
+    // GraphQL cursors are opaque, so the initial event ID has to be resolved to the corresponding
+    // cursor first.
+    let mut cursor = None;
+    let mut polling_interval = initial_polling_interval;
+    let mut page_available = false;
+    while !tx_event.is_closed() {
+        if !page_available {
+            tokio::time::sleep(polling_interval).await;
+        }
+        if let Some(event_id) = last_event.filter(|_| cursor.is_none()) {
+            match graphql_client
+                .cursor_for_event_id(&emitting_module, &event_id)
+                .await
+            {
+                Ok(Some(resolved)) => cursor = Some(resolved),
+                Ok(None) => {
+                    tracing::warn!(
+                        event_cursor = ?event_id,
+                        "no GraphQL event cursor corresponds to the event ID, falling back to \
+                        JSON-RPC",
+                    );
+                    let (event_api, event_filter) = fallback;
+                    return poll_for_events(
+                        tx_event,
+                        initial_polling_interval,
+                        event_api,
+                        event_filter,
+                        last_event,
+                    )
+                    .await;
+                }
+                Err(error) if error.is_retriable_rpc_error() => {
+                    polling_interval =
+                        next_polling_interval(polling_interval, initial_polling_interval);
+                    tracing::warn!(
+                        event_cursor = ?event_id,
+                        backoff = ?polling_interval,
+                        ?error,
+                        "failed to resolve the GraphQL event cursor, retrying after backoff",
+                    );
+                    continue;
+                }
+                Err(error) => bail!("could not resume the GraphQL event stream: {}", error),
+            }
+        }
+
+        match graphql_client
+            .query_module_events(&emitting_module, cursor.as_deref())
+            .await
+        {
+            Ok(page) => {
+                page_available = page.has_next_page;
+                polling_interval = initial_polling_interval;
+
+                for (event, event_cursor) in page.data {
+                    last_event = Some(event.id);
+                    cursor = Some(event_cursor);
+                    if forward_event(&tx_event, event).await.is_break() {
+                        return Ok(());
+                    }
+                }
+            }
+            Err(error) => {
+                // Unlike the JSON-RPC API, the GraphQL service reports query errors for
+                // well-formed requests only in exceptional situations, so all errors are retried.
+                polling_interval =
+                    next_polling_interval(polling_interval, initial_polling_interval);
+                page_available = false;
+                tracing::warn!(
+                    event_cursor = ?last_event,
+                    backoff = ?polling_interval,
+                    ?error,
+                    "GraphQL error while polling for events, retrying after backoff",
+                );
+            }
+        }
+    }
+    tracing::debug!("channel was closed by receiver");
+    Ok(())
+}
+
+/// Doubles the polling interval up to [`MAX_POLLING_INTERVAL`], unless the initial interval is
+/// larger.
+fn next_polling_interval(current: Duration, initial: Duration) -> Duration {
+    current
+        .saturating_mul(2)
+        .min(MAX_POLLING_INTERVAL)
+        .max(initial)
+}
+
+/// Converts the event and sends it on the channel.
+///
+/// Returns [`ControlFlow::Break`] if the channel was closed by the receiver.
+async fn forward_event<U>(tx_event: &mpsc::Sender<U>, event: SuiEvent) -> ControlFlow<()>
+where
+    U: TryFrom<SuiEvent> + Send + Sync + Debug + 'static,
+{
+    let span = tracing::error_span!(
+        "sui-event",
+        event_id = ?event.id,
+        event_type = ?event.type_
+    );
+
+    async move {
+        let event_obj = match event.try_into() {
+            Ok(event_obj) => event_obj,
+            Err(_) => {
+                tracing::error!("could not convert event");
+                return ControlFlow::Continue(());
+            }
+        };
+
+        match tx_event.send(event_obj).await {
+            Ok(()) => {
+                tracing::debug!("received event");
+                ControlFlow::Continue(())
+            }
+            Err(_) => {
+                tracing::debug!("channel was closed by receiver");
+                ControlFlow::Break(())
+            }
+        }
+    }
+    .instrument(span)
+    .await
+}
@@ -33,6 +33,7 @@ use sui_sdk::{
     error::SuiRpcResult,
     rpc_types::{
         Balance,
+        CheckpointId,
         Coin,
         DryRunTransactionBlockResponse,
         ObjectsPage,
@@ -71,11 +72,16 @@ use url::ParseError;
 use walrus_core::ensure;
 use walrus_utils::backoff::{BackoffStrategy, ExponentialBackoff, ExponentialBackoffConfig};
 
-use super::{rpc_config::RpcFallbackConfig, SuiClientError, SuiClientResult};
+use super::{
+    graphql_client::GraphQlClient,
+    rpc_config::RpcFallbackConfig,
+    SuiClientError,
+    SuiClientResult,
+};
 use crate::{
     contracts::{self, AssociatedContractStruct, TypeOriginMap},
     types::move_structs::{Key, Subsidies, SuiDynamicField, SystemObjectForDeserialization},
-    utils::get_sui_object_from_object_response,
+    utils::{get_sui_object_from_graphql, get_sui_object_from_object_response},
 };
 
 /// The list of HTTP status codes that are retriable.
@@ -132,6 +138,7 @@ impl RetriableRpcError for SuiClientError {
         match self {
             SuiClientError::SuiSdkError(error) => error.is_retriable_rpc_error(),
             SuiClientError::Internal(error) => error.is_retriable_rpc_error(),
+            SuiClientError::GraphQlError(error) => error.is_retriable_rpc_error(),
             _ => false,
         }
     }
@@ -212,6 +219,7 @@ where
 pub struct RetriableSuiClient {
     sui_client: SuiClient,
     backoff_config: ExponentialBackoffConfig,
+    graphql_client: Option<GraphQlClient>,
 }
 
 impl RetriableSuiClient {
@@ -225,9 +233,21 @@ impl RetriableSuiClient {
         RetriableSuiClient {
             sui_client,
             backoff_config,
+            graphql_client: None,
         }
     }
 
+    /// Uses the provided GraphQL client for object and event queries instead of JSON-RPC.
+    pub fn with_graphql_client(mut self, graphql_client: GraphQlClient) -> Self {
+        self.graphql_client = Some(graphql_client);
+        self
+    }
+
+    /// Returns the GraphQL client used for object and event queries, if any.
+    pub fn graphql_client(&self) -> Option<&GraphQlClient> {
+        self.graphql_client.as_ref()
+    }
+
     /// Returns a reference to the inner backoff configuration.
     pub fn backoff_config(&self) -> &ExponentialBackoffConfig {
         &self.backoff_config
@@ -519,12 +539,19 @@ impl RetriableSuiClient {
 
     /// Returns a [`SuiObjectResponse`] based on the provided [`ObjectID`].
     ///
-    /// Calls [`sui_sdk::apis::ReadApi::get_object_with_options`] internally.
+    /// Calls [`sui_sdk::apis::ReadApi::get_object_with_options`] internally, or queries the
+    /// GraphQL service if one is configured.
     #[tracing::instrument(level = Level::DEBUG, skip_all)]
     pub async fn get_sui_object<U>(&self, object_id: ObjectID) -> SuiClientResult<U>
     where
         U: AssociatedContractStruct,
     {
+        if let Some(graphql_client) = &self.graphql_client {
+            return retry_rpc_errors(self.get_strategy(), || async {
+                get_sui_object_from_graphql(graphql_client, object_id).await
+            })
+            .await;
+        }
         retry_rpc_errors(self.get_strategy(), || async {
             get_sui_object_from_object_response(
                 &self
@@ -548,6 +575,72 @@ impl RetriableSuiClient {
         .await
     }
 
+    /// Returns the timestamp of the latest checkpoint, in milliseconds since the Unix epoch.
+    ///
+    /// This corresponds to the time of the on-chain clock as observed by the full node.
+    ///
+    /// Calls [`sui_sdk::apis::ReadApi::get_latest_checkpoint_sequence_number`] and
+    /// [`sui_sdk::apis::ReadApi::get_checkpoint`] internally.
+    pub async fn get_latest_checkpoint_timestamp_ms(&self) -> SuiRpcResult<u64> {
+        retry_rpc_errors(self.get_strategy(), || async {
+            let read_api = self.sui_client.read_api();
+            let sequence_number = read_api.get_latest_checkpoint_sequence_number().await?;
+            let checkpoint = read_api
+                .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+                .await?;
+            Ok(checkpoint.timestamp_ms)
+        })
+        .await
+    }
+
+    /// Returns the sequence number of the latest checkpoint known to the full node.
+    ///
+    /// Calls [`sui_sdk::apis::ReadApi::get_latest_checkpoint_sequence_number`] internally.
+    pub async fn get_latest_checkpoint_sequence_number(&self) -> SuiRpcResult<u64> {
+        retry_rpc_errors(self.get_strategy(), || async {
+            self.sui_client
+                .read_api()
+                .get_latest_checkpoint_sequence_number()
+                .await
+        })
+        .await
+    }
+
+    /// Returns the timestamp of the checkpoint with the given sequence number, in milliseconds
+    /// since the Unix epoch.
+    ///
+    /// Calls [`sui_sdk::apis::ReadApi::get_checkpoint`] internally.
+    pub async fn get_checkpoint_timestamp_ms(&self, sequence_number: u64) -> SuiRpcResult<u64> {
+        retry_rpc_errors(self.get_strategy(), || async {
+            let checkpoint = self
+                .sui_client
+                .read_api()
+                .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+                .await?;
+            Ok(checkpoint.timestamp_ms)
+        })
+        .await
+    }
+
+    /// Returns the digests of the transactions included in the checkpoint with the given sequence
+    /// number, in the order of their execution.
+    ///
+    /// Calls [`sui_sdk::apis::ReadApi::get_checkpoint`] internally.
+    pub async fn get_checkpoint_transactions(
+        &self,
+        sequence_number: u64,
+    ) -> SuiRpcResult<Vec<TransactionDigest>> {
+        retry_rpc_errors(self.get_strategy(), || async {
+            let checkpoint = self
+                .sui_client
+                .read_api()
+                .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+                .await?;
+            Ok(checkpoint.transactions)
+        })
+        .await
+    }
+
     // Other wrapper methods.
 
     #[tracing::instrument(level = Level::DEBUG, skip_all)]
@@ -558,6 +651,14 @@ impl RetriableSuiClient {
     where
         U: AssociatedContractStruct,
     {
+        if self.graphql_client.is_some() {
+            return future::try_join_all(
+                object_ids
+                    .iter()
+                    .map(|object_id| self.get_sui_object(*object_id)),
+            )
+            .await;
+        }
         let mut responses = vec![];
         for obj_id_batch in object_ids.chunks(MULTI_GET_OBJ_LIMIT) {
             responses.extend(
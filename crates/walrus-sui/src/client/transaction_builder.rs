@@ -13,9 +13,9 @@ use std::{
 use fastcrypto::traits::ToFromBytes;
 use sui_sdk::rpc_types::SuiObjectDataOptions;
 use sui_types::{
-    base_types::{ObjectID, ObjectType, SuiAddress},
+    base_types::{ObjectID, ObjectRef, ObjectType, SuiAddress},
     programmable_transaction_builder::ProgrammableTransactionBuilder,
-    transaction::{Argument, Command, ObjectArg, ProgrammableTransaction},
+    transaction::{Argument, Command, ObjectArg, ProgrammableTransaction, TransactionData},
     Identifier,
     SUI_CLOCK_OBJECT_ID,
     SUI_CLOCK_OBJECT_SHARED_VERSION,
@@ -44,7 +44,8 @@ use super::{
 use crate::{
     contracts::{self, FunctionTag},
     types::{
-        move_structs::{Authorized, BlobAttribute, NodeMetadata, WalExchange},
+        move_structs::{Authorized, BlobAttribute, NodeMetadata, StakedWal, WalExchange},
+        Blob,
         NetworkAddress,
         NodeRegistrationParams,
         NodeUpdateParams,
@@ -617,6 +618,26 @@ impl WalrusPtbBuilder {
         Ok(())
     }
 
+    /// Adds a call to extend an owned blob with the storage resource.
+    ///
+    /// The storage resource must have the same size as the storage of the blob and start in the
+    /// epoch in which the blob expires.
+    pub async fn extend_blob_with_resource(
+        &mut self,
+        blob_object: ArgumentOrOwnedObject,
+        storage_resource: ArgumentOrOwnedObject,
+    ) -> SuiClientResult<()> {
+        let storage_resource_arg = self.argument_from_arg_or_obj(storage_resource).await?;
+        let args = vec![
+            self.system_arg(Mutability::Immutable).await?,
+            self.argument_from_arg_or_obj(blob_object).await?,
+            storage_resource_arg,
+        ];
+        self.walrus_move_call(contracts::system::extend_blob_with_resource, args)?;
+        self.mark_arg_as_consumed(&storage_resource_arg);
+        Ok(())
+    }
+
     /// Adds a call to extend an owned blob with subsidies.
     pub async fn extend_blob_with_subsidies(
         &mut self,
@@ -847,6 +868,35 @@ impl WalrusPtbBuilder {
         Ok(result_arg)
     }
 
+    /// Adds a call to `request_withdraw_stake` for the `StakedWal` object to the PTB.
+    pub async fn request_withdraw_stake(
+        &mut self,
+        staked_wal: ArgumentOrOwnedObject,
+    ) -> SuiClientResult<()> {
+        let staked_wal_arg = self.argument_from_arg_or_obj(staked_wal).await?;
+        let args = vec![self.staking_arg(Mutability::Mutable).await?, staked_wal_arg];
+        self.walrus_move_call(contracts::staking::request_withdraw_stake, args)?;
+        Ok(())
+    }
+
+    /// Adds a call to `withdraw_stake` for the `StakedWal` object to the PTB.
+    ///
+    /// The withdrawn WAL is added to the WAL coin of the PTB. As the rewards are only known when
+    /// the transaction is executed, the WAL balance of the PTB is only increased by the principal.
+    pub async fn withdraw_stake(&mut self, staked_wal_id: ObjectID) -> SuiClientResult<()> {
+        let staked_wal: StakedWal = self
+            .read_client
+            .sui_client()
+            .get_sui_object(staked_wal_id)
+            .await?;
+        let staked_wal_arg = self.argument_from_arg_or_obj(staked_wal_id.into()).await?;
+        let args = vec![self.staking_arg(Mutability::Mutable).await?, staked_wal_arg];
+        let result_arg = self.walrus_move_call(contracts::staking::withdraw_stake, args)?;
+        self.mark_arg_as_consumed(&staked_wal_arg);
+        self.add_wal_coin_result_arg(result_arg, staked_wal.principal);
+        Ok(())
+    }
+
     /// Adds a call to `register_candidate` to the PTB.
     pub async fn register_candidate(
         &mut self,
@@ -1464,3 +1514,195 @@ impl WalrusPtbBuilder {
         }
     }
 }
+
+/// An unsigned programmable transaction built by a [`TransactionBuilder`].
+#[derive(Debug, Clone)]
+pub struct UnsignedTransaction {
+    /// The address that has to sign the transaction.
+    pub sender: SuiAddress,
+    /// The programmable transaction.
+    pub programmable_transaction: ProgrammableTransaction,
+    /// The SUI balance used by the transaction in addition to the gas budget.
+    pub sui_cost: u64,
+}
+
+impl UnsignedTransaction {
+    /// Returns the transaction data to be signed by the sender, paying for gas with `gas_payment`.
+    ///
+    /// The gas coins must hold a balance of at least `gas_budget` plus [`Self::sui_cost`].
+    pub fn into_transaction_data(
+        self,
+        gas_payment: Vec<ObjectRef>,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> TransactionData {
+        TransactionData::new_programmable(
+            self.sender,
+            gas_payment,
+            self.programmable_transaction,
+            gas_budget,
+            gas_price,
+        )
+    }
+}
+
+/// Builds the transactions to store and extend blobs without signing or submitting them.
+///
+/// This allows integrators with their own signing and submission infrastructure to reuse the
+/// encoding of the Walrus Move calls. The on-chain state needed to build the transactions, such as
+/// prices and the WAL coins of the sender, is read through the read client. All objects created by
+/// the transactions are transferred to the sender.
+///
+/// If a subsidies package is configured, storage is reserved and extended through it. Unlike the
+/// [`SuiContractClient`][super::SuiContractClient], the builder cannot fall back to calling the
+/// system contract directly when the subsidies package is outdated, as it does not execute the
+/// transactions.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    read_client: Arc<SuiReadClient>,
+    sender: SuiAddress,
+}
+
+impl TransactionBuilder {
+    /// Creates a new [`TransactionBuilder`] for transactions signed by `sender`.
+    pub fn new(read_client: Arc<SuiReadClient>, sender: SuiAddress) -> Self {
+        Self {
+            read_client,
+            sender,
+        }
+    }
+
+    /// Builds a transaction purchasing storage of `encoded_size` bytes for the next
+    /// `epochs_ahead` epochs.
+    pub async fn reserve_space(
+        &self,
+        encoded_size: u64,
+        epochs_ahead: EpochCount,
+    ) -> SuiClientResult<UnsignedTransaction> {
+        let mut pt_builder = self.ptb_builder();
+        self.add_reserve_space(&mut pt_builder, encoded_size, epochs_ahead)
+            .await?;
+        self.finish(pt_builder).await
+    }
+
+    /// Builds a transaction registering the blobs with the provided metadata, each using the
+    /// storage resource with the given object ID.
+    pub async fn register_blobs(
+        &self,
+        blob_metadata_and_storage: Vec<(BlobObjectMetadata, ObjectID)>,
+        persistence: BlobPersistence,
+    ) -> SuiClientResult<UnsignedTransaction> {
+        let mut pt_builder = self.ptb_builder();
+        for (blob_metadata, storage_id) in blob_metadata_and_storage {
+            pt_builder
+                .register_blob(storage_id.into(), blob_metadata, persistence)
+                .await?;
+        }
+        self.finish(pt_builder).await
+    }
+
+    /// Builds a transaction purchasing storage for the next `epochs_ahead` epochs for each of the
+    /// blobs and registering them in one atomic transaction.
+    pub async fn reserve_and_register_blobs(
+        &self,
+        epochs_ahead: EpochCount,
+        blob_metadata_list: Vec<BlobObjectMetadata>,
+        persistence: BlobPersistence,
+    ) -> SuiClientResult<UnsignedTransaction> {
+        let mut pt_builder = self.ptb_builder();
+        for blob_metadata in blob_metadata_list {
+            let storage_arg = self
+                .add_reserve_space(&mut pt_builder, blob_metadata.encoded_size, epochs_ahead)
+                .await?;
+            pt_builder
+                .register_blob(storage_arg.into(), blob_metadata, persistence)
+                .await?;
+        }
+        self.finish(pt_builder).await
+    }
+
+    /// Builds a transaction certifying the blob objects with the given IDs using the provided
+    /// certificates.
+    pub async fn certify_blobs(
+        &self,
+        blobs_with_certificates: &[(ObjectID, ConfirmationCertificate)],
+    ) -> SuiClientResult<UnsignedTransaction> {
+        let mut pt_builder = self.ptb_builder();
+        for (blob_object_id, certificate) in blobs_with_certificates {
+            pt_builder
+                .certify_blob(blob_object_id.into(), certificate)
+                .await?;
+        }
+        self.finish(pt_builder).await
+    }
+
+    /// Builds a transaction extending the storage of the owned blob object by `epochs_extended`
+    /// epochs.
+    pub async fn extend_blob(
+        &self,
+        blob_object_id: ObjectID,
+        epochs_extended: EpochCount,
+    ) -> SuiClientResult<UnsignedTransaction> {
+        let blob: Blob = self
+            .read_client
+            .sui_client()
+            .get_sui_object(blob_object_id)
+            .await?;
+        let mut pt_builder = self.ptb_builder();
+        match self.read_client.get_subsidies_package_id() {
+            Some(subsidies_package_id) => {
+                pt_builder
+                    .extend_blob_with_subsidies(
+                        blob_object_id.into(),
+                        epochs_extended,
+                        blob.storage.storage_size,
+                        subsidies_package_id,
+                    )
+                    .await?
+            }
+            None => {
+                pt_builder
+                    .extend_blob_without_subsidies(
+                        blob_object_id.into(),
+                        epochs_extended,
+                        blob.storage.storage_size,
+                    )
+                    .await?
+            }
+        }
+        self.finish(pt_builder).await
+    }
+
+    fn ptb_builder(&self) -> WalrusPtbBuilder {
+        WalrusPtbBuilder::new(self.read_client.clone(), self.sender)
+    }
+
+    async fn add_reserve_space(
+        &self,
+        pt_builder: &mut WalrusPtbBuilder,
+        encoded_size: u64,
+        epochs_ahead: EpochCount,
+    ) -> SuiClientResult<Argument> {
+        match self.read_client.get_subsidies_package_id() {
+            Some(subsidies_package_id) => {
+                pt_builder
+                    .reserve_space_with_subsidies(encoded_size, epochs_ahead, subsidies_package_id)
+                    .await
+            }
+            None => {
+                pt_builder
+                    .reserve_space_without_subsidies(encoded_size, epochs_ahead)
+                    .await
+            }
+        }
+    }
+
+    async fn finish(&self, pt_builder: WalrusPtbBuilder) -> SuiClientResult<UnsignedTransaction> {
+        let (programmable_transaction, sui_cost) = pt_builder.finish().await?;
+        Ok(UnsignedTransaction {
+            sender: self.sender,
+            programmable_transaction,
+            sui_cost,
+        })
+    }
+}
@@ -67,15 +67,23 @@ pub trait AssociatedContractStruct: DeserializeOwned {
         let raw = raw
             .try_as_move()
             .ok_or(MoveConversionError::NotMoveObject)?;
+        Self::try_from_move_contents(&raw.type_, &raw.bcs_bytes)
+    }
+
+    /// Converts the BCS contents of a Move object with the given type to [`Self`].
+    fn try_from_move_contents(
+        type_: &MoveStructTag,
+        bcs_bytes: &[u8],
+    ) -> Result<Self, MoveConversionError> {
         ensure!(
-            raw.type_.name.as_str() == Self::CONTRACT_STRUCT.name
-                && raw.type_.module.as_str() == Self::CONTRACT_STRUCT.module,
+            type_.name.as_str() == Self::CONTRACT_STRUCT.name
+                && type_.module.as_str() == Self::CONTRACT_STRUCT.module,
             MoveConversionError::TypeMismatch {
                 expected: Self::CONTRACT_STRUCT.to_string(),
-                actual: format!("{}::{}", raw.type_.module.as_str(), raw.type_.name.as_str()),
+                actual: format!("{}::{}", type_.module.as_str(), type_.name.as_str()),
             }
         );
-        Ok(bcs::from_bytes(&raw.bcs_bytes)?)
+        Ok(bcs::from_bytes(bcs_bytes)?)
     }
 }
 
@@ -214,6 +222,7 @@ pub mod system {
     contract_ident!(fn system::delete_blob);
     contract_ident!(fn system::certify_event_blob);
     contract_ident!(fn system::extend_blob);
+    contract_ident!(fn system::extend_blob_with_resource);
 }
 
 /// Module for tags corresponding to the Move module `system_state_inner`.
@@ -237,6 +246,8 @@ pub mod staking {
     contract_ident!(struct staking::Staking);
     contract_ident!(fn staking::register_candidate);
     contract_ident!(fn staking::stake_with_pool);
+    contract_ident!(fn staking::request_withdraw_stake);
+    contract_ident!(fn staking::withdraw_stake);
     contract_ident!(fn staking::voting_end);
     contract_ident!(fn staking::initiate_epoch_change);
     contract_ident!(fn staking::epoch_sync_done);
@@ -318,6 +329,7 @@ pub mod blob {
     use super::*;
 
     contract_ident!(struct blob::Blob);
+    contract_ident!(struct blob::BlobAttributeChanged);
     contract_ident!(fn blob::burn);
     contract_ident!(fn blob::add_metadata);
     contract_ident!(fn blob::take_metadata);
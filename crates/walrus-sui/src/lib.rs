@@ -8,6 +8,7 @@ pub mod utils;
 pub mod client;
 pub mod config;
 pub mod contracts;
+pub mod networks;
 pub mod system_setup;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -0,0 +1,313 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Registry of the contract deployments of known Walrus networks.
+//!
+//! The registry maps network names (e.g., `mainnet` or `testnet`) to the IDs of the Walrus
+//! packages and shared objects deployed on that network. A registry of the well-known networks is
+//! embedded in the binary; additional or overriding deployments can be loaded from a YAML manifest.
+//!
+//! The embedded registry is derived from the client configuration in `setup/client_config.yaml`,
+//! so that the object IDs of the public networks are only maintained in a single place. Devnet is
+//! reset regularly and therefore only comes with its RPC URL; its contract objects must be
+//! provided in a manifest.
+
+use std::{collections::BTreeMap, fmt, path::Path, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::ObjectID;
+use walrus_utils::backoff::ExponentialBackoffConfig;
+
+use crate::{
+    client::{contract_config::ContractConfig, SuiClientResult, SuiReadClient},
+    utils::SuiNetwork,
+};
+
+/// The client configuration of the public Walrus networks, from which the embedded registry is
+/// derived.
+const SETUP_CLIENT_CONFIG: &str = include_str!("../../../setup/client_config.yaml");
+
+/// The well-known Walrus networks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Network {
+    /// The Walrus mainnet.
+    Mainnet,
+    /// The Walrus testnet.
+    Testnet,
+    /// The Walrus devnet.
+    Devnet,
+}
+
+impl Network {
+    /// Returns the name of the network as used in the manifest.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+        }
+    }
+
+    /// Returns the Sui network on which the Walrus network is deployed.
+    pub fn sui_network(&self) -> SuiNetwork {
+        match self {
+            Network::Mainnet => SuiNetwork::Mainnet,
+            Network::Testnet => SuiNetwork::Testnet,
+            Network::Devnet => SuiNetwork::Devnet,
+        }
+    }
+
+    /// Returns all well-known networks.
+    pub fn all() -> [Network; 3] {
+        [Network::Mainnet, Network::Testnet, Network::Devnet]
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            "devnet" => Ok(Network::Devnet),
+            _ => Err(anyhow!("unknown network: {s}")),
+        }
+    }
+}
+
+/// The contract deployment of a single Walrus network.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NetworkDeployment {
+    /// The default Sui full-node RPC URL for the network.
+    pub rpc_url: String,
+    /// The ID of the Walrus package.
+    ///
+    /// If unset, the package ID is resolved from the system object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub walrus_package: Option<ObjectID>,
+    /// Object ID of the Walrus system object.
+    ///
+    /// Unset for networks whose contracts are not pinned, such as devnet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_object: Option<ObjectID>,
+    /// Object ID of the Walrus staking object.
+    ///
+    /// Unset for networks whose contracts are not pinned, such as devnet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub staking_object: Option<ObjectID>,
+    /// Object ID of the Walrus subsidies object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsidies_object: Option<ObjectID>,
+    /// Object IDs of the WAL exchange objects.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exchange_objects: Vec<ObjectID>,
+}
+
+impl NetworkDeployment {
+    /// Returns the [`ContractConfig`] for the deployment.
+    ///
+    /// Returns an error if the system or staking object of the deployment is unset.
+    pub fn contract_config(&self) -> Result<ContractConfig> {
+        let (Some(system_object), Some(staking_object)) = (self.system_object, self.staking_object)
+        else {
+            return Err(anyhow!(
+                "the contract objects of the deployment are not set; provide them in a network \
+                manifest"
+            ));
+        };
+        Ok(ContractConfig::new_with_subsidies(
+            system_object,
+            staking_object,
+            self.subsidies_object,
+        ))
+    }
+}
+
+/// The subset of a client-configuration context in `setup/` that describes a deployment.
+#[derive(Debug, Deserialize)]
+struct SetupContext {
+    system_object: ObjectID,
+    staking_object: ObjectID,
+    #[serde(default)]
+    subsidies_object: Option<ObjectID>,
+    #[serde(default)]
+    exchange_objects: Vec<ObjectID>,
+}
+
+/// The subset of the multi-context client configuration in `setup/` that is used by the registry.
+#[derive(Debug, Deserialize)]
+struct SetupClientConfig {
+    contexts: BTreeMap<String, SetupContext>,
+}
+
+/// A registry of Walrus network deployments, indexed by network name.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct NetworkRegistry {
+    networks: BTreeMap<String, NetworkDeployment>,
+}
+
+impl NetworkRegistry {
+    /// Returns the registry of the well-known networks embedded in the binary.
+    pub fn embedded() -> Self {
+        Self::from_setup_client_config(SETUP_CLIENT_CONFIG)
+            .expect("the embedded client configuration is valid")
+    }
+
+    /// Derives the registry of the well-known networks from a multi-context client configuration.
+    ///
+    /// Every well-known network is included with the RPC URL of its Sui network; the contract
+    /// objects are taken from the context with the same name, if there is one.
+    fn from_setup_client_config(client_config: &str) -> Result<Self> {
+        let mut contexts = serde_yaml::from_str::<SetupClientConfig>(client_config)
+            .context("invalid client configuration")?
+            .contexts;
+        let networks = Network::all()
+            .into_iter()
+            .map(|network| {
+                let context = contexts.remove(network.as_str());
+                let deployment = NetworkDeployment {
+                    rpc_url: network.sui_network().env().rpc,
+                    walrus_package: None,
+                    system_object: context.as_ref().map(|context| context.system_object),
+                    staking_object: context.as_ref().map(|context| context.staking_object),
+                    subsidies_object: context
+                        .as_ref()
+                        .and_then(|context| context.subsidies_object),
+                    exchange_objects: context
+                        .map(|context| context.exchange_objects)
+                        .unwrap_or_default(),
+                };
+                (network.to_string(), deployment)
+            })
+            .collect();
+        Ok(Self { networks })
+    }
+
+    /// Parses a registry from a YAML manifest.
+    pub fn from_yaml(manifest: &str) -> Result<Self> {
+        serde_yaml::from_str(manifest).context("invalid network manifest")
+    }
+
+    /// Loads a registry from the YAML manifest at the given path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let manifest = std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read network manifest {}", path.display()))?;
+        Self::from_yaml(&manifest)
+    }
+
+    /// Returns the embedded registry, extended by the manifest at the given path.
+    ///
+    /// Deployments in the manifest override embedded deployments with the same name.
+    pub fn embedded_with_overrides(path: impl AsRef<Path>) -> Result<Self> {
+        let mut registry = Self::embedded();
+        registry.networks.extend(Self::load(path)?.networks);
+        Ok(registry)
+    }
+
+    /// Returns the deployment of the network with the given name, if it is known.
+    pub fn get(&self, network: &str) -> Option<&NetworkDeployment> {
+        self.networks.get(network)
+    }
+
+    /// Returns the deployment of the network with the given name, or an error if it is unknown.
+    pub fn deployment(&self, network: &str) -> Result<&NetworkDeployment> {
+        self.get(network).ok_or_else(|| {
+            anyhow!(
+                "unknown network '{network}'; known networks: {}",
+                self.network_names().collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+
+    /// Returns the names of all networks in the registry.
+    pub fn network_names(&self) -> impl Iterator<Item = &str> {
+        self.networks.keys().map(String::as_str)
+    }
+
+    /// Constructs a [`SuiReadClient`] for the network with the given name, using the network's
+    /// default RPC URL.
+    pub async fn read_client(
+        &self,
+        network: &str,
+        backoff_config: ExponentialBackoffConfig,
+    ) -> SuiClientResult<SuiReadClient> {
+        let deployment = self.deployment(network)?;
+        SuiReadClient::new_for_rpc(
+            &deployment.rpc_url,
+            &deployment.contract_config()?,
+            backoff_config,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_registry_contains_well_known_networks() {
+        let registry = NetworkRegistry::embedded();
+        for network in [Network::Mainnet, Network::Testnet] {
+            let deployment = registry.deployment(network.as_str()).unwrap();
+            assert!(deployment.subsidies_object.is_some());
+            assert_eq!(
+                Some(deployment.contract_config().unwrap().system_object),
+                deployment.system_object
+            );
+        }
+
+        let devnet = registry.deployment(Network::Devnet.as_str()).unwrap();
+        assert_eq!(devnet.rpc_url, SuiNetwork::Devnet.env().rpc);
+        assert!(devnet.contract_config().is_err());
+    }
+
+    #[test]
+    fn embedded_registry_matches_setup_client_config() {
+        let registry = NetworkRegistry::embedded();
+        let contexts = serde_yaml::from_str::<SetupClientConfig>(SETUP_CLIENT_CONFIG)
+            .unwrap()
+            .contexts;
+        for (name, context) in contexts {
+            let deployment = registry.deployment(&name).unwrap();
+            assert_eq!(deployment.system_object, Some(context.system_object));
+            assert_eq!(deployment.staking_object, Some(context.staking_object));
+            assert_eq!(deployment.subsidies_object, context.subsidies_object);
+            assert_eq!(deployment.exchange_objects, context.exchange_objects);
+        }
+    }
+
+    #[test]
+    fn manifest_overrides_embedded_deployments() {
+        let mut registry = NetworkRegistry::embedded();
+        let overrides = NetworkRegistry::from_yaml(
+            "devnet:\n  rpc_url: http://localhost:9000\n  system_object: 0x1\n  staking_object: 0x2\n",
+        )
+        .unwrap();
+        registry.networks.extend(overrides.networks);
+
+        let devnet = registry.deployment("devnet").unwrap();
+        assert_eq!(devnet.system_object, Some(ObjectID::from_single_byte(1)));
+        assert!(devnet.exchange_objects.is_empty());
+        assert!(registry.deployment("mainnet").is_ok());
+        assert!(registry.deployment("localnet").is_err());
+    }
+
+    #[test]
+    fn parses_network_names() {
+        for network in Network::all() {
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+        assert!("localnet".parse::<Network>().is_err());
+    }
+}
@@ -16,6 +16,7 @@ use thiserror::Error;
 
 mod events;
 pub use events::{
+    BlobAttributeChanged,
     BlobCertified,
     BlobDeleted,
     BlobEvent,
@@ -43,10 +44,17 @@ pub use move_structs::{
     StorageNodeCap,
     StorageResource,
     SystemObject,
+    VotingParams,
 };
 
 pub mod move_errors;
 
+mod certificate;
+pub use certificate::{
+    CertificateSigner,
+    CertificateVerificationError,
+    VerifiedConfirmationCertificate,
+};
 use serde::{Deserialize, Serialize};
 use walrus_core::{
     bft,
@@ -196,6 +204,124 @@ impl NodeRegistrationParams {
     }
 }
 
+/// The vote of a committee member on the parameters of the next epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochParamsVote {
+    /// The ID of the storage node.
+    pub node_id: ObjectID,
+    /// The name of the storage node.
+    pub name: String,
+    /// The weight of the vote, i.e., the number of shards held by the node.
+    pub weight: u16,
+    /// The storage price, capacity, and write price voted for by the node.
+    pub voting_params: VotingParams,
+}
+
+/// The parameters of an epoch, as determined by the votes of the committee members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NextEpochParams {
+    /// The total storage capacity of the system.
+    pub total_capacity_size: u64,
+    /// The price per unit size of storage.
+    pub storage_price_per_unit_size: u64,
+    /// The price per unit size of writes.
+    pub write_price_per_unit_size: u64,
+}
+
+/// The tally of the votes of the committee members on the parameters of the next epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochParamsTally {
+    /// The votes of the committee members.
+    pub votes: Vec<EpochParamsVote>,
+    /// The parameters resulting from the votes, computed in the same way as in the contracts.
+    pub outcome: NextEpochParams,
+    /// The parameters selected by the contracts for the next epoch, if already selected.
+    pub selected: Option<NextEpochParams>,
+}
+
+impl EpochParamsTally {
+    /// Tallies the votes of the members of a committee with `n_shards` shards.
+    ///
+    /// The total capacity is the largest value such that a quorum of shards voted for a capacity
+    /// at least as large; the prices are the smallest values such that a quorum of shards voted
+    /// for a price at most as large. The capacity vote of each node is extrapolated to all shards
+    /// based on the number of shards it holds.
+    pub fn new(votes: Vec<EpochParamsVote>, n_shards: NonZeroU16) -> Self {
+        let n_shards = u64::from(n_shards.get());
+        let weighted_votes = |value: fn(&VotingParams) -> u64| {
+            votes
+                .iter()
+                .map(|vote| (value(&vote.voting_params), u64::from(vote.weight)))
+                .collect::<Vec<_>>()
+        };
+        let capacity_votes = votes
+            .iter()
+            .map(|vote| {
+                let weight = u64::from(vote.weight.max(1));
+                let capacity = u128::from(vote.voting_params.node_capacity) * u128::from(n_shards)
+                    / u128::from(weight);
+                (u64::try_from(capacity).unwrap_or(u64::MAX), weight)
+            })
+            .collect();
+
+        let outcome = NextEpochParams {
+            total_capacity_size: quorum_above(capacity_votes, n_shards),
+            storage_price_per_unit_size: quorum_below(
+                weighted_votes(|params| params.storage_price),
+                n_shards,
+            ),
+            write_price_per_unit_size: quorum_below(
+                weighted_votes(|params| params.write_price),
+                n_shards,
+            ),
+        };
+        Self {
+            votes,
+            outcome,
+            selected: None,
+        }
+    }
+}
+
+/// Returns the largest value such that a quorum of the weight voted for a value at least as large.
+///
+/// Returns zero if the votes do not reach a quorum.
+fn quorum_above(mut votes: Vec<(u64, u64)>, n_shards: u64) -> u64 {
+    votes.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+    let mut sum_weight = 0;
+    for (value, weight) in votes {
+        sum_weight += weight;
+        if is_quorum(sum_weight, n_shards) {
+            return value;
+        }
+    }
+    0
+}
+
+/// Returns the smallest value such that a quorum of the weight voted for a value at most as large.
+///
+/// Returns zero if the votes do not reach a quorum.
+fn quorum_below(mut votes: Vec<(u64, u64)>, n_shards: u64) -> u64 {
+    votes.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+    let mut sum_weight: u64 = votes.iter().map(|(_, weight)| weight).sum();
+    if !is_quorum(sum_weight, n_shards) {
+        return 0;
+    }
+    // Remove the highest votes until doing so breaks the quorum.
+    for (value, weight) in votes {
+        sum_weight -= weight;
+        if !is_quorum(sum_weight, n_shards) {
+            return value;
+        }
+    }
+    0
+}
+
+/// Mirrors the quorum check of the contracts, i.e., whether the weight is at least `2f + 1`.
+fn is_quorum(weight: u64, n_shards: u64) -> bool {
+    3 * weight >= 2 * n_shards + 1
+}
+
 /// Error returned when trying to create a committee with no shards.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum InvalidCommittee {
@@ -262,22 +388,45 @@ impl Committee {
         bft::min_n_correct(self.n_shards).get().into()
     }
 
-    /// Checks if the number is large enough to reach a quorum (`2f + 1`).
+    /// Returns the maximum number of faulty shards `f`, given `n_shards`.
     ///
-    /// `f` is the maximum number of faulty shards, given `n_shards`. See [walrus_core::bft] for
-    /// further details.
+    /// See [walrus_core::bft] for further details.
     #[inline]
-    pub fn is_quorum(&self, num: usize) -> bool {
-        num > 2 * usize::from(bft::max_n_faulty(self.n_shards))
+    pub fn f(&self) -> u16 {
+        bft::max_n_faulty(self.n_shards)
     }
 
-    /// Checks if the number is larger or equal to the validity threshold
+    /// Returns the weight, in shards, required for a quorum.
     ///
-    /// The validity threshold is `f + 1`, where `f` is the maximum number of faulty shards. See
-    /// [walrus_core::bft] for further details.
+    /// This mirrors the quorum check of the contracts, which requires `3 * weight >= 2 * n_shards +
+    /// 1`. The threshold is therefore `n_shards - f`, which is `2f + 1` if `n_shards = 3f + 1`.
     #[inline]
-    pub fn is_above_validity(&self, num: usize) -> bool {
-        num > usize::from(bft::max_n_faulty(self.n_shards))
+    pub fn quorum_threshold(&self) -> u16 {
+        self.n_shards.get() - self.f()
+    }
+
+    /// Returns the weight, in shards, required to reach the validity threshold `f + 1`.
+    ///
+    /// Any set of shards with at least this weight contains at least one correct shard.
+    #[inline]
+    pub fn validity_threshold(&self) -> u16 {
+        self.f() + 1
+    }
+
+    /// Checks if the weight, in shards, is large enough to reach a quorum.
+    ///
+    /// See [`quorum_threshold`][Self::quorum_threshold] for further details.
+    #[inline]
+    pub fn is_quorum(&self, weight: usize) -> bool {
+        weight >= usize::from(self.quorum_threshold())
+    }
+
+    /// Checks if the weight, in shards, is larger or equal to the validity threshold.
+    ///
+    /// See [`validity_threshold`][Self::validity_threshold] for further details.
+    #[inline]
+    pub fn is_above_validity(&self, weight: usize) -> bool {
+        weight >= usize::from(self.validity_threshold())
     }
 
     /// Return the shards handled by the specified storage node, based on its public key.
@@ -322,7 +471,7 @@ impl Committee {
             .collect();
         shards_per_node.sort_unstable();
         let mut total = 0;
-        let threshold = bft::max_n_faulty(self.n_shards()).into();
+        let threshold = self.f().into();
         for (idx, count) in shards_per_node.iter().rev().enumerate() {
             total += count;
             if total > threshold {
@@ -507,3 +656,80 @@ fn compare_node_attributes(
 
     errors
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(
+        weight: u16,
+        storage_price: u64,
+        write_price: u64,
+        node_capacity: u64,
+    ) -> EpochParamsVote {
+        EpochParamsVote {
+            node_id: ObjectID::random(),
+            name: String::new(),
+            weight,
+            voting_params: VotingParams {
+                storage_price,
+                write_price,
+                node_capacity,
+            },
+        }
+    }
+
+    #[test]
+    fn tally_selects_quorum_values() {
+        let n_shards = NonZeroU16::new(10).unwrap();
+        let tally = EpochParamsTally::new(
+            vec![
+                vote(4, 10, 1, 400),
+                vote(3, 20, 2, 600),
+                vote(2, 30, 3, 200),
+                vote(1, 40, 4, 1000),
+            ],
+            n_shards,
+        );
+
+        // A quorum requires a weight of 7. The capacity votes, extrapolated to all shards, are
+        // 1000 (weight 4), 2000 (weight 3), 1000 (weight 2), and 10000 (weight 1).
+        assert_eq!(
+            tally.outcome,
+            NextEpochParams {
+                total_capacity_size: 1000,
+                storage_price_per_unit_size: 20,
+                write_price_per_unit_size: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn committee_quorum_matches_contracts() {
+        for n_shards in 1..=100 {
+            let committee = Committee::new(vec![], 0, NonZeroU16::new(n_shards).unwrap()).unwrap();
+            for weight in 0..=n_shards {
+                assert_eq!(
+                    committee.is_quorum(weight.into()),
+                    is_quorum(weight.into(), n_shards.into()),
+                    "n_shards: {n_shards}, weight: {weight}"
+                );
+            }
+            assert!(committee.is_above_validity(committee.validity_threshold().into()));
+            assert!(!committee.is_above_validity(committee.f().into()));
+        }
+        let committee = Committee::new(vec![], 0, NonZeroU16::new(1000).unwrap()).unwrap();
+        assert_eq!(committee.f(), 333);
+        assert_eq!(committee.quorum_threshold(), 667);
+        assert_eq!(committee.validity_threshold(), 334);
+    }
+
+    #[test]
+    fn tally_without_quorum_is_zero() {
+        let n_shards = NonZeroU16::new(10).unwrap();
+        let tally = EpochParamsTally::new(vec![vote(6, 10, 1, 600)], n_shards);
+
+        assert_eq!(tally.outcome.total_capacity_size, 0);
+        assert_eq!(tally.outcome.storage_price_per_unit_size, 0);
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright (c) Walrus Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of certificates of storage nodes against a committee.
+//!
+//! The verification mirrors the checks of the contracts when certifying a blob, and can be used to
+//! audit a certificate independently of storing or certifying the blob.
+
+use std::collections::HashSet;
+
+use fastcrypto::traits::AggregateAuthenticator as _;
+use serde::Serialize;
+use sui_types::base_types::ObjectID;
+use thiserror::Error;
+use walrus_core::{
+    messages::{BlobPersistenceType, Confirmation, ConfirmationCertificate},
+    BlobId,
+    Epoch,
+    PublicKey,
+};
+
+use super::Committee;
+
+/// Errors returned when a certificate fails verification against a committee.
+#[derive(Debug, Error)]
+pub enum CertificateVerificationError {
+    /// The certified message is not a valid storage confirmation.
+    #[error("the certified message is not a valid storage confirmation: {0}")]
+    InvalidMessage(#[from] bcs::Error),
+    /// The certified message is for a different epoch than the committee.
+    #[error("the certificate is for epoch {certificate}, not for epoch {committee}")]
+    EpochMismatch {
+        /// The epoch of the certified message.
+        certificate: Epoch,
+        /// The epoch of the committee.
+        committee: Epoch,
+    },
+    /// A signer index does not correspond to a member of the committee.
+    #[error("the signer index {0} does not correspond to a member of the committee")]
+    UnknownSigner(u16),
+    /// A signer index is included more than once.
+    #[error("the signer index {0} is included more than once")]
+    DuplicateSigner(u16),
+    /// The signers do not hold enough shards to form a quorum.
+    #[error("the signers hold {weight} shards, but a quorum requires {required} shards")]
+    InsufficientWeight {
+        /// The number of shards held by the signers.
+        weight: u16,
+        /// The number of shards required for a quorum.
+        required: u16,
+    },
+    /// The aggregate signature is not valid for the signers and the message.
+    #[error("the aggregate signature is invalid for the signers and the certified message")]
+    InvalidSignature,
+}
+
+/// A member of the committee that signed a certificate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateSigner {
+    /// The index of the member in the committee.
+    pub index: u16,
+    /// The ID of the storage node.
+    pub node_id: ObjectID,
+    /// The name of the storage node.
+    pub name: String,
+    /// The public key of the storage node.
+    pub public_key: PublicKey,
+    /// The number of shards held by the storage node in the epoch.
+    pub weight: u16,
+}
+
+/// The details of a [`ConfirmationCertificate`] that was verified against a committee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedConfirmationCertificate {
+    /// The epoch in which the blob was confirmed.
+    pub epoch: Epoch,
+    /// The ID of the confirmed blob.
+    pub blob_id: BlobId,
+    /// Whether the confirmed blob is permanent or deletable.
+    pub blob_type: BlobPersistenceType,
+    /// The members of the committee that signed the certificate.
+    pub signers: Vec<CertificateSigner>,
+    /// The total number of shards held by the signers.
+    pub signed_weight: u16,
+    /// The number of shards required for a quorum.
+    pub quorum_threshold: u16,
+    /// The total number of shards in the committee.
+    pub n_shards: u16,
+}
+
+impl Committee {
+    /// Verifies that the certificate confirms the storage of a blob by a quorum of the committee.
+    ///
+    /// The certified message must be a storage confirmation for the epoch of the committee, the
+    /// signers must be distinct members of the committee holding a quorum of the shards, and the
+    /// aggregate signature must be valid for their public keys. On success, returns the confirmed
+    /// blob and the details of the signers.
+    pub fn verify_confirmation_certificate(
+        &self,
+        certificate: &ConfirmationCertificate,
+    ) -> Result<VerifiedConfirmationCertificate, CertificateVerificationError> {
+        let confirmation: Confirmation = bcs::from_bytes(&certificate.serialized_message)?;
+        let message = confirmation.as_ref();
+        if message.epoch() != self.epoch {
+            return Err(CertificateVerificationError::EpochMismatch {
+                certificate: message.epoch(),
+                committee: self.epoch,
+            });
+        }
+
+        let mut seen = HashSet::with_capacity(certificate.signers.len());
+        let mut signers = Vec::with_capacity(certificate.signers.len());
+        for &index in &certificate.signers {
+            if !seen.insert(index) {
+                return Err(CertificateVerificationError::DuplicateSigner(index));
+            }
+            let member = self
+                .members()
+                .get(usize::from(index))
+                .ok_or(CertificateVerificationError::UnknownSigner(index))?;
+            signers.push(CertificateSigner {
+                index,
+                node_id: member.node_id,
+                name: member.name.clone(),
+                public_key: member.public_key.clone(),
+                weight: member
+                    .shard_ids
+                    .len()
+                    .try_into()
+                    .expect("the number of shards fits into a u16"),
+            });
+        }
+
+        let signed_weight = signers.iter().map(|signer| signer.weight).sum();
+        if !self.is_quorum(usize::from(signed_weight)) {
+            return Err(CertificateVerificationError::InsufficientWeight {
+                weight: signed_weight,
+                required: self.quorum_threshold(),
+            });
+        }
+
+        let public_keys: Vec<_> = signers
+            .iter()
+            .map(|signer| signer.public_key.clone())
+            .collect();
+        certificate
+            .signature
+            .verify(&public_keys, &certificate.serialized_message)
+            .map_err(|_| CertificateVerificationError::InvalidSignature)?;
+
+        Ok(VerifiedConfirmationCertificate {
+            epoch: self.epoch,
+            blob_id: message.contents().blob_id,
+            blob_type: message.contents().blob_type,
+            signers,
+            signed_weight,
+            quorum_threshold: self.quorum_threshold(),
+            n_shards: self.n_shards().get(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU16;
+
+    use walrus_core::{keys::ProtocolKeyPair, ShardIndex};
+
+    use super::*;
+    use crate::test_utils;
+
+    const EPOCH: Epoch = 3;
+    const BLOB_ID: BlobId = BlobId([7; 32]);
+
+    /// Returns a committee of four members with 1, 2, 3, and 4 shards, and their key pairs.
+    fn committee_and_keys() -> (Committee, Vec<ProtocolKeyPair>) {
+        let mut next_shard = 0;
+        let (members, keys): (Vec<_>, Vec<_>) = (1..=4)
+            .map(|n_shards| {
+                let key_pair = ProtocolKeyPair::generate();
+                let mut member = test_utils::new_move_storage_node_for_testing();
+                member.public_key = key_pair.public().clone();
+                member.shard_ids = (next_shard..next_shard + n_shards)
+                    .map(ShardIndex)
+                    .collect();
+                next_shard += n_shards;
+                (member, key_pair)
+            })
+            .unzip();
+        let committee = Committee::new(members, EPOCH, NonZeroU16::new(10).unwrap())
+            .expect("the committee is valid");
+        (committee, keys)
+    }
+
+    fn certificate(
+        confirmation: &Confirmation,
+        keys: &[ProtocolKeyPair],
+        signers: Vec<u16>,
+    ) -> ConfirmationCertificate {
+        let signed_messages: Vec<_> = signers
+            .iter()
+            .map(|&index| keys[usize::from(index)].sign_message(confirmation))
+            .collect();
+        ConfirmationCertificate::from_signed_messages_and_indices(signed_messages, signers)
+            .expect("the signed messages are identical")
+    }
+
+    #[test]
+    fn verifies_certificate_signed_by_quorum() {
+        let (committee, keys) = committee_and_keys();
+        let confirmation = Confirmation::new(EPOCH, BLOB_ID, BlobPersistenceType::Permanent);
+
+        let verified = committee
+            .verify_confirmation_certificate(&certificate(&confirmation, &keys, vec![1, 3, 2]))
+            .expect("the signers hold 9 of 10 shards");
+        assert_eq!(verified.blob_id, BLOB_ID);
+        assert_eq!(verified.blob_type, BlobPersistenceType::Permanent);
+        assert_eq!(verified.signed_weight, 9);
+        assert_eq!(verified.quorum_threshold, 7);
+        assert_eq!(
+            verified
+                .signers
+                .iter()
+                .map(|signer| (signer.index, signer.weight))
+                .collect::<Vec<_>>(),
+            [(1, 2), (3, 4), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_certificates() {
+        let (committee, keys) = committee_and_keys();
+        let confirmation = Confirmation::new(EPOCH, BLOB_ID, BlobPersistenceType::Permanent);
+
+        assert!(matches!(
+            committee.verify_confirmation_certificate(&certificate(
+                &confirmation,
+                &keys,
+                vec![0, 1, 2]
+            )),
+            Err(CertificateVerificationError::InsufficientWeight {
+                weight: 6,
+                required: 7
+            })
+        ));
+
+        let mut duplicate = certificate(&confirmation, &keys, vec![3, 2]);
+        duplicate.signers.push(3);
+        assert!(matches!(
+            committee.verify_confirmation_certificate(&duplicate),
+            Err(CertificateVerificationError::DuplicateSigner(3))
+        ));
+
+        let mut unknown = certificate(&confirmation, &keys, vec![3, 2]);
+        unknown.signers.push(4);
+        assert!(matches!(
+            committee.verify_confirmation_certificate(&unknown),
+            Err(CertificateVerificationError::UnknownSigner(4))
+        ));
+
+        // The signature does not include the claimed signer 1.
+        let mut missing_signature = certificate(&confirmation, &keys, vec![3, 2]);
+        missing_signature.signers.push(1);
+        assert!(matches!(
+            committee.verify_confirmation_certificate(&missing_signature),
+            Err(CertificateVerificationError::InvalidSignature)
+        ));
+
+        let previous_epoch = Confirmation::new(EPOCH - 1, BLOB_ID, BlobPersistenceType::Permanent);
+        assert!(matches!(
+            committee.verify_confirmation_certificate(&certificate(
+                &previous_epoch,
+                &keys,
+                vec![1, 2, 3]
+            )),
+            Err(CertificateVerificationError::EpochMismatch {
+                certificate: 2,
+                committee: EPOCH
+            })
+        ));
+    }
+}
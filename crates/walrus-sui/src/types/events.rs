@@ -160,6 +160,39 @@ impl TryFrom<SuiEvent> for BlobDeleted {
     }
 }
 
+/// Sui event that the attributes of a blob object have changed.
+///
+/// In contrast to the other events, this event is emitted by the `blob` module and is therefore not
+/// part of the [`ContractEvent`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobAttributeChanged {
+    /// The blob ID.
+    pub blob_id: BlobId,
+    /// The object id of the related `Blob` object
+    pub object_id: ObjectID,
+    /// The ID of the event.
+    pub event_id: EventID,
+}
+
+impl AssociatedSuiEvent for BlobAttributeChanged {
+    const EVENT_STRUCT: StructTag<'static> = contracts::blob::BlobAttributeChanged;
+}
+
+impl TryFrom<SuiEvent> for BlobAttributeChanged {
+    type Error = MoveConversionError;
+
+    fn try_from(sui_event: SuiEvent) -> Result<Self, Self::Error> {
+        ensure_event_type(&sui_event, &Self::EVENT_STRUCT)?;
+
+        let (blob_id, object_id) = bcs::from_bytes(sui_event.bcs.bytes())?;
+        Ok(Self {
+            blob_id,
+            object_id,
+            event_id: sui_event.id,
+        })
+    }
+}
+
 /// Sui event that a blob ID is invalid.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InvalidBlobId {
@@ -845,6 +878,16 @@ impl ContractEvent {
             ContractEvent::DenyListEvent(event) => event.event_epoch(),
         }
     }
+
+    /// The name of the wrapped event.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContractEvent::BlobEvent(event) => event.name(),
+            ContractEvent::EpochChangeEvent(event) => event.name(),
+            ContractEvent::PackageEvent(event) => event.name(),
+            ContractEvent::DenyListEvent(event) => event.name(),
+        }
+    }
 }
 
 impl TryFrom<SuiEvent> for ContractEvent {
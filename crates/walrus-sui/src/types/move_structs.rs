@@ -29,7 +29,7 @@ use walrus_core::{
     ShardIndex,
 };
 
-use super::NetworkAddress;
+use super::{NetworkAddress, NextEpochParams};
 use crate::contracts::{self, AssociatedContractStruct, StructTag};
 
 /// Sui object for storage resources.
@@ -463,6 +463,13 @@ impl AssociatedContractStruct for StakingPool {
     const CONTRACT_STRUCT: StructTag<'static> = contracts::staking_pool::StakingPool;
 }
 
+impl StakingPool {
+    /// Returns true if the pool is active and accepts new stake.
+    pub fn is_active(&self) -> bool {
+        self.state == PoolState::Active
+    }
+}
+
 /// Holds information about a future epoch, namely how much
 /// storage needs to be reclaimed and the rewards to be distributed.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
@@ -594,6 +601,16 @@ pub(crate) struct EpochParams {
     write_price_per_unit_size: u64,
 }
 
+impl From<EpochParams> for NextEpochParams {
+    fn from(params: EpochParams) -> Self {
+        Self {
+            total_capacity_size: params.total_capacity_size,
+            storage_price_per_unit_size: params.storage_price_per_unit_size,
+            write_price_per_unit_size: params.write_price_per_unit_size,
+        }
+    }
+}
+
 /// The epoch state.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
 pub enum EpochState {
@@ -711,6 +728,22 @@ impl SystemObject {
         }
     }
 
+    /// Returns the total storage capacity of the system in bytes.
+    pub fn total_capacity_size(&self) -> u64 {
+        match &self.inner {
+            SystemStateInnerV1Enum::V1(inner) => inner.total_capacity_size,
+            SystemStateInnerV1Enum::V1Testnet(inner) => inner.total_capacity_size,
+        }
+    }
+
+    /// Returns the storage capacity in bytes that is currently reserved for blobs.
+    pub fn used_capacity_size(&self) -> u64 {
+        match &self.inner {
+            SystemStateInnerV1Enum::V1(inner) => inner.used_capacity_size,
+            SystemStateInnerV1Enum::V1Testnet(inner) => inner.used_capacity_size,
+        }
+    }
+
     /// Returns the storage price per unit size.
     pub fn storage_price_per_unit_size(&self) -> u64 {
         match &self.inner {
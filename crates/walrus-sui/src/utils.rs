@@ -28,6 +28,7 @@ use sui_sdk::{
 use sui_types::{
     base_types::SuiAddress,
     crypto::SignatureScheme,
+    error::SuiObjectResponseError,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
     transaction::TransactionData,
 };
@@ -41,7 +42,7 @@ use walrus_core::{
 };
 
 use crate::{
-    client::{SuiClientResult, SuiContractClient},
+    client::{graphql_client::GraphQlClient, SuiClientError, SuiClientResult, SuiContractClient},
     config::load_wallet_context_from_path,
     contracts::AssociatedContractStruct,
 };
@@ -141,13 +142,33 @@ pub(crate) fn get_sui_object_from_object_response<U>(
 where
     U: AssociatedContractStruct,
 {
-    U::try_from_object_data(object_response.data.as_ref().ok_or_else(|| {
+    let Some(data) = object_response.data.as_ref() else {
+        return Err(match object_response.error {
+            Some(
+                SuiObjectResponseError::NotExists { object_id }
+                | SuiObjectResponseError::Deleted { object_id, .. },
+            ) => SuiClientError::ObjectNotFound(object_id),
+            ref error => anyhow!("response does not contain object data [err={error:?}]").into(),
+        });
+    };
+    U::try_from_object_data(data).map_err(|_e| {
         anyhow!(
-            "response does not contain object data [err={:?}]",
-            object_response.error
+            "could not convert object to expected type {}",
+            U::CONTRACT_STRUCT
         )
-    })?)
-    .map_err(|_e| {
+        .into()
+    })
+}
+
+pub(crate) async fn get_sui_object_from_graphql<U>(
+    graphql_client: &GraphQlClient,
+    object_id: ObjectID,
+) -> SuiClientResult<U>
+where
+    U: AssociatedContractStruct,
+{
+    let contents = graphql_client.get_move_object(object_id).await?;
+    U::try_from_move_contents(&contents.type_, &contents.bcs_bytes).map_err(|_e| {
         anyhow!(
             "could not convert object to expected type {}",
             U::CONTRACT_STRUCT
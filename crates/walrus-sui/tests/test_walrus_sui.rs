@@ -32,9 +32,11 @@ use walrus_core::{
 };
 use walrus_sui::{
     client::{
+        transaction_builder::{TransactionBuilder, UnsignedTransaction},
         BlobObjectMetadata,
         BlobPersistence,
         CoinType,
+        ExpirySelectionPolicy,
         PostStoreAction,
         ReadClient,
         SuiContractClient,
@@ -273,6 +275,95 @@ async fn test_register_certify_blob() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[ignore = "ignore integration tests by default"]
+async fn test_register_certify_blob_with_unsigned_transactions() -> anyhow::Result<()> {
+    _ = tracing_subscriber::fmt::try_init();
+    let encoding_type = EncodingType::RS2;
+
+    let (_sui_cluster_handle, mut walrus_client, _) = initialize_contract_and_wallet().await?;
+    let transaction_builder = TransactionBuilder::new(
+        walrus_client.as_ref().read_client.clone(),
+        walrus_client.as_ref().address(),
+    );
+
+    let encoding_config = EncodingConfig::new(NonZeroU16::new(100).unwrap());
+    let size = 10_000;
+    let root_hash = Node::from([1; 32]);
+    let blob_id = BlobId::from_metadata(root_hash.clone(), encoding_type, size);
+    let blob_metadata = BlobObjectMetadata {
+        blob_id,
+        root_hash,
+        unencoded_size: size,
+        encoded_size: encoding_config
+            .get_for_type(encoding_type)
+            .encoded_blob_length(size)
+            .unwrap(),
+        encoding_type,
+    };
+
+    let transaction = transaction_builder
+        .reserve_and_register_blobs(3, vec![blob_metadata], BlobPersistence::Permanent)
+        .await?;
+    assert_eq!(transaction.sender, walrus_client.as_ref().address());
+    sign_and_execute(walrus_client.as_mut().wallet_mut(), transaction).await?;
+
+    let [blob_obj] = walrus_client
+        .as_ref()
+        .owned_blobs(None, ExpirySelectionPolicy::Valid)
+        .await?
+        .try_into()
+        .expect("expected one blob object");
+    assert_eq!(blob_obj.blob_id, blob_id);
+    assert_eq!(blob_obj.certified_epoch, None);
+
+    let transaction = transaction_builder
+        .certify_blobs(&[(blob_obj.id, get_default_blob_certificate(blob_id, 1))])
+        .await?;
+    sign_and_execute(walrus_client.as_mut().wallet_mut(), transaction).await?;
+
+    let [blob_obj] = walrus_client
+        .as_ref()
+        .owned_blobs(None, ExpirySelectionPolicy::Valid)
+        .await?
+        .try_into()
+        .expect("expected one blob object");
+    assert_eq!(blob_obj.certified_epoch, Some(1));
+
+    Ok(())
+}
+
+/// Signs the transaction with the active address of the wallet and executes it.
+async fn sign_and_execute(
+    wallet: &mut WalletContext,
+    transaction: UnsignedTransaction,
+) -> anyhow::Result<()> {
+    let gas_budget = 1_000_000_000;
+    let gas_payment = wallet
+        .get_client()
+        .await?
+        .coin_read_api()
+        .select_coins(
+            transaction.sender,
+            None,
+            (gas_budget + transaction.sui_cost).into(),
+            vec![],
+        )
+        .await?
+        .iter()
+        .map(|coin| coin.object_ref())
+        .collect();
+    let transaction_data = transaction.into_transaction_data(
+        gas_payment,
+        gas_budget,
+        wallet.get_reference_gas_price().await?,
+    );
+    wallet
+        .execute_transaction_may_fail(wallet.sign_transaction(&transaction_data))
+        .await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore = "ignore integration tests by default"]
 async fn test_invalidate_blob() -> anyhow::Result<()> {